@@ -463,7 +463,7 @@ fn helper_pick(flag) {
     };
 
     // Keep each unit inexpensive so libFuzzer can honor short smoke deadlines.
-    if selector(data, 14) % 2 == 0 {
+    if selector(data, 14).is_multiple_of(2) {
         let base_root = root.join("base");
         if let Some(entry_path) = write_case(&base_root, variant, None) {
             kept |= exercise_incremental(
@@ -1,4 +1,5 @@
 use crate::codegen::backend::state::{RBackend, TypedParallelWrapperPlan};
+use crate::mir::analyze::effects;
 use crate::mir::def::{BinOp, FnIR, Instr, IntrinsicOp, Terminator, ValueKind};
 use crate::typeck::{ShapeTy, TypeTerm};
 use rustc_hash::{FxHashMap, FxHashSet};
@@ -55,6 +56,11 @@ impl RBackend {
         if fn_ir.unsupported_dynamic || fn_ir.opaque_interop {
             return None;
         }
+        // Forked workers do not share the session RNG stream, so a kernel that
+        // draws random numbers would depend on the chunking. Keep it serial.
+        if effects::fn_uses_rng(fn_ir) {
+            return None;
+        }
         if !Self::typed_parallel_returns_slice_like(fn_ir) {
             return None;
         }
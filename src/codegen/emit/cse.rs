@@ -40,6 +40,18 @@ impl RBackend {
         vars
     }
 
+    /// Frame variables an `unsafe r` block may rebind: its analyzed write set,
+    /// or every frame variable when the block could not be analyzed.
+    pub(crate) fn unsafe_r_block_written_vars(
+        access: Option<&UnsafeRAccess>,
+        fn_ir: &FnIR,
+    ) -> FxHashSet<String> {
+        match access {
+            Some(access) => access.written().cloned().collect(),
+            None => Self::function_frame_vars(fn_ir),
+        }
+    }
+
     pub(crate) fn named_written_base(base: usize, values: &[Value]) -> Option<String> {
         if let Some(var) = values[base].origin_var.as_ref() {
             return Some(var.clone());
@@ -84,8 +96,8 @@ impl RBackend {
                                 out.insert(var);
                             }
                         }
-                        Instr::UnsafeRBlock { .. } => {
-                            out.extend(Self::function_frame_vars(fn_ir));
+                        Instr::UnsafeRBlock { access, .. } => {
+                            out.extend(Self::unsafe_r_block_written_vars(access.as_ref(), fn_ir));
                         }
                         Instr::Eval { .. } => {}
                     }
@@ -114,8 +126,8 @@ impl RBackend {
                                 out.insert(var);
                             }
                         }
-                        Instr::UnsafeRBlock { .. } => {
-                            out.extend(Self::function_frame_vars(fn_ir));
+                        Instr::UnsafeRBlock { access, .. } => {
+                            out.extend(Self::unsafe_r_block_written_vars(access.as_ref(), fn_ir));
                         }
                         Instr::Eval { .. } => {}
                     }
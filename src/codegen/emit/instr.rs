@@ -167,7 +167,28 @@ impl RBackend {
         self.invalidate_emitted_cse_temps();
     }
 
-    pub(crate) fn emit_unsafe_r_block(&mut self, code: &str, read_only: bool, span: Span) {
+    /// Drop only the emit-time facts that mention a variable the analyzed
+    /// block rebinds.
+    pub(crate) fn invalidate_unsafe_r_written_vars(
+        &mut self,
+        access: &UnsafeRAccess,
+        values: &[Value],
+    ) {
+        for var in access.written() {
+            self.note_var_write(var);
+            self.invalidate_alias_bindings_depending_on_var(var, values);
+        }
+        self.invalidate_emitted_cse_temps();
+    }
+
+    pub(crate) fn emit_unsafe_r_block(
+        &mut self,
+        code: &str,
+        read_only: bool,
+        access: Option<&UnsafeRAccess>,
+        span: Span,
+        values: &[Value],
+    ) {
         self.emit_mark(span, Some("unsafe-r"));
         self.record_span(span);
         let label = if read_only {
@@ -191,7 +212,10 @@ impl RBackend {
             }
         }
         self.write_stmt(&format!("# {label}-end"));
-        self.clear_unsafe_r_emit_assumptions();
+        match access {
+            Some(access) => self.invalidate_unsafe_r_written_vars(access, values),
+            None => self.clear_unsafe_r_emit_assumptions(),
+        }
     }
 
     pub(crate) fn can_reuse_live_expr_alias(&self, val_id: usize, values: &[Value]) -> bool {
@@ -249,9 +273,10 @@ impl RBackend {
             Instr::UnsafeRBlock {
                 code,
                 read_only,
+                access,
                 span,
             } => {
-                self.emit_unsafe_r_block(code, *read_only, *span);
+                self.emit_unsafe_r_block(code, *read_only, access.as_ref(), *span, values);
             }
             Instr::Assign { .. } => {
                 self.emit_assign_instr(AssignEmit::from_instr(instr, values, params));
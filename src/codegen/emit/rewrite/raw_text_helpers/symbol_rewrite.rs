@@ -39,6 +39,7 @@ pub(crate) fn replace_symbol_occurrences_local(
                 .chars()
                 .next()
                 .is_none_or(|ch| !RBackend::is_symbol_char(ch))
+            && !is_named_arg_label_local(&line[idx + symbol.len()..])
         {
             out.push_str(replacement);
             idx += symbol.len();
@@ -53,6 +54,13 @@ pub(crate) fn replace_symbol_occurrences_local(
     out
 }
 
+/// `name = value` inside a call or `list(...)` labels an argument; the name is
+/// not a reference to the variable.
+fn is_named_arg_label_local(rest: &str) -> bool {
+    let rest = rest.trim_start();
+    rest.starts_with('=') && !rest.starts_with("==")
+}
+
 pub(crate) fn unquoted_sym_refs_local(line: &str) -> Vec<String> {
     raw_expr_idents_local(line)
         .into_iter()
@@ -40,3 +40,28 @@ fn rewrite_slice_bound_aliases_keeps_branch_chain_with_empty_else_blocks() {
         assert!(input.contains(needle), "{input}");
     }
 }
+
+#[test]
+fn rewrite_slice_bound_aliases_reaches_every_branch_past_source_marks() {
+    let mut input = [
+        "Sym_83 <- function(dir, size) ",
+        "{",
+        "  start <- rr_idx_cube_vec_i(f, x, 1.0, size)",
+        "  end <- rr_idx_cube_vec_i(f, x, size, size)",
+        "  rr_mark(53L, 17L);",
+        "  if (licm_28) {",
+        "    neighbors[start:end] <- Sym_60(f, x, ys, size)",
+        "  }",
+        "  rr_mark(56L, 17L);",
+        "  if (licm_35) {",
+        "    neighbors[start:end] <- Sym_64(f, x, ys, size)",
+        "  }",
+        "  return(neighbors)",
+        "}",
+        "",
+    ]
+    .join("\n");
+    rewrite_slice_bound_aliases(&mut input);
+    assert!(!input.contains("start:end"), "{input}");
+    assert!(!input.contains("start <- rr_idx_cube_vec_i"), "{input}");
+}
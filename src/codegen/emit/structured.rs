@@ -221,8 +221,9 @@ impl RBackend {
                                 loop_mutated_vars.insert(var);
                             }
                         }
-                        Instr::UnsafeRBlock { .. } => {
-                            loop_mutated_vars.extend(Self::function_frame_vars(fn_ir));
+                        Instr::UnsafeRBlock { access, .. } => {
+                            loop_mutated_vars
+                                .extend(Self::unsafe_r_block_written_vars(access.as_ref(), fn_ir));
                         }
                         Instr::Eval { .. } => {}
                     }
@@ -706,7 +706,10 @@ impl RBackend {
                             &mut FxHashSet::default(),
                         )
                     }
-                    Instr::UnsafeRBlock { .. } => true,
+                    Instr::UnsafeRBlock { access, .. } => access.as_ref().is_none_or(|access| {
+                        access.frame_reads().any(|name| name == var)
+                            || access.written().any(|name| name == var)
+                    }),
                 }) || match block.term {
                     Terminator::If { cond, .. } => {
                         self.value_mentions_var(cond, &fn_ir.values, var, &mut FxHashSet::default())
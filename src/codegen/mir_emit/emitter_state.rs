@@ -9,8 +9,8 @@ use crate::codegen::backend::state::{
     ValueBindingUndo, VarValueBindingUndo, VarVersionUndo,
 };
 use crate::mir::def::{
-    BinOp, BlockId, FnIR, Instr, IntrinsicOp, Lit, Terminator, UnaryOp, Value, ValueKind,
-    value_dependencies,
+    BinOp, BlockId, FnIR, Instr, IntrinsicOp, Lit, Terminator, UnaryOp, UnsafeRAccess, Value,
+    ValueKind, value_dependencies,
 };
 use crate::mir::flow::Facts;
 use crate::mir::opt::poly::is_generated_poly_loop_var_name;
@@ -0,0 +1,505 @@
+//! Source-level coverage for `RR run --coverage`.
+//!
+//! Coverage builds put a counting probe before every statement that starts
+//! its own line and at the top of every braced `then` block, then compile at
+//! `-O0`. Unsafe R blocks keep their function opaque to the optimizer, so no
+//! measured statement is folded away. Counters live in the runtime `.rr_env`
+//! and are written out when R exits; the `else` side of each `if` is derived
+//! as evaluations minus `then` hits, which also covers `else if` chains and
+//! `if`s without an `else`.
+//!
+//! The entry is instrumented up front. Project modules it imports are
+//! instrumented as source analysis reads them, inside
+//! [`collect_module_coverage`]; their probe ids carry an `M<k>:` prefix.
+//! Runtime locations only name entry lines, so only the entry's probe lines
+//! are mapped back out of `rr_mark` calls and the source map.
+
+use crate::codegen::mir_emit::MapEntry;
+use crate::compiler::instrument::{insert_probes, is_braced, probe, visit_statements};
+use crate::compiler::pipeline::synthesize_unoptimized_mir;
+use crate::error::RR;
+use crate::runtime::R_COVERAGE_HOOK;
+use crate::syntax::ast::StmtKind;
+use crate::syntax::parse::Parser;
+use regex::Regex;
+use rustc_hash::FxHashMap;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+/// R function the instrumented program calls for each probe.
+pub const COVERAGE_PROBE_NAME: &str = "rr_cov_hit";
+
+#[derive(Clone, Debug)]
+struct BranchSite {
+    line: u32,
+    /// Whether the `if` has its own line probe counting its evaluations.
+    counted: bool,
+    /// For `else if`, the branch site whose `else` side evaluates this one.
+    parent: Option<usize>,
+}
+
+/// What the probes of one instrumented file measure.
+#[derive(Clone, Debug)]
+struct FileProbes {
+    path: PathBuf,
+    /// Prepended to every probe id of the file; empty for the entry.
+    prefix: String,
+    line_map: Vec<u32>,
+    lines: Vec<u32>,
+    branches: Vec<BranchSite>,
+}
+
+/// An instrumented entry file, the project modules instrumented while it
+/// compiled, and what their probes measure.
+#[derive(Clone, Debug)]
+pub struct CoverageBuild {
+    /// RR text to compile in place of the original entry.
+    pub source: String,
+    entry: FileProbes,
+    /// Only modules under this directory, outside its `vendor/`, are measured.
+    module_root: Option<PathBuf>,
+    modules: Vec<FileProbes>,
+}
+
+/// Hit counts of one `if`: `None` when it was never evaluated.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BranchCoverage {
+    pub line: u32,
+    pub taken: Option<[u64; 2]>,
+}
+
+/// Coverage of one RR source file.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FileCoverage {
+    pub path: PathBuf,
+    /// `(line, hits)` for every measured line, in line order.
+    pub lines: Vec<(u32, u64)>,
+    pub branches: Vec<BranchCoverage>,
+}
+
+/// Coverage of the entry followed by each measured module, in path order.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CoverageReport {
+    pub files: Vec<FileCoverage>,
+}
+
+/// Instrument `source` for coverage. Errors are reported against the
+/// original text, before any probe is inserted.
+pub fn instrument_for_coverage(entry_path: &str, source: &str) -> RR<CoverageBuild> {
+    synthesize_unoptimized_mir(entry_path, source)?;
+    let path = std::fs::canonicalize(entry_path).unwrap_or_else(|_| PathBuf::from(entry_path));
+    let (source, entry) = instrument_file(path, String::new(), source)?;
+    Ok(CoverageBuild {
+        source,
+        entry,
+        module_root: None,
+        modules: Vec::new(),
+    })
+}
+
+fn instrument_file(path: PathBuf, prefix: String, source: &str) -> RR<(String, FileProbes)> {
+    let program = Parser::new(source).parse_program()?;
+    let mut probes = Vec::new();
+    let mut lines = Vec::new();
+    let mut branches: Vec<BranchSite> = Vec::new();
+    let mut else_ifs: FxHashMap<usize, usize> = FxHashMap::default();
+    visit_statements(source, &program.stmts, &mut |stmt, own_line| {
+        let line = stmt.span.start_line;
+        if own_line {
+            lines.push(line);
+            probes.push(probe(
+                source,
+                stmt.span.start_byte,
+                &format!("{}(\"{}L{}\")", COVERAGE_PROBE_NAME, prefix, line),
+            ));
+        }
+        let StmtKind::If {
+            then_blk, else_blk, ..
+        } = &stmt.kind
+        else {
+            return;
+        };
+        if !is_braced(source, then_blk) {
+            return;
+        }
+        let id = branches.len();
+        branches.push(BranchSite {
+            line,
+            counted: own_line,
+            parent: else_ifs.get(&stmt.span.start_byte).copied(),
+        });
+        probes.push(probe(
+            source,
+            then_blk.span.start_byte + 1,
+            &format!("{}(\"{}B{}\")", COVERAGE_PROBE_NAME, prefix, id),
+        ));
+        if let Some(else_blk) = else_blk
+            && !is_braced(source, else_blk)
+            && let [nested] = else_blk.stmts.as_slice()
+            && matches!(nested.kind, StmtKind::If { .. })
+        {
+            else_ifs.insert(nested.span.start_byte, id);
+        }
+    });
+    lines.sort_unstable();
+    lines.dedup();
+    let (source, line_map) = insert_probes(source, probes);
+    Ok((
+        source,
+        FileProbes {
+            path,
+            prefix,
+            line_map,
+            lines,
+            branches,
+        },
+    ))
+}
+
+thread_local! {
+    static ACTIVE_BUILD: RefCell<Option<CoverageBuild>> = const { RefCell::new(None) };
+}
+
+/// Run `f` (the compile of `build.source`) while instrumenting the project
+/// modules it imports, recording their probes in `build`.
+pub fn collect_module_coverage<T>(build: &mut CoverageBuild, f: impl FnOnce() -> T) -> T {
+    build.modules.clear();
+    let prev = ACTIVE_BUILD.with(|slot| slot.replace(Some(build.clone())));
+    let out = f();
+    if let Some(active) = ACTIVE_BUILD.with(|slot| slot.replace(prev)) {
+        build.module_root = active.module_root;
+        build.modules = active.modules;
+    }
+    out
+}
+
+/// Whether imported modules are being instrumented, so they must be read
+/// from source rather than from cached artifacts.
+pub(crate) fn instrumenting_modules() -> bool {
+    ACTIVE_BUILD.with(|slot| slot.borrow().is_some())
+}
+
+/// Project root of the current coverage compile.
+pub(crate) fn set_module_root(root: PathBuf) {
+    ACTIVE_BUILD.with(|slot| {
+        if let Some(build) = slot.borrow_mut().as_mut() {
+            build.module_root = Some(root);
+        }
+    });
+}
+
+/// Text to compile for the imported module at `path`: instrumented when a
+/// coverage compile is active and the module belongs to the project,
+/// otherwise `content` unchanged.
+pub(crate) fn instrument_module(path: &Path, content: String) -> String {
+    ACTIVE_BUILD.with(|slot| {
+        let mut slot = slot.borrow_mut();
+        let Some(build) = slot.as_mut() else {
+            return content;
+        };
+        let in_project = build
+            .module_root
+            .as_deref()
+            .is_some_and(|root| path.starts_with(root) && !path.starts_with(root.join("vendor")));
+        if !in_project || path == build.entry.path {
+            return content;
+        }
+        let prefix = format!("M{}:", build.modules.len() + 1);
+        match instrument_file(path.to_path_buf(), prefix, &content) {
+            Ok((source, probes)) => {
+                build.modules.push(probes);
+                source
+            }
+            // Left as is, the module reports its own parse error.
+            Err(_) => content,
+        }
+    })
+}
+
+impl CoverageBuild {
+    /// Directory report paths are relative to: the project root seen while
+    /// compiling, else the entry's directory.
+    pub fn source_root(&self) -> &Path {
+        self.module_root
+            .as_deref()
+            .or_else(|| self.entry.path.parent())
+            .unwrap_or(Path::new("."))
+    }
+
+    /// Line of the original entry that line `line` of [`Self::source`] came from.
+    pub fn original_line(&self, line: u32) -> u32 {
+        line.checked_sub(1)
+            .and_then(|idx| self.entry.line_map.get(idx as usize))
+            .copied()
+            .unwrap_or(line)
+    }
+
+    /// Prepare compiled R for running: point `rr_mark` locations and the
+    /// source map back at the original entry, and prepend the counters,
+    /// which write `<probe> <hits>` lines to `counts_path` when R exits.
+    pub fn prepare_run(
+        &self,
+        r_code: &str,
+        source_map: &mut [MapEntry],
+        counts_path: &str,
+    ) -> String {
+        let prelude = format!(
+            ".rr_cov_out <- \"{}\"\n{}\n",
+            counts_path.replace('\\', "\\\\").replace('"', "\\\""),
+            R_COVERAGE_HOOK.trim_end()
+        );
+        let shift = prelude.matches('\n').count() as u32;
+        for entry in source_map.iter_mut() {
+            entry.r_line += shift;
+            entry.rr_span.start_line = self.original_line(entry.rr_span.start_line);
+            entry.rr_span.end_line = self.original_line(entry.rr_span.end_line);
+        }
+        let remapped = match Regex::new(r"rr_mark\((\d+)L, ") {
+            Ok(mark) => mark
+                .replace_all(r_code, |cap: &regex::Captures<'_>| {
+                    let line = cap[1].parse().unwrap_or(0);
+                    format!("rr_mark({}L, ", self.original_line(line))
+                })
+                .into_owned(),
+            Err(_) => r_code.to_string(),
+        };
+        prelude + &remapped
+    }
+
+    /// Turn the counts file written by a coverage run into per-line and
+    /// per-branch hits for every instrumented file. Probes that never ran are
+    /// absent from `counts`.
+    pub fn report(&self, counts: &str) -> CoverageReport {
+        let hits: FxHashMap<&str, u64> = counts
+            .lines()
+            .filter_map(|line| {
+                let (probe, count) = line.split_once(' ')?;
+                Some((probe, count.trim().parse().ok()?))
+            })
+            .collect();
+        let mut modules: Vec<&FileProbes> = self.modules.iter().collect();
+        modules.sort_by(|a, b| a.path.cmp(&b.path));
+        let files = std::iter::once(&self.entry)
+            .chain(modules)
+            .map(|file| file.report(&hits))
+            .collect();
+        CoverageReport { files }
+    }
+}
+
+impl FileProbes {
+    fn report(&self, hits: &FxHashMap<&str, u64>) -> FileCoverage {
+        let hits_of = |probe: String| {
+            hits.get(format!("{}{}", self.prefix, probe).as_str())
+                .copied()
+                .unwrap_or(0)
+        };
+        let lines = self
+            .lines
+            .iter()
+            .map(|&line| (line, hits_of(format!("L{}", line))))
+            .collect();
+        let mut else_taken: Vec<Option<u64>> = Vec::with_capacity(self.branches.len());
+        let mut branches = Vec::new();
+        for (id, site) in self.branches.iter().enumerate() {
+            let evaluated = if site.counted {
+                Some(hits_of(format!("L{}", site.line)))
+            } else {
+                site.parent.and_then(|parent| else_taken[parent])
+            };
+            let then_taken = hits_of(format!("B{}", id));
+            let otherwise = evaluated.map(|n| n.saturating_sub(then_taken));
+            else_taken.push(otherwise);
+            if let (Some(evaluated), Some(otherwise)) = (evaluated, otherwise) {
+                branches.push(BranchCoverage {
+                    line: site.line,
+                    taken: (evaluated > 0).then_some([then_taken, otherwise]),
+                });
+            }
+        }
+        FileCoverage {
+            path: self.path.clone(),
+            lines,
+            branches,
+        }
+    }
+}
+
+impl FileCoverage {
+    pub fn lines_hit(&self) -> usize {
+        self.lines.iter().filter(|(_, hits)| *hits > 0).count()
+    }
+
+    pub fn branch_count(&self) -> usize {
+        self.branches.len() * 2
+    }
+
+    pub fn branches_hit(&self) -> usize {
+        self.branches
+            .iter()
+            .filter_map(|branch| branch.taken)
+            .map(|taken| taken.iter().filter(|&&n| n > 0).count())
+            .sum()
+    }
+
+    /// lcov record of this file.
+    pub fn lcov(&self) -> String {
+        let mut out = format!("TN:\nSF:{}\n", self.path.display());
+        for (block, branch) in self.branches.iter().enumerate() {
+            for arm in 0..2 {
+                let taken = branch
+                    .taken
+                    .map(|taken| taken[arm].to_string())
+                    .unwrap_or_else(|| "-".to_string());
+                let _ = writeln!(out, "BRDA:{},{},{},{}", branch.line, block, arm, taken);
+            }
+        }
+        let _ = writeln!(
+            out,
+            "BRF:{}\nBRH:{}",
+            self.branch_count(),
+            self.branches_hit()
+        );
+        for (line, hits) in &self.lines {
+            let _ = writeln!(out, "DA:{},{}", line, hits);
+        }
+        let _ = writeln!(out, "LF:{}\nLH:{}", self.lines.len(), self.lines_hit());
+        out.push_str("end_of_record\n");
+        out
+    }
+
+    /// Cobertura `<class>` of this file, named relative to `source_root`.
+    fn cobertura_class(&self, source_root: &Path, out: &mut String) {
+        let filename = self
+            .path
+            .strip_prefix(source_root)
+            .unwrap_or(&self.path)
+            .to_string_lossy()
+            .replace('\\', "/");
+        let name = filename.trim_end_matches(".rr").replace('/', ".");
+        let _ = writeln!(
+            out,
+            "        <class name=\"{}\" filename=\"{}\" line-rate=\"{}\" branch-rate=\"{}\" complexity=\"0\">\n          <methods/>\n          <lines>",
+            xml_escape(&name),
+            xml_escape(&filename),
+            rate(self.lines_hit(), self.lines.len()),
+            rate(self.branches_hit(), self.branch_count())
+        );
+        let mut rows: BTreeMap<u32, (u64, usize, usize)> = self
+            .lines
+            .iter()
+            .map(|&(line, hits)| (line, (hits, 0, 0)))
+            .collect();
+        for branch in &self.branches {
+            let taken = branch.taken.unwrap_or([0, 0]);
+            let row = rows
+                .entry(branch.line)
+                .or_insert((taken[0] + taken[1], 0, 0));
+            row.1 += taken.iter().filter(|&&n| n > 0).count();
+            row.2 += 2;
+        }
+        for (line, (hits, covered, total)) in rows {
+            let Some(percent) = (covered * 100).checked_div(total) else {
+                let _ = writeln!(
+                    out,
+                    "            <line number=\"{}\" hits=\"{}\" branch=\"false\"/>",
+                    line, hits
+                );
+                continue;
+            };
+            let _ = writeln!(
+                out,
+                "            <line number=\"{}\" hits=\"{}\" branch=\"true\" condition-coverage=\"{}% ({}/{})\"/>",
+                line, hits, percent, covered, total
+            );
+        }
+        out.push_str("          </lines>\n        </class>\n");
+    }
+}
+
+impl CoverageReport {
+    pub fn line_count(&self) -> usize {
+        self.files.iter().map(|file| file.lines.len()).sum()
+    }
+
+    pub fn lines_hit(&self) -> usize {
+        self.files.iter().map(FileCoverage::lines_hit).sum()
+    }
+
+    pub fn branch_count(&self) -> usize {
+        self.files.iter().map(FileCoverage::branch_count).sum()
+    }
+
+    pub fn branches_hit(&self) -> usize {
+        self.files.iter().map(FileCoverage::branches_hit).sum()
+    }
+
+    /// lcov tracefile with one record per file.
+    pub fn lcov(&self) -> String {
+        self.files.iter().map(FileCoverage::lcov).collect()
+    }
+
+    /// Cobertura XML with one class per file, paths relative to
+    /// `source_root`. `timestamp` is in milliseconds since the epoch.
+    pub fn cobertura(&self, source_root: &Path, timestamp: u128) -> String {
+        let line_rate = rate(self.lines_hit(), self.line_count());
+        let branch_rate = rate(self.branches_hit(), self.branch_count());
+        let name = source_root
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let mut out = String::from("<?xml version=\"1.0\" ?>\n");
+        out.push_str(
+            "<!DOCTYPE coverage SYSTEM \"http://cobertura.sourceforge.net/xml/coverage-04.dtd\">\n",
+        );
+        let _ = writeln!(
+            out,
+            "<coverage line-rate=\"{}\" branch-rate=\"{}\" lines-covered=\"{}\" lines-valid=\"{}\" branches-covered=\"{}\" branches-valid=\"{}\" complexity=\"0\" version=\"RR {}\" timestamp=\"{}\">",
+            line_rate,
+            branch_rate,
+            self.lines_hit(),
+            self.line_count(),
+            self.branches_hit(),
+            self.branch_count(),
+            env!("CARGO_PKG_VERSION"),
+            timestamp
+        );
+        let _ = writeln!(
+            out,
+            "  <sources>\n    <source>{}</source>\n  </sources>",
+            xml_escape(&source_root.to_string_lossy())
+        );
+        let _ = writeln!(
+            out,
+            "  <packages>\n    <package name=\"{}\" line-rate=\"{}\" branch-rate=\"{}\" complexity=\"0\">\n      <classes>",
+            xml_escape(&name),
+            line_rate,
+            branch_rate
+        );
+        for file in &self.files {
+            file.cobertura_class(source_root, &mut out);
+        }
+        out.push_str("      </classes>\n    </package>\n  </packages>\n</coverage>\n");
+        out
+    }
+}
+
+fn rate(hit: usize, total: usize) -> String {
+    if total == 0 {
+        return "1".to_string();
+    }
+    format!("{:.4}", hit as f64 / total as f64)
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+#[path = "coverage/tests.rs"]
+mod tests;
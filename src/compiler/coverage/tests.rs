@@ -0,0 +1,168 @@
+use super::*;
+
+const PROGRAM: &str = "\
+fn sign(x: int) -> int {
+  if (x > 0L) {
+    return 1L
+  } else if (x < 0L) {
+    return -1L
+  }
+  return 0L
+}
+
+print(sign(3L))
+print(sign(0L))
+";
+
+fn entry() -> String {
+    std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("target")
+        .join("tests")
+        .join("coverage_unit")
+        .join("main.rr")
+        .to_string_lossy()
+        .to_string()
+}
+
+fn build() -> CoverageBuild {
+    instrument_for_coverage(&entry(), PROGRAM).unwrap_or_else(|e| panic!("{:?}", e))
+}
+
+#[test]
+fn probes_cover_statement_lines_and_then_blocks() {
+    let build = build();
+    assert_eq!(build.entry.lines, vec![2, 3, 5, 7, 10, 11]);
+    assert_eq!(build.entry.branches.len(), 2);
+    assert!(
+        build.source.contains("rr_cov_hit(\"L3\")"),
+        "{}",
+        build.source
+    );
+    assert!(
+        build.source.contains("rr_cov_hit(\"B1\")"),
+        "{}",
+        build.source
+    );
+    for (idx, line) in build.source.lines().enumerate() {
+        let original = build.original_line(idx as u32 + 1);
+        if let Some(text) = PROGRAM.lines().nth(original as usize - 1)
+            && !line.contains(COVERAGE_PROBE_NAME)
+        {
+            assert!(text.contains(line.trim()), "{line:?} vs {text:?}");
+        }
+    }
+}
+
+#[test]
+fn else_sides_are_derived_from_evaluations() {
+    let build = build();
+    let report = build.report("L2 2\nL3 1\nL7 1\nL10 1\nL11 1\nB0 1\n");
+    assert_eq!(report.files.len(), 1);
+    let file = &report.files[0];
+    assert_eq!(
+        file.lines,
+        vec![(2, 2), (3, 1), (5, 0), (7, 1), (10, 1), (11, 1)]
+    );
+    assert_eq!(
+        file.branches,
+        vec![
+            BranchCoverage {
+                line: 2,
+                taken: Some([1, 1]),
+            },
+            BranchCoverage {
+                line: 4,
+                taken: Some([0, 1]),
+            },
+        ]
+    );
+    let lcov = report.lcov();
+    assert!(
+        lcov.starts_with(&format!("TN:\nSF:{}\n", file.path.display())),
+        "{lcov}"
+    );
+    assert!(lcov.contains("BRDA:4,1,0,0\nBRDA:4,1,1,1\n"), "{lcov}");
+    assert!(lcov.contains("BRF:4\nBRH:3\n"), "{lcov}");
+    assert!(lcov.contains("DA:5,0\n"), "{lcov}");
+    assert!(lcov.ends_with("LF:6\nLH:5\nend_of_record\n"), "{lcov}");
+
+    let xml = report.cobertura(build.source_root(), 1_700_000_000_000);
+    assert!(
+        xml.contains("lines-covered=\"5\" lines-valid=\"6\"")
+            && xml.contains("<class name=\"main\" filename=\"main.rr\"")
+            && xml.contains("timestamp=\"1700000000000\""),
+        "{xml}"
+    );
+    assert!(
+        xml.contains(
+            "<line number=\"4\" hits=\"1\" branch=\"true\" condition-coverage=\"50% (1/2)\"/>"
+        ),
+        "{xml}"
+    );
+
+    let unrun = build.report("");
+    assert_eq!(unrun.lines_hit(), 0);
+    assert_eq!(unrun.files[0].branches[0].taken, None);
+    assert!(unrun.lcov().contains("BRDA:2,0,0,-\n"));
+}
+
+#[test]
+fn prepared_r_points_marks_at_original_lines() {
+    let build = build();
+    let mut map = vec![MapEntry {
+        r_line: 3,
+        rr_span: crate::utils::Span {
+            start_line: 5,
+            end_line: 5,
+            ..Default::default()
+        },
+    }];
+    let r_code = build.prepare_run("rr_mark(5L, 3L);\nx <- 1\n", &mut map, "/tmp/c\"ov");
+    assert!(
+        r_code.starts_with(".rr_cov_out <- \"/tmp/c\\\"ov\"\n"),
+        "{r_code}"
+    );
+    let original = build.original_line(5);
+    assert!(
+        r_code.contains(&format!("rr_mark({}L, 3L);", original)),
+        "{r_code}"
+    );
+    let prelude_lines = r_code.lines().count() as u32 - 2;
+    assert_eq!(map[0].r_line, 3 + prelude_lines);
+    assert_eq!(map[0].rr_span.start_line, original);
+}
+
+#[test]
+fn project_modules_are_instrumented_with_their_own_probes() {
+    let mut build = build();
+    let root = build.source_root().to_path_buf();
+    let module = root.join("util.rr");
+    let vendored = root.join("vendor").join("dep.rr");
+    let text = "fn twice(x: int) -> int {\n  return x * 2L\n}\n";
+    let (instrumented, skipped) = collect_module_coverage(&mut build, || {
+        set_module_root(root.clone());
+        (
+            instrument_module(&module, text.to_string()),
+            instrument_module(&vendored, text.to_string()),
+        )
+    });
+    assert!(
+        instrumented.contains("rr_cov_hit(\"M1:L2\")"),
+        "{instrumented}"
+    );
+    assert_eq!(skipped, text);
+    assert!(!instrumenting_modules());
+    assert_eq!(instrument_module(&module, text.to_string()), text);
+
+    let report = build.report("M1:L2 3\nL10 1\n");
+    let paths: Vec<&Path> = report
+        .files
+        .iter()
+        .map(|file| file.path.as_path())
+        .collect();
+    assert_eq!(paths, vec![build.entry.path.as_path(), module.as_path()]);
+    assert_eq!(report.files[1].lines, vec![(2, 3)]);
+    assert_eq!(report.lcov().matches("end_of_record").count(), 2);
+    let xml = report.cobertura(&root, 0);
+    assert!(xml.contains("filename=\"util.rr\""), "{xml}");
+}
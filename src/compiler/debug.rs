@@ -0,0 +1,169 @@
+//! Compile-side support for `RR dap`.
+//!
+//! A debug build instruments the RR source before compiling it: every
+//! statement that starts its own line gets an `unsafe r` hook naming that
+//! line, and the program is then compiled at `-O0`. Unsafe R blocks make
+//! their function opaque to the optimizer, so locals keep their own R
+//! bindings and the hook can read them from its caller's frame. The MIR of
+//! the instrumented program maps emitted R names back to RR names for the
+//! stack and variable views.
+
+use crate::compiler::CompilerParallelConfig;
+use crate::compiler::incremental::{
+    IncrementalCompileRequest, IncrementalOptions, compile_incremental_request,
+};
+use crate::compiler::instrument::{insert_probes, probe, visit_statements};
+use crate::compiler::pipeline::{
+    CompileOutputOptions, OptLevel, default_parallel_config, default_type_config,
+    synthesize_unoptimized_mir, with_quiet_log,
+};
+use crate::error::RR;
+use crate::mir::def::{FnIR, Instr};
+use crate::syntax::parse::Parser;
+
+/// R function the instrumented program calls before each statement.
+pub const DEBUG_HOOK_NAME: &str = "rr_dap_hook";
+
+/// Name shown for the frame running top-level statements.
+pub const DEBUG_TOP_LEVEL_NAME: &str = "<top-level>";
+
+const TOP_LEVEL_PREFIX: &str = "Sym_top_";
+
+/// An emitted R function together with the RR names of its locals.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DebugFunction {
+    pub r_name: String,
+    pub rr_name: String,
+    /// `(R name, RR name)` for every parameter and assigned local.
+    pub locals: Vec<(String, String)>,
+}
+
+impl DebugFunction {
+    /// RR name for the R binding `r_name`, or `None` for compiler temporaries.
+    pub fn rr_local<'a>(&'a self, r_name: &'a str) -> Option<&'a str> {
+        match self.locals.iter().find(|(r, _)| r == r_name) {
+            Some((_, rr)) => Some(rr),
+            None if r_name.starts_with('.') => None,
+            None => Some(r_name),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct DebugBuild {
+    /// Instrumented R with its runtime, but without the debug hook.
+    pub r_code: String,
+    /// Sorted RR lines that carry a hook, i.e. where execution can stop.
+    pub breakable_lines: Vec<u32>,
+    pub functions: Vec<DebugFunction>,
+}
+
+impl DebugBuild {
+    /// Line a breakpoint requested at `line` binds to: the first breakable
+    /// line at or after it.
+    pub fn resolve_breakpoint(&self, line: u32) -> Option<u32> {
+        self.breakable_lines
+            .iter()
+            .copied()
+            .find(|&breakable| breakable >= line)
+    }
+
+    pub fn function(&self, r_name: &str) -> Option<&DebugFunction> {
+        self.functions.iter().find(|f| f.r_name == r_name)
+    }
+}
+
+/// Compile `source` for debugging. Errors are reported against the
+/// original text, before any hook is inserted.
+pub fn compile_for_debug(entry_path: &str, source: &str) -> RR<DebugBuild> {
+    synthesize_unoptimized_mir(entry_path, source)?;
+    let program = Parser::new(source).parse_program()?;
+    let mut hooks = Vec::new();
+    visit_statements(source, &program.stmts, &mut |stmt, own_line| {
+        if own_line {
+            hooks.push((stmt.span.start_byte, stmt.span.start_line));
+        }
+    });
+    let probes = hooks
+        .iter()
+        .map(|&(byte, line)| probe(source, byte, &format!("{}({}L)", DEBUG_HOOK_NAME, line)))
+        .collect();
+    let (instrumented, _) = insert_probes(source, probes);
+
+    let output = with_quiet_log(|| {
+        compile_incremental_request(IncrementalCompileRequest {
+            entry_path,
+            entry_input: &instrumented,
+            opt_level: OptLevel::O0,
+            type_cfg: default_type_config(),
+            parallel_cfg: default_parallel_config(),
+            compiler_parallel_cfg: CompilerParallelConfig::default(),
+            options: IncrementalOptions::disabled(),
+            output_options: CompileOutputOptions {
+                preserve_all_defs: true,
+                ..CompileOutputOptions::default()
+            },
+            session: None,
+            profile: None,
+        })
+    })?;
+    let fns = synthesize_unoptimized_mir(entry_path, &instrumented)?;
+    let functions = fns
+        .iter()
+        .filter_map(|fn_ir| debug_function(fn_ir, &output.r_code))
+        .collect();
+    let mut breakable_lines: Vec<u32> = hooks.iter().map(|&(_, line)| line).collect();
+    breakable_lines.sort_unstable();
+    breakable_lines.dedup();
+    Ok(DebugBuild {
+        r_code: output.r_code,
+        breakable_lines,
+        functions,
+    })
+}
+
+fn debug_function(fn_ir: &FnIR, r_code: &str) -> Option<DebugFunction> {
+    let (r_name, rr_name) = if fn_ir.name.starts_with(TOP_LEVEL_PREFIX) {
+        (fn_ir.name.clone(), DEBUG_TOP_LEVEL_NAME.to_string())
+    } else {
+        let user_name = fn_ir.user_name.clone()?;
+        let r_name = if r_code.contains(&format!("\n{} <- function(", user_name)) {
+            user_name.clone()
+        } else {
+            fn_ir.name.clone()
+        };
+        (r_name, user_name)
+    };
+    let mut locals: Vec<(String, String)> = Vec::new();
+    let mut add = |r: &str, rr: &str| {
+        let r = r.strip_prefix(".arg_").unwrap_or(r);
+        let rr = rr.strip_prefix(".arg_").unwrap_or(rr);
+        if !locals.iter().any(|(known, _)| known == r) {
+            locals.push((r.to_string(), rr.to_string()));
+        }
+    };
+    for param in &fn_ir.params {
+        add(param, param);
+    }
+    for block in &fn_ir.blocks {
+        for instr in &block.instrs {
+            if let Instr::Assign { dst, src, .. } = instr {
+                let origin = fn_ir
+                    .values
+                    .get(*src)
+                    .and_then(|value| value.origin_var.as_deref())
+                    .filter(|origin| !origin.starts_with('.') || origin.starts_with(".arg_"));
+                add(dst, origin.unwrap_or(dst));
+            }
+        }
+    }
+    Some(DebugFunction {
+        r_name,
+        rr_name,
+        locals,
+    })
+}
+
+#[cfg(test)]
+#[path = "debug/tests.rs"]
+mod tests;
@@ -0,0 +1,72 @@
+use super::*;
+
+const PROGRAM: &str = "\
+fn addup(xs: vector<float>, k: int) -> float {
+  let total = 0.0
+  for (i in 1L..length(xs)) {
+    let v = xs[i] * k
+    total = total + v
+  }
+  if (total > 100.0) {
+    print(\"big\")
+  } else if (total < 0.0) {
+    print(\"negative\")
+  }
+  return total
+}
+
+let data = c(1.0, 2.0, 3.0)
+print(addup(data, 2L))
+";
+
+fn entry() -> String {
+    std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("target")
+        .join("tests")
+        .join("debug_unit")
+        .join("main.rr")
+        .to_string_lossy()
+        .to_string()
+}
+
+#[test]
+fn hooks_mark_each_statement_line_and_resolve_breakpoints() {
+    let build = compile_for_debug(&entry(), PROGRAM).unwrap_or_else(|e| panic!("{:?}", e));
+    assert_eq!(
+        build.breakable_lines,
+        vec![2, 3, 4, 5, 7, 8, 10, 12, 15, 16]
+    );
+    assert!(build.r_code.contains("rr_dap_hook(4L)"), "{}", build.r_code);
+    assert!(!build.r_code.contains("rr_dap_hook(9L)"));
+    assert_eq!(build.resolve_breakpoint(1), Some(2));
+    assert_eq!(build.resolve_breakpoint(6), Some(7));
+    assert_eq!(build.resolve_breakpoint(13), Some(15));
+    assert_eq!(build.resolve_breakpoint(17), None);
+}
+
+#[test]
+fn functions_map_r_names_back_to_rr_names() {
+    let build = compile_for_debug(&entry(), PROGRAM).unwrap_or_else(|e| panic!("{:?}", e));
+    let addup = build
+        .function("addup")
+        .unwrap_or_else(|| panic!("{:?}", build.functions));
+    assert_eq!(addup.rr_name, "addup");
+    for local in ["xs", "k", "total", "i", "v"] {
+        assert_eq!(addup.rr_local(local), Some(local), "{:?}", addup.locals);
+    }
+    assert_eq!(addup.rr_local(".tachyon_tmp"), None);
+    assert!(
+        build
+            .functions
+            .iter()
+            .any(|f| f.rr_name == DEBUG_TOP_LEVEL_NAME && build.r_code.contains(&f.r_name)),
+        "{:?}",
+        build.functions
+    );
+}
+
+#[test]
+fn compile_errors_point_at_the_original_source() {
+    let err = compile_for_debug(&entry(), "let x = 1L\nprint(y)\n").expect_err("undefined y");
+    assert!(err.message.contains("'y'"), "{}", err.message);
+}
@@ -0,0 +1,631 @@
+//! API documentation for `RR doc`.
+//!
+//! `///` comments before `fn`, `trait` and `impl` items and `//!` comments at
+//! the top of a file are kept by the parser. A module's page lists its
+//! exported items with signatures rendered from their type hints, and every
+//! trait page lists the impls found anywhere in the documented set. Fenced
+//! ` ```rr ` blocks in docs are examples: `RR doc --test` compiles each one
+//! against its module and runs it.
+
+use crate::codegen::mir_emit::MapEntry;
+use crate::compiler::CompilerParallelConfig;
+use crate::compiler::incremental::{
+    IncrementalCompileRequest, IncrementalOptions, compile_incremental_request,
+};
+use crate::compiler::pipeline::{
+    CompileOutputOptions, OptLevel, default_parallel_config, default_type_config, with_quiet_log,
+};
+use crate::error::RR;
+use crate::syntax::ast::{
+    FnParam, ImplDecl, StmtKind, TraitBound, TraitDecl, TraitMethodSig, TypeExpr,
+};
+use crate::syntax::parse::Parser;
+use std::fmt::Write as _;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DocItemKind {
+    Function,
+    Trait,
+    Impl,
+    AssocType,
+    AssocConst,
+}
+
+/// One documented declaration.
+#[derive(Clone, Debug)]
+pub struct DocItem {
+    pub kind: DocItemKind,
+    /// Item name; `Trait for Type` for impls.
+    pub name: String,
+    pub signature: String,
+    pub doc: Option<String>,
+    pub line: u32,
+    /// Methods and associated items of a trait or impl.
+    pub members: Vec<DocItem>,
+    /// Implemented trait, for impls.
+    pub trait_name: Option<String>,
+}
+
+/// The public API of one `.rr` file.
+#[derive(Clone, Debug)]
+pub struct ModuleDoc {
+    /// Module path relative to the source root, without `.rr`.
+    pub name: String,
+    pub doc: Option<String>,
+    pub items: Vec<DocItem>,
+}
+
+/// A fenced RR example taken from a doc comment.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DocExample {
+    pub module: String,
+    /// Documented item, `Trait.method` for members, empty for the module.
+    pub item: String,
+    /// Line of the documented item.
+    pub line: u32,
+    pub code: String,
+    /// `false` for ` ```rr,no_run ` examples, which are only compiled.
+    pub run: bool,
+}
+
+/// Collect the exported API of `source`, documented as module `name`.
+pub fn document_module(name: &str, source: &str) -> RR<ModuleDoc> {
+    let program = Parser::new(source).parse_program()?;
+    let mut items = Vec::new();
+    for stmt in &program.stmts {
+        let line = stmt.span.start_line;
+        match &stmt.kind {
+            StmtKind::Export(decl) => items.push(DocItem {
+                kind: DocItemKind::Function,
+                name: decl.name.clone(),
+                signature: fn_signature(
+                    source,
+                    &decl.name,
+                    &decl.type_params,
+                    &decl.params,
+                    decl.ret_ty_hint.as_ref(),
+                    &decl.where_bounds,
+                ),
+                doc: decl.doc.clone(),
+                line,
+                members: Vec::new(),
+                trait_name: None,
+            }),
+            StmtKind::TraitDecl(decl) if decl.public => {
+                items.push(trait_item(source, decl, line));
+            }
+            StmtKind::ImplDecl(decl) if decl.public => items.push(impl_item(source, decl, line)),
+            _ => {}
+        }
+    }
+    Ok(ModuleDoc {
+        name: name.to_string(),
+        doc: program.doc,
+        items,
+    })
+}
+
+fn trait_item(source: &str, decl: &TraitDecl, line: u32) -> DocItem {
+    let mut signature = format!("trait {}{}", decl.name, type_params(&decl.type_params));
+    if !decl.supertraits.is_empty() {
+        let _ = write!(signature, ": {}", decl.supertraits.join(" + "));
+    }
+    signature.push_str(&where_clause(&decl.where_bounds));
+    let mut members = Vec::new();
+    for assoc in &decl.assoc_types {
+        members.push(member(
+            DocItemKind::AssocType,
+            &assoc.name,
+            format!("type {}{}", assoc.name, type_params(&assoc.type_params)),
+            assoc.span.start_line,
+        ));
+    }
+    for assoc in &decl.assoc_consts {
+        let mut text = format!("const {}: {}", assoc.name, type_text(&assoc.ty_hint));
+        if let Some(default) = &assoc.default {
+            let _ = write!(text, " = {}", span_text(source, default.span));
+        }
+        members.push(member(
+            DocItemKind::AssocConst,
+            &assoc.name,
+            text,
+            assoc.span.start_line,
+        ));
+    }
+    members.extend(
+        decl.methods
+            .iter()
+            .map(|method| trait_method(source, method)),
+    );
+    DocItem {
+        kind: DocItemKind::Trait,
+        name: decl.name.clone(),
+        signature,
+        doc: decl.doc.clone(),
+        line,
+        members,
+        trait_name: None,
+    }
+}
+
+fn trait_method(source: &str, method: &TraitMethodSig) -> DocItem {
+    DocItem {
+        doc: method.doc.clone(),
+        ..member(
+            DocItemKind::Function,
+            &method.name,
+            fn_signature(
+                source,
+                &method.name,
+                &[],
+                &method.params,
+                method.ret_ty_hint.as_ref(),
+                &method.where_bounds,
+            ),
+            method.span.start_line,
+        )
+    }
+}
+
+fn impl_item(source: &str, decl: &ImplDecl, line: u32) -> DocItem {
+    let name = format!("{} for {}", decl.trait_name, type_text(&decl.for_ty));
+    let signature = format!(
+        "impl{} {}{}{}",
+        type_params(&decl.type_params),
+        if decl.negative { "!" } else { "" },
+        name,
+        where_clause(&decl.where_bounds)
+    );
+    let mut members = Vec::new();
+    for assoc in &decl.assoc_types {
+        members.push(member(
+            DocItemKind::AssocType,
+            &assoc.name,
+            format!("type {} = {}", assoc.name, type_text(&assoc.ty)),
+            assoc.span.start_line,
+        ));
+    }
+    for assoc in &decl.assoc_consts {
+        members.push(member(
+            DocItemKind::AssocConst,
+            &assoc.name,
+            format!(
+                "const {}: {} = {}",
+                assoc.name,
+                type_text(&assoc.ty_hint),
+                span_text(source, assoc.value.span)
+            ),
+            assoc.span.start_line,
+        ));
+    }
+    for method in &decl.methods {
+        members.push(DocItem {
+            doc: method.doc.clone(),
+            ..member(
+                DocItemKind::Function,
+                &method.name,
+                fn_signature(
+                    source,
+                    &method.name,
+                    &method.type_params,
+                    &method.params,
+                    method.ret_ty_hint.as_ref(),
+                    &method.where_bounds,
+                ),
+                method.body.span.start_line,
+            )
+        });
+    }
+    DocItem {
+        kind: DocItemKind::Impl,
+        name,
+        signature,
+        doc: decl.doc.clone(),
+        line,
+        members,
+        trait_name: Some(decl.trait_name.clone()),
+    }
+}
+
+fn member(kind: DocItemKind, name: &str, signature: String, line: u32) -> DocItem {
+    DocItem {
+        kind,
+        name: name.to_string(),
+        signature,
+        doc: None,
+        line,
+        members: Vec::new(),
+        trait_name: None,
+    }
+}
+
+fn fn_signature(
+    source: &str,
+    name: &str,
+    generics: &[String],
+    params: &[FnParam],
+    ret: Option<&TypeExpr>,
+    bounds: &[TraitBound],
+) -> String {
+    let params = params
+        .iter()
+        .map(|param| {
+            let mut text = param.name.clone();
+            if let Some(ty) = &param.ty_hint {
+                let _ = write!(text, ": {}", type_text(ty));
+            }
+            if let Some(default) = &param.default {
+                let _ = write!(text, " = {}", span_text(source, default.span));
+            }
+            text
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    let mut out = format!("fn {}{}({})", name, type_params(generics), params);
+    if let Some(ret) = ret {
+        let _ = write!(out, " -> {}", type_text(ret));
+    }
+    out.push_str(&where_clause(bounds));
+    out
+}
+
+fn type_text(ty: &TypeExpr) -> String {
+    match ty {
+        TypeExpr::Named(name) => name.clone(),
+        TypeExpr::Generic { base, args } => format!(
+            "{}<{}>",
+            base,
+            args.iter().map(type_text).collect::<Vec<_>>().join(", ")
+        ),
+    }
+}
+
+fn type_params(params: &[String]) -> String {
+    if params.is_empty() {
+        String::new()
+    } else {
+        format!("<{}>", params.join(", "))
+    }
+}
+
+fn where_clause(bounds: &[TraitBound]) -> String {
+    if bounds.is_empty() {
+        return String::new();
+    }
+    let bounds = bounds
+        .iter()
+        .map(|bound| format!("{}: {}", bound.type_name, bound.trait_names.join(" + ")))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!(" where {}", bounds)
+}
+
+fn span_text(source: &str, span: crate::utils::Span) -> &str {
+    source
+        .get(span.start_byte..span.end_byte)
+        .unwrap_or_default()
+        .trim()
+}
+
+impl ModuleDoc {
+    /// Every runnable or compile-only example in the module's docs.
+    pub fn examples(&self) -> Vec<DocExample> {
+        let mut out = Vec::new();
+        self.collect_examples("", 1, self.doc.as_deref(), &mut out);
+        for item in &self.items {
+            self.collect_examples(&item.name, item.line, item.doc.as_deref(), &mut out);
+            for member in &item.members {
+                let name = format!("{}.{}", item.name, member.name);
+                self.collect_examples(&name, member.line, member.doc.as_deref(), &mut out);
+            }
+        }
+        out
+    }
+
+    fn collect_examples(
+        &self,
+        item: &str,
+        line: u32,
+        doc: Option<&str>,
+        out: &mut Vec<DocExample>,
+    ) {
+        let Some(doc) = doc else {
+            return;
+        };
+        let mut fence: Option<(bool, Vec<&str>)> = None;
+        for text in doc.lines() {
+            let trimmed = text.trim_start();
+            match (&mut fence, trimmed.strip_prefix("```")) {
+                (None, Some(info)) => fence = example_fence(info).map(|run| (run, Vec::new())),
+                (Some(_), Some(_)) => {
+                    if let Some((run, code)) = fence.take() {
+                        out.push(DocExample {
+                            module: self.name.clone(),
+                            item: item.to_string(),
+                            line,
+                            code: code.join("\n"),
+                            run,
+                        });
+                    }
+                }
+                (Some((_, code)), None) => code.push(text),
+                (None, None) => {}
+            }
+        }
+    }
+}
+
+/// Whether a fence with this info string holds an example, and if so
+/// whether it runs. Fences for other languages and `ignore`d ones do not.
+fn example_fence(info: &str) -> Option<bool> {
+    let mut words = info
+        .split([',', ' '])
+        .map(str::trim)
+        .filter(|w| !w.is_empty());
+    let lang = words.next().unwrap_or("rr");
+    if lang != "rr" {
+        return None;
+    }
+    let attrs: Vec<&str> = words.collect();
+    if attrs.contains(&"ignore") {
+        return None;
+    }
+    Some(!attrs.contains(&"no_run"))
+}
+
+impl DocExample {
+    /// The example as a program that imports its module from `module_path`.
+    pub fn program(&self, module_path: &str) -> String {
+        format!(
+            "import \"{}\"\n\n{}\n",
+            module_path.replace('\\', "/").replace('"', "\\\""),
+            self.code
+        )
+    }
+}
+
+/// Compile an example program built by [`DocExample::program`].
+pub fn compile_doc_example(entry_path: &str, source: &str) -> RR<(String, Vec<MapEntry>)> {
+    let output = with_quiet_log(|| {
+        compile_incremental_request(IncrementalCompileRequest {
+            entry_path,
+            entry_input: source,
+            opt_level: OptLevel::O1,
+            type_cfg: default_type_config(),
+            parallel_cfg: default_parallel_config(),
+            compiler_parallel_cfg: CompilerParallelConfig::default(),
+            options: IncrementalOptions::disabled(),
+            output_options: CompileOutputOptions::default(),
+            session: None,
+            profile: None,
+        })
+    })?;
+    Ok((output.r_code, output.source_map))
+}
+
+/// File name of a module's page, without extension.
+pub fn doc_page_name(module: &str) -> String {
+    module.replace(['/', '\\'], ".")
+}
+
+/// Markdown page for `module`. `all` is the full documented set, used to
+/// list the impls of each trait.
+pub fn render_module_markdown(module: &ModuleDoc, all: &[ModuleDoc]) -> String {
+    let mut out = format!("# Module `{}`\n\n", module.name);
+    if let Some(doc) = &module.doc {
+        let _ = write!(out, "{}\n\n", doc.trim_end());
+    }
+    let sections = [
+        (DocItemKind::Function, "Functions"),
+        (DocItemKind::Trait, "Traits"),
+        (DocItemKind::Impl, "Implementations"),
+    ];
+    for (kind, title) in sections {
+        let items: Vec<&DocItem> = module.items.iter().filter(|i| i.kind == kind).collect();
+        if items.is_empty() {
+            continue;
+        }
+        let _ = write!(out, "## {}\n\n", title);
+        for item in items {
+            let heading = if kind == DocItemKind::Impl {
+                format!("impl {}", item.name)
+            } else {
+                item.name.clone()
+            };
+            render_item(&mut out, "###", &heading, item);
+            if !item.members.is_empty() {
+                out.push_str("#### Members\n\n");
+                for member in &item.members {
+                    render_item(&mut out, "#####", &member.name, member);
+                }
+            }
+            if kind == DocItemKind::Trait {
+                let impls = trait_impls(all, &item.name);
+                if !impls.is_empty() {
+                    out.push_str("#### Implementors\n\n");
+                    for (owner, name) in impls {
+                        let _ = writeln!(
+                            out,
+                            "- [`impl {}`]({}.md) in `{}`",
+                            name,
+                            doc_page_name(owner),
+                            owner
+                        );
+                    }
+                    out.push('\n');
+                }
+            }
+        }
+    }
+    if module.items.is_empty() {
+        out.push_str("This module exports no items.\n");
+    }
+    out
+}
+
+fn render_item(out: &mut String, level: &str, heading: &str, item: &DocItem) {
+    let _ = write!(
+        out,
+        "{} `{}`\n\n```rr\n{}\n```\n\n",
+        level, heading, item.signature
+    );
+    if let Some(doc) = &item.doc {
+        let _ = write!(out, "{}\n\n", doc.trim_end());
+    }
+}
+
+fn trait_impls<'a>(all: &'a [ModuleDoc], trait_name: &str) -> Vec<(&'a str, &'a str)> {
+    all.iter()
+        .flat_map(|module| {
+            module
+                .items
+                .iter()
+                .filter(move |item| item.trait_name.as_deref() == Some(trait_name))
+                .map(move |item| (module.name.as_str(), item.name.as_str()))
+        })
+        .collect()
+}
+
+/// Markdown index of the documented modules.
+pub fn render_index_markdown(title: &str, modules: &[ModuleDoc]) -> String {
+    let mut out = format!("# {}\n\n## Modules\n\n", title);
+    for module in modules {
+        let summary = module
+            .doc
+            .as_deref()
+            .and_then(|doc| doc.lines().find(|line| !line.trim().is_empty()))
+            .map(|line| format!(" — {}", line.trim()))
+            .unwrap_or_default();
+        let _ = writeln!(
+            out,
+            "- [`{}`]({}.md){}",
+            module.name,
+            doc_page_name(&module.name),
+            summary
+        );
+    }
+    out
+}
+
+/// HTML version of a page rendered by this module. Covers the Markdown the
+/// pages use: headings, fenced code, lists, paragraphs, inline code and
+/// links, with links to `.md` pages pointed at their `.html` twins.
+pub fn markdown_to_html(title: &str, markdown: &str) -> String {
+    let mut body = String::new();
+    let mut paragraph: Vec<String> = Vec::new();
+    let mut in_list = false;
+    let mut code: Option<Vec<&str>> = None;
+    let flush = |body: &mut String, paragraph: &mut Vec<String>, in_list: &mut bool| {
+        if !paragraph.is_empty() {
+            let _ = writeln!(body, "<p>{}</p>", paragraph.join(" "));
+            paragraph.clear();
+        }
+        if *in_list {
+            body.push_str("</ul>\n");
+            *in_list = false;
+        }
+    };
+    for line in markdown.lines() {
+        if let Some(lines) = &mut code {
+            if line.trim_start().starts_with("```") {
+                let _ = writeln!(
+                    body,
+                    "<pre><code>{}</code></pre>",
+                    html_escape(&lines.join("\n"))
+                );
+                code = None;
+            } else {
+                lines.push(line);
+            }
+            continue;
+        }
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") {
+            flush(&mut body, &mut paragraph, &mut in_list);
+            code = Some(Vec::new());
+        } else if trimmed.is_empty() {
+            flush(&mut body, &mut paragraph, &mut in_list);
+        } else if let Some(level) = heading_level(trimmed) {
+            flush(&mut body, &mut paragraph, &mut in_list);
+            let text = trimmed[level..].trim();
+            let _ = writeln!(body, "<h{0}>{1}</h{0}>", level, inline_html(text));
+        } else if let Some(text) = trimmed.strip_prefix("- ") {
+            if !paragraph.is_empty() {
+                flush(&mut body, &mut paragraph, &mut in_list);
+            }
+            if !in_list {
+                body.push_str("<ul>\n");
+                in_list = true;
+            }
+            let _ = writeln!(body, "<li>{}</li>", inline_html(text));
+        } else {
+            paragraph.push(inline_html(trimmed));
+        }
+    }
+    if let Some(lines) = code {
+        let _ = writeln!(
+            body,
+            "<pre><code>{}</code></pre>",
+            html_escape(&lines.join("\n"))
+        );
+    }
+    flush(&mut body, &mut paragraph, &mut in_list);
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n</head>\n<body>\n{}</body>\n</html>\n",
+        html_escape(title),
+        body
+    )
+}
+
+fn heading_level(line: &str) -> Option<usize> {
+    let level = line.chars().take_while(|&c| c == '#').count();
+    ((1..=6).contains(&level) && line[level..].starts_with(' ')).then_some(level)
+}
+
+fn inline_html(text: &str) -> String {
+    let mut out = String::new();
+    let mut rest = text;
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix('`')
+            && let Some(end) = after.find('`')
+        {
+            let _ = write!(out, "<code>{}</code>", html_escape(&after[..end]));
+            rest = &after[end + 1..];
+        } else if let Some(after) = rest.strip_prefix('[')
+            && let Some(mid) = after.find("](")
+            && let Some(end) = after[mid + 2..].find(')')
+        {
+            let target = &after[mid + 2..mid + 2 + end];
+            let target = match target.strip_suffix(".md") {
+                Some(page) => format!("{}.html", page),
+                None => target.to_string(),
+            };
+            let _ = write!(
+                out,
+                "<a href=\"{}\">{}</a>",
+                html_escape(&target),
+                inline_html(&after[..mid])
+            );
+            rest = &after[mid + 2 + end + 1..];
+        } else {
+            let first = rest.chars().next().map_or(1, char::len_utf8);
+            let next = rest[first..]
+                .find(['`', '['])
+                .map_or(rest.len(), |idx| idx + first);
+            out.push_str(&html_escape(&rest[..next]));
+            rest = &rest[next..];
+        }
+    }
+    out
+}
+
+fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+#[path = "doc/tests.rs"]
+mod tests;
@@ -0,0 +1,160 @@
+use super::*;
+
+const SHAPES: &str = "\
+//! Plane shapes.
+//!
+//! Areas are in square units.
+
+/// Something with an area.
+export trait Shape {
+  /// Area of the shape.
+  ///
+  /// ```rr
+  /// print(area_of(2.0))
+  /// ```
+  fn area(self) -> float
+}
+
+//// Not a doc comment.
+fn helper(x: float) -> float {
+  return x * x
+}
+
+/// Area of a square with side `side`.
+///
+/// ```rr,no_run
+/// print(area_of(3.0))
+/// ```
+///
+/// ```text
+/// not an example
+/// ```
+export fn area_of(side: float, scale: vector<float> = c(1.0, 2.0)) -> float {
+  return helper(side) * scale[1L]
+}
+";
+
+const CIRCLES: &str = "\
+/// Circles measure their area with pi.
+export impl Shape for float {
+  fn area(self) -> float {
+    return 3.14 * self * self
+  }
+}
+";
+
+fn modules() -> Vec<ModuleDoc> {
+    vec![
+        document_module("shapes", SHAPES).unwrap_or_else(|e| panic!("{:?}", e)),
+        document_module("geo/circles", CIRCLES).unwrap_or_else(|e| panic!("{:?}", e)),
+    ]
+}
+
+#[test]
+fn exported_items_keep_docs_and_typed_signatures() {
+    let docs = modules();
+    let shapes = &docs[0];
+    assert_eq!(
+        shapes.doc.as_deref(),
+        Some("Plane shapes.\n\nAreas are in square units.")
+    );
+    let names: Vec<&str> = shapes.items.iter().map(|i| i.name.as_str()).collect();
+    assert_eq!(names, vec!["Shape", "area_of"]);
+
+    let shape = &shapes.items[0];
+    assert_eq!(shape.kind, DocItemKind::Trait);
+    assert_eq!(shape.signature, "trait Shape");
+    assert_eq!(shape.doc.as_deref(), Some("Something with an area."));
+    assert_eq!(shape.members[0].signature, "fn area(self) -> float");
+    assert!(
+        shape.members[0]
+            .doc
+            .as_deref()
+            .unwrap_or_default()
+            .starts_with("Area of the shape.")
+    );
+
+    let area_of = &shapes.items[1];
+    assert_eq!(
+        area_of.signature,
+        "fn area_of(side: float, scale: vector<float> = c(1.0, 2.0)) -> float"
+    );
+    assert_eq!(area_of.line, 29);
+
+    let circles = &docs[1].items[0];
+    assert_eq!(circles.kind, DocItemKind::Impl);
+    assert_eq!(circles.signature, "impl Shape for float");
+    assert_eq!(circles.trait_name.as_deref(), Some("Shape"));
+    assert_eq!(
+        circles.doc.as_deref(),
+        Some("Circles measure their area with pi.")
+    );
+}
+
+#[test]
+fn examples_follow_fence_info() {
+    let docs = modules();
+    let examples = docs[0].examples();
+    assert_eq!(
+        examples,
+        vec![
+            DocExample {
+                module: "shapes".to_string(),
+                item: "Shape.area".to_string(),
+                line: 12,
+                code: "print(area_of(2.0))".to_string(),
+                run: true,
+            },
+            DocExample {
+                module: "shapes".to_string(),
+                item: "area_of".to_string(),
+                line: 29,
+                code: "print(area_of(3.0))".to_string(),
+                run: false,
+            },
+        ]
+    );
+    assert_eq!(
+        examples[0].program("/src/shapes.rr"),
+        "import \"/src/shapes.rr\"\n\nprint(area_of(2.0))\n"
+    );
+}
+
+#[test]
+fn pages_list_implementors_and_render_to_html() {
+    let docs = modules();
+    let page = render_module_markdown(&docs[0], &docs);
+    assert!(
+        page.starts_with("# Module `shapes`\n\nPlane shapes."),
+        "{page}"
+    );
+    assert!(
+        page.contains("## Functions\n\n### `area_of`\n\n```rr\nfn area_of("),
+        "{page}"
+    );
+    assert!(
+        page.contains(
+            "#### Implementors\n\n- [`impl Shape for float`](geo.circles.md) in `geo/circles`\n"
+        ),
+        "{page}"
+    );
+    let index = render_index_markdown("API", &docs);
+    assert!(
+        index.contains("- [`shapes`](shapes.md) — Plane shapes.\n"),
+        "{index}"
+    );
+
+    let html = markdown_to_html("shapes", &page);
+    assert!(
+        html.contains("<h1>Module <code>shapes</code></h1>"),
+        "{html}"
+    );
+    assert!(
+        html.contains("<li><a href=\"geo.circles.html\"><code>impl Shape for float</code></a> in <code>geo/circles</code></li>"),
+        "{html}"
+    );
+    assert!(
+        html.contains("<pre><code>fn area_of(side: float, scale: vector&lt;float&gt; = c(1.0, 2.0)) -&gt; float</code></pre>"),
+        "{html}"
+    );
+}
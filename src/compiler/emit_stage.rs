@@ -0,0 +1,412 @@
+//! `--emit` stage dumps: stable text forms of each compiler stage.
+//!
+//! A compile run inside [`collect_stage_dumps`] records the requested stages
+//! as the pipeline reaches them; outside it the hooks cost one thread-local
+//! lookup. Per-function stages (`ast`, `hir`, `typed`, `mir`, `mir-opt`)
+//! honour the function filter; `tokens`, `raw-r` and `r` are whole-file.
+
+#[path = "emit_stage/ast_text.rs"]
+mod ast_text;
+#[path = "emit_stage/hir_text.rs"]
+mod hir_text;
+
+use crate::hir::def::{HirItem, HirModule, SymbolId};
+use crate::mir::FnIR;
+use crate::mir::def::{Instr, ValueKind};
+use crate::syntax::ast::{Program, StmtKind};
+use crate::syntax::lex::Lexer;
+use crate::syntax::token::TokenKind;
+use crate::typeck::TypeTerm;
+use rustc_hash::FxHashMap;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum EmitStage {
+    Tokens,
+    Ast,
+    Hir,
+    Typed,
+    Mir,
+    MirOpt,
+    RawR,
+    R,
+}
+
+impl EmitStage {
+    pub const ALL: [Self; 8] = [
+        Self::Tokens,
+        Self::Ast,
+        Self::Hir,
+        Self::Typed,
+        Self::Mir,
+        Self::MirOpt,
+        Self::RawR,
+        Self::R,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Tokens => "tokens",
+            Self::Ast => "ast",
+            Self::Hir => "hir",
+            Self::Typed => "typed",
+            Self::Mir => "mir",
+            Self::MirOpt => "mir-opt",
+            Self::RawR => "raw-r",
+            Self::R => "r",
+        }
+    }
+
+    /// Suffix appended to the output stem, e.g. `main.opt.mir.txt`.
+    pub fn file_suffix(self) -> &'static str {
+        match self {
+            Self::Tokens => "tokens.txt",
+            Self::Ast => "ast.txt",
+            Self::Hir => "hir.txt",
+            Self::Typed => "typed.txt",
+            Self::Mir => "mir.txt",
+            Self::MirOpt => "opt.mir.txt",
+            Self::RawR => "raw.R",
+            Self::R => "final.R",
+        }
+    }
+
+    pub fn parse(raw: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|stage| stage.as_str().eq_ignore_ascii_case(raw.trim()))
+    }
+
+    /// Parse a comma-separated `--emit` list; `all` selects every stage.
+    pub fn parse_list(raw: &str) -> Result<Vec<Self>, String> {
+        let mut stages = Vec::new();
+        for item in raw
+            .split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+        {
+            if item.eq_ignore_ascii_case("all") {
+                stages.extend(Self::ALL);
+                continue;
+            }
+            let Some(stage) = Self::parse(item) else {
+                return Err(format!(
+                    "unknown --emit stage '{}' (expected {} or all)",
+                    item,
+                    Self::ALL.map(Self::as_str).join("|")
+                ));
+            };
+            stages.push(stage);
+        }
+        if stages.is_empty() {
+            return Err("--emit needs at least one stage".to_string());
+        }
+        stages.sort();
+        stages.dedup();
+        Ok(stages)
+    }
+}
+
+/// Requested stages plus the text recorded for them.
+#[derive(Clone, Debug, Default)]
+pub struct StageDumps {
+    stages: Vec<EmitStage>,
+    functions: Vec<String>,
+    /// Project root of the current compile; module headers are relative to it.
+    root: Option<PathBuf>,
+    output: BTreeMap<EmitStage, String>,
+}
+
+impl StageDumps {
+    /// `functions` limits per-function stages to these RR names; empty keeps all.
+    pub fn new(stages: Vec<EmitStage>, functions: Vec<String>) -> Self {
+        Self {
+            stages,
+            functions,
+            root: None,
+            output: BTreeMap::new(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stages.is_empty()
+    }
+
+    /// Recorded text for every requested stage, in pipeline order. A stage the
+    /// compile never reached (or that matched no function) yields an empty string.
+    pub fn stage_texts(&self) -> impl Iterator<Item = (EmitStage, &str)> + '_ {
+        self.stages.iter().map(|stage| {
+            (
+                *stage,
+                self.output.get(stage).map(String::as_str).unwrap_or(""),
+            )
+        })
+    }
+
+    /// Forget text recorded by a previous compile, keeping the request.
+    pub fn clear(&mut self) {
+        self.root = None;
+        self.output.clear();
+    }
+
+    fn wants(&self, stage: EmitStage) -> bool {
+        self.stages.contains(&stage)
+    }
+
+    fn selects(&self, name: &str) -> bool {
+        self.functions.is_empty() || self.functions.iter().any(|f| f == name || f == "*")
+    }
+
+    /// `// module: <path>` line, with the path relative to the project root
+    /// when the module lives under it.
+    fn module_header(&self, path: &Path) -> String {
+        let rel = self
+            .root
+            .as_deref()
+            .and_then(|root| path.strip_prefix(root).ok())
+            .unwrap_or(path);
+        format!("// module: {}\n", rel.display())
+    }
+
+    fn append(&mut self, stage: EmitStage, text: &str) {
+        let out = self.output.entry(stage).or_default();
+        if !out.is_empty() && !out.ends_with("\n\n") {
+            out.push('\n');
+        }
+        out.push_str(text);
+        if !out.ends_with('\n') {
+            out.push('\n');
+        }
+    }
+}
+
+thread_local! {
+    static ACTIVE_DUMPS: RefCell<Option<StageDumps>> = const { RefCell::new(None) };
+}
+
+/// Run `f` (a compile) while recording the stages `dumps` asks for.
+pub fn collect_stage_dumps<T>(dumps: &mut StageDumps, f: impl FnOnce() -> T) -> T {
+    let prev = ACTIVE_DUMPS.with(|slot| slot.replace(Some(std::mem::take(dumps))));
+    let out = f();
+    if let Some(active) = ACTIVE_DUMPS.with(|slot| slot.replace(prev)) {
+        *dumps = active;
+    }
+    out
+}
+
+pub(crate) fn stage_requested(stage: EmitStage) -> bool {
+    ACTIVE_DUMPS.with(|slot| slot.borrow().as_ref().is_some_and(|d| d.wants(stage)))
+}
+
+pub(crate) fn any_stage_requested() -> bool {
+    ACTIVE_DUMPS.with(|slot| slot.borrow().as_ref().is_some_and(|d| !d.is_empty()))
+}
+
+fn with_dumps(stage: EmitStage, f: impl FnOnce(&mut StageDumps)) {
+    ACTIVE_DUMPS.with(|slot| {
+        if let Some(dumps) = slot.borrow_mut().as_mut()
+            && dumps.wants(stage)
+        {
+            f(dumps);
+        }
+    });
+}
+
+/// Project root the current compile's module paths are shown relative to.
+pub(crate) fn set_source_root(root: PathBuf) {
+    ACTIVE_DUMPS.with(|slot| {
+        if let Some(dumps) = slot.borrow_mut().as_mut() {
+            dumps.root = Some(root);
+        }
+    });
+}
+
+/// Token stream of one source file, one token per line.
+pub(crate) fn record_tokens(path: &Path, source: &str) {
+    with_dumps(EmitStage::Tokens, |dumps| {
+        let mut text = dumps.module_header(path);
+        let mut lexer = Lexer::new(source);
+        loop {
+            let token = lexer.next_token();
+            if token.kind == TokenKind::Eof {
+                break;
+            }
+            let _ = writeln!(
+                text,
+                "{}:{}-{}:{} {:?}",
+                token.span.start_line,
+                token.span.start_col,
+                token.span.end_line,
+                token.span.end_col,
+                token.kind
+            );
+        }
+        dumps.append(EmitStage::Tokens, &text);
+    });
+}
+
+/// Parsed AST of one source file; the filter keeps only matching `fn`s.
+pub(crate) fn record_ast(path: &Path, program: &Program) {
+    with_dumps(EmitStage::Ast, |dumps| {
+        let mut text = dumps.module_header(path);
+        for stmt in &program.stmts {
+            let fn_name = match &stmt.kind {
+                StmtKind::FnDecl { name, .. } => Some(name.as_str()),
+                StmtKind::Export(decl) => Some(decl.name.as_str()),
+                _ => None,
+            };
+            let keep = match fn_name {
+                Some(name) => dumps.selects(name),
+                None => dumps.functions.is_empty(),
+            };
+            if keep {
+                text.push_str(&ast_text::stmt_text(stmt));
+            }
+        }
+        dumps.append(EmitStage::Ast, &text);
+    });
+}
+
+/// Desugared HIR of every module; the filter keeps only matching `fn`s.
+pub(crate) fn record_hir(modules: &[(PathBuf, &HirModule)], symbols: &FxHashMap<SymbolId, String>) {
+    with_dumps(EmitStage::Hir, |dumps| {
+        for (path, module) in modules {
+            let mut text = dumps.module_header(path);
+            let mut printer = hir_text::HirPrinter::new(symbols);
+            for item in &module.items {
+                let keep = match item {
+                    HirItem::Fn(f) => symbols.get(&f.name).is_some_and(|name| dumps.selects(name)),
+                    _ => dumps.functions.is_empty(),
+                };
+                if keep {
+                    text.push_str(&printer.item_text(item));
+                }
+            }
+            dumps.append(EmitStage::Hir, &text);
+        }
+    });
+}
+
+fn display_name(fn_ir: &FnIR) -> &str {
+    fn_ir.user_name.as_deref().unwrap_or(&fn_ir.name)
+}
+
+fn sorted_selected_fns<'a>(
+    dumps: &StageDumps,
+    all_fns: &'a FxHashMap<String, FnIR>,
+) -> Vec<&'a FnIR> {
+    let mut fns = all_fns
+        .values()
+        .filter(|fn_ir| dumps.selects(display_name(fn_ir)) || dumps.selects(&fn_ir.name))
+        .collect::<Vec<_>>();
+    fns.sort_by(|a, b| a.name.cmp(&b.name));
+    fns
+}
+
+/// Textual MIR (see [`crate::mir::text`]) before or after Tachyon.
+pub(crate) fn record_mir(stage: EmitStage, all_fns: &FxHashMap<String, FnIR>) {
+    with_dumps(stage, |dumps| {
+        let mut text = String::new();
+        for fn_ir in sorted_selected_fns(dumps, all_fns) {
+            let _ = writeln!(text, "// {}", display_name(fn_ir));
+            text.push_str(&crate::mir::text::print_fn(fn_ir));
+            text.push('\n');
+        }
+        dumps.append(stage, &text);
+    });
+}
+
+/// `None` for functions the compiler generated, such as `Sym_top_*`.
+fn typed_text(fn_ir: &FnIR) -> Option<String> {
+    let user_name = fn_ir.user_name.as_deref()?;
+    let ty = |ty, term: &TypeTerm| {
+        let mut text = crate::mir::text::type_text(ty);
+        if *term != TypeTerm::Any {
+            let _ = write!(text, " ({})", crate::mir::text::term_text(term));
+        }
+        text
+    };
+    let params = fn_ir
+        .params
+        .iter()
+        .enumerate()
+        .map(|(index, param)| {
+            let value = fn_ir
+                .values
+                .iter()
+                .find(|value| matches!(value.kind, ValueKind::Param { index: i } if i == index));
+            match value {
+                Some(value) => format!("{}: {}", param, ty(value.value_ty, &value.value_term)),
+                None => param.clone(),
+            }
+        })
+        .collect::<Vec<_>>();
+    let mut out = format!(
+        "fn {}({}) -> {} {{\n",
+        user_name,
+        params.join(", "),
+        ty(fn_ir.inferred_ret_ty, &fn_ir.inferred_ret_term)
+    );
+    let mut lines = Vec::new();
+    for block in &fn_ir.blocks {
+        for instr in &block.instrs {
+            // Lowering leaves most assign spans unset, and `.`-prefixed
+            // names are compiler temporaries; list user bindings in block order.
+            let Instr::Assign { dst, src, span } = instr else {
+                continue;
+            };
+            let Some(value) = fn_ir.values.get(*src) else {
+                continue;
+            };
+            if dst.starts_with('.') {
+                continue;
+            }
+            let span = if span.start_line > 0 {
+                *span
+            } else {
+                value.span
+            };
+            let pos = if span.start_line > 0 {
+                format!("{}:{} ", span.start_line, span.start_col)
+            } else {
+                String::new()
+            };
+            let line = format!(
+                "  {}{}: {}",
+                pos,
+                dst,
+                ty(value.value_ty, &value.value_term)
+            );
+            if !lines.contains(&line) {
+                lines.push(line);
+            }
+        }
+    }
+    for line in lines {
+        let _ = writeln!(out, "{line}");
+    }
+    out.push_str("}\n");
+    Some(out)
+}
+
+/// Inferred parameter, return and assignment types per user function.
+pub(crate) fn record_typed(all_fns: &FxHashMap<String, FnIR>) {
+    with_dumps(EmitStage::Typed, |dumps| {
+        let text = sorted_selected_fns(dumps, all_fns)
+            .into_iter()
+            .filter_map(typed_text)
+            .collect::<Vec<_>>()
+            .join("\n");
+        dumps.append(EmitStage::Typed, &text);
+    });
+}
+
+/// Whole-file R text (`raw-r` before peephole, `r` final output).
+pub(crate) fn record_r(stage: EmitStage, code: &str) {
+    with_dumps(stage, |dumps| {
+        dumps.output.insert(stage, code.to_string());
+    });
+}
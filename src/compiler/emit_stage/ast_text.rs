@@ -0,0 +1,505 @@
+//! `ast` stage: one node per line, children indented two spaces. Names are
+//! printed as written in the source, quoted.
+
+use crate::syntax::ast::*;
+use std::fmt::Write;
+
+pub(super) fn stmt_text(stmt: &Stmt) -> String {
+    let mut out = String::new();
+    stmt_into(&mut out, 0, stmt);
+    out
+}
+
+fn line(out: &mut String, depth: usize, text: &str) {
+    let _ = writeln!(out, "{:indent$}{}", "", text, indent = depth * 2);
+}
+
+fn type_text(ty: &TypeExpr) -> String {
+    match ty {
+        TypeExpr::Named(name) => name.clone(),
+        TypeExpr::Generic { base, args } => {
+            let args = args.iter().map(type_text).collect::<Vec<_>>();
+            format!("{}<{}>", base, args.join(", "))
+        }
+    }
+}
+
+fn type_suffix(ty: &Option<TypeExpr>) -> String {
+    ty.as_ref()
+        .map(|ty| format!(": {}", type_text(ty)))
+        .unwrap_or_default()
+}
+
+fn ret_suffix(ty: &Option<TypeExpr>) -> String {
+    ty.as_ref()
+        .map(|ty| format!(" -> {}", type_text(ty)))
+        .unwrap_or_default()
+}
+
+fn type_params_text(type_params: &[String]) -> String {
+    if type_params.is_empty() {
+        String::new()
+    } else {
+        format!("<{}>", type_params.join(", "))
+    }
+}
+
+fn where_text(bounds: &[TraitBound]) -> String {
+    if bounds.is_empty() {
+        return String::new();
+    }
+    let bounds = bounds
+        .iter()
+        .map(|b| format!("{}: {}", b.type_name, b.trait_names.join(" + ")))
+        .collect::<Vec<_>>();
+    format!(" where {}", bounds.join(", "))
+}
+
+fn lit_text(lit: &Lit) -> String {
+    match lit {
+        Lit::Int(v) => format!("{v}L"),
+        Lit::Float(v) => format!("{v:?}"),
+        Lit::Str(v) => format!("{v:?}"),
+        Lit::Bool(true) => "TRUE".to_string(),
+        Lit::Bool(false) => "FALSE".to_string(),
+        Lit::Null => "NULL".to_string(),
+        Lit::Na => "NA".to_string(),
+    }
+}
+
+fn pattern_text(pat: &Pattern) -> String {
+    match &pat.kind {
+        PatternKind::Wild => "_".to_string(),
+        PatternKind::Lit(lit) => lit_text(lit),
+        PatternKind::Bind(name) => format!("{name:?}"),
+        PatternKind::List { items, rest } => {
+            let mut items = items.iter().map(pattern_text).collect::<Vec<_>>();
+            if let Some(rest) = rest {
+                items.push(format!("..{rest:?}"));
+            }
+            format!("[{}]", items.join(", "))
+        }
+        PatternKind::Record { fields } => {
+            let fields = fields
+                .iter()
+                .map(|(name, pat)| format!("{name:?}: {}", pattern_text(pat)))
+                .collect::<Vec<_>>();
+            format!("{{{}}}", fields.join(", "))
+        }
+    }
+}
+
+fn params_text(params: &[FnParam]) -> String {
+    let params = params
+        .iter()
+        .map(|p| {
+            let default = if p.default.is_some() { " = .." } else { "" };
+            format!("{:?}{}{}", p.name, type_suffix(&p.ty_hint), default)
+        })
+        .collect::<Vec<_>>();
+    format!("({})", params.join(", "))
+}
+
+/// Parameter defaults, which the header only marks with `= ..`.
+fn param_defaults(out: &mut String, depth: usize, params: &[FnParam]) {
+    for param in params {
+        if let Some(default) = &param.default {
+            line(out, depth, &format!("Default({:?})", param.name));
+            expr_into(out, depth + 1, default);
+        }
+    }
+}
+
+fn block_into(out: &mut String, depth: usize, label: &str, block: &Block) {
+    line(out, depth, label);
+    for stmt in &block.stmts {
+        stmt_into(out, depth + 1, stmt);
+    }
+}
+
+fn fn_decl_into(out: &mut String, depth: usize, decl: &FnDecl) {
+    let export = if decl.public { ", export" } else { "" };
+    line(
+        out,
+        depth,
+        &format!(
+            "FnDecl({:?}{}){}{}{}{}",
+            decl.name,
+            export,
+            type_params_text(&decl.type_params),
+            params_text(&decl.params),
+            ret_suffix(&decl.ret_ty_hint),
+            where_text(&decl.where_bounds)
+        ),
+    );
+    param_defaults(out, depth + 1, &decl.params);
+    for stmt in &decl.body.stmts {
+        stmt_into(out, depth + 1, stmt);
+    }
+}
+
+fn stmt_into(out: &mut String, depth: usize, stmt: &Stmt) {
+    match &stmt.kind {
+        StmtKind::Let {
+            name,
+            ty_hint,
+            init,
+        } => {
+            line(
+                out,
+                depth,
+                &format!("Let({:?}{})", name, type_suffix(ty_hint)),
+            );
+            if let Some(init) = init {
+                expr_into(out, depth + 1, init);
+            }
+        }
+        StmtKind::Assign { target, value } => {
+            line(out, depth, "Assign");
+            lvalue_into(out, depth + 1, target);
+            expr_into(out, depth + 1, value);
+        }
+        StmtKind::FnDecl {
+            name,
+            type_params,
+            params,
+            ret_ty_hint,
+            where_bounds,
+            body,
+            ..
+        } => {
+            line(
+                out,
+                depth,
+                &format!(
+                    "FnDecl({:?}){}{}{}{}",
+                    name,
+                    type_params_text(type_params),
+                    params_text(params),
+                    ret_suffix(ret_ty_hint),
+                    where_text(where_bounds)
+                ),
+            );
+            param_defaults(out, depth + 1, params);
+            for stmt in &body.stmts {
+                stmt_into(out, depth + 1, stmt);
+            }
+        }
+        StmtKind::TraitDecl(decl) => {
+            let supertraits = if decl.supertraits.is_empty() {
+                String::new()
+            } else {
+                format!(": {}", decl.supertraits.join(" + "))
+            };
+            line(
+                out,
+                depth,
+                &format!(
+                    "TraitDecl({:?}){}{}{}",
+                    decl.name,
+                    type_params_text(&decl.type_params),
+                    supertraits,
+                    where_text(&decl.where_bounds)
+                ),
+            );
+            for assoc in &decl.assoc_types {
+                line(out, depth + 1, &format!("Type({:?})", assoc.name));
+            }
+            for assoc in &decl.assoc_consts {
+                line(
+                    out,
+                    depth + 1,
+                    &format!("Const({:?}: {})", assoc.name, type_text(&assoc.ty_hint)),
+                );
+                if let Some(default) = &assoc.default {
+                    expr_into(out, depth + 2, default);
+                }
+            }
+            for method in &decl.methods {
+                line(
+                    out,
+                    depth + 1,
+                    &format!(
+                        "Method({:?}){}{}{}",
+                        method.name,
+                        params_text(&method.params),
+                        ret_suffix(&method.ret_ty_hint),
+                        where_text(&method.where_bounds)
+                    ),
+                );
+                param_defaults(out, depth + 2, &method.params);
+                if let Some(body) = &method.default_body {
+                    for stmt in &body.stmts {
+                        stmt_into(out, depth + 2, stmt);
+                    }
+                }
+            }
+        }
+        StmtKind::ImplDecl(decl) => {
+            let negative = if decl.negative { "!" } else { "" };
+            let s3 = if decl.s3 { " as s3" } else { "" };
+            line(
+                out,
+                depth,
+                &format!(
+                    "ImplDecl({}{:?} for {}){}{}{}",
+                    negative,
+                    decl.trait_name,
+                    type_text(&decl.for_ty),
+                    type_params_text(&decl.type_params),
+                    s3,
+                    where_text(&decl.where_bounds)
+                ),
+            );
+            for assoc in &decl.assoc_types {
+                line(
+                    out,
+                    depth + 1,
+                    &format!("Type({:?} = {})", assoc.name, type_text(&assoc.ty)),
+                );
+            }
+            for assoc in &decl.assoc_consts {
+                line(
+                    out,
+                    depth + 1,
+                    &format!("Const({:?}: {})", assoc.name, type_text(&assoc.ty_hint)),
+                );
+                expr_into(out, depth + 2, &assoc.value);
+            }
+            for method in &decl.methods {
+                fn_decl_into(out, depth + 1, method);
+            }
+        }
+        StmtKind::If {
+            cond,
+            then_blk,
+            else_blk,
+        } => {
+            line(out, depth, "If");
+            expr_into(out, depth + 1, cond);
+            block_into(out, depth + 1, "Then", then_blk);
+            if let Some(else_blk) = else_blk {
+                block_into(out, depth + 1, "Else", else_blk);
+            }
+        }
+        StmtKind::While { cond, body } => {
+            line(out, depth, "While");
+            expr_into(out, depth + 1, cond);
+            block_into(out, depth + 1, "Body", body);
+        }
+        StmtKind::For {
+            var,
+            iter,
+            body,
+            parallel,
+        } => {
+            match parallel {
+                Some(clause) => {
+                    let reductions = clause
+                        .reductions
+                        .iter()
+                        .map(|r| format!("{}({:?})", r.op.as_str(), r.var))
+                        .collect::<Vec<_>>();
+                    line(
+                        out,
+                        depth,
+                        &format!("ParallelFor({:?}) reduce {}", var, reductions.join(", ")),
+                    );
+                }
+                None => line(out, depth, &format!("For({var:?})")),
+            }
+            expr_into(out, depth + 1, iter);
+            block_into(out, depth + 1, "Body", body);
+        }
+        StmtKind::Return { value } => {
+            line(out, depth, "Return");
+            if let Some(value) = value {
+                expr_into(out, depth + 1, value);
+            }
+        }
+        StmtKind::Break => line(out, depth, "Break"),
+        StmtKind::Next => line(out, depth, "Next"),
+        StmtKind::UnsafeRBlock { code, read_only } => {
+            let label = if *read_only {
+                "UnsafeR(read)"
+            } else {
+                "UnsafeR"
+            };
+            line(out, depth, label);
+            line(out, depth + 1, &format!("{code:?}"));
+        }
+        StmtKind::ExprStmt { expr } => {
+            line(out, depth, "ExprStmt");
+            expr_into(out, depth + 1, expr);
+        }
+        StmtKind::Expr(expr) => {
+            line(out, depth, "Expr");
+            expr_into(out, depth + 1, expr);
+        }
+        StmtKind::Import { source, path, spec } => {
+            let kind = match source {
+                ImportSource::Module => "Import",
+                ImportSource::RPackage => "ImportR",
+            };
+            let spec = match spec {
+                ImportSpec::Glob => "*".to_string(),
+                ImportSpec::Named(bindings) => {
+                    let bindings = bindings
+                        .iter()
+                        .map(|b| match &b.local {
+                            Some(local) => format!("{:?} as {:?}", b.imported, local),
+                            None => format!("{:?}", b.imported),
+                        })
+                        .collect::<Vec<_>>();
+                    format!("{{{}}}", bindings.join(", "))
+                }
+                ImportSpec::Namespace(alias) => format!("* as {alias:?}"),
+            };
+            line(out, depth, &format!("{kind}({path:?}) {spec}"));
+        }
+        StmtKind::Export(decl) => fn_decl_into(out, depth, decl),
+        StmtKind::ReExport { path, names } => {
+            let names = names
+                .iter()
+                .map(|name| format!("{name:?}"))
+                .collect::<Vec<_>>();
+            line(
+                out,
+                depth,
+                &format!("ReExport({:?}) {{{}}}", path, names.join(", ")),
+            );
+        }
+    }
+}
+
+fn lvalue_into(out: &mut String, depth: usize, lvalue: &LValue) {
+    match &lvalue.kind {
+        LValueKind::Name(name) => line(out, depth, &format!("Name({name:?})")),
+        LValueKind::Index { base, idx } => {
+            line(out, depth, "Index");
+            expr_into(out, depth + 1, base);
+            for idx in idx {
+                expr_into(out, depth + 1, idx);
+            }
+        }
+        LValueKind::Field { base, name } => {
+            line(out, depth, &format!("Field({name:?})"));
+            expr_into(out, depth + 1, base);
+        }
+    }
+}
+
+fn expr_into(out: &mut String, depth: usize, expr: &Expr) {
+    let child = depth + 1;
+    match &expr.kind {
+        ExprKind::Lit(lit) => line(out, depth, &format!("Lit({})", lit_text(lit))),
+        ExprKind::Name(name) => line(out, depth, &format!("Name({name:?})")),
+        ExprKind::Unary { op, rhs } => {
+            line(out, depth, &format!("Unary({op:?})"));
+            expr_into(out, child, rhs);
+        }
+        ExprKind::Formula { lhs, rhs } => {
+            line(out, depth, "Formula");
+            if let Some(lhs) = lhs {
+                expr_into(out, child, lhs);
+            }
+            expr_into(out, child, rhs);
+        }
+        ExprKind::Binary { op, lhs, rhs } => {
+            line(out, depth, &format!("Binary({op:?})"));
+            expr_into(out, child, lhs);
+            expr_into(out, child, rhs);
+        }
+        ExprKind::Range { a, b } => {
+            line(out, depth, "Range");
+            expr_into(out, child, a);
+            expr_into(out, child, b);
+        }
+        ExprKind::Lambda {
+            params,
+            ret_ty_hint,
+            body,
+        } => {
+            line(
+                out,
+                depth,
+                &format!("Lambda{}{}", params_text(params), ret_suffix(ret_ty_hint)),
+            );
+            param_defaults(out, child, params);
+            for stmt in &body.stmts {
+                stmt_into(out, child, stmt);
+            }
+        }
+        ExprKind::Call {
+            callee,
+            type_args,
+            args,
+        } => {
+            if type_args.is_empty() {
+                line(out, depth, "Call");
+            } else {
+                let type_args = type_args.iter().map(type_text).collect::<Vec<_>>();
+                line(out, depth, &format!("Call<{}>", type_args.join(", ")));
+            }
+            expr_into(out, child, callee);
+            for arg in args {
+                expr_into(out, child, arg);
+            }
+        }
+        ExprKind::NamedArg { name, value } => {
+            line(out, depth, &format!("NamedArg({name:?})"));
+            expr_into(out, child, value);
+        }
+        ExprKind::Index { base, idx } => {
+            line(out, depth, "Index");
+            expr_into(out, child, base);
+            for idx in idx {
+                expr_into(out, child, idx);
+            }
+        }
+        ExprKind::Field { base, name } => {
+            line(out, depth, &format!("Field({name:?})"));
+            expr_into(out, child, base);
+        }
+        ExprKind::VectorLit(items) => {
+            line(out, depth, "VectorLit");
+            for item in items {
+                expr_into(out, child, item);
+            }
+        }
+        ExprKind::RecordLit(fields) => {
+            line(out, depth, "RecordLit");
+            for (name, value) in fields {
+                line(out, child, &format!("Entry({name:?})"));
+                expr_into(out, child + 1, value);
+            }
+        }
+        ExprKind::Pipe { lhs, rhs_call } => {
+            line(out, depth, "Pipe");
+            expr_into(out, child, lhs);
+            expr_into(out, child, rhs_call);
+        }
+        ExprKind::Try { expr } => {
+            line(out, depth, "Try");
+            expr_into(out, child, expr);
+        }
+        ExprKind::Match { scrutinee, arms } => {
+            line(out, depth, "Match");
+            expr_into(out, child, scrutinee);
+            for arm in arms {
+                line(out, child, &format!("Arm({})", pattern_text(&arm.pat)));
+                if let Some(guard) = &arm.guard {
+                    line(out, child + 1, "Guard");
+                    expr_into(out, child + 2, guard);
+                }
+                expr_into(out, child + 1, &arm.body);
+            }
+        }
+        ExprKind::ColRef(name) => line(out, depth, &format!("ColRef({name:?})")),
+        ExprKind::Unquote(inner) => {
+            line(out, depth, "Unquote");
+            expr_into(out, child, inner);
+        }
+        ExprKind::Column(name) => line(out, depth, &format!("Column({name:?})")),
+    }
+}
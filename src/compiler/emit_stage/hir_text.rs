@@ -0,0 +1,652 @@
+//! `hir` stage: the same tree layout as `ast`, with symbols and locals
+//! resolved back to their names.
+
+use crate::hir::def::*;
+use rustc_hash::FxHashMap;
+use std::fmt::Write;
+
+pub(super) struct HirPrinter<'a> {
+    symbols: &'a FxHashMap<SymbolId, String>,
+    /// Names of the locals in scope: a function's `local_names`, plus the
+    /// `let`s seen so far (top-level code has no name table of its own).
+    locals: FxHashMap<LocalId, String>,
+    out: String,
+}
+
+impl<'a> HirPrinter<'a> {
+    pub(super) fn new(symbols: &'a FxHashMap<SymbolId, String>) -> Self {
+        Self {
+            symbols,
+            locals: FxHashMap::default(),
+            out: String::new(),
+        }
+    }
+
+    pub(super) fn item_text(&mut self, item: &HirItem) -> String {
+        self.item(0, item);
+        std::mem::take(&mut self.out)
+    }
+
+    fn line(&mut self, depth: usize, text: &str) {
+        let _ = writeln!(self.out, "{:indent$}{}", "", text, indent = depth * 2);
+    }
+
+    fn sym(&self, id: SymbolId) -> String {
+        self.symbols
+            .get(&id)
+            .cloned()
+            .unwrap_or_else(|| format!("Sym_{}", id.0))
+    }
+
+    fn local(&self, id: LocalId) -> String {
+        self.locals
+            .get(&id)
+            .cloned()
+            .unwrap_or_else(|| format!("%{}", id.0))
+    }
+
+    fn ty(&self, ty: &Ty) -> String {
+        let inner = |name: &str, ty: &Ty| format!("{}<{}>", name, self.ty(ty));
+        match ty {
+            Ty::Any => "any".to_string(),
+            Ty::Never => "never".to_string(),
+            Ty::Null => "null".to_string(),
+            Ty::Logical => "lgl".to_string(),
+            Ty::Int => "int".to_string(),
+            Ty::Double => "dbl".to_string(),
+            Ty::Char => "chr".to_string(),
+            Ty::Vector(ty) => inner("vector", ty),
+            Ty::Matrix(ty) => inner("matrix", ty),
+            Ty::List(ty) => inner("list", ty),
+            Ty::Box(ty) => inner("box", ty),
+            Ty::DataFrame(cols) => {
+                let cols = cols
+                    .iter()
+                    .map(|(name, ty)| format!("{}: {}", self.sym(*name), self.ty(ty)))
+                    .collect::<Vec<_>>();
+                format!("dataframe{{{}}}", cols.join(", "))
+            }
+            Ty::Option(ty) => inner("option", ty),
+            Ty::Result(ok, err) => format!("result<{}, {}>", self.ty(ok), self.ty(err)),
+            Ty::Union(tys) => tys
+                .iter()
+                .map(|ty| self.ty(ty))
+                .collect::<Vec<_>>()
+                .join(" | "),
+        }
+    }
+
+    fn type_ref(ty: &HirTypeRef) -> String {
+        match ty {
+            HirTypeRef::Named(name) => name.clone(),
+            HirTypeRef::Generic { base, args } => {
+                let args = args.iter().map(Self::type_ref).collect::<Vec<_>>();
+                format!("{}<{}>", base, args.join(", "))
+            }
+        }
+    }
+
+    fn where_text(&self, bounds: &[HirTraitBound]) -> String {
+        if bounds.is_empty() {
+            return String::new();
+        }
+        let bounds = bounds
+            .iter()
+            .map(|b| {
+                let traits = b
+                    .trait_names
+                    .iter()
+                    .map(|t| self.sym(*t))
+                    .collect::<Vec<_>>();
+                format!("{}: {}", b.type_name, traits.join(" + "))
+            })
+            .collect::<Vec<_>>();
+        format!(" where {}", bounds.join(", "))
+    }
+
+    fn lit(lit: &HirLit) -> String {
+        match lit {
+            HirLit::Int(v) => format!("{v}L"),
+            HirLit::Double(v) => format!("{v:?}"),
+            HirLit::Char(v) => format!("{v:?}"),
+            HirLit::Bool(true) => "TRUE".to_string(),
+            HirLit::Bool(false) => "FALSE".to_string(),
+            HirLit::NA => "NA".to_string(),
+            HirLit::Null => "NULL".to_string(),
+        }
+    }
+
+    fn item(&mut self, depth: usize, item: &HirItem) {
+        match item {
+            HirItem::Import(import) => {
+                let spec = match &import.spec {
+                    HirImportSpec::Glob => "*".to_string(),
+                    HirImportSpec::Names(names) => {
+                        let names = names.iter().map(|n| self.sym(*n)).collect::<Vec<_>>();
+                        format!("{{{}}}", names.join(", "))
+                    }
+                    HirImportSpec::Namespace { alias, names } => {
+                        let names = names.iter().map(|n| self.sym(*n)).collect::<Vec<_>>();
+                        format!("* as {} {{{}}}", alias, names.join(", "))
+                    }
+                };
+                self.line(depth, &format!("Import({:?}) {}", import.module, spec));
+            }
+            HirItem::Fn(f) => self.function(depth, f),
+            HirItem::Trait(t) => {
+                let supertraits = if t.supertraits.is_empty() {
+                    String::new()
+                } else {
+                    let names = t
+                        .supertraits
+                        .iter()
+                        .map(|s| self.sym(*s))
+                        .collect::<Vec<_>>();
+                    format!(": {}", names.join(" + "))
+                };
+                let public = if t.public { ", pub" } else { "" };
+                let type_params = angle(&t.type_params);
+                let header = format!(
+                    "Trait({}{}){}{}{}",
+                    self.sym(t.name),
+                    public,
+                    type_params,
+                    supertraits,
+                    self.where_text(&t.where_bounds)
+                );
+                self.line(depth, &header);
+                for assoc in &t.assoc_types {
+                    let text = format!("Type({})", self.sym(assoc.name));
+                    self.line(depth + 1, &text);
+                }
+                for assoc in &t.assoc_consts {
+                    let text = format!(
+                        "Const({}: {})",
+                        self.sym(assoc.name),
+                        Self::type_ref(&assoc.ty)
+                    );
+                    self.line(depth + 1, &text);
+                }
+                for method in &t.methods {
+                    let params = method
+                        .params
+                        .iter()
+                        .map(|p| match &p.ty {
+                            Some(ty) => format!("{}: {}", self.sym(p.name), Self::type_ref(ty)),
+                            None => self.sym(p.name),
+                        })
+                        .collect::<Vec<_>>();
+                    let ret = method
+                        .ret_ty
+                        .as_ref()
+                        .map(|ty| format!(" -> {}", Self::type_ref(ty)))
+                        .unwrap_or_default();
+                    let text = format!(
+                        "Method({})({}){}{}",
+                        self.sym(method.name),
+                        params.join(", "),
+                        ret,
+                        self.where_text(&method.where_bounds)
+                    );
+                    self.line(depth + 1, &text);
+                }
+            }
+            HirItem::Impl(i) => {
+                let negative = if i.negative { "!" } else { "" };
+                let public = if i.public { ", pub" } else { "" };
+                let s3 = if i.s3 { " as s3" } else { "" };
+                let header = format!(
+                    "Impl({}{} for {}{}){}{}{}",
+                    negative,
+                    self.sym(i.trait_name),
+                    Self::type_ref(&i.for_ty),
+                    public,
+                    angle(&i.type_params),
+                    s3,
+                    self.where_text(&i.where_bounds)
+                );
+                self.line(depth, &header);
+                for assoc in &i.assoc_types {
+                    let text = format!(
+                        "Type({} = {})",
+                        self.sym(assoc.name),
+                        Self::type_ref(&assoc.ty)
+                    );
+                    self.line(depth + 1, &text);
+                }
+                for assoc in &i.assoc_consts {
+                    let text = format!(
+                        "Const({}: {} = {})",
+                        self.sym(assoc.name),
+                        Self::type_ref(&assoc.ty),
+                        self.sym(assoc.value_fn)
+                    );
+                    self.line(depth + 1, &text);
+                }
+                for method in &i.methods {
+                    let text = format!(
+                        "Method({} = {})",
+                        self.sym(method.trait_method),
+                        self.sym(method.impl_fn)
+                    );
+                    self.line(depth + 1, &text);
+                }
+            }
+            HirItem::Export(name) => {
+                let text = format!("Export({})", self.sym(*name));
+                self.line(depth, &text);
+            }
+            HirItem::TypeAlias { name, ty, .. } => {
+                let text = format!("TypeAlias({} = {})", self.sym(*name), self.ty(ty));
+                self.line(depth, &text);
+            }
+            HirItem::Stmt(stmt) => self.stmt(depth, stmt),
+        }
+    }
+
+    fn function(&mut self, depth: usize, f: &HirFn) {
+        let saved = std::mem::replace(&mut self.locals, f.local_names.clone());
+        let mut params = f
+            .params
+            .iter()
+            .map(|p| {
+                let mut text = self.sym(p.name);
+                if let Some(ty) = &p.ty {
+                    let _ = write!(text, ": {}", self.ty(ty));
+                }
+                if p.default.is_some() {
+                    text.push_str(" = ..");
+                }
+                text
+            })
+            .collect::<Vec<_>>();
+        if f.has_varargs {
+            params.push("...".to_string());
+        }
+        let mut flags = String::new();
+        if f.public {
+            flags.push_str(", pub");
+        }
+        match f.attrs.inline_hint {
+            InlineHint::Default => {}
+            InlineHint::Always => flags.push_str(", inline"),
+            InlineHint::Never => flags.push_str(", noinline"),
+        }
+        if f.attrs.tidy_safe {
+            flags.push_str(", tidy");
+        }
+        let ret = f
+            .ret_ty
+            .as_ref()
+            .map(|ty| format!(" -> {}", self.ty(ty)))
+            .unwrap_or_default();
+        let header = format!(
+            "Fn({}{}){}({}){}{}",
+            self.sym(f.name),
+            flags,
+            angle(&f.type_params),
+            params.join(", "),
+            ret,
+            self.where_text(&f.where_bounds)
+        );
+        self.line(depth, &header);
+        for param in &f.params {
+            if let Some(default) = &param.default {
+                let text = format!("Default({})", self.sym(param.name));
+                self.line(depth + 1, &text);
+                self.expr(depth + 2, default);
+            }
+        }
+        for stmt in &f.body.stmts {
+            self.stmt(depth + 1, stmt);
+        }
+        self.locals = saved;
+    }
+
+    fn block(&mut self, depth: usize, label: &str, block: &HirBlock) {
+        self.line(depth, label);
+        for stmt in &block.stmts {
+            self.stmt(depth + 1, stmt);
+        }
+    }
+
+    fn stmt(&mut self, depth: usize, stmt: &HirStmt) {
+        match stmt {
+            HirStmt::Let {
+                local,
+                name,
+                ty,
+                init,
+                ..
+            } => {
+                let name = self.sym(*name);
+                self.locals.entry(*local).or_insert_with(|| name.clone());
+                let ty = ty
+                    .as_ref()
+                    .map(|ty| format!(": {}", self.ty(ty)))
+                    .unwrap_or_default();
+                self.line(depth, &format!("Let({name}{ty})"));
+                if let Some(init) = init {
+                    self.expr(depth + 1, init);
+                }
+            }
+            HirStmt::Assign { target, value, .. } => {
+                self.line(depth, "Assign");
+                self.lvalue(depth + 1, target);
+                self.expr(depth + 1, value);
+            }
+            HirStmt::If {
+                cond,
+                then_blk,
+                else_blk,
+                ..
+            } => {
+                self.line(depth, "If");
+                self.expr(depth + 1, cond);
+                self.block(depth + 1, "Then", then_blk);
+                if let Some(else_blk) = else_blk {
+                    self.block(depth + 1, "Else", else_blk);
+                }
+            }
+            HirStmt::While { cond, body, .. } => {
+                self.line(depth, "While");
+                self.expr(depth + 1, cond);
+                self.block(depth + 1, "Body", body);
+            }
+            HirStmt::For { iter, body, .. } => {
+                match iter {
+                    HirForIter::Range {
+                        var,
+                        start,
+                        end,
+                        inclusive,
+                    } => {
+                        let op = if *inclusive { "..=" } else { ".." };
+                        let text = format!("For({} in {})", self.local(*var), op);
+                        self.line(depth, &text);
+                        self.expr(depth + 1, start);
+                        self.expr(depth + 1, end);
+                    }
+                    HirForIter::SeqLen { var, len } => {
+                        let text = format!("For({} in seq_len)", self.local(*var));
+                        self.line(depth, &text);
+                        self.expr(depth + 1, len);
+                    }
+                    HirForIter::SeqAlong { var, xs } => {
+                        let text = format!("For({} in seq_along)", self.local(*var));
+                        self.line(depth, &text);
+                        self.expr(depth + 1, xs);
+                    }
+                }
+                self.block(depth + 1, "Body", body);
+            }
+            HirStmt::Return { value, .. } => {
+                self.line(depth, "Return");
+                if let Some(value) = value {
+                    self.expr(depth + 1, value);
+                }
+            }
+            HirStmt::Break { .. } => self.line(depth, "Break"),
+            HirStmt::Next { .. } => self.line(depth, "Next"),
+            HirStmt::UnsafeRBlock {
+                code, read_only, ..
+            } => {
+                let label = if *read_only {
+                    "UnsafeR(read)"
+                } else {
+                    "UnsafeR"
+                };
+                self.line(depth, label);
+                self.line(depth + 1, &format!("{code:?}"));
+            }
+            HirStmt::Expr { expr, .. } => {
+                self.line(depth, "Expr");
+                self.expr(depth + 1, expr);
+            }
+        }
+    }
+
+    fn lvalue(&mut self, depth: usize, lvalue: &HirLValue) {
+        match lvalue {
+            HirLValue::Local(id) => {
+                let text = format!("Local({})", self.local(*id));
+                self.line(depth, &text);
+            }
+            HirLValue::Index { base, index } => {
+                self.line(depth, "Index");
+                self.expr(depth + 1, base);
+                for index in index {
+                    self.expr(depth + 1, index);
+                }
+            }
+            HirLValue::Field { base, name } => {
+                let text = format!("Field({})", self.sym(*name));
+                self.line(depth, &text);
+                self.expr(depth + 1, base);
+            }
+        }
+    }
+
+    fn pat(&self, pat: &HirPat) -> String {
+        match pat {
+            HirPat::Wild => "_".to_string(),
+            HirPat::Lit(lit) => Self::lit(lit),
+            HirPat::Bind { name, .. } => self.sym(*name),
+            HirPat::Or(pats) => pats
+                .iter()
+                .map(|p| self.pat(p))
+                .collect::<Vec<_>>()
+                .join(" | "),
+            HirPat::List { items, rest } => {
+                let mut items = items.iter().map(|p| self.pat(p)).collect::<Vec<_>>();
+                if let Some((name, _)) = rest {
+                    items.push(format!("..{}", self.sym(*name)));
+                }
+                format!("[{}]", items.join(", "))
+            }
+            HirPat::Record { fields } => {
+                let fields = fields
+                    .iter()
+                    .map(|(name, pat)| format!("{}: {}", self.sym(*name), self.pat(pat)))
+                    .collect::<Vec<_>>();
+                format!("{{{}}}", fields.join(", "))
+            }
+        }
+    }
+
+    /// Pattern bindings become locals of the arm.
+    fn bind_pat(&mut self, pat: &HirPat) {
+        match pat {
+            HirPat::Bind { name, local } => {
+                let name = self.sym(*name);
+                self.locals.entry(*local).or_insert(name);
+            }
+            HirPat::Or(pats) | HirPat::List { items: pats, .. } => {
+                for pat in pats {
+                    self.bind_pat(pat);
+                }
+                if let HirPat::List {
+                    rest: Some((name, local)),
+                    ..
+                } = pat
+                {
+                    let name = self.sym(*name);
+                    self.locals.entry(*local).or_insert(name);
+                }
+            }
+            HirPat::Record { fields } => {
+                for (_, pat) in fields {
+                    self.bind_pat(pat);
+                }
+            }
+            HirPat::Wild | HirPat::Lit(_) => {}
+        }
+    }
+
+    fn wrapped(&mut self, depth: usize, label: &str, inner: &HirExpr) {
+        self.line(depth, label);
+        self.expr(depth + 1, inner);
+    }
+
+    fn expr(&mut self, depth: usize, expr: &HirExpr) {
+        let child = depth + 1;
+        match expr {
+            HirExpr::Local(id) => {
+                let text = format!("Local({})", self.local(*id));
+                self.line(depth, &text);
+            }
+            HirExpr::Global(id, _) => {
+                let text = format!("Global({})", self.sym(*id));
+                self.line(depth, &text);
+            }
+            HirExpr::Lit(lit) => self.line(depth, &format!("Lit({})", Self::lit(lit))),
+            HirExpr::Call(call) => {
+                self.line(depth, "Call");
+                self.expr(child, &call.callee);
+                for arg in &call.args {
+                    self.arg(child, arg);
+                }
+            }
+            HirExpr::TidyCall(call) => {
+                self.line(depth, &format!("TidyCall({:?})", call.verb));
+                self.expr(child, &call.input);
+                for arg in &call.args {
+                    match arg {
+                        TidyArg::Expr(expr) => self.tidy(child, expr),
+                        TidyArg::Named(name, expr) => {
+                            let text = format!("NamedArg({})", self.sym(*name));
+                            self.line(child, &text);
+                            self.tidy(child + 1, expr);
+                        }
+                    }
+                }
+            }
+            HirExpr::Index { base, index } => {
+                self.line(depth, "Index");
+                self.expr(child, base);
+                for index in index {
+                    self.expr(child, index);
+                }
+            }
+            HirExpr::Field { base, name } => {
+                let text = format!("Field({})", self.sym(*name));
+                self.line(depth, &text);
+                self.expr(child, base);
+            }
+            HirExpr::Block(block) => self.block(depth, "Block", block),
+            HirExpr::IfExpr {
+                cond,
+                then_expr,
+                else_expr,
+            } => {
+                self.line(depth, "IfExpr");
+                self.expr(child, cond);
+                self.expr(child, then_expr);
+                self.expr(child, else_expr);
+            }
+            HirExpr::Match { scrut, arms } => {
+                self.line(depth, "Match");
+                self.expr(child, scrut);
+                for arm in arms {
+                    self.bind_pat(&arm.pat);
+                    let text = format!("Arm({})", self.pat(&arm.pat));
+                    self.line(child, &text);
+                    if let Some(guard) = &arm.guard {
+                        self.line(child + 1, "Guard");
+                        self.expr(child + 2, guard);
+                    }
+                    self.expr(child + 1, &arm.body);
+                }
+            }
+            HirExpr::Some(inner) => self.wrapped(depth, "Some", inner),
+            HirExpr::None => self.line(depth, "None"),
+            HirExpr::Ok(inner) => self.wrapped(depth, "Ok", inner),
+            HirExpr::Err(inner) => self.wrapped(depth, "Err", inner),
+            HirExpr::Try(inner) => self.wrapped(depth, "Try", inner),
+            HirExpr::Unary { op, expr } => {
+                self.line(depth, &format!("Unary({op:?})"));
+                self.expr(child, expr);
+            }
+            HirExpr::Binary { op, lhs, rhs, .. } => {
+                self.line(depth, &format!("Binary({op:?})"));
+                self.expr(child, lhs);
+                self.expr(child, rhs);
+            }
+            HirExpr::ListLit(fields) => {
+                self.line(depth, "ListLit");
+                for (name, value) in fields {
+                    let text = format!("Entry({})", self.sym(*name));
+                    self.line(child, &text);
+                    self.expr(child + 1, value);
+                }
+            }
+            HirExpr::VectorLit(items) => {
+                self.line(depth, "VectorLit");
+                for item in items {
+                    self.expr(child, item);
+                }
+            }
+            HirExpr::Range { start, end } => {
+                self.line(depth, "Range");
+                self.expr(child, start);
+                self.expr(child, end);
+            }
+            HirExpr::Unquote(inner) => self.wrapped(depth, "Unquote", inner),
+            HirExpr::Column(name) => self.line(depth, &format!("Column({name:?})")),
+        }
+    }
+
+    fn arg(&mut self, depth: usize, arg: &HirArg) {
+        match arg {
+            HirArg::Pos(expr) => self.expr(depth, expr),
+            HirArg::Named { name, value } => {
+                let text = format!("NamedArg({})", self.sym(*name));
+                self.line(depth, &text);
+                self.expr(depth + 1, value);
+            }
+        }
+    }
+
+    fn tidy(&mut self, depth: usize, expr: &TidyExpr) {
+        let child = depth + 1;
+        match expr {
+            TidyExpr::Lit(lit) => self.line(depth, &format!("Lit({})", Self::lit(lit))),
+            TidyExpr::Ident(name) => {
+                let text = format!("Global({})", self.sym(*name));
+                self.line(depth, &text);
+            }
+            TidyExpr::Col(name) => {
+                let text = format!("Col({})", self.sym(*name));
+                self.line(depth, &text);
+            }
+            TidyExpr::Env(local) => {
+                let text = format!("Env({})", self.local(*local));
+                self.line(depth, &text);
+            }
+            TidyExpr::Unary { op, expr } => {
+                self.line(depth, &format!("Unary({op:?})"));
+                self.tidy(child, expr);
+            }
+            TidyExpr::Binary { op, lhs, rhs } => {
+                self.line(depth, &format!("Binary({op:?})"));
+                self.tidy(child, lhs);
+                self.tidy(child, rhs);
+            }
+            TidyExpr::Call { callee, args } => {
+                self.line(depth, "Call");
+                self.tidy(child, callee);
+                for arg in args {
+                    self.tidy(child, arg);
+                }
+            }
+        }
+    }
+}
+
+fn angle(type_params: &[String]) -> String {
+    if type_params.is_empty() {
+        String::new()
+    } else {
+        format!("<{}>", type_params.join(", "))
+    }
+}
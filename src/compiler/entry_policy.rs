@@ -1,5 +1,5 @@
 use crate::error::{RRCode, RRException, Stage};
-use crate::syntax::ast::{Expr, ExprKind, Stmt, StmtKind};
+use crate::syntax::ast::{Expr, ExprKind, FnDecl, Stmt, StmtKind, TypeExpr};
 use crate::syntax::parse::Parser;
 use std::path::Path;
 
@@ -61,6 +61,7 @@ fn stmt_contains_plain_main_call(stmt: &Stmt) -> bool {
         | StmtKind::TraitDecl(_)
         | StmtKind::ImplDecl(_)
         | StmtKind::Export(_)
+        | StmtKind::ReExport { .. }
         | StmtKind::Import { .. } => false,
         StmtKind::If {
             cond,
@@ -87,24 +88,94 @@ fn stmt_contains_plain_main_call(stmt: &Stmt) -> bool {
     }
 }
 
-fn source_defines_main_function(source: &str) -> Result<(bool, bool), RRException> {
+/// How the synthesized entry call invokes `main`.
+struct MainEntry {
+    /// `fn main(args: vector<str>)` receives the trailing command arguments.
+    takes_args: bool,
+    /// `fn main() -> int` sets the process exit status.
+    returns_status: bool,
+}
+
+/// Only checked when RR synthesizes the call; a file that calls `main`
+/// itself may give it any parameters.
+fn main_entry(decl: &FnDecl) -> Result<MainEntry, RRException> {
+    if decl.params.len() > 1 {
+        return Err(RRException::new(
+            "RR.SemanticError",
+            RRCode::E1002,
+            Stage::Parse,
+            format!(
+                "fn main() takes at most one parameter, found {}",
+                decl.params.len()
+            ),
+        )
+        .at(decl.params[1].span)
+        .help("declare `fn main(args: vector<str>)` to receive the program arguments"));
+    }
+    let returns_status = matches!(
+        &decl.ret_ty_hint,
+        Some(TypeExpr::Named(name)) if matches!(name.as_str(), "int" | "integer" | "i32" | "i64" | "isize")
+    );
+    Ok(MainEntry {
+        takes_args: !decl.params.is_empty(),
+        returns_status,
+    })
+}
+
+fn source_defines_main_function(source: &str) -> Result<(Option<FnDecl>, bool), RRException> {
     let mut parser = Parser::new(source);
     let program = parser.parse_program()?;
-    let has_main_fn = program.stmts.iter().any(|stmt| match &stmt.kind {
-        StmtKind::FnDecl { name, .. } => name == "main",
-        StmtKind::Export(fndecl) => fndecl.name == "main",
-        _ => false,
+    let main_decl = program.stmts.iter().find_map(|stmt| match &stmt.kind {
+        StmtKind::FnDecl {
+            name,
+            type_params,
+            params,
+            ret_ty_hint,
+            where_bounds,
+            body,
+            doc,
+        } if name == "main" => Some(FnDecl {
+            name: name.clone(),
+            type_params: type_params.clone(),
+            params: params.clone(),
+            ret_ty_hint: ret_ty_hint.clone(),
+            where_bounds: where_bounds.clone(),
+            body: body.clone(),
+            public: false,
+            doc: doc.clone(),
+        }),
+        StmtKind::Export(fndecl) if fndecl.name == "main" => Some(fndecl.clone()),
+        _ => None,
     });
     let has_top_level_main_call = program.stmts.iter().any(stmt_contains_plain_main_call);
-    Ok((has_main_fn, has_top_level_main_call))
+    Ok((main_decl, has_top_level_main_call))
 }
 
-fn source_with_main_call_appended(source: &str) -> String {
+/// Append the call of `main`. Arguments come from `commandArgs()` and an
+/// `int` result becomes the exit status through `quit()`.
+fn source_with_main_call_appended(source: &str, entry: &MainEntry) -> String {
     let mut patched = source.to_string();
     if !patched.ends_with('\n') {
         patched.push('\n');
     }
-    patched.push_str("\nmain()\n");
+    patched.push('\n');
+    if entry.takes_args || entry.returns_status {
+        patched.push_str("import r * as rr_entry_base from \"base\"\n");
+    }
+    let call = if entry.takes_args {
+        "main(rr_entry_base.commandArgs(trailingOnly = TRUE))"
+    } else {
+        "main()"
+    };
+    if entry.returns_status {
+        patched.push_str(&format!(
+            "rr_entry_base.quit(save = \"no\", status = {})\n",
+            call
+        ));
+    } else {
+        patched.push_str(call);
+        patched.push('\n');
+    }
     patched
 }
 
@@ -113,8 +184,8 @@ pub fn prepare_project_entry_source(
     source: &str,
     command: &str,
 ) -> Result<String, RRException> {
-    let (has_main_fn, has_top_level_main_call) = source_defines_main_function(source)?;
-    if !has_main_fn {
+    let (main_decl, has_top_level_main_call) = source_defines_main_function(source)?;
+    let Some(main_decl) = main_decl else {
         return Err(RRException::new(
             "RR.SemanticError",
             RRCode::E1001,
@@ -128,18 +199,24 @@ pub fn prepare_project_entry_source(
             "add `fn main() {{ ... }}` to the entry file before running `RR {}`",
             command
         )));
-    }
+    };
     if has_top_level_main_call {
         return Ok(source.to_string());
     }
 
-    Ok(source_with_main_call_appended(source))
+    Ok(source_with_main_call_appended(
+        source,
+        &main_entry(&main_decl)?,
+    ))
 }
 
 pub fn prepare_single_file_build_source(source: &str) -> Result<String, RRException> {
-    let (has_main_fn, has_top_level_main_call) = source_defines_main_function(source)?;
-    if has_main_fn && !has_top_level_main_call {
-        return Ok(source_with_main_call_appended(source));
+    let (main_decl, has_top_level_main_call) = source_defines_main_function(source)?;
+    match main_decl {
+        Some(main_decl) if !has_top_level_main_call => Ok(source_with_main_call_appended(
+            source,
+            &main_entry(&main_decl)?,
+        )),
+        _ => Ok(source.to_string()),
     }
-    Ok(source.to_string())
 }
@@ -0,0 +1,1295 @@
+//! R-to-RR migration for `RR import-r`.
+//!
+//! Ordinary R is parsed into a small statement tree and printed back as RR:
+//! first bindings get `let`, top-level functions that only touch other
+//! functions become `fn` items, `library(pkg)` and `pkg::fn` become `import r`
+//! forms, and unqualified calls into a modeled R package become named imports.
+//! A statement the translator cannot express in RR is copied verbatim into an
+//! `unsafe r { }` block and listed in the report; names it assigns are
+//! declared with `let name = NULL` first so later RR code can still use them.
+
+use crate::compiler::CompilerParallelConfig;
+use crate::compiler::incremental::{
+    IncrementalCompileRequest, IncrementalOptions, compile_incremental_request,
+};
+use crate::compiler::pipeline::{
+    CompileOutputOptions, OptLevel, default_parallel_config, default_type_config, with_quiet_log,
+};
+use crate::error::RR;
+use crate::mir::semantics::call_model::{
+    builtin_arity, is_dynamic_fallback_builtin, is_modeled_package_export, is_tidy_helper_call,
+};
+use crate::syntax::r_subset::{
+    Arg, Comment, Expr, Stmt, StmtKind, Tok, lex, parse_program, unsupported_operator,
+};
+use rustc_hash::FxHashSet;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// R packages searched, in order, for names not attached with `library()`.
+const DEFAULT_SEARCH_PATH: &[&str] =
+    &["stats", "graphics", "grDevices", "utils", "methods", "base"];
+
+/// Words the RR lexer reserves that are ordinary names in R.
+const RR_KEYWORDS: &[&str] = &[
+    "fn", "let", "true", "false", "null", "na", "match", "import", "export", "trait", "impl",
+    "where",
+];
+
+/// One statement `RR import-r` could not translate.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ImportRNote {
+    /// 1-based line of the statement in the R source.
+    pub line: u32,
+    pub reason: String,
+}
+
+#[derive(Clone, Debug)]
+pub struct ImportROutput {
+    pub source: String,
+    /// Statements wrapped in `unsafe r { }`, in source order.
+    pub notes: Vec<ImportRNote>,
+}
+
+/// Translate R `source` to RR.
+pub fn translate_r(source: &str) -> ImportROutput {
+    let (stmts, comments) = parse_program(source);
+    Translator::new(source, comments).run(&stmts)
+}
+
+/// Compile a translated program with the default strict pipeline.
+pub fn check_import_r(entry_path: &str, source: &str) -> RR<()> {
+    with_quiet_log(|| {
+        compile_incremental_request(IncrementalCompileRequest {
+            entry_path,
+            entry_input: source,
+            opt_level: OptLevel::O1,
+            type_cfg: default_type_config(),
+            parallel_cfg: default_parallel_config(),
+            compiler_parallel_cfg: CompilerParallelConfig::default(),
+            options: IncrementalOptions::disabled(),
+            output_options: CompileOutputOptions::default(),
+            session: None,
+            profile: None,
+        })
+    })?;
+    Ok(())
+}
+
+// ----- Translation -----
+
+#[derive(Clone, Default)]
+struct Imports {
+    /// `pkg::name` namespaces in use, by package.
+    namespaces: BTreeSet<String>,
+    /// Named imports by package: local name -> R name.
+    named: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+#[derive(Clone, Default)]
+struct Scope {
+    declared: FxHashSet<String>,
+    /// Names first assigned inside a nested block or raw statement. They are
+    /// declared with `let name = NULL` at the top of the scope.
+    hoisted: Vec<String>,
+}
+
+struct Translator<'a> {
+    src: &'a str,
+    comments: Vec<Comment>,
+    next_comment: usize,
+    imports: Imports,
+    attached: Vec<String>,
+    /// Names assigned anywhere at the top level.
+    globals: FxHashSet<String>,
+    /// Top-level functions emitted as `fn` items.
+    fn_items: FxHashSet<String>,
+    scopes: Vec<Scope>,
+    notes: Vec<ImportRNote>,
+}
+
+type TResult<T> = Result<T, String>;
+
+impl<'a> Translator<'a> {
+    fn new(src: &'a str, comments: Vec<Comment>) -> Self {
+        Self {
+            src,
+            comments,
+            next_comment: 0,
+            imports: Imports::default(),
+            attached: Vec::new(),
+            globals: FxHashSet::default(),
+            fn_items: FxHashSet::default(),
+            scopes: vec![Scope::default()],
+            notes: Vec::new(),
+        }
+    }
+
+    fn run(mut self, stmts: &[Stmt]) -> ImportROutput {
+        for stmt in stmts {
+            collect_attached(stmt, &mut self.attached);
+        }
+        let mut top_assigns: BTreeMap<String, usize> = BTreeMap::new();
+        for stmt in stmts {
+            for name in assigned_names(stmt, self.src) {
+                *top_assigns.entry(name).or_default() += 1;
+            }
+        }
+        self.globals = top_assigns.keys().cloned().collect();
+        let candidates: FxHashSet<String> = stmts
+            .iter()
+            .filter_map(|stmt| match &stmt.kind {
+                StmtKind::Expr(Expr::Assign(target, value)) => match (&**target, &**value) {
+                    (Expr::Ident(name), Expr::Function(..))
+                        if top_assigns.get(name) == Some(&1) && rr_name_ok(name) =>
+                    {
+                        Some(name.clone())
+                    }
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect();
+        // A function may only become an `fn` item when it reads no top-level
+        // variable: `fn` bodies cannot see top-level `let` bindings.
+        for stmt in stmts {
+            if let StmtKind::Expr(Expr::Assign(target, value)) = &stmt.kind
+                && let (Expr::Ident(name), Expr::Function(params, body)) = (&**target, &**value)
+                && candidates.contains(name)
+            {
+                let mut locals: FxHashSet<String> = params.iter().map(|(p, _)| p.clone()).collect();
+                collect_expr_assigns(body, &mut locals);
+                let mut reads = FxHashSet::default();
+                collect_reads(body, &mut reads);
+                let reads_global = reads.iter().any(|read| {
+                    !locals.contains(read)
+                        && self.globals.contains(read)
+                        && !candidates.contains(read)
+                });
+                if !reads_global {
+                    self.fn_items.insert(name.clone());
+                }
+            }
+        }
+
+        let mut header = Vec::new();
+        while let Some(comment) = self.comments.get(self.next_comment) {
+            let first_code = stmts.first().map_or(u32::MAX, |stmt| stmt.line);
+            if comment.line >= first_code {
+                break;
+            }
+            header.push(format!("//{}", comment_text(&comment.text)));
+            self.next_comment += 1;
+        }
+        let last_line = stmts.last().map_or(0, |stmt| stmt.end_line);
+        let body = self.emit_block(stmts, 0, true, false, last_line.saturating_add(1));
+        let scope = self.scopes.pop().unwrap_or_default();
+
+        let mut out = String::new();
+        for line in &header {
+            out.push_str(line);
+            out.push('\n');
+        }
+        if !header.is_empty() {
+            out.push('\n');
+        }
+        let imports = self.import_lines();
+        for line in &imports {
+            out.push_str(line);
+            out.push('\n');
+        }
+        if !imports.is_empty() {
+            out.push('\n');
+        }
+        for name in &scope.hoisted {
+            out.push_str(&format!("let {} = NULL\n", name));
+        }
+        for line in body {
+            out.push_str(&line);
+            out.push('\n');
+        }
+        ImportROutput {
+            source: out,
+            notes: self.notes,
+        }
+    }
+
+    fn import_lines(&self) -> Vec<String> {
+        let mut packages: Vec<&str> = self.attached.iter().map(String::as_str).collect();
+        for pkg in self
+            .imports
+            .named
+            .keys()
+            .chain(self.imports.namespaces.iter())
+        {
+            if !packages.contains(&pkg.as_str()) {
+                packages.push(pkg);
+            }
+        }
+        let mut lines = Vec::new();
+        for pkg in packages {
+            let named = self.imports.named.get(pkg);
+            if let Some(named) = named {
+                let list = named
+                    .iter()
+                    .map(|(local, name)| {
+                        if local == name {
+                            name.clone()
+                        } else {
+                            format!("{} as {}", name, local)
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                lines.push(format!("import r {{ {} }} from \"{}\"", list, pkg));
+            }
+            if self.imports.namespaces.contains(pkg) || named.is_none() {
+                let alias = namespace_alias(pkg);
+                if alias == pkg {
+                    lines.push(format!("import r \"{}\"", pkg));
+                } else {
+                    lines.push(format!("import r * as {} from \"{}\"", alias, pkg));
+                }
+            }
+        }
+        lines
+    }
+
+    // ----- statements -----
+
+    /// Emit a statement list. `top` marks a function body or the file, where
+    /// first assignments become `let`; `tail` marks a list whose last
+    /// statement produces a function's value. `end_line` is the line of the
+    /// closing brace, used to place comments before it.
+    fn emit_block(
+        &mut self,
+        stmts: &[Stmt],
+        indent: usize,
+        top: bool,
+        tail: bool,
+        end_line: u32,
+    ) -> Vec<String> {
+        let mut lines: Vec<String> = Vec::new();
+        let mut last_line = None;
+        for (index, stmt) in stmts.iter().enumerate() {
+            self.flush_comments(stmt.line, indent, &mut lines, &mut last_line);
+            if let Some(prev) = last_line
+                && stmt.line > prev + 1
+                && !lines.is_empty()
+            {
+                lines.push(String::new());
+            }
+            let is_tail = tail && index + 1 == stmts.len();
+            let emitted = self.emit_stmt_or_raw(stmt, indent, top, is_tail);
+            lines.extend(emitted);
+            last_line = Some(stmt.end_line);
+            self.attach_trailing_comment(stmt.end_line, &mut lines);
+        }
+        self.flush_comments(end_line, indent, &mut lines, &mut last_line);
+        lines
+    }
+
+    fn flush_comments(
+        &mut self,
+        before_line: u32,
+        indent: usize,
+        lines: &mut Vec<String>,
+        last_line: &mut Option<u32>,
+    ) {
+        while let Some(comment) = self.comments.get(self.next_comment) {
+            if comment.line >= before_line {
+                break;
+            }
+            if let Some(prev) = *last_line
+                && comment.line > prev + 1
+                && !lines.is_empty()
+            {
+                lines.push(String::new());
+            }
+            lines.push(format!("{}//{}", pad(indent), comment_text(&comment.text)));
+            *last_line = Some(comment.line);
+            self.next_comment += 1;
+        }
+    }
+
+    /// Append a comment that trails the statement ending on `line`, and drop
+    /// comments inside the statement that no nested block emitted.
+    fn attach_trailing_comment(&mut self, line: u32, lines: &mut [String]) {
+        while let Some(comment) = self.comments.get(self.next_comment) {
+            if comment.line > line {
+                break;
+            }
+            if comment.line == line
+                && comment.trailing
+                && let Some(last) = lines.last_mut()
+            {
+                last.push_str(&format!(" //{}", comment_text(&comment.text)));
+            }
+            self.next_comment += 1;
+        }
+    }
+
+    fn emit_stmt_or_raw(
+        &mut self,
+        stmt: &Stmt,
+        indent: usize,
+        top: bool,
+        tail: bool,
+    ) -> Vec<String> {
+        let reason = match &stmt.kind {
+            StmtKind::Expr(expr) => {
+                let imports = self.imports.clone();
+                let scopes = self.scopes.clone();
+                let next_comment = self.next_comment;
+                let notes = self.notes.len();
+                match self.emit_stmt(expr, indent, tail, top) {
+                    Ok(lines) => return lines,
+                    Err(reason) => {
+                        self.imports = imports;
+                        self.scopes = scopes;
+                        self.next_comment = next_comment;
+                        self.notes.truncate(notes);
+                        reason
+                    }
+                }
+            }
+            StmtKind::Raw(reason) => reason.clone(),
+        };
+        self.emit_raw(stmt, indent, reason)
+    }
+
+    fn emit_raw(&mut self, stmt: &Stmt, indent: usize, reason: String) -> Vec<String> {
+        for name in assigned_names(stmt, self.src) {
+            self.declare_nested(&name);
+        }
+        self.notes.push(ImportRNote {
+            line: stmt.line,
+            reason: reason.clone(),
+        });
+        let text = &self.src[stmt.start..stmt.end];
+        let column = self.src[..stmt.start]
+            .rfind('\n')
+            .map_or(stmt.start, |nl| stmt.start - nl - 1);
+        let mut lines = vec![
+            format!("{}// import-r: {}", pad(indent), reason),
+            format!("{}unsafe r {{", pad(indent)),
+        ];
+        for (i, line) in text.lines().enumerate() {
+            let body = if i == 0 {
+                line
+            } else {
+                let strip = line.len() - line.trim_start_matches([' ', '\t']).len();
+                &line[strip.min(column)..]
+            };
+            if body.trim().is_empty() {
+                lines.push(String::new());
+            } else {
+                lines.push(format!("{}{}", pad(indent + 2), body.trim_end()));
+            }
+        }
+        lines.push(format!("{}}}", pad(indent)));
+        lines
+    }
+
+    /// Emit one statement. `top` is true for statements directly in a
+    /// function body or at file level, where a first assignment becomes `let`.
+    fn emit_stmt(
+        &mut self,
+        expr: &Expr,
+        indent: usize,
+        tail: bool,
+        top: bool,
+    ) -> TResult<Vec<String>> {
+        let p = pad(indent);
+        match expr {
+            Expr::Assign(target, value) => self.emit_assign(target, value, indent, top),
+            Expr::If(cond, then, els) => {
+                let cond = self.emit_expr(cond, indent)?;
+                let mut lines = vec![format!("{}if ({}) {{", p, cond)];
+                lines.extend(self.emit_branch(then, indent + 2, tail)?);
+                let mut els = els.as_deref();
+                while let Some(branch) = els {
+                    if let Expr::If(cond, then, next) = branch {
+                        let cond = self.emit_expr(cond, indent)?;
+                        lines.push(format!("{}}} else if ({}) {{", p, cond));
+                        lines.extend(self.emit_branch(then, indent + 2, tail)?);
+                        els = next.as_deref();
+                    } else {
+                        lines.push(format!("{}}} else {{", p));
+                        lines.extend(self.emit_branch(branch, indent + 2, tail)?);
+                        els = None;
+                    }
+                }
+                lines.push(format!("{}}}", p));
+                Ok(lines)
+            }
+            Expr::For(var, seq, body) => {
+                if !rr_name_ok(var) {
+                    return Err(format!("loop variable `{}` is not an RR name", var));
+                }
+                let seq = self.emit_expr(seq, indent)?;
+                // `for` binds its variable itself.
+                self.scope().declared.insert(var.clone());
+                let mut lines = vec![format!("{}for ({} in {}) {{", p, var, seq)];
+                lines.extend(self.emit_branch(body, indent + 2, false)?);
+                lines.push(format!("{}}}", p));
+                Ok(lines)
+            }
+            Expr::While(cond, body) => {
+                let cond = self.emit_expr(cond, indent)?;
+                let mut lines = vec![format!("{}while ({}) {{", p, cond)];
+                lines.extend(self.emit_branch(body, indent + 2, false)?);
+                lines.push(format!("{}}}", p));
+                Ok(lines)
+            }
+            Expr::Repeat(body) => {
+                let mut lines = vec![format!("{}while (TRUE) {{", p)];
+                lines.extend(self.emit_branch(body, indent + 2, false)?);
+                lines.push(format!("{}}}", p));
+                Ok(lines)
+            }
+            Expr::Break => Ok(vec![format!("{}break", p)]),
+            Expr::Next => Ok(vec![format!("{}next", p)]),
+            Expr::Call(callee, args) if matches!(&**callee, Expr::Ident(name) if name == "return") => {
+                match args.as_slice() {
+                    [] => Ok(vec![format!("{}return NULL", p)]),
+                    [
+                        Arg {
+                            name: None,
+                            value: Some(value),
+                        },
+                    ] => Ok(vec![format!(
+                        "{}return {}",
+                        p,
+                        self.emit_expr(value, indent)?
+                    )]),
+                    _ => Err("`return` with several arguments".to_string()),
+                }
+            }
+            _ if library_package(expr).is_some() => Ok(Vec::new()),
+            Expr::Block(..) => Err("bare `{ }` block".to_string()),
+            _ => {
+                let value = self.emit_expr(expr, indent)?;
+                if tail && !top {
+                    Ok(vec![format!("{}return {}", p, value)])
+                } else {
+                    Ok(vec![format!("{}{}", p, value)])
+                }
+            }
+        }
+    }
+
+    /// Emit an `if`/loop branch body. Branches of a function's final `if`
+    /// return their last value explicitly.
+    fn emit_branch(&mut self, body: &Expr, indent: usize, tail: bool) -> TResult<Vec<String>> {
+        match body {
+            Expr::Block(stmts, end_line) => {
+                let lines = self.emit_block(stmts, indent, false, tail, *end_line);
+                Ok(lines)
+            }
+            other => self.emit_stmt(other, indent, tail, false),
+        }
+    }
+
+    fn emit_assign(
+        &mut self,
+        target: &Expr,
+        value: &Expr,
+        indent: usize,
+        top: bool,
+    ) -> TResult<Vec<String>> {
+        let p = pad(indent);
+        match target {
+            Expr::Ident(name) => {
+                if !rr_name_ok(name) {
+                    return Err(format!("`{}` is not an RR name", name));
+                }
+                if matches!(value, Expr::Assign(..)) {
+                    return Err("chained assignment".to_string());
+                }
+                if indent == 0
+                    && self.fn_items.contains(name)
+                    && let Expr::Function(params, body) = value
+                {
+                    return self.emit_fn_item(name, params, body);
+                }
+                // `y <- if (c) a else b` assigns `y` in each branch instead.
+                if let Expr::If(..) = value
+                    && let Some(branches) = assign_in_branches(name, value)
+                {
+                    let mut lines = Vec::new();
+                    if !self.declared_here(name) {
+                        if top {
+                            self.scope().declared.insert(name.clone());
+                            lines.push(format!("{}let {} = NULL", p, name));
+                        } else {
+                            self.declare_nested(name);
+                        }
+                    }
+                    lines.extend(self.emit_stmt(&branches, indent, false, false)?);
+                    return Ok(lines);
+                }
+                let value = self.emit_expr(value, indent)?;
+                if self.declared_here(name) {
+                    Ok(vec![format!("{}{} <- {}", p, name, value)])
+                } else if top {
+                    self.scope().declared.insert(name.clone());
+                    Ok(vec![format!("{}let {} <- {}", p, name, value)])
+                } else {
+                    self.declare_nested(name);
+                    Ok(vec![format!("{}{} <- {}", p, name, value)])
+                }
+            }
+            Expr::Index { .. } | Expr::Dollar(..) => {
+                let mut base = target;
+                while let Expr::Index { base: inner, .. } | Expr::Dollar(inner, _) = base {
+                    base = inner;
+                }
+                match base {
+                    Expr::Ident(name) if self.is_visible(name) => {}
+                    _ => return Err("assignment into an unknown object".to_string()),
+                }
+                let target = self.emit_expr(target, indent)?;
+                let value = self.emit_expr(value, indent)?;
+                Ok(vec![format!("{}{} <- {}", p, target, value)])
+            }
+            Expr::Call(callee, _) => Err(format!(
+                "replacement call `{}(...) <-`",
+                match &**callee {
+                    Expr::Ident(name) => name.as_str(),
+                    _ => "f",
+                }
+            )),
+            _ => Err("assignment to an expression".to_string()),
+        }
+    }
+
+    fn emit_fn_item(
+        &mut self,
+        name: &str,
+        params: &[(String, Option<Expr>)],
+        body: &Expr,
+    ) -> TResult<Vec<String>> {
+        let params = self.emit_params(params, 0)?;
+        self.scopes.push(Scope {
+            declared: params.1,
+            hoisted: Vec::new(),
+        });
+        let body = self.emit_fn_body(body, 2);
+        let scope = self.scopes.pop().unwrap_or_default();
+        let mut lines = vec![format!("fn {}({}) {{", name, params.0)];
+        lines.extend(hoisted_lets(&scope, 2));
+        lines.extend(body?);
+        lines.push("}".to_string());
+        Ok(lines)
+    }
+
+    fn emit_fn_body(&mut self, body: &Expr, indent: usize) -> TResult<Vec<String>> {
+        match body {
+            Expr::Block(stmts, end_line) => {
+                Ok(self.emit_block(stmts, indent, true, true, *end_line))
+            }
+            other => self.emit_stmt(other, indent, true, true),
+        }
+    }
+
+    fn emit_params(
+        &mut self,
+        params: &[(String, Option<Expr>)],
+        indent: usize,
+    ) -> TResult<(String, FxHashSet<String>)> {
+        let mut out = Vec::new();
+        let mut declared = FxHashSet::default();
+        for (name, default) in params {
+            if name == "..." {
+                return Err("`...` parameters".to_string());
+            }
+            if !rr_name_ok(name) {
+                return Err(format!("parameter `{}` is not an RR name", name));
+            }
+            match default {
+                Some(default) => {
+                    out.push(format!("{} = {}", name, self.emit_expr(default, indent)?))
+                }
+                None => out.push(name.clone()),
+            }
+            declared.insert(name.clone());
+        }
+        Ok((out.join(", "), declared))
+    }
+
+    // ----- scopes -----
+
+    fn scope(&mut self) -> &mut Scope {
+        if self.scopes.is_empty() {
+            self.scopes.push(Scope::default());
+        }
+        let last = self.scopes.len() - 1;
+        &mut self.scopes[last]
+    }
+
+    fn declared_here(&self, name: &str) -> bool {
+        self.scopes
+            .last()
+            .is_some_and(|scope| scope.declared.contains(name))
+    }
+
+    /// Declare `name` in the current function scope on its first assignment
+    /// inside a nested block, hoisting a `let` to the top of the scope.
+    fn declare_nested(&mut self, name: &str) {
+        if !self.declared_here(name) && rr_name_ok(name) {
+            let scope = self.scope();
+            scope.declared.insert(name.to_string());
+            scope.hoisted.push(name.to_string());
+        }
+    }
+
+    fn is_visible(&self, name: &str) -> bool {
+        self.scopes
+            .iter()
+            .any(|scope| scope.declared.contains(name))
+            || self.globals.contains(name)
+    }
+
+    // ----- expressions -----
+
+    fn emit_expr(&mut self, expr: &Expr, indent: usize) -> TResult<String> {
+        match expr {
+            Expr::Num(text) => rr_number(text, false),
+            Expr::Str(text) => Ok(text.clone()),
+            Expr::Ident(name) => self.resolve_value(name),
+            Expr::Ns(pkg, name) => self.namespaced(pkg, name),
+            Expr::NsInternal(pkg, _) => Err(format!("internal namespace access {}:::", pkg)),
+            Expr::Call(callee, args) => {
+                let callee = match &**callee {
+                    Expr::Ident(name) => match name.as_str() {
+                        "return" => return Err("`return` used as a value".to_string()),
+                        "library" | "require" => {
+                            return Err(format!("`{}` outside statement position", name));
+                        }
+                        _ => self.resolve_call(name, args.len())?,
+                    },
+                    Expr::Ns(pkg, name) => self.namespaced(pkg, name)?,
+                    _ => return Err("call of a computed function".to_string()),
+                };
+                let args = self.emit_args(args, indent, false)?;
+                Ok(format!("{}({})", callee, args))
+            }
+            Expr::Index { base, args, double } => {
+                let base_text = self.emit_operand(base, PREC_CALL, false, indent)?;
+                if args.iter().any(|arg| arg.name.is_some()) {
+                    return Err("named index argument".to_string());
+                }
+                let args = self.emit_args(args, indent, true)?;
+                if *double {
+                    Ok(format!("{}[[{}]]", base_text, args))
+                } else {
+                    Ok(format!("{}[{}]", base_text, args))
+                }
+            }
+            Expr::Dollar(base, field) => {
+                if !rr_name_ok(field) || field.contains('.') {
+                    return Err(format!("field `${}` is not an RR name", field));
+                }
+                let base = self.emit_operand(base, PREC_CALL, false, indent)?;
+                Ok(format!("{}.{}", base, field))
+            }
+            Expr::Slot(..) => Err(unsupported_operator("@")),
+            Expr::Unary(op, _) if op == "~" => Err(unsupported_operator(op)),
+            Expr::Unary(op, operand) => {
+                let operand = self.emit_operand(operand, PREC_PREFIX, false, indent)?;
+                Ok(format!("{}{}", op, operand))
+            }
+            Expr::Binary(op, lhs, rhs) => self.emit_binary(op, lhs, rhs, indent),
+            Expr::Paren(inner) => Ok(format!("({})", self.emit_expr(inner, indent)?)),
+            Expr::Function(params, body) => self.emit_lambda(params, body, indent),
+            Expr::If(..) => Err("`if` used as a value".to_string()),
+            Expr::For(..) | Expr::While(..) | Expr::Repeat(..) => {
+                Err("loop used as a value".to_string())
+            }
+            Expr::Block(..) => Err("`{ }` block used as a value".to_string()),
+            Expr::Assign(..) => Err("assignment used as a value".to_string()),
+            Expr::SuperAssign(..) => Err(unsupported_operator("<<-")),
+            Expr::Break | Expr::Next => Err("`break`/`next` used as a value".to_string()),
+        }
+    }
+
+    fn emit_args(&mut self, args: &[Arg], indent: usize, index: bool) -> TResult<String> {
+        let mut out = Vec::with_capacity(args.len());
+        for arg in args {
+            let Some(value) = &arg.value else {
+                return Err("empty argument".to_string());
+            };
+            if index && matches!(value, Expr::Unary(op, _) if op == "-") {
+                return Err("negative index".to_string());
+            }
+            let value = match int_literal(value).filter(|_| index) {
+                Some(literal) => literal?,
+                None => self.emit_expr(value, indent)?,
+            };
+            match &arg.name {
+                Some(name) if rr_name_ok(name) => out.push(format!("{} = {}", name, value)),
+                Some(name) => return Err(format!("argument name `{}` is not an RR name", name)),
+                None => out.push(value),
+            }
+        }
+        Ok(out.join(", "))
+    }
+
+    fn emit_binary(&mut self, op: &str, lhs: &Expr, rhs: &Expr, indent: usize) -> TResult<String> {
+        match op {
+            "^" | "**" => {
+                let times = match rhs {
+                    Expr::Num(text) if text == "2" || text == "2L" => 2,
+                    Expr::Num(text) if text == "3" || text == "3L" => 3,
+                    _ => return Err("`^` with an exponent other than 2 or 3".to_string()),
+                };
+                if !is_pure(lhs) {
+                    return Err("`^` on an expression with calls".to_string());
+                }
+                let base = self.emit_operand(lhs, PREC_PRODUCT, true, indent)?;
+                Ok(vec![base; times].join(" * "))
+            }
+            "%in%" => {
+                let callee = self.resolve_call("is.element", 2)?;
+                let lhs = self.emit_expr(lhs, indent)?;
+                let rhs = self.emit_expr(rhs, indent)?;
+                Ok(format!("{}({}, {})", callee, lhs, rhs))
+            }
+            ":" => {
+                let lhs = self.emit_range_end(lhs, indent)?;
+                let rhs = self.emit_range_end(rhs, indent)?;
+                Ok(format!("{}..{}", lhs, rhs))
+            }
+            _ => {
+                let rr_op = match op {
+                    "%%" => "%",
+                    "%*%" => "%*%",
+                    "+" | "-" | "*" | "/" | "<" | ">" | "<=" | ">=" | "==" | "!=" | "&&" | "||"
+                    | "&" | "|" | "|>" => op,
+                    _ => return Err(unsupported_operator(op)),
+                };
+                let prec = rr_binary_prec(op);
+                let lhs = self.emit_operand(lhs, prec, false, indent)?;
+                let rhs = self.emit_operand(rhs, prec, true, indent)?;
+                Ok(format!("{} {} {}", lhs, rr_op, rhs))
+            }
+        }
+    }
+
+    fn emit_range_end(&mut self, expr: &Expr, indent: usize) -> TResult<String> {
+        match int_literal(expr) {
+            Some(literal) => literal,
+            None => self.emit_operand(expr, PREC_RANGE, true, indent),
+        }
+    }
+
+    /// Emit `expr` as an operand of an RR operator binding at `parent`,
+    /// parenthesizing it where RR precedence would regroup it.
+    fn emit_operand(
+        &mut self,
+        expr: &Expr,
+        parent: u8,
+        right: bool,
+        indent: usize,
+    ) -> TResult<String> {
+        let text = self.emit_expr(expr, indent)?;
+        let prec = rr_expr_prec(expr);
+        if prec < parent || (right && prec == parent) {
+            Ok(format!("({})", text))
+        } else {
+            Ok(text)
+        }
+    }
+
+    fn emit_lambda(
+        &mut self,
+        params: &[(String, Option<Expr>)],
+        body: &Expr,
+        indent: usize,
+    ) -> TResult<String> {
+        let (params, declared) = self.emit_params(params, indent)?;
+        self.scopes.push(Scope {
+            declared,
+            hoisted: Vec::new(),
+        });
+        let simple = !matches!(body, Expr::Block(..));
+        let body_lines = if simple {
+            self.emit_expr(body, indent).map(|value| vec![value])
+        } else {
+            self.emit_fn_body(body, indent + 2)
+        };
+        let scope = self.scopes.pop().unwrap_or_default();
+        let body_lines = body_lines?;
+        if simple {
+            return Ok(format!("function({}) {{ {} }}", params, body_lines[0]));
+        }
+        let mut lines = vec![format!("function({}) {{", params)];
+        lines.extend(hoisted_lets(&scope, indent + 2));
+        lines.extend(body_lines);
+        lines.push(format!("{}}}", pad(indent)));
+        Ok(lines.join("\n"))
+    }
+
+    // ----- names -----
+
+    fn resolve_value(&mut self, name: &str) -> TResult<String> {
+        if self.is_visible(name) {
+            return if rr_name_ok(name) {
+                Ok(name.to_string())
+            } else {
+                Err(format!("`{}` is not an RR name", name))
+            };
+        }
+        match name {
+            "TRUE" | "FALSE" | "NULL" | "NA" => return Ok(name.to_string()),
+            "T" => return Ok("TRUE".to_string()),
+            "F" => return Ok("FALSE".to_string()),
+            "Inf" | "NaN" | "NA_integer_" | "NA_real_" | "NA_character_" => {
+                return Err(format!("R constant `{}` has no RR spelling", name));
+            }
+            _ => {}
+        }
+        // Builtins are only callable by name; pass them as values through an
+        // aliased import.
+        self.resolve_package(name, builtin_arity(name).is_some())
+    }
+
+    fn resolve_call(&mut self, name: &str, argc: usize) -> TResult<String> {
+        if self.is_visible(name) {
+            return if rr_name_ok(name) {
+                Ok(name.to_string())
+            } else {
+                Err(format!("`{}` is not an RR name", name))
+            };
+        }
+        let keyword = RR_KEYWORDS.contains(&name);
+        let arity = builtin_arity(name);
+        let builtin_fits =
+            arity.is_some_and(|(min, max)| argc >= min && max.is_none_or(|max| argc <= max));
+        if !keyword
+            && (builtin_fits || is_dynamic_fallback_builtin(name) || is_tidy_helper_call(name))
+        {
+            return Ok(name.to_string());
+        }
+        // A call the builtin's arity rejects goes to the R function instead.
+        self.resolve_package(name, keyword || arity.is_some())
+    }
+
+    /// Find `name` in an attached or default R package and record a named
+    /// import for it.
+    fn resolve_package(&mut self, name: &str, alias: bool) -> TResult<String> {
+        let attached = self.attached.iter().rev().map(String::as_str);
+        let search: Vec<&str> = attached
+            .chain(DEFAULT_SEARCH_PATH.iter().copied())
+            .collect();
+        let Some(pkg) = search
+            .into_iter()
+            .find(|pkg| is_modeled_package_export(pkg, name))
+            .map(str::to_string)
+        else {
+            let unmodeled: Vec<&str> = self
+                .attached
+                .iter()
+                .filter(|pkg| !DEFAULT_SEARCH_PATH.contains(&pkg.as_str()))
+                .map(String::as_str)
+                .collect();
+            return Err(if unmodeled.is_empty() {
+                format!("unknown name `{}`", name)
+            } else {
+                format!(
+                    "unknown name `{}` (it may come from {})",
+                    name,
+                    unmodeled.join(", ")
+                )
+            });
+        };
+        if !rr_name_ok(name) && !RR_KEYWORDS.contains(&name) {
+            return Err(format!("`{}` is not an RR name", name));
+        }
+        let local = if alias || RR_KEYWORDS.contains(&name) {
+            format!("{}_{}", namespace_alias(&pkg), name.replace('.', "_"))
+        } else {
+            name.to_string()
+        };
+        self.imports
+            .named
+            .entry(pkg)
+            .or_default()
+            .insert(local.clone(), name.to_string());
+        Ok(local)
+    }
+
+    fn namespaced(&mut self, pkg: &str, name: &str) -> TResult<String> {
+        if !rr_name_ok(pkg) && !pkg.contains('.') {
+            return Err(format!("package `{}` is not an RR name", pkg));
+        }
+        if !name.split('.').all(|part| {
+            part.chars()
+                .next()
+                .is_some_and(|c| c.is_alphabetic() || c == '_')
+        }) {
+            return Err(format!("`{}::{}` is not an RR name", pkg, name));
+        }
+        self.imports.namespaces.insert(pkg.to_string());
+        Ok(format!("{}.{}", namespace_alias(pkg), name))
+    }
+}
+
+// ----- helpers -----
+
+const PREC_PIPE: u8 = 2;
+const PREC_OR: u8 = 3;
+const PREC_AND: u8 = 4;
+const PREC_EQUALITY: u8 = 5;
+const PREC_COMPARISON: u8 = 6;
+const PREC_RANGE: u8 = 7;
+const PREC_SUM: u8 = 8;
+const PREC_PRODUCT: u8 = 9;
+const PREC_PREFIX: u8 = 10;
+const PREC_CALL: u8 = 11;
+
+/// Binding strength of an R binary operator once printed as RR.
+fn rr_binary_prec(op: &str) -> u8 {
+    match op {
+        "|>" => PREC_PIPE,
+        "||" | "|" => PREC_OR,
+        "&&" | "&" => PREC_AND,
+        "==" | "!=" => PREC_EQUALITY,
+        "<" | ">" | "<=" | ">=" => PREC_COMPARISON,
+        ":" => PREC_RANGE,
+        "+" | "-" => PREC_SUM,
+        "*" | "/" | "%%" | "%*%" | "^" | "**" => PREC_PRODUCT,
+        // `%in%` prints as a call.
+        _ => PREC_CALL,
+    }
+}
+
+fn rr_expr_prec(expr: &Expr) -> u8 {
+    match expr {
+        Expr::Binary(op, ..) => rr_binary_prec(op),
+        Expr::Unary(..) => PREC_PREFIX,
+        Expr::Function(..) | Expr::Assign(..) => 0,
+        _ => PREC_CALL,
+    }
+}
+
+/// Spell an R number as RR. R numbers are doubles unless suffixed `L`; in
+/// index and range positions whole numbers are written as integers.
+fn rr_number(text: &str, int_context: bool) -> TResult<String> {
+    if let Some(digits) = text.strip_suffix('L') {
+        if digits.chars().all(|c| c.is_ascii_digit()) {
+            return Ok(text.to_string());
+        }
+        return Err(format!("number literal `{}`", text));
+    }
+    if text.starts_with("0x") || text.starts_with("0X") || text.ends_with('i') {
+        return Err(format!("number literal `{}`", text));
+    }
+    let value: f64 = text
+        .parse()
+        .map_err(|_| format!("number literal `{}`", text))?;
+    if !value.is_finite() {
+        return Err(format!("number literal `{}`", text));
+    }
+    if int_context && value.fract() == 0.0 && value.abs() < 1e9 {
+        return Ok(format!("{}L", value as i64));
+    }
+    let out = format!("{}", value);
+    if out.contains('.') {
+        Ok(out)
+    } else {
+        Ok(format!("{}.0", out))
+    }
+}
+
+/// A number literal, possibly negated, spelled as an RR integer.
+fn int_literal(expr: &Expr) -> Option<TResult<String>> {
+    match expr {
+        Expr::Num(text) => Some(rr_number(text, true)),
+        Expr::Unary(op, inner) if op == "-" => match &**inner {
+            Expr::Num(text) => Some(rr_number(text, true).map(|n| format!("-{}", n))),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Whether an R name can be written unchanged in RR: dot-separated parts
+/// that each start like an RR identifier and are not RR keywords.
+fn rr_name_ok(name: &str) -> bool {
+    !RR_KEYWORDS.contains(&name)
+        && name.split('.').all(|part| {
+            part.chars()
+                .next()
+                .is_some_and(|c| c.is_alphabetic() || c == '_')
+                && !matches!(
+                    part,
+                    "if" | "else"
+                        | "for"
+                        | "while"
+                        | "in"
+                        | "return"
+                        | "break"
+                        | "next"
+                        | "function"
+                )
+                && !RR_KEYWORDS.contains(&part)
+        })
+}
+
+fn namespace_alias(pkg: &str) -> String {
+    pkg.replace('.', "_")
+}
+
+fn pad(indent: usize) -> String {
+    " ".repeat(indent)
+}
+
+fn comment_text(text: &str) -> String {
+    if text.is_empty() || text.starts_with(' ') {
+        text.to_string()
+    } else {
+        format!(" {}", text)
+    }
+}
+
+fn hoisted_lets(scope: &Scope, indent: usize) -> Vec<String> {
+    scope
+        .hoisted
+        .iter()
+        .map(|name| format!("{}let {} = NULL", pad(indent), name))
+        .collect()
+}
+
+/// Whether an expression can be evaluated twice without changing behavior.
+fn is_pure(expr: &Expr) -> bool {
+    match expr {
+        Expr::Num(_) | Expr::Str(_) | Expr::Ident(_) => true,
+        Expr::Paren(inner) | Expr::Unary(_, inner) | Expr::Dollar(inner, _) => is_pure(inner),
+        Expr::Binary(_, lhs, rhs) => is_pure(lhs) && is_pure(rhs),
+        Expr::Index { base, args, .. } => {
+            is_pure(base)
+                && args
+                    .iter()
+                    .all(|arg| arg.value.as_ref().is_some_and(is_pure))
+        }
+        _ => false,
+    }
+}
+
+/// Rewrite an `if`/`else` value so that each branch assigns its last value to
+/// `name`. `None` when some branch has no value.
+fn assign_in_branches(name: &str, value: &Expr) -> Option<Expr> {
+    match value {
+        Expr::If(cond, then, Some(els)) => Some(Expr::If(
+            cond.clone(),
+            Box::new(assign_in_branches(name, then)?),
+            Some(Box::new(assign_in_branches(name, els)?)),
+        )),
+        Expr::If(_, _, None) => None,
+        Expr::Block(stmts, end_line) => {
+            let mut stmts = stmts.clone();
+            let last = stmts.last_mut()?;
+            let StmtKind::Expr(expr) = &last.kind else {
+                return None;
+            };
+            last.kind = StmtKind::Expr(assign_in_branches(name, expr)?);
+            Some(Expr::Block(stmts, *end_line))
+        }
+        Expr::Paren(inner) if matches!(&**inner, Expr::If(..)) => assign_in_branches(name, inner),
+        Expr::For(..) | Expr::While(..) | Expr::Repeat(..) | Expr::Assign(..) => None,
+        Expr::Break | Expr::Next => None,
+        _ => Some(Expr::Assign(
+            Box::new(Expr::Ident(name.to_string())),
+            Box::new(value.clone()),
+        )),
+    }
+}
+
+/// Package attached by a `library(pkg)` or `require(pkg)` statement.
+fn library_package(expr: &Expr) -> Option<String> {
+    let Expr::Call(callee, args) = expr else {
+        return None;
+    };
+    let Expr::Ident(name) = &**callee else {
+        return None;
+    };
+    match name.as_str() {
+        "library" | "require" => match args.first()?.value.as_ref()? {
+            Expr::Ident(pkg) if args.len() == 1 => Some(pkg.clone()),
+            Expr::Str(pkg) if args.len() == 1 => Some(pkg.trim_matches('"').to_string()),
+            _ => None,
+        },
+        "suppressPackageStartupMessages" | "suppressMessages" if args.len() == 1 => {
+            library_package(args[0].value.as_ref()?)
+        }
+        _ => None,
+    }
+}
+
+fn collect_attached(stmt: &Stmt, out: &mut Vec<String>) {
+    if let StmtKind::Expr(expr) = &stmt.kind {
+        collect_attached_expr(expr, out);
+    }
+}
+
+fn collect_attached_expr(expr: &Expr, out: &mut Vec<String>) {
+    if let Some(pkg) = library_package(expr) {
+        if !out.contains(&pkg) {
+            out.push(pkg);
+        }
+        return;
+    }
+    match expr {
+        Expr::Block(stmts, _) => {
+            for stmt in stmts {
+                collect_attached(stmt, out);
+            }
+        }
+        Expr::If(_, then, els) => {
+            collect_attached_expr(then, out);
+            if let Some(els) = els {
+                collect_attached_expr(els, out);
+            }
+        }
+        Expr::For(_, _, body) | Expr::While(_, body) | Expr::Repeat(body) => {
+            collect_attached_expr(body, out)
+        }
+        Expr::Function(_, body) => collect_attached_expr(body, out),
+        Expr::Assign(_, value) => collect_attached_expr(value, out),
+        _ => {}
+    }
+}
+
+/// Names a statement assigns in its own scope, not counting nested functions.
+fn assigned_names(stmt: &Stmt, src: &str) -> Vec<String> {
+    let mut names = FxHashSet::default();
+    match &stmt.kind {
+        StmtKind::Expr(expr) => collect_expr_assigns(expr, &mut names),
+        StmtKind::Raw(_) => {
+            let (toks, _) = lex(&src[stmt.start..stmt.end]);
+            let mut depth = 0usize;
+            for (i, tok) in toks.iter().enumerate() {
+                match &tok.tok {
+                    Tok::LParen | Tok::LBracket => depth += 1,
+                    Tok::RParen | Tok::RBracket => depth = depth.saturating_sub(1),
+                    Tok::Ident(name) => {
+                        let next = toks.get(i + 1).map(|t| &t.tok);
+                        let prev = i.checked_sub(1).map(|p| &toks[p].tok);
+                        let assigned = matches!(next, Some(Tok::Op(op)) if op == "<-")
+                            || (depth == 0 && matches!(next, Some(Tok::Op(op)) if op == "="))
+                            || matches!(prev, Some(Tok::Op(op)) if op == "->");
+                        if assigned {
+                            names.insert(name.clone());
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+    let mut names: Vec<String> = names.into_iter().collect();
+    names.sort();
+    names
+}
+
+fn collect_expr_assigns(expr: &Expr, out: &mut FxHashSet<String>) {
+    match expr {
+        Expr::Assign(target, value) => {
+            if let Expr::Ident(name) = &**target {
+                out.insert(name.clone());
+            }
+            collect_expr_assigns(value, out);
+        }
+        Expr::Block(stmts, _) => {
+            for stmt in stmts {
+                match &stmt.kind {
+                    StmtKind::Expr(expr) => collect_expr_assigns(expr, out),
+                    StmtKind::Raw(_) => {}
+                }
+            }
+        }
+        Expr::If(_, then, els) => {
+            collect_expr_assigns(then, out);
+            if let Some(els) = els {
+                collect_expr_assigns(els, out);
+            }
+        }
+        Expr::For(var, _, body) => {
+            out.insert(var.clone());
+            collect_expr_assigns(body, out);
+        }
+        Expr::While(_, body) | Expr::Repeat(body) => collect_expr_assigns(body, out),
+        _ => {}
+    }
+}
+
+/// Every name read anywhere in `expr`, including inside nested functions.
+fn collect_reads(expr: &Expr, out: &mut FxHashSet<String>) {
+    match expr {
+        Expr::Ident(name) => {
+            out.insert(name.clone());
+        }
+        Expr::Call(callee, args) => {
+            collect_reads(callee, out);
+            for value in args.iter().filter_map(|arg| arg.value.as_ref()) {
+                collect_reads(value, out);
+            }
+        }
+        Expr::Index { base, args, .. } => {
+            collect_reads(base, out);
+            for value in args.iter().filter_map(|arg| arg.value.as_ref()) {
+                collect_reads(value, out);
+            }
+        }
+        Expr::Dollar(inner, _)
+        | Expr::Slot(inner, _)
+        | Expr::Unary(_, inner)
+        | Expr::Paren(inner) => collect_reads(inner, out),
+        Expr::Repeat(inner) => collect_reads(inner, out),
+        Expr::Binary(_, lhs, rhs) | Expr::While(lhs, rhs) | Expr::For(_, lhs, rhs) => {
+            collect_reads(lhs, out);
+            collect_reads(rhs, out);
+        }
+        Expr::Assign(target, value) => {
+            if !matches!(&**target, Expr::Ident(_)) {
+                collect_reads(target, out);
+            }
+            collect_reads(value, out);
+        }
+        Expr::SuperAssign(target, value) => {
+            collect_reads(target, out);
+            collect_reads(value, out);
+        }
+        Expr::If(cond, then, els) => {
+            collect_reads(cond, out);
+            collect_reads(then, out);
+            if let Some(els) = els {
+                collect_reads(els, out);
+            }
+        }
+        Expr::Block(stmts, _) => {
+            for stmt in stmts {
+                if let StmtKind::Expr(expr) = &stmt.kind {
+                    collect_reads(expr, out);
+                }
+            }
+        }
+        Expr::Function(params, body) => {
+            for default in params.iter().filter_map(|(_, default)| default.as_ref()) {
+                collect_reads(default, out);
+            }
+            collect_reads(body, out);
+        }
+        Expr::Num(_)
+        | Expr::Str(_)
+        | Expr::Ns(..)
+        | Expr::NsInternal(..)
+        | Expr::Break
+        | Expr::Next => {}
+    }
+}
+
+#[cfg(test)]
+#[path = "import_r/tests.rs"]
+mod tests;
@@ -0,0 +1,192 @@
+use super::*;
+
+const SCRIPT: &str = "\
+# Summary statistics.
+
+library(stats)
+
+scale <- 2.5
+norm2 <- function(v) {
+  total <- 0
+  for (i in 1:length(v)) {
+    total <- total + v[i]^2  # accumulate
+  }
+  sqrt(total)
+}
+scaled <- function(x) x * scale
+
+xs <- c(1, 2, 3)
+label <- if (median(xs) > 2) 'high' else \"low\"
+names(xs) <- c(\"a\", \"b\", \"c\")
+print(stats::sd(xs) + norm2(xs))
+";
+
+#[test]
+fn translate_r_declares_imports_and_wraps_unsupported_statements() {
+    let out = translate_r(SCRIPT);
+    let expected = "\
+// Summary statistics.
+
+import r { median } from \"stats\"
+import r \"stats\"
+
+let scale <- 2.5
+fn norm2(v) {
+  let total <- 0.0
+  for (i in 1L..length(v)) {
+    total <- total + v[i] * v[i] // accumulate
+  }
+  sqrt(total)
+}
+let scaled <- function(x) { x * scale }
+
+let xs <- c(1.0, 2.0, 3.0)
+let label = NULL
+if (median(xs) > 2.0) {
+  label <- \"high\"
+} else {
+  label <- \"low\"
+}
+// import-r: replacement call `names(...) <-`
+unsafe r {
+  names(xs) <- c(\"a\", \"b\", \"c\")
+}
+print(stats.sd(xs) + norm2(xs))
+";
+    assert_eq!(out.source, expected);
+    assert_eq!(
+        out.notes,
+        vec![ImportRNote {
+            line: 17,
+            reason: "replacement call `names(...) <-`".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn translate_r_hoists_names_assigned_in_blocks_and_raw_statements() {
+    let out = translate_r(
+        "\
+f <- function(n) {
+  if (n > 1) {
+    acc <- n
+  } else {
+    acc <- 1
+  }
+  acc
+}
+g <- function(x) {
+  h <<- x
+  y <- x %o% x
+  y
+}
+",
+    );
+    assert_eq!(
+        out.source,
+        "\
+fn f(n) {
+  let acc = NULL
+  if (n > 1.0) {
+    acc <- n
+  } else {
+    acc <- 1.0
+  }
+  acc
+}
+fn g(x) {
+  let y = NULL
+  // import-r: superassignment `<<-`
+  unsafe r {
+    h <<- x
+  }
+  // import-r: operator `%o%`
+  unsafe r {
+    y <- x %o% x
+  }
+  y
+}
+"
+    );
+    assert_eq!(out.notes.len(), 2);
+}
+
+#[test]
+fn translate_r_rewrites_operators_and_names() {
+    let out = translate_r(
+        "\
+library(data.table)
+cfg <- list(a = 1L, b = T)
+hit <- 2 %in% c(1, 2)
+r <- (cfg$a + 1) %% 3
+m <- -(r - 1) * 2
+l <- list()
+k <- 0
+repeat {
+  k <- k + 1
+  if (k > 2) break
+}
+d <- data.table::fread(\"x.csv\")
+",
+    );
+    assert_eq!(
+        out.source,
+        "\
+import r * as data_table from \"data.table\"
+import r { list as base_list, is.element } from \"base\"
+
+let cfg <- list(a = 1L, b = TRUE)
+let hit <- is.element(2.0, c(1.0, 2.0))
+let r <- (cfg.a + 1.0) % 3.0
+let m <- -(r - 1.0) * 2.0
+let l <- base_list()
+let k <- 0.0
+while (TRUE) {
+  k <- k + 1.0
+  if (k > 2.0) {
+    break
+  }
+}
+let d <- data_table.fread(\"x.csv\")
+"
+    );
+    assert!(out.notes.is_empty());
+}
+
+#[test]
+fn translate_r_reports_unknown_names_and_parse_errors() {
+    let out = translate_r(
+        "\
+x <- fancy(1)
+y <- function(...) 1
+z <- 1 +
+  Inf
+",
+    );
+    let reasons: Vec<(u32, &str)> = out
+        .notes
+        .iter()
+        .map(|note| (note.line, note.reason.as_str()))
+        .collect();
+    assert_eq!(
+        reasons,
+        vec![
+            (1, "unknown name `fancy`"),
+            (2, "`...` parameters"),
+            (3, "R constant `Inf` has no RR spelling"),
+        ]
+    );
+    assert!(
+        out.source
+            .starts_with("let x = NULL\nlet y = NULL\nlet z = NULL\n")
+    );
+    assert!(out.source.contains("unsafe r {\n  z <- 1 +\n    Inf\n}\n"));
+}
+
+#[test]
+fn translated_script_compiles_with_the_strict_pipeline() {
+    let out = translate_r(SCRIPT);
+    let entry = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("import_r_check.rr");
+    check_import_r(&entry.to_string_lossy(), &out.source)
+        .unwrap_or_else(|e| panic!("{:?}\n{}", e, out.source));
+}
@@ -1,6 +1,7 @@
 use super::*;
 pub(crate) fn raw_r_debug_dump_requested() -> bool {
     env::var_os("RR_DEBUG_RAW_R_PATH").is_some()
+        || crate::compiler::emit_stage::any_stage_requested()
 }
 
 pub(crate) fn maybe_fill_incremental_profile(
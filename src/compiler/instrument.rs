@@ -0,0 +1,117 @@
+//! Source-level probes shared by `RR dap` and `RR run --coverage`.
+//!
+//! Probes are `unsafe r { ... }` statements inserted into the RR text before
+//! it is compiled. The compiler keeps unsafe R blocks in place and treats
+//! their function as opaque, so every probe survives into the emitted R even
+//! at `-O0`.
+
+use crate::syntax::ast::{Block, Stmt, StmtKind};
+
+/// Visit every executable statement under `stmts`, outer statements first.
+/// `own_line` tells whether a probe can be inserted right before it.
+pub(crate) fn visit_statements(source: &str, stmts: &[Stmt], visit: &mut impl FnMut(&Stmt, bool)) {
+    for stmt in stmts {
+        match &stmt.kind {
+            StmtKind::FnDecl { body, .. } => visit_block(source, body, visit),
+            StmtKind::Export(decl) => visit_block(source, &decl.body, visit),
+            StmtKind::ImplDecl(decl) => {
+                for method in &decl.methods {
+                    visit_block(source, &method.body, visit);
+                }
+            }
+            StmtKind::TraitDecl(_) | StmtKind::Import { .. } => {}
+            kind => {
+                visit(stmt, starts_own_line(source, stmt.span.start_byte));
+                match kind {
+                    StmtKind::If {
+                        then_blk, else_blk, ..
+                    } => {
+                        visit_block(source, then_blk, visit);
+                        if let Some(else_blk) = else_blk {
+                            visit_block(source, else_blk, visit);
+                        }
+                    }
+                    StmtKind::While { body, .. } | StmtKind::For { body, .. } => {
+                        visit_block(source, body, visit)
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
+fn visit_block(source: &str, block: &Block, visit: &mut impl FnMut(&Stmt, bool)) {
+    visit_statements(source, &block.stmts, visit);
+}
+
+/// A probe can only go before a statement that opens a line or a block;
+/// anywhere else (`else if`, `a; b`) it would change how the line parses.
+fn starts_own_line(source: &str, byte: usize) -> bool {
+    let Some(before) = source.get(..byte) else {
+        return false;
+    };
+    let prefix = before.trim_end();
+    prefix.is_empty() || prefix.ends_with('{') || before[prefix.len()..].contains('\n')
+}
+
+/// Whether `block` is written with braces, so a probe fits right after `{`.
+pub(crate) fn is_braced(source: &str, block: &Block) -> bool {
+    source.as_bytes().get(block.span.start_byte) == Some(&b'{')
+}
+
+/// A probe running `r_code`, to insert at `byte`. The probe ends its own
+/// line and the original text continues at the same column on the next.
+pub(crate) fn probe(source: &str, byte: usize, r_code: &str) -> (usize, String) {
+    let line_start = source
+        .get(..byte)
+        .and_then(|before| before.rfind('\n'))
+        .map_or(0, |idx| idx + 1);
+    let indent: String = source
+        .get(line_start..byte)
+        .unwrap_or_default()
+        .chars()
+        .map(|ch| if ch.is_whitespace() { ch } else { ' ' })
+        .collect();
+    (byte, format!("unsafe r {{ {} }}\n{}", r_code, indent))
+}
+
+/// Insert each `(byte offset, text)` probe into `source`. Returns the new
+/// text and, for each of its lines, the line of `source` it came from; a
+/// line that starts inside a probe maps to the line the probe was put on.
+pub(crate) fn insert_probes(source: &str, mut probes: Vec<(usize, String)>) -> (String, Vec<u32>) {
+    probes.sort_by_key(|(byte, _)| *byte);
+    let mut out = String::with_capacity(source.len() + probes.len() * 32);
+    let mut line_map = vec![1];
+    let mut line = 1u32;
+    let mut copied = 0;
+    for (byte, text) in &probes {
+        let Some(chunk) = source.get(copied..*byte) else {
+            continue;
+        };
+        copy_text(&mut out, &mut line_map, &mut line, chunk, true);
+        copy_text(&mut out, &mut line_map, &mut line, text, false);
+        copied = *byte;
+    }
+    let rest = source.get(copied..).unwrap_or_default();
+    copy_text(&mut out, &mut line_map, &mut line, rest, true);
+    (out, line_map)
+}
+
+fn copy_text(
+    out: &mut String,
+    line_map: &mut Vec<u32>,
+    line: &mut u32,
+    text: &str,
+    original: bool,
+) {
+    for ch in text.chars() {
+        out.push(ch);
+        if ch == '\n' {
+            if original {
+                *line += 1;
+            }
+            line_map.push(*line);
+        }
+    }
+}
@@ -0,0 +1,229 @@
+//! Source lints.
+//!
+//! Lints are warnings with a stable ID (`unused_variable`, `na_comparison`,
+//! ...) and a stable `W` code. Each lint has a level — `allow`, `warn` or
+//! `deny` — that a project can override with `lint <id> <level>` lines in
+//! `rr.mod`. Syntactic lints walk the parsed program; lints that need type or
+//! fallback facts run over the MIR produced by the normal front end, before
+//! any optimization, so they see the program as written.
+
+use crate::error::{RR, RRCode, RRException, Severity, Stage};
+use crate::syntax::parse::Parser;
+use rustc_hash::FxHashMap;
+use std::collections::BTreeMap;
+
+#[path = "lint/ast.rs"]
+mod ast;
+#[path = "lint/mir.rs"]
+mod mir;
+
+#[cfg(test)]
+#[path = "lint/tests.rs"]
+mod tests;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LintLevel {
+    Allow,
+    Warn,
+    Deny,
+}
+
+impl LintLevel {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Allow => "allow",
+            Self::Warn => "warn",
+            Self::Deny => "deny",
+        }
+    }
+
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw {
+            "allow" => Some(Self::Allow),
+            "warn" => Some(Self::Warn),
+            "deny" => Some(Self::Deny),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct LintInfo {
+    pub id: &'static str,
+    pub code: RRCode,
+    pub default_level: LintLevel,
+    pub summary: &'static str,
+}
+
+pub const LINTS: &[LintInfo] = &[
+    LintInfo {
+        id: "unused_variable",
+        code: RRCode::W1001,
+        default_level: LintLevel::Warn,
+        summary: "`let` binding inside a function that is never read",
+    },
+    LintInfo {
+        id: "unused_import",
+        code: RRCode::W1002,
+        default_level: LintLevel::Warn,
+        summary: "named or namespace R import that is never referenced",
+    },
+    LintInfo {
+        id: "shadowed_binding",
+        code: RRCode::W1003,
+        default_level: LintLevel::Warn,
+        summary: "`let` or loop variable that rebinds a name already in scope",
+    },
+    LintInfo {
+        id: "na_comparison",
+        code: RRCode::W1004,
+        default_level: LintLevel::Warn,
+        summary: "`x == NA` or `x != NA`, which is always NA in R",
+    },
+    LintInfo {
+        id: "length_recycling",
+        code: RRCode::W1005,
+        default_level: LintLevel::Warn,
+        summary: "element-wise operation on vectors whose known lengths differ",
+    },
+    LintInfo {
+        id: "unreachable_code",
+        code: RRCode::W1006,
+        default_level: LintLevel::Warn,
+        summary: "statement after `return`, `break` or `next`",
+    },
+    LintInfo {
+        id: "hybrid_fallback",
+        code: RRCode::W1007,
+        default_level: LintLevel::Warn,
+        summary: "call that forces its function onto the hybrid/opaque fallback path",
+    },
+];
+
+pub fn lint_info(id: &str) -> Option<&'static LintInfo> {
+    LINTS.iter().find(|info| info.id == id)
+}
+
+/// Effective lint levels: catalog defaults overridden by `rr.mod`.
+#[derive(Clone, Debug, Default)]
+pub struct LintConfig {
+    overrides: FxHashMap<&'static str, LintLevel>,
+}
+
+impl LintConfig {
+    /// Build a config from `rr.mod` `lint` directives, rejecting unknown lint
+    /// IDs and levels.
+    pub fn from_manifest_levels(levels: &BTreeMap<String, String>) -> Result<Self, String> {
+        let mut config = Self::default();
+        for (id, level) in levels {
+            let Some(info) = lint_info(id) else {
+                return Err(format!(
+                    "unknown lint '{}' in rr.mod (known lints: {})",
+                    id,
+                    LINTS
+                        .iter()
+                        .map(|info| info.id)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ));
+            };
+            let Some(level) = LintLevel::parse(level) else {
+                return Err(format!(
+                    "invalid level '{}' for lint '{}'; expected allow, warn or deny",
+                    level, id
+                ));
+            };
+            config.overrides.insert(info.id, level);
+        }
+        Ok(config)
+    }
+
+    pub fn set(&mut self, id: &str, level: LintLevel) -> Result<(), String> {
+        let Some(info) = lint_info(id) else {
+            return Err(format!("unknown lint '{}'", id));
+        };
+        self.overrides.insert(info.id, level);
+        Ok(())
+    }
+
+    pub fn level(&self, info: &LintInfo) -> LintLevel {
+        self.overrides
+            .get(info.id)
+            .copied()
+            .unwrap_or(info.default_level)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct LintFinding {
+    pub lint: &'static LintInfo,
+    pub level: LintLevel,
+    pub diagnostic: RRException,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct LintReport {
+    pub findings: Vec<LintFinding>,
+    /// Front-end error that stopped MIR synthesis. Syntactic lints are still
+    /// reported; MIR lints are skipped.
+    pub compile_error: Option<RRException>,
+}
+
+/// Lint one source file. Parse errors are returned as `Err`.
+pub fn lint_source(entry_path: &str, source: &str, config: &LintConfig) -> RR<LintReport> {
+    let program = Parser::new(source).parse_program()?;
+    let mut raw = ast::lint_program(&program, source);
+    let compile_error = match mir::lint_entry(entry_path, source, &program) {
+        Ok(mir_lints) => {
+            raw.extend(mir_lints);
+            None
+        }
+        Err(e) => Some(e),
+    };
+
+    let mut findings = Vec::new();
+    for (id, diagnostic) in raw {
+        let Some(info) = lint_info(id) else {
+            continue;
+        };
+        let level = config.level(info);
+        let severity = match level {
+            LintLevel::Allow => continue,
+            LintLevel::Warn => Severity::Warning,
+            LintLevel::Deny => Severity::Error,
+        };
+        let diagnostic = diagnostic.with_severity(severity).help(format!(
+            "lint `{}` is set to {}; change it with `lint {} <allow|warn|deny>` in rr.mod",
+            info.id,
+            level.as_str(),
+            info.id
+        ));
+        findings.push(LintFinding {
+            lint: info,
+            level,
+            diagnostic,
+        });
+    }
+    findings.sort_by_key(|finding| {
+        finding
+            .diagnostic
+            .span
+            .map(|span| (span.start_line, span.start_col))
+            .unwrap_or((u32::MAX, u32::MAX))
+    });
+    Ok(LintReport {
+        findings,
+        compile_error,
+    })
+}
+
+fn lint_diagnostic(
+    id: &'static str,
+    stage: Stage,
+    message: impl Into<String>,
+) -> (&'static str, RRException) {
+    let code = lint_info(id)
+        .map(|info| info.code.clone())
+        .unwrap_or(RRCode::W1001);
+    (id, RRException::new("RR.Lint", code, stage, message))
+}
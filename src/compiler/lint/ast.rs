@@ -0,0 +1,474 @@
+use super::lint_diagnostic;
+use crate::error::{DiagnosticLabelKind, RRException, Stage};
+use crate::syntax::ast::{
+    BinOp, Block, Expr, ExprKind, FnParam, ImportSource, ImportSpec, LValueKind, Lit, Program,
+    Stmt, StmtKind,
+};
+use crate::utils::Span;
+use rustc_hash::{FxHashMap, FxHashSet};
+
+type RawLint = (&'static str, RRException);
+
+pub(super) fn lint_program(program: &Program, source: &str) -> Vec<RawLint> {
+    let mut out = Vec::new();
+    lint_unused_imports(program, &mut out);
+    for_each_fn_body(&program.stmts, &mut |body| {
+        lint_unused_locals(body, &mut out)
+    });
+    let mut shadow = ShadowWalker {
+        scopes: vec![FxHashMap::default()],
+        out: &mut out,
+    };
+    shadow.walk_stmts(&program.stmts);
+    lint_unreachable(&program.stmts, &mut out);
+    for_each_expr(&program.stmts, &mut |expr| {
+        lint_na_comparison(expr, source, &mut out)
+    });
+    out
+}
+
+fn lint_unused_imports(program: &Program, out: &mut Vec<RawLint>) {
+    let mut reads = FxHashSet::default();
+    collect_reads(&program.stmts, &mut reads);
+    let used = |name: &str| {
+        reads.contains(name)
+            || reads.iter().any(|read| {
+                read.strip_prefix(name)
+                    .is_some_and(|rest| rest.starts_with("::") || rest.starts_with('.'))
+            })
+    };
+    for stmt in &program.stmts {
+        let StmtKind::Import {
+            source: ImportSource::RPackage,
+            path,
+            spec,
+        } = &stmt.kind
+        else {
+            continue;
+        };
+        let locals: Vec<&str> = match spec {
+            ImportSpec::Glob => Vec::new(),
+            ImportSpec::Named(bindings) => bindings
+                .iter()
+                .map(|binding| binding.local.as_deref().unwrap_or(&binding.imported))
+                .collect(),
+            ImportSpec::Namespace(alias) => vec![alias.as_str()],
+        };
+        for local in locals {
+            if local.starts_with('_') || used(local) {
+                continue;
+            }
+            let (id, diag) = lint_diagnostic(
+                "unused_import",
+                Stage::Lower,
+                format!("R import `{}` from \"{}\" is never used", local, path),
+            );
+            out.push((id, diag.at(stmt.span)));
+        }
+    }
+}
+
+fn lint_unused_locals(body: &Block, out: &mut Vec<RawLint>) {
+    let mut reads = FxHashSet::default();
+    collect_reads(&body.stmts, &mut reads);
+    let mut lets = Vec::new();
+    collect_lets(&body.stmts, &mut lets);
+    let mut reported = FxHashSet::default();
+    for (name, span) in lets {
+        if name.starts_with('_') || reads.contains(name) || !reported.insert(name) {
+            continue;
+        }
+        let (id, diag) = lint_diagnostic(
+            "unused_variable",
+            Stage::Lower,
+            format!("local `{}` is assigned but never read", name),
+        );
+        out.push((
+            id,
+            diag.at(span).help(format!(
+                "prefix it with an underscore (`_{}`) if this is intentional",
+                name
+            )),
+        ));
+    }
+}
+
+/// `let` bindings owned by this function body; nested lambdas are linted on
+/// their own.
+fn collect_lets<'a>(stmts: &'a [Stmt], out: &mut Vec<(&'a str, Span)>) {
+    for stmt in stmts {
+        match &stmt.kind {
+            StmtKind::Let { name, .. } => out.push((name, stmt.span)),
+            StmtKind::If {
+                then_blk, else_blk, ..
+            } => {
+                collect_lets(&then_blk.stmts, out);
+                if let Some(else_blk) = else_blk {
+                    collect_lets(&else_blk.stmts, out);
+                }
+            }
+            StmtKind::While { body, .. } | StmtKind::For { body, .. } => {
+                collect_lets(&body.stmts, out)
+            }
+            _ => {}
+        }
+    }
+}
+
+struct ShadowWalker<'o> {
+    scopes: Vec<FxHashMap<String, Span>>,
+    out: &'o mut Vec<RawLint>,
+}
+
+impl ShadowWalker<'_> {
+    fn bind(&mut self, name: &str, span: Span, what: &str) {
+        if !name.starts_with('_')
+            && let Some(prev) = self
+                .scopes
+                .iter()
+                .rev()
+                .find_map(|scope| scope.get(name).copied())
+        {
+            let (id, diag) = lint_diagnostic(
+                "shadowed_binding",
+                Stage::Lower,
+                format!("{} `{}` shadows an earlier binding", what, name),
+            );
+            self.out.push((
+                id,
+                diag.at(span).label(
+                    DiagnosticLabelKind::Origin,
+                    prev,
+                    format!("`{}` was first bound here", name),
+                ),
+            ));
+        }
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(name.to_string(), span);
+        }
+    }
+
+    fn walk_fn(&mut self, params: &[FnParam], body: &Block) {
+        self.scopes.push(FxHashMap::default());
+        for param in params {
+            if let Some(default) = &param.default {
+                self.walk_expr(default);
+            }
+            if let Some(scope) = self.scopes.last_mut() {
+                scope.insert(param.name.clone(), param.span);
+            }
+        }
+        self.walk_stmts(&body.stmts);
+        self.scopes.pop();
+    }
+
+    fn walk_block(&mut self, block: &Block) {
+        self.scopes.push(FxHashMap::default());
+        self.walk_stmts(&block.stmts);
+        self.scopes.pop();
+    }
+
+    fn walk_stmts(&mut self, stmts: &[Stmt]) {
+        for stmt in stmts {
+            self.walk_stmt(stmt);
+        }
+    }
+
+    fn walk_stmt(&mut self, stmt: &Stmt) {
+        match &stmt.kind {
+            StmtKind::Let { name, init, .. } => {
+                if let Some(init) = init {
+                    self.walk_expr(init);
+                }
+                self.bind(name, stmt.span, "local");
+            }
+            StmtKind::FnDecl { params, body, .. } => {
+                // Top-level functions start from a clean scope: R closures see
+                // globals, but rebinding one locally is ordinary style.
+                let saved = std::mem::take(&mut self.scopes);
+                self.walk_fn(params, body);
+                self.scopes = saved;
+            }
+            StmtKind::Export(decl) => {
+                let saved = std::mem::take(&mut self.scopes);
+                self.walk_fn(&decl.params, &decl.body);
+                self.scopes = saved;
+            }
+            StmtKind::ImplDecl(decl) => {
+                for method in &decl.methods {
+                    let saved = std::mem::take(&mut self.scopes);
+                    self.walk_fn(&method.params, &method.body);
+                    self.scopes = saved;
+                }
+            }
+            StmtKind::If {
+                cond,
+                then_blk,
+                else_blk,
+            } => {
+                self.walk_expr(cond);
+                self.walk_block(then_blk);
+                if let Some(else_blk) = else_blk {
+                    self.walk_block(else_blk);
+                }
+            }
+            StmtKind::While { cond, body } => {
+                self.walk_expr(cond);
+                self.walk_block(body);
+            }
+            StmtKind::For {
+                var, iter, body, ..
+            } => {
+                self.walk_expr(iter);
+                self.scopes.push(FxHashMap::default());
+                self.bind(var, stmt.span, "loop variable");
+                self.walk_stmts(&body.stmts);
+                self.scopes.pop();
+            }
+            _ => {
+                for expr in stmt_exprs(stmt) {
+                    self.walk_expr(expr);
+                }
+            }
+        }
+    }
+
+    fn walk_expr(&mut self, expr: &Expr) {
+        if let ExprKind::Lambda { params, body, .. } = &expr.kind {
+            self.walk_fn(params, body);
+            return;
+        }
+        for child in expr_children(expr) {
+            self.walk_expr(child);
+        }
+    }
+}
+
+fn lint_unreachable(stmts: &[Stmt], out: &mut Vec<RawLint>) {
+    if let Some(pos) = stmts.iter().position(diverges)
+        && let Some(next) = stmts.get(pos + 1)
+    {
+        let (id, diag) = lint_diagnostic("unreachable_code", Stage::Lower, "unreachable statement");
+        out.push((
+            id,
+            diag.at(next.span).label(
+                DiagnosticLabelKind::Origin,
+                stmts[pos].span,
+                "any code following this statement is unreachable",
+            ),
+        ));
+    }
+    for stmt in stmts {
+        for block in stmt_blocks(stmt) {
+            lint_unreachable(&block.stmts, out);
+        }
+        for expr in stmt_exprs(stmt) {
+            visit_expr(expr, &mut |expr| {
+                if let ExprKind::Lambda { body, .. } = &expr.kind {
+                    lint_unreachable(&body.stmts, out);
+                }
+            });
+        }
+    }
+}
+
+fn diverges(stmt: &Stmt) -> bool {
+    match &stmt.kind {
+        StmtKind::Return { .. } | StmtKind::Break | StmtKind::Next => true,
+        StmtKind::If {
+            then_blk,
+            else_blk: Some(else_blk),
+            ..
+        } => then_blk.stmts.iter().any(diverges) && else_blk.stmts.iter().any(diverges),
+        _ => false,
+    }
+}
+
+fn lint_na_comparison(expr: &Expr, source: &str, out: &mut Vec<RawLint>) {
+    let ExprKind::Binary { op, lhs, rhs } = &expr.kind else {
+        return;
+    };
+    if !matches!(op, BinOp::Eq | BinOp::Ne) {
+        return;
+    }
+    let is_na = |e: &Expr| matches!(e.kind, ExprKind::Lit(Lit::Na));
+    let other = match (is_na(lhs), is_na(rhs)) {
+        (false, true) => lhs,
+        (true, false) => rhs,
+        _ => return,
+    };
+    let op_text = if *op == BinOp::Eq { "==" } else { "!=" };
+    let (id, mut diag) = lint_diagnostic(
+        "na_comparison",
+        Stage::Lower,
+        format!("comparison `{} NA` is always NA", op_text),
+    );
+    diag = diag
+        .at(expr.span)
+        .note("R propagates NA through `==` and `!=`, so this never yields TRUE or FALSE");
+    let replacement = source
+        .get(other.span.start_byte..other.span.end_byte)
+        .filter(|text| !text.is_empty())
+        .map(|text| {
+            if *op == BinOp::Eq {
+                format!("is.na({})", text)
+            } else {
+                format!("!is.na({})", text)
+            }
+        });
+    diag = match replacement {
+        Some(replacement) => diag.replace(expr.span, replacement, "test for missing values"),
+        None => diag.help("use is.na() to test for missing values"),
+    };
+    out.push((id, diag));
+}
+
+/// Call `f` with the body of every top-level function, method and lambda in
+/// the program.
+fn for_each_fn_body(stmts: &[Stmt], f: &mut dyn FnMut(&Block)) {
+    for stmt in stmts {
+        match &stmt.kind {
+            StmtKind::FnDecl { body, .. } => f(body),
+            StmtKind::Export(decl) => f(&decl.body),
+            StmtKind::ImplDecl(decl) => {
+                for method in &decl.methods {
+                    f(&method.body);
+                }
+            }
+            _ => {}
+        }
+    }
+    for_each_expr(stmts, &mut |expr| {
+        if let ExprKind::Lambda { body, .. } = &expr.kind {
+            f(body);
+        }
+    });
+}
+
+fn collect_reads(stmts: &[Stmt], out: &mut FxHashSet<String>) {
+    for_each_expr(stmts, &mut |expr| {
+        if let ExprKind::Name(name) = &expr.kind {
+            out.insert(name.clone());
+        }
+    });
+}
+
+/// Visit every expression reachable from `stmts`, including those inside
+/// nested blocks, functions and lambdas.
+fn for_each_expr(stmts: &[Stmt], f: &mut dyn FnMut(&Expr)) {
+    for stmt in stmts {
+        for expr in stmt_exprs(stmt) {
+            visit_expr(expr, f);
+        }
+        for block in stmt_blocks(stmt) {
+            for_each_expr(&block.stmts, f);
+        }
+    }
+}
+
+fn visit_expr(expr: &Expr, f: &mut dyn FnMut(&Expr)) {
+    f(expr);
+    if let ExprKind::Lambda { body, .. } = &expr.kind {
+        for_each_expr(&body.stmts, f);
+    }
+    for child in expr_children(expr) {
+        visit_expr(child, f);
+    }
+}
+
+/// Expressions owned directly by a statement (not those in nested blocks).
+fn stmt_exprs(stmt: &Stmt) -> Vec<&Expr> {
+    let mut out = Vec::new();
+    match &stmt.kind {
+        StmtKind::Let { init, .. } => out.extend(init.iter()),
+        StmtKind::Assign { target, value } => {
+            match &target.kind {
+                LValueKind::Name(_) => {}
+                LValueKind::Index { base, idx } => {
+                    out.push(base);
+                    out.extend(idx.iter());
+                }
+                LValueKind::Field { base, .. } => out.push(base),
+            }
+            out.push(value);
+        }
+        StmtKind::FnDecl { params, .. } => {
+            out.extend(params.iter().filter_map(|p| p.default.as_ref()));
+        }
+        StmtKind::Export(decl) => {
+            out.extend(decl.params.iter().filter_map(|p| p.default.as_ref()));
+        }
+        StmtKind::TraitDecl(decl) => {
+            out.extend(decl.assoc_consts.iter().filter_map(|c| c.default.as_ref()));
+        }
+        StmtKind::ImplDecl(decl) => {
+            out.extend(decl.assoc_consts.iter().map(|c| &c.value));
+            for method in &decl.methods {
+                out.extend(method.params.iter().filter_map(|p| p.default.as_ref()));
+            }
+        }
+        StmtKind::If { cond, .. } | StmtKind::While { cond, .. } => out.push(cond),
+        StmtKind::For { iter, .. } => out.push(iter),
+        StmtKind::Return { value } => out.extend(value.iter()),
+        StmtKind::ExprStmt { expr } | StmtKind::Expr(expr) => out.push(expr),
+        StmtKind::Break
+        | StmtKind::Next
+        | StmtKind::UnsafeRBlock { .. }
+        | StmtKind::Import { .. }
+        | StmtKind::ReExport { .. } => {}
+    }
+    out
+}
+
+/// Blocks nested directly in a statement.
+fn stmt_blocks(stmt: &Stmt) -> Vec<&Block> {
+    match &stmt.kind {
+        StmtKind::FnDecl { body, .. } => vec![body],
+        StmtKind::Export(decl) => vec![&decl.body],
+        StmtKind::TraitDecl(decl) => decl
+            .methods
+            .iter()
+            .filter_map(|m| m.default_body.as_ref())
+            .collect(),
+        StmtKind::ImplDecl(decl) => decl.methods.iter().map(|m| &m.body).collect(),
+        StmtKind::If {
+            then_blk, else_blk, ..
+        } => std::iter::once(then_blk).chain(else_blk.iter()).collect(),
+        StmtKind::While { body, .. } | StmtKind::For { body, .. } => vec![body],
+        _ => Vec::new(),
+    }
+}
+
+/// Direct sub-expressions, excluding lambda bodies.
+fn expr_children(expr: &Expr) -> Vec<&Expr> {
+    match &expr.kind {
+        ExprKind::Lit(_) | ExprKind::Name(_) | ExprKind::ColRef(_) | ExprKind::Column(_) => {
+            Vec::new()
+        }
+        ExprKind::Unary { rhs, .. } => vec![rhs],
+        ExprKind::Formula { lhs, rhs } => lhs.iter().map(|l| &**l).chain([&**rhs]).collect(),
+        ExprKind::Binary { lhs, rhs, .. } => vec![lhs, rhs],
+        ExprKind::Range { a, b } => vec![a, b],
+        ExprKind::Lambda { params, .. } => {
+            params.iter().filter_map(|p| p.default.as_ref()).collect()
+        }
+        ExprKind::Call { callee, args, .. } => {
+            std::iter::once(&**callee).chain(args.iter()).collect()
+        }
+        ExprKind::NamedArg { value, .. } => vec![value],
+        ExprKind::Index { base, idx } => std::iter::once(&**base).chain(idx.iter()).collect(),
+        ExprKind::Field { base, .. } => vec![base],
+        ExprKind::VectorLit(items) => items.iter().collect(),
+        ExprKind::RecordLit(fields) => fields.iter().map(|(_, value)| value).collect(),
+        ExprKind::Pipe { lhs, rhs_call } => vec![lhs, rhs_call],
+        ExprKind::Try { expr } | ExprKind::Unquote(expr) => vec![expr],
+        ExprKind::Match { scrutinee, arms } => {
+            let mut out = vec![&**scrutinee];
+            for arm in arms {
+                out.extend(arm.guard.iter().map(|g| &**g));
+                out.push(&arm.body);
+            }
+            out
+        }
+    }
+}
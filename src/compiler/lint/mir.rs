@@ -0,0 +1,171 @@
+use super::lint_diagnostic;
+use crate::compiler::pipeline::synthesize_unoptimized_mir;
+use crate::error::{RR, RRException, Stage};
+use crate::mir::def::{BinOp, FnIR, Lit, ValueId, ValueKind};
+use crate::syntax::ast::{Program, StmtKind};
+use crate::typeck::ShapeTy;
+use rustc_hash::FxHashSet;
+
+type RawLint = (&'static str, RRException);
+
+/// Run the front end on `entry_path` and lint the MIR of the functions and
+/// top-level code that `program` (its parsed source) defines. Functions from
+/// imported modules are linted when those modules are linted themselves.
+pub(super) fn lint_entry(entry_path: &str, source: &str, program: &Program) -> RR<Vec<RawLint>> {
+    let mut local_fns: FxHashSet<&str> = FxHashSet::default();
+    for stmt in &program.stmts {
+        match &stmt.kind {
+            StmtKind::FnDecl { name, .. } => {
+                local_fns.insert(name);
+            }
+            StmtKind::Export(decl) => {
+                local_fns.insert(&decl.name);
+            }
+            _ => {}
+        }
+    }
+
+    let fns = synthesize_unoptimized_mir(entry_path, source)?;
+    let mut out = Vec::new();
+    for fn_ir in &fns {
+        let label = if fn_ir.name == "Sym_top_0" {
+            "top-level code".to_string()
+        } else {
+            match fn_ir.user_name.as_deref() {
+                Some(name) if local_fns.contains(name) => format!("`{}`", name),
+                _ => continue,
+            }
+        };
+        lint_length_recycling(fn_ir, &mut out);
+        lint_hybrid_fallback(fn_ir, &label, &mut out);
+    }
+    Ok(out)
+}
+
+/// Element-wise operators recycle the shorter operand silently. Only
+/// operands whose lengths are known constants are compared: two symbolic
+/// lengths (e.g. two vector parameters) may well match at runtime.
+fn lint_length_recycling(fn_ir: &FnIR, out: &mut Vec<RawLint>) {
+    let mut seen = FxHashSet::default();
+    for value in &fn_ir.values {
+        let ValueKind::Binary { op, lhs, rhs } = &value.kind else {
+            continue;
+        };
+        if matches!(op, BinOp::MatMul) || value.span.start_line == 0 {
+            continue;
+        }
+        let (l, r) = (&fn_ir.values[*lhs], &fn_ir.values[*rhs]);
+        let (ShapeTy::Vector, ShapeTy::Vector) = (l.value_ty.shape, r.value_ty.shape) else {
+            continue;
+        };
+        let (Some(l_len), Some(r_len)) = (known_len(fn_ir, *lhs), known_len(fn_ir, *rhs)) else {
+            continue;
+        };
+        let span = value.span;
+        if l_len == r_len || !seen.insert((span.start_byte, span.end_byte)) {
+            continue;
+        }
+        let (id, diag) = lint_diagnostic(
+            "length_recycling",
+            Stage::Mir,
+            format!(
+                "vector operands have lengths {} and {}; the shorter one would be silently recycled",
+                l_len, r_len
+            ),
+        );
+        let mut diag = diag
+            .at(span)
+            .help("make the lengths match, or recycle explicitly with rep_len()");
+        for (side, operand, len) in [("left", l, l_len), ("right", r, r_len)] {
+            if operand.span.start_line > 0 {
+                diag = diag.label(
+                    crate::error::DiagnosticLabelKind::Use,
+                    operand.span,
+                    format!("{} operand has length {}", side, len),
+                );
+            }
+        }
+        out.push((id, diag));
+    }
+}
+
+/// Length of `vid` when it is fixed by the expression that built it:
+/// scalar constants, `c(...)` of such values, `a:b` over integer literals,
+/// and the `numeric(n)`/`seq_len(n)` family with a literal `n`.
+fn known_len(fn_ir: &FnIR, vid: ValueId) -> Option<i64> {
+    let value = &fn_ir.values[vid];
+    match &value.kind {
+        ValueKind::Const(Lit::Int(_) | Lit::Float(_) | Lit::Str(_) | Lit::Bool(_) | Lit::Na) => {
+            Some(1)
+        }
+        ValueKind::Range { start, end } => {
+            let (start, end) = (const_count(fn_ir, *start)?, const_count(fn_ir, *end)?);
+            Some((end - start).abs() + 1)
+        }
+        ValueKind::Call {
+            callee,
+            args,
+            names,
+        } => {
+            if names.iter().any(Option::is_some) {
+                return None;
+            }
+            match (callee.as_str(), args.as_slice()) {
+                ("c", args) => args
+                    .iter()
+                    .try_fold(0i64, |acc, arg| Some(acc + known_len(fn_ir, *arg)?)),
+                ("numeric" | "double" | "integer" | "character" | "logical" | "seq_len", [n])
+                | ("rep_len", [_, n]) => const_count(fn_ir, *n),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+fn const_count(fn_ir: &FnIR, vid: ValueId) -> Option<i64> {
+    match fn_ir.values[vid].kind {
+        ValueKind::Const(Lit::Int(n)) if n >= 0 => Some(n),
+        ValueKind::Const(Lit::Float(f)) if f >= 0.0 && f.fract() == 0.0 => Some(f as i64),
+        _ => None,
+    }
+}
+
+fn lint_hybrid_fallback(fn_ir: &FnIR, label: &str, out: &mut Vec<RawLint>) {
+    for reason in &fn_ir.fallback_reasons {
+        let interop = fn_ir
+            .hybrid_interop_reasons
+            .iter()
+            .find(|interop| &interop.summary() == reason);
+        let call_span = interop.and_then(|interop| {
+            fn_ir.values.iter().find_map(|value| match &value.kind {
+                ValueKind::Call { callee, .. }
+                    if callee.as_str() == &*interop.callee && value.span.start_line > 0 =>
+                {
+                    Some(value.span)
+                }
+                _ => None,
+            })
+        });
+        let message = match interop {
+            Some(interop) => format!(
+                "call to `{}` forces {} onto the hybrid fallback path",
+                interop.callee, label
+            ),
+            None => format!("{} falls back to hybrid R emission: {}", label, reason),
+        };
+        let (id, mut diag) = lint_diagnostic("hybrid_fallback", Stage::Mir, message);
+        if let Some(span) = call_span {
+            diag = diag.at(span);
+        } else if fn_ir.span.start_line > 0 {
+            diag = diag.at(fn_ir.span);
+        }
+        if let Some(interop) = interop {
+            diag = diag.note(interop.why.to_string());
+            if let Some(suggestion) = &interop.suggestion {
+                diag = diag.help(suggestion.to_string());
+            }
+        }
+        out.push((id, diag));
+    }
+}
@@ -0,0 +1,115 @@
+use super::*;
+use crate::syntax::parse::Parser;
+
+fn ast_lint_ids(src: &str) -> Vec<&'static str> {
+    let program = Parser::new(src)
+        .parse_program()
+        .expect("lint fixture should parse");
+    ast::lint_program(&program, src)
+        .into_iter()
+        .map(|(id, _)| id)
+        .collect()
+}
+
+#[test]
+fn used_and_underscored_bindings_are_quiet() {
+    let ids = ast_lint_ids(
+        r#"
+import r { median } from "stats"
+
+fn f(xs) {
+  let _scratch = 1L
+  let total = median(xs)
+  return total
+}
+"#,
+    );
+    assert!(ids.is_empty(), "unexpected lints: {ids:?}");
+}
+
+#[test]
+fn reports_each_syntactic_lint_once() {
+    let ids = ast_lint_ids(
+        r#"
+import r * as utils from "utils"
+
+fn f(v) {
+  let v = 1L
+  let dead = 2L
+  let missing = v != NA
+  if (missing) {
+    return 1L
+  } else {
+    return 2L
+  }
+  print(v)
+}
+"#,
+    );
+    assert_eq!(
+        ids.iter().filter(|id| **id == "unused_import").count(),
+        1,
+        "{ids:?}"
+    );
+    assert_eq!(
+        ids.iter().filter(|id| **id == "unused_variable").count(),
+        1,
+        "{ids:?}"
+    );
+    assert_eq!(
+        ids.iter().filter(|id| **id == "shadowed_binding").count(),
+        1,
+        "{ids:?}"
+    );
+    assert_eq!(
+        ids.iter().filter(|id| **id == "na_comparison").count(),
+        1,
+        "{ids:?}"
+    );
+    assert_eq!(
+        ids.iter().filter(|id| **id == "unreachable_code").count(),
+        1,
+        "{ids:?}"
+    );
+}
+
+#[test]
+fn na_comparison_suggests_is_na() {
+    let src = "let flag = score == NA\n";
+    let program = Parser::new(src).parse_program().expect("should parse");
+    let lints = ast::lint_program(&program, src);
+    let (_, diag) = lints
+        .iter()
+        .find(|(id, _)| *id == "na_comparison")
+        .expect("na_comparison should fire");
+    assert_eq!(
+        diag.fixes
+            .first()
+            .and_then(|fix| fix.replacement.as_deref()),
+        Some("is.na(score)")
+    );
+}
+
+#[test]
+fn manifest_levels_override_defaults_and_reject_unknown_ids() {
+    let mut levels = BTreeMap::new();
+    levels.insert("unused_import".to_string(), "allow".to_string());
+    levels.insert("na_comparison".to_string(), "deny".to_string());
+    let config = LintConfig::from_manifest_levels(&levels).expect("levels should be valid");
+    let level_of = |id| config.level(lint_info(id).expect("known lint"));
+    assert_eq!(level_of("unused_import"), LintLevel::Allow);
+    assert_eq!(level_of("na_comparison"), LintLevel::Deny);
+    assert_eq!(level_of("shadowed_binding"), LintLevel::Warn);
+
+    levels.insert("no_such_lint".to_string(), "warn".to_string());
+    let err = LintConfig::from_manifest_levels(&levels).expect_err("unknown lint");
+    assert!(err.contains("unknown lint 'no_such_lint'"), "{err}");
+}
+
+#[test]
+fn lint_codes_round_trip_through_rr_code() {
+    for info in LINTS {
+        let code: RRCode = info.code.as_str().parse().expect("W code should parse");
+        assert_eq!(code.as_str(), info.code.as_str());
+    }
+}
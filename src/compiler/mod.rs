@@ -1,13 +1,35 @@
+mod coverage;
+mod debug;
+mod doc;
+mod emit_stage;
 mod entry_policy;
+mod import_r;
 pub(crate) mod incremental;
+mod instrument;
+mod lint;
 pub(crate) mod peephole;
 pub(crate) mod pipeline;
 mod r_peephole;
+mod repl;
 pub(crate) mod scheduler;
 
 pub use crate::codegen::mir_emit::MapEntry;
+pub use crate::mir::opt::remarks::{OptRemark, RemarkKind, collect_opt_remarks};
 pub use crate::typeck::{NativeBackend, TypeConfig, TypeMode};
+pub use coverage::{
+    BranchCoverage, COVERAGE_PROBE_NAME, CoverageBuild, CoverageReport, FileCoverage,
+    collect_module_coverage, instrument_for_coverage,
+};
+pub use debug::{
+    DEBUG_HOOK_NAME, DEBUG_TOP_LEVEL_NAME, DebugBuild, DebugFunction, compile_for_debug,
+};
+pub use doc::{
+    DocExample, DocItem, DocItemKind, ModuleDoc, compile_doc_example, doc_page_name,
+    document_module, markdown_to_html, render_index_markdown, render_module_markdown,
+};
+pub use emit_stage::{EmitStage, StageDumps, collect_stage_dumps};
 pub use entry_policy::{prepare_project_entry_source, prepare_single_file_build_source};
+pub use import_r::{ImportRNote, ImportROutput, check_import_r, translate_r};
 
 #[doc(hidden)]
 pub mod internal {
@@ -37,6 +59,9 @@ pub use incremental::{
     IncrementalStats, compile_incremental_request, compile_with_configs_incremental,
     module_tree_fingerprint, module_tree_snapshot,
 };
+pub use lint::{
+    LINTS, LintConfig, LintFinding, LintInfo, LintLevel, LintReport, lint_info, lint_source,
+};
 pub use pipeline::{
     CliLog, CompileMode, CompileOutputOptions, CompileProfile, CompileWithProfileRequest, OptLevel,
     ParallelBackend, ParallelConfig, ParallelMode, compile, compile_with_config,
@@ -44,6 +69,9 @@ pub use pipeline::{
     compile_with_configs_with_options_and_compiler_parallel, compile_with_profile_request,
     default_parallel_config, default_type_config, json_escape,
 };
+pub use repl::{
+    REPL_VALUE_NAME, ReplBinding, ReplCell, ReplError, ReplSession, input_is_incomplete,
+};
 pub use scheduler::{
     CompilerParallelConfig, CompilerParallelMode, CompilerParallelProfile, CompilerParallelStage,
     CompilerParallelStageProfile, CompilerScheduler, default_compiler_parallel_config,
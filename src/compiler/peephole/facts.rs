@@ -561,7 +561,11 @@ pub(crate) fn is_dead_parenthesized_eval_line(line: &str) -> bool {
     {
         return false;
     }
-    trimmed.starts_with('(') && trimmed.ends_with(')')
+    // Inlined helper calls can leave `(cat(x))`-style statements behind;
+    // only drop the line when evaluating it cannot have an effect.
+    trimmed.starts_with('(')
+        && trimmed.ends_with(')')
+        && super::expr_has_only_pure_calls(trimmed, &FxHashSet::default())
 }
 
 pub(crate) fn is_dead_plain_ident_eval_line(line: &str) -> bool {
@@ -1,3 +1,4 @@
+use super::helpers_cleanup::helper_calls::helper_ident_is_named_label;
 use super::{
     FunctionFacts, PeepholeAnalysisCache, assign_re, cached_function_facts, count_unquoted_braces,
     expr_has_only_pure_calls, expr_idents, ident_re, is_control_flow_boundary, plain_ident_re,
@@ -292,10 +293,14 @@ pub(crate) fn run_immediate_single_use_inline_bundle_with_cache(
                 continue;
             }
 
+            let next_line = out[next_idx].clone();
             let rewritten = ident_re
-                .replace_all(&out[next_idx], |m: &Captures<'_>| {
+                .replace_all(&next_line, |m: &Captures<'_>| {
                     let ident = m.get(0).map(|mm| mm.as_str()).unwrap_or("");
-                    if ident == lhs {
+                    let is_label = m
+                        .get(0)
+                        .is_some_and(|mm| helper_ident_is_named_label(&next_line, mm.end()));
+                    if ident == lhs && !is_label {
                         rhs.to_string()
                     } else {
                         ident.to_string()
@@ -649,3 +649,19 @@ pub(crate) fn does_not_hoist_two_use_vector_helper_calls_within_single_assignmen
         "{joined}"
     );
 }
+
+#[test]
+pub(crate) fn keeps_effectful_helper_call_statement_after_inlining() {
+    let input = "\
+Sym_1 <- function(x) \n\
+{\n\
+  return(cat(x))\n\
+}\n\
+Sym_5 <- function(x) \n\
+{\n\
+  Sym_1(x)\n\
+  return(x)\n\
+}\n";
+    let out = optimize_emitted_r(input, true);
+    assert!(out.contains("cat(x)"), "{out}");
+}
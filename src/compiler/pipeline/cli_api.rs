@@ -177,6 +177,7 @@ pub enum ParallelBackend {
     Auto,
     R,
     OpenMp,
+    Psock,
 }
 
 impl ParallelBackend {
@@ -185,6 +186,7 @@ impl ParallelBackend {
             Self::Auto => "auto",
             Self::R => "r",
             Self::OpenMp => "openmp",
+            Self::Psock => "psock",
         }
     }
 }
@@ -210,6 +212,7 @@ impl std::str::FromStr for ParallelBackend {
             "auto" => Ok(Self::Auto),
             "r" => Ok(Self::R),
             "openmp" => Ok(Self::OpenMp),
+            "psock" => Ok(Self::Psock),
             _ => Err(()),
         }
     }
@@ -321,6 +324,21 @@ pub(crate) fn parse_nonnegative_usize_env(key: &str) -> Option<usize> {
         .and_then(|raw| raw.trim().parse::<usize>().ok())
 }
 
+thread_local! {
+    static QUIET_LOG_SCOPE: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+}
+
+/// Run `f` with compile progress logging suppressed on this thread, as if
+/// `RR_QUIET_LOG` were set. For tools that compile behind their own output.
+pub(crate) fn with_quiet_log<T>(f: impl FnOnce() -> T) -> T {
+    QUIET_LOG_SCOPE.with(|slot| {
+        let prev = slot.replace(true);
+        let out = f();
+        slot.set(prev);
+        out
+    })
+}
+
 pub(crate) fn env_truthy(key: &str) -> bool {
     env::var(key)
         .ok()
@@ -365,7 +383,7 @@ impl CliLog {
         let no_color = env::var_os("NO_COLOR").is_some();
         let force_color = env::var_os("RR_FORCE_COLOR").is_some();
         let force_verbose = env::var_os("RR_VERBOSE_LOG").is_some();
-        let quiet = env_truthy("RR_QUIET_LOG");
+        let quiet = env_truthy("RR_QUIET_LOG") || QUIET_LOG_SCOPE.with(|slot| slot.get());
         let slow_step_ms = parse_nonnegative_usize_env("RR_SLOW_STEP_MS").unwrap_or(3000);
         let slow_step_repeat_ms = parse_nonnegative_usize_env("RR_SLOW_STEP_REPEAT_MS")
             .unwrap_or(6000)
@@ -1,4 +1,5 @@
 use super::*;
+use crate::compiler::emit_stage::EmitStage;
 
 pub(crate) fn runtime_roots_for_output(
     final_output: &str,
@@ -307,6 +308,9 @@ pub(crate) fn compile_with_pipeline_request(
         )?;
 
         let mut all_fns = program.take_all_fns_map()?;
+        crate::compiler::emit_stage::record_typed(&all_fns);
+        crate::compiler::emit_stage::record_mir(EmitStage::Mir, &all_fns);
+        check_parallel_for_loops(&ui, &mut all_fns, request.parallel_cfg.mode)?;
         let tachyon_metrics = run_tachyon_phase(TachyonPhaseRequest {
             ui: &ui,
             total_steps: TOTAL_STEPS,
@@ -318,6 +322,7 @@ pub(crate) fn compile_with_pipeline_request(
             optimized_mir_cache_root: request.optimized_mir_cache_root.as_deref(),
         })?;
         verify_emittable_program(&all_fns)?;
+        crate::compiler::emit_stage::record_mir(EmitStage::MirOpt, &all_fns);
         program.restore_all_fns_map(all_fns)?;
         let top_level_call_names = program.top_level_call_names();
         let emit_order = if request.output_opts.preserve_all_defs {
@@ -412,6 +417,7 @@ pub(crate) fn compile_with_pipeline_request(
         };
         let final_source_map =
             shifted_source_map_for_final_output_prefix(final_source_map, &final_code);
+        crate::compiler::emit_stage::record_r(EmitStage::R, &final_code);
         let total_elapsed = compile_started.elapsed();
         ui.pulse_success(total_elapsed);
 
@@ -472,3 +478,45 @@ pub(crate) fn verify_emittable_program(
     }
     Ok(())
 }
+
+/// Run the front end and MIR synthesis on `entry_input` without optimizing
+/// or emitting, returning every function sorted by name. Tools that inspect
+/// lowered MIR (lints, the REPL) use this instead of the full pipeline.
+pub(crate) fn synthesize_unoptimized_mir(
+    entry_path: &str,
+    entry_input: &str,
+) -> crate::error::RR<Vec<crate::mir::def::FnIR>> {
+    crate::pkg::with_project_root_hint(entry_path, || {
+        let ui = CliLog {
+            quiet: true,
+            detailed: false,
+            ..CliLog::new()
+        };
+        let (
+            SourceAnalysisOutput {
+                desugared_hir,
+                global_symbols,
+            },
+            _,
+        ) = run_source_analysis_and_canonicalization(
+            &ui,
+            entry_path,
+            entry_input,
+            2,
+            CompileOutputOptions::default(),
+        )?;
+        let scheduler = CompilerScheduler::new(CompilerParallelConfig::default());
+        let (mut program, _) = run_mir_synthesis(
+            &ui,
+            2,
+            desugared_hir,
+            &global_symbols,
+            default_type_config(),
+            &scheduler,
+        )?;
+        let mut fns: Vec<crate::mir::def::FnIR> =
+            program.take_all_fns_map()?.into_values().collect();
+        fns.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(fns)
+    })
+}
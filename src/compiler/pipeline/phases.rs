@@ -4,6 +4,10 @@
 //! Tachyon execution and runtime injection live in `tachyon_runtime.rs`.
 use super::*;
 
+#[path = "phases/parallel_for.rs"]
+pub(crate) mod parallel_for;
+pub(crate) use parallel_for::*;
+
 #[path = "phases/source_emit.rs"]
 pub(crate) mod source_emit;
 pub(crate) use source_emit::*;
@@ -0,0 +1,147 @@
+//! Parallel-safety check for explicit `parallel for` loops.
+//!
+//! HIR lowering turns each loop into `rr_parallel_for(label, lo, hi, body,
+//! kinds, inits, reject)`, where `reject` holds the first syntactic reason the
+//! loop is unsafe (or is empty). Here the lifted body is handed to the poly
+//! dependence proof. A proven loop keeps the parallel helper; any other loop
+//! is rewritten to `rr_parallel_for_serial`, which runs it once over the whole
+//! range. Under `--parallel-mode required` the rejection is a compile error.
+use super::*;
+use crate::error::{RRCode, RRException, Stage};
+use crate::mir::def::{FnIR, Lit, ValueId, ValueKind};
+
+const PARALLEL_FOR: &str = "rr_parallel_for";
+const PARALLEL_FOR_SERIAL: &str = "rr_parallel_for_serial";
+
+pub(crate) fn check_parallel_for_loops(
+    ui: &CliLog,
+    all_fns: &mut FxHashMap<String, FnIR>,
+    mode: ParallelMode,
+) -> crate::error::RR<()> {
+    let mut sites = Vec::new();
+    for (name, fn_ir) in all_fns.iter() {
+        for (vid, value) in fn_ir.values.iter().enumerate() {
+            if matches!(&value.kind, ValueKind::Call { callee, args, .. }
+                if callee == PARALLEL_FOR && args.len() == 7)
+            {
+                sites.push((name.clone(), vid));
+            }
+        }
+    }
+    sites.sort();
+
+    for (fn_name, vid) in sites {
+        let fn_ir = &all_fns[&fn_name];
+        let ValueKind::Call { args, .. } = &fn_ir.values[vid].kind else {
+            continue;
+        };
+        let span = fn_ir.values[vid].span;
+        let label = const_str(fn_ir, args[0]).unwrap_or_default();
+        let verdict = match const_str(fn_ir, args[6]).filter(|reason| !reason.is_empty()) {
+            Some(reason) => Err(parse_reject(&reason)),
+            None => prove_site(all_fns, fn_ir, args),
+        };
+
+        let Some(fn_ir) = all_fns.get_mut(&fn_name) else {
+            continue;
+        };
+        if let ValueKind::Call {
+            callee,
+            args,
+            names,
+        } = &mut fn_ir.values[vid].kind
+        {
+            if verdict.is_err() {
+                *callee = PARALLEL_FOR_SERIAL.to_string();
+            }
+            args.truncate(6);
+            names.truncate(6);
+        }
+
+        let Err((code, reason)) = verdict else {
+            continue;
+        };
+        if mode == ParallelMode::Required {
+            return Err(RRException::new(
+                "RR.ParallelError",
+                code,
+                Stage::Mir,
+                format!("parallel for cannot run in parallel: {}", reason),
+            )
+            .at(span)
+            .note("--parallel-mode required rejects loops that fall back to serial execution"));
+        }
+        ui.warn(&format!(
+            "parallel for at {} runs serially [{}]: {}",
+            label,
+            code.as_str(),
+            reason
+        ));
+    }
+    Ok(())
+}
+
+fn const_str(fn_ir: &FnIR, vid: ValueId) -> Option<String> {
+    match &fn_ir.values.get(vid)?.kind {
+        ValueKind::Const(Lit::Str(text)) => Some(text.clone()),
+        _ => None,
+    }
+}
+
+/// `"E1032: reason"` as recorded by HIR lowering.
+fn parse_reject(reason: &str) -> (RRCode, String) {
+    reason
+        .split_once(": ")
+        .and_then(|(code, text)| Some((code.parse::<RRCode>().ok()?, text.to_string())))
+        .unwrap_or((RRCode::E1030, reason.to_string()))
+}
+
+fn prove_site(
+    all_fns: &FxHashMap<String, FnIR>,
+    fn_ir: &FnIR,
+    args: &[ValueId],
+) -> Result<(), (RRCode, String)> {
+    let unanalyzable = || (RRCode::E1030, "loop body could not be analyzed".to_string());
+    let body_name = match &fn_ir.values[args[3]].kind {
+        ValueKind::Call { callee, args, .. } if callee == "rr_closure_make" => args
+            .first()
+            .and_then(|arg| load_name(fn_ir, *arg))
+            .ok_or_else(unanalyzable)?,
+        _ => load_name(fn_ir, args[3]).ok_or_else(unanalyzable)?,
+    };
+    let body = all_fns.get(&body_name).ok_or_else(unanalyzable)?;
+
+    let ValueKind::RecordLit { fields } = &fn_ir.values[args[5]].kind else {
+        return Err(unanalyzable());
+    };
+    let kinds = match &fn_ir.values[args[4]].kind {
+        ValueKind::Call { callee, args, .. } if callee == "c" => args
+            .iter()
+            .map(|arg| const_str(fn_ir, *arg))
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(unanalyzable)?,
+        _ => return Err(unanalyzable()),
+    };
+    let slice_vars = fields
+        .iter()
+        .zip(&kinds)
+        .filter(|(_, kind)| kind.as_str() == "slice")
+        .map(|((name, _), _)| name.clone())
+        .collect::<Vec<_>>();
+
+    if crate::mir::analyze::effects::fn_uses_rng(body) {
+        return Err((
+            RRCode::E1030,
+            "the body draws random numbers, which depends on the chunking".to_string(),
+        ));
+    }
+    crate::mir::opt::poly::prove_parallel_loop(body, &slice_vars)
+        .map_err(|reason| (RRCode::E1030, reason))
+}
+
+fn load_name(fn_ir: &FnIR, vid: ValueId) -> Option<String> {
+    match &fn_ir.values.get(vid)?.kind {
+        ValueKind::Load { var } => Some(var.clone()),
+        _ => None,
+    }
+}
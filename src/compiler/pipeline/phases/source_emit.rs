@@ -19,6 +19,10 @@ pub(crate) struct CachedModuleArtifact {
     pub(crate) public_symbols: Vec<String>,
     pub(crate) public_function_arities: Vec<(String, usize)>,
     pub(crate) emit_roots: Vec<String>,
+    /// Names selected from each imported module. Cached modules are checked
+    /// against their imports' current exports after every module is loaded.
+    #[serde(default)]
+    pub(crate) selected_imports: Vec<(String, Vec<String>)>,
     pub(crate) module_fingerprint: u64,
     pub(crate) symbols: Vec<(u32, String)>,
     #[serde(default)]
@@ -47,6 +51,7 @@ pub(crate) struct MirLowerJob {
 /// Synthetic lowering job used for module top-level entry shims.
 pub(crate) struct TopLevelMirLowerJob {
     pub(crate) fn_name: String,
+    pub(crate) var_names: FxHashMap<crate::hir::def::LocalId, String>,
     pub(crate) hir_fn: crate::hir::def::HirFn,
 }
 
@@ -610,6 +615,8 @@ pub(crate) mod cached_emit;
 pub(crate) mod mir_synthesis;
 #[path = "source_emit/module_artifacts.rs"]
 pub(crate) mod module_artifacts;
+#[path = "source_emit/module_scope.rs"]
+pub(crate) mod module_scope;
 #[path = "source_emit/raw_emit.rs"]
 pub(crate) mod raw_emit;
 #[path = "source_emit/source_analysis.rs"]
@@ -1,5 +1,6 @@
 use super::raw_emit::*;
 use super::*;
+use crate::compiler::emit_stage::EmitStage;
 use crate::mir::FnIR;
 use assembly_cache::store_optimized_assembly_cache_state;
 use metrics::{EmitMetricContext, EmitStageTimings, OptimizedAssemblyHits};
@@ -712,6 +713,7 @@ pub(crate) fn emit_r_functions_cached(
     if let Some(path) = std::env::var_os("RR_DEBUG_RAW_R_PATH") {
         let _ = std::fs::write(path, &final_output);
     }
+    crate::compiler::emit_stage::record_r(EmitStage::RawR, &final_output);
     let peephole_cache_key = cache.map(|_| {
         crate::compiler::pipeline::peephole_output_cache_key(
             &final_output,
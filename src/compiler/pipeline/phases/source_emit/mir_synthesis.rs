@@ -81,6 +81,18 @@ pub(crate) fn run_mir_synthesis(
 
         if !top_level_stmts.is_empty() {
             let top_fn_name = format!("Sym_top_{}", module.id.0);
+            let body = crate::hir::def::HirBlock {
+                stmts: top_level_stmts,
+                span: crate::utils::Span::default(),
+            };
+            // Top-level locals normally stay SSA-only. A writing `unsafe r`
+            // block needs them bound by name so later reads reload them.
+            let local_names =
+                if crate::mir::lower_hir::MirLowerer::block_contains_write_unsafe_r(&body) {
+                    top_level_local_names(&body, global_symbols)
+                } else {
+                    FxHashMap::default()
+                };
             let top_fn = crate::hir::def::HirFn {
                 id: crate::hir::def::FnId(1_000_000 + module.id.0),
                 name: crate::hir::def::SymbolId(1_000_000 + module.id.0),
@@ -90,20 +102,18 @@ pub(crate) fn run_mir_synthesis(
                 has_varargs: false,
                 ret_ty: None,
                 ret_ty_inferred: false,
-                body: crate::hir::def::HirBlock {
-                    stmts: top_level_stmts,
-                    span: crate::utils::Span::default(),
-                },
+                body,
                 attrs: crate::hir::def::HirFnAttrs {
                     inline_hint: crate::hir::def::InlineHint::Never,
                     tidy_safe: false,
                 },
                 span: crate::utils::Span::default(),
-                local_names: FxHashMap::default(),
+                local_names: local_names.clone(),
                 public: false,
             };
             top_jobs.push(TopLevelMirLowerJob {
                 fn_name: top_fn_name.clone(),
+                var_names: local_names,
                 hir_fn: top_fn,
             });
             meta_by_name.insert(top_fn_name.clone(), (false, true));
@@ -151,7 +161,7 @@ pub(crate) fn run_mir_synthesis(
             let lowerer = crate::mir::lower_hir::MirLowerer::new(
                 job.fn_name.clone(),
                 Vec::new(),
-                FxHashMap::default(),
+                job.var_names,
                 global_symbols,
                 &known_fn_arities,
             );
@@ -193,3 +203,43 @@ pub(crate) fn run_mir_synthesis(
         },
     ))
 }
+
+/// R names for the `let` bindings of top-level statements. A name declared
+/// more than once is left out so shadowed bindings never share a frame slot.
+fn top_level_local_names(
+    body: &crate::hir::def::HirBlock,
+    global_symbols: &FxHashMap<crate::hir::def::SymbolId, String>,
+) -> FxHashMap<crate::hir::def::LocalId, String> {
+    fn collect(
+        block: &crate::hir::def::HirBlock,
+        out: &mut Vec<(crate::hir::def::LocalId, crate::hir::def::SymbolId)>,
+    ) {
+        for stmt in &block.stmts {
+            match stmt {
+                crate::hir::def::HirStmt::Let { local, name, .. } => out.push((*local, *name)),
+                crate::hir::def::HirStmt::If {
+                    then_blk, else_blk, ..
+                } => {
+                    collect(then_blk, out);
+                    if let Some(else_blk) = else_blk {
+                        collect(else_blk, out);
+                    }
+                }
+                crate::hir::def::HirStmt::While { body, .. }
+                | crate::hir::def::HirStmt::For { body, .. } => collect(body, out),
+                _ => {}
+            }
+        }
+    }
+
+    let mut lets = Vec::new();
+    collect(body, &mut lets);
+    let mut counts: FxHashMap<crate::hir::def::SymbolId, usize> = FxHashMap::default();
+    for (_, name) in &lets {
+        *counts.entry(*name).or_default() += 1;
+    }
+    lets.into_iter()
+        .filter(|(_, name)| counts.get(name) == Some(&1))
+        .filter_map(|(local, name)| Some((local, global_symbols.get(&name)?.clone())))
+        .collect()
+}
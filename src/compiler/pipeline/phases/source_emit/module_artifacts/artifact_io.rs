@@ -25,12 +25,13 @@ pub(crate) fn build_cached_module_artifact(
     let public_symbols = collect_public_symbols_from_module(module, &symbol_map);
     let public_function_arities = collect_public_function_arities(module, &symbol_map);
     let emit_roots = collect_emit_roots(module, &symbol_map);
+    let selected_imports = collect_selected_imports(module, &symbol_map);
     let module_fingerprint = serde_json::to_vec(module)
         .map(|bytes| stable_hash_bytes(&bytes))
         .unwrap_or(0);
     Ok(CachedModuleArtifact {
         schema: "rr-module-artifact".to_string(),
-        schema_version: 2,
+        schema_version: 3,
         compiler_version: env!("CARGO_PKG_VERSION").to_string(),
         canonical_path: module_path.to_string_lossy().to_string(),
         source_len,
@@ -38,6 +39,7 @@ pub(crate) fn build_cached_module_artifact(
         public_symbols,
         public_function_arities,
         emit_roots,
+        selected_imports,
         module_fingerprint,
         symbols: symbol_entries
             .into_iter()
@@ -113,7 +115,7 @@ pub(crate) fn load_module_artifact(
         Err(_) => return Ok(None),
     };
     if artifact.schema != "rr-module-artifact"
-        || artifact.schema_version != 2
+        || artifact.schema_version != 3
         || artifact.compiler_version != env!("CARGO_PKG_VERSION")
         || artifact.canonical_path != module_path.to_string_lossy()
     {
@@ -41,13 +41,14 @@ pub(crate) fn enqueue_ast_module_imports(
 ) -> crate::error::RR<usize> {
     let mut targets = Vec::new();
     for stmt in stmts {
-        let crate::syntax::ast::StmtKind::Import {
-            source: crate::syntax::ast::ImportSource::Module,
-            path,
-            ..
-        } = &stmt.kind
-        else {
-            continue;
+        let path = match &stmt.kind {
+            crate::syntax::ast::StmtKind::Import {
+                source: crate::syntax::ast::ImportSource::Module,
+                path,
+                ..
+            }
+            | crate::syntax::ast::StmtKind::ReExport { path, .. } => path,
+            _ => continue,
         };
         let target = crate::pkg::resolve_import_path(curr_path, path)?;
         if loaded_paths.contains(&target) {
@@ -17,12 +17,12 @@ pub(crate) fn collect_public_symbols_from_module(
 ) -> Vec<String> {
     let mut names = Vec::new();
     for item in &module.items {
-        if let crate::hir::def::HirItem::Fn(f) = item
-            && f.public
-            && f.type_params.is_empty()
-            && let Some(name) = symbol_map.get(&f.name)
-        {
-            names.push(name.clone());
+        match item {
+            crate::hir::def::HirItem::Fn(f) if f.public && f.type_params.is_empty() => {
+                names.extend(symbol_map.get(&f.name).cloned());
+            }
+            crate::hir::def::HirItem::Export(sym) => names.extend(symbol_map.get(sym).cloned()),
+            _ => {}
         }
     }
     names.sort();
@@ -30,6 +30,36 @@ pub(crate) fn collect_public_symbols_from_module(
     names
 }
 
+/// Names each module import selects (`import { ... }`, `export { ... } from`,
+/// and the members used through `import * as ns`), keyed by import path.
+pub(crate) fn collect_selected_imports(
+    module: &crate::hir::def::HirModule,
+    symbol_map: &FxHashMap<crate::hir::def::SymbolId, String>,
+) -> Vec<(String, Vec<String>)> {
+    let mut out: Vec<(String, Vec<String>)> = Vec::new();
+    for item in &module.items {
+        let crate::hir::def::HirItem::Import(imp) = item else {
+            continue;
+        };
+        let syms = match &imp.spec {
+            crate::hir::def::HirImportSpec::Glob => continue,
+            crate::hir::def::HirImportSpec::Names(names)
+            | crate::hir::def::HirImportSpec::Namespace { names, .. } => names,
+        };
+        let names = syms.iter().filter_map(|sym| symbol_map.get(sym).cloned());
+        match out.iter_mut().find(|(path, _)| *path == imp.module) {
+            Some((_, selected)) => selected.extend(names),
+            None => out.push((imp.module.clone(), names.collect())),
+        }
+    }
+    for (_, names) in &mut out {
+        names.sort();
+        names.dedup();
+    }
+    out.sort_by(|a, b| a.0.cmp(&b.0));
+    out
+}
+
 pub(crate) fn collect_public_function_arities(
     module: &crate::hir::def::HirModule,
     symbol_map: &FxHashMap<crate::hir::def::SymbolId, String>,
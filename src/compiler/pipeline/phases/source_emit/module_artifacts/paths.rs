@@ -12,6 +12,19 @@ pub(crate) fn module_artifact_cache_root(entry_path: &str) -> PathBuf {
         return PathBuf::from(v).join("modules");
     }
 
+    if let Some(root) = project_root_for_entry(entry_path) {
+        return root.join("Build").join("incremental").join("modules");
+    }
+    normalize_module_path(Path::new(entry_path))
+        .parent()
+        .unwrap_or(Path::new("."))
+        .join(".rr-cache")
+        .join("modules")
+}
+
+/// Nearest ancestor of the entry that is a managed (`rr.mod`, `src/main.rr`,
+/// `src/lib.rr`) or legacy (`main.rr` outside `src/`) project root.
+pub(crate) fn project_root_for_entry(entry_path: &str) -> Option<PathBuf> {
     let normalized_entry = normalize_module_path(Path::new(entry_path));
     let mut cur = normalized_entry
         .parent()
@@ -24,16 +37,9 @@ pub(crate) fn module_artifact_cache_root(entry_path: &str) -> PathBuf {
         let legacy_root = cur.file_name().and_then(|name| name.to_str()) != Some("src")
             && cur.join("main.rr").is_file();
         if managed_root || legacy_root {
-            return cur.join("Build").join("incremental").join("modules");
+            return Some(cur);
         }
-        let Some(parent) = cur.parent() else {
-            return normalized_entry
-                .parent()
-                .unwrap_or(Path::new("."))
-                .join(".rr-cache")
-                .join("modules");
-        };
-        cur = parent.to_path_buf();
+        cur = cur.parent()?.to_path_buf();
     }
 }
 
@@ -0,0 +1,314 @@
+//! Cross-module checks that need every loaded module: names selected by
+//! `import { ... }`, `import * as ns` and `export { ... } from` must be
+//! exported by their module, a function name may be defined by only one
+//! module because all module functions share one R namespace, and a module
+//! may call a function it reaches through `import { ... }` or `import * as`
+//! unqualified only when it selected that name or imported the whole module.
+//!
+//! The checks run on HIR, so modules loaded from the artifact cache are
+//! re-validated against the current exports of the modules they import.
+//! Spans carry no file, so only diagnostics in the entry module get one; the
+//! others name the module's path instead.
+use super::*;
+use crate::error::{RRCode, RRException};
+use crate::hir::def::{HirImportSpec, HirItem, HirModule, SymbolId};
+
+struct ModuleScope<'a> {
+    path: &'a Path,
+    module: &'a HirModule,
+    defined: FxHashSet<&'a str>,
+    traits: FxHashSet<&'a str>,
+    exports: FxHashSet<&'a str>,
+}
+
+pub(crate) fn check_module_scopes(
+    modules: &[(PathBuf, &HirModule)],
+    symbols: &FxHashMap<SymbolId, String>,
+) -> crate::error::RR<()> {
+    let name_of = |sym: &SymbolId| symbols.get(sym).map(String::as_str);
+    let scopes = modules
+        .iter()
+        .map(|(path, module)| {
+            let mut defined = FxHashSet::default();
+            let mut traits = FxHashSet::default();
+            let mut exports = FxHashSet::default();
+            for item in &module.items {
+                match item {
+                    HirItem::Fn(f) => {
+                        if let Some(name) = name_of(&f.name) {
+                            defined.insert(name);
+                            if f.public {
+                                exports.insert(name);
+                            }
+                        }
+                    }
+                    HirItem::Trait(t) => {
+                        if let Some(name) = name_of(&t.name) {
+                            traits.insert(name);
+                            if t.public {
+                                exports.insert(name);
+                            }
+                        }
+                    }
+                    HirItem::Export(sym) => exports.extend(name_of(sym)),
+                    _ => {}
+                }
+            }
+            ModuleScope {
+                path,
+                module,
+                defined,
+                traits,
+                exports,
+            }
+        })
+        .collect::<Vec<_>>();
+    let by_path = scopes
+        .iter()
+        .map(|scope| (scope.path, scope))
+        .collect::<FxHashMap<_, _>>();
+
+    let mut errors = Vec::new();
+    let mut owners: FxHashMap<&str, &Path> = FxHashMap::default();
+    for scope in &scopes {
+        let at = |err: RRException, span| {
+            if scope.module.id.0 == 0 {
+                err.at(span)
+            } else {
+                err
+            }
+        };
+        for item in &scope.module.items {
+            let HirItem::Fn(f) = item else {
+                continue;
+            };
+            let Some(name) = name_of(&f.name).filter(|name| !name.starts_with("__")) else {
+                continue;
+            };
+            match owners.get(name) {
+                Some(owner) if *owner != scope.path => errors.push(
+                    at(
+                        RRException::new(
+                            "RR.SemanticError",
+                            RRCode::E1002,
+                            Stage::Lower,
+                            format!(
+                                "function '{}' is defined in both '{}' and '{}'",
+                                name,
+                                owner.display(),
+                                scope.path.display()
+                            ),
+                        ),
+                        f.span,
+                    )
+                    .note(
+                        "RR modules share one R namespace, so `import { ... as ... }` and \
+                         `import * as` cannot tell them apart",
+                    )
+                    .help("rename one of the functions"),
+                ),
+                Some(_) => {}
+                None => {
+                    owners.insert(name, scope.path);
+                }
+            }
+        }
+
+        for item in &scope.module.items {
+            let HirItem::Import(imp) = item else {
+                continue;
+            };
+            let selected = match &imp.spec {
+                HirImportSpec::Glob => continue,
+                HirImportSpec::Names(names) => names,
+                HirImportSpec::Namespace { names, .. } => names,
+            };
+            let target_path = crate::pkg::resolve_import_path(scope.path, &imp.module)?;
+            let Some(target) = by_path.get(target_path.as_path()) else {
+                continue;
+            };
+            for name in selected.iter().filter_map(&name_of) {
+                if target.exports.contains(name) {
+                    continue;
+                }
+                let mut err = at(
+                    RRException::new(
+                        "RR.SemanticError",
+                        RRCode::E1002,
+                        Stage::Lower,
+                        format!("module '{}' does not export '{}'", imp.module, name),
+                    ),
+                    imp.span,
+                )
+                .note(format!("imported from '{}'", scope.path.display()));
+                let kind = if target.defined.contains(name) {
+                    Some("fn")
+                } else if target.traits.contains(name) {
+                    Some("trait")
+                } else {
+                    None
+                };
+                if let Some(kind) = kind {
+                    err = err.help(format!(
+                        "declare it with `export {} {}` in '{}'",
+                        kind,
+                        name,
+                        target.path.display()
+                    ));
+                }
+                errors.push(err);
+            }
+        }
+    }
+
+    for scope in &scopes {
+        let view = import_view(scope, &by_path, &name_of)?;
+        for (sym, span) in &scope.module.unqualified_refs {
+            let Some(name) = name_of(sym) else {
+                continue;
+            };
+            let Some(owner) = owners.get(name).and_then(|owner| by_path.get(owner)) else {
+                continue;
+            };
+            if !view.restricted.contains(owner.path) || view.visible.contains(name) {
+                continue;
+            }
+            let mut err = RRException::new(
+                "RR.SemanticError",
+                RRCode::E1002,
+                Stage::Lower,
+                format!(
+                    "function '{}' from '{}' is not imported here",
+                    name,
+                    owner.path.display()
+                ),
+            )
+            .note(format!("referenced in '{}'", scope.path.display()));
+            if scope.module.id.0 == 0 {
+                err = err.at(*span);
+            }
+            err = if !owner.exports.contains(name) {
+                err.note(format!(
+                    "'{}' is not exported by '{}'",
+                    name,
+                    owner.path.display()
+                ))
+            } else if let Some(alias) = namespace_alias_for(scope, owner.path) {
+                err.help(format!("call it as `{}.{}`", alias, name))
+            } else {
+                err.help(format!(
+                    "add '{}' to the `import {{ ... }}` list for '{}'",
+                    name,
+                    owner.path.display()
+                ))
+            };
+            errors.push(err);
+        }
+    }
+
+    match errors.len() {
+        0 => Ok(()),
+        1 => Err(errors.remove(0)),
+        count => Err(RRException::aggregate(
+            "RR.SemanticError",
+            RRCode::E1002,
+            Stage::Lower,
+            format!("module import check failed: {} error(s)", count),
+            errors,
+        )),
+    }
+}
+
+/// What a module can call unqualified, and which modules it reaches only
+/// through `import { ... }` or `import * as`.
+struct ImportView<'a> {
+    /// Its own functions, names selected by `import { ... }`, and every
+    /// function reachable through `import "path.rr"`, which keeps its original
+    /// meaning and also exposes what the imported module pulls in that way.
+    visible: FxHashSet<&'a str>,
+    /// Modules behind a selective or namespace import, including the modules
+    /// those import in turn, that no whole-module import exposes. Files the
+    /// module reaches some other way, such as siblings of a package directory
+    /// import, keep sharing the R namespace unchecked.
+    restricted: FxHashSet<&'a Path>,
+}
+
+fn import_view<'a>(
+    scope: &ModuleScope<'a>,
+    by_path: &FxHashMap<&Path, &ModuleScope<'a>>,
+    name_of: &impl Fn(&SymbolId) -> Option<&'a str>,
+) -> crate::error::RR<ImportView<'a>> {
+    let mut visible = scope.defined.clone();
+    let mut globbed = Vec::new();
+    let mut selective = Vec::new();
+    for item in &scope.module.items {
+        let HirItem::Import(imp) = item else {
+            continue;
+        };
+        let path = crate::pkg::resolve_import_path(scope.path, &imp.module)?;
+        match &imp.spec {
+            HirImportSpec::Glob => globbed.push(path),
+            HirImportSpec::Names(names) => {
+                visible.extend(names.iter().filter_map(name_of));
+                selective.push(path);
+            }
+            HirImportSpec::Namespace { .. } => selective.push(path),
+        }
+    }
+
+    let mut exposed = FxHashSet::default();
+    while let Some(path) = globbed.pop() {
+        let Some(target) = by_path.get(path.as_path()) else {
+            continue;
+        };
+        if !exposed.insert(target.path) {
+            continue;
+        }
+        visible.extend(target.defined.iter().copied());
+        visible.extend(target.exports.iter().copied());
+        for item in &target.module.items {
+            if let HirItem::Import(imp) = item
+                && matches!(imp.spec, HirImportSpec::Glob)
+            {
+                globbed.push(crate::pkg::resolve_import_path(target.path, &imp.module)?);
+            }
+        }
+    }
+
+    let mut reached = FxHashSet::default();
+    while let Some(path) = selective.pop() {
+        let Some(target) = by_path.get(path.as_path()) else {
+            continue;
+        };
+        if !reached.insert(target.path) {
+            continue;
+        }
+        for item in &target.module.items {
+            if let HirItem::Import(imp) = item {
+                selective.push(crate::pkg::resolve_import_path(target.path, &imp.module)?);
+            }
+        }
+    }
+    let restricted = reached
+        .into_iter()
+        .filter(|path| *path != scope.path && !exposed.contains(path))
+        .collect();
+    Ok(ImportView {
+        visible,
+        restricted,
+    })
+}
+
+/// The `import * as` alias this module uses for `target`, if any.
+fn namespace_alias_for<'a>(scope: &ModuleScope<'a>, target: &Path) -> Option<&'a str> {
+    scope.module.items.iter().find_map(|item| match item {
+        HirItem::Import(imp) => match &imp.spec {
+            HirImportSpec::Namespace { alias, .. } => {
+                let path = crate::pkg::resolve_import_path(scope.path, &imp.module).ok()?;
+                (path.as_path() == target).then_some(alias.as_str())
+            }
+            _ => None,
+        },
+        _ => None,
+    })
+}
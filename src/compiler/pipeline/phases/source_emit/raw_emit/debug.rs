@@ -1,4 +1,5 @@
 use super::*;
+use crate::compiler::emit_stage::EmitStage;
 use std::time::Instant;
 
 pub(crate) fn contains_unsafe_r_escape(output: &str) -> bool {
@@ -13,9 +14,10 @@ pub(crate) fn maybe_emit_raw_debug_output(
     output_opts: CompileOutputOptions,
     cache: Option<&dyn EmitFunctionCache>,
 ) -> crate::error::RR<u128> {
-    let Some(path) = std::env::var_os("RR_DEBUG_RAW_R_PATH") else {
+    let path = std::env::var_os("RR_DEBUG_RAW_R_PATH");
+    if path.is_none() && !crate::compiler::emit_stage::stage_requested(EmitStage::RawR) {
         return Ok(0);
-    };
+    }
     let started = Instant::now();
     let raw_output = if contains_generated_poly_loop_controls(assembled_output)
         || contains_unsafe_r_escape(assembled_output)
@@ -47,6 +49,9 @@ pub(crate) fn maybe_emit_raw_debug_output(
             apply_full_raw_rewrites(assembled_output.to_string(), pure_user_calls, output_opts)
         }
     };
-    let _ = std::fs::write(path, &raw_output);
+    if let Some(path) = path {
+        let _ = std::fs::write(path, &raw_output);
+    }
+    crate::compiler::emit_stage::record_r(EmitStage::RawR, &raw_output);
     Ok(started.elapsed().as_nanos())
 }
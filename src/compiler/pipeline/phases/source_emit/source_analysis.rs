@@ -14,6 +14,14 @@ pub(crate) fn run_source_analysis_and_canonicalization(
     let module_cache_root = module_artifact_cache_root(entry_path);
 
     let entry_abs = normalize_module_path(Path::new(entry_path));
+    let instrument_modules = crate::compiler::coverage::instrumenting_modules();
+    if crate::compiler::emit_stage::any_stage_requested() || instrument_modules {
+        let root = project_root_for_entry(entry_path)
+            .or_else(|| entry_abs.parent().map(Path::to_path_buf))
+            .unwrap_or_default();
+        crate::compiler::emit_stage::set_source_root(root.clone());
+        crate::compiler::coverage::set_module_root(root);
+    }
     loaded_paths.insert(entry_abs.clone());
     queue.push_back(ModuleLoadJob {
         path: entry_abs,
@@ -33,6 +41,7 @@ pub(crate) fn run_source_analysis_and_canonicalization(
         "parse + scope resolution",
     );
     let mut hir_modules = Vec::new();
+    let mut module_paths: FxHashMap<u32, PathBuf> = FxHashMap::default();
     let mut hir_lowerer =
         crate::hir::lower::Lowerer::with_policy(crate::hir::lower::LoweringPolicy {
             strict_let: output_opts.strict_let,
@@ -56,7 +65,8 @@ pub(crate) fn run_source_analysis_and_canonicalization(
         let curr_path_str = curr_path.to_string_lossy().to_string();
         ui.trace(&format!("module#{}", mod_id), &curr_path_str);
 
-        if !is_entry && ast.is_none() {
+        // Cached artifacts hold uninstrumented modules.
+        if !is_entry && ast.is_none() && !instrument_modules {
             let artifact_started = Instant::now();
             if let Some(module) = load_module_artifact(
                 &module_cache_root,
@@ -74,6 +84,7 @@ pub(crate) fn run_source_analysis_and_canonicalization(
                     &mut queue,
                     &mut next_mod_id,
                 )?;
+                module_paths.insert(mod_id, curr_path);
                 hir_modules.push(module);
                 continue;
             }
@@ -81,14 +92,16 @@ pub(crate) fn run_source_analysis_and_canonicalization(
         }
 
         let source_started = Instant::now();
-        let ast_prog = if let Some(ast_prog) = ast {
+        let mut ast_prog = if let Some(ast_prog) = ast {
             ast_prog
         } else {
             let content = if let Some(content) = content {
                 content
             } else {
                 match fs::read_to_string(&curr_path) {
-                    Ok(content) => content,
+                    Ok(content) => {
+                        crate::compiler::coverage::instrument_module(&curr_path, content)
+                    }
                     Err(e) => {
                         return Err(crate::error::RRException::new(
                             "RR.ParseError",
@@ -100,6 +113,7 @@ pub(crate) fn run_source_analysis_and_canonicalization(
                 }
             };
             parsed_modules += 1;
+            crate::compiler::emit_stage::record_tokens(&curr_path, &content);
             let mut parser = Parser::new(&content);
             match parser.parse_program() {
                 Ok(p) => p,
@@ -134,6 +148,11 @@ pub(crate) fn run_source_analysis_and_canonicalization(
             }
         }
 
+        crate::compiler::emit_stage::record_ast(&curr_path, &ast_prog);
+        if let Err(e) = hir_lowerer.resolve_trait_import_aliases(&mut ast_prog) {
+            load_errors.push(e);
+            continue;
+        }
         let source_metadata = ast_prog.clone();
         let hir_mod = match hir_lowerer.lower_module(ast_prog, crate::hir::def::ModuleId(mod_id)) {
             Ok(v) => v,
@@ -159,15 +178,18 @@ pub(crate) fn run_source_analysis_and_canonicalization(
         let desugared_module = desugar_single_module(hir_mod)?;
         canonicalization_elapsed_ns += canonicalize_started.elapsed().as_nanos();
         if !is_entry {
-            store_module_artifact(
-                &module_cache_root,
-                &curr_path,
-                &desugared_module,
-                &hir_lowerer,
-                source_metadata.clone(),
-            )?;
+            if !instrument_modules {
+                store_module_artifact(
+                    &module_cache_root,
+                    &curr_path,
+                    &desugared_module,
+                    &hir_lowerer,
+                    source_metadata.clone(),
+                )?;
+            }
             hir_lowerer.prune_private_module_metadata(&source_metadata);
         }
+        module_paths.insert(mod_id, curr_path);
         hir_modules.push(desugared_module);
     }
     if !load_errors.is_empty() {
@@ -189,10 +211,28 @@ pub(crate) fn run_source_analysis_and_canonicalization(
     ));
 
     hir_modules.sort_by_key(|module| module.id.0);
+    let global_symbols = hir_lowerer.into_symbols();
+    let scoped_modules = hir_modules
+        .iter()
+        .filter_map(|module| Some((module_paths.get(&module.id.0)?.clone(), module)))
+        .collect::<Vec<_>>();
+    super::module_scope::check_module_scopes(&scoped_modules, &global_symbols)?;
+    crate::compiler::emit_stage::record_hir(&scoped_modules, &global_symbols);
+    if crate::mir::opt::remarks::remarks_enabled() {
+        for (path, module) in &scoped_modules {
+            for item in &module.items {
+                if let crate::hir::def::HirItem::Fn(f) = item {
+                    crate::mir::opt::remarks::note_function_source(
+                        &format!("Sym_{}", f.name.0),
+                        path.clone(),
+                    );
+                }
+            }
+        }
+    }
     let hir_prog = crate::hir::def::HirProgram {
         modules: hir_modules,
     };
-    let global_symbols = hir_lowerer.into_symbols();
 
     let step_desugar = ui.step_start(
         2,
@@ -336,7 +336,7 @@ pub(crate) fn rewrite_slice_bound_aliases_in_raw_emitted_r(output: &str) -> Stri
         let mut use_line_idxs = Vec::new();
         for (line_no, line) in lines.iter().enumerate().skip(end_idx + 1) {
             let trimmed = line.trim();
-            if trimmed.is_empty() {
+            if trimmed.is_empty() || trimmed.starts_with("rr_mark(") {
                 continue;
             }
             if line.contains("<- function") {
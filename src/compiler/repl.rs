@@ -0,0 +1,542 @@
+//! Compile-side state for `RR repl`.
+//!
+//! Every cell is compiled as a whole program: a small prelude, the
+//! definitions entered so far, one `let` per earlier binding the cell reads,
+//! the cell itself, and a write-back for each binding the cell may change.
+//! Bindings live in the R global environment of the session, and each one is
+//! re-declared with the type inferred when it was bound, so strict `let` and
+//! type checking behave as if the whole session were one file.
+
+use crate::compiler::CompilerParallelConfig;
+use crate::compiler::incremental::{
+    IncrementalCompileRequest, IncrementalOptions, IncrementalSession, compile_incremental_request,
+};
+use crate::compiler::pipeline::{
+    CompileOutputOptions, OptLevel, default_parallel_config, default_type_config,
+    synthesize_unoptimized_mir, with_quiet_log,
+};
+use crate::error::{RR, RRCode, RRException, Stage};
+use crate::mir::def::{FnIR, ValueKind};
+use crate::syntax::ast::{ExprKind, Lit, StmtKind};
+use crate::syntax::lex::Lexer;
+use crate::syntax::parse::Parser;
+use crate::syntax::token::TokenKind;
+use crate::typeck::{PrimTy, ShapeTy, TypeState, TypeTerm};
+use rustc_hash::FxHashSet;
+
+const PRELUDE: &str = "import r { get as __repl_get, assign as __repl_assign, globalenv as __repl_globalenv } from \"base\"\n";
+const WRITE_BACK_CALLEE: &str = "base::assign";
+
+/// Name bound to the value of a cell that ends in an expression.
+pub const REPL_VALUE_NAME: &str = "it";
+
+/// Calls that print their own result; a cell ending in one of these is run
+/// as a statement instead of binding and echoing `it`.
+const SELF_PRINTING_CALLS: &[&str] = &[
+    "print",
+    "cat",
+    "message",
+    "writeLines",
+    "invisible",
+    "plot",
+    "str",
+    "warning",
+];
+
+#[derive(Clone, Debug)]
+struct Definition {
+    key: String,
+    source: String,
+}
+
+/// A value bound by an earlier cell and kept in the R session.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReplBinding {
+    pub name: String,
+    /// Inferred type, as shown to the user.
+    pub ty: String,
+    /// Type hint used when the binding is re-declared, when it has one.
+    hint: Option<String>,
+}
+
+/// A compiled cell, ready to be evaluated and then committed.
+#[derive(Clone, Debug)]
+pub struct ReplCell {
+    /// Generated R for the whole cell, including echoing `it`.
+    pub r_code: String,
+    /// Inferred type of `it` when the cell ends in an expression.
+    pub value_ty: Option<String>,
+    /// False for cells that only add definitions. Those are re-emitted with
+    /// every later cell, so they can be committed without running R.
+    pub needs_eval: bool,
+    definitions: Vec<Definition>,
+    bindings: Vec<ReplBinding>,
+}
+
+/// A compile failure together with the program text its spans refer to.
+#[derive(Debug)]
+pub struct ReplError {
+    pub source: String,
+    pub error: RRException,
+}
+
+impl ReplError {
+    fn new(source: &str, error: RRException) -> Self {
+        Self {
+            source: source.to_string(),
+            error,
+        }
+    }
+}
+
+struct ParsedCell {
+    definitions: Vec<Definition>,
+    body: Vec<String>,
+    lets: Vec<String>,
+    has_value: bool,
+}
+
+pub struct ReplSession {
+    entry_path: String,
+    opt_level: OptLevel,
+    definitions: Vec<Definition>,
+    bindings: Vec<ReplBinding>,
+    incremental: IncrementalSession,
+}
+
+impl ReplSession {
+    /// `entry_path` anchors module resolution and the incremental cache; it
+    /// does not need to exist.
+    pub fn new(entry_path: impl Into<String>, opt_level: OptLevel) -> Self {
+        Self {
+            entry_path: entry_path.into(),
+            opt_level,
+            definitions: Vec::new(),
+            bindings: Vec::new(),
+            incremental: IncrementalSession::default(),
+        }
+    }
+
+    pub fn bindings(&self) -> &[ReplBinding] {
+        &self.bindings
+    }
+
+    /// Compile one cell against the session. Nothing is recorded until the
+    /// cell is passed to [`ReplSession::commit`].
+    pub fn compile_cell(&mut self, input: &str) -> Result<ReplCell, ReplError> {
+        let parsed = parse_cell(input, true)?;
+        let definitions = merge_definitions(&self.definitions, parsed.definitions);
+        let program = self.assemble(&definitions, &parsed.body, &parsed.lets);
+        let fns = self.synthesize(&program)?;
+        let mut written = written_types(&fns);
+
+        let mut r_code = self.compile_program(&program)?;
+        let mut bindings = self.bindings.clone();
+        for name in &parsed.lets {
+            let (ty, hint) = written
+                .remove(name)
+                .unwrap_or_else(|| ("any".to_string(), None));
+            let binding = ReplBinding {
+                name: name.clone(),
+                ty,
+                hint,
+            };
+            match bindings.iter_mut().find(|b| b.name == *name) {
+                Some(slot) => *slot = binding,
+                None => bindings.push(binding),
+            }
+        }
+        let value_ty = parsed.has_value.then(|| {
+            bindings
+                .iter()
+                .find(|b| b.name == REPL_VALUE_NAME)
+                .map(|b| b.ty.clone())
+                .unwrap_or_else(|| "any".to_string())
+        });
+        if value_ty.is_some() {
+            r_code.push_str(&format!(
+                "\nif (!is.null({0})) print({0})\n",
+                REPL_VALUE_NAME
+            ));
+        }
+        Ok(ReplCell {
+            r_code,
+            value_ty,
+            needs_eval: !parsed.body.is_empty(),
+            definitions,
+            bindings,
+        })
+    }
+
+    /// Record the definitions and bindings of a cell that evaluated cleanly.
+    pub fn commit(&mut self, cell: ReplCell) {
+        self.definitions = cell.definitions;
+        self.bindings = cell.bindings;
+    }
+
+    /// Inferred type of `expr` in the current session, without running it.
+    pub fn type_of(&self, expr: &str) -> Result<String, ReplError> {
+        let input = format!("let {} = {}", REPL_VALUE_NAME, expr.trim());
+        let parsed = parse_cell(&input, false)?;
+        if !parsed.definitions.is_empty() || parsed.body.len() != 1 {
+            return Err(ReplError::new(
+                &input,
+                RRException::new(
+                    "RR.ParseError",
+                    RRCode::E0001,
+                    Stage::Parse,
+                    ":type expects a single expression",
+                ),
+            ));
+        }
+        let program = self.assemble(&self.definitions, &parsed.body, &parsed.lets);
+        let fns = self.synthesize(&program)?;
+        Ok(written_types(&fns)
+            .remove(REPL_VALUE_NAME)
+            .map(|(ty, _)| ty)
+            .unwrap_or_else(|| "any".to_string()))
+    }
+
+    /// Textual MIR of function `name`, as lowered before optimization.
+    pub fn mir_of(&self, name: &str) -> Result<String, ReplError> {
+        let program = self.assemble(&self.definitions, &[], &[]);
+        let fns = self.synthesize(&program)?;
+        fns.iter()
+            .find(|fn_ir| fn_ir.user_name.as_deref() == Some(name))
+            .map(|fn_ir| {
+                // Functions lower under internal `Sym_N` symbols; show the
+                // name the user wrote instead.
+                let mut shown = fn_ir.clone();
+                shown.name = name.to_string();
+                crate::mir::text::print_fn(&shown)
+            })
+            .ok_or_else(|| undefined_function(&program, name))
+    }
+
+    /// Generated R definition of function `name`.
+    pub fn emit_of(&mut self, name: &str) -> Result<String, ReplError> {
+        let program = self.assemble(&self.definitions.clone(), &[], &[]);
+        let r_code = self.compile_program(&program)?;
+        extract_r_function(&r_code, name).ok_or_else(|| undefined_function(&program, name))
+    }
+
+    fn assemble(&self, definitions: &[Definition], body: &[String], lets: &[String]) -> String {
+        let mut reads: FxHashSet<String> = FxHashSet::default();
+        for stmt in body {
+            reads.extend(identifiers(stmt));
+        }
+        let mut out = String::from(PRELUDE);
+        for def in definitions {
+            out.push_str(&def.source);
+            out.push('\n');
+        }
+        let mut writes: Vec<&str> = Vec::new();
+        for binding in self.bindings.iter().filter(|b| reads.contains(&b.name)) {
+            let hint = binding
+                .hint
+                .as_deref()
+                .map(|hint| format!(": {}", hint))
+                .unwrap_or_default();
+            out.push_str(&format!(
+                "let {0}{1} = __repl_get(\"{0}\", envir = __repl_globalenv())\n",
+                binding.name, hint
+            ));
+            writes.push(&binding.name);
+        }
+        for stmt in body {
+            out.push_str(stmt);
+            out.push('\n');
+        }
+        for name in lets {
+            if !writes.contains(&name.as_str()) {
+                writes.push(name);
+            }
+        }
+        for name in writes {
+            out.push_str(&format!(
+                "__repl_assign(\"{0}\", {0}, envir = __repl_globalenv())\n",
+                name
+            ));
+        }
+        out
+    }
+
+    fn synthesize(&self, program: &str) -> Result<Vec<FnIR>, ReplError> {
+        synthesize_unoptimized_mir(&self.entry_path, program)
+            .map_err(|error| ReplError::new(program, error))
+    }
+
+    fn compile_program(&mut self, program: &str) -> Result<String, ReplError> {
+        let output: RR<_> = with_quiet_log(|| {
+            compile_incremental_request(IncrementalCompileRequest {
+                entry_path: &self.entry_path,
+                entry_input: program,
+                opt_level: self.opt_level,
+                type_cfg: default_type_config(),
+                parallel_cfg: default_parallel_config(),
+                compiler_parallel_cfg: CompilerParallelConfig::default(),
+                options: IncrementalOptions::auto(),
+                output_options: CompileOutputOptions {
+                    preserve_all_defs: true,
+                    ..CompileOutputOptions::default()
+                },
+                session: Some(&mut self.incremental),
+                profile: None,
+            })
+        });
+        output
+            .map(|output| output.r_code)
+            .map_err(|error| ReplError::new(program, error))
+    }
+}
+
+/// Whether `input` still has unclosed brackets or strings, so the REPL
+/// should keep reading lines before compiling it.
+pub fn input_is_incomplete(input: &str) -> bool {
+    let mut lexer = Lexer::new(input);
+    let mut depth = 0i64;
+    loop {
+        match lexer.next_token().kind {
+            TokenKind::Eof => return depth > 0,
+            TokenKind::LParen | TokenKind::LBrace | TokenKind::LBracket => depth += 1,
+            TokenKind::RParen | TokenKind::RBrace | TokenKind::RBracket => depth -= 1,
+            TokenKind::Invalid(message) if message.starts_with("unterminated") => return true,
+            _ => {}
+        }
+    }
+}
+
+fn parse_cell(input: &str, bind_value: bool) -> Result<ParsedCell, ReplError> {
+    let program = Parser::new(input)
+        .parse_program()
+        .map_err(|error| ReplError::new(input, error))?;
+    let mut parsed = ParsedCell {
+        definitions: Vec::new(),
+        body: Vec::new(),
+        lets: Vec::new(),
+        has_value: false,
+    };
+    let last = program.stmts.len().saturating_sub(1);
+    for (idx, stmt) in program.stmts.iter().enumerate() {
+        let text = input
+            .get(stmt.span.start_byte..stmt.span.end_byte)
+            .unwrap_or_default()
+            .trim()
+            .to_string();
+        let key = match &stmt.kind {
+            StmtKind::Import { .. } => Some(format!("import {}", text)),
+            StmtKind::FnDecl { name, .. } => Some(format!("fn {}", name)),
+            StmtKind::Export(decl) => Some(format!("fn {}", decl.name)),
+            StmtKind::TraitDecl(decl) => Some(format!("trait {}", decl.name)),
+            StmtKind::ImplDecl(_) => Some(format!(
+                "impl {}",
+                text.split('{').next().unwrap_or_default().trim()
+            )),
+            _ => None,
+        };
+        if let Some(key) = key {
+            parsed.definitions.push(Definition { key, source: text });
+            continue;
+        }
+        match &stmt.kind {
+            StmtKind::Let { name, .. } => {
+                parsed.lets.push(name.clone());
+                parsed.body.push(text);
+            }
+            StmtKind::ExprStmt { expr } | StmtKind::Expr(expr)
+                if bind_value && idx == last && !prints_itself(&expr.kind) =>
+            {
+                parsed
+                    .body
+                    .push(format!("let {} = {}", REPL_VALUE_NAME, text));
+                parsed.lets.push(REPL_VALUE_NAME.to_string());
+                parsed.has_value = true;
+            }
+            _ => parsed.body.push(text),
+        }
+    }
+    Ok(parsed)
+}
+
+fn prints_itself(expr: &ExprKind) -> bool {
+    match expr {
+        ExprKind::Call { callee, .. } => matches!(
+            &callee.kind,
+            ExprKind::Name(name) if SELF_PRINTING_CALLS.contains(&name.as_str())
+        ),
+        _ => false,
+    }
+}
+
+/// Later definitions replace earlier ones with the same key in place, so a
+/// redefined function keeps its position relative to its callers.
+fn merge_definitions(existing: &[Definition], new: Vec<Definition>) -> Vec<Definition> {
+    let mut merged = existing.to_vec();
+    for def in new {
+        match merged.iter_mut().find(|old| old.key == def.key) {
+            Some(slot) => *slot = def,
+            None => merged.push(def),
+        }
+    }
+    merged
+}
+
+fn identifiers(source: &str) -> FxHashSet<String> {
+    let mut lexer = Lexer::new(source);
+    let mut names = FxHashSet::default();
+    loop {
+        match lexer.next_token().kind {
+            TokenKind::Eof => return names,
+            TokenKind::Ident(name) => {
+                names.insert(name);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Types of the values written back by the top-level code, keyed by binding
+/// name, as `(display, hint)`.
+fn written_types(fns: &[FnIR]) -> rustc_hash::FxHashMap<String, (String, Option<String>)> {
+    let mut out = rustc_hash::FxHashMap::default();
+    for fn_ir in fns.iter().filter(|fn_ir| fn_ir.user_name.is_none()) {
+        for value in &fn_ir.values {
+            let ValueKind::Call { callee, args, .. } = &value.kind else {
+                continue;
+            };
+            if callee != WRITE_BACK_CALLEE || args.len() < 2 {
+                continue;
+            }
+            let ValueKind::Const(Lit::Str(name)) = &fn_ir.values[args[0]].kind else {
+                continue;
+            };
+            let written = &fn_ir.values[args[1]];
+            out.insert(
+                name.clone(),
+                (
+                    type_label(&written.value_term, written.value_ty),
+                    type_hint(&written.value_term, written.value_ty),
+                ),
+            );
+        }
+    }
+    out
+}
+
+fn prim_label(prim: PrimTy) -> &'static str {
+    match prim {
+        PrimTy::Any => "any",
+        PrimTy::Null => "null",
+        PrimTy::Logical => "bool",
+        PrimTy::Int => "int",
+        PrimTy::Double => "float",
+        PrimTy::Char => "str",
+    }
+}
+
+fn state_label(ty: TypeState) -> Option<String> {
+    let prim = prim_label(ty.prim);
+    match ty.shape {
+        ShapeTy::Scalar => Some(prim.to_string()),
+        ShapeTy::Vector => Some(format!("vector<{}>", prim)),
+        ShapeTy::Matrix => Some(format!("matrix<{}>", prim)),
+        ShapeTy::Unknown if ty.prim == PrimTy::Null => Some(prim.to_string()),
+        ShapeTy::Unknown => None,
+    }
+}
+
+/// User-facing spelling of an inferred type, in type-hint syntax where the
+/// language has one.
+fn type_label(term: &TypeTerm, ty: TypeState) -> String {
+    if term.is_any() {
+        return state_label(ty).unwrap_or_else(|| "any".to_string());
+    }
+    term_label(term)
+}
+
+fn term_label(term: &TypeTerm) -> String {
+    let fields = |fields: &[(String, TypeTerm)]| {
+        fields
+            .iter()
+            .map(|(name, term)| format!("{}: {}", name, term_label(term)))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    match term {
+        TypeTerm::Any => "any".to_string(),
+        TypeTerm::Never => "never".to_string(),
+        TypeTerm::Null => "null".to_string(),
+        TypeTerm::Logical => "bool".to_string(),
+        TypeTerm::Int => "int".to_string(),
+        TypeTerm::Double => "float".to_string(),
+        TypeTerm::Char => "str".to_string(),
+        TypeTerm::Vector(elem) | TypeTerm::VectorLen(elem, _) => {
+            format!("vector<{}>", term_label(elem))
+        }
+        TypeTerm::Matrix(elem) | TypeTerm::MatrixDim(elem, _, _) => {
+            format!("matrix<{}>", term_label(elem))
+        }
+        TypeTerm::ArrayDim(elem, _) => format!("array<{}>", term_label(elem)),
+        TypeTerm::DataFrame(_) => "dataframe".to_string(),
+        TypeTerm::DataFrameNamed(cols) => format!("dataframe{{{}}}", fields(cols)),
+        TypeTerm::NamedList(items) => format!("list{{{}}}", fields(items)),
+        TypeTerm::List(elem) => format!("list<{}>", term_label(elem)),
+        TypeTerm::Boxed(inner) => format!("box<{}>", term_label(inner)),
+        TypeTerm::Option(inner) => format!("option<{}>", term_label(inner)),
+        TypeTerm::Union(arms) => arms.iter().map(term_label).collect::<Vec<_>>().join(" | "),
+    }
+}
+
+/// Type hint for re-declaring a binding, when its type can be written as one.
+fn type_hint(term: &TypeTerm, ty: TypeState) -> Option<String> {
+    fn expressible(term: &TypeTerm) -> bool {
+        match term {
+            TypeTerm::Logical | TypeTerm::Int | TypeTerm::Double | TypeTerm::Char => true,
+            TypeTerm::Vector(elem)
+            | TypeTerm::VectorLen(elem, _)
+            | TypeTerm::Matrix(elem)
+            | TypeTerm::MatrixDim(elem, _, _)
+            | TypeTerm::List(elem)
+            | TypeTerm::Boxed(elem)
+            | TypeTerm::Option(elem) => elem.is_any() || expressible(elem),
+            _ => false,
+        }
+    }
+    if term.is_any() {
+        return (ty.prim != PrimTy::Any && ty.prim != PrimTy::Null)
+            .then(|| state_label(ty))
+            .flatten();
+    }
+    expressible(term).then(|| term_label(term))
+}
+
+fn undefined_function(program: &str, name: &str) -> ReplError {
+    ReplError::new(
+        program,
+        RRException::new(
+            "RR.SemanticError",
+            RRCode::E1001,
+            Stage::Lower,
+            format!("undefined function '{}' in this session", name),
+        ),
+    )
+}
+
+/// The `name <- function(...)` block of generated R, up to its closing brace.
+fn extract_r_function(r_code: &str, name: &str) -> Option<String> {
+    let header = format!("{} <- function(", name);
+    let mut lines = r_code.lines();
+    let first = lines.by_ref().find(|line| line.starts_with(&header))?;
+    let mut out = vec![first.trim_end()];
+    for line in lines {
+        out.push(line.trim_end());
+        if line == "}" {
+            break;
+        }
+    }
+    Some(out.join("\n"))
+}
+
+#[cfg(test)]
+#[path = "repl/tests.rs"]
+mod tests;
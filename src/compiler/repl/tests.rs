@@ -0,0 +1,94 @@
+use super::*;
+
+fn session() -> ReplSession {
+    let entry = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("target")
+        .join("tests")
+        .join("repl_unit")
+        .join("repl.rr");
+    ReplSession::new(entry.to_string_lossy().to_string(), OptLevel::O1)
+}
+
+fn binding_ty<'a>(session: &'a ReplSession, name: &str) -> Option<&'a str> {
+    session
+        .bindings()
+        .iter()
+        .find(|binding| binding.name == name)
+        .map(|binding| binding.ty.as_str())
+}
+
+#[test]
+fn bindings_carry_inferred_types_into_later_cells() {
+    let mut repl = session();
+    let cell = repl
+        .compile_cell("let xs = c(1.0, 2.0, 3.0)\nlet n = 2L")
+        .unwrap_or_else(|e| panic!("{:?}", e.error));
+    assert!(cell.value_ty.is_none());
+    assert!(
+        cell.r_code.contains("base::assign(\"xs\""),
+        "{}",
+        cell.r_code
+    );
+    repl.commit(cell);
+    assert_eq!(binding_ty(&repl, "xs"), Some("vector<float>"));
+    assert_eq!(binding_ty(&repl, "n"), Some("int"));
+
+    let cell = repl
+        .compile_cell("n + 1L")
+        .unwrap_or_else(|e| panic!("{:?}", e.error));
+    assert_eq!(cell.value_ty.as_deref(), Some("int"));
+    assert!(cell.r_code.contains("print(it)"), "{}", cell.r_code);
+    assert_eq!(
+        repl.type_of("xs * 2.0")
+            .map_err(|e| e.error.message.clone()),
+        Ok("vector<float>".to_string())
+    );
+}
+
+#[test]
+fn strict_let_rejects_names_no_cell_declared() {
+    let mut repl = session();
+    let err = repl
+        .compile_cell("missing + 1L")
+        .expect_err("undeclared read should fail");
+    assert!(
+        err.error.message.contains("undefined variable 'missing'"),
+        "{}",
+        err.error.message
+    );
+    assert!(repl.bindings().is_empty());
+}
+
+#[test]
+fn definitions_are_replaced_and_exposed_to_meta_commands() {
+    let mut repl = session();
+    let cell = repl
+        .compile_cell("fn sq(x) {\n  return x * x\n}")
+        .unwrap_or_else(|e| panic!("{:?}", e.error));
+    repl.commit(cell);
+    let cell = repl
+        .compile_cell("fn sq(x) {\n  return x * x * 1L\n}\nprint(sq(3L))")
+        .unwrap_or_else(|e| panic!("{:?}", e.error));
+    assert!(cell.value_ty.is_none(), "print() should not bind `it`");
+    repl.commit(cell);
+
+    let mir = repl
+        .mir_of("sq")
+        .unwrap_or_else(|e| panic!("{:?}", e.error));
+    assert!(mir.starts_with("fn sq(x"), "{mir}");
+    let emitted = repl
+        .emit_of("sq")
+        .unwrap_or_else(|e| panic!("{:?}", e.error));
+    assert!(emitted.starts_with("sq <- function(x)"), "{emitted}");
+    assert!(emitted.ends_with('}'), "{emitted}");
+    assert!(repl.mir_of("nope").is_err());
+}
+
+#[test]
+fn open_brackets_and_strings_need_more_input() {
+    assert!(input_is_incomplete("fn f(x) {\n"));
+    assert!(input_is_incomplete("print(c(1L,\n"));
+    assert!(input_is_incomplete("let s = \"abc"));
+    assert!(!input_is_incomplete("fn f(x) {\n  return x\n}\n"));
+    assert!(!input_is_incomplete("1L + 2L"));
+}
@@ -1,4 +1,5 @@
 use crate::error::{InternalCompilerError, RRException, Stage};
+use crate::mir::opt::remarks;
 use rayon::prelude::*;
 use rayon::{ThreadPool, ThreadPoolBuilder};
 use std::collections::{BTreeMap, VecDeque};
@@ -399,7 +400,8 @@ impl CompilerScheduler {
         if !self.should_parallelize(job_count, total_ir) {
             f()
         } else if let Some(pool) = self.pool.as_deref() {
-            pool.install(f)
+            let sink = remarks::current_sink();
+            pool.install(|| remarks::with_sink(sink, f))
         } else {
             f()
         }
@@ -420,7 +422,8 @@ impl CompilerScheduler {
         if !decision.parallelize {
             f()
         } else if let Some(pool) = self.pool.as_deref() {
-            pool.install(f)
+            let sink = remarks::current_sink();
+            pool.install(|| remarks::with_sink(sink, f))
         } else {
             f()
         }
@@ -437,7 +440,14 @@ impl CompilerScheduler {
             return jobs.into_iter().map(f).collect();
         }
         if let Some(pool) = self.pool.as_deref() {
-            pool.install(|| jobs.into_par_iter().map(f).collect())
+            // Optimization remarks are collected per thread; hand the
+            // caller's sink to the workers.
+            let sink = remarks::current_sink();
+            pool.install(|| {
+                jobs.into_par_iter()
+                    .map(|job| remarks::with_sink(sink.clone(), || f(job)))
+                    .collect()
+            })
         } else {
             jobs.into_iter().map(f).collect()
         }
@@ -461,7 +471,12 @@ impl CompilerScheduler {
             return jobs.into_iter().map(f).collect();
         }
         if let Some(pool) = self.pool.as_deref() {
-            pool.install(|| jobs.into_par_iter().map(f).collect())
+            let sink = remarks::current_sink();
+            pool.install(|| {
+                jobs.into_par_iter()
+                    .map(|job| remarks::with_sink(sink.clone(), || f(job)))
+                    .collect()
+            })
         } else {
             jobs.into_iter().map(f).collect()
         }
@@ -498,8 +513,10 @@ impl CompilerScheduler {
         let first_err: Arc<Mutex<Option<(usize, E)>>> = Arc::new(Mutex::new(None));
         let worker_count = self.worker_count().min(job_count.max(1));
         let f = &f;
+        let sink = remarks::current_sink();
         pool.scope(|scope| {
             for _ in 0..worker_count {
+                let sink = sink.clone();
                 let queue = Arc::clone(&queue);
                 let results = Arc::clone(&results);
                 let first_err = Arc::clone(&first_err);
@@ -516,7 +533,7 @@ impl CompilerScheduler {
                         let Some((idx, job)) = next_job else {
                             break;
                         };
-                        match f(job) {
+                        match remarks::with_sink(sink.clone(), || f(job)) {
                             Ok(value) => {
                                 let mut guard = lock_or_recover(&results);
                                 guard[idx] = Some(value);
@@ -588,8 +605,10 @@ impl CompilerScheduler {
         let first_err: Arc<Mutex<Option<(usize, E)>>> = Arc::new(Mutex::new(None));
         let worker_count = self.worker_count().min(job_count.max(1));
         let f = &f;
+        let sink = remarks::current_sink();
         pool.scope(|scope| {
             for _ in 0..worker_count {
+                let sink = sink.clone();
                 let queue = Arc::clone(&queue);
                 let results = Arc::clone(&results);
                 let first_err = Arc::clone(&first_err);
@@ -606,7 +625,7 @@ impl CompilerScheduler {
                         let Some((idx, job)) = next_job else {
                             break;
                         };
-                        match f(job) {
+                        match remarks::with_sink(sink.clone(), || f(job)) {
                             Ok(value) => {
                                 let mut guard = lock_or_recover(&results);
                                 guard[idx] = Some(value);
@@ -71,6 +71,11 @@ impl DiagnosticBuilder {
         self
     }
 
+    pub fn warning(mut self) -> Self {
+        self.inner = self.inner.warning();
+        self
+    }
+
     pub fn build(self) -> RRException {
         self.inner
     }
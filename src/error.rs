@@ -21,6 +21,13 @@ pub enum RRCode {
     E3001,   // Unsupported Feature
     E9999,   // Internal Error (legacy)
     ICE9001, // Internal Compiler Error
+    W1001,   // Lint: unused_variable
+    W1002,   // Lint: unused_import
+    W1003,   // Lint: shadowed_binding
+    W1004,   // Lint: na_comparison
+    W1005,   // Lint: length_recycling
+    W1006,   // Lint: unreachable_code
+    W1007,   // Lint: hybrid_fallback
 }
 
 impl RRCode {
@@ -41,6 +48,13 @@ impl RRCode {
             Self::E3001 => "E3001",
             Self::E9999 => "E9999",
             Self::ICE9001 => "ICE9001",
+            Self::W1001 => "W1001",
+            Self::W1002 => "W1002",
+            Self::W1003 => "W1003",
+            Self::W1004 => "W1004",
+            Self::W1005 => "W1005",
+            Self::W1006 => "W1006",
+            Self::W1007 => "W1007",
         }
     }
 }
@@ -65,11 +79,33 @@ impl std::str::FromStr for RRCode {
             "E3001" => Ok(Self::E3001),
             "E9999" => Ok(Self::E9999),
             "ICE9001" => Ok(Self::ICE9001),
+            "W1001" => Ok(Self::W1001),
+            "W1002" => Ok(Self::W1002),
+            "W1003" => Ok(Self::W1003),
+            "W1004" => Ok(Self::W1004),
+            "W1005" => Ok(Self::W1005),
+            "W1006" => Ok(Self::W1006),
+            "W1007" => Ok(Self::W1007),
             _ => Err(()),
         }
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl Severity {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Error => "error",
+            Self::Warning => "warning",
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum Stage {
     Lex,
@@ -130,6 +166,7 @@ pub struct RRExceptionData {
     pub module: &'static str,
     pub message: Box<str>,
     pub code: RRCode,
+    pub severity: Severity,
     pub stage: Stage,
     pub span: Option<Span>,
     pub stacktrace: Box<Vec<Frame>>,
@@ -192,6 +229,7 @@ impl RRException {
             module,
             message: msg.into().into_boxed_str(),
             code,
+            severity: Severity::Error,
             stage,
             span: None,
             stacktrace: Box::new(Vec::new()),
@@ -220,6 +258,19 @@ impl RRException {
         self
     }
 
+    pub fn with_severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    pub fn warning(self) -> Self {
+        self.with_severity(Severity::Warning)
+    }
+
+    pub fn is_warning(&self) -> bool {
+        self.severity == Severity::Warning
+    }
+
     pub fn push_frame(mut self, name: impl Into<String>, span: Option<Span>) -> Self {
         self.stacktrace.push(Frame {
             name: name.into(),
@@ -281,7 +332,11 @@ impl RRException {
 
     pub fn display(&self, source: Option<&str>, file: Option<&str>) {
         let color = color_enabled_stdout();
-        let palette = palette_for_module(self.module);
+        let palette = if self.is_warning() {
+            palette_for_module("Warning")
+        } else {
+            palette_for_module(self.module)
+        };
         let code_color = palette_for_rr_code(self.code.as_str(), palette.code);
         let file = file.unwrap_or("RR");
         let at = if let Some(span) = self.span {
@@ -302,7 +357,12 @@ impl RRException {
             style(
                 color,
                 code_color,
-                &format!("    error[{}]: {}", self.code.as_str(), self.message),
+                &format!(
+                    "    {}[{}]: {}",
+                    self.severity.as_str(),
+                    self.code.as_str(),
+                    self.message
+                ),
             )
         );
         println!(
@@ -584,7 +644,7 @@ fn palette_for_rr_code<'a>(code: &'a str, fallback: &'a str) -> &'a str {
         "1;33"
     } else if code.starts_with("E2") {
         "1;31"
-    } else if code.starts_with("E3") {
+    } else if code.starts_with("E3") || code.starts_with('W') {
         "1;38;5;208"
     } else {
         fallback
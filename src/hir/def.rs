@@ -45,6 +45,11 @@ pub struct HirModule {
     pub id: ModuleId,
     pub path: Vec<SymbolId>,
     pub items: Vec<HirItem>,
+    /// Names the module references without a local binding, import alias or
+    /// namespace prefix, with the first span each appears at. Module scope
+    /// checks use them to keep unselected functions of other modules out.
+    #[serde(default)]
+    pub unqualified_refs: Vec<(SymbolId, Span)>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -69,6 +74,11 @@ pub struct HirImport {
 pub enum HirImportSpec {
     Glob,
     Names(Vec<SymbolId>),
+    /// `import * as alias`; `names` are the members the module refers to.
+    Namespace {
+        alias: String,
+        names: Vec<SymbolId>,
+    },
 }
 
 // ----- Functions -----
@@ -198,6 +208,8 @@ pub struct HirImpl {
     pub span: Span,
     #[serde(default)]
     pub public: bool,
+    #[serde(default)]
+    pub s3: bool,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -357,6 +369,7 @@ pub enum HirExpr {
         op: HirBinOp,
         lhs: Box<HirExpr>,
         rhs: Box<HirExpr>,
+        span: Span,
     },
 
     ListLit(Vec<(SymbolId, HirExpr)>),
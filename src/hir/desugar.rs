@@ -213,10 +213,11 @@ impl Desugarer {
                     arms: new_arms,
                 })
             }
-            HirExpr::Binary { op, lhs, rhs } => Ok(HirExpr::Binary {
+            HirExpr::Binary { op, lhs, rhs, span } => Ok(HirExpr::Binary {
                 op,
                 lhs: Box::new(self.desugar_expr(*lhs)?),
                 rhs: Box::new(self.desugar_expr(*rhs)?),
+                span,
             }),
             HirExpr::Range { start, end } => Ok(HirExpr::Range {
                 start: Box::new(self.desugar_expr(*start)?),
@@ -299,6 +300,7 @@ impl Desugarer {
                     op: HirBinOp::And,
                     lhs: Box::new(pat_check),
                     rhs: Box::new(g),
+                    span,
                 }
             } else {
                 pat_check
@@ -341,7 +343,7 @@ impl Desugarer {
         }))
     }
 
-    fn pattern_match_check(&mut self, root: LocalId, pat: &HirPat, _span: Span) -> RR<HirExpr> {
+    fn pattern_match_check(&mut self, root: LocalId, pat: &HirPat, span: Span) -> RR<HirExpr> {
         match pat {
             HirPat::Wild => Ok(HirExpr::Lit(HirLit::Bool(true))),
             HirPat::Bind { .. } => Ok(HirExpr::Lit(HirLit::Bool(true))),
@@ -349,6 +351,7 @@ impl Desugarer {
                 op: HirBinOp::Eq,
                 lhs: Box::new(HirExpr::Local(root)),
                 rhs: Box::new(HirExpr::Lit(l.clone())),
+                span,
             }),
             _ => Ok(HirExpr::Lit(HirLit::Bool(false))),
         }
@@ -110,6 +110,24 @@ pub struct Lowerer {
     r_import_aliases: FxHashMap<String, SymbolId>,
     // Namespace imports from R packages, lowered from ns.foo(...) to pkg::foo(...).
     r_namespace_aliases: FxHashMap<String, String>,
+    // Renamed imports from RR modules in the module being lowered, local -> imported.
+    module_import_aliases: FxHashMap<String, SymbolId>,
+    // Namespace imports from RR modules in the module being lowered, alias ->
+    // (module path, members referenced as alias.name).
+    module_namespace_aliases: FxHashMap<String, (String, Vec<SymbolId>)>,
+    // Names the module being lowered references unqualified without a
+    // binding, in first-use order.
+    module_unqualified_refs: Vec<(SymbolId, Span)>,
+    // Types with an `impl ... as s3`; record literals built at one of these
+    // types carry the R class attribute.
+    s3_classes: FxHashSet<String>,
+    // Declared parameters of top-level functions with a named parameter
+    // type, so record literals passed to an S3-typed parameter get the class.
+    fn_param_types: FxHashMap<String, Vec<(String, Option<HirTypeRef>)>>,
+    // Exported S3 wrapper name -> trait whose impl exported it.
+    s3_methods: FxHashMap<String, String>,
+    // Declared return type of the function being lowered.
+    current_ret_ty: Option<HirTypeRef>,
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -141,6 +159,8 @@ mod exprs;
 mod items;
 #[path = "lower/metadata.rs"]
 mod metadata;
+#[path = "lower/parallel_for.rs"]
+mod parallel_for;
 #[path = "lower/stmts.rs"]
 mod stmts;
 #[path = "lower/trait_queries.rs"]
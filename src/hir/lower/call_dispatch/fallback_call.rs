@@ -14,7 +14,8 @@ impl Lowerer {
         } else {
             self.lower_expr(callee)?
         };
-        let hargs = self.lower_call_args(args)?;
+        let mut hargs = self.lower_call_args(args)?;
+        self.apply_s3_arg_classes(&c, &mut hargs);
         Ok(HirExpr::Call(HirCall {
             callee: Box::new(c),
             args: hargs,
@@ -10,11 +10,13 @@ impl Lowerer {
         span: Span,
         callee_span: Span,
     ) -> RR<Option<HirExpr>> {
-        let ast::ExprKind::Name(name) = &callee.kind else {
-            return Ok(None);
+        let name = match (&callee.kind, self.module_import_callee_name(callee)) {
+            (_, Some(imported)) => imported,
+            (ast::ExprKind::Name(name), None) => name.clone(),
+            _ => return Ok(None),
         };
         let Some(generic_sym) =
-            self.resolve_generic_call(name, type_args, args, expected_ret_ty, span)?
+            self.resolve_generic_call(&name, type_args, args, expected_ret_ty, span)?
         else {
             return Ok(None);
         };
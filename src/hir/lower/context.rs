@@ -30,6 +30,13 @@ impl Lowerer {
             current_where_bounds: FxHashMap::default(),
             r_import_aliases: FxHashMap::default(),
             r_namespace_aliases: FxHashMap::default(),
+            module_import_aliases: FxHashMap::default(),
+            module_namespace_aliases: FxHashMap::default(),
+            module_unqualified_refs: Vec::new(),
+            s3_classes: FxHashSet::default(),
+            fn_param_types: FxHashMap::default(),
+            s3_methods: FxHashMap::default(),
+            current_ret_ty: None,
         }
     }
     pub fn take_warnings(&mut self) -> Vec<String> {
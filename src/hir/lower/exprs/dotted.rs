@@ -19,7 +19,10 @@ impl Lowerer {
         Some(s)
     }
     pub(crate) fn root_is_unbound_for_dotted(&self, dotted: &str) -> bool {
-        let root = dotted.split('.').next().unwrap_or(dotted);
+        // A leading dot belongs to the root name, as in R's `.x`.
+        let rest = dotted.trim_start_matches('.');
+        let lead = dotted.len() - rest.len();
+        let root = &dotted[..lead + rest.find('.').unwrap_or(rest.len())];
         self.lookup(root).is_none()
     }
     pub(crate) fn lower_dotted_ref(&mut self, dotted: &str, span: Span) -> HirExpr {
@@ -29,12 +32,14 @@ impl Lowerer {
             HirExpr::Global(sym, span)
         } else if let Some(sym) = self.r_import_aliases.get(dotted).copied() {
             HirExpr::Global(sym, span)
+        } else if let Some(sym) = self.module_import_target(dotted) {
+            HirExpr::Global(sym, span)
         } else if let Some((root, rest)) = dotted.split_once('.')
             && let Some(pkg) = self.r_namespace_aliases.get(root)
         {
             HirExpr::Global(self.intern_symbol(&format!("{}::{}", pkg, rest)), span)
         } else {
-            HirExpr::Global(self.intern_symbol(dotted), span)
+            HirExpr::Global(self.unqualified_global(dotted, span), span)
         }
     }
 }
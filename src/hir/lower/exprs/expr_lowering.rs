@@ -34,8 +34,13 @@ impl Lowerer {
                     Ok(HirExpr::Global(sym, expr.span))
                 } else if let Some(sym) = self.r_import_aliases.get(&n).copied() {
                     Ok(HirExpr::Global(sym, expr.span))
+                } else if let Some(sym) = self.module_import_target(&n) {
+                    Ok(HirExpr::Global(sym, expr.span))
                 } else {
-                    Ok(HirExpr::Global(self.intern_symbol(&n), expr.span))
+                    Ok(HirExpr::Global(
+                        self.unqualified_global(&n, expr.span),
+                        expr.span,
+                    ))
                 }
             }
             ast::ExprKind::Binary { op, lhs, rhs } => {
@@ -79,6 +84,7 @@ impl Lowerer {
                     op: hop,
                     lhs: Box::new(self.lower_expr(*lhs)?),
                     rhs: Box::new(self.lower_expr(*rhs)?),
+                    span: expr.span,
                 })
             }
             ast::ExprKind::Formula { lhs, rhs } => {
@@ -174,6 +180,7 @@ impl Lowerer {
                                 _ => hargs.push(HirArg::Pos(self.lower_expr(a)?)),
                             }
                         }
+                        self.apply_s3_arg_classes(&c, &mut hargs);
                         Ok(HirExpr::Call(HirCall {
                             callee: Box::new(c),
                             args: hargs,
@@ -201,6 +208,7 @@ impl Lowerer {
                                     _ => hargs.push(HirArg::Pos(self.lower_expr(a)?)),
                                 }
                             }
+                            self.apply_s3_arg_classes(&c, &mut hargs);
                             let call = HirExpr::Call(HirCall {
                                 callee: Box::new(c),
                                 args: hargs,
@@ -262,7 +262,9 @@ impl Lowerer {
                     visit_block(lowerer, scopes, seen, captures, body);
                     scopes.pop();
                 }
-                ast::StmtKind::For { var, iter, body } => {
+                ast::StmtKind::For {
+                    var, iter, body, ..
+                } => {
                     visit_expr(lowerer, scopes, seen, captures, iter);
                     let mut loop_scope = FxHashSet::default();
                     loop_scope.insert(var.clone());
@@ -286,6 +288,7 @@ impl Lowerer {
                 | ast::StmtKind::ImplDecl(_)
                 | ast::StmtKind::Import { .. }
                 | ast::StmtKind::Export(_)
+                | ast::StmtKind::ReExport { .. }
                 | ast::StmtKind::Break
                 | ast::StmtKind::Next => {}
             }
@@ -380,6 +383,10 @@ impl Lowerer {
         let saved_type_params = std::mem::take(&mut self.current_type_params);
         let saved_where_bounds = std::mem::take(&mut self.current_where_bounds);
         let saved_next_local = self.next_local_id;
+        let saved_ret_ty = std::mem::replace(
+            &mut self.current_ret_ty,
+            ret_ty_hint.as_ref().map(Self::ast_type_ref),
+        );
         self.next_local_id = 0;
 
         let mut hir_params: Vec<HirParam> = Vec::new();
@@ -426,7 +433,9 @@ impl Lowerer {
             });
         }
         let hir_body = Self::apply_implicit_tail_return(self.lower_block(body)?);
+        let hir_body = self.apply_s3_tail_class(hir_body);
         let lambda_local_names = std::mem::take(&mut self.local_names);
+        self.current_ret_ty = saved_ret_ty;
 
         self.scopes = saved_scopes;
         self.local_names = saved_local_names;
@@ -1,7 +1,13 @@
 use super::*;
 #[path = "items/functions.rs"]
 mod functions;
+#[path = "items/module_imports.rs"]
+mod module_imports;
 #[path = "items/module_items.rs"]
 mod module_items;
+#[path = "items/s3_methods.rs"]
+mod s3_methods;
+#[path = "items/trait_import_aliases.rs"]
+mod trait_import_aliases;
 #[path = "items/trait_items.rs"]
 mod trait_items;
@@ -22,6 +22,10 @@ impl Lowerer {
         let saved_type_params = std::mem::take(&mut self.current_type_params);
         let saved_where_bounds = std::mem::take(&mut self.current_where_bounds);
         let saved_next_local = self.next_local_id;
+        let saved_ret_ty = std::mem::replace(
+            &mut self.current_ret_ty,
+            ret_ty_hint.as_ref().map(Self::ast_type_ref),
+        );
         self.next_local_id = 0;
         self.current_type_params = type_params.iter().cloned().collect();
         self.current_where_bounds = Self::where_bound_map(&where_bounds);
@@ -61,9 +65,11 @@ impl Lowerer {
         }
 
         let hir_body = Self::apply_implicit_tail_return(self.lower_block(body)?);
+        let hir_body = self.apply_s3_tail_class(hir_body);
         self.exit_scope();
 
         let local_names = self.local_names.drain().collect();
+        self.current_ret_ty = saved_ret_ty;
 
         self.scopes = saved_scopes;
         self.local_names = saved_local_names;
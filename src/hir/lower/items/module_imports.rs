@@ -0,0 +1,207 @@
+use super::*;
+impl Lowerer {
+    /// Register `import ... from "path.rr"` for the module being lowered.
+    ///
+    /// Module functions share one R namespace, so selected names and namespace
+    /// members lower to the imported function's own symbol. Whether the module
+    /// actually exports them is checked once every module has been loaded.
+    pub(crate) fn register_module_import(
+        &mut self,
+        path: &str,
+        spec: ast::ImportSpec,
+        span: Span,
+    ) -> RR<HirImportSpec> {
+        match spec {
+            ast::ImportSpec::Glob => Ok(HirImportSpec::Glob),
+            ast::ImportSpec::Named(bindings) => {
+                let mut names = Vec::with_capacity(bindings.len());
+                for binding in bindings {
+                    let sym = self.intern_symbol(&binding.imported);
+                    names.push(sym);
+                    let Some(local) = binding.local.filter(|local| *local != binding.imported)
+                    else {
+                        continue;
+                    };
+                    // Renamed traits were rewritten to their own name before
+                    // lowering; see `resolve_trait_import_aliases`.
+                    if self.trait_defs.contains_key(&binding.imported) {
+                        continue;
+                    }
+                    if self.generic_fns.contains_key(&binding.imported) {
+                        return Err(module_import_error(
+                            format!(
+                                "generic function '{}' from '{}' cannot be imported under another name",
+                                binding.imported, path
+                            ),
+                            span,
+                        ));
+                    }
+                    if let Some(prev) = self.module_import_local_binding(&local)
+                        && self.module_import_aliases.get(&local) != Some(&sym)
+                    {
+                        return Err(module_import_error(
+                            format!(
+                                "module import local '{}' is already bound to {}; use 'as' to choose a different local name",
+                                local, prev
+                            ),
+                            span,
+                        ));
+                    }
+                    self.module_import_aliases.insert(local, sym);
+                }
+                Ok(HirImportSpec::Names(names))
+            }
+            ast::ImportSpec::Namespace(alias) => {
+                if let Some((prev_path, _)) = self.module_namespace_aliases.get(&alias)
+                    && prev_path == path
+                {
+                    return Ok(HirImportSpec::Namespace {
+                        alias,
+                        names: Vec::new(),
+                    });
+                }
+                if let Some(prev) = self.module_import_local_binding(&alias) {
+                    return Err(module_import_error(
+                        format!(
+                            "module namespace alias '{}' is already bound to {}; choose another alias",
+                            alias, prev
+                        ),
+                        span,
+                    ));
+                }
+                self.module_namespace_aliases
+                    .insert(alias.clone(), (path.to_string(), Vec::new()));
+                Ok(HirImportSpec::Namespace {
+                    alias,
+                    names: Vec::new(),
+                })
+            }
+        }
+    }
+
+    /// Describe what `local` already names among this module's imports.
+    pub(crate) fn module_import_local_binding(&self, local: &str) -> Option<String> {
+        if let Some(sym) = self.module_import_aliases.get(local) {
+            let name = self
+                .symbols
+                .get(sym)
+                .map(String::as_str)
+                .unwrap_or("<unknown>");
+            return Some(format!("module import '{}'", name));
+        }
+        if let Some((path, _)) = self.module_namespace_aliases.get(local) {
+            return Some(format!("module namespace '{}'", path));
+        }
+        if let Some(sym) = self.r_import_aliases.get(local) {
+            let name = self
+                .symbols
+                .get(sym)
+                .map(String::as_str)
+                .unwrap_or("<unknown>");
+            return Some(format!("R import '{}'", name));
+        }
+        self.r_namespace_aliases
+            .get(local)
+            .map(|pkg| format!("R namespace '{}'", pkg))
+    }
+
+    /// Resolve a renamed import (`b` in `{ a as b }`) or a namespace member
+    /// (`ns.a`) to the imported function symbol. Callers check that the root
+    /// is not shadowed by a local first.
+    pub(crate) fn module_import_target(&mut self, dotted: &str) -> Option<SymbolId> {
+        if let Some(sym) = self.module_import_aliases.get(dotted).copied() {
+            return Some(sym);
+        }
+        let (root, member) = dotted.split_once('.')?;
+        if !self.module_namespace_aliases.contains_key(root) {
+            return None;
+        }
+        let sym = self.intern_symbol(member);
+        if let Some((_, names)) = self.module_namespace_aliases.get_mut(root)
+            && !names.contains(&sym)
+        {
+            names.push(sym);
+        }
+        Some(sym)
+    }
+
+    /// Intern a name referenced without any binding, import alias or namespace
+    /// prefix, and remember it for the cross-module scope check.
+    pub(crate) fn unqualified_global(&mut self, name: &str, span: Span) -> SymbolId {
+        let sym = self.intern_symbol(name);
+        if !self
+            .module_unqualified_refs
+            .iter()
+            .any(|(seen, _)| *seen == sym)
+        {
+            self.module_unqualified_refs.push((sym, span));
+        }
+        sym
+    }
+
+    /// The function name a call resolves to when it goes through a module
+    /// import, for dispatch that works on names (generic instantiation).
+    pub(crate) fn module_import_callee_name(&mut self, callee: &ast::Expr) -> Option<String> {
+        let dotted = Self::dotted_name_from_expr(callee)?;
+        if !self.root_is_unbound_for_dotted(&dotted) {
+            return None;
+        }
+        let sym = self.module_import_target(&dotted)?;
+        self.symbols.get(&sym).cloned()
+    }
+
+    /// Clear per-module import state before lowering the next module.
+    pub(crate) fn begin_module_imports(&mut self) {
+        self.module_import_aliases.clear();
+        self.module_namespace_aliases.clear();
+        self.module_unqualified_refs.clear();
+    }
+
+    /// Record the namespace members the module used and reject import names
+    /// that clash with the module's own functions. Returns the names the
+    /// module referenced unqualified.
+    pub(crate) fn finish_module_imports(
+        &mut self,
+        items: &mut [HirItem],
+    ) -> RR<Vec<(SymbolId, Span)>> {
+        for item in items.iter() {
+            let HirItem::Fn(f) = item else {
+                continue;
+            };
+            let Some(name) = self.symbols.get(&f.name) else {
+                continue;
+            };
+            let clash = if let Some(sym) = self.module_import_aliases.get(name) {
+                self.symbols
+                    .get(sym)
+                    .map(|imported| format!("the import of '{}' as '{}'", imported, name))
+            } else {
+                self.module_namespace_aliases
+                    .get(name)
+                    .map(|(path, _)| format!("the namespace alias for '{}'", path))
+            };
+            if let Some(clash) = clash {
+                return Err(module_import_error(
+                    format!("function '{}' conflicts with {}", name, clash),
+                    f.span,
+                ));
+            }
+        }
+        for item in items.iter_mut() {
+            if let HirItem::Import(HirImport {
+                spec: HirImportSpec::Namespace { alias, names },
+                ..
+            }) = item
+                && let Some((_, used)) = self.module_namespace_aliases.get(alias.as_str())
+            {
+                names.clone_from(used);
+                names.sort_by_key(|sym| sym.0);
+            }
+        }
+        Ok(std::mem::take(&mut self.module_unqualified_refs))
+    }
+}
+
+fn module_import_error(message: String, span: Span) -> RRException {
+    RRException::new("RR.SemanticError", RRCode::E1002, Stage::Lower, message).at(span)
+}
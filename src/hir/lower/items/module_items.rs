@@ -12,6 +12,8 @@ impl Lowerer {
         self.register_trait_decls(&prog.stmts)?;
         self.register_generic_fn_decls(&prog.stmts)?;
         self.register_impl_decls(&prog.stmts)?;
+        self.register_fn_param_types(&prog.stmts);
+        self.begin_module_imports();
 
         let mut items = Vec::new();
         for stmt in prog.stmts {
@@ -25,6 +27,7 @@ impl Lowerer {
                     ret_ty_hint,
                     where_bounds,
                     body,
+                    ..
                 } => {
                     let fn_item = self.lower_fn(LowerFnParts {
                         name,
@@ -45,28 +48,28 @@ impl Lowerer {
                 }
                 ast::StmtKind::ImplDecl(decl) => {
                     let (impl_item, method_fns) = self.lower_impl_decl(decl, stmt.span)?;
+                    let s3_methods = if impl_item.s3 {
+                        self.lower_s3_methods(&impl_item, &method_fns)?
+                    } else {
+                        Vec::new()
+                    };
                     items.push(HirItem::Impl(impl_item));
                     for method_fn in method_fns {
                         items.push(HirItem::Fn(method_fn));
                     }
+                    for (wrapper, registration) in s3_methods {
+                        items.push(HirItem::Fn(wrapper));
+                        items.push(HirItem::Stmt(registration));
+                    }
                     self.flush_pending_fns(&mut items);
                 }
                 ast::StmtKind::Import { source, path, spec } => {
                     match source {
                         ast::ImportSource::Module => {
-                            if !matches!(spec, ast::ImportSpec::Glob) {
-                                return Err(RRException::new(
-                                    "RR.SemanticError",
-                                    RRCode::E1002,
-                                    Stage::Lower,
-                                    "RR module import does not support named or namespace specifiers"
-                                        .to_string(),
-                                )
-                                .at(stmt.span));
-                            }
+                            let spec = self.register_module_import(&path, spec, stmt.span)?;
                             let import = HirImport {
                                 module: path,
-                                spec: HirImportSpec::Glob,
+                                spec,
                                 span: stmt.span,
                             };
                             items.push(HirItem::Import(import));
@@ -134,6 +137,20 @@ impl Lowerer {
                                             )
                                             .at(stmt.span));
                                     }
+                                    if self.module_import_aliases.contains_key(&local)
+                                        || self.module_namespace_aliases.contains_key(&local)
+                                    {
+                                        return Err(RRException::new(
+                                                "RR.SemanticError",
+                                                RRCode::E1002,
+                                                Stage::Lower,
+                                                format!(
+                                                    "R import local '{}' conflicts with a module import; use 'as' to rename the imported symbol",
+                                                    local
+                                                ),
+                                            )
+                                            .at(stmt.span));
+                                    }
                                     if let Some(prev_pkg) = self.r_namespace_aliases.get(&local)
                                         && prev_pkg != &path
                                     {
@@ -152,6 +169,20 @@ impl Lowerer {
                                 }
                             }
                             ast::ImportSpec::Namespace(alias) => {
+                                if self.module_import_aliases.contains_key(&alias)
+                                    || self.module_namespace_aliases.contains_key(&alias)
+                                {
+                                    return Err(RRException::new(
+                                            "RR.SemanticError",
+                                            RRCode::E1002,
+                                            Stage::Lower,
+                                            format!(
+                                                "R namespace alias '{}' conflicts with a module import; choose another alias",
+                                                alias
+                                            ),
+                                        )
+                                        .at(stmt.span));
+                                }
                                 if self.r_import_aliases.contains_key(&alias) {
                                     let prev_name = self
                                         .r_import_aliases
@@ -191,6 +222,18 @@ impl Lowerer {
                     }
                     self.flush_pending_fns(&mut items);
                 }
+                ast::StmtKind::ReExport { path, names } => {
+                    let syms = names
+                        .iter()
+                        .map(|name| self.intern_symbol(name))
+                        .collect::<Vec<_>>();
+                    items.push(HirItem::Import(HirImport {
+                        module: path,
+                        spec: HirImportSpec::Names(syms.clone()),
+                        span: stmt.span,
+                    }));
+                    items.extend(syms.into_iter().map(HirItem::Export));
+                }
                 ast::StmtKind::Export(fndecl) => {
                     let mut fn_item = self.lower_fn(LowerFnParts {
                         name: fndecl.name,
@@ -206,17 +249,21 @@ impl Lowerer {
                     self.flush_pending_fns(&mut items);
                 }
                 _ => {
-                    let s = self.lower_stmt(stmt)?;
-                    items.push(HirItem::Stmt(s));
+                    let mut stmts = Vec::new();
+                    self.lower_stmt_into(stmt, &mut stmts)?;
+                    items.extend(stmts.into_iter().map(HirItem::Stmt));
                     self.flush_pending_fns(&mut items);
                 }
             }
         }
 
+        let unqualified_refs = self.finish_module_imports(&mut items)?;
+
         Ok(HirModule {
             id: mod_id,
             path: vec![],
             items,
+            unqualified_refs,
         })
     }
 }
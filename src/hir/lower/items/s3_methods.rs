@@ -0,0 +1,279 @@
+use super::*;
+impl Lowerer {
+    /// Check an `impl ... as s3` header. S3 dispatch is keyed by one class
+    /// name, so only exported, non-generic impls for user types qualify.
+    pub(crate) fn validate_s3_impl(
+        span: Span,
+        decl: &ast::ImplDecl,
+        for_ty: &HirTypeRef,
+        is_generic_impl: bool,
+    ) -> RR<()> {
+        let class = for_ty.key();
+        let problem = if decl.negative {
+            Some(format!(
+                "negative impl of trait '{}' for '{}' cannot be exported as S3 methods",
+                decl.trait_name, class
+            ))
+        } else if is_generic_impl {
+            Some(format!(
+                "generic impl of trait '{}' for '{}' cannot be exported as S3 methods",
+                decl.trait_name, class
+            ))
+        } else if !matches!(for_ty, HirTypeRef::Named(_))
+            || Self::parse_type_hint_expr(&decl.for_ty).is_some()
+        {
+            Some(format!(
+                "S3 methods need a user-defined record type, found '{}'",
+                class
+            ))
+        } else if !decl.public {
+            Some(format!(
+                "S3 methods for '{}' must be exported; write `export impl {} for {} as s3`",
+                class, decl.trait_name, class
+            ))
+        } else {
+            None
+        };
+        match problem {
+            Some(message) => {
+                Err(
+                    RRException::new("RR.SemanticError", RRCode::E1002, Stage::Lower, message)
+                        .at(span),
+                )
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Build the R-facing `generic.Class` wrappers for an `impl ... as s3`,
+    /// each paired with the top-level statement that registers it.
+    ///
+    /// The wrappers only forward to the mangled impl functions, so RR code
+    /// keeps calling those directly through static dispatch.
+    pub(crate) fn lower_s3_methods(
+        &mut self,
+        hir_impl: &HirImpl,
+        impl_fns: &[HirFn],
+    ) -> RR<Vec<(HirFn, HirStmt)>> {
+        let class = hir_impl.for_ty.key();
+        let trait_name = self
+            .symbols
+            .get(&hir_impl.trait_name)
+            .cloned()
+            .unwrap_or_default();
+        let self_sym = self.intern_symbol("self");
+        let mut methods = Vec::new();
+        for method in &hir_impl.methods {
+            let Some(impl_fn) = impl_fns.iter().find(|f| f.name == method.impl_fn) else {
+                continue;
+            };
+            // Associated functions without a receiver have nothing to dispatch on.
+            if impl_fn.params.first().map(|p| p.name) != Some(self_sym) {
+                continue;
+            }
+            let generic = self
+                .symbols
+                .get(&method.trait_method)
+                .map(|name| name.replace('_', "."))
+                .unwrap_or_default();
+            let wrapper_name = format!("{}.{}", generic, class);
+            if let Some(prev) = self.s3_methods.get(&wrapper_name) {
+                return Err(RRException::new(
+                    "RR.SemanticError",
+                    RRCode::E1002,
+                    Stage::Lower,
+                    format!(
+                        "S3 method '{}' is exported by both '{}' and '{}' impls",
+                        wrapper_name, prev, trait_name
+                    ),
+                )
+                .at(hir_impl.span));
+            }
+            self.s3_methods
+                .insert(wrapper_name.clone(), trait_name.clone());
+            let wrapper = self.s3_wrapper_fn(&wrapper_name, &generic, impl_fn, hir_impl.span);
+            let registration = self.s3_registration(&generic, &class, wrapper.name, hir_impl.span);
+            methods.push((wrapper, registration));
+        }
+        Ok(methods)
+    }
+
+    /// `generic.Class <- function(self, <params>, ...)` forwarding to `impl_fn`.
+    /// `print` methods return their receiver invisibly, as R expects.
+    fn s3_wrapper_fn(&mut self, name: &str, generic: &str, impl_fn: &HirFn, span: Span) -> HirFn {
+        let mut params = impl_fn.params.clone();
+        for param in &mut params {
+            param.default = None;
+        }
+        params.push(HirParam {
+            name: self.intern_symbol("..."),
+            ty: None,
+            ty_inferred: false,
+            default: None,
+            span,
+        });
+        // `...` gets no local: it is only there to satisfy R's generic
+        // signatures and is never read.
+        let mut local_names = FxHashMap::default();
+        let mut args = Vec::with_capacity(impl_fn.params.len());
+        for (idx, param) in impl_fn.params.iter().enumerate() {
+            let local = LocalId(idx as u32);
+            let name = self.symbols.get(&param.name).cloned().unwrap_or_default();
+            local_names.insert(local, name);
+            args.push(HirArg::Pos(HirExpr::Local(local)));
+        }
+        let call = HirExpr::Call(HirCall {
+            callee: Box::new(HirExpr::Global(impl_fn.name, span)),
+            args,
+            span,
+        });
+        let (stmts, ret_ty) = if generic == "print" {
+            let invisible = HirExpr::Call(HirCall {
+                callee: Box::new(HirExpr::Global(self.intern_symbol("base::invisible"), span)),
+                args: vec![HirArg::Pos(HirExpr::Local(LocalId(0)))],
+                span,
+            });
+            (
+                vec![
+                    HirStmt::Expr { expr: call, span },
+                    HirStmt::Return {
+                        value: Some(invisible),
+                        span,
+                    },
+                ],
+                None,
+            )
+        } else {
+            (
+                vec![HirStmt::Return {
+                    value: Some(call),
+                    span,
+                }],
+                impl_fn.ret_ty.clone(),
+            )
+        };
+        HirFn {
+            id: self.alloc_fn_id(),
+            name: self.intern_symbol(name),
+            type_params: Vec::new(),
+            where_bounds: Vec::new(),
+            params,
+            has_varargs: false,
+            ret_ty,
+            ret_ty_inferred: false,
+            body: HirBlock { stmts, span },
+            attrs: HirFnAttrs {
+                inline_hint: InlineHint::Never,
+                tidy_safe: false,
+            },
+            span,
+            local_names,
+            public: true,
+        }
+    }
+
+    /// `base::registerS3method("generic", "Class", generic.Class)`, so the
+    /// method dispatches even when the script is not sourced into the
+    /// global environment.
+    fn s3_registration(
+        &mut self,
+        generic: &str,
+        class: &str,
+        wrapper: SymbolId,
+        span: Span,
+    ) -> HirStmt {
+        let register = self.intern_symbol("base::registerS3method");
+        HirStmt::Expr {
+            expr: HirExpr::Call(HirCall {
+                callee: Box::new(HirExpr::Global(register, span)),
+                args: vec![
+                    HirArg::Pos(HirExpr::Lit(HirLit::Char(generic.to_string()))),
+                    HirArg::Pos(HirExpr::Lit(HirLit::Char(class.to_string()))),
+                    HirArg::Pos(HirExpr::Global(wrapper, span)),
+                ],
+                span,
+            }),
+            span,
+        }
+    }
+
+    /// Tag a record literal built at an S3-exported type with its class, so
+    /// R's generics dispatch to the exported methods.
+    pub(crate) fn with_s3_class(&mut self, expr: HirExpr, ty: Option<&HirTypeRef>) -> HirExpr {
+        let Some(HirTypeRef::Named(class)) = ty else {
+            return expr;
+        };
+        if !matches!(expr, HirExpr::ListLit(_)) || !self.s3_classes.contains(class) {
+            return expr;
+        }
+        let structure = self.intern_symbol("base::structure");
+        let class_sym = self.intern_symbol("class");
+        HirExpr::Call(HirCall {
+            callee: Box::new(HirExpr::Global(structure, Span::default())),
+            args: vec![
+                HirArg::Pos(expr),
+                HirArg::Named {
+                    name: class_sym,
+                    value: HirExpr::Lit(HirLit::Char(class.clone())),
+                },
+            ],
+            span: Span::default(),
+        })
+    }
+
+    /// Apply [`Self::with_s3_class`] to the arguments of a call to a
+    /// top-level function, at the declared type of the parameter each one
+    /// binds: named arguments by name, the rest in parameter order.
+    pub(crate) fn apply_s3_arg_classes(&mut self, callee: &HirExpr, args: &mut [HirArg]) {
+        let HirExpr::Global(sym, _) = callee else {
+            return;
+        };
+        let Some(params) = self
+            .symbols
+            .get(sym)
+            .and_then(|name| self.fn_param_types.get(name))
+            .cloned()
+        else {
+            return;
+        };
+        let named: Vec<Option<String>> = args
+            .iter()
+            .map(|arg| match arg {
+                HirArg::Named { name, .. } => self.symbols.get(name).cloned(),
+                HirArg::Pos(_) => None,
+            })
+            .collect();
+        let mut positional = params
+            .iter()
+            .filter(|(param, _)| !named.iter().flatten().any(|name| name == param));
+        for (arg, name) in args.iter_mut().zip(&named) {
+            let ty = match name {
+                Some(name) => params
+                    .iter()
+                    .find(|(param, _)| param == name)
+                    .and_then(|(_, ty)| ty.as_ref()),
+                None if matches!(arg, HirArg::Pos(_)) => {
+                    positional.next().and_then(|(_, ty)| ty.as_ref())
+                }
+                None => None,
+            };
+            let (HirArg::Pos(value) | HirArg::Named { value, .. }) = arg;
+            let value_expr = std::mem::replace(value, HirExpr::Lit(HirLit::Null));
+            *value = self.with_s3_class(value_expr, ty);
+        }
+    }
+
+    /// Apply [`Self::with_s3_class`] to the return made from a function's
+    /// tail expression, which is lowered before it becomes a return.
+    pub(crate) fn apply_s3_tail_class(&mut self, mut body: HirBlock) -> HirBlock {
+        let ret_ty = self.current_ret_ty.clone();
+        if let Some(HirStmt::Return {
+            value: Some(value), ..
+        }) = body.stmts.last_mut()
+        {
+            let tail = std::mem::replace(value, HirExpr::Lit(HirLit::Null));
+            *value = self.with_s3_class(tail, ret_ty.as_ref());
+        }
+        body
+    }
+}
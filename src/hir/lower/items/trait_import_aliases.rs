@@ -0,0 +1,324 @@
+use super::*;
+impl Lowerer {
+    /// Rewrite traits selected under another name by `import { Area as PhysArea }`
+    /// back to the trait's own name before the module is lowered.
+    ///
+    /// Trait metadata is keyed by trait name, so the rename is applied to the
+    /// module's source: impl headers, supertraits, where clauses, `dyn` and
+    /// projection types, and `Trait::method(...)` calls. Imported traits are
+    /// registered by then because a module's imports are lowered first.
+    pub fn resolve_trait_import_aliases(&self, prog: &mut ast::Program) -> RR<()> {
+        let mut aliases: FxHashMap<String, String> = FxHashMap::default();
+        for stmt in &prog.stmts {
+            let ast::StmtKind::Import {
+                source: ast::ImportSource::Module,
+                spec: ast::ImportSpec::Named(bindings),
+                ..
+            } = &stmt.kind
+            else {
+                continue;
+            };
+            for binding in bindings {
+                let Some(local) = binding
+                    .local
+                    .as_ref()
+                    .filter(|local| **local != binding.imported)
+                else {
+                    continue;
+                };
+                if !self.trait_defs.contains_key(&binding.imported) {
+                    continue;
+                }
+                if let Some(prev) = aliases.get(local)
+                    && *prev != binding.imported
+                {
+                    return Err(trait_alias_error(
+                        format!(
+                            "module import local '{}' is already bound to trait '{}'; use 'as' to choose a different local name",
+                            local, prev
+                        ),
+                        stmt.span,
+                    ));
+                }
+                aliases.insert(local.clone(), binding.imported.clone());
+            }
+        }
+        if aliases.is_empty() {
+            return Ok(());
+        }
+        for stmt in &prog.stmts {
+            if let ast::StmtKind::TraitDecl(decl) = &stmt.kind
+                && let Some(imported) = aliases.get(&decl.name)
+            {
+                return Err(trait_alias_error(
+                    format!(
+                        "trait '{}' conflicts with the import of '{}' as '{}'",
+                        decl.name, imported, decl.name
+                    ),
+                    stmt.span,
+                ));
+            }
+        }
+
+        let renamer = TraitAliasRenamer { aliases: &aliases };
+        for stmt in &mut prog.stmts {
+            renamer.stmt(stmt);
+        }
+        Ok(())
+    }
+}
+
+struct TraitAliasRenamer<'a> {
+    aliases: &'a FxHashMap<String, String>,
+}
+
+impl TraitAliasRenamer<'_> {
+    fn trait_name(&self, name: &mut String) {
+        if let Some(imported) = self.aliases.get(name.as_str()) {
+            name.clone_from(imported);
+        }
+    }
+
+    fn type_name(&self, name: &mut String) {
+        if let Some(trait_name) = name.strip_prefix("dyn ")
+            && let Some(imported) = self.aliases.get(trait_name)
+        {
+            *name = format!("dyn {}", imported);
+            return;
+        }
+        if let Some(parts) = Lowerer::type_projection_parts(name)
+            && let Some(imported) = parts.trait_name.and_then(|t| self.aliases.get(t))
+        {
+            *name = Lowerer::qualified_type_projection_key(parts.base, imported, parts.assoc);
+        }
+    }
+
+    fn type_expr(&self, ty: &mut ast::TypeExpr) {
+        match ty {
+            ast::TypeExpr::Named(name) => self.type_name(name),
+            ast::TypeExpr::Generic { base, args } => {
+                self.type_name(base);
+                for arg in args {
+                    self.type_expr(arg);
+                }
+            }
+        }
+    }
+
+    fn bounds(&self, bounds: &mut [ast::TraitBound]) {
+        for bound in bounds {
+            self.type_name(&mut bound.type_name);
+            for trait_name in &mut bound.trait_names {
+                self.trait_name(trait_name);
+            }
+        }
+    }
+
+    fn signature(
+        &self,
+        params: &mut [ast::FnParam],
+        ret_ty_hint: &mut Option<ast::TypeExpr>,
+        where_bounds: &mut [ast::TraitBound],
+    ) {
+        for param in params {
+            if let Some(ty) = &mut param.ty_hint {
+                self.type_expr(ty);
+            }
+            if let Some(default) = &mut param.default {
+                self.expr(default);
+            }
+        }
+        if let Some(ty) = ret_ty_hint {
+            self.type_expr(ty);
+        }
+        self.bounds(where_bounds);
+    }
+
+    fn fn_decl(&self, decl: &mut ast::FnDecl) {
+        self.signature(
+            &mut decl.params,
+            &mut decl.ret_ty_hint,
+            &mut decl.where_bounds,
+        );
+        self.block(&mut decl.body);
+    }
+
+    fn block(&self, block: &mut ast::Block) {
+        for stmt in &mut block.stmts {
+            self.stmt(stmt);
+        }
+    }
+
+    fn stmt(&self, stmt: &mut ast::Stmt) {
+        match &mut stmt.kind {
+            ast::StmtKind::Let { ty_hint, init, .. } => {
+                if let Some(ty) = ty_hint {
+                    self.type_expr(ty);
+                }
+                if let Some(init) = init {
+                    self.expr(init);
+                }
+            }
+            ast::StmtKind::Assign { target, value } => {
+                match &mut target.kind {
+                    ast::LValueKind::Name(_) => {}
+                    ast::LValueKind::Index { base, idx } => {
+                        self.expr(base);
+                        idx.iter_mut().for_each(|e| self.expr(e));
+                    }
+                    ast::LValueKind::Field { base, .. } => self.expr(base),
+                }
+                self.expr(value);
+            }
+            ast::StmtKind::FnDecl {
+                params,
+                ret_ty_hint,
+                where_bounds,
+                body,
+                ..
+            } => {
+                self.signature(params, ret_ty_hint, where_bounds);
+                self.block(body);
+            }
+            ast::StmtKind::Export(decl) => self.fn_decl(decl),
+            ast::StmtKind::TraitDecl(decl) => {
+                for supertrait in &mut decl.supertraits {
+                    self.trait_name(supertrait);
+                }
+                self.bounds(&mut decl.where_bounds);
+                for assoc in &mut decl.assoc_consts {
+                    self.type_expr(&mut assoc.ty_hint);
+                    if let Some(default) = &mut assoc.default {
+                        self.expr(default);
+                    }
+                }
+                for method in &mut decl.methods {
+                    self.signature(
+                        &mut method.params,
+                        &mut method.ret_ty_hint,
+                        &mut method.where_bounds,
+                    );
+                    if let Some(body) = &mut method.default_body {
+                        self.block(body);
+                    }
+                }
+            }
+            ast::StmtKind::ImplDecl(decl) => {
+                self.trait_name(&mut decl.trait_name);
+                self.type_expr(&mut decl.for_ty);
+                self.bounds(&mut decl.where_bounds);
+                for assoc in &mut decl.assoc_types {
+                    self.type_expr(&mut assoc.ty);
+                }
+                for assoc in &mut decl.assoc_consts {
+                    self.type_expr(&mut assoc.ty_hint);
+                    self.expr(&mut assoc.value);
+                }
+                for method in &mut decl.methods {
+                    self.fn_decl(method);
+                }
+            }
+            ast::StmtKind::If {
+                cond,
+                then_blk,
+                else_blk,
+            } => {
+                self.expr(cond);
+                self.block(then_blk);
+                if let Some(else_blk) = else_blk {
+                    self.block(else_blk);
+                }
+            }
+            ast::StmtKind::While { cond, body } => {
+                self.expr(cond);
+                self.block(body);
+            }
+            ast::StmtKind::For { iter, body, .. } => {
+                self.expr(iter);
+                self.block(body);
+            }
+            ast::StmtKind::Return { value } => {
+                if let Some(value) = value {
+                    self.expr(value);
+                }
+            }
+            ast::StmtKind::ExprStmt { expr } | ast::StmtKind::Expr(expr) => self.expr(expr),
+            ast::StmtKind::Break
+            | ast::StmtKind::Next
+            | ast::StmtKind::UnsafeRBlock { .. }
+            | ast::StmtKind::Import { .. }
+            | ast::StmtKind::ReExport { .. } => {}
+        }
+    }
+
+    fn expr(&self, expr: &mut ast::Expr) {
+        match &mut expr.kind {
+            ast::ExprKind::Call {
+                callee,
+                type_args,
+                args,
+            } => {
+                // `PhysArea::area(x)` and `PhysArea::area::<T>(x)` name the trait.
+                if let ast::ExprKind::Field { base, .. } = &mut callee.kind
+                    && let ast::ExprKind::Name(name) = &mut base.kind
+                {
+                    self.trait_name(name);
+                }
+                self.expr(callee);
+                type_args.iter_mut().for_each(|ty| self.type_expr(ty));
+                args.iter_mut().for_each(|arg| self.expr(arg));
+            }
+            ast::ExprKind::Lambda {
+                params,
+                ret_ty_hint,
+                body,
+            } => {
+                self.signature(params, ret_ty_hint, &mut []);
+                self.block(body);
+            }
+            ast::ExprKind::Unary { rhs, .. } => self.expr(rhs),
+            ast::ExprKind::Formula { lhs, rhs } => {
+                if let Some(lhs) = lhs {
+                    self.expr(lhs);
+                }
+                self.expr(rhs);
+            }
+            ast::ExprKind::Binary { lhs, rhs, .. } | ast::ExprKind::Range { a: lhs, b: rhs } => {
+                self.expr(lhs);
+                self.expr(rhs);
+            }
+            ast::ExprKind::Pipe { lhs, rhs_call } => {
+                self.expr(lhs);
+                self.expr(rhs_call);
+            }
+            ast::ExprKind::NamedArg { value, .. } => self.expr(value),
+            ast::ExprKind::Index { base, idx } => {
+                self.expr(base);
+                idx.iter_mut().for_each(|e| self.expr(e));
+            }
+            ast::ExprKind::Field { base, .. } => self.expr(base),
+            ast::ExprKind::VectorLit(items) => items.iter_mut().for_each(|e| self.expr(e)),
+            ast::ExprKind::RecordLit(fields) => {
+                fields.iter_mut().for_each(|(_, e)| self.expr(e));
+            }
+            ast::ExprKind::Try { expr } | ast::ExprKind::Unquote(expr) => self.expr(expr),
+            ast::ExprKind::Match { scrutinee, arms } => {
+                self.expr(scrutinee);
+                for arm in arms {
+                    if let Some(guard) = &mut arm.guard {
+                        self.expr(guard);
+                    }
+                    self.expr(&mut arm.body);
+                }
+            }
+            ast::ExprKind::Lit(_)
+            | ast::ExprKind::Name(_)
+            | ast::ExprKind::ColRef(_)
+            | ast::ExprKind::Column(_) => {}
+        }
+    }
+}
+
+fn trait_alias_error(message: String, span: Span) -> RRException {
+    RRException::new("RR.SemanticError", RRCode::E1002, Stage::Lower, message).at(span)
+}
@@ -126,6 +126,7 @@ impl Lowerer {
                     methods: Vec::new(),
                     span,
                     public: decl.public,
+                    s3: false,
                 },
                 Vec::new(),
             ));
@@ -154,6 +155,7 @@ impl Lowerer {
                     methods: Vec::new(),
                     span,
                     public: decl.public,
+                    s3: false,
                 },
                 Vec::new(),
             ));
@@ -216,6 +218,7 @@ impl Lowerer {
                     where_bounds: trait_method.where_bounds.clone(),
                     body: Self::substitute_block_type_hints(default_body, &impl_type_subst),
                     public: false,
+                    doc: trait_method.doc.clone(),
                 }
             } else {
                 continue;
@@ -338,6 +341,7 @@ impl Lowerer {
                 methods: hir_methods,
                 span,
                 public: decl.public,
+                s3: decl.s3,
             },
             lowered_fns,
         ))
@@ -3,12 +3,18 @@ impl Lowerer {
     pub fn preload_module_metadata(&mut self, prog: &ast::Program) -> RR<()> {
         self.register_trait_decls(&prog.stmts)?;
         self.register_generic_fn_decls(&prog.stmts)?;
+        self.register_fn_param_types(&prog.stmts);
         self.register_impl_decls(&prog.stmts)
     }
     pub fn preload_public_module_metadata(&mut self, prog: &ast::Program) -> RR<()> {
         let stmts = self.public_metadata_stmts(&prog.stmts);
         self.register_trait_decls(&stmts)?;
         self.register_generic_fn_decls(&stmts)?;
+        self.register_fn_param_types(
+            prog.stmts
+                .iter()
+                .filter(|stmt| matches!(stmt.kind, ast::StmtKind::Export(_))),
+        );
         self.register_impl_decls(&stmts)
     }
     pub(crate) fn public_metadata_stmts(&self, stmts: &[ast::Stmt]) -> Vec<ast::Stmt> {
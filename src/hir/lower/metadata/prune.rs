@@ -5,6 +5,7 @@ impl Lowerer {
         let mut public_traits = FxHashSet::default();
         let mut module_impl_traits = FxHashSet::default();
         let mut private_generic_fns = Vec::new();
+        let mut private_fns = Vec::new();
 
         for stmt in &prog.stmts {
             match &stmt.kind {
@@ -20,6 +21,7 @@ impl Lowerer {
                 ast::StmtKind::FnDecl {
                     name, type_params, ..
                 } if !type_params.is_empty() => private_generic_fns.push(name.clone()),
+                ast::StmtKind::FnDecl { name, .. } => private_fns.push(name.clone()),
                 _ => {}
             }
         }
@@ -27,6 +29,9 @@ impl Lowerer {
         for name in private_generic_fns {
             self.generic_fns.remove(&name);
         }
+        for name in private_fns {
+            self.fn_param_types.remove(&name);
+        }
 
         self.trait_defs
             .retain(|name, _| !module_traits.contains(name) || public_traits.contains(name));
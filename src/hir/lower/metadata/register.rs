@@ -132,6 +132,40 @@ impl Lowerer {
         }
         Ok(())
     }
+    pub(crate) fn register_fn_param_types<'a>(
+        &mut self,
+        stmts: impl IntoIterator<Item = &'a ast::Stmt>,
+    ) {
+        for stmt in stmts {
+            let (name, params) = match &stmt.kind {
+                ast::StmtKind::FnDecl {
+                    name,
+                    type_params,
+                    params,
+                    ..
+                } if type_params.is_empty() => (name, params),
+                ast::StmtKind::Export(fndecl) if fndecl.type_params.is_empty() => {
+                    (&fndecl.name, &fndecl.params)
+                }
+                _ => continue,
+            };
+            let param_types = params
+                .iter()
+                .map(|param| {
+                    let ty = param.ty_hint.as_ref().map(Self::ast_type_ref);
+                    (param.name.clone(), ty)
+                })
+                .collect::<Vec<_>>();
+            if param_types
+                .iter()
+                .any(|(_, ty)| matches!(ty, Some(HirTypeRef::Named(_))))
+            {
+                self.fn_param_types.insert(name.clone(), param_types);
+            } else {
+                self.fn_param_types.remove(name);
+            }
+        }
+    }
     pub(crate) fn register_generic_fn_decls(&mut self, stmts: &[ast::Stmt]) -> RR<()> {
         for stmt in stmts {
             let fndecl = match &stmt.kind {
@@ -142,6 +176,7 @@ impl Lowerer {
                     ret_ty_hint,
                     where_bounds,
                     body,
+                    doc,
                 } if !type_params.is_empty() => ast::FnDecl {
                     name: name.clone(),
                     type_params: type_params.clone(),
@@ -150,6 +185,7 @@ impl Lowerer {
                     where_bounds: where_bounds.clone(),
                     body: body.clone(),
                     public: false,
+                    doc: doc.clone(),
                 },
                 ast::StmtKind::Export(fndecl) if !fndecl.type_params.is_empty() => fndecl.clone(),
                 _ => continue,
@@ -179,6 +215,10 @@ impl Lowerer {
 
     pub(crate) fn register_impl_decl(&mut self, span: Span, decl: &ast::ImplDecl) -> RR<()> {
         let plan = self.prepare_impl_registration(span, decl)?;
+        if decl.s3 {
+            Self::validate_s3_impl(span, decl, &plan.for_ty, plan.is_generic_impl)?;
+            self.s3_classes.insert(plan.for_ty.key());
+        }
         if decl.negative {
             self.register_negative_impl_decl(span, decl, plan)
         } else {
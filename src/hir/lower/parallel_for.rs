@@ -0,0 +1,698 @@
+//! Desugaring of `parallel for` loops.
+//!
+//! The loop body is lifted into a lambda over an iteration sub-range and the
+//! variables the loop writes in the enclosing scope ("slots"):
+//!
+//! ```text
+//! let .__rr_pfor_L_C = rr_parallel_for("L:C", lo, hi,
+//!     function(.__rr_pfor_lo, .__rr_pfor_hi, acc, out) {
+//!         for (i in .__rr_pfor_lo...__rr_pfor_hi) { body }
+//!         { acc: acc, out: out }
+//!     },
+//!     c("sum", "slice"), { acc: acc, out: out }, "")
+//! acc <- .__rr_pfor_L_C.acc
+//! out <- .__rr_pfor_L_C.out
+//! ```
+//!
+//! A slot is a `reduce(...)` accumulator, an array written only at the loop
+//! variable (`out[i] <- ...`, a "slice"), or any other write ("value"). The
+//! syntactic checks here record the first reason the loop cannot run in
+//! parallel as the last argument; the MIR proof in
+//! `compiler::pipeline::check_parallel_for_loops` adds the dependence check
+//! and decides between the parallel helper and `rr_parallel_for_serial`.
+use super::*;
+
+const PFOR_LO: &str = ".__rr_pfor_lo";
+const PFOR_HI: &str = ".__rr_pfor_hi";
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum SlotKind {
+    Reduce(ast::ReductionOp),
+    Slice,
+    Value,
+}
+
+impl SlotKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Reduce(op) => op.as_str(),
+            Self::Slice => "slice",
+            Self::Value => "value",
+        }
+    }
+}
+
+struct BodyScan<'a> {
+    lowerer: &'a Lowerer,
+    var: &'a str,
+    reductions: &'a [ast::ParallelReduction],
+    /// Names bound inside the body, innermost block last.
+    scopes: Vec<FxHashSet<String>>,
+    loop_depth: usize,
+    slots: Vec<(String, SlotKind)>,
+    reject: Option<(RRCode, String)>,
+}
+
+impl BodyScan<'_> {
+    fn reject(&mut self, code: RRCode, reason: String) {
+        if self.reject.is_none() {
+            self.reject = Some((code, reason));
+        }
+    }
+
+    fn is_inner(&self, name: &str) -> bool {
+        self.scopes.iter().any(|scope| scope.contains(name))
+    }
+
+    fn is_outer(&self, name: &str) -> bool {
+        !self.is_inner(name) && self.lowerer.lookup(name).is_some()
+    }
+
+    fn reduction(&self, name: &str) -> Option<ast::ReductionOp> {
+        if self.is_inner(name) {
+            return None;
+        }
+        self.reductions
+            .iter()
+            .find(|red| red.var == name)
+            .map(|red| red.op)
+    }
+
+    /// `[i]` with `i` the parallel loop variable, not an inner rebinding.
+    fn is_loop_var_index(&self, idx: &[ast::Expr]) -> bool {
+        let [index] = idx else {
+            return false;
+        };
+        is_name(index, self.var)
+            && !self
+                .scopes
+                .iter()
+                .skip(1)
+                .any(|scope| scope.contains(self.var))
+    }
+
+    fn record_slot(&mut self, name: &str, kind: SlotKind) {
+        match self.slots.iter_mut().find(|(slot, _)| slot == name) {
+            Some((_, existing)) => {
+                if *existing == SlotKind::Slice && kind == SlotKind::Value {
+                    *existing = SlotKind::Value;
+                }
+            }
+            None => self.slots.push((name.to_string(), kind)),
+        }
+    }
+
+    fn block(&mut self, block: &ast::Block) -> RR<()> {
+        self.scopes.push(FxHashSet::default());
+        for stmt in &block.stmts {
+            self.stmt(stmt)?;
+        }
+        self.scopes.pop();
+        Ok(())
+    }
+
+    fn declare(&mut self, name: &str) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(name.to_string());
+        }
+    }
+
+    fn stmt(&mut self, stmt: &ast::Stmt) -> RR<()> {
+        match &stmt.kind {
+            ast::StmtKind::Let { name, init, .. } => {
+                if let Some(init) = init {
+                    self.expr(init);
+                }
+                self.declare(name);
+            }
+            ast::StmtKind::Assign { target, value } => self.assign(target, value),
+            ast::StmtKind::If {
+                cond,
+                then_blk,
+                else_blk,
+            } => {
+                self.expr(cond);
+                self.block(then_blk)?;
+                if let Some(else_blk) = else_blk {
+                    self.block(else_blk)?;
+                }
+            }
+            ast::StmtKind::While { cond, body } => {
+                self.expr(cond);
+                self.loop_depth += 1;
+                self.block(body)?;
+                self.loop_depth -= 1;
+            }
+            ast::StmtKind::For {
+                var, iter, body, ..
+            } => {
+                self.expr(iter);
+                self.scopes.push(FxHashSet::from_iter([var.clone()]));
+                self.loop_depth += 1;
+                self.block(body)?;
+                self.loop_depth -= 1;
+                self.scopes.pop();
+            }
+            ast::StmtKind::Return { .. } => {
+                return Err(RRException::new(
+                    "RR.SemanticError",
+                    RRCode::E1030,
+                    Stage::Lower,
+                    "`return` cannot leave a parallel for loop".to_string(),
+                )
+                .at(stmt.span)
+                .help("compute the value into a reduce(...) accumulator instead"));
+            }
+            ast::StmtKind::Break if self.loop_depth == 0 => self.reject(
+                RRCode::E1030,
+                "`break` makes later iterations depend on earlier ones".to_string(),
+            ),
+            ast::StmtKind::UnsafeRBlock { .. } => self.reject(
+                RRCode::E1030,
+                "an `unsafe r` block may touch any variable".to_string(),
+            ),
+            ast::StmtKind::ExprStmt { expr } | ast::StmtKind::Expr(expr) => self.expr(expr),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn assign(&mut self, target: &ast::LValue, value: &ast::Expr) {
+        match &target.kind {
+            ast::LValueKind::Name(name) => {
+                if let Some(op) = self.reduction(name) {
+                    match reduction_operands(op, name, value) {
+                        Some(operands) => {
+                            for operand in operands {
+                                self.expr(operand);
+                            }
+                        }
+                        None => {
+                            self.reject(
+                                RRCode::E1032,
+                                format!(
+                                    "`{name}` is updated in a way that is not a `{}` reduction",
+                                    op.as_str()
+                                ),
+                            );
+                            self.expr(value);
+                        }
+                    }
+                    return;
+                }
+                self.expr(value);
+                if self.is_outer(name) {
+                    self.record_slot(name, SlotKind::Value);
+                    self.reject(
+                        RRCode::E1030,
+                        format!("`{name}` carries a value from one iteration to the next"),
+                    );
+                }
+            }
+            ast::LValueKind::Index { base, idx } => {
+                for expr in idx {
+                    self.expr(expr);
+                }
+                self.expr(value);
+                let Some(root) = lvalue_root(base) else {
+                    return;
+                };
+                if self.reduction(root).is_some() {
+                    self.reject(
+                        RRCode::E1032,
+                        format!("reduction accumulator `{root}` is written element-wise"),
+                    );
+                    return;
+                }
+                if !self.is_outer(root) {
+                    return;
+                }
+                if matches!(&base.kind, ast::ExprKind::Name(_)) && self.is_loop_var_index(idx) {
+                    self.record_slot(root, SlotKind::Slice);
+                } else {
+                    self.record_slot(root, SlotKind::Value);
+                    self.reject(
+                        RRCode::E1030,
+                        format!("`{root}` is written at an index other than `{}`", self.var),
+                    );
+                }
+            }
+            ast::LValueKind::Field { base, .. } => {
+                self.expr(value);
+                if let Some(root) = lvalue_root(base)
+                    && self.is_outer(root)
+                {
+                    self.record_slot(root, SlotKind::Value);
+                    self.reject(
+                        RRCode::E1030,
+                        format!("field of `{root}` is written in every iteration"),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Reading an accumulator anywhere but its own update would observe a
+    /// partial, chunk-local value.
+    fn expr(&mut self, expr: &ast::Expr) {
+        let mut mentioned = Vec::new();
+        collect_names(expr, &mut mentioned);
+        for name in mentioned {
+            if self.reduction(name).is_some() {
+                self.reject(
+                    RRCode::E1032,
+                    format!("reduction accumulator `{name}` is read inside the loop"),
+                );
+            }
+        }
+    }
+}
+
+fn lvalue_root(expr: &ast::Expr) -> Option<&str> {
+    match &expr.kind {
+        ast::ExprKind::Name(name) => Some(name),
+        ast::ExprKind::Index { base, .. } | ast::ExprKind::Field { base, .. } => lvalue_root(base),
+        _ => None,
+    }
+}
+
+fn is_name(expr: &ast::Expr, name: &str) -> bool {
+    matches!(&expr.kind, ast::ExprKind::Name(n) if n == name)
+}
+
+/// Operands other than the accumulator in `acc <- acc + a + b`,
+/// `acc <- a * acc` or `acc <- max(acc, a)`, or `None` when `value` is not an
+/// update of that shape.
+fn reduction_operands<'e>(
+    op: ast::ReductionOp,
+    acc: &str,
+    value: &'e ast::Expr,
+) -> Option<Vec<&'e ast::Expr>> {
+    let mut operands = Vec::new();
+    match op {
+        ast::ReductionOp::Sum | ast::ReductionOp::Prod => {
+            let bin = if op == ast::ReductionOp::Sum {
+                ast::BinOp::Add
+            } else {
+                ast::BinOp::Mul
+            };
+            flatten_binary(value, bin, &mut operands);
+        }
+        ast::ReductionOp::Min | ast::ReductionOp::Max => {
+            let ast::ExprKind::Call {
+                callee,
+                type_args,
+                args,
+            } = &value.kind
+            else {
+                return None;
+            };
+            if !is_name(callee, op.as_str())
+                || !type_args.is_empty()
+                || args.len() < 2
+                || args
+                    .iter()
+                    .any(|arg| matches!(arg.kind, ast::ExprKind::NamedArg { .. }))
+            {
+                return None;
+            }
+            operands.extend(args.iter());
+        }
+    }
+    let acc_pos = operands.iter().position(|expr| is_name(expr, acc))?;
+    if operands.len() < 2 {
+        return None;
+    }
+    operands.remove(acc_pos);
+    Some(operands)
+}
+
+fn flatten_binary<'e>(expr: &'e ast::Expr, op: ast::BinOp, out: &mut Vec<&'e ast::Expr>) {
+    match &expr.kind {
+        ast::ExprKind::Binary {
+            op: inner,
+            lhs,
+            rhs,
+        } if *inner == op => {
+            flatten_binary(lhs, op, out);
+            flatten_binary(rhs, op, out);
+        }
+        _ => out.push(expr),
+    }
+}
+
+fn collect_names<'e>(expr: &'e ast::Expr, out: &mut Vec<&'e str>) {
+    match &expr.kind {
+        ast::ExprKind::Name(name) => out.push(name),
+        ast::ExprKind::Lit(_) | ast::ExprKind::ColRef(_) | ast::ExprKind::Column(_) => {}
+        ast::ExprKind::Unary { rhs, .. } => collect_names(rhs, out),
+        ast::ExprKind::Formula { lhs, rhs } => {
+            if let Some(lhs) = lhs {
+                collect_names(lhs, out);
+            }
+            collect_names(rhs, out);
+        }
+        ast::ExprKind::Binary { lhs, rhs, .. } => {
+            collect_names(lhs, out);
+            collect_names(rhs, out);
+        }
+        ast::ExprKind::Range { a, b } => {
+            collect_names(a, out);
+            collect_names(b, out);
+        }
+        ast::ExprKind::Lambda { body, .. } => {
+            for stmt in &body.stmts {
+                collect_stmt_names(stmt, out);
+            }
+        }
+        ast::ExprKind::Call { callee, args, .. } => {
+            collect_names(callee, out);
+            for arg in args {
+                collect_names(arg, out);
+            }
+        }
+        ast::ExprKind::NamedArg { value, .. } => collect_names(value, out),
+        ast::ExprKind::Index { base, idx } => {
+            collect_names(base, out);
+            for expr in idx {
+                collect_names(expr, out);
+            }
+        }
+        ast::ExprKind::Field { base, .. } => collect_names(base, out),
+        ast::ExprKind::VectorLit(items) => {
+            for item in items {
+                collect_names(item, out);
+            }
+        }
+        ast::ExprKind::RecordLit(fields) => {
+            for (_, value) in fields {
+                collect_names(value, out);
+            }
+        }
+        ast::ExprKind::Pipe { lhs, rhs_call } => {
+            collect_names(lhs, out);
+            collect_names(rhs_call, out);
+        }
+        ast::ExprKind::Try { expr } | ast::ExprKind::Unquote(expr) => collect_names(expr, out),
+        ast::ExprKind::Match { scrutinee, arms } => {
+            collect_names(scrutinee, out);
+            for arm in arms {
+                if let Some(guard) = &arm.guard {
+                    collect_names(guard, out);
+                }
+                collect_names(&arm.body, out);
+            }
+        }
+    }
+}
+
+fn collect_stmt_names<'e>(stmt: &'e ast::Stmt, out: &mut Vec<&'e str>) {
+    let block = |block: &'e ast::Block, out: &mut Vec<&'e str>| {
+        for stmt in &block.stmts {
+            collect_stmt_names(stmt, out);
+        }
+    };
+    match &stmt.kind {
+        ast::StmtKind::Let {
+            init: Some(init), ..
+        } => collect_names(init, out),
+        ast::StmtKind::Assign { target, value } => {
+            match &target.kind {
+                ast::LValueKind::Name(name) => out.push(name),
+                ast::LValueKind::Index { base, idx } => {
+                    collect_names(base, out);
+                    for expr in idx {
+                        collect_names(expr, out);
+                    }
+                }
+                ast::LValueKind::Field { base, .. } => collect_names(base, out),
+            }
+            collect_names(value, out);
+        }
+        ast::StmtKind::If {
+            cond,
+            then_blk,
+            else_blk,
+        } => {
+            collect_names(cond, out);
+            block(then_blk, out);
+            if let Some(else_blk) = else_blk {
+                block(else_blk, out);
+            }
+        }
+        ast::StmtKind::While { cond, body } => {
+            collect_names(cond, out);
+            block(body, out);
+        }
+        ast::StmtKind::For { iter, body, .. } => {
+            collect_names(iter, out);
+            block(body, out);
+        }
+        ast::StmtKind::Return { value: Some(expr) }
+        | ast::StmtKind::ExprStmt { expr }
+        | ast::StmtKind::Expr(expr) => collect_names(expr, out),
+        _ => {}
+    }
+}
+
+fn name_expr(name: &str, span: Span) -> ast::Expr {
+    ast::Expr {
+        kind: ast::ExprKind::Name(name.to_string()),
+        span,
+    }
+}
+
+fn lit_expr(lit: ast::Lit, span: Span) -> ast::Expr {
+    ast::Expr {
+        kind: ast::ExprKind::Lit(lit),
+        span,
+    }
+}
+
+fn call_expr(callee: &str, args: Vec<ast::Expr>, span: Span) -> ast::Expr {
+    ast::Expr {
+        kind: ast::ExprKind::Call {
+            callee: Box::new(name_expr(callee, span)),
+            type_args: Vec::new(),
+            args,
+        },
+        span,
+    }
+}
+
+fn slot_record(slots: &[(String, SlotKind)], span: Span) -> ast::Expr {
+    ast::Expr {
+        kind: ast::ExprKind::RecordLit(
+            slots
+                .iter()
+                .map(|(name, _)| (name.clone(), name_expr(name, span)))
+                .collect(),
+        ),
+        span,
+    }
+}
+
+/// Loop bounds of the iterator, mirroring how `for` canonicalizes its
+/// iterator: `a..b`, `seq_len(n)`, and `1..length(xs)` for anything else.
+fn parallel_for_bounds(iter: ast::Expr) -> (ast::Expr, ast::Expr) {
+    let span = iter.span;
+    match iter.kind {
+        ast::ExprKind::Range { a, b } => (*a, *b),
+        ast::ExprKind::Call {
+            callee,
+            type_args,
+            mut args,
+        } if type_args.is_empty()
+            && args.len() == 1
+            && (is_name(&callee, "seq_len") || is_name(&callee, "seq_along")) =>
+        {
+            let arg = args.remove(0);
+            let hi = if is_name(&callee, "seq_len") {
+                arg
+            } else {
+                call_expr("length", vec![arg], span)
+            };
+            (lit_expr(ast::Lit::Int(1), span), hi)
+        }
+        kind => {
+            let xs = ast::Expr { kind, span };
+            (
+                lit_expr(ast::Lit::Int(1), span),
+                call_expr("length", vec![xs], span),
+            )
+        }
+    }
+}
+
+impl Lowerer {
+    /// Lower `stmt` into `out`; a `parallel for` expands to several statements.
+    pub(crate) fn lower_stmt_into(&mut self, stmt: ast::Stmt, out: &mut Vec<HirStmt>) -> RR<()> {
+        if !matches!(
+            &stmt.kind,
+            ast::StmtKind::For {
+                parallel: Some(_),
+                ..
+            }
+        ) {
+            out.push(self.lower_stmt(stmt)?);
+            return Ok(());
+        }
+        for stmt in self.desugar_parallel_for(stmt)? {
+            out.push(self.lower_stmt(stmt)?);
+        }
+        Ok(())
+    }
+
+    fn desugar_parallel_for(&mut self, stmt: ast::Stmt) -> RR<Vec<ast::Stmt>> {
+        let span = stmt.span;
+        let ast::StmtKind::For {
+            var,
+            iter,
+            body,
+            parallel: Some(clause),
+        } = stmt.kind
+        else {
+            return Err(InternalCompilerError::new(
+                Stage::Lower,
+                "parallel for desugaring called on another statement",
+            )
+            .at(span)
+            .into_exception());
+        };
+
+        let mut seen = FxHashSet::default();
+        for red in &clause.reductions {
+            if red.var == var || self.lookup(&red.var).is_none() || !seen.insert(&red.var) {
+                return Err(RRException::new(
+                    "RR.SemanticError",
+                    RRCode::E1032,
+                    Stage::Lower,
+                    format!(
+                        "reduce({}: {}) needs a distinct variable declared before the loop",
+                        red.op.as_str(),
+                        red.var
+                    ),
+                )
+                .at(red.span));
+            }
+        }
+
+        let mut scan = BodyScan {
+            lowerer: self,
+            var: &var,
+            reductions: &clause.reductions,
+            scopes: vec![FxHashSet::from_iter([var.clone()])],
+            loop_depth: 0,
+            slots: clause
+                .reductions
+                .iter()
+                .map(|red| (red.var.clone(), SlotKind::Reduce(red.op)))
+                .collect(),
+            reject: None,
+        };
+        scan.block(&body)?;
+        let BodyScan { slots, reject, .. } = scan;
+        let reject = reject
+            .map(|(code, reason)| format!("{}: {}", code.as_str(), reason))
+            .unwrap_or_default();
+
+        let (lo, hi) = parallel_for_bounds(iter);
+        let params = [PFOR_LO, PFOR_HI]
+            .into_iter()
+            .map(str::to_string)
+            .chain(slots.iter().map(|(name, _)| name.clone()))
+            .map(|name| ast::FnParam {
+                name,
+                ty_hint: None,
+                default: None,
+                span,
+            })
+            .collect::<Vec<_>>();
+        let lifted_loop = ast::Stmt {
+            kind: ast::StmtKind::For {
+                var,
+                iter: ast::Expr {
+                    kind: ast::ExprKind::Range {
+                        a: Box::new(name_expr(PFOR_LO, span)),
+                        b: Box::new(name_expr(PFOR_HI, span)),
+                    },
+                    span,
+                },
+                body,
+                parallel: None,
+            },
+            span,
+        };
+        let lifted = ast::Expr {
+            kind: ast::ExprKind::Lambda {
+                params,
+                ret_ty_hint: None,
+                body: ast::Block {
+                    stmts: vec![
+                        lifted_loop,
+                        ast::Stmt {
+                            kind: ast::StmtKind::ExprStmt {
+                                expr: slot_record(&slots, span),
+                            },
+                            span,
+                        },
+                    ],
+                    span,
+                },
+            },
+            span,
+        };
+        let kinds = ast::Expr {
+            kind: ast::ExprKind::VectorLit(
+                slots
+                    .iter()
+                    .map(|(_, kind)| lit_expr(ast::Lit::Str(kind.as_str().to_string()), span))
+                    .collect(),
+            ),
+            span,
+        };
+        let label = format!("{}:{}", span.start_line, span.start_col);
+        let result = format!(".__rr_pfor_{}_{}", span.start_line, span.start_col);
+        let call = call_expr(
+            "rr_parallel_for",
+            vec![
+                lit_expr(ast::Lit::Str(label), span),
+                lo,
+                hi,
+                lifted,
+                kinds,
+                slot_record(&slots, span),
+                lit_expr(ast::Lit::Str(reject), span),
+            ],
+            span,
+        );
+
+        let mut stmts = vec![ast::Stmt {
+            kind: ast::StmtKind::Let {
+                name: result.clone(),
+                ty_hint: None,
+                init: Some(call),
+            },
+            span,
+        }];
+        for (name, _) in &slots {
+            stmts.push(ast::Stmt {
+                kind: ast::StmtKind::Assign {
+                    target: ast::LValue {
+                        kind: ast::LValueKind::Name(name.clone()),
+                        span,
+                    },
+                    value: ast::Expr {
+                        kind: ast::ExprKind::Field {
+                            base: Box::new(name_expr(&result, span)),
+                            name: name.clone(),
+                        },
+                        span,
+                    },
+                },
+                span,
+            });
+        }
+        Ok(stmts)
+    }
+}
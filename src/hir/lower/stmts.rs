@@ -63,7 +63,7 @@ impl Lowerer {
     pub(crate) fn lower_block(&mut self, block: ast::Block) -> RR<HirBlock> {
         let mut stmts = Vec::new();
         for s in block.stmts {
-            stmts.push(self.lower_stmt(s)?);
+            self.lower_stmt_into(s, &mut stmts)?;
         }
         Ok(HirBlock {
             stmts,
@@ -87,7 +87,8 @@ impl Lowerer {
                     ty_hint.as_ref().map(Self::ast_type_ref)
                 };
                 let val = if let Some(e) = init {
-                    Some(self.lower_expr_with_expected(e, expected_ty.as_ref())?)
+                    let val = self.lower_expr_with_expected(e, expected_ty.as_ref())?;
+                    Some(self.with_s3_class(val, expected_ty.as_ref()))
                 } else {
                     None
                 };
@@ -193,7 +194,12 @@ impl Lowerer {
                     span: stmt.span,
                 })
             }
-            ast::StmtKind::For { var, iter, body } => {
+            ast::StmtKind::For {
+                var,
+                iter,
+                body,
+                parallel: None,
+            } => {
                 let iter_expr = self.lower_expr(iter)?;
                 self.enter_scope();
                 let lid = self.declare_local(&var);
@@ -254,7 +260,9 @@ impl Lowerer {
             }
             ast::StmtKind::Return { value } => {
                 let v = if let Some(e) = value {
-                    Some(self.lower_expr(e)?)
+                    let v = self.lower_expr(e)?;
+                    let ret_ty = self.current_ret_ty.clone();
+                    Some(self.with_s3_class(v, ret_ty.as_ref()))
                 } else {
                     None
                 };
@@ -232,6 +232,9 @@ impl Lowerer {
             .ret_ty_hint
             .map(|ty| Self::substitute_type_expr(ty, &subst));
         let inst_body = Self::substitute_block_type_hints(info.decl.body, &subst);
+        // The body belongs to the module that declared the generic, so its
+        // unqualified names are not the calling module's references.
+        let caller_refs = std::mem::take(&mut self.module_unqualified_refs);
         let inst_fn = self.lower_fn(LowerFnParts {
             name: inst_name,
             type_params: Vec::new(),
@@ -240,8 +243,9 @@ impl Lowerer {
             where_bounds: Vec::new(),
             body: inst_body,
             span,
-        })?;
-        self.pending_fns.push(inst_fn);
+        });
+        self.module_unqualified_refs = caller_refs;
+        self.pending_fns.push(inst_fn?);
         Ok(Some(inst_sym))
     }
 }
@@ -377,6 +377,7 @@ impl Lowerer {
                     where_bounds: trait_method.where_bounds,
                     body: default_body,
                     public: false,
+                    doc: trait_method.doc,
                 }
             } else {
                 continue;
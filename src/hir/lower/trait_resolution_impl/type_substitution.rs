@@ -94,6 +94,7 @@ impl Lowerer {
                 ret_ty_hint,
                 where_bounds,
                 body,
+                doc,
             } => ast::StmtKind::FnDecl {
                 name,
                 type_params,
@@ -104,6 +105,7 @@ impl Lowerer {
                 ret_ty_hint: ret_ty_hint.map(|ty| Self::substitute_type_expr(ty, subst)),
                 where_bounds,
                 body: Self::substitute_block_type_hints(body, subst),
+                doc,
             },
             ast::StmtKind::If {
                 cond,
@@ -118,10 +120,16 @@ impl Lowerer {
                 cond: Self::substitute_expr_type_hints(cond, subst),
                 body: Self::substitute_block_type_hints(body, subst),
             },
-            ast::StmtKind::For { var, iter, body } => ast::StmtKind::For {
+            ast::StmtKind::For {
+                var,
+                iter,
+                body,
+                parallel,
+            } => ast::StmtKind::For {
                 var,
                 iter: Self::substitute_expr_type_hints(iter, subst),
                 body: Self::substitute_block_type_hints(body, subst),
+                parallel,
             },
             ast::StmtKind::Return { value } => ast::StmtKind::Return {
                 value: value.map(|expr| Self::substitute_expr_type_hints(expr, subst)),
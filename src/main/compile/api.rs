@@ -1,7 +1,8 @@
 use rr::compiler::{
     CompileOutputOptions, CompileProfile, CompileWithProfileRequest, CompilerParallelConfig,
     IncrementalCompileOutput, IncrementalCompileRequest, IncrementalOptions, IncrementalSession,
-    IncrementalStats, OptLevel, ParallelConfig, TypeConfig, compile_incremental_request,
+    IncrementalStats, OptLevel, OptRemark, ParallelConfig, StageDumps, TypeConfig,
+    collect_opt_remarks, collect_stage_dumps, compile_incremental_request,
     compile_with_profile_request,
 };
 use rr::error::RRException;
@@ -21,6 +22,10 @@ pub(crate) struct CliCompileRequest<'a> {
     pub(crate) profile: Option<&'a mut CompileProfile>,
     pub(crate) cold_compile: bool,
     pub(crate) profile_use: Option<&'a str>,
+    /// Receives optimization remarks when `--remarks` is set.
+    pub(crate) remarks: Option<&'a mut Vec<OptRemark>>,
+    /// Receives `--emit` stage text.
+    pub(crate) stage_dumps: Option<&'a mut StageDumps>,
 }
 
 pub(crate) fn compile_output_options(
@@ -38,6 +43,21 @@ pub(crate) fn compile_output_options(
 
 pub(crate) fn compile_cli_source(
     mut req: CliCompileRequest<'_>,
+) -> Result<IncrementalCompileOutput, RRException> {
+    if let Some(dumps) = req.stage_dumps.take() {
+        dumps.clear();
+        return collect_stage_dumps(dumps, || compile_cli_source(req));
+    }
+    let Some(sink) = req.remarks.take() else {
+        return compile_cli_source_inner(req);
+    };
+    let (result, remarks) = collect_opt_remarks(|| compile_cli_source_inner(req));
+    sink.extend(remarks);
+    result
+}
+
+fn compile_cli_source_inner(
+    mut req: CliCompileRequest<'_>,
 ) -> Result<IncrementalCompileOutput, RRException> {
     super::with_profile_use_override(req.profile_use, || {
         super::with_compile_cache_override(req.cold_compile, || {
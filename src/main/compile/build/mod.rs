@@ -1,4 +1,4 @@
-use rr::compiler::{CliLog, CompileProfile};
+use rr::compiler::{CliLog, CompileProfile, OptRemark, StageDumps};
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -7,7 +7,7 @@ use super::{
     default_build_output_dir, parse_command_opts, prepare_project_entry_source,
     prepare_single_file_build_source, report_dir_create_failure, report_file_write_failure,
     report_path_read_failure, resolve_project_entry_in_dir, write_compile_profile_artifact,
-    write_compile_profile_collection,
+    write_compile_profile_collection, write_opt_remarks_report, write_stage_dumps,
 };
 
 mod files;
@@ -43,8 +43,10 @@ pub(crate) fn cmd_build(args: &[String]) -> i32 {
 
     let mut built = 0usize;
     let mut compile_profiles: Vec<(String, CompileProfile)> = Vec::new();
+    let mut remarks: Vec<OptRemark> = Vec::new();
     for rr in &plan.rr_files {
-        let Some(profile_entry) = compile_and_write_build_file(&ui, &opts, &plan, rr) else {
+        let Some(profile_entry) = compile_and_write_build_file(&ui, &opts, &plan, rr, &mut remarks)
+        else {
             return 1;
         };
         built += 1;
@@ -63,6 +65,11 @@ pub(crate) fn cmd_build(args: &[String]) -> i32 {
     if let Err(code) = write_build_profiles(&ui, &opts, &compile_profiles) {
         return code;
     }
+    if let Some(path) = opts.remarks.as_deref()
+        && let Err(code) = write_opt_remarks_report(&ui, &remarks, path)
+    {
+        return code;
+    }
     0
 }
 
@@ -191,6 +198,8 @@ fn compile_build_source(
     rr_path_str: &str,
     input: &str,
     compile_profile: &mut Option<CompileProfile>,
+    remarks: &mut Vec<OptRemark>,
+    stage_dumps: &mut StageDumps,
 ) -> Result<String, i32> {
     let output_opts = compile_output_options(opts, true);
     compile_cli_source(CliCompileRequest {
@@ -206,6 +215,8 @@ fn compile_build_source(
         profile: compile_profile.as_mut(),
         cold_compile: opts.cold_compile,
         profile_use: opts.profile_use.as_deref(),
+        remarks: opts.remarks.is_some().then_some(remarks),
+        stage_dumps: (!stage_dumps.is_empty()).then_some(stage_dumps),
     })
     .map(|out| out.r_code)
     .map_err(|err| {
@@ -219,11 +230,21 @@ fn compile_and_write_build_file(
     opts: &CommonOpts,
     plan: &BuildPlan,
     rr: &PathBuf,
+    remarks: &mut Vec<OptRemark>,
 ) -> Option<Option<(String, CompileProfile)>> {
     let rr_abs = fs::canonicalize(rr).unwrap_or_else(|_| rr.clone());
     let (rr_path_str, input) = prepare_build_source(ui, plan, &rr_abs)?;
     let mut compile_profile = opts.profile_compile.then(CompileProfile::default);
-    let r_code = compile_build_source(opts, &rr_path_str, &input, &mut compile_profile).ok()?;
+    let mut stage_dumps = StageDumps::new(opts.emit.clone(), opts.emit_functions.clone());
+    let r_code = compile_build_source(
+        opts,
+        &rr_path_str,
+        &input,
+        &mut compile_profile,
+        remarks,
+        &mut stage_dumps,
+    )
+    .ok()?;
 
     let out_file = build_output_file(
         plan.dir_mode,
@@ -245,6 +266,25 @@ fn compile_and_write_build_file(
     }
 
     ui.success(&format!("Built {} -> {}", rr.display(), out_file.display()));
+    if !stage_dumps.is_empty() {
+        let emit_dir = match &opts.emit_dir {
+            Some(dir) => {
+                let rel_parent = out_file
+                    .strip_prefix(&plan.out_root)
+                    .ok()
+                    .and_then(Path::parent)
+                    .map(Path::to_path_buf)
+                    .unwrap_or_default();
+                PathBuf::from(dir).join(rel_parent)
+            }
+            None => out_file.parent().map(Path::to_path_buf).unwrap_or_default(),
+        };
+        let stem = out_file
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "main".to_string());
+        write_stage_dumps(ui, &stage_dumps, &emit_dir, &stem).ok()?;
+    }
     Some(compile_profile.map(|profile| (rr_path_str, profile)))
 }
 
@@ -0,0 +1,27 @@
+use rr::compiler::{CliLog, StageDumps};
+use std::fs;
+use std::path::Path;
+
+use super::{report_dir_create_failure, report_file_write_failure};
+
+/// Write each `--emit` stage as `<dir>/<stem>.<suffix>`.
+pub(crate) fn write_stage_dumps(
+    ui: &CliLog,
+    dumps: &StageDumps,
+    dir: &Path,
+    stem: &str,
+) -> Result<(), i32> {
+    if let Err(e) = fs::create_dir_all(dir) {
+        report_dir_create_failure(ui, dir, &e, "--emit output directory");
+        return Err(1);
+    }
+    for (stage, text) in dumps.stage_texts() {
+        let path = dir.join(format!("{}.{}", stem, stage.file_suffix()));
+        if let Err(e) = fs::write(&path, text) {
+            report_file_write_failure(ui, &path, &e, "--emit output path");
+            return Err(1);
+        }
+        ui.success(&format!("Emitted {} -> {}", stage.as_str(), path.display()));
+    }
+    Ok(())
+}
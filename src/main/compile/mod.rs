@@ -1,9 +1,11 @@
 mod api;
 mod build;
 mod cache;
+mod emit;
 mod entry;
 mod options;
 mod profile;
+mod remarks;
 mod run;
 mod target;
 mod watch;
@@ -13,9 +15,11 @@ pub(crate) use self::run::cmd_run;
 pub(crate) use self::watch::cmd_watch;
 pub(crate) use api::{CliCompileRequest, compile_cli_source, compile_output_options};
 pub(crate) use cache::{with_compile_cache_override, with_profile_use_override};
+pub(crate) use emit::write_stage_dumps;
 pub(crate) use entry::{prepare_project_entry_source, prepare_single_file_build_source};
 pub(crate) use options::{CommandMode, CommonOpts, parse_command_opts};
 pub(crate) use profile::{write_compile_profile_artifact, write_compile_profile_collection};
+pub(crate) use remarks::write_opt_remarks_report;
 pub(crate) use target::{
     file_name_is_main_rr, resolve_command_input, resolve_project_entry_in_dir,
 };
@@ -75,7 +75,7 @@ impl CommonCompileFlag {
             Self::TypeMode => "Missing value after --type-mode (strict)",
             Self::NativeBackend => "Missing value after --native-backend (off|optional|required)",
             Self::ParallelMode => "Missing value after --parallel-mode (off|optional|required)",
-            Self::ParallelBackend => "Missing value after --parallel-backend (auto|r|openmp)",
+            Self::ParallelBackend => "Missing value after --parallel-backend (auto|r|openmp|psock)",
             Self::ParallelThreads => "Missing value after --parallel-threads",
             Self::ParallelMinTrip => "Missing value after --parallel-min-trip",
             Self::CompilerParallelMode => {
@@ -80,7 +80,7 @@ pub(super) fn apply_common_compile_flags(
             state.parallel_cfg.backend = match v.parse::<ParallelBackend>() {
                 Ok(m) => m,
                 Err(()) => {
-                    ui.error("Invalid --parallel-backend. Use auto|r|openmp");
+                    ui.error("Invalid --parallel-backend. Use auto|r|openmp|psock");
                     return Err(1);
                 }
             };
@@ -1,4 +1,4 @@
-use rr::compiler::{CliLog, CompileMode, IncrementalOptions, OptLevel};
+use rr::compiler::{CliLog, CompileMode, EmitStage, IncrementalOptions, OptLevel};
 
 mod flag_defs;
 mod flags;
@@ -18,6 +18,10 @@ pub(crate) fn parse_command_opts(
     let mut i = 0;
     while i < args.len() {
         let arg = &args[i];
+        if matches!(mode, CommandMode::Run) && arg == "--" {
+            opts.program_args = args[i + 1..].to_vec();
+            break;
+        }
         if mode.takes_output_arg(arg) {
             if i + 1 >= args.len() {
                 if matches!(mode, CommandMode::Legacy) {
@@ -43,6 +47,13 @@ pub(crate) fn parse_command_opts(
                 Ok(false) => {
                     if mode.allow_keep_r() && arg == "--keep-r" {
                         opts.keep_r = true;
+                    } else if mode.allow_coverage() && arg == "--coverage" {
+                        if i + 1 >= args.len() {
+                            ui.error("Missing value after --coverage (report file)");
+                            return Err(1);
+                        }
+                        i += 1;
+                        opts.coverage = Some(args[i].clone());
                     } else if mode.allow_no_runtime() && arg == "--no-runtime" {
                         opts.no_runtime = true;
                     } else if arg == "--preserve-all-defs" || arg == "--preserve-all-def" {
@@ -107,6 +118,50 @@ pub(crate) fn parse_command_opts(
                         }
                         i += 1;
                         opts.profile_use = Some(args[i].clone());
+                    } else if mode.allow_remarks() && arg == "--remarks" {
+                        if i + 1 >= args.len() {
+                            ui.error("Missing value after --remarks (report file)");
+                            return Err(1);
+                        }
+                        i += 1;
+                        // Cached optimized MIR would skip the passes that report.
+                        opts.cold_compile = true;
+                        opts.remarks = Some(args[i].clone());
+                    } else if mode.allow_emit() && arg == "--emit" {
+                        if i + 1 >= args.len() {
+                            ui.error("Missing value after --emit (e.g. mir,mir-opt)");
+                            return Err(1);
+                        }
+                        i += 1;
+                        opts.emit = match EmitStage::parse_list(&args[i]) {
+                            Ok(stages) => stages,
+                            Err(message) => {
+                                ui.error(&message);
+                                return Err(1);
+                            }
+                        };
+                        // Cached artifacts would skip the stages being dumped.
+                        opts.cold_compile = true;
+                    } else if mode.allow_emit() && arg == "--emit-dir" {
+                        if i + 1 >= args.len() {
+                            ui.error("Missing directory after --emit-dir");
+                            return Err(1);
+                        }
+                        i += 1;
+                        opts.emit_dir = Some(args[i].clone());
+                    } else if mode.allow_emit() && arg == "--emit-fn" {
+                        if i + 1 >= args.len() {
+                            ui.error("Missing function name after --emit-fn");
+                            return Err(1);
+                        }
+                        i += 1;
+                        opts.emit_functions.extend(
+                            args[i]
+                                .split(',')
+                                .map(str::trim)
+                                .filter(|name| !name.is_empty())
+                                .map(str::to_string),
+                        );
                     } else if arg == "--compile-mode" {
                         if i + 1 >= args.len() {
                             ui.error("Missing value after --compile-mode");
@@ -156,6 +211,11 @@ pub(crate) fn parse_command_opts(
         i += 1;
     }
 
+    if opts.emit.is_empty() && (opts.emit_dir.is_some() || !opts.emit_functions.is_empty()) {
+        ui.error("--emit-dir and --emit-fn need --emit <stages>");
+        return Err(1);
+    }
+
     if matches!(
         mode,
         CommandMode::Run | CommandMode::Build | CommandMode::Watch
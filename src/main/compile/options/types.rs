@@ -1,6 +1,6 @@
 use rr::compiler::{
-    CompileMode, CompilerParallelConfig, IncrementalOptions, OptLevel, ParallelConfig, TypeConfig,
-    default_compiler_parallel_config, default_parallel_config, default_type_config,
+    CompileMode, CompilerParallelConfig, EmitStage, IncrementalOptions, OptLevel, ParallelConfig,
+    TypeConfig, default_compiler_parallel_config, default_parallel_config, default_type_config,
 };
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -36,6 +36,18 @@ impl CommandMode {
         matches!(self, Self::Legacy | Self::Run)
     }
 
+    pub(crate) fn allow_coverage(self) -> bool {
+        matches!(self, Self::Run)
+    }
+
+    pub(crate) fn allow_remarks(self) -> bool {
+        matches!(self, Self::Legacy | Self::Run | Self::Build)
+    }
+
+    pub(crate) fn allow_emit(self) -> bool {
+        matches!(self, Self::Legacy | Self::Build)
+    }
+
     pub(crate) fn allow_no_runtime(self) -> bool {
         matches!(self, Self::Legacy)
     }
@@ -50,6 +62,7 @@ pub(crate) struct CommonOpts {
     pub(crate) target: String,
     pub(crate) output_path: Option<String>,
     pub(crate) keep_r: bool,
+    pub(crate) coverage: Option<String>,
     pub(crate) no_runtime: bool,
     pub(crate) preserve_all_defs: bool,
     pub(crate) opt_level: OptLevel,
@@ -63,10 +76,18 @@ pub(crate) struct CommonOpts {
     pub(crate) profile_compile: bool,
     pub(crate) profile_compile_out: Option<String>,
     pub(crate) profile_use: Option<String>,
+    /// Optimization remarks report path (`.json`, or human-readable text).
+    pub(crate) remarks: Option<String>,
+    /// `--emit` stages, with `--emit-dir` and the `--emit-fn` filter.
+    pub(crate) emit: Vec<EmitStage>,
+    pub(crate) emit_dir: Option<String>,
+    pub(crate) emit_functions: Vec<String>,
     pub(crate) compile_mode: CompileMode,
     pub(crate) compile_mode_explicit: bool,
     pub(crate) watch_poll_ms: u64,
     pub(crate) watch_once: bool,
+    /// Arguments after `--` on `RR run`, passed on to `main(args)`.
+    pub(crate) program_args: Vec<String>,
 }
 
 impl CommonOpts {
@@ -75,6 +96,7 @@ impl CommonOpts {
             target: mode.default_target().to_string(),
             output_path: mode.default_output_path(),
             keep_r: false,
+            coverage: None,
             no_runtime: false,
             preserve_all_defs: false,
             opt_level: OptLevel::O1,
@@ -88,6 +110,10 @@ impl CommonOpts {
             profile_compile: false,
             profile_compile_out: None,
             profile_use: None,
+            remarks: None,
+            emit: Vec::new(),
+            emit_dir: None,
+            emit_functions: Vec::new(),
             compile_mode: match mode {
                 CommandMode::Legacy => CompileMode::Standard,
                 CommandMode::Run | CommandMode::Build | CommandMode::Watch => CompileMode::FastDev,
@@ -95,6 +121,7 @@ impl CommonOpts {
             compile_mode_explicit: false,
             watch_poll_ms: 500,
             watch_once: false,
+            program_args: Vec::new(),
         }
     }
 }
@@ -0,0 +1,102 @@
+use rr::compiler::{CliLog, OptRemark, json_escape};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::{report_dir_create_failure, report_file_write_failure};
+
+/// Write `--remarks` output: JSON for a `.json` path, one line per remark
+/// (`file:line:col: kind [pass] function: message`) otherwise.
+pub(crate) fn write_opt_remarks_report(
+    ui: &CliLog,
+    remarks: &[OptRemark],
+    out_path: &str,
+) -> Result<(), i32> {
+    let path = PathBuf::from(out_path);
+    let report = if path.extension().and_then(|ext| ext.to_str()) == Some("json") {
+        opt_remarks_to_json(remarks)
+    } else {
+        opt_remarks_to_text(remarks)
+    };
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty()
+        && let Err(e) = fs::create_dir_all(parent)
+    {
+        report_dir_create_failure(ui, parent, &e, "remarks output directory");
+        return Err(1);
+    }
+    if let Err(e) = fs::write(&path, report) {
+        report_file_write_failure(ui, &path, &e, "remarks output path");
+        return Err(1);
+    }
+    ui.success(&format!(
+        "Optimization remarks ({}) -> {}",
+        remarks.len(),
+        path.display()
+    ));
+    Ok(())
+}
+
+fn remark_file(remark: &OptRemark) -> Option<String> {
+    remark
+        .file
+        .as_deref()
+        .map(Path::to_string_lossy)
+        .map(|file| file.into_owned())
+}
+
+fn opt_remarks_to_json(remarks: &[OptRemark]) -> String {
+    let mut out =
+        String::from("{\n  \"schema\": \"rr-opt-remarks\",\n  \"version\": 1,\n  \"remarks\": [");
+    for (idx, remark) in remarks.iter().enumerate() {
+        out.push_str(if idx == 0 { "\n" } else { ",\n" });
+        let file = remark_file(remark)
+            .map(|file| format!("\"{}\"", json_escape(&file)))
+            .unwrap_or_else(|| "null".to_string());
+        let reason = remark
+            .reason
+            .as_deref()
+            .map(|reason| format!("\"{}\"", json_escape(reason)))
+            .unwrap_or_else(|| "null".to_string());
+        out.push_str(&format!(
+            "    {{\"file\": {}, \"line\": {}, \"col\": {}, \"end_line\": {}, \"end_col\": {}, \"pass\": \"{}\", \"kind\": \"{}\", \"function\": \"{}\", \"message\": \"{}\", \"reason\": {}}}",
+            file,
+            remark.span.start_line,
+            remark.span.start_col,
+            remark.span.end_line,
+            remark.span.end_col,
+            remark.pass,
+            remark.kind.as_str(),
+            json_escape(&remark.function),
+            json_escape(&remark.message),
+            reason
+        ));
+    }
+    if !remarks.is_empty() {
+        out.push('\n');
+        out.push_str("  ");
+    }
+    out.push_str("]\n}\n");
+    out
+}
+
+fn opt_remarks_to_text(remarks: &[OptRemark]) -> String {
+    let mut out = String::new();
+    for remark in remarks {
+        let file = remark_file(remark).unwrap_or_else(|| "<unknown>".to_string());
+        out.push_str(&format!(
+            "{}:{}:{}: {} [{}] {}: {}",
+            file,
+            remark.span.start_line,
+            remark.span.start_col,
+            remark.kind.as_str(),
+            remark.pass,
+            remark.function,
+            remark.message
+        ));
+        if let Some(reason) = &remark.reason {
+            out.push_str(&format!(" ({reason})"));
+        }
+        out.push('\n');
+    }
+    out
+}
@@ -1,11 +1,16 @@
-use rr::compiler::{CliLog, CompileProfile, IncrementalSession};
+use rr::compiler::{
+    CliLog, CompileProfile, CoverageBuild, IncrementalOptions, IncrementalSession, OptLevel,
+    collect_module_coverage, instrument_for_coverage,
+};
 use rr::runtime::runner::Runner;
 use std::fs;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use super::{
     CliCompileRequest, CommandMode, compile_cli_source, compile_output_options, parse_command_opts,
     prepare_project_entry_source, report_path_read_failure, resolve_command_input,
-    write_compile_profile_artifact,
+    write_compile_profile_artifact, write_opt_remarks_report,
 };
 
 pub(crate) fn cmd_run(args: &[String]) -> i32 {
@@ -32,7 +37,29 @@ pub(crate) fn cmd_run(args: &[String]) -> i32 {
             return 1;
         }
     };
-    let input = match prepare_project_entry_source(&input_path, &raw_input, "run") {
+    // Coverage compiles an instrumented copy of the entry at -O0 so that no
+    // measured statement is optimized away; project modules are instrumented
+    // as they load, so incremental caches are bypassed. The implicit `main()`
+    // call is appended afterwards and stays unmeasured.
+    let mut coverage = match opts.coverage.as_deref() {
+        Some(report_path) => match instrument_for_coverage(&input_path_str, &raw_input) {
+            Ok(build) => Some((report_path, build)),
+            Err(e) => {
+                e.display(Some(&raw_input), Some(&input_path_str));
+                return 1;
+            }
+        },
+        None => None,
+    };
+    let (entry_source, opt_level, incremental) = match &coverage {
+        Some((_, build)) => (
+            build.source.as_str(),
+            OptLevel::O0,
+            IncrementalOptions::disabled(),
+        ),
+        None => (raw_input.as_str(), opts.opt_level, opts.incremental),
+    };
+    let input = match prepare_project_entry_source(&input_path, entry_source, "run") {
         Ok(source) => source,
         Err(err) => {
             err.display(Some(&raw_input), Some(&input_path_str));
@@ -42,21 +69,35 @@ pub(crate) fn cmd_run(args: &[String]) -> i32 {
 
     let output_opts = compile_output_options(&opts, true);
     let mut compile_profile = opts.profile_compile.then(CompileProfile::default);
+    let mut remarks = Vec::new();
     let mut session = IncrementalSession::default();
-    let result = compile_cli_source(CliCompileRequest {
-        entry_path: &input_path_str,
-        input: &input,
-        opt_level: opts.opt_level,
-        type_cfg: opts.type_cfg,
-        parallel_cfg: opts.parallel_cfg,
-        compiler_parallel_cfg: opts.compiler_parallel_cfg,
-        incremental: opts.incremental,
-        output_opts,
-        session: Some(&mut session),
-        profile: compile_profile.as_mut(),
-        cold_compile: opts.cold_compile,
-        profile_use: opts.profile_use.as_deref(),
-    });
+    let mut compile = || {
+        compile_cli_source(CliCompileRequest {
+            entry_path: &input_path_str,
+            input: &input,
+            opt_level,
+            type_cfg: opts.type_cfg,
+            parallel_cfg: opts.parallel_cfg,
+            compiler_parallel_cfg: opts.compiler_parallel_cfg,
+            incremental,
+            output_opts,
+            session: Some(&mut session),
+            profile: compile_profile.as_mut(),
+            cold_compile: opts.cold_compile,
+            profile_use: opts.profile_use.as_deref(),
+            remarks: opts.remarks.is_some().then_some(&mut remarks),
+            stage_dumps: None,
+        })
+    };
+    let result = match coverage.as_mut() {
+        Some((_, build)) => collect_module_coverage(build, compile),
+        None => compile(),
+    };
+    if let Some(path) = opts.remarks.as_deref()
+        && let Err(code) = write_opt_remarks_report(&ui, &remarks, path)
+    {
+        return code;
+    }
 
     match result {
         Ok(out) => {
@@ -69,16 +110,38 @@ pub(crate) fn cmd_run(args: &[String]) -> i32 {
             {
                 return code;
             }
-            let r_code = out.r_code;
-            let source_map = out.source_map;
-            Runner::run(
+            let mut r_code = out.r_code;
+            let mut source_map = out.source_map;
+            let counts_path = input_path.with_extension("rrcov");
+            if let Some((_, build)) = &coverage {
+                fs::remove_file(&counts_path).ok();
+                r_code =
+                    build.prepare_run(&r_code, &mut source_map, &counts_path.to_string_lossy());
+            }
+            let status = Runner::run(
                 &input_path_str,
                 &input,
                 &r_code,
                 &source_map,
                 None,
+                &opts.program_args,
                 opts.keep_r,
-            )
+            );
+            match &coverage {
+                Some((report_path, build)) => {
+                    match write_coverage_report(&counts_path, report_path, build) {
+                        Ok(summary) => {
+                            ui.success(&summary);
+                            status
+                        }
+                        Err(message) => {
+                            ui.error(&message);
+                            1
+                        }
+                    }
+                }
+                None => status,
+            }
         }
         Err(e) => {
             e.display(Some(&input), Some(&input_path_str));
@@ -86,3 +149,36 @@ pub(crate) fn cmd_run(args: &[String]) -> i32 {
         }
     }
 }
+
+/// Write the lcov (or, for `.xml` paths, Cobertura) report of a coverage
+/// run and return a one-line summary.
+fn write_coverage_report(
+    counts_path: &Path,
+    report_path: &str,
+    build: &CoverageBuild,
+) -> Result<String, String> {
+    // A program that never reaches a probe leaves no counts file.
+    let counts = fs::read_to_string(counts_path).unwrap_or_default();
+    fs::remove_file(counts_path).ok();
+    let report = build.report(&counts);
+    let text = if report_path.ends_with(".xml") {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis())
+            .unwrap_or_default();
+        report.cobertura(build.source_root(), timestamp)
+    } else {
+        report.lcov()
+    };
+    fs::write(report_path, text)
+        .map_err(|e| format!("failed to write coverage report {}: {}", report_path, e))?;
+    Ok(format!(
+        "coverage {}/{} lines, {}/{} branches in {} files -> {}",
+        report.lines_hit(),
+        report.line_count(),
+        report.branches_hit(),
+        report.branch_count(),
+        report.files.len(),
+        report_path
+    ))
+}
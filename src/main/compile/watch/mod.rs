@@ -144,6 +144,8 @@ pub(crate) fn cmd_watch(args: &[String]) -> i32 {
             profile: compile_profile.as_mut(),
             cold_compile: opts.cold_compile,
             profile_use: opts.profile_use.as_deref(),
+            remarks: None,
+            stage_dumps: None,
         });
 
         match watch_result {
@@ -0,0 +1,583 @@
+use rr::compiler::{CliLog, DebugBuild, compile_for_debug};
+use rr::runtime::R_DEBUG_HOOK;
+use serde_json::{Value, json};
+use std::collections::VecDeque;
+use std::env;
+use std::fs;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::path::PathBuf;
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+
+/// Prefix of every line the debug hook writes for the adapter.
+const MARKER: &str = "#<<RR-DAP>>";
+const THREAD_ID: i64 = 1;
+
+enum Event {
+    Request(Value),
+    ClientClosed,
+    RStdout(String),
+    RStderr(String),
+    RClosed,
+}
+
+struct Debuggee {
+    child: Child,
+    stdin: ChildStdin,
+    script: PathBuf,
+}
+
+struct Adapter {
+    seq: i64,
+    events: Sender<Event>,
+    program: Option<PathBuf>,
+    build: Option<DebugBuild>,
+    stop_on_entry: bool,
+    no_debug: bool,
+    breakpoints: Vec<u32>,
+    breakpoints_dirty: bool,
+    debuggee: Option<Debuggee>,
+    stopped: bool,
+    /// R function name of each frame reported by the last stack trace.
+    frames: Vec<(i64, String)>,
+    /// Requests that arrived while waiting for an answer from R.
+    queued: VecDeque<Value>,
+}
+
+fn read_message(input: &mut impl BufRead) -> io::Result<Option<Value>> {
+    let mut length = None;
+    loop {
+        let mut header = String::new();
+        if input.read_line(&mut header)? == 0 {
+            return Ok(None);
+        }
+        let header = header.trim();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':')
+            && name.trim().eq_ignore_ascii_case("content-length")
+        {
+            length = value.trim().parse::<usize>().ok();
+        }
+    }
+    let Some(length) = length else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "message without Content-Length",
+        ));
+    };
+    let mut body = vec![0; length];
+    input.read_exact(&mut body)?;
+    serde_json::from_slice(&body)
+        .map(Some)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+fn forward_lines(reader: impl Read + Send + 'static, events: Sender<Event>, stdout: bool) {
+    thread::spawn(move || {
+        for line in BufReader::new(reader).lines() {
+            let Ok(line) = line else { break };
+            let event = if stdout {
+                Event::RStdout(line)
+            } else {
+                Event::RStderr(line)
+            };
+            if events.send(event).is_err() {
+                return;
+            }
+        }
+        if stdout {
+            let _ = events.send(Event::RClosed);
+        }
+    });
+}
+
+impl Adapter {
+    fn send(&mut self, mut message: Value) {
+        self.seq += 1;
+        message["seq"] = json!(self.seq);
+        let body = message.to_string();
+        let mut out = io::stdout().lock();
+        let _ = write!(out, "Content-Length: {}\r\n\r\n{}", body.len(), body);
+        let _ = out.flush();
+    }
+
+    fn respond(&mut self, request: &Value, result: Result<Value, String>) {
+        let mut message = json!({
+            "type": "response",
+            "request_seq": request["seq"],
+            "command": request["command"],
+            "success": result.is_ok(),
+        });
+        match result {
+            Ok(body) => message["body"] = body,
+            Err(error) => message["message"] = json!(error),
+        }
+        self.send(message);
+    }
+
+    fn event(&mut self, event: &str, body: Value) {
+        self.send(json!({ "type": "event", "event": event, "body": body }));
+    }
+
+    fn output(&mut self, category: &str, text: &str) {
+        self.event(
+            "output",
+            json!({ "category": category, "output": format!("{}\n", text) }),
+        );
+    }
+
+    /// Handle one request. Returns false once the session is over.
+    fn handle(&mut self, request: Value, rx: &Receiver<Event>) -> bool {
+        let args = request["arguments"].clone();
+        let result = match request["command"].as_str().unwrap_or_default() {
+            "initialize" => Ok(json!({
+                "supportsConfigurationDoneRequest": true,
+                "supportsTerminateRequest": true,
+            })),
+            "launch" => {
+                let result = self.launch(&args);
+                let ok = result.is_ok();
+                self.respond(&request, result);
+                if ok {
+                    self.event("initialized", json!({}));
+                }
+                return true;
+            }
+            "setBreakpoints" => self.set_breakpoints(&args, rx),
+            "setExceptionBreakpoints" => Ok(json!({ "breakpoints": [] })),
+            "configurationDone" => self.start(),
+            "threads" => Ok(json!({ "threads": [{ "id": THREAD_ID, "name": "main" }] })),
+            "stackTrace" => self.stack_trace(rx),
+            "scopes" => Ok(json!({ "scopes": [{
+                "name": "Locals",
+                "variablesReference": args["frameId"],
+                "expensive": false,
+            }] })),
+            "variables" => self.variables(&args, rx),
+            "continue" => self
+                .resume("continue")
+                .map(|_| json!({ "allThreadsContinued": true })),
+            "next" => self.resume("stepover").map(|_| json!({})),
+            "stepIn" => self.resume("stepin").map(|_| json!({})),
+            "stepOut" => self.resume("stepout").map(|_| json!({})),
+            "disconnect" | "terminate" => {
+                self.stop_debuggee();
+                self.respond(&request, Ok(json!({})));
+                if request["command"] == "terminate" {
+                    self.event("terminated", json!({}));
+                    return true;
+                }
+                return false;
+            }
+            command => Err(format!("unsupported request '{}'", command)),
+        };
+        self.respond(&request, result);
+        true
+    }
+
+    fn launch(&mut self, args: &Value) -> Result<Value, String> {
+        let Some(program) = args["program"].as_str() else {
+            return Err("launch needs a `program` path".to_string());
+        };
+        let path = env::current_dir().unwrap_or_default().join(program);
+        let source = fs::read_to_string(&path)
+            .map_err(|e| format!("failed to read {}: {}", path.display(), e))?;
+        let entry = path.to_string_lossy().to_string();
+        let build = compile_for_debug(&entry, &source).map_err(|e| match e.span {
+            Some(span) => format!("{}:{}: {}", program, span.start_line, e.message),
+            None => format!("{}: {}", program, e.message),
+        })?;
+        self.program = Some(path);
+        self.build = Some(build);
+        self.no_debug = args["noDebug"].as_bool().unwrap_or(false);
+        self.stop_on_entry = !self.no_debug && args["stopOnEntry"].as_bool().unwrap_or(false);
+        Ok(json!({}))
+    }
+
+    fn set_breakpoints(&mut self, args: &Value, rx: &Receiver<Event>) -> Result<Value, String> {
+        let requested: Vec<u64> = args["breakpoints"]
+            .as_array()
+            .map(|bps| bps.iter().filter_map(|bp| bp["line"].as_u64()).collect())
+            .unwrap_or_default();
+        let same_file = match (args["source"]["path"].as_str(), &self.program) {
+            (Some(path), Some(program)) => {
+                let path = env::current_dir().unwrap_or_default().join(path);
+                fs::canonicalize(&path).ok() == fs::canonicalize(program).ok()
+            }
+            _ => false,
+        };
+        let mut breakpoints = Vec::new();
+        let mut reported = Vec::new();
+        for line in requested {
+            let resolved = self
+                .build
+                .as_ref()
+                .filter(|_| same_file)
+                .and_then(|build| build.resolve_breakpoint(line as u32));
+            reported.push(match resolved {
+                Some(resolved) => {
+                    breakpoints.push(resolved);
+                    json!({ "verified": true, "line": resolved })
+                }
+                None if same_file => json!({
+                    "verified": false,
+                    "line": line,
+                    "message": "no statement at or after this line",
+                }),
+                None => json!({
+                    "verified": false,
+                    "line": line,
+                    "message": "breakpoints are only supported in the launched program",
+                }),
+            });
+        }
+        if same_file {
+            breakpoints.sort_unstable();
+            breakpoints.dedup();
+            self.breakpoints = breakpoints;
+            self.breakpoints_dirty = true;
+            if self.stopped {
+                self.sync_breakpoints(rx);
+            }
+        }
+        Ok(json!({ "breakpoints": reported }))
+    }
+
+    fn breakpoint_list(&self) -> String {
+        self.breakpoints
+            .iter()
+            .map(|line| line.to_string())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn sync_breakpoints(&mut self, rx: &Receiver<Event>) {
+        if self.breakpoints_dirty && !self.no_debug {
+            let command = format!("bps {}", self.breakpoint_list());
+            self.query(&command, rx);
+            self.breakpoints_dirty = false;
+        }
+    }
+
+    fn start(&mut self) -> Result<Value, String> {
+        let (Some(program), Some(build)) = (&self.program, &self.build) else {
+            return Err("configurationDone before a successful launch".to_string());
+        };
+        if self.debuggee.is_some() {
+            return Ok(json!({}));
+        }
+        let script = program.with_extension("dap.R");
+        let bps = if self.no_debug {
+            String::new()
+        } else {
+            self.breakpoints
+                .iter()
+                .map(|line| format!("{}L", line))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let mode = if self.stop_on_entry {
+            "entry"
+        } else {
+            "continue"
+        };
+        let text = format!(
+            "{}\n.rr_dap$bps <- c({})\n.rr_dap$mode <- \"{}\"\n\n{}",
+            R_DEBUG_HOOK, bps, mode, build.r_code
+        );
+        fs::write(&script, text)
+            .map_err(|e| format!("failed to write {}: {}", script.display(), e))?;
+        let rscript = env::var("RRSCRIPT")
+            .ok()
+            .filter(|v| !v.trim().is_empty())
+            .unwrap_or_else(|| "Rscript".to_string());
+        // Like `RR run`, this executes the env-selected Rscript on the file
+        // written above; stdin carries the hook's commands.
+        let spawned = Command::new(&rscript /* audit: allow */)
+            .arg("--vanilla")
+            .arg(&script)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn();
+        let mut child = match spawned {
+            Ok(child) => child,
+            Err(e) => {
+                fs::remove_file(&script).ok();
+                let message = format!(
+                    "failed to execute '{}': {}; install Rscript or set RRSCRIPT=/absolute/path/to/Rscript",
+                    rscript, e
+                );
+                self.output("stderr", &message);
+                self.event("terminated", json!({}));
+                return Err(message);
+            }
+        };
+        let (Some(stdin), Some(stdout), Some(stderr)) =
+            (child.stdin.take(), child.stdout.take(), child.stderr.take())
+        else {
+            let _ = child.kill();
+            return Err(format!("failed to open pipes to '{}'", rscript));
+        };
+        forward_lines(stdout, self.events.clone(), true);
+        forward_lines(stderr, self.events.clone(), false);
+        self.breakpoints_dirty = false;
+        self.debuggee = Some(Debuggee {
+            child,
+            stdin,
+            script,
+        });
+        Ok(json!({}))
+    }
+
+    fn write_command(&mut self, command: &str) -> bool {
+        match self.debuggee.as_mut() {
+            Some(debuggee) => writeln!(debuggee.stdin, "{}", command)
+                .and_then(|_| debuggee.stdin.flush())
+                .is_ok(),
+            None => false,
+        }
+    }
+
+    /// Send `command` to the stopped hook and collect its reply lines.
+    fn query(&mut self, command: &str, rx: &Receiver<Event>) -> Option<Vec<String>> {
+        if !self.stopped || !self.write_command(command) {
+            return None;
+        }
+        let mut reply = Vec::new();
+        loop {
+            match rx.recv().ok()? {
+                Event::Request(request) => self.queued.push_back(request),
+                Event::ClientClosed => {
+                    self.queued.push_back(json!({ "command": "disconnect" }));
+                }
+                Event::RStdout(line) => match split_marker(&line) {
+                    (text, Some(payload)) => {
+                        if let Some(text) = text {
+                            self.output("stdout", text);
+                        }
+                        if payload == "end" {
+                            return Some(reply);
+                        }
+                        reply.push(payload.to_string());
+                    }
+                    (text, None) => self.output("stdout", text.unwrap_or_default()),
+                },
+                Event::RStderr(line) => self.output("stderr", &line),
+                Event::RClosed => {
+                    self.finish();
+                    return None;
+                }
+            }
+        }
+    }
+
+    fn stack_trace(&mut self, rx: &Receiver<Event>) -> Result<Value, String> {
+        let reply = self
+            .query("stack", rx)
+            .ok_or_else(|| "the program is not stopped".to_string())?;
+        let (Some(build), Some(program)) = (&self.build, &self.program) else {
+            return Err("no program launched".to_string());
+        };
+        let source = json!({
+            "name": program.file_name().map(|n| n.to_string_lossy().to_string()),
+            "path": program.to_string_lossy(),
+        });
+        let mut frames = Vec::new();
+        let mut known = Vec::new();
+        for entry in &reply {
+            let mut fields = entry.splitn(4, ' ');
+            let (Some("frame"), Some(id), Some(line), Some(r_name)) =
+                (fields.next(), fields.next(), fields.next(), fields.next())
+            else {
+                continue;
+            };
+            let (Ok(id), Ok(line)) = (id.parse::<i64>(), line.parse::<u32>()) else {
+                continue;
+            };
+            let Some(function) = build.function(r_name) else {
+                continue;
+            };
+            if line == 0 {
+                continue;
+            }
+            known.push((id, r_name.to_string()));
+            frames.push(json!({
+                "id": id,
+                "name": function.rr_name,
+                "line": line,
+                "column": 1,
+                "source": source,
+            }));
+        }
+        self.frames = known;
+        Ok(json!({ "stackFrames": frames, "totalFrames": frames.len() }))
+    }
+
+    fn variables(&mut self, args: &Value, rx: &Receiver<Event>) -> Result<Value, String> {
+        let id = args["variablesReference"].as_i64().unwrap_or_default();
+        let Some((_, r_name)) = self.frames.iter().find(|(frame, _)| *frame == id).cloned() else {
+            return Ok(json!({ "variables": [] }));
+        };
+        let reply = self
+            .query(&format!("vars {}", id), rx)
+            .ok_or_else(|| "the program is not stopped".to_string())?;
+        let function = self
+            .build
+            .as_ref()
+            .and_then(|build| build.function(&r_name));
+        let mut variables = Vec::new();
+        for entry in &reply {
+            let Some(entry) = entry.strip_prefix("var ") else {
+                continue;
+            };
+            let mut fields = entry.splitn(3, '\t');
+            let (Some(name), Some(class), Some(value)) =
+                (fields.next(), fields.next(), fields.next())
+            else {
+                continue;
+            };
+            let Some(name) = function.and_then(|f| f.rr_local(name)) else {
+                continue;
+            };
+            variables.push(json!({
+                "name": name,
+                "type": class,
+                "value": value,
+                "variablesReference": 0,
+            }));
+        }
+        Ok(json!({ "variables": variables }))
+    }
+
+    fn resume(&mut self, command: &str) -> Result<(), String> {
+        if !self.stopped || !self.write_command(command) {
+            return Err("the program is not stopped".to_string());
+        }
+        self.stopped = false;
+        Ok(())
+    }
+
+    fn on_r_stdout(&mut self, line: &str, rx: &Receiver<Event>) {
+        let (text, payload) = split_marker(line);
+        if let Some(text) = text {
+            self.output("stdout", text);
+        }
+        let Some(payload) = payload else {
+            return;
+        };
+        let mut fields = payload.split(' ');
+        if let (Some("stopped"), Some(reason), Some(_line)) =
+            (fields.next(), fields.next(), fields.next())
+        {
+            self.stopped = true;
+            self.sync_breakpoints(rx);
+            self.event(
+                "stopped",
+                json!({ "reason": reason, "threadId": THREAD_ID, "allThreadsStopped": true }),
+            );
+        }
+    }
+
+    /// Report the end of the program once R has closed its stdout.
+    fn finish(&mut self) {
+        self.stopped = false;
+        let Some(mut debuggee) = self.debuggee.take() else {
+            return;
+        };
+        let code = debuggee
+            .child
+            .wait()
+            .ok()
+            .and_then(|status| status.code())
+            .unwrap_or(1);
+        fs::remove_file(&debuggee.script).ok();
+        self.event("exited", json!({ "exitCode": code }));
+        self.event("terminated", json!({}));
+    }
+
+    fn stop_debuggee(&mut self) {
+        if let Some(mut debuggee) = self.debuggee.take() {
+            let _ = debuggee.child.kill();
+            let _ = debuggee.child.wait();
+            fs::remove_file(&debuggee.script).ok();
+        }
+        self.stopped = false;
+    }
+}
+
+/// Split a line of R output into program text and a hook message. The hook
+/// may follow program output that did not end in a newline.
+fn split_marker(line: &str) -> (Option<&str>, Option<&str>) {
+    match line.find(MARKER) {
+        Some(0) => (None, Some(&line[MARKER.len()..])),
+        Some(at) => (Some(&line[..at]), Some(&line[at + MARKER.len()..])),
+        None => (Some(line), None),
+    }
+}
+
+pub(crate) fn cmd_dap(args: &[String]) -> i32 {
+    if !args.is_empty() {
+        CliLog::new().error("Usage: RR dap");
+        return 1;
+    }
+    let (tx, rx) = mpsc::channel();
+    let client = tx.clone();
+    thread::spawn(move || {
+        let mut input = BufReader::new(io::stdin());
+        loop {
+            match read_message(&mut input) {
+                Ok(Some(request)) => {
+                    if client.send(Event::Request(request)).is_err() {
+                        return;
+                    }
+                }
+                Ok(None) | Err(_) => {
+                    let _ = client.send(Event::ClientClosed);
+                    return;
+                }
+            }
+        }
+    });
+    let mut adapter = Adapter {
+        seq: 0,
+        events: tx,
+        program: None,
+        build: None,
+        stop_on_entry: false,
+        no_debug: false,
+        breakpoints: Vec::new(),
+        breakpoints_dirty: false,
+        debuggee: None,
+        stopped: false,
+        frames: Vec::new(),
+        queued: VecDeque::new(),
+    };
+    loop {
+        let event = match adapter.queued.pop_front() {
+            Some(request) => Event::Request(request),
+            None => match rx.recv() {
+                Ok(event) => event,
+                Err(_) => break,
+            },
+        };
+        match event {
+            Event::Request(request) => {
+                if !adapter.handle(request, &rx) {
+                    break;
+                }
+            }
+            Event::ClientClosed => {
+                adapter.stop_debuggee();
+                break;
+            }
+            Event::RStdout(line) => adapter.on_r_stdout(&line, &rx),
+            Event::RStderr(line) => adapter.output("stderr", &line),
+            Event::RClosed => adapter.finish(),
+        }
+    }
+    0
+}
@@ -0,0 +1,220 @@
+use rr::compiler::{
+    CliLog, DocExample, ModuleDoc, compile_doc_example, doc_page_name, document_module,
+    markdown_to_html, render_index_markdown, render_module_markdown,
+};
+use rr::runtime::runner::Runner;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::lint::collect_rr_files;
+use super::project::default_build_output_dir;
+
+struct DocArgs {
+    target: String,
+    out_dir: Option<PathBuf>,
+    test: bool,
+}
+
+fn parse_doc_args(args: &[String]) -> Result<DocArgs, String> {
+    let mut parsed = DocArgs {
+        target: ".".to_string(),
+        out_dir: None,
+        test: false,
+    };
+    let mut target = None;
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--out-dir" => {
+                let Some(dir) = iter.next() else {
+                    return Err("Missing directory path after --out-dir".to_string());
+                };
+                parsed.out_dir = Some(PathBuf::from(dir));
+            }
+            "--test" => parsed.test = true,
+            other if other.starts_with('-') => return Err(format!("Unknown option: {}", other)),
+            other if target.is_none() => target = Some(other.to_string()),
+            _ => return Err("Usage: RR doc [dir|file.rr] [--out-dir <dir>] [--test]".to_string()),
+        }
+    }
+    if let Some(target) = target {
+        parsed.target = target;
+    }
+    Ok(parsed)
+}
+
+/// Source files documented for `RR doc <target>` and the root their module
+/// names are taken relative to: a project's `src/`, a directory, or the
+/// parent of a single `.rr` file.
+fn doc_sources(raw: &str) -> Result<(PathBuf, Vec<PathBuf>), String> {
+    let path = PathBuf::from(raw);
+    let path = fs::canonicalize(&path).map_err(|_| format!("doc target not found: '{}'", raw))?;
+    if path.is_file() {
+        if path.extension().and_then(|ext| ext.to_str()) != Some("rr") {
+            return Err("doc target must be a .rr file or directory".to_string());
+        }
+        let root = path.parent().map(Path::to_path_buf).unwrap_or_default();
+        return Ok((root, vec![path]));
+    }
+    let src = path.join("src");
+    let root = if src.is_dir() { src } else { path };
+    let mut files = Vec::new();
+    collect_rr_files(&root, &mut files)?;
+    if files.is_empty() {
+        return Err(format!("no .rr files found under '{}'", raw));
+    }
+    Ok((root, files))
+}
+
+fn module_name(root: &Path, file: &Path) -> String {
+    let relative = file.strip_prefix(root).unwrap_or(file).with_extension("");
+    relative
+        .components()
+        .map(|part| part.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn write_pages(out_dir: &Path, title: &str, modules: &[ModuleDoc]) -> Result<(), String> {
+    fs::create_dir_all(out_dir)
+        .map_err(|e| format!("failed to create '{}': {}", out_dir.display(), e))?;
+    let mut pages = vec![(
+        "index".to_string(),
+        title.to_string(),
+        render_index_markdown(title, modules),
+    )];
+    for module in modules {
+        pages.push((
+            doc_page_name(&module.name),
+            module.name.clone(),
+            render_module_markdown(module, modules),
+        ));
+    }
+    for (name, page_title, markdown) in pages {
+        let html = markdown_to_html(&page_title, &markdown);
+        for (ext, text) in [("md", markdown), ("html", html)] {
+            let path = out_dir.join(format!("{}.{}", name, ext));
+            fs::write(&path, text)
+                .map_err(|e| format!("failed to write '{}': {}", path.display(), e))?;
+        }
+    }
+    Ok(())
+}
+
+/// Compile, and unless it is `no_run`, run one example. Returns whether it
+/// passed.
+fn run_example(
+    ui: &CliLog,
+    out_dir: &Path,
+    module_file: &Path,
+    index: usize,
+    example: &DocExample,
+) -> bool {
+    let label = if example.item.is_empty() {
+        example.module.clone()
+    } else {
+        format!("{}::{}", example.module, example.item)
+    };
+    let entry =
+        out_dir
+            .join("doctest")
+            .join(format!("{}_{}.rr", doc_page_name(&example.module), index));
+    if let Some(dir) = entry.parent()
+        && let Err(e) = fs::create_dir_all(dir)
+    {
+        ui.error(&format!("failed to create '{}': {}", dir.display(), e));
+        return false;
+    }
+    let entry_str = entry.to_string_lossy().to_string();
+    let source = example.program(&module_file.to_string_lossy());
+    let status = match compile_doc_example(&entry_str, &source) {
+        Ok(_) if !example.run => 0,
+        Ok((r_code, source_map)) => {
+            Runner::run(&entry_str, &source, &r_code, &source_map, None, &[], false)
+        }
+        Err(e) => {
+            e.display(Some(&source), Some(&entry_str));
+            1
+        }
+    };
+    let outcome = if status == 0 { "ok" } else { "FAILED" };
+    println!("doctest {} (line {}) ... {}", label, example.line, outcome);
+    status == 0
+}
+
+pub(crate) fn cmd_doc(args: &[String]) -> i32 {
+    let ui = CliLog::new();
+    let args = match parse_doc_args(args) {
+        Ok(args) => args,
+        Err(message) => {
+            ui.error(&message);
+            return 1;
+        }
+    };
+    let (root, files) = match doc_sources(&args.target) {
+        Ok(sources) => sources,
+        Err(message) => {
+            ui.error(&message);
+            return 1;
+        }
+    };
+    let mut modules = Vec::with_capacity(files.len());
+    for file in &files {
+        let source = match fs::read_to_string(file) {
+            Ok(source) => source,
+            Err(e) => {
+                ui.error(&format!("failed to read '{}': {}", file.display(), e));
+                return 1;
+            }
+        };
+        match document_module(&module_name(&root, file), &source) {
+            Ok(module) => modules.push(module),
+            Err(e) => {
+                e.display(Some(&source), Some(&file.to_string_lossy()));
+                return 1;
+            }
+        }
+    }
+    // Doc examples import their module by path, which only resolves from an
+    // absolute entry file.
+    let out_dir = match args.out_dir {
+        Some(dir) if dir.is_relative() => std::env::current_dir()
+            .map(|cwd| cwd.join(&dir))
+            .unwrap_or(dir),
+        Some(dir) => dir,
+        None => default_build_output_dir(&root).with_file_name("doc"),
+    };
+    let title = root
+        .parent()
+        .filter(|_| root.ends_with("src"))
+        .unwrap_or(&root)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| "RR".to_string());
+    if let Err(message) = write_pages(&out_dir, &title, &modules) {
+        ui.error(&message);
+        return 1;
+    }
+    ui.success(&format!(
+        "documented {} module(s) -> {}",
+        modules.len(),
+        out_dir.display()
+    ));
+    if !args.test {
+        return 0;
+    }
+
+    let mut passed = 0usize;
+    let mut failed = 0usize;
+    for (module, file) in modules.iter().zip(&files) {
+        for (index, example) in module.examples().iter().enumerate() {
+            if run_example(&ui, &out_dir, file, index, example) {
+                passed += 1;
+            } else {
+                failed += 1;
+            }
+        }
+    }
+    println!("doctest result: {} passed; {} failed", passed, failed);
+    if failed == 0 { 0 } else { 1 }
+}
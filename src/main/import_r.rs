@@ -0,0 +1,87 @@
+use rr::compiler::{CliLog, check_import_r, translate_r};
+use std::fs;
+use std::path::PathBuf;
+
+struct ImportRArgs {
+    input: PathBuf,
+    output: Option<PathBuf>,
+}
+
+fn parse_import_r_args(args: &[String]) -> Result<ImportRArgs, String> {
+    const USAGE: &str = "Usage: RR import-r <file.R> [-o file.rr]";
+    let mut input = None;
+    let mut output = None;
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "-o" => {
+                let Some(path) = iter.next() else {
+                    return Err("Missing file path after -o".to_string());
+                };
+                output = Some(PathBuf::from(path));
+            }
+            other if other.starts_with('-') => return Err(format!("Unknown option: {}", other)),
+            other if input.is_none() => input = Some(PathBuf::from(other)),
+            _ => return Err(USAGE.to_string()),
+        }
+    }
+    let Some(input) = input else {
+        return Err(USAGE.to_string());
+    };
+    Ok(ImportRArgs { input, output })
+}
+
+pub(crate) fn cmd_import_r(args: &[String]) -> i32 {
+    let ui = CliLog::new();
+    let args = match parse_import_r_args(args) {
+        Ok(args) => args,
+        Err(message) => {
+            ui.error(&message);
+            return 1;
+        }
+    };
+    let source = match fs::read_to_string(&args.input) {
+        Ok(source) => source,
+        Err(e) => {
+            ui.error(&format!("failed to read '{}': {}", args.input.display(), e));
+            return 1;
+        }
+    };
+    let output = translate_r(&source);
+    let out_path = args
+        .output
+        .unwrap_or_else(|| args.input.with_extension("rr"));
+    if let Err(e) = fs::write(&out_path, &output.source) {
+        ui.error(&format!("failed to write '{}': {}", out_path.display(), e));
+        return 1;
+    }
+    for note in &output.notes {
+        println!(
+            "{}:{}: wrapped in unsafe r: {}",
+            args.input.display(),
+            note.line,
+            note.reason
+        );
+    }
+    ui.success(&format!(
+        "translated '{}' -> '{}' ({} statement(s) left as unsafe r)",
+        args.input.display(),
+        out_path.display(),
+        output.notes.len()
+    ));
+
+    // Relative imports in the output resolve against an absolute entry path.
+    let entry = fs::canonicalize(&out_path).unwrap_or(out_path);
+    let entry = entry.to_string_lossy();
+    match check_import_r(&entry, &output.source) {
+        Ok(()) => {
+            ui.success("translated source compiles with the strict pipeline");
+            0
+        }
+        Err(e) => {
+            e.display(Some(&output.source), Some(&entry));
+            ui.error("translated source does not compile; fix the reported lines by hand");
+            1
+        }
+    }
+}
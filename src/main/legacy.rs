@@ -1,10 +1,10 @@
 use super::compile::{
     CliCompileRequest, CommandMode, compile_cli_source, compile_output_options, parse_command_opts,
-    write_compile_profile_artifact,
+    write_compile_profile_artifact, write_opt_remarks_report, write_stage_dumps,
 };
 use super::io_errors::{report_file_write_failure, report_path_read_failure};
 use super::usage::print_usage;
-use rr::compiler::{CliLog, CompileProfile};
+use rr::compiler::{CliLog, CompileProfile, StageDumps};
 use rr::runtime::runner::Runner;
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -38,6 +38,8 @@ pub(crate) fn cmd_legacy(args: &[String]) -> i32 {
 
     let output_opts = compile_output_options(&opts, !opts.no_runtime);
     let mut compile_profile = opts.profile_compile.then(CompileProfile::default);
+    let mut remarks = Vec::new();
+    let mut stage_dumps = StageDumps::new(opts.emit.clone(), opts.emit_functions.clone());
     let result = compile_cli_source(CliCompileRequest {
         entry_path: &input_path_str,
         input: &input,
@@ -51,7 +53,34 @@ pub(crate) fn cmd_legacy(args: &[String]) -> i32 {
         profile: compile_profile.as_mut(),
         cold_compile: opts.cold_compile,
         profile_use: opts.profile_use.as_deref(),
+        remarks: opts.remarks.is_some().then_some(&mut remarks),
+        stage_dumps: (!stage_dumps.is_empty()).then_some(&mut stage_dumps),
     });
+    if let Some(path) = opts.remarks.as_deref()
+        && let Err(code) = write_opt_remarks_report(&ui, &remarks, path)
+    {
+        return code;
+    }
+    if !stage_dumps.is_empty() {
+        let emit_dir = match (&opts.emit_dir, &opts.output_path) {
+            (Some(dir), _) => PathBuf::from(dir),
+            (None, Some(out_path)) => Path::new(out_path)
+                .parent()
+                .map(Path::to_path_buf)
+                .unwrap_or_default(),
+            (None, None) => input_path
+                .parent()
+                .map(Path::to_path_buf)
+                .unwrap_or_default(),
+        };
+        let stem = input_path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "main".to_string());
+        if let Err(code) = write_stage_dumps(&ui, &stage_dumps, &emit_dir, &stem) {
+            return code;
+        }
+    }
     match result {
         Ok(out) => {
             if let Some(profile) = compile_profile.as_ref()
@@ -79,6 +108,7 @@ pub(crate) fn cmd_legacy(args: &[String]) -> i32 {
                     &r_code,
                     &source_map,
                     None,
+                    &[],
                     opts.keep_r,
                 )
             } else {
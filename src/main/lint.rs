@@ -0,0 +1,134 @@
+use rr::compiler::{CliLog, LintConfig, LintLevel, lint_source};
+use rr::pkg::{Manifest, find_manifest_root};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub(crate) fn collect_rr_files(dir: &Path, out: &mut Vec<PathBuf>) -> Result<(), String> {
+    let entries = fs::read_dir(dir)
+        .map_err(|e| format!("failed to read directory '{}': {}", dir.display(), e))?;
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .collect();
+    paths.sort();
+    for path in paths {
+        let name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or_default();
+        if path.is_dir() {
+            if !name.starts_with('.') && !matches!(name, "Build" | "target" | "vendor") {
+                collect_rr_files(&path, out)?;
+            }
+        } else if path.extension().and_then(|ext| ext.to_str()) == Some("rr") {
+            out.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Files linted for `RR lint <target>`: a single `.rr` file, or every `.rr`
+/// file under a directory (its `src/` when it has one).
+fn lint_targets(raw: &str) -> Result<Vec<PathBuf>, String> {
+    let path = PathBuf::from(raw);
+    if path.is_file() {
+        if path.extension().and_then(|ext| ext.to_str()) != Some("rr") {
+            return Err("lint target must be a .rr file or directory".to_string());
+        }
+        return Ok(vec![fs::canonicalize(&path).unwrap_or(path)]);
+    }
+    if !path.is_dir() {
+        return Err(format!("lint target not found: '{}'", raw));
+    }
+    let root = fs::canonicalize(&path).unwrap_or(path);
+    let src = root.join("src");
+    let mut files = Vec::new();
+    collect_rr_files(if src.is_dir() { &src } else { &root }, &mut files)?;
+    if files.is_empty() {
+        return Err(format!("no .rr files found under '{}'", raw));
+    }
+    Ok(files)
+}
+
+fn lint_config_for(path: &Path) -> Result<LintConfig, String> {
+    let Some(root) = find_manifest_root(path) else {
+        return Ok(LintConfig::default());
+    };
+    let manifest = Manifest::load_from_dir(&root)?;
+    LintConfig::from_manifest_levels(&manifest.lints)
+        .map_err(|e| format!("{}: {}", root.join("rr.mod").display(), e))
+}
+
+pub(crate) fn cmd_lint(args: &[String]) -> i32 {
+    let ui = CliLog::new();
+    let target = match args {
+        [] => ".",
+        [target] if !target.starts_with('-') => target.as_str(),
+        _ => {
+            ui.error("Usage: RR lint [file.rr|dir]");
+            return 1;
+        }
+    };
+    let files = match lint_targets(target) {
+        Ok(files) => files,
+        Err(message) => {
+            ui.error(&message);
+            return 1;
+        }
+    };
+    let config = match files.first().map(|file| lint_config_for(file)) {
+        Some(Ok(config)) => config,
+        Some(Err(message)) => {
+            ui.error(&message);
+            return 1;
+        }
+        None => LintConfig::default(),
+    };
+
+    let mut warnings = 0usize;
+    let mut errors = 0usize;
+    for file in &files {
+        let file_label = file.to_string_lossy().to_string();
+        let source = match fs::read_to_string(file) {
+            Ok(source) => source,
+            Err(e) => {
+                ui.error(&format!("Failed to read {}: {}", file_label, e));
+                errors += 1;
+                continue;
+            }
+        };
+        match lint_source(&file_label, &source, &config) {
+            Ok(report) => {
+                for finding in report.findings {
+                    match finding.level {
+                        LintLevel::Deny => errors += 1,
+                        LintLevel::Warn => warnings += 1,
+                        LintLevel::Allow => continue,
+                    }
+                    finding.diagnostic.display(Some(&source), Some(&file_label));
+                }
+                if let Some(e) = report.compile_error {
+                    e.display(Some(&source), Some(&file_label));
+                    errors += 1;
+                }
+            }
+            Err(e) => {
+                e.display(Some(&source), Some(&file_label));
+                errors += 1;
+            }
+        }
+    }
+
+    let summary = format!(
+        "{} file(s): {} warning(s), {} error(s)",
+        files.len(),
+        warnings,
+        errors
+    );
+    if errors > 0 {
+        ui.error(&format!("Lint failed for {}", summary));
+        1
+    } else {
+        ui.success(&format!("Linted {}", summary));
+        0
+    }
+}
@@ -0,0 +1,85 @@
+use rr::compiler::CliLog;
+use rr::compiler::internal::mir::opt::TachyonEngine;
+use rr::compiler::internal::mir::{text, verify};
+use std::fs;
+
+fn parse_mir_opt_args(args: &[String]) -> Result<(String, Vec<String>), String> {
+    let mut input = None;
+    let mut passes = Vec::new();
+    let mut idx = 0usize;
+    while idx < args.len() {
+        let arg = &args[idx];
+        let list = if arg == "--passes" {
+            idx += 1;
+            let Some(list) = args.get(idx) else {
+                return Err("Missing pass list after --passes".to_string());
+            };
+            Some(list.as_str())
+        } else {
+            arg.strip_prefix("--passes=")
+        };
+        if let Some(list) = list {
+            passes.extend(
+                list.split(',')
+                    .map(str::trim)
+                    .filter(|name| !name.is_empty())
+                    .map(str::to_string),
+            );
+        } else if arg.starts_with('-') {
+            return Err(format!("Unknown RR mir-opt option: {arg}"));
+        } else if input.replace(arg.clone()).is_some() {
+            return Err("RR mir-opt takes exactly one input file".to_string());
+        }
+        idx += 1;
+    }
+    let Some(input) = input else {
+        return Err("Usage: RR mir-opt <file.mir> [--passes <p1,p2,...>]".to_string());
+    };
+    Ok((input, passes))
+}
+
+pub(crate) fn cmd_mir_opt(args: &[String]) -> i32 {
+    let ui = CliLog::new();
+    let (input, passes) = match parse_mir_opt_args(args) {
+        Ok(parsed) => parsed,
+        Err(message) => {
+            ui.error(&message);
+            return 1;
+        }
+    };
+    let source = match fs::read_to_string(&input) {
+        Ok(source) => source,
+        Err(e) => {
+            ui.error(&format!("Failed to read {input}: {e}"));
+            return 1;
+        }
+    };
+    let mut fns = match text::parse_fns(&source) {
+        Ok(fns) => fns,
+        Err(e) => {
+            ui.error(&format!("{input}: {e}"));
+            return 1;
+        }
+    };
+    let passes: Vec<&str> = passes.iter().map(String::as_str).collect();
+    let engine = TachyonEngine::new();
+    for fn_ir in &mut fns {
+        if let Err(e) = verify::verify_ir(fn_ir) {
+            ui.error(&format!("{input}: fn {} is not valid MIR: {e}", fn_ir.name));
+            return 1;
+        }
+        if let Err(message) = engine.run_function_passes_by_name(fn_ir, &passes) {
+            ui.error(&message);
+            return 1;
+        }
+        if let Err(e) = verify::verify_ir(fn_ir) {
+            ui.error(&format!(
+                "fn {} failed verification after passes: {e}",
+                fn_ir.name
+            ));
+            return 1;
+        }
+    }
+    print!("{}", text::print_fns(&fns));
+    0
+}
@@ -1,23 +1,35 @@
 use std::env;
 
 pub(crate) mod compile;
+pub(crate) mod dap;
+pub(crate) mod doc;
+pub(crate) mod import_r;
 pub(crate) mod io_errors;
 pub(crate) mod legacy;
+pub(crate) mod lint;
+pub(crate) mod mir_opt;
 pub(crate) mod module;
 pub(crate) mod package;
 pub(crate) mod panic;
 pub(crate) mod project;
 pub(crate) mod registry;
+pub(crate) mod repl;
 pub(crate) mod usage;
 pub(crate) mod watch_hash;
 
 use self::compile::{cmd_build, cmd_run, cmd_watch};
+use self::dap::cmd_dap;
+use self::doc::cmd_doc;
+use self::import_r::cmd_import_r;
 use self::legacy::cmd_legacy;
+use self::lint::cmd_lint;
+use self::mir_opt::cmd_mir_opt;
 use self::module::cmd_mod;
-use self::package::{cmd_install, cmd_outdated, cmd_publish, cmd_remove, cmd_update};
+use self::package::{cmd_audit, cmd_install, cmd_outdated, cmd_publish, cmd_remove, cmd_update};
 use self::panic::{install_broken_pipe_panic_hook, panic_payload_is_broken_pipe};
 use self::project::{cmd_init, cmd_new};
 use self::registry::{cmd_registry, cmd_search};
+use self::repl::cmd_repl;
 use self::usage::{print_usage, print_version};
 
 pub(crate) fn run() {
@@ -38,8 +50,27 @@ pub(crate) fn run() {
     }
 }
 
+/// Strip the global `--offline` flag, which any subcommand accepts before a
+/// `--` separator, and switch package resolution to offline mode.
+fn take_offline_flag(args: &mut Vec<String>) {
+    let end = args
+        .iter()
+        .position(|arg| arg == "--")
+        .unwrap_or(args.len());
+    let before = args.len();
+    let mut idx = 0usize;
+    args.retain(|arg| {
+        idx += 1;
+        idx > end || arg != "--offline"
+    });
+    if args.len() != before {
+        rr::pkg::set_offline_mode(true);
+    }
+}
+
 pub(crate) fn run_cli() -> i32 {
-    let args: Vec<String> = env::args().collect();
+    let mut args: Vec<String> = env::args().collect();
+    take_offline_flag(&mut args);
     if args.len() < 2 {
         print_usage();
         return 0;
@@ -71,9 +102,16 @@ pub(crate) fn run_cli() -> i32 {
         "outdated" => cmd_outdated(&args[2..]),
         "update" => cmd_update(&args[2..]),
         "publish" => cmd_publish(&args[2..]),
+        "audit" => cmd_audit(&args[2..]),
         "search" => cmd_search(&args[2..]),
         "registry" => cmd_registry(&args[2..]),
         "mod" => cmd_mod(&args[2..]),
+        "mir-opt" => cmd_mir_opt(&args[2..]),
+        "lint" => cmd_lint(&args[2..]),
+        "repl" => cmd_repl(&args[2..]),
+        "dap" => cmd_dap(&args[2..]),
+        "doc" => cmd_doc(&args[2..]),
+        "import-r" => cmd_import_r(&args[2..]),
         "build" => cmd_build(&args[2..]),
         "run" => cmd_run(&args[2..]),
         "watch" => cmd_watch(&args[2..]),
@@ -1,6 +1,7 @@
 use rr::compiler::CliLog;
 use std::env;
 use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 fn resolve_mod_project_root(ui: &CliLog, subcommand: &str) -> Result<PathBuf, i32> {
     let cwd = match env::current_dir() {
@@ -133,9 +134,177 @@ pub(crate) fn cmd_mod(args: &[String]) -> i32 {
                 }
             }
         }
+        [subcommand, rest @ ..] if subcommand == "sbom" => {
+            let format = match rest {
+                [] => "cyclonedx",
+                [flag, format] if flag == "--format" => format.as_str(),
+                _ => {
+                    ui.error("Usage: RR mod sbom [--format cyclonedx|spdx]");
+                    return 1;
+                }
+            };
+            let project_root = match resolve_mod_project_root(&ui, "sbom") {
+                Ok(root) => root,
+                Err(code) => return code,
+            };
+            match rr::pkg::project_sbom(&project_root, format) {
+                Ok(document) => {
+                    print!("{document}");
+                    0
+                }
+                Err(message) => {
+                    ui.error(&message);
+                    1
+                }
+            }
+        }
+        [subcommand, rest @ ..] if subcommand == "cache" => cmd_mod_cache(&ui, rest),
         _ => {
             ui.error("RR mod expects a supported subcommand");
-            ui.warn("use RR mod graph, RR mod why, RR mod verify, RR mod tidy, or RR mod vendor");
+            ui.warn(
+                "use RR mod graph, RR mod why, RR mod verify, RR mod tidy, RR mod vendor, RR mod sbom, or RR mod cache",
+            );
+            1
+        }
+    }
+}
+
+fn format_cache_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0usize;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes}B")
+    } else {
+        format!("{value:.1}{}", UNITS[unit])
+    }
+}
+
+fn format_cache_age(last_used_secs: Option<u64>) -> String {
+    let Some(last_used) = last_used_secs else {
+        return "unknown".to_string();
+    };
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default();
+    let age = now.saturating_sub(last_used);
+    match age {
+        0..60 => format!("{age}s"),
+        60..3600 => format!("{}m", age / 60),
+        3600..86400 => format!("{}h", age / 3600),
+        _ => format!("{}d", age / 86400),
+    }
+}
+
+fn cache_entries_noun(count: usize) -> &'static str {
+    if count == 1 {
+        "cache entry"
+    } else {
+        "cache entries"
+    }
+}
+
+fn cache_entry_label(entry: &rr::pkg::CacheEntry) -> String {
+    match &entry.version {
+        Some(version) => format!("{} {}@{}", entry.kind, entry.name, version),
+        None => format!("{} {}", entry.kind, entry.name),
+    }
+}
+
+fn cmd_mod_cache(ui: &CliLog, args: &[String]) -> i32 {
+    match args {
+        [action] if action == "list" => match rr::pkg::list_package_cache() {
+            Ok(entries) => {
+                for entry in &entries {
+                    println!(
+                        "{} size={} last_used={}",
+                        cache_entry_label(entry),
+                        format_cache_size(entry.size_bytes),
+                        format_cache_age(entry.last_used_secs)
+                    );
+                }
+                ui.success(&format!(
+                    "{} {} under {}",
+                    entries.len(),
+                    cache_entries_noun(entries.len()),
+                    rr::pkg::package_home().display()
+                ));
+                0
+            }
+            Err(message) => {
+                ui.error(&message);
+                1
+            }
+        },
+        [action] if action == "verify" => match rr::pkg::verify_package_cache() {
+            Ok(report) => {
+                if report.issues.is_empty() {
+                    ui.success(&format!(
+                        "Verified {} {}",
+                        report.checked,
+                        cache_entries_noun(report.checked)
+                    ));
+                    return 0;
+                }
+                for issue in report.issues {
+                    ui.error(&format!(
+                        "{}: {} root={}",
+                        cache_entry_label(&issue.entry),
+                        issue.message,
+                        issue.entry.root.display()
+                    ));
+                }
+                ui.warn("run RR mod cache clean to drop corrupted entries");
+                1
+            }
+            Err(message) => {
+                ui.error(&message);
+                1
+            }
+        },
+        [action, rest @ ..] if action == "clean" => {
+            let older_than = match rest {
+                [] => None,
+                [flag, age] if flag == "--older-than" => match rr::pkg::parse_cache_age(age) {
+                    Ok(age) => Some(age),
+                    Err(message) => {
+                        ui.error(&message);
+                        return 1;
+                    }
+                },
+                _ => {
+                    ui.error("RR mod cache clean accepts only --older-than <age>");
+                    return 1;
+                }
+            };
+            match rr::pkg::clean_package_cache(older_than) {
+                Ok(report) => {
+                    for entry in &report.removed {
+                        println!("removed {}", cache_entry_label(entry));
+                    }
+                    ui.success(&format!(
+                        "Removed {} {} ({}), kept {}",
+                        report.removed.len(),
+                        cache_entries_noun(report.removed.len()),
+                        format_cache_size(report.freed_bytes),
+                        report.kept
+                    ));
+                    0
+                }
+                Err(message) => {
+                    ui.error(&message);
+                    1
+                }
+            }
+        }
+        _ => {
+            ui.error("RR mod cache expects list, verify, or clean");
+            ui.warn("use RR mod cache list|verify|clean [--older-than <age>]");
             1
         }
     }
@@ -1,6 +1,6 @@
 use rr::compiler::CliLog;
 use std::env;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 fn current_project_root(ui: &CliLog, command: &str) -> Result<PathBuf, i32> {
     let cwd = match env::current_dir() {
@@ -22,6 +22,85 @@ fn current_project_root(ui: &CliLog, command: &str) -> Result<PathBuf, i32> {
     Ok(project_root)
 }
 
+/// Warn about newly locked versions that the registry has advisories for.
+fn warn_affected_dependencies(ui: &CliLog, project_root: &Path) {
+    match rr::pkg::project_advisory_warnings(project_root) {
+        Ok(findings) => {
+            for finding in findings {
+                ui.warn(&format!(
+                    "{}@{} is affected by advisory {}: {}",
+                    finding.advisory.module_path,
+                    finding.version,
+                    finding.advisory.id,
+                    finding.advisory.summary
+                ));
+            }
+        }
+        Err(message) => ui.warn(&format!("failed to check security advisories: {}", message)),
+    }
+}
+
+pub(crate) fn cmd_audit(args: &[String]) -> i32 {
+    let ui = CliLog::new();
+    let mut registry = None::<PathBuf>;
+    let mut i = 0usize;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--registry" => {
+                if i + 1 >= args.len() {
+                    ui.error("Missing value after --registry");
+                    ui.warn("use RR audit [--registry <dir>]");
+                    return 1;
+                }
+                i += 1;
+                registry = Some(PathBuf::from(&args[i]));
+            }
+            arg => {
+                ui.error(&format!("Unknown argument for RR audit: {}", arg));
+                ui.warn("use RR audit [--registry <dir>]");
+                return 1;
+            }
+        }
+        i += 1;
+    }
+    let project_root = match current_project_root(&ui, "audit") {
+        Ok(root) => root,
+        Err(code) => return code,
+    };
+    match rr::pkg::audit_project_dependencies(&project_root, registry.as_deref()) {
+        Ok(report) if report.findings.is_empty() => {
+            ui.success(&format!(
+                "No advisories affect {} locked module(s)",
+                report.checked
+            ));
+            0
+        }
+        Ok(report) => {
+            for finding in &report.findings {
+                println!(
+                    "{} {}@{} range={}: {}",
+                    finding.advisory.id,
+                    finding.advisory.module_path,
+                    finding.version,
+                    finding.advisory.range,
+                    finding.advisory.summary
+                );
+                println!("  {}", finding.chain.join(" -> "));
+            }
+            ui.error(&format!(
+                "{} advisory finding(s) in {} locked module(s)",
+                report.findings.len(),
+                report.checked
+            ));
+            1
+        }
+        Err(message) => {
+            ui.error(&message);
+            1
+        }
+    }
+}
+
 pub(crate) fn cmd_install(args: &[String]) -> i32 {
     let ui = CliLog::new();
     if args.len() != 1 {
@@ -43,6 +122,7 @@ pub(crate) fn cmd_install(args: &[String]) -> i32 {
             ));
             ui.success(&format!("Project: {}", report.project_root.display()));
             ui.success(&format!("Lock entries: {}", report.all_modules.len()));
+            warn_affected_dependencies(&ui, &report.project_root);
             0
         }
         Err(message) => {
@@ -133,6 +213,7 @@ pub(crate) fn cmd_update(args: &[String]) -> i32 {
                 "Updated dependency graph: {} locked module(s)",
                 modules.len()
             ));
+            warn_affected_dependencies(&ui, &project_root);
             0
         }
         Err(message) => {
@@ -153,11 +234,12 @@ pub(crate) fn cmd_publish(args: &[String]) -> i32 {
         match arg.as_str() {
             "--dry-run" => options.dry_run = true,
             "--allow-dirty" => options.allow_dirty = true,
+            "--allow-breaking" => options.allow_breaking = true,
             "--push-tag" => options.push_tag = true,
             "--registry" => {
                 if i + 1 >= args.len() {
                     ui.error("Missing value after --registry");
-                    ui.warn("use RR publish <version> [--dry-run] [--allow-dirty] [--push-tag] [--remote <name>] [--registry <dir>]");
+                    ui.warn("use RR publish <version> [--dry-run] [--allow-dirty] [--allow-breaking] [--push-tag] [--remote <name>] [--registry <dir>]");
                     return 1;
                 }
                 i += 1;
@@ -166,7 +248,7 @@ pub(crate) fn cmd_publish(args: &[String]) -> i32 {
             "--remote" => {
                 if i + 1 >= args.len() {
                     ui.error("Missing value after --remote");
-                    ui.warn("use RR publish <version> [--dry-run] [--allow-dirty] [--push-tag] [--remote <name>] [--registry <dir>]");
+                    ui.warn("use RR publish <version> [--dry-run] [--allow-dirty] [--allow-breaking] [--push-tag] [--remote <name>] [--registry <dir>]");
                     return 1;
                 }
                 i += 1;
@@ -174,13 +256,13 @@ pub(crate) fn cmd_publish(args: &[String]) -> i32 {
             }
             _ if arg.starts_with('-') => {
                 ui.error(&format!("Unknown option for RR publish: {}", arg));
-                ui.warn("use RR publish <version> [--dry-run] [--allow-dirty] [--push-tag] [--remote <name>] [--registry <dir>]");
+                ui.warn("use RR publish <version> [--dry-run] [--allow-dirty] [--allow-breaking] [--push-tag] [--remote <name>] [--registry <dir>]");
                 return 1;
             }
             _ if version.is_none() => version = Some(arg.clone()),
             _ => {
                 ui.error("RR publish expects exactly one version argument");
-                ui.warn("use RR publish <version> [--dry-run] [--allow-dirty] [--push-tag] [--remote <name>] [--registry <dir>]");
+                ui.warn("use RR publish <version> [--dry-run] [--allow-dirty] [--allow-breaking] [--push-tag] [--remote <name>] [--registry <dir>]");
                 return 1;
             }
         }
@@ -190,7 +272,7 @@ pub(crate) fn cmd_publish(args: &[String]) -> i32 {
     let Some(version) = version else {
         ui.error("Missing version for RR publish");
         ui.warn(
-            "use RR publish <version> [--dry-run] [--allow-dirty] [--push-tag] [--remote <name>] [--registry <dir>]",
+            "use RR publish <version> [--dry-run] [--allow-dirty] [--allow-breaking] [--push-tag] [--remote <name>] [--registry <dir>]",
         );
         return 1;
     };
@@ -6,6 +6,12 @@ pub(crate) fn cmd_registry_audit(args: &[String]) -> i32 {
     if matches!(args.first().map(String::as_str), Some("export")) {
         return cmd_registry_audit_export(&args[1..]);
     }
+    if matches!(args.first().map(String::as_str), Some("verify")) {
+        return cmd_registry_audit_verify(&args[1..]);
+    }
+    if matches!(args.first().map(String::as_str), Some("reseal")) {
+        return cmd_registry_audit_reseal(&args[1..]);
+    }
     let mut registry = None::<PathBuf>;
     let mut limit = None::<usize>;
     let mut action = None::<String>;
@@ -217,3 +223,108 @@ pub(crate) fn cmd_registry_audit_export(args: &[String]) -> i32 {
         }
     }
 }
+
+pub(crate) fn cmd_registry_audit_verify(args: &[String]) -> i32 {
+    let ui = CliLog::new();
+    let mut registry = None::<PathBuf>;
+    let mut i = 0usize;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--registry" => {
+                if i + 1 >= args.len() {
+                    ui.error("Missing value after --registry");
+                    ui.warn("use RR registry audit verify [--registry <dir>]");
+                    return 1;
+                }
+                i += 1;
+                registry = Some(PathBuf::from(&args[i]));
+            }
+            arg => {
+                ui.error(&format!("Unexpected argument: {}", arg));
+                ui.warn("use RR registry audit verify [--registry <dir>]");
+                return 1;
+            }
+        }
+        i += 1;
+    }
+
+    match rr::pkg::verify_registry_audit_log(registry.as_deref()) {
+        Ok(report) => {
+            if let Some(broken) = &report.broken {
+                ui.error(&format!(
+                    "Registry audit log is broken at line {}: {}",
+                    broken.line, broken.reason
+                ));
+                return 1;
+            }
+            if report.legacy_entries > 0 {
+                println!(
+                    "{} legacy entrie(s) predate the hash chain and seed it",
+                    report.legacy_entries
+                );
+            }
+            if let Some(head) = &report.head {
+                println!("head {}", head);
+            }
+            match (&report.checkpoint_scheme, &report.checkpoint_signer) {
+                (Some(scheme), Some(signer)) => {
+                    println!("checkpoint signed with {} by {}", scheme, signer)
+                }
+                (Some(scheme), None) => println!("checkpoint signed with {}", scheme),
+                _ if report.entries > 0 => ui.warn("audit checkpoint is not signed"),
+                _ => {}
+            }
+            ui.success(&format!(
+                "Registry audit log verified ({} chained entrie(s))",
+                report.entries
+            ));
+            0
+        }
+        Err(message) => {
+            ui.error(&message);
+            1
+        }
+    }
+}
+
+pub(crate) fn cmd_registry_audit_reseal(args: &[String]) -> i32 {
+    let ui = CliLog::new();
+    let mut registry = None::<PathBuf>;
+    let mut i = 0usize;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--registry" => {
+                if i + 1 >= args.len() {
+                    ui.error("Missing value after --registry");
+                    ui.warn("use RR registry audit reseal [--registry <dir>]");
+                    return 1;
+                }
+                i += 1;
+                registry = Some(PathBuf::from(&args[i]));
+            }
+            arg => {
+                ui.error(&format!("Unexpected argument: {}", arg));
+                ui.warn("use RR registry audit reseal [--registry <dir>]");
+                return 1;
+            }
+        }
+        i += 1;
+    }
+
+    match rr::pkg::reseal_registry_audit_log(registry.as_deref()) {
+        Ok(report) => {
+            if let Some(head) = &report.head {
+                println!("head {}", head);
+            }
+            ui.success(&format!(
+                "Registry audit checkpoint resealed ({} chained entrie(s))",
+                report.entries
+            ));
+            0
+        }
+        Err(message) => {
+            ui.error(&message);
+            1
+        }
+    }
+}
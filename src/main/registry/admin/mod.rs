@@ -2,6 +2,7 @@ mod audit;
 mod keys;
 mod policy;
 mod risk;
+mod serve;
 
 pub(crate) use self::audit::cmd_registry_audit;
 pub(crate) use self::keys::{cmd_registry_keygen, cmd_registry_onboard};
@@ -9,3 +10,4 @@ pub(crate) use self::policy::{
     cmd_registry_policy_apply, cmd_registry_policy_bootstrap, cmd_registry_policy_show,
 };
 pub(crate) use self::risk::cmd_registry_risk;
+pub(crate) use self::serve::cmd_registry_serve;
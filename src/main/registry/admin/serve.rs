@@ -0,0 +1,58 @@
+use rr::compiler::CliLog;
+use std::io::Write;
+use std::path::PathBuf;
+
+const SERVE_USAGE: &str = "use RR registry serve <dir> [--addr <host:port>]";
+
+pub(crate) fn cmd_registry_serve(args: &[String]) -> i32 {
+    let ui = CliLog::new();
+    let mut registry = None::<PathBuf>;
+    let mut addr = "127.0.0.1:8470".to_string();
+    let mut i = 0usize;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--addr" => {
+                if i + 1 >= args.len() {
+                    ui.error("Missing value after --addr");
+                    ui.warn(SERVE_USAGE);
+                    return 1;
+                }
+                i += 1;
+                addr = args[i].clone();
+            }
+            arg if arg.starts_with('-') => {
+                ui.error(&format!("Unknown option: {}", arg));
+                ui.warn(SERVE_USAGE);
+                return 1;
+            }
+            value if registry.is_none() => registry = Some(PathBuf::from(value)),
+            _ => {
+                ui.error("RR registry serve accepts one registry directory");
+                ui.warn(SERVE_USAGE);
+                return 1;
+            }
+        }
+        i += 1;
+    }
+    let Some(registry) = registry else {
+        ui.error("Missing registry directory for RR registry serve");
+        ui.warn(SERVE_USAGE);
+        return 1;
+    };
+
+    let served = rr::pkg::serve_registry(&registry, &addr, |bound| {
+        println!(
+            "Serving registry {} at http://{}",
+            registry.display(),
+            bound
+        );
+        let _ = std::io::stdout().flush();
+    });
+    match served {
+        Ok(()) => 0,
+        Err(message) => {
+            ui.error(&message);
+            1
+        }
+    }
+}
@@ -22,8 +22,8 @@ use self::read::{
     cmd_registry_report, cmd_registry_verify,
 };
 use self::release::{
-    cmd_registry_approve, cmd_registry_deprecate, cmd_registry_promote, cmd_registry_unapprove,
-    cmd_registry_undeprecate, cmd_registry_unyank, cmd_registry_yank,
+    cmd_registry_advisory_add, cmd_registry_approve, cmd_registry_deprecate, cmd_registry_promote,
+    cmd_registry_unapprove, cmd_registry_undeprecate, cmd_registry_unyank, cmd_registry_yank,
 };
 use self::usage::REGISTRY_USAGE;
 
@@ -38,6 +38,9 @@ pub(crate) fn cmd_registry(args: &[String]) -> i32 {
     if matches!(args.first().map(String::as_str), Some("risk")) {
         return cmd_registry_risk(&args[1..]);
     }
+    if matches!(args.first().map(String::as_str), Some("serve")) {
+        return cmd_registry_serve(&args[1..]);
+    }
     if matches!(args.first().map(String::as_str), Some("audit")) {
         return cmd_registry_audit(&args[1..]);
     }
@@ -118,6 +121,11 @@ pub(crate) fn cmd_registry(args: &[String]) -> i32 {
         [subcommand, module_path, message @ ..] if subcommand == "deprecate" => {
             cmd_registry_deprecate(&ui, registry, module_path, message)
         }
+        [group, subcommand, module_path, range, id, summary @ ..]
+            if group == "advisory" && subcommand == "add" && !summary.is_empty() =>
+        {
+            cmd_registry_advisory_add(&ui, registry, module_path, range, id, summary)
+        }
         [subcommand, module_path] if subcommand == "undeprecate" => {
             cmd_registry_undeprecate(&ui, registry, module_path)
         }
@@ -31,6 +31,11 @@ pub(super) fn cmd_registry_policy_lint(ui: &CliLog, registry: Option<&Path>) ->
                 report.allowed_signer_count
             );
             println!("auto_approve_signers={}", report.auto_approve_signer_count);
+            println!(
+                "allowed_licenses={} denied_licenses={}",
+                report.allowed_license_count, report.denied_license_count
+            );
+            println!("owner_rules={}", report.owner_rule_count);
             for warning in report.warnings {
                 ui.warn(&warning);
             }
@@ -100,6 +100,27 @@ pub(super) fn cmd_registry_diff(
             for path in diff.changed_files {
                 println!("~ {}", path);
             }
+            println!(
+                "api breaking={} compatible={}",
+                diff.api_changes
+                    .iter()
+                    .filter(|change| change.kind.is_breaking())
+                    .count(),
+                diff.api_changes
+                    .iter()
+                    .filter(|change| !change.kind.is_breaking())
+                    .count()
+            );
+            for change in diff.api_changes {
+                println!(
+                    "{} {} {} {}: {}",
+                    if change.kind.is_breaking() { "!" } else { "." },
+                    change.kind.as_str(),
+                    change.file,
+                    change.item,
+                    change.detail
+                );
+            }
             0
         }
         Err(message) => {
@@ -117,7 +138,7 @@ pub(super) fn cmd_registry_queue(ui: &CliLog, registry: Option<&Path>) -> i32 {
                 return 0;
             }
             for item in items {
-                println!(
+                let mut line = format!(
                     "{} {} yanked={} signed={} signer={}",
                     item.path,
                     item.version,
@@ -125,6 +146,13 @@ pub(super) fn cmd_registry_queue(ui: &CliLog, registry: Option<&Path>) -> i32 {
                     if item.signed { "true" } else { "false" },
                     item.signer.as_deref().unwrap_or("-")
                 );
+                if let Some(required) = item.required_approvals {
+                    line.push_str(&format!(" approvals={}/{}", item.owner_approvals, required));
+                }
+                if !item.approvals.is_empty() {
+                    line.push_str(&format!(" approved_by={}", item.approvals.join(",")));
+                }
+                println!("{}", line);
             }
             0
         }
@@ -165,8 +193,13 @@ pub(super) fn cmd_registry_info(ui: &CliLog, registry: Option<&Path>, module_pat
                     .as_deref()
                     .filter(|value| !value.is_empty())
                     .unwrap_or("-");
+                let approved_by = if release.approvals.is_empty() {
+                    "-".to_string()
+                } else {
+                    release.approvals.join(",")
+                };
                 println!(
-                    "release {} files={} yanked={} approved={} signed={} scheme={} signer={} archive={} sum={}",
+                    "release {} files={} yanked={} approved={} signed={} scheme={} signer={} archive={} sum={} approved_by={}",
                     release.version,
                     release.file_count,
                     if release.yanked { "true" } else { "false" },
@@ -175,7 +208,8 @@ pub(super) fn cmd_registry_info(ui: &CliLog, registry: Option<&Path>, module_pat
                     scheme,
                     signer,
                     release.archive_rel,
-                    release.archive_sum
+                    release.archive_sum,
+                    approved_by
                 );
             }
             0
@@ -14,8 +14,18 @@ pub(super) fn cmd_registry_approve(
         Err(code) => return code,
     };
     match rr::pkg::approve_registry_release(module_path, version, registry) {
-        Ok(()) => {
-            ui.success(&format!("Approved {} {}", module_path, version));
+        Ok(approval) => {
+            match approval.required_approvals {
+                Some(required) if !approval.approved => ui.success(&format!(
+                    "Recorded approval for {} {} ({}/{} owner approvals; still pending)",
+                    module_path, version, approval.owner_approvals, required
+                )),
+                Some(required) => ui.success(&format!(
+                    "Approved {} {} ({}/{} owner approvals)",
+                    module_path, version, approval.owner_approvals, required
+                )),
+                None => ui.success(&format!("Approved {} {}", module_path, version)),
+            }
             0
         }
         Err(message) => {
@@ -156,3 +166,37 @@ pub(super) fn cmd_registry_undeprecate(
         }
     }
 }
+
+pub(super) fn cmd_registry_advisory_add(
+    ui: &CliLog,
+    registry: Option<&Path>,
+    module_path: &str,
+    range: &str,
+    id: &str,
+    summary: &[String],
+) -> i32 {
+    let registry = match require_registry_root(registry, ui, "RR registry advisory add") {
+        Ok(path) => path,
+        Err(code) => return code,
+    };
+    match rr::pkg::add_registry_advisory(module_path, range, id, &summary.join(" "), registry) {
+        Ok(advisory) => {
+            ui.success(&format!(
+                "Added advisory {} for {} {}{}",
+                advisory.id,
+                module_path,
+                advisory.range,
+                if advisory.sig.is_some() {
+                    " (signed)"
+                } else {
+                    ""
+                }
+            ));
+            0
+        }
+        Err(message) => {
+            ui.error(&message);
+            1
+        }
+    }
+}
@@ -1 +1 @@
-pub(super) const REGISTRY_USAGE: &str = "use RR registry keygen [identity] [--out-dir <dir>], RR registry onboard [identity] [--out-dir <dir>] [--require-signed] [--require-approval] [--auto-approve] [--registry <dir>], RR registry list [--registry <dir>], RR registry report [module-path] [--registry <dir>], RR registry diff <module-path> <from-version> <to-version> [--registry <dir>], RR registry risk <module-path> <version> [--against <version>] [--registry <dir>], RR registry channel show <module-path> [--registry <dir>], RR registry channel set <module-path> <channel> <version> [--registry <dir>], RR registry channel clear <module-path> <channel> [--registry <dir>], RR registry queue [--registry <dir>], RR registry audit [--limit <n>] [--action <kind>] [--module <path>] [--contains <text>] [--registry <dir>], RR registry audit export <file> [--format <tsv|jsonl>] [--limit <n>] [--action <kind>] [--module <path>] [--contains <text>] [--registry <dir>], RR registry policy bootstrap <trusted-public-key> [--signer <identity>] [--auto-approve-signer <identity>] [--require-signed] [--require-approval] [--registry <dir>], RR registry policy show [--registry <dir>], RR registry policy lint [--registry <dir>], RR registry policy rotate-key <old-public-key> <new-public-key> [--registry <dir>], RR registry policy apply <file> [--registry <dir>], RR registry info <module-path> [--registry <dir>], RR registry approve <module-path> <version> [--registry <dir>], RR registry unapprove <module-path> <version> [--registry <dir>], RR registry promote <module-path> <version> [--registry <dir>], RR registry yank <module-path> <version> [--registry <dir>], RR registry unyank <module-path> <version> [--registry <dir>], RR registry deprecate <module-path> <message> [--registry <dir>], RR registry undeprecate <module-path> [--registry <dir>], or RR registry verify [module-path] [--registry <dir>]";
+pub(super) const REGISTRY_USAGE: &str = "use RR registry keygen [identity] [--out-dir <dir>], RR registry onboard [identity] [--out-dir <dir>] [--require-signed] [--require-approval] [--auto-approve] [--registry <dir>], RR registry list [--registry <dir>], RR registry report [module-path] [--registry <dir>], RR registry diff <module-path> <from-version> <to-version> [--registry <dir>], RR registry risk <module-path> <version> [--against <version>] [--registry <dir>], RR registry channel show <module-path> [--registry <dir>], RR registry channel set <module-path> <channel> <version> [--registry <dir>], RR registry channel clear <module-path> <channel> [--registry <dir>], RR registry queue [--registry <dir>], RR registry audit [--limit <n>] [--action <kind>] [--module <path>] [--contains <text>] [--registry <dir>], RR registry audit export <file> [--format <tsv|jsonl>] [--limit <n>] [--action <kind>] [--module <path>] [--contains <text>] [--registry <dir>], RR registry audit verify [--registry <dir>], RR registry audit reseal [--registry <dir>], RR registry policy bootstrap <trusted-public-key> [--signer <identity>] [--auto-approve-signer <identity>] [--require-signed] [--require-approval] [--registry <dir>], RR registry policy show [--registry <dir>], RR registry policy lint [--registry <dir>], RR registry policy rotate-key <old-public-key> <new-public-key> [--registry <dir>], RR registry policy apply <file> [--registry <dir>], RR registry info <module-path> [--registry <dir>], RR registry approve <module-path> <version> [--registry <dir>], RR registry unapprove <module-path> <version> [--registry <dir>], RR registry promote <module-path> <version> [--registry <dir>], RR registry yank <module-path> <version> [--registry <dir>], RR registry unyank <module-path> <version> [--registry <dir>], RR registry deprecate <module-path> <message> [--registry <dir>], RR registry undeprecate <module-path> [--registry <dir>], RR registry advisory add <module-path> <version-range> <id> <summary> [--registry <dir>], RR registry serve <dir> [--addr <host:port>], or RR registry verify [module-path] [--registry <dir>]";
@@ -0,0 +1,190 @@
+use rr::compiler::{CliLog, OptLevel, REPL_VALUE_NAME, ReplSession, input_is_incomplete};
+use rr::runtime::session::RSession;
+use std::env;
+use std::fs;
+use std::io::{self, BufRead, IsTerminal, Write};
+
+const REPL_LABEL: &str = "<repl>";
+
+const REPL_HELP: &str = "\
+Enter RR statements, definitions or expressions; an expression's value is
+printed and bound to `it`. Unclosed brackets continue on the next line.
+  :type <expr>     show the inferred type of <expr> without running it
+  :mir <fn>        show the MIR of <fn> before optimization
+  :emit <fn>       show the R emitted for <fn>
+  :load <file.rr>  compile and run a file in this session
+  :help            show this help
+  :quit            leave the REPL";
+
+struct Repl {
+    ui: CliLog,
+    session: ReplSession,
+    r: Option<RSession>,
+    failed: bool,
+}
+
+impl Repl {
+    /// Compile `input` as one cell, run it in the R session and, when R
+    /// accepts it, record its definitions and bindings.
+    fn run_cell(&mut self, input: &str) {
+        let cell = match self.session.compile_cell(input) {
+            Ok(cell) => cell,
+            Err(e) => {
+                e.error.display(Some(&e.source), Some(REPL_LABEL));
+                self.failed = true;
+                return;
+            }
+        };
+        if !cell.needs_eval {
+            self.session.commit(cell);
+            return;
+        }
+        if self.r.is_none() {
+            match RSession::start(None) {
+                Ok(r) => self.r = Some(r),
+                Err(message) => {
+                    self.ui
+                        .error(&format!("R session unavailable: {}", message));
+                    eprintln!("help: install Rscript or set RRSCRIPT=/absolute/path/to/Rscript");
+                    self.failed = true;
+                    return;
+                }
+            }
+        }
+        let Some(r) = self.r.as_mut() else {
+            return;
+        };
+        let mut stdout = io::stdout();
+        match r.eval(&cell.r_code, &mut stdout) {
+            Ok(None) => {
+                if let Some(ty) = &cell.value_ty {
+                    println!("{}: {}", REPL_VALUE_NAME, ty);
+                }
+                self.session.commit(cell);
+            }
+            Ok(Some(message)) => {
+                self.ui.error(&format!("R error: {}", message));
+                self.failed = true;
+            }
+            Err(message) => {
+                self.ui.error(&format!(
+                    "{}; a new session starts with the next cell",
+                    message
+                ));
+                self.r = None;
+                self.failed = true;
+            }
+        }
+    }
+
+    /// Handle a `:command`. Returns false when the REPL should exit.
+    fn run_meta(&mut self, line: &str) -> bool {
+        let (command, arg) = line
+            .split_once(char::is_whitespace)
+            .map(|(command, arg)| (command, arg.trim()))
+            .unwrap_or((line, ""));
+        let result = match (command, arg) {
+            (":quit" | ":q", _) => return false,
+            (":help" | ":h", _) => Ok(REPL_HELP.to_string()),
+            (":type" | ":t", expr) if !expr.is_empty() => self.session.type_of(expr),
+            (":mir", name) if !name.is_empty() => self.session.mir_of(name),
+            (":emit", name) if !name.is_empty() => self.session.emit_of(name),
+            (":load", path) if !path.is_empty() => {
+                match fs::read_to_string(path) {
+                    Ok(source) => self.run_cell(&source),
+                    Err(e) => {
+                        self.ui.error(&format!("Failed to read {}: {}", path, e));
+                        self.failed = true;
+                    }
+                }
+                return true;
+            }
+            (":type" | ":t" | ":mir" | ":emit" | ":load", _) => {
+                self.ui
+                    .error(&format!("{} needs an argument; see :help", command));
+                self.failed = true;
+                return true;
+            }
+            _ => {
+                self.ui
+                    .error(&format!("unknown REPL command '{}'; see :help", command));
+                self.failed = true;
+                return true;
+            }
+        };
+        match result {
+            Ok(text) => println!("{}", text.trim_end()),
+            Err(e) => {
+                e.error.display(Some(&e.source), Some(REPL_LABEL));
+                self.failed = true;
+            }
+        }
+        true
+    }
+}
+
+pub(crate) fn cmd_repl(args: &[String]) -> i32 {
+    let ui = CliLog::new();
+    if !args.is_empty() {
+        ui.error("Usage: RR repl");
+        return 1;
+    }
+    let entry = env::current_dir()
+        .unwrap_or_default()
+        .join("repl.rr")
+        .to_string_lossy()
+        .to_string();
+    let mut repl = Repl {
+        ui,
+        session: ReplSession::new(entry, OptLevel::O1),
+        r: None,
+        failed: false,
+    };
+
+    let stdin = io::stdin();
+    let interactive = stdin.is_terminal();
+    if interactive {
+        println!(
+            "RR Tachyon v{} repl; :help for commands, :quit to leave",
+            env!("CARGO_PKG_VERSION")
+        );
+    }
+    let mut input = stdin.lock();
+    let mut buffer = String::new();
+    let mut line = String::new();
+    loop {
+        if interactive {
+            print!("{}", if buffer.is_empty() { "rr> " } else { "... " });
+            let _ = io::stdout().flush();
+        }
+        line.clear();
+        match input.read_line(&mut line) {
+            Ok(0) => break,
+            Ok(_) => {}
+            Err(e) => {
+                repl.ui.error(&format!("Failed to read input: {}", e));
+                return 1;
+            }
+        }
+        if buffer.is_empty() && line.trim_start().starts_with(':') {
+            if !repl.run_meta(line.trim()) {
+                break;
+            }
+            continue;
+        }
+        buffer.push_str(&line);
+        if input_is_incomplete(&buffer) {
+            continue;
+        }
+        let cell = std::mem::take(&mut buffer);
+        if !cell.trim().is_empty() {
+            repl.run_cell(&cell);
+        }
+    }
+    if !buffer.trim().is_empty() {
+        repl.run_cell(&buffer);
+    }
+    // Piped sessions report failures through the exit code; an interactive
+    // session already showed them as they happened.
+    if repl.failed && !interactive { 1 } else { 0 }
+}
@@ -10,8 +10,9 @@ pub(crate) fn print_usage() {
     eprintln!("  RR outdated");
     eprintln!("  RR update [module-path]");
     eprintln!(
-        "  RR publish <version> [--dry-run] [--allow-dirty] [--push-tag] [--remote <name>] [--registry <dir>]"
+        "  RR publish <version> [--dry-run] [--allow-dirty] [--allow-breaking] [--push-tag] [--remote <name>] [--registry <dir>]"
     );
+    eprintln!("  RR audit [--registry <dir>]");
     eprintln!("  RR search <query> [--registry <dir>]");
     eprintln!("  RR registry keygen [identity] [--out-dir <dir>]");
     eprintln!(
@@ -33,6 +34,8 @@ pub(crate) fn print_usage() {
     eprintln!(
         "  RR registry audit export <file> [--format <tsv|jsonl>] [--limit <n>] [--action <kind>] [--module <path>] [--contains <text>] [--registry <dir>]"
     );
+    eprintln!("  RR registry audit verify [--registry <dir>]");
+    eprintln!("  RR registry audit reseal [--registry <dir>]");
     eprintln!(
         "  RR registry policy bootstrap <trusted-public-key> [--signer <identity>] [--auto-approve-signer <identity>] [--require-signed] [--require-approval] [--registry <dir>]"
     );
@@ -51,12 +54,24 @@ pub(crate) fn print_usage() {
     eprintln!("  RR registry deprecate <module-path> <message> [--registry <dir>]");
     eprintln!("  RR registry undeprecate <module-path> [--registry <dir>]");
     eprintln!("  RR registry verify [module-path] [--registry <dir>]");
+    eprintln!("  RR registry serve <dir> [--addr <host:port>]");
+    eprintln!(
+        "  RR registry advisory add <module-path> <version-range> <id> <summary> [--registry <dir>]"
+    );
     eprintln!("  RR mod graph");
     eprintln!("  RR mod why <module-path>");
     eprintln!("  RR mod verify");
     eprintln!("  RR mod tidy");
     eprintln!("  RR mod vendor");
-    eprintln!("  RR run [entry.rr|dir|.] [options]");
+    eprintln!("  RR mod sbom [--format cyclonedx|spdx]");
+    eprintln!("  RR mod cache list|verify|clean [--older-than <age>]");
+    eprintln!("  RR mir-opt <file.mir> [--passes <p1,p2,...>]");
+    eprintln!("  RR lint [file.rr|dir]");
+    eprintln!("  RR repl");
+    eprintln!("  RR dap");
+    eprintln!("  RR doc [dir|file.rr] [--out-dir <dir>] [--test]");
+    eprintln!("  RR import-r <file.R> [-o file.rr]");
+    eprintln!("  RR run [entry.rr|dir|.] [options] [-- <args>...]");
     eprintln!("  RR build [dir|file.rr] [options]");
     eprintln!("  RR watch [entry.rr|dir|.] [options]");
     eprintln!("Options:");
@@ -72,10 +87,19 @@ pub(crate) fn print_usage() {
     eprintln!("  --module <path>             Registry audit module filter");
     eprintln!("  --contains <text>           Registry audit substring filter");
     eprintln!("  --format <tsv|jsonl>        Registry audit export output format");
+    eprintln!("  --format <cyclonedx|spdx>   RR mod sbom document format (default cyclonedx)");
+    eprintln!(
+        "  --offline                     Resolve modules only from rr.lock, vendor/ and cache"
+    );
+    eprintln!(
+        "  --older-than <age>            RR mod cache clean: only entries unused for <age> (30d, 12h)"
+    );
+    eprintln!("  --test                        RR doc: compile and run examples in doc comments");
+    eprintln!("  --passes <p1,p2,...>         MIR passes for RR mir-opt, by timing name");
     eprintln!("  --type-mode <strict>          Static typing mode (RR 2.0 stable default)");
     eprintln!("  --native-backend <off|optional|required>  Native intrinsic backend mode");
     eprintln!("  --parallel-mode <off|optional|required>   Parallel execution mode");
-    eprintln!("  --parallel-backend <auto|r|openmp>        Parallel backend selection");
+    eprintln!("  --parallel-backend <auto|r|openmp|psock>  Parallel backend selection");
     eprintln!("  --parallel-threads <N>                    Parallel worker threads (0=auto)");
     eprintln!("  --parallel-min-trip <N>                   Minimum trip-count for parallel path");
     eprintln!(
@@ -111,12 +135,24 @@ pub(crate) fn print_usage() {
     );
     eprintln!("  --profile-compile-out <file>              Write compile profile JSON to a file");
     eprintln!("  --profile-use <file>                      Use optimizer hot-count profile data");
+    eprintln!(
+        "  --remarks <file>                          Write optimization remarks (.json or text; implies --cold)"
+    );
+    eprintln!(
+        "  --emit <stages>                           Dump tokens,ast,hir,typed,mir,mir-opt,raw-r,r (or all; implies --cold)"
+    );
+    eprintln!("  --emit-dir <dir>                          Directory for --emit dumps");
+    eprintln!(
+        "  --emit-fn <names>                         Limit per-function --emit dumps to these functions"
+    );
     eprintln!(
         "  --compile-mode <standard|fast-dev>        Compiler pass profile selection (build/run/watch default fast-dev)"
     );
     eprintln!("  --poll-ms <N>                             Watch polling interval in milliseconds");
     eprintln!("  --once                                    Run a single watch tick and exit");
     eprintln!("  --keep-r                      Keep generated .gen.R when running");
+    eprintln!("  --coverage <file>             RR run: write lcov (or Cobertura .xml) coverage");
+    eprintln!("  -- <args>...                  RR run: pass the remaining arguments to main(args)");
     eprintln!("  --no-runtime                  Emit helper-only R without source/native bootstrap");
     eprintln!("  --preserve-all-defs          Keep otherwise unreachable generated helpers");
     eprintln!("  --preserve-all-def           Alias for --preserve-all-defs");
@@ -1,3 +1,4 @@
+use crate::mir::semantics::call_model;
 use crate::mir::*;
 use std::collections::HashSet;
 
@@ -191,6 +192,30 @@ pub fn call_is_pure(callee: &str) -> bool {
     }
 }
 
+/// Checks if a call draws from, or reseeds, R's random number generator.
+pub fn call_uses_rng(callee: &str) -> bool {
+    call_model::is_rng_call(callee)
+}
+
+/// Checks if a function draws random numbers directly, including from an
+/// `unsafe r` block that calls an RNG function or could not be analyzed.
+/// Calls to user functions are not followed; kernels that admit user calls
+/// check them through their own whitelists.
+pub fn fn_uses_rng(fn_ir: &FnIR) -> bool {
+    let direct = fn_ir.values.iter().any(
+        |value| matches!(&value.kind, ValueKind::Call { callee, .. } if call_uses_rng(callee)),
+    );
+    direct
+        || fn_ir.blocks.iter().any(|block| {
+            block.instrs.iter().any(|instr| match instr {
+                Instr::UnsafeRBlock { access, .. } => access
+                    .as_ref()
+                    .is_none_or(|access| access.calls.iter().any(|call| call_uses_rng(call))),
+                _ => false,
+            })
+        })
+}
+
 /// Checks if an entire basic block is effect-free (excluding terminator).
 pub fn block_is_pure(bid: BlockId, fn_ir: &FnIR) -> bool {
     let block = &fn_ir.blocks[bid];
@@ -233,6 +258,58 @@ mod tests {
         assert!(!call_is_pure("rr_bool"));
     }
 
+    #[test]
+    fn rng_calls_are_tracked_with_and_without_namespace() {
+        for callee in ["runif", "stats::rnorm", "base::sample", "set.seed", "rpois"] {
+            assert!(call_uses_rng(callee), "{callee}");
+        }
+        for callee in ["sum", "stats::sd", "dplyr::sample_n", "rr_field_get"] {
+            assert!(!call_uses_rng(callee), "{callee}");
+        }
+    }
+
+    #[test]
+    fn fn_uses_rng_sees_calls_and_unsafe_r_blocks() {
+        let mut fn_ir = FnIR::new("draws".to_string(), vec![]);
+        let b0 = fn_ir.add_block();
+        fn_ir.entry = b0;
+        fn_ir.body_head = b0;
+        fn_ir.blocks[b0].term = Terminator::Return(None);
+        assert!(!fn_uses_rng(&fn_ir));
+
+        let push_block = |fn_ir: &mut FnIR, code: &str| {
+            fn_ir.blocks[b0].instrs.push(Instr::UnsafeRBlock {
+                code: code.to_string(),
+                read_only: true,
+                access: crate::mir::analyze::unsafe_r::analyze_unsafe_r(code),
+                span: Span::default(),
+            });
+        };
+        push_block(&mut fn_ir, "print(x)");
+        assert!(!fn_uses_rng(&fn_ir));
+        push_block(&mut fn_ir, "print(stats::runif(1))");
+        assert!(fn_uses_rng(&fn_ir));
+
+        fn_ir.blocks[b0].instrs.clear();
+        let n = fn_ir.add_value(
+            ValueKind::Const(Lit::Int(3)),
+            Span::default(),
+            Facts::empty(),
+            None,
+        );
+        fn_ir.add_value(
+            ValueKind::Call {
+                callee: "rnorm".to_string(),
+                args: vec![n],
+                names: vec![None],
+            },
+            Span::default(),
+            Facts::empty(),
+            None,
+        );
+        assert!(fn_uses_rng(&fn_ir));
+    }
+
     #[test]
     fn phi_cycle_does_not_recurse_forever() {
         let mut fn_ir = FnIR::new("phi_cycle".to_string(), vec![]);
@@ -2,4 +2,6 @@ pub mod alias;
 pub mod effects;
 pub mod escape;
 pub mod na;
+pub mod ownership;
 pub mod range;
+pub mod unsafe_r;
@@ -0,0 +1,570 @@
+use crate::mir::analyze::alias::{self, AliasClass};
+use crate::mir::analyze::effects;
+use crate::mir::analyze::escape::EscapeAnalysis;
+use crate::mir::def::EscapeStatus;
+use crate::mir::*;
+use rustc_hash::{FxHashMap, FxHashSet};
+
+/// Per-variable ownership state: the allocation a variable uniquely owns.
+///
+/// R's copy-on-modify semantics mean that a vector bound to exactly one
+/// variable, freshly allocated and never handed to a retaining call, can be
+/// updated in place. Any copy (`y <- x`), retaining call argument or opaque
+/// raw-R write demotes the variable back to shared.
+pub type OwnedVars = FxHashMap<VarId, ValueId>;
+
+#[derive(Debug, Clone, Default)]
+pub struct OwnershipFacts {
+    block_in: Vec<OwnedVars>,
+}
+
+impl OwnershipFacts {
+    /// Runs escape analysis first so escaped allocations are never treated as
+    /// uniquely owned, then computes the flow-sensitive ownership facts.
+    pub fn analyze_function(fn_ir: &mut FnIR) -> Self {
+        EscapeAnalysis::analyze_function(fn_ir);
+        Self::compute(fn_ir)
+    }
+
+    /// Computes ownership facts using whatever escape status is already
+    /// recorded on the values. `Unknown` escape status is accepted because
+    /// variable-level sharing is tracked independently through loads.
+    pub fn compute(fn_ir: &FnIR) -> Self {
+        let preds = build_pred_map(fn_ir);
+        let mut block_in: Vec<Option<OwnedVars>> = vec![None; fn_ir.blocks.len()];
+        let mut block_out: Vec<Option<OwnedVars>> = vec![None; fn_ir.blocks.len()];
+        if fn_ir.entry < fn_ir.blocks.len() {
+            block_in[fn_ir.entry] = Some(OwnedVars::default());
+        }
+
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for bid in 0..fn_ir.blocks.len() {
+                let incoming = if bid == fn_ir.entry {
+                    Some(OwnedVars::default())
+                } else {
+                    meet_pred_states(
+                        preds.get(&bid).map(Vec::as_slice).unwrap_or(&[]),
+                        &block_out,
+                    )
+                };
+                let Some(incoming) = incoming else {
+                    continue;
+                };
+                if block_in[bid].as_ref() != Some(&incoming) {
+                    block_in[bid] = Some(incoming.clone());
+                    changed = true;
+                }
+                let mut state = incoming;
+                for instr in &fn_ir.blocks[bid].instrs {
+                    transfer_instr(fn_ir, instr, &mut state);
+                }
+                if block_out[bid].as_ref() != Some(&state) {
+                    block_out[bid] = Some(state);
+                    changed = true;
+                }
+            }
+        }
+
+        Self {
+            block_in: block_in
+                .into_iter()
+                .map(Option::unwrap_or_default)
+                .collect(),
+        }
+    }
+
+    /// Variables uniquely owned on entry to `bid`, keyed to their allocation.
+    pub fn owned_at_entry(&self, bid: BlockId) -> Option<&OwnedVars> {
+        self.block_in.get(bid)
+    }
+
+    /// The allocation `var` uniquely owns on entry to `bid`, if any.
+    pub fn owned_alloc(&self, bid: BlockId, var: &str) -> Option<ValueId> {
+        self.block_in.get(bid)?.get(var).copied()
+    }
+
+    /// A cursor that walks block `bid` forward from its entry state.
+    pub fn cursor(&self, bid: BlockId) -> OwnershipCursor {
+        OwnershipCursor {
+            bid,
+            next: 0,
+            state: self.block_in.get(bid).cloned().unwrap_or_default(),
+        }
+    }
+
+    /// The allocation `var` uniquely owns on entry to every block in `blocks`,
+    /// provided it is never rebound inside them. In-place stores keep
+    /// ownership, so this holds for loop-carried destinations.
+    pub fn owned_throughout(
+        &self,
+        fn_ir: &FnIR,
+        blocks: impl IntoIterator<Item = BlockId>,
+        var: &str,
+    ) -> Option<ValueId> {
+        let mut alloc = None;
+        for bid in blocks {
+            let owned = self.owned_alloc(bid, var)?;
+            match alloc {
+                None => alloc = Some(owned),
+                Some(prev) if prev == owned => {}
+                Some(_) => return None,
+            }
+            let rebinds = fn_ir.blocks[bid].instrs.iter().any(|instr| {
+                matches!(instr, Instr::Assign { dst, .. } if dst == var)
+                    || instr.unsafe_r_may_write(var)
+            });
+            if rebinds {
+                return None;
+            }
+        }
+        alloc
+    }
+}
+
+/// Ownership state walked forward through one block. Queries must move
+/// forward, so asking about every instruction of a block costs one transfer
+/// per instruction in total.
+#[derive(Debug, Clone)]
+pub struct OwnershipCursor {
+    bid: BlockId,
+    next: usize,
+    state: OwnedVars,
+}
+
+impl OwnershipCursor {
+    /// The allocation `var` uniquely owns just before `instrs[instr_idx]` of
+    /// the cursor's block executes.
+    pub fn owned_alloc_before(
+        &mut self,
+        fn_ir: &FnIR,
+        instr_idx: usize,
+        var: &str,
+    ) -> Option<ValueId> {
+        debug_assert!(
+            instr_idx >= self.next,
+            "ownership cursor cannot move backwards"
+        );
+        let instrs = &fn_ir.blocks[self.bid].instrs;
+        while self.next < instr_idx.min(instrs.len()) {
+            transfer_instr(fn_ir, &instrs[self.next], &mut self.state);
+            self.next += 1;
+        }
+        self.state.get(var).copied()
+    }
+}
+
+/// Variables a function may write through in place (indexed stores and
+/// writing raw-R blocks). Binding a second name to an allocation is free
+/// unless one of the names is written this way.
+#[derive(Debug, Clone, Default)]
+pub struct InPlaceWrites {
+    vars: FxHashSet<VarId>,
+    opaque: bool,
+}
+
+impl InPlaceWrites {
+    pub fn collect(fn_ir: &FnIR) -> Self {
+        let mut writes = Self::default();
+        for block in &fn_ir.blocks {
+            for instr in &block.instrs {
+                match instr {
+                    Instr::StoreIndex1D { base, .. }
+                    | Instr::StoreIndex2D { base, .. }
+                    | Instr::StoreIndex3D { base, .. } => match store_base_var(fn_ir, *base) {
+                        Some(var) => {
+                            writes.vars.insert(var.clone());
+                        }
+                        None => writes.opaque = true,
+                    },
+                    Instr::UnsafeRBlock {
+                        read_only: false,
+                        access,
+                        ..
+                    } => match access {
+                        Some(access) => writes.vars.extend(access.written().cloned()),
+                        None => writes.opaque = true,
+                    },
+                    _ => {}
+                }
+            }
+        }
+        writes
+    }
+
+    pub fn may_write(&self, var: &str) -> bool {
+        self.opaque || self.vars.contains(var)
+    }
+}
+
+fn store_base_var(fn_ir: &FnIR, base: ValueId) -> Option<&VarId> {
+    let value = fn_ir.values.get(base)?;
+    match &value.kind {
+        ValueKind::Load { var } => Some(var),
+        _ => value.origin_var.as_ref(),
+    }
+}
+
+/// Refines [`alias::alias_class_for_base`] with ownership: a base whose
+/// origin variable uniquely owns its storage at `bid` can only alias that
+/// variable, even when its value shape (phi, index, call) is otherwise opaque.
+pub fn alias_class_with_ownership(
+    fn_ir: &FnIR,
+    facts: &OwnershipFacts,
+    bid: BlockId,
+    base: ValueId,
+) -> AliasClass {
+    let cls = alias::alias_class_for_base(fn_ir, base);
+    if !matches!(cls, AliasClass::Unknown) {
+        return cls;
+    }
+    let value = &fn_ir.values[base];
+    let owner_block = value.phi_block.unwrap_or(bid);
+    match &value.origin_var {
+        Some(var) if facts.owned_alloc(owner_block, var).is_some() => AliasClass::Var(var.clone()),
+        _ => AliasClass::Unknown,
+    }
+}
+
+/// Whether `vid` produces a new vector that no other binding can observe.
+pub fn is_fresh_allocation(fn_ir: &FnIR, vid: ValueId) -> bool {
+    let value = &fn_ir.values[vid];
+    if value.escape == EscapeStatus::Escaped {
+        return false;
+    }
+    match &value.kind {
+        ValueKind::Call { callee, .. } => is_allocation_call(callee),
+        _ => matches!(
+            alias::alias_class_for_base(fn_ir, vid),
+            AliasClass::Fresh(_)
+        ),
+    }
+}
+
+/// The length operand of an allocation, when the allocation call pins it.
+pub fn allocation_length(fn_ir: &FnIR, alloc: ValueId) -> Option<ValueId> {
+    let ValueKind::Call {
+        callee,
+        args,
+        names,
+        ..
+    } = &fn_ir.values.get(alloc)?.kind
+    else {
+        return None;
+    };
+    let named = |name: &str| {
+        names
+            .iter()
+            .position(|n| n.as_deref() == Some(name))
+            .and_then(|pos| args.get(pos).copied())
+    };
+    match callee.strip_prefix("base::").unwrap_or(callee) {
+        "numeric" | "integer" | "logical" | "character" | "double" | "seq_len"
+            if args.len() == 1 =>
+        {
+            Some(args[0])
+        }
+        "vector" => named("length").or_else(|| args.get(1).copied()),
+        "rep.int" | "rep_len" if args.len() == 2 => {
+            let value_is_scalar = matches!(fn_ir.values[args[0]].kind, ValueKind::Const(_));
+            value_is_scalar.then_some(args[1])
+        }
+        _ => None,
+    }
+}
+
+/// Whether two length operands are provably the same runtime value.
+pub fn same_length_operand(fn_ir: &FnIR, a: ValueId, b: ValueId) -> bool {
+    if a == b {
+        return true;
+    }
+    let never_reassigned = |var: &str| {
+        !fn_ir.blocks.iter().any(|block| {
+            block
+                .instrs
+                .iter()
+                .any(|instr| matches!(instr, Instr::Assign { dst, .. } if dst == var))
+        })
+    };
+    match (&fn_ir.values[a].kind, &fn_ir.values[b].kind) {
+        (ValueKind::Param { index: x }, ValueKind::Param { index: y }) => x == y,
+        (ValueKind::Load { var: x }, ValueKind::Load { var: y }) => x == y && never_reassigned(x),
+        (ValueKind::Len { base: x }, ValueKind::Len { base: y }) => {
+            x == y
+                || matches!(
+                    (&fn_ir.values[*x].kind, &fn_ir.values[*y].kind),
+                    (ValueKind::Load { var: vx }, ValueKind::Load { var: vy })
+                        if vx == vy && never_reassigned(vx)
+                )
+        }
+        (ValueKind::Const(Lit::Int(x)), ValueKind::Const(Lit::Int(y))) => x == y,
+        _ => false,
+    }
+}
+
+fn is_allocation_call(callee: &str) -> bool {
+    matches!(
+        callee.strip_prefix("base::").unwrap_or(callee),
+        "numeric"
+            | "integer"
+            | "logical"
+            | "character"
+            | "double"
+            | "vector"
+            | "rep"
+            | "rep.int"
+            | "rep_len"
+            | "matrix"
+            | "array"
+            | "c"
+            | "seq_len"
+            | "seq_along"
+            | "rr_named_list"
+    )
+}
+
+fn transfer_instr(fn_ir: &FnIR, instr: &Instr, state: &mut OwnedVars) {
+    match instr {
+        Instr::Assign { dst, src, .. } => {
+            release_retained_loads(fn_ir, *src, state);
+            state.remove(dst);
+            match &fn_ir.values[*src].kind {
+                ValueKind::Load { var } if var != dst => {
+                    // `dst <- var` shares one allocation between two bindings.
+                    state.remove(var);
+                }
+                _ if is_fresh_allocation(fn_ir, *src)
+                    && !state.values().any(|alloc| alloc == src) =>
+                {
+                    state.insert(dst.clone(), *src);
+                }
+                _ => {}
+            }
+        }
+        Instr::Eval { val, .. } => release_retained_loads(fn_ir, *val, state),
+        Instr::StoreIndex1D { val, .. }
+        | Instr::StoreIndex2D { val, .. }
+        | Instr::StoreIndex3D { val, .. } => {
+            // Writing through the base keeps it owned; storing a whole vector
+            // into a container element shares that vector with the container.
+            release_retained_loads(fn_ir, *val, state);
+            if let ValueKind::Load { var } = &fn_ir.values[*val].kind {
+                state.remove(var);
+            }
+        }
+        Instr::UnsafeRBlock {
+            read_only: false,
+            access,
+            ..
+        } => match access {
+            Some(access) => {
+                for var in access.written() {
+                    state.remove(var);
+                }
+            }
+            None => state.clear(),
+        },
+        Instr::UnsafeRBlock { .. } => {}
+    }
+}
+
+/// Drops ownership of variables whose loads flow into calls that may retain
+/// a reference (closures, user functions, package code).
+fn release_retained_loads(fn_ir: &FnIR, root: ValueId, state: &mut OwnedVars) {
+    if state.is_empty() {
+        return;
+    }
+    let mut stack = vec![root];
+    let mut seen = FxHashSet::default();
+    while let Some(vid) = stack.pop() {
+        if !seen.insert(vid) {
+            continue;
+        }
+        let kind = &fn_ir.values[vid].kind;
+        if let ValueKind::Call { callee, args, .. } = kind
+            && !effects::call_is_pure(callee)
+        {
+            for arg in args {
+                if let ValueKind::Load { var } = &fn_ir.values[*arg].kind {
+                    state.remove(var);
+                }
+            }
+        }
+        if let ValueKind::RecordLit { fields } = kind {
+            for (_, value) in fields {
+                if let ValueKind::Load { var } = &fn_ir.values[*value].kind {
+                    state.remove(var);
+                }
+            }
+        }
+        if !matches!(kind, ValueKind::Phi { .. }) {
+            stack.extend(value_dependencies(kind));
+        }
+    }
+}
+
+fn build_pred_map(fn_ir: &FnIR) -> FxHashMap<BlockId, Vec<BlockId>> {
+    let mut preds: FxHashMap<BlockId, Vec<BlockId>> = FxHashMap::default();
+    for block in &fn_ir.blocks {
+        match block.term {
+            Terminator::Goto(target) => preds.entry(target).or_default().push(block.id),
+            Terminator::If {
+                then_bb, else_bb, ..
+            } => {
+                preds.entry(then_bb).or_default().push(block.id);
+                preds.entry(else_bb).or_default().push(block.id);
+            }
+            Terminator::Return(_) | Terminator::Unreachable => {}
+        }
+    }
+    preds
+}
+
+fn meet_pred_states(preds: &[BlockId], block_out: &[Option<OwnedVars>]) -> Option<OwnedVars> {
+    let mut out: Option<OwnedVars> = None;
+    for pred in preds {
+        // Unvisited predecessors (back edges on the first sweep) are optimistic.
+        let Some(pred_state) = block_out.get(*pred).and_then(Option::as_ref) else {
+            continue;
+        };
+        out = Some(match out {
+            None => pred_state.clone(),
+            Some(mut acc) => {
+                acc.retain(|var, alloc| pred_state.get(var) == Some(alloc));
+                acc
+            }
+        });
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mir::flow::Facts;
+    use crate::utils::Span;
+
+    fn call(fn_ir: &mut FnIR, callee: &str, args: Vec<ValueId>) -> ValueId {
+        let names = vec![None; args.len()];
+        fn_ir.add_value(
+            ValueKind::Call {
+                callee: callee.to_string(),
+                args,
+                names,
+            },
+            Span::default(),
+            Facts::empty(),
+            None,
+        )
+    }
+
+    fn load(fn_ir: &mut FnIR, var: &str) -> ValueId {
+        fn_ir.add_value(
+            ValueKind::Load {
+                var: var.to_string(),
+            },
+            Span::default(),
+            Facts::empty(),
+            Some(var.to_string()),
+        )
+    }
+
+    fn assign(fn_ir: &mut FnIR, bid: BlockId, dst: &str, src: ValueId) {
+        fn_ir.blocks[bid].instrs.push(Instr::Assign {
+            dst: dst.to_string(),
+            src,
+            span: Span::default(),
+        });
+    }
+
+    #[test]
+    fn fresh_allocation_is_owned_until_copied() {
+        let mut fn_ir = FnIR::new("owner".to_string(), vec!["n".to_string()]);
+        let entry = fn_ir.add_block();
+        let after = fn_ir.add_block();
+        fn_ir.entry = entry;
+        fn_ir.body_head = entry;
+
+        let n = fn_ir.add_value(
+            ValueKind::Param { index: 0 },
+            Span::default(),
+            Facts::empty(),
+            Some("n".to_string()),
+        );
+        let alloc = call(&mut fn_ir, "numeric", vec![n]);
+        assign(&mut fn_ir, entry, "x", alloc);
+        fn_ir.blocks[entry].term = Terminator::Goto(after);
+        let x = load(&mut fn_ir, "x");
+        assign(&mut fn_ir, after, "y", x);
+        fn_ir.blocks[after].term = Terminator::Return(None);
+
+        let facts = OwnershipFacts::analyze_function(&mut fn_ir);
+        assert_eq!(facts.owned_alloc(after, "x"), Some(alloc));
+        assert_eq!(allocation_length(&fn_ir, alloc), Some(n));
+        assert_eq!(
+            facts.cursor(after).owned_alloc_before(&fn_ir, 0, "x"),
+            Some(alloc)
+        );
+        let mut cursor = facts.cursor(after);
+        assert_eq!(
+            cursor.owned_alloc_before(&fn_ir, 1, "x"),
+            None,
+            "copying into another binding must demote the source to shared"
+        );
+        assert_eq!(cursor.owned_alloc_before(&fn_ir, 1, "y"), None);
+    }
+
+    #[test]
+    fn retaining_call_argument_and_divergent_join_drop_ownership() {
+        let mut fn_ir = FnIR::new("owner_join".to_string(), vec!["n".to_string()]);
+        let entry = fn_ir.add_block();
+        let left = fn_ir.add_block();
+        let right = fn_ir.add_block();
+        let join = fn_ir.add_block();
+        fn_ir.entry = entry;
+        fn_ir.body_head = entry;
+
+        let n = fn_ir.add_value(
+            ValueKind::Param { index: 0 },
+            Span::default(),
+            Facts::empty(),
+            Some("n".to_string()),
+        );
+        let cond = fn_ir.add_value(
+            ValueKind::Const(Lit::Bool(true)),
+            Span::default(),
+            Facts::empty(),
+            None,
+        );
+        let a_alloc = call(&mut fn_ir, "numeric", vec![n]);
+        let b_alloc = call(&mut fn_ir, "integer", vec![n]);
+        assign(&mut fn_ir, entry, "a", a_alloc);
+        assign(&mut fn_ir, entry, "b", b_alloc);
+        fn_ir.blocks[entry].term = Terminator::If {
+            cond,
+            then_bb: left,
+            else_bb: right,
+        };
+        let a_load = load(&mut fn_ir, "a");
+        let retained = call(&mut fn_ir, "Sym_7", vec![a_load]);
+        fn_ir.blocks[left].instrs.push(Instr::Eval {
+            val: retained,
+            span: Span::default(),
+        });
+        fn_ir.blocks[left].term = Terminator::Goto(join);
+        let b_realloc = call(&mut fn_ir, "integer", vec![n]);
+        assign(&mut fn_ir, right, "b", b_realloc);
+        fn_ir.blocks[right].term = Terminator::Goto(join);
+        fn_ir.blocks[join].term = Terminator::Return(None);
+
+        let facts = OwnershipFacts::analyze_function(&mut fn_ir);
+        assert_eq!(facts.owned_alloc(left, "a"), Some(a_alloc));
+        assert_eq!(facts.owned_alloc(join, "a"), None);
+        assert_eq!(
+            facts.owned_alloc(join, "b"),
+            None,
+            "different allocations on each path must not merge into one owner"
+        );
+    }
+}
@@ -0,0 +1,397 @@
+//! Read/write sets for `unsafe r { }` blocks.
+//!
+//! The block's code is parsed with the R-subset parser and walked for the
+//! names it reads, binds, binds with `<<-` and calls in the enclosing frame.
+//! Only calls to functions known to see nothing but their arguments are
+//! followed; any other call, and anything that can reach the frame by other
+//! means (`assign`, `eval`, `parent.frame`, `<<-` from a nested function,
+//! `return`, ...), makes the analysis give up, and callers keep treating the
+//! block as opaque. So do
+//! the probes `RR dap` and `RR run --coverage` insert: they must stay exactly
+//! where they were written, between statements that keep their own bindings.
+
+use crate::mir::UnsafeRAccess;
+use crate::mir::analyze::effects;
+use crate::mir::semantics::call_model;
+use crate::syntax::r_subset::{Arg, Expr, StmtKind, parse_program};
+use rustc_hash::FxHashSet;
+
+/// Functions that read or bind frame variables by name, evaluate code in the
+/// frame, or leave the enclosing RR function.
+const FRAME_DYNAMIC_FUNCTIONS: &[&str] = &[
+    "as.environment",
+    "assign",
+    "attach",
+    "delayedAssign",
+    "do.call",
+    "environment",
+    "eval",
+    "eval.parent",
+    "evalq",
+    "exists",
+    "get",
+    "get0",
+    "load",
+    "list2env",
+    "local",
+    "lockBinding",
+    "makeActiveBinding",
+    "match.arg",
+    "match.call",
+    "match.fun",
+    "mget",
+    "missing",
+    "on.exit",
+    "parent.env",
+    "parent.frame",
+    "Recall",
+    "remove",
+    "return",
+    "rm",
+    "source",
+    "sys.call",
+    "sys.frame",
+    "sys.frames",
+    "sys.function",
+    "sys.source",
+    "unlockBinding",
+    "with",
+    "within",
+    // rlang
+    "caller_env",
+    "current_env",
+    "env_bind",
+    "env_poke",
+    "local_bindings",
+];
+
+/// Base R functions that only see their arguments: they neither look up nor
+/// bind names in the calling frame. Calls to anything else, including user
+/// functions, may reach the frame, so the block stays opaque. Replacement
+/// functions are listed with their `<-` suffix.
+const FRAME_SAFE_FUNCTIONS: &[&str] = &[
+    "all",
+    "any",
+    "as.character",
+    "as.double",
+    "as.integer",
+    "as.logical",
+    "as.numeric",
+    "as.vector",
+    "attr",
+    "attr<-",
+    "cat",
+    "character",
+    "class",
+    "class<-",
+    "colnames",
+    "colnames<-",
+    "cumprod",
+    "cumsum",
+    "dim<-",
+    "dimnames<-",
+    "flush.console",
+    "format",
+    "formatC",
+    "grepl",
+    "gsub",
+    "head",
+    "identical",
+    "ifelse",
+    "inherits",
+    "integer",
+    "invisible",
+    "is.character",
+    "is.null",
+    "is.numeric",
+    "isFALSE",
+    "isTRUE",
+    "length<-",
+    "levels",
+    "levels<-",
+    "list",
+    "logical",
+    "match",
+    "median",
+    "message",
+    "names",
+    "names<-",
+    "nchar",
+    "order",
+    "paste",
+    "paste0",
+    "print",
+    "quantile",
+    "range",
+    "rep",
+    "rev",
+    "rownames",
+    "rownames<-",
+    "seq",
+    "signif",
+    "sort",
+    "sprintf",
+    "stop",
+    "strsplit",
+    "sub",
+    "substr",
+    "substring",
+    "tail",
+    "tolower",
+    "toupper",
+    "trimws",
+    "typeof",
+    "unique",
+    "unlist",
+    "warning",
+    "which",
+    "which.max",
+    "which.min",
+    "writeLines",
+];
+
+/// Packages whose exports are looked up in [`FRAME_SAFE_FUNCTIONS`] when
+/// called as `pkg::name`.
+const FRAME_SAFE_PACKAGES: &[&str] = &["base", "stats", "utils"];
+
+/// Instrumentation hooks. The debug hook reads its caller's frame by RR
+/// name, and a coverage probe counts the statement it precedes.
+const INSTRUMENTATION_HOOKS: &[&str] = &[
+    crate::compiler::DEBUG_HOOK_NAME,
+    crate::compiler::COVERAGE_PROBE_NAME,
+];
+
+fn is_frame_dynamic(name: &str) -> bool {
+    FRAME_DYNAMIC_FUNCTIONS.contains(&name) || INSTRUMENTATION_HOOKS.contains(&name)
+}
+
+fn is_frame_safe(name: &str) -> bool {
+    FRAME_SAFE_FUNCTIONS.contains(&name)
+        || effects::call_is_pure(name)
+        || call_model::is_rng_call(name)
+}
+
+/// Binary operators that rebind their left operand.
+const FRAME_DYNAMIC_OPERATORS: &[&str] = &["%<>%"];
+
+/// Compute the frame access of `unsafe r` code, or `None` when the code does
+/// not parse or uses a construct the analysis cannot see through.
+pub fn analyze_unsafe_r(code: &str) -> Option<UnsafeRAccess> {
+    let (stmts, _) = parse_program(code);
+    let mut walker = Walker::default();
+    for stmt in &stmts {
+        match &stmt.kind {
+            StmtKind::Expr(expr) => walker.expr(expr)?,
+            StmtKind::Raw(_) => return None,
+        }
+    }
+    Some(walker.access)
+}
+
+#[derive(Default)]
+struct Walker {
+    access: UnsafeRAccess,
+    /// Names bound by each enclosing nested function, innermost last.
+    scopes: Vec<FxHashSet<String>>,
+    /// R loops open in the block's own frame.
+    loop_depth: usize,
+}
+
+impl Walker {
+    fn bound(&self, name: &str) -> bool {
+        self.scopes.iter().any(|scope| scope.contains(name))
+    }
+
+    fn read(&mut self, name: &str) -> Option<()> {
+        if is_frame_dynamic(name) {
+            return None;
+        }
+        if !self.bound(name) {
+            self.access.reads.insert(name.to_string());
+        }
+        Some(())
+    }
+
+    fn bind(&mut self, name: &str) {
+        match self.scopes.last_mut() {
+            Some(scope) => {
+                scope.insert(name.to_string());
+            }
+            None => {
+                self.access.writes.insert(name.to_string());
+            }
+        }
+    }
+
+    fn call(&mut self, name: String) -> Option<()> {
+        if self.bound(&name) {
+            return Some(());
+        }
+        if is_frame_dynamic(&name) || !is_frame_safe(&name) {
+            return None;
+        }
+        self.access.calls.insert(name);
+        Some(())
+    }
+
+    fn args(&mut self, args: &[Arg]) -> Option<()> {
+        for value in args.iter().filter_map(|arg| arg.value.as_ref()) {
+            self.expr(value)?;
+        }
+        Some(())
+    }
+
+    fn expr(&mut self, expr: &Expr) -> Option<()> {
+        match expr {
+            Expr::Num(_) | Expr::Str(_) => Some(()),
+            Expr::Ident(name) => self.read(name),
+            Expr::Ns(_, name) | Expr::NsInternal(_, name) => {
+                if is_frame_dynamic(name) {
+                    return None;
+                }
+                Some(())
+            }
+            Expr::Call(callee, args) => {
+                match &**callee {
+                    Expr::Ident(name) => self.call(name.clone())?,
+                    Expr::Ns(pkg, name) | Expr::NsInternal(pkg, name) => {
+                        if is_frame_dynamic(name)
+                            || !FRAME_SAFE_PACKAGES.contains(&pkg.as_str())
+                            || !is_frame_safe(name)
+                        {
+                            return None;
+                        }
+                        self.access.calls.insert(format!("{}::{}", pkg, name));
+                    }
+                    // `(f)(x)`, `fs[[1]](x)`, `f(x)(y)`: the callee is not a name.
+                    _ => return None,
+                }
+                self.args(args)
+            }
+            Expr::Index { base, args, .. } => {
+                self.expr(base)?;
+                self.args(args)
+            }
+            Expr::Dollar(base, _) | Expr::Slot(base, _) | Expr::Paren(base) => self.expr(base),
+            Expr::Unary(_, operand) => self.expr(operand),
+            Expr::Binary(op, lhs, rhs) => {
+                if FRAME_DYNAMIC_OPERATORS.contains(&op.as_str()) {
+                    return None;
+                }
+                self.expr(lhs)?;
+                self.expr(rhs)
+            }
+            Expr::Block(stmts, _) => {
+                for stmt in stmts {
+                    match &stmt.kind {
+                        StmtKind::Expr(expr) => self.expr(expr)?,
+                        StmtKind::Raw(_) => return None,
+                    }
+                }
+                Some(())
+            }
+            Expr::If(cond, then, els) => {
+                self.expr(cond)?;
+                self.expr(then)?;
+                match els {
+                    Some(els) => self.expr(els),
+                    None => Some(()),
+                }
+            }
+            Expr::For(var, seq, body) => {
+                self.expr(seq)?;
+                self.bind(var);
+                self.in_loop(body)
+            }
+            Expr::While(cond, body) => {
+                self.expr(cond)?;
+                self.in_loop(body)
+            }
+            Expr::Repeat(body) => self.in_loop(body),
+            // Outside an R loop of its own, `break`/`next` would leave an RR loop.
+            Expr::Break | Expr::Next => {
+                (self.loop_depth > 0 || !self.scopes.is_empty()).then_some(())
+            }
+            Expr::Function(params, body) => {
+                self.scopes
+                    .push(params.iter().map(|(name, _)| name.clone()).collect());
+                let saved_loops = std::mem::take(&mut self.loop_depth);
+                let walked = self.function_body(params, body);
+                self.loop_depth = saved_loops;
+                self.scopes.pop();
+                walked
+            }
+            Expr::Assign(target, value) => {
+                self.expr(value)?;
+                self.target(target, false)
+            }
+            Expr::SuperAssign(target, value) => {
+                // From a nested function `<<-` can rebind a frame variable.
+                if !self.scopes.is_empty() {
+                    return None;
+                }
+                self.expr(value)?;
+                self.target(target, true)
+            }
+        }
+    }
+
+    fn function_body(&mut self, params: &[(String, Option<Expr>)], body: &Expr) -> Option<()> {
+        for default in params.iter().filter_map(|(_, default)| default.as_ref()) {
+            self.expr(default)?;
+        }
+        self.expr(body)
+    }
+
+    fn in_loop(&mut self, body: &Expr) -> Option<()> {
+        self.loop_depth += 1;
+        let walked = self.expr(body);
+        self.loop_depth -= 1;
+        walked
+    }
+
+    /// Record an assignment target: a name, or a replacement form such as
+    /// `x[i]`, `x$f`, `names(x)` that reads and rebinds its root name.
+    fn target(&mut self, target: &Expr, super_assign: bool) -> Option<()> {
+        let root = match target {
+            Expr::Ident(name) => name,
+            Expr::Index { base, args, .. } => {
+                self.args(args)?;
+                return self.replacement_root(base, super_assign);
+            }
+            Expr::Dollar(base, _) | Expr::Slot(base, _) => {
+                return self.replacement_root(base, super_assign);
+            }
+            Expr::Call(callee, args) => {
+                let Expr::Ident(name) = &**callee else {
+                    return None;
+                };
+                if is_frame_dynamic(name) {
+                    return None;
+                }
+                self.call(format!("{}<-", name))?;
+                let (first, rest) = args.split_first()?;
+                self.args(rest)?;
+                return self.replacement_root(first.value.as_ref()?, super_assign);
+            }
+            _ => return None,
+        };
+        if super_assign {
+            self.access.super_writes.insert(root.clone());
+        } else {
+            self.bind(root);
+        }
+        Some(())
+    }
+
+    fn replacement_root(&mut self, target: &Expr, super_assign: bool) -> Option<()> {
+        if let Expr::Ident(name) = target {
+            self.read(name)?;
+        }
+        self.target(target, super_assign)
+    }
+}
+
+#[cfg(test)]
+#[path = "unsafe_r/tests.rs"]
+mod tests;
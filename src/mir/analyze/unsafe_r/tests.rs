@@ -0,0 +1,70 @@
+use super::*;
+
+fn names(set: &std::collections::BTreeSet<String>) -> Vec<&str> {
+    set.iter().map(String::as_str).collect()
+}
+
+#[test]
+fn collects_reads_writes_and_calls_of_the_enclosing_frame() {
+    let access = analyze_unsafe_r(
+        r#"
+tmp <- x + 1L
+names(out)[i] <- "a"
+for (k in seq_len(n)) total <- total + k
+cache <<- tmp
+f <- function(v, w = scale) { local_tmp <- v * w; local_tmp + offset }
+print(stats::sd(ys), digits = 3)
+"#,
+    )
+    .expect("analyzable block");
+
+    assert_eq!(
+        names(&access.reads),
+        vec![
+            "i", "k", "n", "offset", "out", "scale", "tmp", "total", "x", "ys"
+        ]
+    );
+    assert_eq!(names(&access.writes), vec!["f", "k", "out", "tmp", "total"]);
+    assert_eq!(names(&access.super_writes), vec!["cache"]);
+    assert_eq!(
+        names(&access.calls),
+        vec!["names<-", "print", "seq_len", "stats::sd"]
+    );
+}
+
+#[test]
+fn print_only_block_reads_without_writing() {
+    let access = analyze_unsafe_r("print(x)\ncat(sprintf('%d\\n', y))").expect("analyzable");
+    assert_eq!(names(&access.reads), vec!["x", "y"]);
+    assert!(access.writes.is_empty() && access.super_writes.is_empty());
+}
+
+#[test]
+fn dynamic_frame_access_and_parse_failures_fall_back() {
+    for code in [
+        "assign('x', 1)",
+        "eval(quote(x <- 1))",
+        "base::get('x')",
+        "vals <- lapply(keys, get)",
+        "f <- function() x <<- 1",
+        "return(1)",
+        "break",
+        "x %<>% sort",
+        "environment(f) <- e",
+        "x <- `my var`",
+        "x <- (1 +",
+        "\"assign\"(\"scale\", 0L)",
+        "(assign)('x', 1)",
+        "fs[[1]]('x')",
+        "load(\"state.RData\")",
+        "rlang::env_bind(rlang::current_env(), scale = 0L)",
+        "rlang::env_poke(rlang::caller_env(), 'scale', 0L)",
+        "local_bindings(scale = 0L)",
+        "refresh_state()",
+        "pkg::helper(x)",
+    ] {
+        assert_eq!(analyze_unsafe_r(code), None, "{code}");
+    }
+    assert!(analyze_unsafe_r("repeat { if (length(queue) == 0L) break }").is_some());
+    assert!(analyze_unsafe_r("g <- function(f) f(x); print(stats::runif(1))").is_some());
+}
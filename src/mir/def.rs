@@ -4,6 +4,7 @@ use crate::typeck::{TypeState, TypeTerm};
 use crate::utils::Span;
 use rustc_hash::{FxHashMap, FxHashSet};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
 
 impl Span {
     pub fn dummy() -> Self {
@@ -226,15 +227,73 @@ pub enum Instr {
         val: ValueId,
         span: Span,
     },
-    // Raw R escape hatch. Read/write blocks are opaque; read-only blocks remain
-    // effectful barriers but do not invalidate RR locals.
+    // Raw R escape hatch. Read-only blocks remain effectful barriers but do
+    // not invalidate RR locals. `access` holds the frame names the code
+    // touches when it parses as the supported R subset; read/write blocks
+    // without it are opaque.
     UnsafeRBlock {
         code: String,
         read_only: bool,
+        access: Option<UnsafeRAccess>,
         span: Span,
     },
 }
 
+impl Instr {
+    pub fn span(&self) -> Span {
+        match self {
+            Instr::Assign { span, .. }
+            | Instr::Eval { span, .. }
+            | Instr::StoreIndex1D { span, .. }
+            | Instr::StoreIndex2D { span, .. }
+            | Instr::StoreIndex3D { span, .. }
+            | Instr::UnsafeRBlock { span, .. } => *span,
+        }
+    }
+
+    /// Whether this is an `unsafe r` block that may rebind `var`.
+    pub fn unsafe_r_may_write(&self, var: &str) -> bool {
+        match self {
+            Instr::UnsafeRBlock {
+                read_only: false,
+                access,
+                ..
+            } => access.as_ref().is_none_or(|access| access.may_write(var)),
+            _ => false,
+        }
+    }
+}
+
+/// Names an `unsafe r` block touches in the enclosing R frame, as found by
+/// `mir::analyze::unsafe_r`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnsafeRAccess {
+    /// Free variables the code reads.
+    pub reads: BTreeSet<String>,
+    /// Names bound with `<-`, `=`, `->`, `for` or a replacement call.
+    pub writes: BTreeSet<String>,
+    /// `<<-` and `->>` targets.
+    pub super_writes: BTreeSet<String>,
+    /// Functions called by name, `pkg::name` for namespaced calls.
+    pub calls: BTreeSet<String>,
+}
+
+impl UnsafeRAccess {
+    pub fn may_write(&self, var: &str) -> bool {
+        self.writes.contains(var) || self.super_writes.contains(var)
+    }
+
+    /// Every name written, including `<<-` targets.
+    pub fn written(&self) -> impl Iterator<Item = &String> {
+        self.writes.iter().chain(&self.super_writes)
+    }
+
+    /// Frame bindings the code may look up: variable reads and callees.
+    pub fn frame_reads(&self) -> impl Iterator<Item = &String> {
+        self.reads.iter().chain(&self.calls)
+    }
+}
+
 // Proof correspondence:
 // `VerifyIrValueRecordSubset` lifts reduced table rows to a small record over
 // the key fields carried here: `id`, `kind`, `origin_var`, `phi_block`, and
@@ -0,0 +1,74 @@
+//! Reference interpreter for `FnIR`.
+//!
+//! The interpreter executes MIR directly with the R semantics the generated
+//! code relies on (per-element NA, recycling, 1-based indexing, R integer
+//! overflow to NA). It exists to check optimizer passes against each other,
+//! not to replace the R backend, so anything it cannot model faithfully is
+//! reported as [`InterpError::Unsupported`] instead of being approximated.
+
+use std::fmt;
+
+#[path = "interp/builtins.rs"]
+mod builtins;
+#[path = "interp/exec.rs"]
+mod exec;
+#[path = "interp/inputs.rs"]
+mod inputs;
+#[path = "interp/validate.rs"]
+mod validate;
+#[path = "interp/value.rs"]
+mod value;
+
+pub use self::exec::Interpreter;
+pub use self::inputs::generate_inputs;
+pub use self::validate::{Divergence, validate_translation};
+pub use self::value::{RValue, RVector};
+
+#[cfg(test)]
+#[path = "interp/tests.rs"]
+mod tests;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum InterpError {
+    /// The program raised an R-level error (bounds, NA condition, ...).
+    Runtime(String),
+    /// The MIR uses a construct the interpreter does not model.
+    Unsupported(String),
+    /// The execution step budget was exhausted.
+    OutOfFuel,
+    /// The MIR itself is malformed (dangling ids, phi without an edge).
+    Malformed(String),
+}
+
+impl InterpError {
+    pub fn runtime(msg: impl Into<String>) -> Self {
+        Self::Runtime(msg.into())
+    }
+
+    pub fn unsupported(msg: impl Into<String>) -> Self {
+        Self::Unsupported(msg.into())
+    }
+
+    pub fn malformed(msg: impl Into<String>) -> Self {
+        Self::Malformed(msg.into())
+    }
+
+    /// Whether this outcome says nothing about the function's semantics and
+    /// should be ignored when comparing two runs.
+    pub fn is_inconclusive(&self) -> bool {
+        matches!(self, Self::Unsupported(_) | Self::OutOfFuel)
+    }
+}
+
+impl fmt::Display for InterpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Runtime(msg) => write!(f, "runtime error: {msg}"),
+            Self::Unsupported(msg) => write!(f, "unsupported: {msg}"),
+            Self::OutOfFuel => write!(f, "out of fuel"),
+            Self::Malformed(msg) => write!(f, "malformed MIR: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for InterpError {}
@@ -0,0 +1,550 @@
+use super::InterpError;
+use super::value::{self, RValue, RVector};
+use crate::mir::IntrinsicOp;
+use crate::syntax::ast::BinOp;
+
+/// Evaluate a call to an R builtin or RR runtime helper.
+pub(super) fn call(
+    callee: &str,
+    args: &[RValue],
+    names: &[Option<String>],
+) -> Result<RValue, InterpError> {
+    if names.iter().any(Option::is_some) {
+        return Err(InterpError::unsupported(format!(
+            "named arguments in call to {callee}"
+        )));
+    }
+    let callee = callee.strip_prefix("base::").unwrap_or(callee);
+    match callee {
+        "length" => Ok(RValue::scalar_int(arg(callee, args, 0)?.len() as i64)),
+        "seq_len" => {
+            let n = count_arg(callee, args, 0)?;
+            Ok(RValue::int((1..=n as i64).map(Some).collect()))
+        }
+        "seq_along" => {
+            let n = arg(callee, args, 0)?.len() as i64;
+            Ok(RValue::int((1..=n).map(Some).collect()))
+        }
+        "rr_indices" => {
+            let n = arg(callee, args, 0)?.len() as i64;
+            Ok(RValue::int((0..n).map(Some).collect()))
+        }
+        "c" => concat(args),
+        "numeric" | "double" => Ok(RValue::double(vec![Some(0.0); count_arg(callee, args, 0)?])),
+        "integer" => Ok(RValue::int(vec![Some(0); count_arg(callee, args, 0)?])),
+        "logical" => Ok(RValue::logical(vec![
+            Some(false);
+            count_arg(callee, args, 0)?
+        ])),
+        "character" => Ok(RValue::char(vec![
+            Some(String::new());
+            count_arg(callee, args, 0)?
+        ])),
+        "as.integer" => Ok(RValue::int(arg(callee, args, 0)?.data()?.as_int())),
+        "as.numeric" | "as.double" => Ok(RValue::double(arg(callee, args, 0)?.data()?.as_double())),
+        "as.logical" => Ok(RValue::logical(arg(callee, args, 0)?.data()?.as_logical())),
+        "as.character" => Ok(RValue::char(arg(callee, args, 0)?.data()?.as_char())),
+        "is.na" => {
+            let data = arg(callee, args, 0)?.data()?;
+            let out = match data {
+                RVector::Double(v) => v.iter().map(|x| Some(x.is_none_or(f64::is_nan))).collect(),
+                _ => data.as_char().iter().map(|x| Some(x.is_none())).collect(),
+            };
+            Ok(RValue::logical(out))
+        }
+        "sum" => sum(args),
+        "prod" => Ok(RValue::double(vec![
+            doubles(args)?
+                .iter()
+                .try_fold(1.0, |acc, x| x.map(|x| acc * x)),
+        ])),
+        "mean" => mean(arg(callee, args, 0)?),
+        "min" | "max" => extremum(callee == "max", args),
+        "abs" => {
+            let x = arg(callee, args, 0)?;
+            match x.data()? {
+                RVector::Logical(_) | RVector::Int(_) => Ok(RValue::Vector {
+                    data: RVector::Int(
+                        x.data()?.as_int().iter().map(|v| v.map(i64::abs)).collect(),
+                    ),
+                    dim: x.dim().map(<[usize]>::to_vec),
+                }),
+                _ => math1(x, f64::abs),
+            }
+        }
+        "sqrt" => math1(arg(callee, args, 0)?, f64::sqrt),
+        "exp" => math1(arg(callee, args, 0)?, f64::exp),
+        "log" if args.len() == 1 => math1(&args[0], f64::ln),
+        "log2" => math1(arg(callee, args, 0)?, f64::log2),
+        "log10" => math1(arg(callee, args, 0)?, f64::log10),
+        "sin" => math1(arg(callee, args, 0)?, f64::sin),
+        "cos" => math1(arg(callee, args, 0)?, f64::cos),
+        "tan" => math1(arg(callee, args, 0)?, f64::tan),
+        "floor" => math1(arg(callee, args, 0)?, f64::floor),
+        "ceiling" => math1(arg(callee, args, 0)?, f64::ceil),
+        "trunc" => math1(arg(callee, args, 0)?, f64::trunc),
+        "round" if args.len() == 1 => math1(&args[0], f64::round_ties_even),
+        "pmax" | "pmin" => parallel_extremum(callee == "pmax", args),
+        "any" | "all" => any_all(callee == "any", args),
+        "ifelse" => ifelse(args),
+        "rep" | "rep.int" => {
+            let x = arg(callee, args, 0)?;
+            let times = count_arg(callee, args, 1)?;
+            repeat_to(x, x.len() * times)
+        }
+        "rep_len" => repeat_to(arg(callee, args, 0)?, count_arg(callee, args, 1)?),
+        "paste0" => paste0(args),
+        "rr_index1_read" | "rr_index1_read_strict" => {
+            index1_read(arg(callee, args, 0)?, arg(callee, args, 1)?)
+        }
+        "rr_index1_read_vec" | "rr_gather" | "rr_index1_read_vec_floor" => {
+            let idx = index_vec_floor(arg(callee, args, 1)?)?;
+            arg(callee, args, 0)?.index1(&idx)
+        }
+        "rr_index_vec_floor" => index_vec_floor(arg(callee, args, 0)?),
+        "rr_same_len" => {
+            let (a, b) = (arg(callee, args, 0)?, arg(callee, args, 1)?);
+            if a.len() != b.len() {
+                return Err(InterpError::runtime(format!(
+                    "length mismatch ({} vs {})",
+                    a.len(),
+                    b.len()
+                )));
+            }
+            Ok(RValue::Null)
+        }
+        "rr_same_or_scalar" => Ok(RValue::scalar_bool(true)),
+        "rr_ifelse_strict" => {
+            let cond = arg(callee, args, 0)?.data()?;
+            let RVector::Logical(mask) = cond else {
+                return Err(InterpError::runtime("condition must be logical"));
+            };
+            if mask.iter().any(Option::is_none) {
+                return Err(InterpError::runtime("condition is NA"));
+            }
+            ifelse(args.get(..3).unwrap_or(args))
+        }
+        "rr_which_true" => {
+            let mask = arg(callee, args, 0)?.data()?.as_logical();
+            Ok(RValue::int(
+                mask.iter()
+                    .enumerate()
+                    .filter(|(_, m)| **m == Some(true))
+                    .map(|(i, _)| Some(i as i64 + 1))
+                    .collect(),
+            ))
+        }
+        "rr_assign_slice" => assign_slice(args),
+        "rr_field_get" => {
+            let name = arg(callee, args, 1)?.data()?.as_char();
+            let Some(Some(name)) = name.first() else {
+                return Err(InterpError::runtime("field name must be a string"));
+            };
+            arg(callee, args, 0)?.field(name)
+        }
+        "rr_field_set" => {
+            let name = arg(callee, args, 1)?.data()?.as_char();
+            let Some(Some(name)) = name.first() else {
+                return Err(InterpError::runtime("field name must be a string"));
+            };
+            arg(callee, args, 0)?.with_field(name, arg(callee, args, 2)?.clone())
+        }
+        _ => Err(InterpError::unsupported(format!("call to {callee}"))),
+    }
+}
+
+pub(super) fn intrinsic(op: IntrinsicOp, args: &[RValue]) -> Result<RValue, InterpError> {
+    let name = format!("{op:?}");
+    let as_double = |v: &RValue| -> Result<RValue, InterpError> {
+        Ok(RValue::Vector {
+            data: RVector::Double(v.data()?.as_double()),
+            dim: v.dim().map(<[usize]>::to_vec),
+        })
+    };
+    let bin = |op: BinOp| -> Result<RValue, InterpError> {
+        value::binary(
+            op,
+            &as_double(arg(&name, args, 0)?)?,
+            &as_double(arg(&name, args, 1)?)?,
+        )
+    };
+    match op {
+        IntrinsicOp::VecAddF64 => bin(BinOp::Add),
+        IntrinsicOp::VecSubF64 => bin(BinOp::Sub),
+        IntrinsicOp::VecMulF64 => bin(BinOp::Mul),
+        IntrinsicOp::VecDivF64 => bin(BinOp::Div),
+        IntrinsicOp::VecAbsF64 => math1(arg(&name, args, 0)?, f64::abs),
+        IntrinsicOp::VecLogF64 => math1(arg(&name, args, 0)?, f64::ln),
+        IntrinsicOp::VecSqrtF64 => math1(arg(&name, args, 0)?, f64::sqrt),
+        IntrinsicOp::VecPmaxF64 => parallel_extremum(true, args),
+        IntrinsicOp::VecPminF64 => parallel_extremum(false, args),
+        IntrinsicOp::VecSumF64 => {
+            let sum = arg(&name, args, 0)?
+                .data()?
+                .as_double()
+                .iter()
+                .try_fold(0.0, |acc, x| x.map(|x| acc + x));
+            Ok(RValue::double(vec![sum]))
+        }
+        IntrinsicOp::VecMeanF64 => mean(arg(&name, args, 0)?),
+    }
+}
+
+/// R's `a:b` for scalar endpoints.
+pub(super) fn colon(start: &RValue, end: &RValue) -> Result<RValue, InterpError> {
+    let endpoint = |v: &RValue| -> Result<f64, InterpError> {
+        let data = v.data()?;
+        if data.is_empty() {
+            return Err(InterpError::runtime("argument of length 0"));
+        }
+        data.as_double()[0].ok_or_else(|| InterpError::runtime("NA/NaN argument"))
+    };
+    let (a, b) = (endpoint(start)?, endpoint(end)?);
+    let span = (b - a).abs().floor() as usize;
+    if span > 10_000_000 {
+        return Err(InterpError::unsupported("range too long"));
+    }
+    let step = if a <= b { 1.0 } else { -1.0 };
+    if a == a.trunc() && a.abs() <= i32::MAX as f64 && b.abs() <= i32::MAX as f64 {
+        let (a, step) = (a as i64, step as i64);
+        Ok(RValue::int(
+            (0..=span as i64).map(|i| Some(a + i * step)).collect(),
+        ))
+    } else {
+        Ok(RValue::double(
+            (0..=span).map(|i| Some(a + i as f64 * step)).collect(),
+        ))
+    }
+}
+
+/// Scalar `base[i]` as emitted for `Index1D`, including R's `x[NA]`.
+pub(super) fn index1_read(base: &RValue, idx: &RValue) -> Result<RValue, InterpError> {
+    if let Ok(RVector::Logical(mask)) = idx.data() {
+        if mask.len() == 1 && mask[0].is_none() {
+            return Ok(match base.data()? {
+                RVector::Logical(_) => RValue::logical(vec![None; base.len()]),
+                RVector::Int(_) => RValue::int(vec![None; base.len()]),
+                RVector::Double(_) => RValue::double(vec![None; base.len()]),
+                RVector::Char(_) => RValue::char(vec![None; base.len()]),
+            });
+        }
+        return Err(InterpError::unsupported("logical subscript"));
+    }
+    if let RValue::List(_) = base {
+        return base.index1(idx);
+    }
+    let positions = idx.data()?.as_double();
+    if positions
+        .iter()
+        .flatten()
+        .any(|p| *p != p.trunc() || *p < 1.0)
+    {
+        return Err(InterpError::runtime("index must be an integer >= 1"));
+    }
+    base.index1(idx)
+}
+
+fn index_vec_floor(idx: &RValue) -> Result<RValue, InterpError> {
+    let floored: Vec<Option<i64>> = match idx.data()? {
+        RVector::Int(v) => v.clone(),
+        RVector::Double(v) => v
+            .iter()
+            .map(|x| x.filter(|x| x.is_finite()).map(|x| x.floor() as i64))
+            .collect(),
+        _ => return Err(InterpError::runtime("index must be numeric")),
+    };
+    if floored.iter().flatten().any(|i| *i < 1) {
+        return Err(InterpError::runtime("index must be >= 1"));
+    }
+    Ok(RValue::int(floored))
+}
+
+fn arg<'v>(callee: &str, args: &'v [RValue], i: usize) -> Result<&'v RValue, InterpError> {
+    args.get(i)
+        .ok_or_else(|| InterpError::runtime(format!("{callee}: missing argument {}", i + 1)))
+}
+
+fn count_arg(callee: &str, args: &[RValue], i: usize) -> Result<usize, InterpError> {
+    let value = arg(callee, args, i)?.data()?;
+    let Some(Some(n)) = value.as_double().first().copied() else {
+        return Err(InterpError::runtime(format!(
+            "{callee}: invalid length argument"
+        )));
+    };
+    if n < 0.0 {
+        return Err(InterpError::runtime(format!("{callee}: negative length")));
+    }
+    if n > 10_000_000.0 {
+        return Err(InterpError::unsupported("allocation too large"));
+    }
+    Ok(n as usize)
+}
+
+fn doubles(args: &[RValue]) -> Result<Vec<Option<f64>>, InterpError> {
+    let mut out = Vec::new();
+    for a in args {
+        if matches!(a, RValue::Null) {
+            continue;
+        }
+        out.extend(a.data()?.as_double());
+    }
+    Ok(out)
+}
+
+fn concat(args: &[RValue]) -> Result<RValue, InterpError> {
+    let mut parts = Vec::with_capacity(args.len());
+    for a in args {
+        match a {
+            RValue::Null => {}
+            RValue::Vector { data, .. } => parts.push(data.clone()),
+            RValue::List(_) => return Err(InterpError::unsupported("c() of lists")),
+        }
+    }
+    if parts.is_empty() {
+        return Ok(RValue::Null);
+    }
+    Ok(RValue::vector(RVector::concat(&parts)))
+}
+
+fn sum(args: &[RValue]) -> Result<RValue, InterpError> {
+    let mut parts = Vec::with_capacity(args.len());
+    for a in args {
+        if !matches!(a, RValue::Null) {
+            parts.push(a.data()?.clone());
+        }
+    }
+    match RVector::concat(&parts) {
+        RVector::Char(_) => Err(InterpError::runtime(
+            "invalid 'type' (character) of argument",
+        )),
+        RVector::Double(v) => Ok(RValue::double(vec![
+            v.iter().try_fold(0.0, |acc, x| x.map(|x| acc + x)),
+        ])),
+        ints => {
+            let total = ints
+                .as_int()
+                .iter()
+                .try_fold(0i64, |acc, x| x.and_then(|x| acc.checked_add(x)));
+            // Integer overflow in sum() yields NA like R.
+            Ok(RValue::int(vec![
+                total.filter(|t| t.abs() <= i32::MAX as i64),
+            ]))
+        }
+    }
+}
+
+fn mean(x: &RValue) -> Result<RValue, InterpError> {
+    let data = x.data()?;
+    if matches!(data, RVector::Char(_)) {
+        return Ok(RValue::double(vec![None]));
+    }
+    let values = data.as_double();
+    let total = values.iter().try_fold(0.0, |acc, x| x.map(|x| acc + x));
+    Ok(RValue::double(vec![total.map(|t| t / values.len() as f64)]))
+}
+
+fn extremum(max: bool, args: &[RValue]) -> Result<RValue, InterpError> {
+    let mut parts = Vec::with_capacity(args.len());
+    for a in args {
+        if !matches!(a, RValue::Null) {
+            parts.push(a.data()?.clone());
+        }
+    }
+    let all = RVector::concat(&parts);
+    let pick = |a: f64, b: f64| if max { a.max(b) } else { a.min(b) };
+    match all {
+        RVector::Char(_) => Err(InterpError::unsupported("min/max of character")),
+        RVector::Double(_) => {
+            let values = all.as_double();
+            if values.is_empty() {
+                return Ok(RValue::scalar_double(if max {
+                    f64::NEG_INFINITY
+                } else {
+                    f64::INFINITY
+                }));
+            }
+            let folded = values
+                .iter()
+                .try_fold(None::<f64>, |acc, x| {
+                    x.map(|x| Some(acc.map_or(x, |a| if x.is_nan() { x } else { pick(a, x) })))
+                })
+                .flatten();
+            Ok(RValue::double(vec![folded]))
+        }
+        ints => {
+            let values = ints.as_int();
+            if values.is_empty() {
+                return Ok(RValue::scalar_double(if max {
+                    f64::NEG_INFINITY
+                } else {
+                    f64::INFINITY
+                }));
+            }
+            let folded = values
+                .iter()
+                .try_fold(None::<i64>, |acc, x| {
+                    x.map(|x| Some(acc.map_or(x, |a| if max { a.max(x) } else { a.min(x) })))
+                })
+                .flatten();
+            Ok(RValue::int(vec![folded]))
+        }
+    }
+}
+
+fn math1(x: &RValue, f: fn(f64) -> f64) -> Result<RValue, InterpError> {
+    let data = x.data()?;
+    if matches!(data, RVector::Char(_)) {
+        return Err(InterpError::runtime(
+            "non-numeric argument to mathematical function",
+        ));
+    }
+    Ok(RValue::Vector {
+        data: RVector::Double(data.as_double().iter().map(|v| v.map(f)).collect()),
+        dim: x.dim().map(<[usize]>::to_vec),
+    })
+}
+
+fn parallel_extremum(max: bool, args: &[RValue]) -> Result<RValue, InterpError> {
+    let Some(first) = args.first() else {
+        return Err(InterpError::runtime("no arguments to pmax/pmin"));
+    };
+    let mut acc = first.clone();
+    for next in &args[1..] {
+        let (a, b) = (acc.data()?.as_double(), next.data()?.as_double());
+        let n = if a.is_empty() || b.is_empty() {
+            0
+        } else {
+            a.len().max(b.len())
+        };
+        let out = (0..n)
+            .map(|i| match (a[i % a.len()], b[i % b.len()]) {
+                (Some(x), Some(y)) => Some(if max { x.max(y) } else { x.min(y) }),
+                _ => None,
+            })
+            .collect();
+        acc = RValue::Vector {
+            data: RVector::Double(out),
+            dim: acc.dim().map(<[usize]>::to_vec),
+        };
+    }
+    let all_int = args
+        .iter()
+        .all(|a| matches!(a.data(), Ok(RVector::Int(_) | RVector::Logical(_))));
+    if all_int {
+        return Ok(RValue::Vector {
+            data: RVector::Int(acc.data()?.as_int()),
+            dim: acc.dim().map(<[usize]>::to_vec),
+        });
+    }
+    Ok(acc)
+}
+
+fn any_all(any: bool, args: &[RValue]) -> Result<RValue, InterpError> {
+    let mut saw_na = false;
+    for a in args {
+        if matches!(a, RValue::Null) {
+            continue;
+        }
+        for v in a.data()?.as_logical() {
+            match v {
+                Some(b) if b == any => return Ok(RValue::scalar_bool(any)),
+                Some(_) => {}
+                None => saw_na = true,
+            }
+        }
+    }
+    Ok(RValue::logical(vec![(!saw_na).then_some(!any)]))
+}
+
+fn ifelse(args: &[RValue]) -> Result<RValue, InterpError> {
+    let test = arg("ifelse", args, 0)?;
+    let (yes, no) = (arg("ifelse", args, 1)?, arg("ifelse", args, 2)?);
+    let mask = test.data()?.as_logical();
+    let joined = RVector::concat(&[yes.data()?.clone(), no.data()?.clone()]);
+    let (yes_len, no_len) = (yes.len(), no.len());
+    let mut out = RValue::Vector {
+        data: match joined {
+            RVector::Logical(_) => RVector::Logical(Vec::new()),
+            RVector::Int(_) => RVector::Int(Vec::new()),
+            RVector::Double(_) => RVector::Double(Vec::new()),
+            RVector::Char(_) => RVector::Char(Vec::new()),
+        },
+        dim: None,
+    };
+    for (i, m) in mask.iter().enumerate() {
+        let picked = match m {
+            Some(true) if yes_len > 0 => RValue::vector(joined.clone()).element(i % yes_len)?,
+            Some(false) if no_len > 0 => {
+                RValue::vector(joined.clone()).element(yes_len + i % no_len)?
+            }
+            Some(_) => return Err(InterpError::runtime("replacement has length zero")),
+            None => RValue::logical(vec![None]),
+        };
+        out.store1(i + 1, &picked)?;
+    }
+    if let (RValue::Vector { dim, .. }, Some(test_dim)) = (&mut out, test.dim()) {
+        *dim = Some(test_dim.to_vec());
+    }
+    Ok(out)
+}
+
+fn repeat_to(x: &RValue, len: usize) -> Result<RValue, InterpError> {
+    if len > 10_000_000 {
+        return Err(InterpError::unsupported("allocation too large"));
+    }
+    let data = x.data()?;
+    if data.is_empty() {
+        return Ok(RValue::vector(RVector::concat(std::slice::from_ref(data))));
+    }
+    let idx: Vec<Option<i64>> = (0..len)
+        .map(|i| Some((i % data.len()) as i64 + 1))
+        .collect();
+    x.index1(&RValue::int(idx))
+}
+
+fn paste0(args: &[RValue]) -> Result<RValue, InterpError> {
+    let mut acc = RValue::char(vec![Some(String::new())]);
+    for a in args {
+        let piece = RValue::char(
+            a.data()?
+                .as_char()
+                .into_iter()
+                .map(|s| Some(s.unwrap_or_else(|| "NA".to_string())))
+                .collect(),
+        );
+        acc = value::binary(BinOp::Add, &acc, &piece)?;
+    }
+    Ok(acc)
+}
+
+fn assign_slice(args: &[RValue]) -> Result<RValue, InterpError> {
+    let dest = arg("rr_assign_slice", args, 0)?;
+    let start = arg("rr_assign_slice", args, 1)?.index_scalar("slice start")?;
+    let end_raw = arg("rr_assign_slice", args, 2)?.data()?.as_double();
+    let Some(Some(end)) = end_raw.first().copied() else {
+        return Err(InterpError::runtime("slice end is NA"));
+    };
+    let values = arg("rr_assign_slice", args, 3)?;
+    if end < start as f64 {
+        return Ok(dest.clone());
+    }
+    let end = end as usize;
+    if end > dest.len() {
+        return Err(InterpError::runtime(format!(
+            "slice end out of bounds: {end} > {}",
+            dest.len()
+        )));
+    }
+    let expected = end - start + 1;
+    if values.len() != expected {
+        return Err(InterpError::runtime(format!(
+            "slice length mismatch ({} vs {expected})",
+            values.len()
+        )));
+    }
+    let mut out = dest.clone();
+    for (offset, pos) in (start..=end).enumerate() {
+        out.store1(pos, &values.element(offset)?)?;
+    }
+    Ok(out)
+}
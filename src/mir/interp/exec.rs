@@ -0,0 +1,571 @@
+use super::InterpError;
+use super::builtins;
+use super::value::{self, RValue, RVector};
+use crate::mir::*;
+use crate::typeck::ShapeTy;
+use rustc_hash::FxHashMap;
+
+const DEFAULT_FUEL: usize = 200_000;
+const DEFAULT_MAX_CALL_DEPTH: usize = 32;
+const MAX_VALUE_DEPTH: usize = 4096;
+
+/// Executes `FnIR` bodies.
+///
+/// Values are evaluated at their use site against the current variable
+/// environment, which mirrors how codegen materializes them. Phi values are
+/// bound when control enters their owning block, reading the argument that
+/// belongs to the incoming edge.
+pub struct Interpreter<'a> {
+    functions: Option<&'a FxHashMap<String, FnIR>>,
+    fuel: usize,
+    max_call_depth: usize,
+}
+
+struct Frame<'f> {
+    fn_ir: &'f FnIR,
+    args: &'f [RValue],
+    vars: FxHashMap<VarId, RValue>,
+    phis: FxHashMap<ValueId, RValue>,
+    phis_by_block: FxHashMap<BlockId, Vec<ValueId>>,
+    /// Write count per variable, bumped by assignments and stores.
+    versions: FxHashMap<VarId, u64>,
+    /// Variable (and its version) each assigned value was last bound to.
+    bindings: FxHashMap<ValueId, (VarId, u64)>,
+}
+
+type Cache = FxHashMap<ValueId, RValue>;
+
+impl Default for Interpreter<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> Interpreter<'a> {
+    pub fn new() -> Self {
+        Self {
+            functions: None,
+            fuel: DEFAULT_FUEL,
+            max_call_depth: DEFAULT_MAX_CALL_DEPTH,
+        }
+    }
+
+    /// Resolve calls to user functions against `functions` instead of
+    /// treating them as unsupported.
+    pub fn with_functions(mut self, functions: &'a FxHashMap<String, FnIR>) -> Self {
+        self.functions = Some(functions);
+        self
+    }
+
+    /// Maximum number of blocks entered per top-level run.
+    pub fn with_fuel(mut self, fuel: usize) -> Self {
+        self.fuel = fuel;
+        self
+    }
+
+    pub fn run(&self, fn_ir: &FnIR, args: &[RValue]) -> Result<RValue, InterpError> {
+        let mut fuel = self.fuel;
+        self.run_frame(fn_ir, args, &mut fuel, 0)
+    }
+
+    fn run_frame(
+        &self,
+        fn_ir: &FnIR,
+        args: &[RValue],
+        fuel: &mut usize,
+        depth: usize,
+    ) -> Result<RValue, InterpError> {
+        if depth > self.max_call_depth {
+            return Err(InterpError::unsupported("call depth limit"));
+        }
+        if fn_ir.unsupported_dynamic || fn_ir.opaque_interop {
+            return Err(InterpError::unsupported("dynamic or opaque function"));
+        }
+        if args.len() != fn_ir.params.len() {
+            return Err(InterpError::runtime(format!(
+                "{} expects {} argument(s), got {}",
+                fn_ir.name,
+                fn_ir.params.len(),
+                args.len()
+            )));
+        }
+        let mut frame = Frame {
+            fn_ir,
+            args,
+            vars: fn_ir
+                .params
+                .iter()
+                .cloned()
+                .zip(args.iter().cloned())
+                .collect(),
+            phis: FxHashMap::default(),
+            phis_by_block: phis_by_block(fn_ir),
+            versions: FxHashMap::default(),
+            bindings: FxHashMap::default(),
+        };
+
+        let mut bid = fn_ir.entry;
+        let mut pred: Option<BlockId> = None;
+        loop {
+            if *fuel == 0 {
+                return Err(InterpError::OutOfFuel);
+            }
+            *fuel -= 1;
+            let block = fn_ir
+                .blocks
+                .get(bid)
+                .ok_or_else(|| InterpError::malformed(format!("missing block {bid}")))?;
+            if let Some(pred) = pred {
+                self.enter_block(&mut frame, pred, bid, fuel, depth)?;
+            }
+            for instr in &block.instrs {
+                self.exec_instr(&mut frame, instr, fuel, depth)?;
+            }
+            match &block.term {
+                Terminator::Goto(target) => {
+                    pred = Some(bid);
+                    bid = *target;
+                }
+                Terminator::If {
+                    cond,
+                    then_bb,
+                    else_bb,
+                } => {
+                    let cond = self.eval_root(&frame, *cond, fuel, depth)?;
+                    pred = Some(bid);
+                    bid = if cond.truthy()? { *then_bb } else { *else_bb };
+                }
+                Terminator::Return(Some(val)) => {
+                    return self.eval_root(&frame, *val, fuel, depth);
+                }
+                Terminator::Return(None) => return Ok(RValue::Null),
+                Terminator::Unreachable => {
+                    return Err(InterpError::runtime(format!(
+                        "reached unreachable block {bid}"
+                    )));
+                }
+            }
+        }
+    }
+
+    fn enter_block(
+        &self,
+        frame: &mut Frame<'_>,
+        pred: BlockId,
+        bid: BlockId,
+        fuel: &mut usize,
+        depth: usize,
+    ) -> Result<(), InterpError> {
+        let Some(phis) = frame.phis_by_block.get(&bid) else {
+            return Ok(());
+        };
+        // Phi arguments are read in parallel on the incoming edge.
+        let mut cache = Cache::default();
+        let mut bound = Vec::with_capacity(phis.len());
+        for phi in phis {
+            let ValueKind::Phi { args } = &frame.fn_ir.values[*phi].kind else {
+                continue;
+            };
+            let Some((arg, _)) = args.iter().find(|(_, from)| *from == pred) else {
+                return Err(InterpError::malformed(format!(
+                    "phi {phi} in block {bid} has no argument for predecessor {pred}"
+                )));
+            };
+            bound.push((*phi, self.eval(frame, *arg, &mut cache, fuel, depth, 0)?));
+        }
+        frame.phis.extend(bound);
+        Ok(())
+    }
+
+    fn exec_instr(
+        &self,
+        frame: &mut Frame<'_>,
+        instr: &Instr,
+        fuel: &mut usize,
+        depth: usize,
+    ) -> Result<(), InterpError> {
+        match instr {
+            Instr::Assign { dst, src, .. } => {
+                let value = self.compute(frame, *src, &mut Cache::default(), fuel, depth, 0)?;
+                frame.vars.insert(dst.clone(), value);
+                let version = frame.note_write(dst);
+                frame.bindings.insert(*src, (dst.clone(), version));
+            }
+            Instr::Eval { val, .. } => {
+                self.eval_root(frame, *val, fuel, depth)?;
+            }
+            Instr::StoreIndex1D { base, idx, val, .. } => {
+                let var = store_target(frame, *base)?;
+                let mut cache = Cache::default();
+                let mut dest = self.store_dest(frame, &var, *base, &mut cache, fuel, depth)?;
+                let idx = self.eval(frame, *idx, &mut cache, fuel, depth, 0)?;
+                let val = self.eval(frame, *val, &mut cache, fuel, depth, 0)?;
+                store_positions(&mut dest, &idx, &val)?;
+                frame.note_write(&var);
+                frame.vars.insert(var, dest);
+            }
+            Instr::StoreIndex2D {
+                base, r, c, val, ..
+            } => {
+                self.store_nd(frame, *base, &[*r, *c], *val, fuel, depth)?;
+            }
+            Instr::StoreIndex3D {
+                base, i, j, k, val, ..
+            } => {
+                self.store_nd(frame, *base, &[*i, *j, *k], *val, fuel, depth)?;
+            }
+            Instr::UnsafeRBlock { .. } => {
+                return Err(InterpError::unsupported("unsafe R block"));
+            }
+        }
+        Ok(())
+    }
+
+    fn store_nd(
+        &self,
+        frame: &mut Frame<'_>,
+        base: ValueId,
+        subs: &[ValueId],
+        val: ValueId,
+        fuel: &mut usize,
+        depth: usize,
+    ) -> Result<(), InterpError> {
+        let var = store_target(frame, base)?;
+        let mut cache = Cache::default();
+        let mut dest = self.store_dest(frame, &var, base, &mut cache, fuel, depth)?;
+        let mut positions = Vec::with_capacity(subs.len());
+        for sub in subs {
+            positions.push(
+                self.eval(frame, *sub, &mut cache, fuel, depth, 0)?
+                    .index_scalar("index")?,
+            );
+        }
+        let val = self.eval(frame, val, &mut cache, fuel, depth, 0)?;
+        dest.store_nd(&positions, &val)?;
+        frame.note_write(&var);
+        frame.vars.insert(var, dest);
+        Ok(())
+    }
+
+    /// Current contents of the store target. The base value names the
+    /// variable being updated, so its live binding wins over re-evaluating
+    /// the defining expression.
+    fn store_dest(
+        &self,
+        frame: &Frame<'_>,
+        var: &str,
+        base: ValueId,
+        cache: &mut Cache,
+        fuel: &mut usize,
+        depth: usize,
+    ) -> Result<RValue, InterpError> {
+        match frame.vars.get(var) {
+            Some(current) => Ok(current.clone()),
+            None => self.eval(frame, base, cache, fuel, depth, 0),
+        }
+    }
+
+    fn eval_root(
+        &self,
+        frame: &Frame<'_>,
+        vid: ValueId,
+        fuel: &mut usize,
+        depth: usize,
+    ) -> Result<RValue, InterpError> {
+        self.eval(frame, vid, &mut Cache::default(), fuel, depth, 0)
+    }
+
+    fn eval(
+        &self,
+        frame: &Frame<'_>,
+        vid: ValueId,
+        cache: &mut Cache,
+        fuel: &mut usize,
+        depth: usize,
+        nesting: usize,
+    ) -> Result<RValue, InterpError> {
+        if let Some(hit) = cache.get(&vid) {
+            return Ok(hit.clone());
+        }
+        if let Some(value) = frame.fn_ir.values.get(vid)
+            && let Some(var) = frame.stale_var(vid, value).or_else(|| frame.bound_var(vid))
+        {
+            return Ok(frame.vars[var].clone());
+        }
+        self.compute(frame, vid, cache, fuel, depth, nesting)
+    }
+
+    /// Evaluate `vid` from its defining expression. Assignment right-hand
+    /// sides go through here directly, since codegen always emits them as
+    /// expressions rather than as a read of an earlier binding.
+    fn compute(
+        &self,
+        frame: &Frame<'_>,
+        vid: ValueId,
+        cache: &mut Cache,
+        fuel: &mut usize,
+        depth: usize,
+        nesting: usize,
+    ) -> Result<RValue, InterpError> {
+        if nesting > MAX_VALUE_DEPTH {
+            return Err(InterpError::malformed(format!(
+                "value {vid} is part of a non-phi cycle"
+            )));
+        }
+        let value = frame
+            .fn_ir
+            .values
+            .get(vid)
+            .ok_or_else(|| InterpError::malformed(format!("missing value {vid}")))?;
+        let sub = |id: ValueId, cache: &mut Cache, fuel: &mut usize| {
+            self.eval(frame, id, cache, fuel, depth, nesting + 1)
+        };
+        let out = match &value.kind {
+            ValueKind::Const(lit) => RValue::from_lit(lit),
+            // A phi that has not been bound on an edge is read through its
+            // origin variable, the same name codegen materializes it as.
+            ValueKind::Phi { .. } => match (frame.phis.get(&vid), &value.origin_var) {
+                (Some(bound), _) => bound.clone(),
+                (None, Some(var)) if frame.vars.contains_key(var) => frame.vars[var].clone(),
+                _ => {
+                    return Err(InterpError::malformed(format!(
+                        "phi {vid} read before its block was entered"
+                    )));
+                }
+            },
+            ValueKind::Param { index } => frame.args.get(*index).cloned().ok_or_else(|| {
+                InterpError::malformed(format!("parameter index {index} out of range"))
+            })?,
+            ValueKind::Load { var } => frame
+                .vars
+                .get(var)
+                .cloned()
+                .ok_or_else(|| InterpError::unsupported(format!("unbound variable '{var}'")))?,
+            ValueKind::RSymbol { name } => {
+                return Err(InterpError::unsupported(format!("raw symbol '{name}'")));
+            }
+            ValueKind::Len { base } => RValue::scalar_int(sub(*base, cache, fuel)?.len() as i64),
+            ValueKind::Indices { base } => {
+                let n = sub(*base, cache, fuel)?.len() as i64;
+                RValue::int((0..n).map(Some).collect())
+            }
+            ValueKind::Range { start, end } => {
+                let start = sub(*start, cache, fuel)?;
+                let end = sub(*end, cache, fuel)?;
+                builtins::colon(&start, &end)?
+            }
+            ValueKind::Binary { op, lhs, rhs } => {
+                let lhs = sub(*lhs, cache, fuel)?;
+                let rhs = sub(*rhs, cache, fuel)?;
+                value::binary(*op, &lhs, &rhs)?
+            }
+            ValueKind::Unary { op, rhs } => value::unary(*op, &sub(*rhs, cache, fuel)?)?,
+            ValueKind::Call {
+                callee,
+                args,
+                names,
+            } => {
+                let mut evaluated = Vec::with_capacity(args.len());
+                for arg in args {
+                    evaluated.push(sub(*arg, cache, fuel)?);
+                }
+                self.call(callee, &evaluated, names, fuel, depth)?
+            }
+            ValueKind::Intrinsic { op, args } => {
+                let mut evaluated = Vec::with_capacity(args.len());
+                for arg in args {
+                    evaluated.push(sub(*arg, cache, fuel)?);
+                }
+                builtins::intrinsic(*op, &evaluated)?
+            }
+            ValueKind::RecordLit { fields } => {
+                let mut items = Vec::with_capacity(fields.len());
+                for (name, field) in fields {
+                    items.push((Some(name.clone()), sub(*field, cache, fuel)?));
+                }
+                RValue::List(items)
+            }
+            ValueKind::FieldGet { base, field } => sub(*base, cache, fuel)?.field(field)?,
+            ValueKind::FieldSet { base, field, value } => {
+                let base = sub(*base, cache, fuel)?;
+                let value = sub(*value, cache, fuel)?;
+                base.with_field(field, value)?
+            }
+            ValueKind::Index1D { base, idx, .. } => {
+                let base = sub(*base, cache, fuel)?;
+                let idx = sub(*idx, cache, fuel)?;
+                builtins::index1_read(&base, &idx)?
+            }
+            ValueKind::Index2D { base, r, c } => {
+                let base = sub(*base, cache, fuel)?;
+                let r = sub(*r, cache, fuel)?.index_scalar("row index")?;
+                let c = sub(*c, cache, fuel)?.index_scalar("column index")?;
+                base.index_nd(&[r, c])?
+            }
+            ValueKind::Index3D { base, i, j, k } => {
+                let base = sub(*base, cache, fuel)?;
+                let i = sub(*i, cache, fuel)?.index_scalar("index")?;
+                let j = sub(*j, cache, fuel)?.index_scalar("index")?;
+                let k = sub(*k, cache, fuel)?.index_scalar("index")?;
+                base.index_nd(&[i, j, k])?
+            }
+        };
+        cache.insert(vid, out.clone());
+        Ok(out)
+    }
+
+    fn call(
+        &self,
+        callee: &str,
+        args: &[RValue],
+        names: &[Option<String>],
+        fuel: &mut usize,
+        depth: usize,
+    ) -> Result<RValue, InterpError> {
+        if let Some(target) = self.functions.and_then(|fns| fns.get(callee)) {
+            if names.iter().any(Option::is_some) {
+                return Err(InterpError::unsupported("named arguments to user function"));
+            }
+            return self.run_frame(target, args, fuel, depth + 1);
+        }
+        builtins::call(callee, args, names)
+    }
+}
+
+impl Frame<'_> {
+    fn note_write(&mut self, var: &str) -> u64 {
+        let version = self.versions.entry(var.to_string()).or_default();
+        *version += 1;
+        *version
+    }
+
+    fn version(&self, var: &str) -> u64 {
+        self.versions.get(var).copied().unwrap_or(0)
+    }
+
+    /// The variable `vid` was assigned to, while that assignment is still the
+    /// variable's latest write. Mirrors `resolve_bound_value`.
+    fn bound_var(&self, vid: ValueId) -> Option<&VarId> {
+        let (var, version) = self.bindings.get(&vid)?;
+        (*version == self.version(var) && self.vars.contains_key(var)).then_some(var)
+    }
+
+    /// The variable codegen reads instead of re-emitting an aggregate value:
+    /// either the variable the value was assigned to, once that variable has
+    /// been written again, or the origin variable of a fresh allocation that
+    /// has already been initialized. Mirrors `resolve_stale_origin_var`.
+    fn stale_var<'v>(&'v self, vid: ValueId, value: &'v Value) -> Option<&'v VarId> {
+        let aggregate = !matches!(value.value_ty.shape, ShapeTy::Scalar);
+        let self_update =
+            matches!(&value.kind, ValueKind::Call { callee, .. } if callee == "rr_assign_slice");
+        if !aggregate || self_update {
+            return None;
+        }
+        if let Some((var, version)) = self.bindings.get(&vid)
+            && *version != self.version(var)
+            && self.vars.contains_key(var)
+        {
+            return Some(var);
+        }
+        let origin = value.origin_var.as_ref()?;
+        let fresh = matches!(
+            &value.kind,
+            ValueKind::Call { callee, .. } if is_fresh_allocation(callee)
+        );
+        (fresh && self.version(origin) > 0 && self.vars.contains_key(origin)).then_some(origin)
+    }
+}
+
+/// Allocation calls codegen treats as known-fresh aggregates.
+fn is_fresh_allocation(callee: &str) -> bool {
+    matches!(
+        callee,
+        "rep.int" | "numeric" | "vector" | "matrix" | "seq_len" | "seq_along"
+    )
+}
+
+fn phis_by_block(fn_ir: &FnIR) -> FxHashMap<BlockId, Vec<ValueId>> {
+    let mut preds: FxHashMap<BlockId, Vec<BlockId>> = FxHashMap::default();
+    for block in &fn_ir.blocks {
+        let succs = match &block.term {
+            Terminator::Goto(target) => vec![*target],
+            Terminator::If {
+                then_bb, else_bb, ..
+            } => vec![*then_bb, *else_bb],
+            Terminator::Return(_) | Terminator::Unreachable => Vec::new(),
+        };
+        for succ in succs {
+            preds.entry(succ).or_default().push(block.id);
+        }
+    }
+    let mut out: FxHashMap<BlockId, Vec<ValueId>> = FxHashMap::default();
+    for value in &fn_ir.values {
+        let ValueKind::Phi { args } = &value.kind else {
+            continue;
+        };
+        // Phis copied by a pass may lose their owner; recover it from the
+        // unique block whose predecessors match the incoming edges.
+        let owner = value.phi_block.or_else(|| {
+            let mut candidates = preds.iter().filter(|(_, ps)| {
+                !args.is_empty() && args.iter().all(|(_, from)| ps.contains(from))
+            });
+            match (candidates.next(), candidates.next()) {
+                (Some((bid, _)), None) => Some(*bid),
+                _ => None,
+            }
+        });
+        if let Some(bid) = owner {
+            out.entry(bid).or_default().push(value.id);
+        }
+    }
+    out
+}
+
+/// The variable a store writes back to, following the same resolution as
+/// codegen's mutable-base lookup.
+fn store_target(frame: &Frame<'_>, base: ValueId) -> Result<VarId, InterpError> {
+    let value = &frame.fn_ir.values[base];
+    if let Some(var) = &value.origin_var {
+        return Ok(var.clone());
+    }
+    match &value.kind {
+        ValueKind::Load { var } => Ok(var.clone()),
+        ValueKind::Param { index } => frame
+            .fn_ir
+            .params
+            .get(*index)
+            .cloned()
+            .ok_or_else(|| InterpError::malformed("store to missing parameter")),
+        _ => Err(InterpError::unsupported("store to an unnamed base")),
+    }
+}
+
+/// `dest[idx] <- val` for one or more positions with R recycling of `val`.
+fn store_positions(dest: &mut RValue, idx: &RValue, val: &RValue) -> Result<(), InterpError> {
+    let positions = idx.data()?;
+    if matches!(positions, RVector::Logical(_) | RVector::Char(_)) {
+        return Err(InterpError::unsupported("non-numeric store index"));
+    }
+    let positions = positions.as_double();
+    if positions.is_empty() {
+        return Ok(());
+    }
+    if val.is_empty() {
+        return Err(InterpError::runtime("replacement has length zero"));
+    }
+    for (n, pos) in positions.iter().enumerate() {
+        let Some(pos) = pos else {
+            if val.len() > 1 {
+                return Err(InterpError::runtime(
+                    "NAs are not allowed in subscripted assignments",
+                ));
+            }
+            continue;
+        };
+        if *pos < 1.0 {
+            return Err(InterpError::runtime("index must be >= 1"));
+        }
+        dest.store1(pos.trunc() as usize, &val.element(n % val.len())?)?;
+    }
+    Ok(())
+}
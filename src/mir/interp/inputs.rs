@@ -0,0 +1,118 @@
+use super::value::RValue;
+use crate::mir::FnIR;
+use crate::typeck::{LenSym, NaTy, PrimTy, ShapeTy, TypeState};
+use rustc_hash::FxHashMap;
+
+/// Deterministically generate `cases` argument lists for `fn_ir`.
+///
+/// Inputs follow the parameter type hints: scalars stay scalar, vector
+/// parameters that share a length symbol get the same length, and NA only
+/// appears for parameters whose hint allows it. Values are small and mostly
+/// positive so that they double as loop bounds and 1-based indices.
+pub fn generate_inputs(fn_ir: &FnIR, cases: usize) -> Vec<Vec<RValue>> {
+    (0..cases)
+        .map(|case| {
+            let mut rng = SplitMix64::new(0x5EED_0000 ^ case as u64);
+            let mut lens: FxHashMap<LenSym, usize> = FxHashMap::default();
+            let base_len = 1 + case % 5;
+            fn_ir
+                .params
+                .iter()
+                .enumerate()
+                .map(|(i, _)| {
+                    let ty = fn_ir
+                        .param_ty_hints
+                        .get(i)
+                        .copied()
+                        .unwrap_or(TypeState::unknown());
+                    let len = match ty.len_sym {
+                        Some(sym) => *lens.entry(sym).or_insert(base_len),
+                        None => base_len + rng.below(3) as usize,
+                    };
+                    gen_value(&mut rng, ty, case, len)
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn gen_value(rng: &mut SplitMix64, ty: TypeState, case: usize, len: usize) -> RValue {
+    let shape = match ty.shape {
+        ShapeTy::Unknown if case % 4 != 1 => ShapeTy::Scalar,
+        ShapeTy::Unknown => ShapeTy::Vector,
+        shape => shape,
+    };
+    let (len, dim) = match shape {
+        ShapeTy::Scalar => (1, None),
+        ShapeTy::Matrix => {
+            let (r, c) = (1 + case % 3, 1 + (case / 3) % 3);
+            (r * c, Some(vec![r, c]))
+        }
+        _ => (len, None),
+    };
+    // Only every fourth case exercises NA so most cases check the common path.
+    let allow_na = ty.na == NaTy::Maybe && case % 4 == 3;
+    let na = |rng: &mut SplitMix64| allow_na && rng.below(4) == 0;
+    let value = match ty.prim {
+        PrimTy::Null => return RValue::Null,
+        PrimTy::Logical => RValue::logical(
+            (0..len)
+                .map(|_| (!na(rng)).then(|| rng.below(2) == 1))
+                .collect(),
+        ),
+        PrimTy::Int => RValue::int(
+            (0..len)
+                .map(|_| (!na(rng)).then(|| 1 + rng.below(6) as i64))
+                .collect(),
+        ),
+        PrimTy::Char => RValue::char(
+            (0..len)
+                .map(|_| {
+                    (!na(rng)).then(|| ["a", "b", "rr", ""][rng.below(4) as usize].to_string())
+                })
+                .collect(),
+        ),
+        // Whole-valued doubles dominate since untyped parameters are often
+        // counts; every third case mixes in fractional and negative values.
+        PrimTy::Double | PrimTy::Any => RValue::double(
+            (0..len)
+                .map(|_| {
+                    (!na(rng)).then(|| {
+                        if case % 3 == 2 {
+                            rng.below(13) as f64 * 0.5 - 1.0
+                        } else {
+                            rng.below(8) as f64
+                        }
+                    })
+                })
+                .collect(),
+        ),
+    };
+    match (value, dim) {
+        (RValue::Vector { data, .. }, Some(dim)) => RValue::Vector {
+            data,
+            dim: Some(dim),
+        },
+        (value, _) => value,
+    }
+}
+
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    fn below(&mut self, n: u64) -> u64 {
+        self.next() % n
+    }
+}
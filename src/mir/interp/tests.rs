@@ -0,0 +1,231 @@
+use super::*;
+use crate::mir::flow::Facts;
+use crate::mir::*;
+use crate::syntax::ast::{BinOp, Lit};
+use crate::utils::Span;
+
+fn push_value(fn_ir: &mut FnIR, kind: ValueKind) -> ValueId {
+    fn_ir.add_value(kind, Span::default(), Facts::empty(), None)
+}
+
+fn konst(fn_ir: &mut FnIR, lit: Lit) -> ValueId {
+    push_value(fn_ir, ValueKind::Const(lit))
+}
+
+fn binary(fn_ir: &mut FnIR, op: BinOp, lhs: ValueId, rhs: ValueId) -> ValueId {
+    push_value(fn_ir, ValueKind::Binary { op, lhs, rhs })
+}
+
+fn phi(fn_ir: &mut FnIR, block: BlockId) -> ValueId {
+    let id = push_value(fn_ir, ValueKind::Phi { args: Vec::new() });
+    fn_ir.values[id].phi_block = Some(block);
+    id
+}
+
+fn set_phi_args(fn_ir: &mut FnIR, phi: ValueId, args: Vec<(ValueId, BlockId)>) {
+    fn_ir.values[phi].kind = ValueKind::Phi { args };
+}
+
+fn doubles(values: &[Option<f64>]) -> RValue {
+    RValue::double(values.to_vec())
+}
+
+/// `fn add_const(x) { return x + k }`
+fn add_const(k: f64) -> FnIR {
+    let mut fn_ir = FnIR::new("add_const".to_string(), vec!["x".to_string()]);
+    let entry = fn_ir.add_block();
+    fn_ir.entry = entry;
+    fn_ir.body_head = entry;
+    let x = push_value(&mut fn_ir, ValueKind::Param { index: 0 });
+    let k = konst(&mut fn_ir, Lit::Float(k));
+    let sum = binary(&mut fn_ir, BinOp::Add, x, k);
+    fn_ir.blocks[entry].term = Terminator::Return(Some(sum));
+    fn_ir
+}
+
+/// Sums `x[i]` for `i` in `1..=length(x)` through a phi-carried loop.
+fn loop_sum() -> FnIR {
+    let mut fn_ir = FnIR::new("loop_sum".to_string(), vec!["x".to_string()]);
+    let entry = fn_ir.add_block();
+    let header = fn_ir.add_block();
+    let body = fn_ir.add_block();
+    let exit = fn_ir.add_block();
+    fn_ir.entry = entry;
+    fn_ir.body_head = entry;
+
+    let x = push_value(&mut fn_ir, ValueKind::Param { index: 0 });
+    let one = konst(&mut fn_ir, Lit::Int(1));
+    let zero = konst(&mut fn_ir, Lit::Float(0.0));
+    let i = phi(&mut fn_ir, header);
+    let acc = phi(&mut fn_ir, header);
+    let len = push_value(&mut fn_ir, ValueKind::Len { base: x });
+    let cond = binary(&mut fn_ir, BinOp::Le, i, len);
+    let elem = push_value(
+        &mut fn_ir,
+        ValueKind::Index1D {
+            base: x,
+            idx: i,
+            is_safe: false,
+            is_na_safe: false,
+        },
+    );
+    let next_acc = binary(&mut fn_ir, BinOp::Add, acc, elem);
+    let next_i = binary(&mut fn_ir, BinOp::Add, i, one);
+    set_phi_args(&mut fn_ir, i, vec![(one, entry), (next_i, body)]);
+    set_phi_args(&mut fn_ir, acc, vec![(zero, entry), (next_acc, body)]);
+
+    fn_ir.blocks[entry].term = Terminator::Goto(header);
+    fn_ir.blocks[header].term = Terminator::If {
+        cond,
+        then_bb: body,
+        else_bb: exit,
+    };
+    fn_ir.blocks[body].term = Terminator::Goto(header);
+    fn_ir.blocks[exit].term = Terminator::Return(Some(acc));
+    fn_ir
+}
+
+#[test]
+fn phi_loop_follows_incoming_edges_and_propagates_na() {
+    let fn_ir = loop_sum();
+    let interp = Interpreter::new();
+    let out = interp
+        .run(&fn_ir, &[doubles(&[Some(1.0), Some(2.0), Some(3.5)])])
+        .expect("loop should run");
+    assert_eq!(out, RValue::scalar_double(6.5));
+
+    let out = interp
+        .run(&fn_ir, &[doubles(&[Some(1.0), None])])
+        .expect("NA element should propagate, not fail");
+    assert_eq!(out, doubles(&[None]));
+
+    let out = interp.run(&fn_ir, &[RValue::double(Vec::new())]).unwrap();
+    assert_eq!(out, RValue::scalar_double(0.0));
+}
+
+#[test]
+fn binary_ops_recycle_and_integer_overflow_is_na() {
+    let lhs = doubles(&[Some(1.0), Some(2.0), Some(3.0), Some(4.0)]);
+    let rhs = doubles(&[Some(10.0), Some(20.0)]);
+    let out = value::binary(BinOp::Add, &lhs, &rhs).unwrap();
+    assert_eq!(
+        out,
+        doubles(&[Some(11.0), Some(22.0), Some(13.0), Some(24.0)])
+    );
+
+    let big = RValue::scalar_int(i32::MAX as i64);
+    let out = value::binary(BinOp::Add, &big, &RValue::scalar_int(1)).unwrap();
+    assert_eq!(out, RValue::int(vec![None]));
+
+    let out = value::binary(BinOp::Mod, &RValue::scalar_int(-7), &RValue::scalar_int(3)).unwrap();
+    assert_eq!(out, RValue::scalar_int(2));
+
+    let na_and_false = value::binary(
+        BinOp::And,
+        &RValue::logical(vec![None]),
+        &RValue::scalar_bool(false),
+    )
+    .unwrap();
+    assert_eq!(na_and_false, RValue::scalar_bool(false));
+}
+
+#[test]
+fn stores_are_one_based_and_extend_with_na() {
+    let mut fn_ir = FnIR::new("store".to_string(), vec![]);
+    let entry = fn_ir.add_block();
+    fn_ir.entry = entry;
+    fn_ir.body_head = entry;
+    let two = konst(&mut fn_ir, Lit::Int(2));
+    let alloc = push_value(
+        &mut fn_ir,
+        ValueKind::Call {
+            callee: "numeric".to_string(),
+            args: vec![two],
+            names: vec![None],
+        },
+    );
+    fn_ir.values[alloc].origin_var = Some("x".to_string());
+    let four = konst(&mut fn_ir, Lit::Int(4));
+    let val = konst(&mut fn_ir, Lit::Float(7.0));
+    let load = push_value(
+        &mut fn_ir,
+        ValueKind::Load {
+            var: "x".to_string(),
+        },
+    );
+    fn_ir.blocks[entry].instrs.push(Instr::Assign {
+        dst: "x".to_string(),
+        src: alloc,
+        span: Span::default(),
+    });
+    fn_ir.blocks[entry].instrs.push(Instr::StoreIndex1D {
+        base: alloc,
+        idx: four,
+        val,
+        is_safe: false,
+        is_na_safe: false,
+        is_vector: false,
+        span: Span::default(),
+    });
+    fn_ir.blocks[entry].term = Terminator::Return(Some(load));
+
+    let out = Interpreter::new().run(&fn_ir, &[]).unwrap();
+    assert_eq!(out, doubles(&[Some(0.0), Some(0.0), None, Some(7.0)]));
+}
+
+#[test]
+fn na_condition_is_a_runtime_error() {
+    let mut fn_ir = FnIR::new("na_cond".to_string(), vec![]);
+    let entry = fn_ir.add_block();
+    let done = fn_ir.add_block();
+    fn_ir.entry = entry;
+    fn_ir.body_head = entry;
+    let na = konst(&mut fn_ir, Lit::Na);
+    fn_ir.blocks[entry].term = Terminator::If {
+        cond: na,
+        then_bb: done,
+        else_bb: done,
+    };
+    fn_ir.blocks[done].term = Terminator::Return(None);
+
+    let err = Interpreter::new().run(&fn_ir, &[]).unwrap_err();
+    assert!(matches!(err, InterpError::Runtime(_)), "{err}");
+}
+
+#[test]
+fn translation_validation_reports_first_diverging_case() {
+    let before = add_const(1.0);
+    let inputs = generate_inputs(&before, 6);
+    assert_eq!(inputs.len(), 6);
+    let interp = Interpreter::new();
+
+    assert!(validate_translation(&interp, &before, &add_const(1.0), &inputs).is_none());
+
+    let divergence = validate_translation(&interp, &before, &add_const(2.0), &inputs)
+        .expect("changing the constant must be observable");
+    assert_eq!(divergence.case, 0);
+    assert!(divergence.to_string().contains("before ="));
+}
+
+#[test]
+fn unsupported_calls_are_inconclusive() {
+    let mut fn_ir = FnIR::new("opaque".to_string(), vec![]);
+    let entry = fn_ir.add_block();
+    fn_ir.entry = entry;
+    fn_ir.body_head = entry;
+    let call = push_value(
+        &mut fn_ir,
+        ValueKind::Call {
+            callee: "Sys.time".to_string(),
+            args: vec![],
+            names: vec![],
+        },
+    );
+    fn_ir.blocks[entry].term = Terminator::Return(Some(call));
+
+    let err = Interpreter::new().run(&fn_ir, &[]).unwrap_err();
+    assert!(err.is_inconclusive());
+    assert!(
+        validate_translation(&Interpreter::new(), &fn_ir, &add_const(0.0), &[vec![]]).is_none()
+    );
+}
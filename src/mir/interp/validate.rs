@@ -0,0 +1,68 @@
+use super::{InterpError, Interpreter, RValue};
+use crate::mir::FnIR;
+use std::fmt;
+
+/// First input on which two versions of a function behave differently.
+#[derive(Debug, Clone)]
+pub struct Divergence {
+    pub case: usize,
+    pub inputs: Vec<RValue>,
+    pub before: Result<RValue, InterpError>,
+    pub after: Result<RValue, InterpError>,
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let show = |r: &Result<RValue, InterpError>| match r {
+            Ok(v) => v.to_string(),
+            Err(e) => e.to_string(),
+        };
+        let inputs: Vec<String> = self.inputs.iter().map(ToString::to_string).collect();
+        write!(
+            f,
+            "case {} ({}): before = {}, after = {}",
+            self.case,
+            inputs.join(", "),
+            show(&self.before),
+            show(&self.after)
+        )
+    }
+}
+
+/// Run `before` and `after` on every input and return the first case whose
+/// observable result differs.
+///
+/// Cases where either side hits an unsupported construct or runs out of fuel
+/// are skipped. A run that errors before the pass but succeeds after it is
+/// also accepted, since removing a dead failing computation is a legal
+/// refinement; the reverse, or two different successful results, is not.
+pub fn validate_translation(
+    interp: &Interpreter<'_>,
+    before: &FnIR,
+    after: &FnIR,
+    inputs: &[Vec<RValue>],
+) -> Option<Divergence> {
+    for (case, args) in inputs.iter().enumerate() {
+        let expected = interp.run(before, args);
+        if matches!(&expected, Err(e) if e.is_inconclusive() || matches!(e, InterpError::Malformed(_)))
+        {
+            continue;
+        }
+        let actual = interp.run(after, args);
+        let diverged = match (&expected, &actual) {
+            (_, Err(e)) if e.is_inconclusive() => false,
+            (Ok(a), Ok(b)) => !a.observably_equal(b),
+            (Ok(_), Err(_)) => true,
+            (Err(_), _) => false,
+        };
+        if diverged {
+            return Some(Divergence {
+                case,
+                inputs: args.clone(),
+                before: expected,
+                after: actual,
+            });
+        }
+    }
+    None
+}
@@ -0,0 +1,711 @@
+use super::InterpError;
+use crate::syntax::ast::{BinOp, Lit, UnaryOp};
+use std::fmt;
+
+/// Atomic vector payload with R's per-element NA encoded as `None`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RVector {
+    Logical(Vec<Option<bool>>),
+    Int(Vec<Option<i64>>),
+    Double(Vec<Option<f64>>),
+    Char(Vec<Option<String>>),
+}
+
+/// A runtime value as observed by generated R code.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RValue {
+    Null,
+    Vector {
+        data: RVector,
+        dim: Option<Vec<usize>>,
+    },
+    List(Vec<(Option<String>, RValue)>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Rank {
+    Logical,
+    Int,
+    Double,
+    Char,
+}
+
+impl RVector {
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Logical(v) => v.len(),
+            Self::Int(v) => v.len(),
+            Self::Double(v) => v.len(),
+            Self::Char(v) => v.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn rank(&self) -> Rank {
+        match self {
+            Self::Logical(_) => Rank::Logical,
+            Self::Int(_) => Rank::Int,
+            Self::Double(_) => Rank::Double,
+            Self::Char(_) => Rank::Char,
+        }
+    }
+
+    fn coerce(&self, rank: Rank) -> Self {
+        match rank {
+            Rank::Logical => Self::Logical(self.as_logical()),
+            Rank::Int => Self::Int(self.as_int()),
+            Rank::Double => Self::Double(self.as_double()),
+            Rank::Char => Self::Char(self.as_char()),
+        }
+    }
+
+    pub fn as_logical(&self) -> Vec<Option<bool>> {
+        match self {
+            Self::Logical(v) => v.clone(),
+            Self::Int(v) => v.iter().map(|x| x.map(|x| x != 0)).collect(),
+            Self::Double(v) => v
+                .iter()
+                .map(|x| x.filter(|x| !x.is_nan()).map(|x| x != 0.0))
+                .collect(),
+            Self::Char(v) => v
+                .iter()
+                .map(|x| match x.as_deref() {
+                    Some("TRUE" | "true" | "T" | "True") => Some(true),
+                    Some("FALSE" | "false" | "F" | "False") => Some(false),
+                    _ => None,
+                })
+                .collect(),
+        }
+    }
+
+    pub fn as_int(&self) -> Vec<Option<i64>> {
+        match self {
+            Self::Logical(v) => v.iter().map(|x| x.map(i64::from)).collect(),
+            Self::Int(v) => v.clone(),
+            Self::Double(v) => v
+                .iter()
+                .map(|x| x.filter(|x| x.is_finite()).map(|x| x.trunc() as i64))
+                .collect(),
+            Self::Char(v) => v
+                .iter()
+                .map(|x| x.as_deref().and_then(|s| s.trim().parse::<f64>().ok()))
+                .map(|x| x.filter(|x| x.is_finite()).map(|x| x.trunc() as i64))
+                .collect(),
+        }
+    }
+
+    pub fn as_double(&self) -> Vec<Option<f64>> {
+        match self {
+            Self::Logical(v) => v
+                .iter()
+                .map(|x| x.map(|b| f64::from(u8::from(b))))
+                .collect(),
+            Self::Int(v) => v.iter().map(|x| x.map(|x| x as f64)).collect(),
+            Self::Double(v) => v.clone(),
+            Self::Char(v) => v
+                .iter()
+                .map(|x| x.as_deref().and_then(|s| s.trim().parse::<f64>().ok()))
+                .collect(),
+        }
+    }
+
+    pub fn as_char(&self) -> Vec<Option<String>> {
+        match self {
+            Self::Logical(v) => v
+                .iter()
+                .map(|x| x.map(|b| if b { "TRUE" } else { "FALSE" }.to_string()))
+                .collect(),
+            Self::Int(v) => v.iter().map(|x| x.map(|x| x.to_string())).collect(),
+            Self::Double(v) => v.iter().map(|x| x.map(format_double)).collect(),
+            Self::Char(v) => v.clone(),
+        }
+    }
+
+    fn na_of_rank(rank: Rank, len: usize) -> Self {
+        match rank {
+            Rank::Logical => Self::Logical(vec![None; len]),
+            Rank::Int => Self::Int(vec![None; len]),
+            Rank::Double => Self::Double(vec![None; len]),
+            Rank::Char => Self::Char(vec![None; len]),
+        }
+    }
+
+    /// Element `i` (0-based) as a length-one vector of the same type.
+    fn element(&self, i: usize) -> Self {
+        match self {
+            Self::Logical(v) => Self::Logical(vec![v.get(i).copied().flatten()]),
+            Self::Int(v) => Self::Int(vec![v.get(i).copied().flatten()]),
+            Self::Double(v) => Self::Double(vec![v.get(i).copied().flatten()]),
+            Self::Char(v) => Self::Char(vec![v.get(i).cloned().flatten()]),
+        }
+    }
+
+    fn gather(&self, idx: &[Option<usize>]) -> Self {
+        match self {
+            Self::Logical(v) => Self::Logical(
+                idx.iter()
+                    .map(|i| i.and_then(|i| v.get(i).copied().flatten()))
+                    .collect(),
+            ),
+            Self::Int(v) => Self::Int(
+                idx.iter()
+                    .map(|i| i.and_then(|i| v.get(i).copied().flatten()))
+                    .collect(),
+            ),
+            Self::Double(v) => Self::Double(
+                idx.iter()
+                    .map(|i| i.and_then(|i| v.get(i).copied().flatten()))
+                    .collect(),
+            ),
+            Self::Char(v) => Self::Char(
+                idx.iter()
+                    .map(|i| i.and_then(|i| v.get(i).cloned().flatten()))
+                    .collect(),
+            ),
+        }
+    }
+
+    fn set(&mut self, i: usize, value: &Self) {
+        let len = self.len();
+        if i >= len {
+            self.extend_na(i + 1 - len);
+        }
+        match (self, value) {
+            (Self::Logical(v), Self::Logical(x)) => v[i] = x.first().copied().flatten(),
+            (Self::Int(v), Self::Int(x)) => v[i] = x.first().copied().flatten(),
+            (Self::Double(v), Self::Double(x)) => v[i] = x.first().copied().flatten(),
+            (Self::Char(v), Self::Char(x)) => v[i] = x.first().cloned().flatten(),
+            _ => unreachable!("set requires matching vector types"),
+        }
+    }
+
+    fn extend_na(&mut self, extra: usize) {
+        match self {
+            Self::Logical(v) => v.extend(std::iter::repeat_n(None, extra)),
+            Self::Int(v) => v.extend(std::iter::repeat_n(None, extra)),
+            Self::Double(v) => v.extend(std::iter::repeat_n(None, extra)),
+            Self::Char(v) => v.extend(std::iter::repeat_n(None, extra)),
+        }
+    }
+
+    pub fn concat(parts: &[RVector]) -> Self {
+        let rank = parts
+            .iter()
+            .map(RVector::rank)
+            .max()
+            .unwrap_or(Rank::Logical);
+        match rank {
+            Rank::Logical => Self::Logical(parts.iter().flat_map(|p| p.as_logical()).collect()),
+            Rank::Int => Self::Int(parts.iter().flat_map(|p| p.as_int()).collect()),
+            Rank::Double => Self::Double(parts.iter().flat_map(|p| p.as_double()).collect()),
+            Rank::Char => Self::Char(parts.iter().flat_map(|p| p.as_char()).collect()),
+        }
+    }
+}
+
+pub(crate) fn format_double(x: f64) -> String {
+    if x.is_nan() {
+        "NaN".to_string()
+    } else if x.is_infinite() {
+        if x > 0.0 { "Inf" } else { "-Inf" }.to_string()
+    } else if x == x.trunc() && x.abs() < 1e15 {
+        format!("{}", x as i64)
+    } else {
+        format!("{}", x)
+    }
+}
+
+impl RValue {
+    pub fn vector(data: RVector) -> Self {
+        Self::Vector { data, dim: None }
+    }
+
+    pub fn logical(values: Vec<Option<bool>>) -> Self {
+        Self::vector(RVector::Logical(values))
+    }
+
+    pub fn int(values: Vec<Option<i64>>) -> Self {
+        Self::vector(RVector::Int(values))
+    }
+
+    pub fn double(values: Vec<Option<f64>>) -> Self {
+        Self::vector(RVector::Double(values))
+    }
+
+    pub fn char(values: Vec<Option<String>>) -> Self {
+        Self::vector(RVector::Char(values))
+    }
+
+    pub fn scalar_int(x: i64) -> Self {
+        Self::int(vec![Some(x)])
+    }
+
+    pub fn scalar_double(x: f64) -> Self {
+        Self::double(vec![Some(x)])
+    }
+
+    pub fn scalar_bool(x: bool) -> Self {
+        Self::logical(vec![Some(x)])
+    }
+
+    pub fn from_lit(lit: &Lit) -> Self {
+        match lit {
+            Lit::Int(i) => Self::scalar_int(*i),
+            Lit::Float(f) => Self::scalar_double(*f),
+            Lit::Str(s) => Self::char(vec![Some(s.clone())]),
+            Lit::Bool(b) => Self::scalar_bool(*b),
+            Lit::Null => Self::Null,
+            Lit::Na => Self::logical(vec![None]),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Null => 0,
+            Self::Vector { data, .. } => data.len(),
+            Self::List(items) => items.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn data(&self) -> Result<&RVector, InterpError> {
+        match self {
+            Self::Vector { data, .. } => Ok(data),
+            Self::Null => Err(InterpError::runtime("argument is NULL")),
+            Self::List(_) => Err(InterpError::runtime(
+                "argument is a list, not an atomic vector",
+            )),
+        }
+    }
+
+    pub fn dim(&self) -> Option<&[usize]> {
+        match self {
+            Self::Vector { dim, .. } => dim.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// R truthiness for `if`: length-one, non-NA logical coercion.
+    pub fn truthy(&self) -> Result<bool, InterpError> {
+        let data = self.data()?;
+        if data.len() != 1 {
+            return Err(InterpError::runtime(format!(
+                "condition has length {}",
+                data.len()
+            )));
+        }
+        data.as_logical()[0]
+            .ok_or_else(|| InterpError::runtime("missing value where TRUE/FALSE needed"))
+    }
+
+    /// A scalar 1-based index, rejecting NA, zero and negative positions.
+    pub fn index_scalar(&self, what: &str) -> Result<usize, InterpError> {
+        let data = self.data()?;
+        if data.len() != 1 {
+            return Err(InterpError::runtime(format!("{what} must be scalar")));
+        }
+        let Some(raw) = data.as_double()[0] else {
+            return Err(InterpError::runtime(format!("{what} is NA")));
+        };
+        if raw.trunc() != raw {
+            return Err(InterpError::runtime(format!("{what} must be integer")));
+        }
+        if raw < 1.0 {
+            return Err(InterpError::runtime(format!("{what} must be >= 1")));
+        }
+        Ok(raw as usize)
+    }
+
+    /// `base[idx]` with 1-based indexing; out-of-range positions read NA.
+    pub fn index1(&self, idx: &RValue) -> Result<RValue, InterpError> {
+        if let Self::List(items) = self {
+            let i = idx.index_scalar("index")?;
+            return Ok(items
+                .get(i - 1)
+                .map(|(_, v)| v.clone())
+                .unwrap_or(RValue::Null));
+        }
+        let data = self.data()?;
+        let positions = idx.data()?.as_double();
+        let mut out = Vec::with_capacity(positions.len());
+        for pos in positions {
+            match pos {
+                None => out.push(None),
+                Some(p) if p < 1.0 => {
+                    return Err(InterpError::runtime("index must be >= 1"));
+                }
+                Some(p) => out.push(Some(p.trunc() as usize - 1)),
+            }
+        }
+        Ok(RValue::vector(data.gather(&out)))
+    }
+
+    /// Element `i` (0-based) of an atomic vector as a length-one value.
+    pub fn element(&self, i: usize) -> Result<RValue, InterpError> {
+        Ok(RValue::vector(self.data()?.element(i)))
+    }
+
+    pub fn index_nd(&self, subs: &[usize]) -> Result<RValue, InterpError> {
+        let offset = self.nd_offset(subs)?;
+        Ok(RValue::vector(self.data()?.element(offset)))
+    }
+
+    fn nd_offset(&self, subs: &[usize]) -> Result<usize, InterpError> {
+        let dims = self
+            .dim()
+            .ok_or_else(|| InterpError::runtime("incorrect number of dimensions"))?;
+        if dims.len() != subs.len() {
+            return Err(InterpError::runtime("incorrect number of dimensions"));
+        }
+        let mut offset = 0usize;
+        let mut stride = 1usize;
+        for (sub, extent) in subs.iter().zip(dims) {
+            if *sub == 0 || sub > extent {
+                return Err(InterpError::runtime("subscript out of bounds"));
+            }
+            offset += (sub - 1) * stride;
+            stride *= extent;
+        }
+        Ok(offset)
+    }
+
+    /// `base[i] <- value` for a scalar position, extending with NA like R.
+    pub fn store1(&mut self, i: usize, value: &RValue) -> Result<(), InterpError> {
+        if let Self::List(items) = self {
+            while items.len() < i {
+                items.push((None, RValue::Null));
+            }
+            items[i - 1].1 = value.clone();
+            return Ok(());
+        }
+        if matches!(self, Self::Null) {
+            *self = RValue::vector(RVector::na_of_rank(value.data()?.rank(), 0));
+        }
+        let Self::Vector { data, dim } = self else {
+            unreachable!("null and list handled above");
+        };
+        let value = value.data()?;
+        if value.len() != 1 {
+            return Err(InterpError::runtime(
+                "number of items to replace is not a multiple of replacement length",
+            ));
+        }
+        let rank = data.rank().max(value.rank());
+        if data.rank() != rank {
+            *data = data.coerce(rank);
+        }
+        if i > data.len() {
+            *dim = None;
+        }
+        data.set(i - 1, &value.coerce(rank));
+        Ok(())
+    }
+
+    pub fn store_nd(&mut self, subs: &[usize], value: &RValue) -> Result<(), InterpError> {
+        let offset = self.nd_offset(subs)?;
+        self.store1(offset + 1, value)
+    }
+
+    pub fn field(&self, name: &str) -> Result<RValue, InterpError> {
+        match self {
+            Self::List(items) => Ok(items
+                .iter()
+                .find(|(n, _)| n.as_deref() == Some(name))
+                .map(|(_, v)| v.clone())
+                .unwrap_or(RValue::Null)),
+            _ => Err(InterpError::runtime(
+                "$ operator is invalid for atomic vectors",
+            )),
+        }
+    }
+
+    pub fn with_field(&self, name: &str, value: RValue) -> Result<RValue, InterpError> {
+        let Self::List(items) = self else {
+            return Err(InterpError::runtime("field update on a non-record value"));
+        };
+        let mut items = items.clone();
+        if let Some(slot) = items.iter_mut().find(|(n, _)| n.as_deref() == Some(name)) {
+            slot.1 = value;
+        } else {
+            items.push((Some(name.to_string()), value));
+        }
+        Ok(RValue::List(items))
+    }
+
+    /// Observable equality for translation validation. Doubles compare with a
+    /// small relative tolerance so reassociation does not count as divergence.
+    pub fn observably_equal(&self, other: &RValue) -> bool {
+        match (self, other) {
+            (Self::Null, Self::Null) => true,
+            (Self::List(a), Self::List(b)) => {
+                a.len() == b.len()
+                    && a.iter()
+                        .zip(b)
+                        .all(|((na, va), (nb, vb))| na == nb && va.observably_equal(vb))
+            }
+            (Self::Vector { data: a, dim: da }, Self::Vector { data: b, dim: db }) => {
+                if a.len() != b.len() || da != db {
+                    return false;
+                }
+                match (a, b) {
+                    (RVector::Char(x), RVector::Char(y)) => x == y,
+                    (RVector::Char(_), _) | (_, RVector::Char(_)) => false,
+                    _ => a
+                        .as_double()
+                        .iter()
+                        .zip(b.as_double())
+                        .all(|(x, y)| doubles_match(*x, y)),
+                }
+            }
+            _ => false,
+        }
+    }
+}
+
+fn doubles_match(x: Option<f64>, y: Option<f64>) -> bool {
+    match (x, y) {
+        (None, None) => true,
+        (Some(x), Some(y)) if x.is_nan() || y.is_nan() => x.is_nan() && y.is_nan(),
+        (Some(x), Some(y)) if x.is_infinite() || y.is_infinite() => x == y,
+        (Some(x), Some(y)) => (x - y).abs() <= 1e-9 * x.abs().max(y.abs()).max(1.0),
+        _ => false,
+    }
+}
+
+impl fmt::Display for RValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Null => write!(f, "NULL"),
+            Self::List(items) => {
+                write!(f, "list(")?;
+                for (i, (name, value)) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    match name {
+                        Some(name) => write!(f, "{name} = {value}")?,
+                        None => write!(f, "{value}")?,
+                    }
+                }
+                write!(f, ")")
+            }
+            Self::Vector { data, dim } => {
+                let items: Vec<String> = match data {
+                    RVector::Logical(v) => v
+                        .iter()
+                        .map(|x| {
+                            x.map_or("NA".to_string(), |b| {
+                                if b { "TRUE" } else { "FALSE" }.to_string()
+                            })
+                        })
+                        .collect(),
+                    RVector::Int(v) => v
+                        .iter()
+                        .map(|x| x.map_or("NA".to_string(), |x| format!("{x}L")))
+                        .collect(),
+                    RVector::Double(v) => v
+                        .iter()
+                        .map(|x| x.map_or("NA".to_string(), format_double))
+                        .collect(),
+                    RVector::Char(v) => v
+                        .iter()
+                        .map(|x| x.as_ref().map_or("NA".to_string(), |s| format!("{s:?}")))
+                        .collect(),
+                };
+                if items.len() == 1 && dim.is_none() {
+                    write!(f, "{}", items[0])
+                } else {
+                    write!(f, "c({})", items.join(", "))?;
+                    if let Some(dim) = dim {
+                        write!(f, " dim={dim:?}")?;
+                    }
+                    Ok(())
+                }
+            }
+        }
+    }
+}
+
+fn recycled_len(a: usize, b: usize) -> usize {
+    if a == 0 || b == 0 { 0 } else { a.max(b) }
+}
+
+fn result_dim(lhs: &RValue, rhs: &RValue) -> Option<Vec<usize>> {
+    lhs.dim().or_else(|| rhs.dim()).map(<[usize]>::to_vec)
+}
+
+pub fn unary(op: UnaryOp, rhs: &RValue) -> Result<RValue, InterpError> {
+    let data = rhs.data()?;
+    let dim = rhs.dim().map(<[usize]>::to_vec);
+    let out = match op {
+        UnaryOp::Neg => match data {
+            RVector::Logical(_) | RVector::Int(_) => {
+                RVector::Int(data.as_int().iter().map(|x| x.map(|x| -x)).collect())
+            }
+            RVector::Double(v) => RVector::Double(v.iter().map(|x| x.map(|x| -x)).collect()),
+            RVector::Char(_) => {
+                return Err(InterpError::runtime("invalid argument to unary operator"));
+            }
+        },
+        UnaryOp::Not => match data {
+            RVector::Char(_) => return Err(InterpError::runtime("invalid argument type")),
+            _ => RVector::Logical(data.as_logical().iter().map(|x| x.map(|x| !x)).collect()),
+        },
+        UnaryOp::Formula => return Err(InterpError::unsupported("formula values")),
+    };
+    Ok(RValue::Vector { data: out, dim })
+}
+
+pub fn binary(op: BinOp, lhs: &RValue, rhs: &RValue) -> Result<RValue, InterpError> {
+    if op == BinOp::MatMul {
+        return Err(InterpError::unsupported("matrix multiplication"));
+    }
+    let a = lhs.data()?;
+    let b = rhs.data()?;
+    let n = recycled_len(a.len(), b.len());
+    let dim = result_dim(lhs, rhs);
+    let rank = a.rank().max(b.rank());
+    let data = match op {
+        BinOp::Add if rank == Rank::Char => {
+            // RR lowers string `+` to `paste0`.
+            let (x, y) = (a.as_char(), b.as_char());
+            RVector::Char(
+                (0..n)
+                    .map(|i| {
+                        let l = x[i % x.len()].clone().unwrap_or_else(|| "NA".to_string());
+                        let r = y[i % y.len()].clone().unwrap_or_else(|| "NA".to_string());
+                        Some(l + &r)
+                    })
+                    .collect(),
+            )
+        }
+        BinOp::Add | BinOp::Sub | BinOp::Mul | BinOp::Mod if rank <= Rank::Int => {
+            let (x, y) = (a.as_int(), b.as_int());
+            RVector::Int(
+                (0..n)
+                    .map(|i| int_arith(op, x[i % x.len()], y[i % y.len()]))
+                    .collect(),
+            )
+        }
+        BinOp::Add | BinOp::Sub | BinOp::Mul | BinOp::Div | BinOp::Mod => {
+            if rank == Rank::Char {
+                return Err(InterpError::runtime(
+                    "non-numeric argument to binary operator",
+                ));
+            }
+            let (x, y) = (a.as_double(), b.as_double());
+            RVector::Double(
+                (0..n)
+                    .map(|i| double_arith(op, x[i % x.len()], y[i % y.len()]))
+                    .collect(),
+            )
+        }
+        BinOp::Eq | BinOp::Ne | BinOp::Lt | BinOp::Le | BinOp::Gt | BinOp::Ge => {
+            if rank == Rank::Char {
+                let (x, y) = (a.as_char(), b.as_char());
+                RVector::Logical(
+                    (0..n)
+                        .map(|i| match (&x[i % x.len()], &y[i % y.len()]) {
+                            (Some(l), Some(r)) => Some(compare(op, l.cmp(r))),
+                            _ => None,
+                        })
+                        .collect(),
+                )
+            } else {
+                let (x, y) = (a.as_double(), b.as_double());
+                RVector::Logical(
+                    (0..n)
+                        .map(|i| match (x[i % x.len()], y[i % y.len()]) {
+                            (Some(l), Some(r)) if !l.is_nan() && !r.is_nan() => {
+                                l.partial_cmp(&r).map(|ord| compare(op, ord))
+                            }
+                            _ => None,
+                        })
+                        .collect(),
+                )
+            }
+        }
+        BinOp::And | BinOp::Or => {
+            let (x, y) = (a.as_logical(), b.as_logical());
+            RVector::Logical(
+                (0..n)
+                    .map(|i| logic(op, x[i % x.len()], y[i % y.len()]))
+                    .collect(),
+            )
+        }
+        BinOp::MatMul => unreachable!("handled above"),
+    };
+    Ok(RValue::Vector { data, dim })
+}
+
+fn compare(op: BinOp, ord: std::cmp::Ordering) -> bool {
+    use std::cmp::Ordering::*;
+    match op {
+        BinOp::Eq => ord == Equal,
+        BinOp::Ne => ord != Equal,
+        BinOp::Lt => ord == Less,
+        BinOp::Le => ord != Greater,
+        BinOp::Gt => ord == Greater,
+        BinOp::Ge => ord != Less,
+        _ => unreachable!("comparison operator expected"),
+    }
+}
+
+fn logic(op: BinOp, x: Option<bool>, y: Option<bool>) -> Option<bool> {
+    match op {
+        BinOp::And => match (x, y) {
+            (Some(false), _) | (_, Some(false)) => Some(false),
+            (Some(true), Some(true)) => Some(true),
+            _ => None,
+        },
+        _ => match (x, y) {
+            (Some(true), _) | (_, Some(true)) => Some(true),
+            (Some(false), Some(false)) => Some(false),
+            _ => None,
+        },
+    }
+}
+
+const R_INT_MAX: i64 = i32::MAX as i64;
+
+fn int_arith(op: BinOp, x: Option<i64>, y: Option<i64>) -> Option<i64> {
+    let (x, y) = (x?, y?);
+    let out = match op {
+        BinOp::Add => x.checked_add(y)?,
+        BinOp::Sub => x.checked_sub(y)?,
+        BinOp::Mul => x.checked_mul(y)?,
+        BinOp::Mod => {
+            if y == 0 {
+                return None;
+            }
+            x.rem_euclid(y) + if y < 0 && x.rem_euclid(y) != 0 { y } else { 0 }
+        }
+        _ => unreachable!("integer arithmetic operator expected"),
+    };
+    // R integers are 32-bit; overflow produces NA with a warning.
+    (out.abs() <= R_INT_MAX).then_some(out)
+}
+
+fn double_arith(op: BinOp, x: Option<f64>, y: Option<f64>) -> Option<f64> {
+    let (x, y) = (x?, y?);
+    Some(match op {
+        BinOp::Add => x + y,
+        BinOp::Sub => x - y,
+        BinOp::Mul => x * y,
+        BinOp::Div => x / y,
+        BinOp::Mod => {
+            if y == 0.0 {
+                f64::NAN
+            } else {
+                x - (x / y).floor() * y
+            }
+        }
+        _ => unreachable!("double arithmetic operator expected"),
+    })
+}
@@ -5,6 +5,7 @@
 
 use crate::error::{InternalCompilerError, RR, RRException, Stage};
 use crate::hir::def as hir;
+use crate::mir::analyze::unsafe_r::analyze_unsafe_r;
 use crate::mir::flow::Facts;
 use crate::mir::*;
 use crate::syntax::ast::{BinOp, Lit};
@@ -46,7 +47,9 @@ pub struct MirLowerer<'a> {
     known_functions: &'a FxHashMap<String, usize>,
     loop_stack: Vec<LoopTargets>,
     tidy_mask_depth: usize,
-    unsafe_r_seen: bool,
+    // Names writing `unsafe r` blocks may rebind; `None` when one of them
+    // could not be analyzed. Reads of these locals reload them by name.
+    unsafe_r_writes: Option<FxHashSet<String>>,
 }
 
 #[path = "lower_hir/construction.rs"]
@@ -30,7 +30,7 @@ impl<'a> MirLowerer<'a> {
             known_functions,
             loop_stack: Vec::new(),
             tidy_mask_depth: 0,
-            unsafe_r_seen: false,
+            unsafe_r_writes: Some(FxHashSet::default()),
         }
     }
 
@@ -219,7 +219,19 @@ impl<'a> MirLowerer<'a> {
         locals
     }
 
-    pub(crate) fn invalidate_defs_after_unsafe_r(&mut self, span: Span) {
+    /// Rebind locals to a post-block Load after a writing `unsafe r` block:
+    /// the names in `written`, or every local when the writes are unknown.
+    pub(crate) fn invalidate_defs_after_unsafe_r(
+        &mut self,
+        span: Span,
+        written: Option<&FxHashSet<hir::LocalId>>,
+    ) {
+        if let Some(written) = written {
+            for &local in written {
+                self.reload_local_after_unsafe_r(local, span);
+            }
+            return;
+        }
         let mut affected_locals = self
             .visible_def_locals(self.curr_block, &mut FxHashSet::default())
             .into_iter()
@@ -234,27 +246,34 @@ impl<'a> MirLowerer<'a> {
         // over-promised as direct frame bindings.
         affected_locals.extend(self.var_names.keys().copied());
         for local in affected_locals {
-            let Some(var) = self.var_names.get(&local).cloned() else {
-                continue;
-            };
-            let load =
-                self.add_value_with_name(ValueKind::Load { var: var.clone() }, span, Some(var));
-            self.defs
-                .entry(self.curr_block)
-                .or_default()
-                .insert(local, load);
+            self.reload_local_after_unsafe_r(local, span);
         }
     }
 
+    fn reload_local_after_unsafe_r(&mut self, local: hir::LocalId, span: Span) {
+        let Some(var) = self.var_names.get(&local).cloned() else {
+            return;
+        };
+        let load = self.add_value_with_name(ValueKind::Load { var: var.clone() }, span, Some(var));
+        self.defs
+            .entry(self.curr_block)
+            .or_default()
+            .insert(local, load);
+    }
+
     pub(crate) fn unsafe_r_load_for_var(
         &mut self,
         var: hir::LocalId,
         resolved: ValueId,
     ) -> Option<ValueId> {
-        if !self.unsafe_r_seen {
+        let name = self.var_names.get(&var)?.clone();
+        if self
+            .unsafe_r_writes
+            .as_ref()
+            .is_some_and(|names| !names.contains(&name))
+        {
             return None;
         }
-        let name = self.var_names.get(&var)?.clone();
         if matches!(
             self.fn_ir.values.get(resolved).map(|value| &value.kind),
             Some(ValueKind::Load { var }) if var == &name
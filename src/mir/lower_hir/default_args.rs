@@ -202,7 +202,7 @@ impl<'a> MirLowerer<'a> {
                 Self::render_default_unop(op),
                 self.render_default_expr(expr)?
             )),
-            hir::HirExpr::Binary { op, lhs, rhs } => Ok(format!(
+            hir::HirExpr::Binary { op, lhs, rhs, .. } => Ok(format!(
                 "({} {} {})",
                 self.render_default_expr(lhs)?,
                 Self::render_default_binop(op),
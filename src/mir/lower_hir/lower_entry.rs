@@ -1,20 +1,41 @@
 use super::*;
 impl<'a> MirLowerer<'a> {
     pub(crate) fn block_contains_write_unsafe_r(blk: &hir::HirBlock) -> bool {
-        blk.stmts.iter().any(|stmt| match stmt {
-            hir::HirStmt::UnsafeRBlock { read_only, .. } => !read_only,
+        Self::unsafe_r_block_writes(blk).is_none_or(|names| !names.is_empty())
+    }
+
+    /// Names the writing `unsafe r` blocks in `blk` may rebind, or `None`
+    /// when one of them cannot be analyzed.
+    pub(crate) fn unsafe_r_block_writes(blk: &hir::HirBlock) -> Option<FxHashSet<String>> {
+        let mut names = FxHashSet::default();
+        Self::collect_unsafe_r_writes(blk, &mut names).then_some(names)
+    }
+
+    fn collect_unsafe_r_writes(blk: &hir::HirBlock, names: &mut FxHashSet<String>) -> bool {
+        blk.stmts.iter().all(|stmt| match stmt {
+            hir::HirStmt::UnsafeRBlock {
+                code,
+                read_only: false,
+                ..
+            } => match analyze_unsafe_r(code) {
+                Some(access) => {
+                    names.extend(access.written().cloned());
+                    true
+                }
+                None => false,
+            },
             hir::HirStmt::If {
                 then_blk, else_blk, ..
             } => {
-                Self::block_contains_write_unsafe_r(then_blk)
-                    || else_blk
+                Self::collect_unsafe_r_writes(then_blk, names)
+                    && else_blk
                         .as_ref()
-                        .is_some_and(Self::block_contains_write_unsafe_r)
+                        .is_none_or(|blk| Self::collect_unsafe_r_writes(blk, names))
             }
             hir::HirStmt::While { body, .. } | hir::HirStmt::For { body, .. } => {
-                Self::block_contains_write_unsafe_r(body)
+                Self::collect_unsafe_r_writes(body, names)
             }
-            _ => false,
+            _ => true,
         })
     }
 
@@ -29,7 +50,7 @@ impl<'a> MirLowerer<'a> {
     // and the Coq `Lowering*` / `Pipeline*Subset` companions model reduced
     // slices of this source-to-MIR lowering entry point.
     pub fn lower_fn(mut self, f: hir::HirFn) -> RR<FnIR> {
-        self.unsafe_r_seen = Self::block_contains_write_unsafe_r(&f.body);
+        self.unsafe_r_writes = Self::unsafe_r_block_writes(&f.body);
         self.fn_ir.span = f.span;
         self.fn_ir.user_name = self.symbols.get(&f.name).cloned();
         self.fn_ir.param_default_r_exprs = f
@@ -7,7 +7,9 @@ impl<'a> MirLowerer<'a> {
             hir::HirExpr::Local(l) => self.read_var(l, self.curr_block),
             hir::HirExpr::Global(sym, span) => Ok(self.lower_global(sym, span)),
             hir::HirExpr::Unary { op, expr } => self.lower_unary_expr(op, *expr),
-            hir::HirExpr::Binary { op, lhs, rhs } => self.lower_binary_expr(op, *lhs, *rhs),
+            hir::HirExpr::Binary { op, lhs, rhs, span } => {
+                self.lower_binary_expr(op, *lhs, *rhs, span)
+            }
             hir::HirExpr::Field { base, name } => self.lower_field_expr(*base, name),
             hir::HirExpr::Index { base, index } => self.lower_index_expr(*base, index),
             hir::HirExpr::Block(blk) => self.lower_block(blk),
@@ -85,11 +87,12 @@ impl<'a> MirLowerer<'a> {
         op: hir::HirBinOp,
         lhs: hir::HirExpr,
         rhs: hir::HirExpr,
+        span: Span,
     ) -> RR<ValueId> {
         let lhs = self.lower_expr(lhs)?;
         let rhs = self.lower_expr(rhs)?;
         let op = self.map_binop(op);
-        Ok(self.add_value(ValueKind::Binary { op, lhs, rhs }, Span::default()))
+        Ok(self.add_value(ValueKind::Binary { op, lhs, rhs }, span))
     }
 
     pub(crate) fn lower_field_expr(
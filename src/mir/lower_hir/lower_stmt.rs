@@ -216,21 +216,49 @@ impl<'a> MirLowerer<'a> {
     }
 
     pub(crate) fn lower_unsafe_r_block(&mut self, code: String, read_only: bool, span: Span) {
+        let access = analyze_unsafe_r(&code);
+        // Locals a parsed block rebinds; `None` when its writes are unknown.
+        let written = access.as_ref().map(|access| {
+            self.var_names
+                .iter()
+                .filter(|(_, name)| access.may_write(name))
+                .map(|(&local, _)| local)
+                .collect::<FxHashSet<_>>()
+        });
         if !read_only {
-            self.unsafe_r_seen = true;
-            self.fn_ir.mark_opaque_interop(
-                "unsafe R block requires conservative optimization".to_string(),
-            );
+            match (&mut self.unsafe_r_writes, &access) {
+                (Some(names), Some(access)) => names.extend(access.written().cloned()),
+                (names, _) => *names = None,
+            }
+            // A block that only reads RR locals, or binds R-side temporaries,
+            // leaves the function optimizable.
+            let rebinds_rr_state = match &access {
+                Some(access) => {
+                    !access.super_writes.is_empty()
+                        || written.as_ref().is_some_and(|locals| !locals.is_empty())
+                        || access
+                            .writes
+                            .iter()
+                            .any(|name| self.fn_ir.params.contains(name))
+                }
+                None => true,
+            };
+            if rebinds_rr_state {
+                self.fn_ir.mark_opaque_interop(
+                    "unsafe R block requires conservative optimization".to_string(),
+                );
+            }
         }
         self.fn_ir.blocks[self.curr_block]
             .instrs
             .push(Instr::UnsafeRBlock {
                 code,
                 read_only,
+                access,
                 span,
             });
         if !read_only {
-            self.invalidate_defs_after_unsafe_r(span);
+            self.invalidate_defs_after_unsafe_r(span, written.as_ref());
         }
     }
 
@@ -2,10 +2,12 @@ pub mod analyze;
 pub(crate) mod const_fold;
 pub mod def;
 pub mod flow;
+pub mod interp;
 pub mod lower_hir;
 pub mod opt;
 pub mod semantics;
 pub mod structurizer;
+pub mod text;
 pub mod verify;
 
 #[allow(unused_imports)]
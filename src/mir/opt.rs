@@ -68,6 +68,7 @@ pub mod outline;
 pub mod parallel_copy;
 #[path = "opt/poly/mod.rs"]
 pub mod poly;
+pub mod remarks;
 pub mod sccp;
 pub mod simplify;
 pub mod tco;
@@ -647,4 +648,18 @@ mod tests {
         assert!(oz.configured_max_full_opt_ir() < o2.configured_max_full_opt_ir());
         assert!(oz.configured_max_full_opt_fn_ir() < o2.configured_max_full_opt_fn_ir());
     }
+
+    #[test]
+    fn translation_divergence_is_reported_once_per_compilation() {
+        let before = dummy_fn("f", 1);
+        let after = dummy_fn("f", 2);
+
+        let engine = TachyonEngine::new();
+        assert!(engine.maybe_validate_translation(&before, &after, "test", "pass"));
+        assert!(!engine.maybe_validate_translation(&before, &after, "test", "pass"));
+
+        // A fresh compilation reports the same function again.
+        let next = TachyonEngine::new();
+        assert!(next.maybe_validate_translation(&before, &after, "test", "pass"));
+    }
 }
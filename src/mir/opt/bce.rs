@@ -2,7 +2,8 @@ use crate::mir::analyze::na::NaState;
 use crate::mir::analyze::range::ensure_value_range;
 use crate::mir::analyze::range::{RangeInterval, SymbolicBound};
 use crate::mir::*;
-use rustc_hash::FxHashSet;
+use crate::utils::Span;
+use rustc_hash::{FxHashMap, FxHashSet};
 
 #[path = "bce/loop_rules.rs"]
 mod loop_rules;
@@ -1,8 +1,10 @@
 use super::*;
 use crate::mir::analyze::na::{self, NaState};
+use crate::mir::analyze::ownership::OwnershipFacts;
 use crate::mir::analyze::range::{analyze_ranges, ensure_value_range, transfer_instr};
 use crate::mir::opt::loop_analysis::{LoopAnalyzer, LoopInfo};
-use rustc_hash::FxHashSet;
+use crate::mir::opt::remarks;
+use rustc_hash::{FxHashMap, FxHashSet};
 
 #[derive(Clone)]
 pub(crate) struct CanonicalIvRule<'a> {
@@ -32,10 +34,12 @@ pub fn optimize_with_loop_info(fn_ir: &mut FnIR, loops: &[LoopInfo]) -> bool {
     let mut node_visits = 0usize;
     let visit_limit = bce_visit_limit();
     let mut one_based_indices = FxHashSet::default();
+    let ownership = OwnershipFacts::analyze_function(fn_ir);
 
     // Pass 1: Handle StoreIndex1D instructions
     for (bid, facts_at_bid) in bb_facts.iter().enumerate().take(fn_ir.blocks.len()) {
         let mut cur_facts = facts_at_bid.clone();
+        let mut owned = ownership.cursor(bid);
         let num_instrs = fn_ir.blocks[bid].instrs.len();
 
         for i in 0..num_instrs {
@@ -45,7 +49,11 @@ pub fn optimize_with_loop_info(fn_ir: &mut FnIR, loops: &[LoopInfo]) -> bool {
                     ensure_value_range(*idx, &fn_ir.values, &mut cur_facts);
                     let iv_proven = iv_exact_in_block(bid, *idx, &canonical_ivs, fn_ir);
                     let idx_intv = cur_facts.get(*idx);
-                    let in_bounds = interval_proves_in_bounds(fn_ir, &idx_intv, *base) || iv_proven;
+                    let in_bounds = interval_proves_in_bounds(fn_ir, &idx_intv, *base)
+                        || iv_proven
+                        || owned_allocation_proves_in_bounds(
+                            fn_ir, &mut owned, i, *base, &idx_intv,
+                        );
                     if iv_non_na_in_block(bid, *idx, &canonical_ivs, &one_based_ivs, fn_ir) {
                         one_based_indices.insert(*idx);
                     }
@@ -58,6 +66,19 @@ pub fn optimize_with_loop_info(fn_ir: &mut FnIR, loops: &[LoopInfo]) -> bool {
                 }
             };
 
+            if let Instr::StoreIndex1D { is_safe, span, .. } = fn_ir.blocks[bid].instrs[i] {
+                if in_bounds && !is_safe {
+                    remarks::passed("bce", fn_ir, span, "bounds check removed from store");
+                } else if !in_bounds && !is_safe && loops.iter().any(|lp| lp.body.contains(&bid)) {
+                    remarks::missed(
+                        "bce",
+                        fn_ir,
+                        span,
+                        "bounds check kept on store: index not proven in range",
+                        "index-not-proven",
+                    );
+                }
+            }
             if let Instr::StoreIndex1D {
                 ref mut is_safe,
                 ref mut is_na_safe,
@@ -82,13 +103,14 @@ pub fn optimize_with_loop_info(fn_ir: &mut FnIR, loops: &[LoopInfo]) -> bool {
     }
 
     // Pass 2: Handle Index1D loads, including nested loads inside expression trees.
-    let mut safe_values = FxHashSet::default();
+    let mut safe_values = FxHashMap::default();
     let mut non_na_values = FxHashSet::default();
     macro_rules! collect_safety_for {
-        ($bid:expr, $facts:expr, $($value:expr),+ $(,)?) => {{
+        ($bid:expr, $span:expr, $facts:expr, $($value:expr),+ $(,)?) => {{
             let mut seen = FxHashSet::default();
             let mut collector = IndexSafetyCollector {
                 bid: $bid,
+                span: $span,
                 facts: &mut $facts,
                 fn_ir,
                 canonical_ivs: &canonical_ivs,
@@ -109,20 +131,20 @@ pub fn optimize_with_loop_info(fn_ir: &mut FnIR, loops: &[LoopInfo]) -> bool {
         for instr in &fn_ir.blocks[bid].instrs {
             match instr {
                 Instr::Assign { src, .. } | Instr::Eval { val: src, .. } => {
-                    collect_safety_for!(bid, cur_facts, *src);
+                    collect_safety_for!(bid, instr.span(), cur_facts, *src);
                 }
                 Instr::StoreIndex1D { base, idx, val, .. } => {
-                    collect_safety_for!(bid, cur_facts, *base, *idx, *val);
+                    collect_safety_for!(bid, instr.span(), cur_facts, *base, *idx, *val);
                 }
                 Instr::StoreIndex2D {
                     base, r, c, val, ..
                 } => {
-                    collect_safety_for!(bid, cur_facts, *base, *r, *c, *val);
+                    collect_safety_for!(bid, instr.span(), cur_facts, *base, *r, *c, *val);
                 }
                 Instr::StoreIndex3D {
                     base, i, j, k, val, ..
                 } => {
-                    collect_safety_for!(bid, cur_facts, *base, *i, *j, *k, *val);
+                    collect_safety_for!(bid, instr.span(), cur_facts, *base, *i, *j, *k, *val);
                 }
                 Instr::UnsafeRBlock { .. } => {}
             }
@@ -141,7 +163,7 @@ pub fn optimize_with_loop_info(fn_ir: &mut FnIR, loops: &[LoopInfo]) -> bool {
         }
     }
 
-    for vid in safe_values {
+    for (vid, instr_span) in safe_values {
         if let ValueKind::Index1D {
             ref mut is_safe, ..
         } = fn_ir.values[vid].kind
@@ -149,6 +171,13 @@ pub fn optimize_with_loop_info(fn_ir: &mut FnIR, loops: &[LoopInfo]) -> bool {
         {
             *is_safe = true;
             changed = true;
+            // Loads built during lowering may carry the whole function's
+            // span; point at the statement that reads the value instead.
+            let span = match fn_ir.values[vid].span {
+                span if span.start_line == 0 || span == fn_ir.span => instr_span,
+                span => span,
+            };
+            remarks::passed("bce", fn_ir, span, "bounds check removed from load");
         }
     }
 
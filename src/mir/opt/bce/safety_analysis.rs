@@ -1,12 +1,15 @@
 use super::*;
+use crate::mir::analyze::ownership::{self, OwnershipCursor};
 pub(crate) struct IndexSafetyCollector<'a> {
     pub(crate) bid: BlockId,
+    /// Span of the instruction whose operands are being walked.
+    pub(crate) span: Span,
     pub(crate) facts: &'a mut crate::mir::analyze::range::RangeFacts,
     pub(crate) fn_ir: &'a FnIR,
     pub(crate) canonical_ivs: &'a [CanonicalIvRule<'a>],
     pub(crate) one_based_ivs: &'a [OneBasedIvRule<'a>],
     pub(crate) na_states: &'a [NaState],
-    pub(crate) safe_values: &'a mut FxHashSet<ValueId>,
+    pub(crate) safe_values: &'a mut FxHashMap<ValueId, Span>,
     pub(crate) non_na_values: &'a mut FxHashSet<ValueId>,
     pub(crate) one_based_values: &'a mut FxHashSet<ValueId>,
     pub(crate) seen: &'a mut FxHashSet<ValueId>,
@@ -83,7 +86,7 @@ impl IndexSafetyCollector<'_> {
             self.one_based_values.insert(idx);
         }
         if !is_safe && (interval_proves_in_bounds(self.fn_ir, &idx_intv, base) || iv_proven) {
-            self.safe_values.insert(vid);
+            self.safe_values.entry(vid).or_insert(self.span);
         }
         let na_proven = matches!(self.na_states[idx], NaState::Never)
             || iv_non_na_in_block(
@@ -115,6 +118,39 @@ pub(crate) fn interval_proves_in_bounds(fn_ir: &FnIR, intv: &RangeInterval, base
     lo_safe && hi_safe
 }
 
+/// Proves a store in bounds when its base uniquely owns an allocation whose
+/// pinned length bounds the index interval. Ownership guarantees no other
+/// binding resized or replaced the vector since it was allocated.
+pub(crate) fn owned_allocation_proves_in_bounds(
+    fn_ir: &FnIR,
+    owned: &mut OwnershipCursor,
+    instr_idx: usize,
+    base: ValueId,
+    intv: &RangeInterval,
+) -> bool {
+    let Some(var) = value_base_name(fn_ir, base) else {
+        return false;
+    };
+    let Some(alloc) = owned.owned_alloc_before(fn_ir, instr_idx, var) else {
+        return false;
+    };
+    let Some(alloc_len) = ownership::allocation_length(fn_ir, alloc) else {
+        return false;
+    };
+    let lo_safe = matches!(intv.lo, SymbolicBound::Const(n) if n >= 1);
+    let hi_safe = match &intv.hi {
+        SymbolicBound::VarPlus(limit, off) => {
+            *off <= 0 && ownership::same_length_operand(fn_ir, *limit, alloc_len)
+        }
+        SymbolicBound::Const(hi) => matches!(
+            fn_ir.values[alloc_len].kind,
+            ValueKind::Const(Lit::Int(len)) if *hi <= len
+        ),
+        _ => false,
+    };
+    lo_safe && hi_safe
+}
+
 pub(crate) fn same_base_for_len(fn_ir: &FnIR, len_base: ValueId, index_base: ValueId) -> bool {
     if len_base == index_base {
         return true;
@@ -36,6 +36,11 @@ pub(crate) fn is_callmap_vector_safe_user_fn(
     if name.starts_with("Sym_top_") {
         return false;
     }
+    // Mapping over a whole vector changes how many draws happen and in what
+    // order, so kernels that use the RNG keep their scalar loop.
+    if crate::mir::analyze::effects::fn_uses_rng(fn_ir) {
+        return false;
+    }
 
     for bb in &fn_ir.blocks {
         for ins in &bb.instrs {
@@ -136,8 +136,8 @@ impl TachyonEngine {
                         self.collect_value_live_vars(*val, &fn_ir.values, &mut live);
                         new_instrs_rev.push(instr.clone());
                     }
-                    Instr::UnsafeRBlock { code, .. } => {
-                        Self::collect_unsafe_r_named_vars(code, &mut live);
+                    Instr::UnsafeRBlock { code, access, .. } => {
+                        Self::collect_unsafe_r_block_vars(code, access.as_ref(), &mut live);
                         new_instrs_rev.push(instr.clone());
                     }
                 }
@@ -251,14 +251,27 @@ impl TachyonEngine {
                     self.collect_value_live_vars(*k, values, &mut live);
                     self.collect_value_live_vars(*val, values, &mut live);
                 }
-                Instr::UnsafeRBlock { code, .. } => {
-                    Self::collect_unsafe_r_named_vars(code, &mut live);
+                Instr::UnsafeRBlock { code, access, .. } => {
+                    Self::collect_unsafe_r_block_vars(code, access.as_ref(), &mut live);
                 }
             }
         }
         live
     }
 
+    /// Frame names an `unsafe r` block may read: its analyzed reads and
+    /// callees, or every identifier in the code when it was not analyzable.
+    pub(crate) fn collect_unsafe_r_block_vars(
+        code: &str,
+        access: Option<&UnsafeRAccess>,
+        live: &mut FxHashSet<VarId>,
+    ) {
+        match access {
+            Some(access) => live.extend(access.frame_reads().cloned()),
+            None => Self::collect_unsafe_r_named_vars(code, live),
+        }
+    }
+
     pub(crate) fn collect_unsafe_r_named_vars(code: &str, live: &mut FxHashSet<VarId>) {
         let mut ident = String::new();
         let mut string_quote: Option<char> = None;
@@ -959,6 +959,7 @@ pub(crate) fn dce_keeps_dot_prefixed_temp_read_by_unsafe_r() {
     fn_ir.blocks[fn_ir.entry].instrs.push(Instr::UnsafeRBlock {
         code: "print(.tachyon_keep)".to_string(),
         read_only: true,
+        access: None,
         span: Span::default(),
     });
     fn_ir.blocks[fn_ir.entry].term = Terminator::Return(Some(zero));
@@ -36,7 +36,7 @@ pub(in crate::mir::opt) use catalog::{
     PHASE_ORDER_STRUCTURAL_CLEANUP_PASSES, PREPARE_FOR_CODEGEN_CLEANUP_PASSES,
     PREPARE_FOR_CODEGEN_DESSA_PASSES, PROGRAM_FRESH_ALIAS_PASSES, PROGRAM_INLINE_CLEANUP_PASSES,
     PROGRAM_INLINE_PASSES, PROGRAM_OUTLINE_PASSES, PROGRAM_POST_DESSA_PASSES,
-    PROGRAM_RECORD_SPECIALIZATION_PASSES,
+    PROGRAM_RECORD_SPECIALIZATION_PASSES, function_pass_by_name,
 };
 pub(in crate::mir::opt) use context::{
     ChronosContext, ChronosFunctionSequenceRequest, ChronosProgramContext,
@@ -118,6 +118,7 @@ pub(crate) fn run_poly(
     if !poly::poly_enabled() {
         return ChronosPassOutcome::unchanged();
     }
+    remarks::begin_pass_run("poly", fn_ir);
     let loops = ctx.analysis_cache.loops(fn_ir);
     let p_stats = poly::optimize_with_stats_with_loop_info(fn_ir, loops);
     let changed = p_stats.schedule_applied > 0;
@@ -133,6 +134,7 @@ pub(crate) fn run_vectorize(
     let Some(user_call_whitelist) = ctx.user_call_whitelist else {
         return ChronosPassOutcome::unchanged();
     };
+    remarks::begin_pass_run("vectorize", fn_ir);
     let loops = ctx.analysis_cache.loops(fn_ir);
     let v_stats =
         v_opt::optimize_with_stats_with_whitelist_and_loops(fn_ir, user_call_whitelist, loops);
@@ -146,6 +148,7 @@ pub(crate) fn run_unroll(
     fn_ir: &mut FnIR,
     ctx: &mut ChronosContext<'_>,
 ) -> ChronosPassOutcome {
+    remarks::begin_pass_run("unroll", fn_ir);
     ChronosPassOutcome::counted(unroll::optimize(fn_ir, engine, ctx.stats))
 }
 
@@ -171,6 +174,7 @@ pub(crate) fn run_licm(
     fn_ir: &mut FnIR,
     ctx: &mut ChronosContext<'_>,
 ) -> ChronosPassOutcome {
+    remarks::begin_pass_run("licm", fn_ir);
     let loops = ctx.analysis_cache.loops(fn_ir);
     ChronosPassOutcome::changed(licm::MirLicm::new().optimize_with_loop_info(fn_ir, loops))
 }
@@ -226,6 +230,7 @@ pub(crate) fn run_bce(
     fn_ir: &mut FnIR,
     ctx: &mut ChronosContext<'_>,
 ) -> ChronosPassOutcome {
+    remarks::begin_pass_run("bce", fn_ir);
     let loops = ctx.analysis_cache.loops(fn_ir);
     ChronosPassOutcome::changed(bce::optimize_with_loop_info(fn_ir, loops))
 }
@@ -395,3 +395,41 @@ pub(in crate::mir::opt) const PREPARE_FOR_CODEGEN_CLEANUP_PASSES: &[ChronosPassS
         "OptimizerPipelineSoundness.prepare_for_codegen_dce",
     ),
 ];
+
+/// Function-scope sets searched by [`function_pass_by_name`], most canonical
+/// placement first.
+const NAMED_FUNCTION_PASS_SETS: &[&[ChronosPassSpec]] = &[
+    PHASE_ORDER_STANDARD_CORE_PASSES,
+    PHASE_ORDER_STANDARD_BUDGET_PASSES,
+    PHASE_ORDER_BALANCED_STRUCTURAL_PASSES,
+    PREPARE_FOR_CODEGEN_DESSA_PASSES,
+    PROGRAM_POST_DESSA_PASSES,
+    PROGRAM_FRESH_ALIAS_PASSES,
+    PREPARE_FOR_CODEGEN_CLEANUP_PASSES,
+];
+
+/// The spec for a single function pass named by its timing name, as a
+/// one-element sequence that can be handed to a `ChronosPassManager`.
+pub(in crate::mir::opt) fn function_pass_by_name(
+    name: &str,
+) -> Result<&'static [ChronosPassSpec], String> {
+    let Some(id) = ChronosPassId::from_name(name) else {
+        let known: Vec<&str> = ChronosPassId::ALL
+            .iter()
+            .map(|id| id.timing_name())
+            .collect();
+        return Err(format!(
+            "unknown pass '{name}' (expected one of: {})",
+            known.join(", ")
+        ));
+    };
+    for set in NAMED_FUNCTION_PASS_SETS {
+        let set: &'static [ChronosPassSpec] = set;
+        if let Some(idx) = set.iter().position(|spec| spec.id == id) {
+            return Ok(&set[idx..=idx]);
+        }
+    }
+    Err(format!(
+        "pass '{name}' runs over the whole program and cannot be applied to a single function"
+    ))
+}
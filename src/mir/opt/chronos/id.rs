@@ -28,6 +28,41 @@ pub(in crate::mir::opt) enum ChronosPassId {
 }
 
 impl ChronosPassId {
+    pub(in crate::mir::opt) const ALL: &'static [Self] = &[
+        Self::IndexCanonicalize,
+        Self::RecordCallSpecialize,
+        Self::RecordReturnSpecialize,
+        Self::SimplifyCfg,
+        Self::Sccp,
+        Self::Intrinsics,
+        Self::Gvn,
+        Self::Simplify,
+        Self::TypeSpecialize,
+        Self::Inline,
+        Self::Outline,
+        Self::Poly,
+        Self::Vectorize,
+        Self::Unroll,
+        Self::Tco,
+        Self::LoopOpt,
+        Self::Licm,
+        Self::Sroa,
+        Self::Dce,
+        Self::DeSsa,
+        Self::CopyCleanup,
+        Self::FreshAlias,
+        Self::FreshAlloc,
+        Self::Bce,
+    ];
+
+    /// Look a pass up by its timing name, the spelling used by `RR mir-opt`.
+    pub(in crate::mir::opt) fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|id| id.timing_name() == name)
+    }
+
     pub(in crate::mir::opt) const fn timing_name(self) -> &'static str {
         match self {
             Self::IndexCanonicalize => "index_canonicalize",
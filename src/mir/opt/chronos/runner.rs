@@ -168,6 +168,7 @@ impl ChronosPassManager {
         let opportunity = function_opportunity(engine, fn_ir, ctx, spec);
         ctx.timings.record_opportunity(opportunity);
         TachyonEngine::dump_mir_snapshot(fn_ir, spec.verify_label, spec.id.timing_name(), "before");
+        let interp_before = TachyonEngine::verify_interp_enabled().then(|| fn_ir.clone());
         let started = Instant::now();
         let outcome = (spec.run)(engine, fn_ir, ctx);
         let elapsed_ns = started.elapsed().as_nanos();
@@ -188,6 +189,16 @@ impl ChronosPassManager {
         }
         record_stats_delta(ctx.stats, spec.id, outcome);
         TachyonEngine::maybe_verify(fn_ir, spec.verify_label);
+        if let Some(before) = interp_before
+            && outcome.is_changed()
+        {
+            engine.maybe_validate_translation(
+                &before,
+                fn_ir,
+                spec.verify_label,
+                spec.id.timing_name(),
+            );
+        }
         TachyonEngine::dump_mir_snapshot(fn_ir, spec.verify_label, spec.id.timing_name(), "after");
         TachyonEngine::debug_stage_dump(fn_ir, spec.verify_label);
         outcome
@@ -34,6 +34,14 @@ impl TachyonEngine {
         })
     }
 
+    pub(crate) fn verify_interp_enabled() -> bool {
+        Self::env_bool("RR_VERIFY_INTERP", false)
+    }
+
+    pub(crate) fn verify_interp_cases() -> usize {
+        Self::env_usize("RR_VERIFY_INTERP_CASES", 8).max(1)
+    }
+
     pub(crate) fn sanitize_dump_component(raw: &str) -> String {
         let mut out = String::with_capacity(raw.len());
         for ch in raw.chars() {
@@ -79,6 +87,41 @@ impl TachyonEngine {
         let _ = fs::write(path, payload);
     }
 
+    pub(crate) fn dump_interp_divergence(
+        before: &FnIR,
+        after: &FnIR,
+        stage: &str,
+        pass: &str,
+        divergence: &crate::mir::interp::Divergence,
+    ) {
+        let Some(root) = Self::verify_dump_dir() else {
+            return;
+        };
+        let root = std::path::Path::new(&root);
+        if fs::create_dir_all(root).is_err() {
+            return;
+        }
+        for (moment, fn_ir) in [("before", before), ("after", after)] {
+            let file_name = format!(
+                "interp__{}__{}__{}__{moment}.mir.txt",
+                Self::sanitize_dump_component(stage),
+                Self::sanitize_dump_component(pass),
+                Self::sanitize_dump_component(&fn_ir.name)
+            );
+            let divergence = divergence
+                .to_string()
+                .lines()
+                .map(|line| format!("// {line}\n"))
+                .collect::<String>();
+            let payload = format!(
+                "// interp divergence\n// moment: {moment}\n// pass: {pass}\n// stage: {stage}\n// function: {}\n{divergence}\n{}",
+                fn_ir.name,
+                crate::mir::text::print_fn(fn_ir)
+            );
+            let _ = fs::write(root.join(file_name), payload);
+        }
+    }
+
     pub(crate) fn mir_dump_dir() -> Option<String> {
         env::var("RR_MIR_DUMP_DIR").ok().and_then(|v| {
             let trimmed = v.trim();
@@ -154,8 +197,9 @@ impl TachyonEngine {
             Self::sanitize_dump_component(&fn_ir.name),
         );
         let payload = format!(
-            "# mir dump\nmoment: {moment}\npass: {pass}\nstage: {stage}\nfunction: {}\n\n{:#?}\n",
-            fn_ir.name, fn_ir
+            "// mir dump\n// moment: {moment}\n// pass: {pass}\n// stage: {stage}\n// function: {}\n\n{}",
+            fn_ir.name,
+            crate::mir::text::print_fn(fn_ir)
         );
         let _ = fs::write(root.join(file_name), payload);
     }
@@ -183,6 +227,44 @@ impl TachyonEngine {
         }
     }
 
+    /// Run `before` and `after` of one pass through the MIR interpreter and
+    /// report the first divergence per function. Later passes over an already
+    /// diverged function are not reported again within this compilation, so
+    /// the report names the pass that introduced the difference.
+    /// Returns whether a new divergence was reported.
+    pub(crate) fn maybe_validate_translation(
+        &self,
+        before: &FnIR,
+        after: &FnIR,
+        stage: &str,
+        pass: &str,
+    ) -> bool {
+        if self
+            .interp_diverged
+            .lock()
+            .map(|seen| seen.contains(&after.name))
+            .unwrap_or(true)
+        {
+            return false;
+        }
+        let inputs = crate::mir::interp::generate_inputs(before, Self::verify_interp_cases());
+        let interp = crate::mir::interp::Interpreter::new();
+        let Some(divergence) =
+            crate::mir::interp::validate_translation(&interp, before, after, &inputs)
+        else {
+            return false;
+        };
+        if let Ok(mut seen) = self.interp_diverged.lock() {
+            seen.insert(after.name.clone());
+        }
+        eprintln!(
+            "   [interp] {} diverged after {pass} ({stage}): {divergence}",
+            chronos::runner::display_fn_name(after)
+        );
+        Self::dump_interp_divergence(before, after, stage, pass, &divergence);
+        true
+    }
+
     pub(crate) fn max_opt_iterations() -> usize {
         24
     }
@@ -12,6 +12,9 @@ pub struct TachyonEngine {
     pub(crate) phase_ordering_default_mode: types::PhaseOrderingMode,
     pub(crate) compile_mode: crate::compiler::CompileMode,
     pub(crate) opt_level: crate::compiler::OptLevel,
+    /// Functions whose translation validation already diverged during this
+    /// compilation; see [`TachyonEngine::maybe_validate_translation`].
+    pub(crate) interp_diverged: std::sync::Mutex<FxHashSet<String>>,
 }
 
 // Backward compatibility alias for older call sites.
@@ -29,6 +32,7 @@ impl TachyonEngine {
             phase_ordering_default_mode: types::PhaseOrderingMode::Off,
             compile_mode: crate::compiler::CompileMode::Standard,
             opt_level: crate::compiler::OptLevel::O2,
+            interp_diverged: Default::default(),
         }
     }
 
@@ -39,6 +43,7 @@ impl TachyonEngine {
             phase_ordering_default_mode,
             compile_mode: crate::compiler::CompileMode::Standard,
             opt_level: crate::compiler::OptLevel::O2,
+            interp_diverged: Default::default(),
         }
     }
 
@@ -62,6 +67,7 @@ impl TachyonEngine {
             phase_ordering_default_mode,
             compile_mode,
             opt_level,
+            interp_diverged: Default::default(),
         }
     }
 
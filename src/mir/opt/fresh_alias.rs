@@ -1,4 +1,5 @@
 use crate::mir::analyze::effects;
+use crate::mir::analyze::ownership::{InPlaceWrites, OwnershipFacts};
 use crate::mir::*;
 use rustc_hash::{FxHashMap, FxHashSet};
 
@@ -39,10 +40,13 @@ pub(crate) fn optimize_function_with_fresh_user_calls(
 
 fn optimize_function(fn_ir: &mut FnIR, fresh_user_calls: &FxHashSet<String>) -> bool {
     let in_states = compute_in_states(fn_ir, fresh_user_calls);
+    let ownership = OwnershipFacts::compute(fn_ir);
+    let in_place_writes = InPlaceWrites::collect(fn_ir);
     let mut changed = false;
     let mut seen_fresh_recipe_assigns: FxHashSet<ValueId> = FxHashSet::default();
     for bid in 0..fn_ir.blocks.len() {
         let mut fresh_vars = in_states.get(bid).cloned().unwrap_or_default();
+        let mut owned = ownership.cursor(bid);
         let instr_len = fn_ir.blocks[bid].instrs.len();
         for idx in 0..instr_len {
             let instr = fn_ir.blocks[bid].instrs[idx].clone();
@@ -79,8 +83,25 @@ fn optimize_function(fn_ir: &mut FnIR, fresh_user_calls: &FxHashSet<String>) ->
                             &fn_ir.values[recipe].kind,
                             ValueKind::Call { callee, .. } if is_fresh_call(callee, fresh_user_calls)
                         ) && seen_fresh_recipe_assigns.contains(&recipe);
-                        let should_clone =
-                            from_alias || recipe_already_bound_elsewhere || recipe_seen_before;
+                        // `dst` may share the allocation another variable holds when
+                        // that variable uniquely owns it and neither name is ever
+                        // written in place: re-running the recipe would only be a
+                        // defensive copy.
+                        let source_var = match &fn_ir.values[src].kind {
+                            ValueKind::Load { var } if from_alias => Some(var.clone()),
+                            _ => fresh_vars
+                                .iter()
+                                .filter(|(var, vid)| *var != &dst && **vid == recipe)
+                                .map(|(var, _)| var.clone())
+                                .min(),
+                        };
+                        let shares_owned_source = source_var.is_some_and(|var| {
+                            owned.owned_alloc_before(fn_ir, idx, &var).is_some()
+                                && !in_place_writes.may_write(&var)
+                                && !in_place_writes.may_write(&dst)
+                        });
+                        let should_clone = !shares_owned_source
+                            && (from_alias || recipe_already_bound_elsewhere || recipe_seen_before);
                         let new_src = if should_clone {
                             clone_value_metadata(fn_ir, recipe)
                         } else {
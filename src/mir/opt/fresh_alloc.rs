@@ -1,3 +1,4 @@
+use crate::mir::analyze::ownership::{self, OwnershipFacts};
 use crate::mir::def::EscapeStatus;
 use crate::mir::opt::loop_analysis::{LoopAnalyzer, LoopInfo, build_pred_map};
 use crate::mir::*;
@@ -24,6 +25,7 @@ fn optimize_with_analyzed_loops(fn_ir: &mut FnIR, loops: &[LoopInfo]) -> bool {
 
     // 2. Pre-allocation for simple map-style loops
     let preds = build_pred_map(fn_ir);
+    let ownership = OwnershipFacts::compute(fn_ir);
 
     for lp in loops {
         let preheaders: Vec<BlockId> = preds
@@ -96,6 +98,12 @@ fn optimize_with_analyzed_loops(fn_ir: &mut FnIR, loops: &[LoopInfo]) -> bool {
                 if already_prealloc.contains(&dest_var) || inserted.contains(&dest_var) {
                     continue;
                 }
+                // A destination that already uniquely owns an allocation of the
+                // loop's length is updated in place; re-allocating it would only
+                // add a defensive copy.
+                if owns_loop_sized_allocation(fn_ir, &ownership, lp, &dest_var, len_source) {
+                    continue;
+                }
 
                 plans.push((dest_var.clone(), *val, len_source));
                 inserted.insert(dest_var);
@@ -171,6 +179,33 @@ enum LenSource {
     Len(ValueId),
 }
 
+fn owns_loop_sized_allocation(
+    fn_ir: &FnIR,
+    ownership: &OwnershipFacts,
+    lp: &LoopInfo,
+    var: &str,
+    len_source: LenSource,
+) -> bool {
+    let Some(alloc) = ownership.owned_throughout(fn_ir, lp.body.iter().copied(), var) else {
+        return false;
+    };
+    let Some(alloc_len) = ownership::allocation_length(fn_ir, alloc) else {
+        return false;
+    };
+    match len_source {
+        LenSource::Len(n) => ownership::same_length_operand(fn_ir, n, alloc_len),
+        LenSource::Along(x) => match &fn_ir.values[alloc_len].kind {
+            ValueKind::Len { base } => {
+                *base == x
+                    || resolve_base_var(fn_ir, *base).is_some_and(|name| {
+                        resolve_base_var(fn_ir, x).as_deref() == Some(name.as_str())
+                    })
+            }
+            _ => false,
+        },
+    }
+}
+
 fn resolve_base_var(fn_ir: &FnIR, base: ValueId) -> Option<VarId> {
     if let ValueKind::Load { var } = &fn_ir.values[base].kind {
         return Some(var.clone());
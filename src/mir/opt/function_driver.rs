@@ -6,6 +6,38 @@ impl TachyonEngine {
         let _ = self.run_function_with_stats(fn_ir, &empty);
     }
 
+    /// Run the named passes over `fn_ir`, in order, outside any phase plan.
+    ///
+    /// Names are pass timing names (`simplify_cfg`, `gvn`, `licm`, ...). Each
+    /// pass runs once with its usual enablement gate and verification; whole
+    /// program passes such as `inline` are rejected.
+    pub fn run_function_passes_by_name(
+        &self,
+        fn_ir: &mut FnIR,
+        passes: &[&str],
+    ) -> Result<TachyonPulseStats, String> {
+        let specs = passes
+            .iter()
+            .map(|name| chronos::function_pass_by_name(name))
+            .collect::<Result<Vec<_>, _>>()?;
+        let loop_optimizer = loop_opt::MirLoopOptimizer::new();
+        let empty = FxHashSet::default();
+        let mut profile = TachyonRunProfile::default();
+        for spec in specs {
+            self.run_chronos_function_sequence(chronos::ChronosFunctionSequenceRequest {
+                stage: spec[0].stage,
+                passes: spec,
+                fn_ir,
+                loop_optimizer: &loop_optimizer,
+                user_call_whitelist: Some(&empty),
+                fresh_user_calls: None,
+                stats: &mut profile.pulse_stats,
+                timings: &mut profile.pass_timings,
+            });
+        }
+        Ok(profile.pulse_stats)
+    }
+
     pub fn run_function_with_stats(
         &self,
         fn_ir: &mut FnIR,
@@ -83,16 +83,30 @@ fn build_cse_context(fn_ir: &FnIR) -> CseContext {
 
     for block in &fn_ir.blocks {
         for instr in &block.instrs {
-            if let Instr::StoreIndex1D { base, .. }
-            | Instr::StoreIndex2D { base, .. }
-            | Instr::StoreIndex3D { base, .. } = instr
-            {
-                let cls = alias::alias_class_for_base(fn_ir, *base);
-                if matches!(cls, alias::AliasClass::Unknown) {
-                    ctx.has_unknown_mutation = true;
-                } else {
-                    ctx.mutated_aliases.insert(cls);
+            match instr {
+                Instr::StoreIndex1D { base, .. }
+                | Instr::StoreIndex2D { base, .. }
+                | Instr::StoreIndex3D { base, .. } => {
+                    let cls = alias::alias_class_for_base(fn_ir, *base);
+                    if matches!(cls, alias::AliasClass::Unknown) {
+                        ctx.has_unknown_mutation = true;
+                    } else {
+                        ctx.mutated_aliases.insert(cls);
+                    }
                 }
+                Instr::UnsafeRBlock {
+                    access: Some(access),
+                    ..
+                } => {
+                    ctx.mutated_aliases
+                        .extend(access.written().cloned().map(alias::AliasClass::Var));
+                }
+                Instr::UnsafeRBlock {
+                    read_only: false,
+                    access: None,
+                    ..
+                } => ctx.has_unknown_mutation = true,
+                _ => {}
             }
         }
     }
@@ -895,3 +895,72 @@ fn gvn_propagates_fieldset_cse_into_field_gets() {
         _ => panic!("sum value shape changed unexpectedly"),
     }
 }
+
+#[test]
+fn gvn_cse_index_reads_across_unsafe_r_block_that_writes_other_names() {
+    let index_sum_around_block = |code: &str| {
+        let mut fn_ir = one_block_fn("gvn_unsafe_r");
+        let base = fn_ir.add_value(
+            ValueKind::Load {
+                var: "xs".to_string(),
+            },
+            Span::dummy(),
+            Facts::empty(),
+            Some("xs".to_string()),
+        );
+        let one = fn_ir.add_value(
+            ValueKind::Const(Lit::Int(1)),
+            Span::dummy(),
+            Facts::empty(),
+            None,
+        );
+        let idx1 = fn_ir.add_value(
+            ValueKind::Index1D {
+                base,
+                idx: one,
+                is_safe: true,
+                is_na_safe: true,
+            },
+            Span::dummy(),
+            Facts::empty(),
+            None,
+        );
+        let idx2 = fn_ir.add_value(
+            ValueKind::Index1D {
+                base,
+                idx: one,
+                is_safe: true,
+                is_na_safe: true,
+            },
+            Span::dummy(),
+            Facts::empty(),
+            None,
+        );
+        fn_ir.blocks[fn_ir.entry].instrs.push(Instr::UnsafeRBlock {
+            code: code.to_string(),
+            read_only: false,
+            access: crate::mir::analyze::unsafe_r::analyze_unsafe_r(code),
+            span: Span::dummy(),
+        });
+        let sum = fn_ir.add_value(
+            ValueKind::Binary {
+                op: BinOp::Add,
+                lhs: idx1,
+                rhs: idx2,
+            },
+            Span::dummy(),
+            Facts::empty(),
+            None,
+        );
+        fn_ir.blocks[fn_ir.entry].term = Terminator::Return(Some(sum));
+        optimize(&mut fn_ir);
+        match fn_ir.values[sum].kind {
+            ValueKind::Binary { lhs, rhs, .. } => lhs == rhs,
+            _ => panic!("sum value shape changed unexpectedly"),
+        }
+    };
+
+    assert!(index_sum_around_block("tmp <- sum(ys); print(tmp)"));
+    assert!(!index_sum_around_block("xs[1] <- 0"));
+    assert!(!index_sum_around_block("assign('xs', 0)"));
+}
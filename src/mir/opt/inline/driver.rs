@@ -1,4 +1,5 @@
 use super::*;
+use crate::mir::opt::remarks;
 pub(crate) struct InlineSite<'a> {
     pub(crate) call_block: BlockId,
     pub(crate) instr_idx: usize,
@@ -103,33 +104,38 @@ impl MirInliner {
             for (idx, instr) in caller.blocks[bid].instrs.iter().enumerate() {
                 if let Some((callee_name, args, target_val, call_dst, call_span)) =
                     self.analyze_instr(caller, instr, all_fns)
+                    && let Some(callee) = all_fns.get(&callee_name)
                 {
-                    if callee_name == caller.name {
+                    let rejection = if callee_name == caller.name {
+                        Some(InlineRejection::Recursive)
+                    } else if let Some(rejection) = self.inline_rejection(callee, caller, policy) {
+                        Some(rejection)
+                    } else if !growth.can_inline(
+                        Self::fn_ir_size(caller),
+                        caller_growth_limit,
+                        Self::estimate_inline_growth(callee, policy),
+                    ) {
+                        Some(InlineRejection::GrowthBudget)
+                    } else {
+                        None
+                    };
+                    if let Some(rejection) = rejection {
+                        remarks::missed(
+                            "inline",
+                            caller,
+                            Self::remark_span(caller, call_span, target_val),
+                            format!(
+                                "call to {} not inlined: {}",
+                                Self::display_name(callee),
+                                rejection.label()
+                            ),
+                            rejection.code(),
+                        );
                         continue;
                     }
-
-                    if let Some(callee) = all_fns.get(&callee_name) {
-                        if callee.unsupported_dynamic {
-                            continue;
-                        }
-                        if !self.should_inline(callee, caller, policy) {
-                            continue;
-                        }
-                        let predicted_growth = Self::estimate_inline_growth(callee, policy);
-                        if !growth.can_inline(
-                            Self::fn_ir_size(caller),
-                            caller_growth_limit,
-                            predicted_growth,
-                        ) {
-                            continue;
-                        }
-                        if self.inline_callsite_cost(callee) > policy.max_callsite_cost {
-                            continue;
-                        }
-                        candidate =
-                            Some((bid, idx, callee_name, args, target_val, call_dst, call_span));
-                        break 'scan;
-                    }
+                    candidate =
+                        Some((bid, idx, callee_name, args, target_val, call_dst, call_span));
+                    break 'scan;
                 }
             }
         }
@@ -137,6 +143,12 @@ impl MirInliner {
         if let Some((bid, idx, callee_name, args, target_val, call_dst, call_span)) = candidate
             && let Some(callee) = all_fns.get(&callee_name)
         {
+            remarks::passed(
+                "inline",
+                caller,
+                Self::remark_span(caller, call_span, target_val),
+                format!("inlined call to {}", Self::display_name(callee)),
+            );
             let before_size = Self::fn_ir_size(caller);
             self.perform_inline(
                 caller,
@@ -199,37 +211,52 @@ impl MirInliner {
         caller: &FnIR,
         policy: &InlinePolicy,
     ) -> bool {
+        self.inline_rejection(target, caller, policy).is_none()
+    }
+
+    pub(crate) fn inline_rejection(
+        &self,
+        target: &FnIR,
+        caller: &FnIR,
+        policy: &InlinePolicy,
+    ) -> Option<InlineRejection> {
         if target.unsupported_dynamic {
-            return false;
+            return Some(InlineRejection::DynamicCallee);
         }
-        if target.name.starts_with("Sym_top_") {
-            return false;
+        if target.name.starts_with("Sym_top_") || target.name.starts_with("__rr_outline_") {
+            return Some(InlineRejection::TopLevelOrHelper);
         }
-        if target.name.starts_with("__rr_outline_") {
-            return false;
+        // Raw R resolves names in the callee's own frame.
+        if target.blocks.iter().any(|block| {
+            block
+                .instrs
+                .iter()
+                .any(|instr| matches!(instr, Instr::UnsafeRBlock { .. }))
+        }) {
+            return Some(InlineRejection::UnsafeRBlock);
         }
         let caller_instr_cnt: usize = caller.blocks.iter().map(|b| b.instrs.len()).sum();
         if caller_instr_cnt > policy.max_caller_instrs {
-            return false;
+            return Some(InlineRejection::CallerTooLarge);
         }
         let block_cnt = target.blocks.len();
         let instr_cnt: usize = target.blocks.iter().map(|b| b.instrs.len()).sum();
         if block_cnt > policy.max_blocks || instr_cnt > policy.max_instrs {
-            return false;
+            return Some(InlineRejection::CalleeTooLarge);
         }
         if caller_instr_cnt.saturating_add(instr_cnt) > policy.max_total_instrs {
-            return false;
+            return Some(InlineRejection::CallerTooLarge);
         }
         let kernel_cost = self.inline_kernel_cost(target);
         if kernel_cost > policy.max_kernel_cost {
-            return false;
+            return Some(InlineRejection::TooCostly);
         }
         if target
             .values
             .iter()
             .any(|value| matches!(value.kind, ValueKind::Call { .. }))
         {
-            return false;
+            return Some(InlineRejection::CalleeHasCalls);
         }
 
         let mut loop_edges = 0usize;
@@ -252,11 +279,24 @@ impl MirInliner {
             }
         }
         if !policy.allow_loops && loop_edges > 0 {
-            return false;
+            return Some(InlineRejection::CalleeHasLoops);
         }
 
         let cost = self.inline_callsite_cost(target);
-        cost <= policy.max_cost && cost <= policy.max_callsite_cost
+        (cost > policy.max_cost || cost > policy.max_callsite_cost)
+            .then_some(InlineRejection::TooCostly)
+    }
+
+    fn display_name(fn_ir: &FnIR) -> &str {
+        fn_ir.user_name.as_deref().unwrap_or(&fn_ir.name)
+    }
+
+    fn remark_span(caller: &FnIR, call_span: Span, call_val: ValueId) -> Span {
+        if call_span.start_line == 0 {
+            caller.values[call_val].span
+        } else {
+            call_span
+        }
     }
 
     pub(crate) fn inline_disabled() -> bool {
@@ -697,9 +737,16 @@ impl MirInliner {
             };
 
             let before_size = Self::fn_ir_size(caller);
+            let call_span = caller.values[val_id].span;
             if let Some(replacement) =
                 self.inline_call_value(caller, val_id, callee, ret_val, &args)
             {
+                remarks::passed(
+                    "inline",
+                    caller,
+                    call_span,
+                    format!("inlined call to {}", Self::display_name(callee)),
+                );
                 self.replace_uses(caller, val_id, replacement);
                 caller.values[val_id].kind = ValueKind::Const(crate::syntax::ast::Lit::Null);
                 let after_size = Self::fn_ir_size(caller);
@@ -15,6 +15,52 @@ pub(crate) struct InlinePolicy {
     pub(crate) allow_loops: bool,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum InlineRejection {
+    Recursive,
+    DynamicCallee,
+    TopLevelOrHelper,
+    UnsafeRBlock,
+    CallerTooLarge,
+    CalleeTooLarge,
+    CalleeHasCalls,
+    CalleeHasLoops,
+    TooCostly,
+    GrowthBudget,
+}
+
+impl InlineRejection {
+    pub(crate) const fn label(self) -> &'static str {
+        match self {
+            Self::Recursive => "recursive call",
+            Self::DynamicCallee => "callee uses dynamic features",
+            Self::TopLevelOrHelper => "callee is top-level code or an outlined helper",
+            Self::UnsafeRBlock => "callee contains an unsafe R block",
+            Self::CallerTooLarge => "caller is too large",
+            Self::CalleeTooLarge => "callee is too large",
+            Self::CalleeHasCalls => "callee makes calls of its own",
+            Self::CalleeHasLoops => "callee contains loops",
+            Self::TooCostly => "call site cost exceeds the inline limit",
+            Self::GrowthBudget => "code growth budget exhausted",
+        }
+    }
+
+    pub(crate) const fn code(self) -> &'static str {
+        match self {
+            Self::Recursive => "recursive",
+            Self::DynamicCallee => "dynamic-callee",
+            Self::TopLevelOrHelper => "not-inlinable",
+            Self::UnsafeRBlock => "unsafe-r-block",
+            Self::CallerTooLarge => "caller-too-large",
+            Self::CalleeTooLarge => "callee-too-large",
+            Self::CalleeHasCalls => "callee-has-calls",
+            Self::CalleeHasLoops => "callee-has-loops",
+            Self::TooCostly => "too-costly",
+            Self::GrowthBudget => "growth-budget",
+        }
+    }
+}
+
 pub(crate) struct InlineGrowthBudget {
     pub(crate) total_ir: usize,
     pub(crate) max_total_ir: usize,
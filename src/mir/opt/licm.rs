@@ -1,5 +1,7 @@
+use crate::mir::analyze::ownership::{self, OwnershipFacts};
 use crate::mir::analyze::{alias, effects};
 use crate::mir::opt::loop_analysis::{LoopAnalyzer, LoopInfo};
+use crate::mir::opt::remarks;
 use crate::mir::*;
 
 pub struct MirLicm;
@@ -45,6 +47,7 @@ impl MirLicm {
     ) -> bool {
         let mut changed = false;
         let loop_written_vars = self.collect_loop_written_vars(fn_ir, loop_info);
+        let ownership = OwnershipFacts::analyze_function(fn_ir);
 
         // Build Value -> Block map
         let mut val_to_bb = std::collections::HashMap::new();
@@ -77,14 +80,23 @@ impl MirLicm {
                     Instr::StoreIndex1D { base, .. }
                     | Instr::StoreIndex2D { base, .. }
                     | Instr::StoreIndex3D { base, .. } => {
-                        let cls = alias::alias_class_for_base(fn_ir, *base);
+                        let cls =
+                            ownership::alias_class_with_ownership(fn_ir, &ownership, bb, *base);
                         if matches!(cls, alias::AliasClass::Unknown) {
                             loop_has_unknown_mutation = true;
                         } else {
                             loop_mutated_aliases.insert(cls);
                         }
                     }
-                    Instr::UnsafeRBlock { .. } => {
+                    // An analyzed block only rebinds the names it assigns.
+                    Instr::UnsafeRBlock {
+                        access: Some(access),
+                        ..
+                    } => {
+                        loop_mutated_aliases
+                            .extend(access.written().cloned().map(alias::AliasClass::Var));
+                    }
+                    Instr::UnsafeRBlock { access: None, .. } => {
                         loop_has_unknown_mutation = true;
                     }
                     _ => {}
@@ -106,6 +118,8 @@ impl MirLicm {
         }
 
         let loop_ctx = LoopEffectCtx {
+            header: loop_info.header,
+            ownership,
             mutated_aliases: loop_mutated_aliases,
             has_unknown_mutation: loop_has_unknown_mutation,
             has_impure_call: loop_has_impure_call,
@@ -168,6 +182,16 @@ impl MirLicm {
 
         if pre_headers.len() != 1 {
             // No unique pre-header (e.g. multiple entries or none). Abort.
+            remarks::missed(
+                "licm",
+                fn_ir,
+                remarks::loop_span(fn_ir, loop_info),
+                format!(
+                    "{} loop-invariant expression(s) not hoisted: loop has no unique preheader",
+                    hoist_candidates.len()
+                ),
+                "no-preheader",
+            );
             return false;
         }
         let pre_header = pre_headers[0];
@@ -200,6 +224,12 @@ impl MirLicm {
                     src: val_id_to_hoist,
                     span: crate::utils::Span::default(),
                 });
+                remarks::passed(
+                    "licm",
+                    fn_ir,
+                    fn_ir.values[val_id].span,
+                    "hoisted loop-invariant expression out of the loop",
+                );
                 changed = true;
             }
 
@@ -319,7 +349,11 @@ impl MirLicm {
                         }
                     },
                     Instr::Eval { .. } => {}
-                    Instr::UnsafeRBlock { .. } => {}
+                    Instr::UnsafeRBlock { access, .. } => {
+                        if let Some(access) = access {
+                            vars.extend(access.written().cloned());
+                        }
+                    }
                 }
             }
         }
@@ -477,7 +511,7 @@ impl MirLicm {
                 if loop_ctx.has_impure_call || loop_ctx.has_unknown_mutation {
                     return false;
                 }
-                let cls = alias::alias_class_for_base(fn_ir, *base);
+                let cls = loop_ctx.alias_class(fn_ir, *base);
                 if matches!(cls, alias::AliasClass::Unknown) {
                     return false;
                 }
@@ -487,7 +521,7 @@ impl MirLicm {
                 if loop_ctx.has_impure_call || loop_ctx.has_unknown_mutation {
                     return false;
                 }
-                let cls = alias::alias_class_for_base(fn_ir, *base);
+                let cls = loop_ctx.alias_class(fn_ir, *base);
                 if matches!(cls, alias::AliasClass::Unknown) {
                     return false;
                 }
@@ -502,7 +536,7 @@ impl MirLicm {
                 if loop_ctx.has_impure_call || loop_ctx.has_unknown_mutation {
                     return false;
                 }
-                let cls = alias::alias_class_for_base(fn_ir, *base);
+                let cls = loop_ctx.alias_class(fn_ir, *base);
                 if matches!(cls, alias::AliasClass::Unknown) {
                     return false;
                 }
@@ -520,7 +554,7 @@ impl MirLicm {
                 if loop_ctx.has_impure_call || loop_ctx.has_unknown_mutation {
                     return false;
                 }
-                let cls = alias::alias_class_for_base(fn_ir, *base);
+                let cls = loop_ctx.alias_class(fn_ir, *base);
                 if matches!(cls, alias::AliasClass::Unknown) {
                     return false;
                 }
@@ -563,7 +597,15 @@ impl MirLicm {
 
 #[derive(Debug)]
 struct LoopEffectCtx {
+    header: BlockId,
+    ownership: OwnershipFacts,
     mutated_aliases: std::collections::HashSet<alias::AliasClass>,
     has_unknown_mutation: bool,
     has_impure_call: bool,
 }
+
+impl LoopEffectCtx {
+    fn alias_class(&self, fn_ir: &FnIR, base: ValueId) -> alias::AliasClass {
+        ownership::alias_class_with_ownership(fn_ir, &self.ownership, self.header, base)
+    }
+}
@@ -120,6 +120,7 @@ impl<'a> LoopAnalyzer<'a> {
             limit_adjust,
             is_seq_len,
             is_seq_along,
+            owned_full_dests: FxHashSet::default(),
         })
     }
 
@@ -14,6 +14,10 @@ pub struct LoopInfo {
     pub iv: Option<InductionVar>,
     pub limit: Option<ValueId>,
     pub limit_adjust: i64,
+    /// Destinations that uniquely own an allocation sized to this loop's trip
+    /// count. Left empty by loop discovery; vectorization fills it from one
+    /// ownership analysis per function.
+    pub owned_full_dests: FxHashSet<VarId>,
 }
 
 #[derive(Debug, Clone)]
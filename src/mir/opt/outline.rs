@@ -44,10 +44,31 @@ pub(crate) fn optimize_program(
             stats.outline_skipped += 1;
             continue;
         };
+        let span = parent.blocks[candidate.block]
+            .instrs
+            .get(candidate.start)
+            .map(Instr::span)
+            .unwrap_or(parent.span);
         let Some(helper) = extract::extract_helper(parent, &candidate, helper_name) else {
+            remarks::missed(
+                "outline",
+                parent,
+                span,
+                "region not outlined: helper extraction failed",
+                "extract-failed",
+            );
             stats.outline_skipped += 1;
             continue;
         };
+        remarks::passed(
+            "outline",
+            parent,
+            span,
+            format!(
+                "outlined {} instructions into a helper",
+                candidate.region_len()
+            ),
+        );
         let helper_name = helper.name.clone();
         all_fns.insert(helper_name, helper);
         stats.outline_applied += 1;
@@ -372,7 +372,13 @@ fn instr_read_vars(fn_ir: &FnIR, instr: &Instr) -> Vec<VarId> {
             vars.extend(value_read_vars(fn_ir, *val));
             vars
         }
-        Instr::UnsafeRBlock { .. } => Vec::new(),
+        Instr::UnsafeRBlock { code, access, .. } => {
+            let mut vars = FxHashSet::default();
+            TachyonEngine::collect_unsafe_r_block_vars(code, access.as_ref(), &mut vars);
+            let mut vars: Vec<VarId> = vars.into_iter().collect();
+            vars.sort();
+            vars
+        }
     }
 }
 
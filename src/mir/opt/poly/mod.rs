@@ -6,6 +6,7 @@ mod codegen_generic;
 mod cost;
 mod dependence_backend;
 mod isl;
+mod parallel_proof;
 mod schedule;
 mod scop;
 mod tree;
@@ -13,6 +14,7 @@ mod verify;
 
 use crate::mir::FnIR;
 use crate::mir::opt::loop_analysis::{LoopAnalyzer, LoopInfo};
+use crate::mir::opt::remarks;
 use std::cmp::Reverse;
 
 use backend::make_backend_from_env;
@@ -20,11 +22,12 @@ use codegen::lower_schedule_tree;
 pub(crate) use codegen_generic::is_generated_loop_iv_name as is_generated_poly_loop_var_name;
 use cost::{estimate_schedule_cost, r_backend_code_size_reject_reason};
 use dependence_backend::{DependenceState, make_dependence_backend};
+pub use parallel_proof::prove_parallel_loop;
 use schedule::SchedulePlanKind;
 pub use scop::{LoopDimension, PolyStmt, PolyStmtKind, ScopExtractionFailure, ScopRegion};
 use verify::{
     build_certificate, dump_certificate, dump_rejection, dump_root_from_env, format_reject_reason,
-    format_scop_summary,
+    format_scop_summary, reject_reason_code,
 };
 
 #[derive(Debug, Default, Clone, Copy)]
@@ -213,10 +216,31 @@ pub(crate) fn optimize_with_stats_inner(
         stats.schedule_backend_hint_selected += iter_stats.schedule_backend_hint_selected;
 
         for lp in &loops {
-            let Ok(scop) = scop::extract_scop_region(fn_ir, lp, &loops) else {
-                continue;
+            let span = remarks::loop_span(fn_ir, lp);
+            let scop = match scop::extract_scop_region(fn_ir, lp, &loops) {
+                Ok(scop) => scop,
+                Err(reason) => {
+                    remarks::missed(
+                        "poly",
+                        fn_ir,
+                        span,
+                        format!(
+                            "loop is not a static control region: {}",
+                            format_reject_reason(reason)
+                        ),
+                        reject_reason_code(reason),
+                    );
+                    continue;
+                }
             };
             if poly_auto_mode_enabled() && scop.dimensions.len() == 1 {
+                remarks::missed(
+                    "poly",
+                    fn_ir,
+                    span,
+                    "single-dimension loop left to the vectorizer in auto mode",
+                    "single-dimension",
+                );
                 continue;
             }
             let backend = make_backend_from_env();
@@ -225,6 +249,13 @@ pub(crate) fn optimize_with_stats_inner(
             let schedule_tree = backend.build_schedule_tree(&scop, &deps);
             let schedule = schedule_tree.to_primary_plan();
             if let Some(reason) = r_backend_code_size_reject_reason(&scop, &schedule) {
+                remarks::missed(
+                    "poly",
+                    fn_ir,
+                    span,
+                    format!("{:?} schedule rejected: {}", schedule.kind, reason),
+                    "code-size",
+                );
                 if poly_trace_enabled() {
                     eprintln!(
                         "   [poly] {} loop header={} latch={} skip schedule={:?}: {}",
@@ -245,11 +276,38 @@ pub(crate) fn optimize_with_stats_inner(
             }
             let emitted = lower_schedule_tree(fn_ir, lp, &scop, &schedule_tree).emitted;
             if emitted {
+                remarks::passed(
+                    "poly",
+                    fn_ir,
+                    span,
+                    format!(
+                        "applied {:?} schedule ({})",
+                        schedule.kind,
+                        format_scop_summary(&scop)
+                    ),
+                );
                 stats.schedule_applied += 1;
                 stats.record_schedule_applied(schedule.kind);
                 changed = true;
                 break;
             }
+            if schedule.kind == SchedulePlanKind::None {
+                remarks::missed(
+                    "poly",
+                    fn_ir,
+                    span,
+                    "no profitable schedule for this region",
+                    "no-schedule",
+                );
+            } else {
+                remarks::missed(
+                    "poly",
+                    fn_ir,
+                    span,
+                    format!("{:?} schedule could not be lowered", schedule.kind),
+                    "not-lowered",
+                );
+            }
         }
     }
     stats
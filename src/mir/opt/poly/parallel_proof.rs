@@ -0,0 +1,56 @@
+//! Dependence proof for explicit `parallel for` loops.
+//!
+//! The lifted loop body must form a SCoP whose dependences the configured
+//! backend proves legal, and arrays written as slices (`out[i] <- ...`) may
+//! only be touched at the loop variable so chunks never see each other's
+//! elements.
+
+use super::affine::AffineSymbol;
+use super::backend::make_backend_from_env;
+use super::dependence_backend::make_dependence_backend;
+use super::scop::extract_scop_region;
+use super::verify::format_reject_reason;
+use crate::mir::FnIR;
+use crate::mir::opt::loop_analysis::LoopAnalyzer;
+
+/// Prove that the iterations of the outermost loop of `fn_ir` are
+/// independent apart from recognized reductions. `slice_vars` name the arrays
+/// each chunk writes only at its own indices. The error is a short reason for
+/// the user.
+pub fn prove_parallel_loop(fn_ir: &FnIR, slice_vars: &[String]) -> Result<(), String> {
+    let loops = LoopAnalyzer::new(fn_ir).find_loops();
+    let Some(outer) = loops.iter().max_by_key(|lp| lp.body.len()) else {
+        return Err("loop shape is not recognized".to_string());
+    };
+    let scop = extract_scop_region(fn_ir, outer, &loops).map_err(|reason| {
+        format!(
+            "loop body is not analyzable ({})",
+            format_reject_reason(reason)
+        )
+    })?;
+    let Some(iv) = scop.dimensions.first().map(|dim| dim.iv_name.clone()) else {
+        return Err("loop shape is not recognized".to_string());
+    };
+    let at_iv = AffineSymbol::LoopIv(iv);
+    for access in scop.statements.iter().flat_map(|stmt| &stmt.accesses) {
+        if !slice_vars.contains(&access.memref.name) {
+            continue;
+        }
+        let identity = matches!(
+            access.subscripts.as_slice(),
+            [sub] if sub.constant == 0 && sub.terms.len() == 1 && sub.terms.get(&at_iv) == Some(&1)
+        );
+        if !identity {
+            return Err(format!(
+                "`{}` is accessed at an index other than the loop variable",
+                access.memref.name
+            ));
+        }
+    }
+    let backend = make_backend_from_env();
+    let deps = make_dependence_backend(backend.as_ref()).analyze(fn_ir, &scop);
+    if !deps.legality_is_proven() {
+        return Err("iterations may depend on each other".to_string());
+    }
+    Ok(())
+}
@@ -44,6 +44,18 @@ pub fn format_reject_reason(reason: ScopExtractionFailure) -> &'static str {
     }
 }
 
+/// Stable code for optimization remarks.
+pub fn reject_reason_code(reason: ScopExtractionFailure) -> &'static str {
+    match reason {
+        ScopExtractionFailure::UnsupportedCfgShape => "unsupported-cfg-shape",
+        ScopExtractionFailure::MissingInductionVar => "missing-induction-var",
+        ScopExtractionFailure::NonAffineLoopBound => "non-affine-bound",
+        ScopExtractionFailure::NonAffineAccess => "non-affine-access",
+        ScopExtractionFailure::EffectfulStatement => "effectful-statement",
+        ScopExtractionFailure::UnsupportedNestedLoop => "unsupported-nested-loop",
+    }
+}
+
 pub fn format_scop_summary(scop: &ScopRegion) -> String {
     let access_count = scop
         .statements
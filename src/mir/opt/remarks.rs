@@ -0,0 +1,264 @@
+//! Optimization remarks: why each pass did or did not transform a loop,
+//! call site or region, tied to the RR source span it came from.
+//!
+//! Passes report through [`passed`], [`missed`] and [`analysis`], which do
+//! nothing unless a compile runs inside [`collect_opt_remarks`]. The sink is
+//! thread-local; the compiler scheduler reinstalls it on worker threads with
+//! [`current_sink`] and [`with_sink`].
+
+use crate::mir::opt::loop_analysis::LoopInfo;
+use crate::mir::{FnIR, Instr, Terminator};
+use crate::utils::Span;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RemarkKind {
+    /// The pass transformed the code.
+    Passed,
+    /// The pass looked at the code and declined, with a reason.
+    Missed,
+    /// A fact the pass established that explains later decisions.
+    Analysis,
+}
+
+impl RemarkKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Passed => "passed",
+            Self::Missed => "missed",
+            Self::Analysis => "analysis",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OptRemark {
+    /// Source file of the function, when source analysis recorded one.
+    pub file: Option<PathBuf>,
+    pub span: Span,
+    pub pass: &'static str,
+    pub kind: RemarkKind,
+    /// User-facing function name.
+    pub function: String,
+    pub message: String,
+    /// Machine-readable reason code for missed remarks.
+    pub reason: Option<String>,
+}
+
+impl OptRemark {
+    fn sort_key(&self) -> impl Ord + '_ {
+        (
+            &self.file,
+            self.span.start_line,
+            self.span.start_col,
+            self.pass,
+            self.kind,
+            &self.function,
+            &self.message,
+        )
+    }
+}
+
+#[derive(Default)]
+struct RemarkLog {
+    /// Remarks with the MIR name of the function they came from.
+    remarks: Vec<(String, OptRemark)>,
+    /// MIR function name -> defining source file.
+    files: BTreeMap<String, PathBuf>,
+}
+
+/// Shared remark log; cloned onto worker threads.
+#[derive(Clone, Default)]
+pub struct RemarkSink(Arc<Mutex<RemarkLog>>);
+
+impl RemarkSink {
+    fn lock(&self) -> MutexGuard<'_, RemarkLog> {
+        self.0
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+thread_local! {
+    static ACTIVE_SINK: RefCell<Option<RemarkSink>> = const { RefCell::new(None) };
+}
+
+/// Run `f` with remark collection enabled on this thread and return the
+/// remarks it produced, deduplicated and ordered by file and position.
+pub fn collect_opt_remarks<T>(f: impl FnOnce() -> T) -> (T, Vec<OptRemark>) {
+    let sink = RemarkSink::default();
+    let out = with_sink(Some(sink.clone()), f);
+    let log = std::mem::take(&mut *sink.lock());
+    let mut remarks = settle_verdicts(log.remarks)
+        .into_iter()
+        .map(|(fn_name, mut remark)| {
+            remark.file = log.files.get(&fn_name).cloned();
+            remark
+        })
+        .collect::<Vec<_>>();
+    remarks.sort_by(|a, b| a.sort_key().cmp(&b.sort_key()));
+    remarks.dedup();
+    (out, remarks)
+}
+
+/// Fixed-point pipelines run a pass over the same loop once per iteration.
+/// Keep one verdict per function, pass and position: a transformation wins
+/// over any miss, otherwise the last reason given stands.
+fn settle_verdicts(remarks: Vec<(String, OptRemark)>) -> Vec<(String, OptRemark)> {
+    let slot = |(fn_name, remark): &(String, OptRemark)| {
+        (
+            fn_name.clone(),
+            remark.pass,
+            remark.span.start_line,
+            remark.span.start_col,
+            remark.kind == RemarkKind::Analysis,
+        )
+    };
+    let mut out: Vec<(String, OptRemark)> = Vec::new();
+    for entry in remarks {
+        let key = slot(&entry);
+        let same = |other: &(String, OptRemark)| slot(other) == key;
+        match entry.1.kind {
+            RemarkKind::Passed => {
+                out.retain(|other| !(same(other) && other.1.kind == RemarkKind::Missed))
+            }
+            RemarkKind::Missed => {
+                if out
+                    .iter()
+                    .any(|other| same(other) && other.1.kind == RemarkKind::Passed)
+                {
+                    continue;
+                }
+                out.retain(|other| !same(other));
+            }
+            RemarkKind::Analysis => out.retain(|other| !same(other)),
+        }
+        out.push(entry);
+    }
+    out
+}
+
+/// The sink installed on this thread, for handing to worker threads.
+pub(crate) fn current_sink() -> Option<RemarkSink> {
+    ACTIVE_SINK.with(|slot| slot.borrow().clone())
+}
+
+/// Run `f` with `sink` installed on this thread.
+pub(crate) fn with_sink<T>(sink: Option<RemarkSink>, f: impl FnOnce() -> T) -> T {
+    let prev = ACTIVE_SINK.with(|slot| slot.replace(sink));
+    let out = f();
+    ACTIVE_SINK.with(|slot| slot.replace(prev));
+    out
+}
+
+pub(crate) fn remarks_enabled() -> bool {
+    ACTIVE_SINK.with(|slot| slot.borrow().is_some())
+}
+
+/// Record the source file that defines MIR function `fn_name`.
+pub(crate) fn note_function_source(fn_name: &str, path: PathBuf) {
+    if let Some(sink) = current_sink() {
+        sink.lock().files.insert(fn_name.to_string(), path);
+    }
+}
+
+/// Start another run of `pass` over `fn_ir`. The fixed-point pipeline reruns
+/// loop passes on code earlier runs may have reshaped, so misses from those
+/// runs are dropped; transformations they reported stay.
+pub(crate) fn begin_pass_run(pass: &'static str, fn_ir: &FnIR) {
+    if let Some(sink) = current_sink() {
+        sink.lock().remarks.retain(|(fn_name, remark)| {
+            remark.kind != RemarkKind::Missed || remark.pass != pass || *fn_name != fn_ir.name
+        });
+    }
+}
+
+pub(crate) fn passed(pass: &'static str, fn_ir: &FnIR, span: Span, message: impl Into<String>) {
+    record(pass, RemarkKind::Passed, fn_ir, span, message.into(), None);
+}
+
+pub(crate) fn missed(
+    pass: &'static str,
+    fn_ir: &FnIR,
+    span: Span,
+    message: impl Into<String>,
+    reason: &str,
+) {
+    record(
+        pass,
+        RemarkKind::Missed,
+        fn_ir,
+        span,
+        message.into(),
+        Some(reason.to_string()),
+    );
+}
+
+pub(crate) fn analysis(pass: &'static str, fn_ir: &FnIR, span: Span, message: impl Into<String>) {
+    record(
+        pass,
+        RemarkKind::Analysis,
+        fn_ir,
+        span,
+        message.into(),
+        None,
+    );
+}
+
+fn record(
+    pass: &'static str,
+    kind: RemarkKind,
+    fn_ir: &FnIR,
+    span: Span,
+    message: String,
+    reason: Option<String>,
+) {
+    let Some(sink) = current_sink() else {
+        return;
+    };
+    let span = if is_unknown_span(span) {
+        fn_ir.span
+    } else {
+        span
+    };
+    let remark = OptRemark {
+        file: None,
+        span,
+        pass,
+        kind,
+        function: fn_ir
+            .user_name
+            .clone()
+            .unwrap_or_else(|| fn_ir.name.clone()),
+        message,
+        reason,
+    };
+    sink.lock().remarks.push((fn_ir.name.clone(), remark));
+}
+
+fn is_unknown_span(span: Span) -> bool {
+    span.start_line == 0
+}
+
+/// Best source span for a loop: its exit condition, else the first
+/// statement of its body, else the function.
+pub(crate) fn loop_span(fn_ir: &FnIR, lp: &LoopInfo) -> Span {
+    if let Some(block) = fn_ir.blocks.get(lp.header)
+        && let Terminator::If { cond, .. } = block.term
+        && let Some(value) = fn_ir.values.get(cond)
+        && !is_unknown_span(value.span)
+    {
+        return value.span;
+    }
+    let mut body = lp.body.iter().copied().collect::<Vec<_>>();
+    body.sort_unstable();
+    body.into_iter()
+        .filter_map(|bid| fn_ir.blocks.get(bid))
+        .flat_map(|block| block.instrs.iter().map(Instr::span))
+        .filter(|span| !is_unknown_span(*span))
+        .min_by_key(|span| span.start_byte)
+        .unwrap_or(fn_ir.span)
+}
@@ -39,6 +39,7 @@ pub(crate) fn scalarized_loaded_vars_required_by_non_alias_uses(
     field_maps: &FxHashMap<ValueId, SroaFieldMap>,
 ) -> FxHashSet<String> {
     let mut roots = Vec::new();
+    let mut unsafe_r_reads = FxHashSet::default();
     for block in &fn_ir.blocks {
         for instr in &block.instrs {
             match instr {
@@ -57,7 +58,13 @@ pub(crate) fn scalarized_loaded_vars_required_by_non_alias_uses(
                 } => {
                     roots.extend([*base, *i, *j, *k, *val]);
                 }
-                Instr::UnsafeRBlock { .. } => {}
+                Instr::UnsafeRBlock { code, access, .. } => {
+                    TachyonEngine::collect_unsafe_r_block_vars(
+                        code,
+                        access.as_ref(),
+                        &mut unsafe_r_reads,
+                    );
+                }
             }
         }
         match &block.term {
@@ -69,6 +76,7 @@ pub(crate) fn scalarized_loaded_vars_required_by_non_alias_uses(
 
     let unique_assignments = unique_var_assignments(fn_ir);
     let mut required = loaded_vars_in_values(fn_ir, roots);
+    required.extend(unsafe_r_reads);
     let mut changed = true;
     while changed {
         changed = false;
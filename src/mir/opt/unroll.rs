@@ -17,14 +17,42 @@ pub fn optimize(fn_ir: &mut FnIR, engine: &TachyonEngine, stats: &mut TachyonPul
     let loops = loop_analysis::LoopAnalyzer::new(fn_ir).find_loops();
     let mut applied = 0usize;
     for lp in loops {
-        let Some(candidate) = analysis::analyze(fn_ir, &lp, policy) else {
-            continue;
+        let span = remarks::loop_span(fn_ir, &lp);
+        let candidate = match analysis::analyze(fn_ir, &lp, policy) {
+            Ok(candidate) => candidate,
+            Err(reason) => {
+                remarks::missed(
+                    "unroll",
+                    fn_ir,
+                    span,
+                    format!("loop not unrolled: {}", reason.label()),
+                    reason.code(),
+                );
+                continue;
+            }
         };
         stats.unroll_candidates += 1;
         if transform::apply(fn_ir, &candidate) {
+            let how = match candidate.mode {
+                analysis::UnrollMode::Full => "fully unrolled".to_string(),
+                analysis::UnrollMode::Partial { factor } => format!("unrolled by {factor}"),
+            };
+            remarks::passed(
+                "unroll",
+                fn_ir,
+                span,
+                format!("loop {} (trip count {})", how, candidate.trip_count),
+            );
             stats.unroll_applied += 1;
             applied += 1;
         } else {
+            remarks::missed(
+                "unroll",
+                fn_ir,
+                span,
+                "loop not unrolled: rewrite failed",
+                "rewrite-failed",
+            );
             stats.unroll_skipped += 1;
         }
     }
@@ -16,45 +16,93 @@ pub(crate) enum UnrollMode {
     Partial { factor: usize },
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum UnrollSkipReason {
+    LoopShape,
+    NonUnitStep,
+    UnknownTripCount,
+    TripCountOutOfRange,
+    UnsafeRBlock,
+    LoopCarriedValue,
+    GrowthLimit,
+    NoPreheader,
+}
+
+impl UnrollSkipReason {
+    pub(crate) const fn label(self) -> &'static str {
+        match self {
+            Self::LoopShape => "loop is not a single-block counted loop",
+            Self::NonUnitStep => "induction variable does not step by 1",
+            Self::UnknownTripCount => "trip count is not a compile-time constant",
+            Self::TripCountOutOfRange => "trip count outside the unroll limits",
+            Self::UnsafeRBlock => "body contains an unsafe R block",
+            Self::LoopCarriedValue => "body reads a loop-carried value",
+            Self::GrowthLimit => "no unroll factor fits the code growth limit",
+            Self::NoPreheader => "loop has no entry from outside",
+        }
+    }
+
+    pub(crate) const fn code(self) -> &'static str {
+        match self {
+            Self::LoopShape => "loop-shape",
+            Self::NonUnitStep => "non-unit-step",
+            Self::UnknownTripCount => "unknown-trip-count",
+            Self::TripCountOutOfRange => "trip-count-out-of-range",
+            Self::UnsafeRBlock => "unsafe-r-block",
+            Self::LoopCarriedValue => "loop-carried-value",
+            Self::GrowthLimit => "growth-limit",
+            Self::NoPreheader => "no-preheader",
+        }
+    }
+}
+
 pub(crate) fn analyze(
     fn_ir: &FnIR,
     lp: &loop_analysis::LoopInfo,
     policy: UnrollPolicy,
-) -> Option<UnrollCandidate> {
+) -> Result<UnrollCandidate, UnrollSkipReason> {
+    use UnrollSkipReason as Skip;
     if lp.exits.len() != 1 || lp.body.len() != 2 || lp.limit_adjust != 0 {
-        return None;
+        return Err(Skip::LoopShape);
     }
-    let iv = lp.iv.as_ref()?;
+    let iv = lp.iv.as_ref().ok_or(Skip::LoopShape)?;
     if iv.step != 1 || iv.step_op != BinOp::Add {
-        return None;
+        return Err(Skip::NonUnitStep);
     }
-    let trip_count = constant_trip_count(fn_ir, lp, iv)?;
+    let trip_count = constant_trip_count(fn_ir, lp, iv).ok_or(Skip::UnknownTripCount)?;
     if trip_count < 2 || trip_count > policy.max_trip.max(policy.max_partial_trip) {
-        return None;
+        return Err(Skip::TripCountOutOfRange);
     }
-    let (body, exit) = loop_body_and_exit(fn_ir, lp.header, &lp.body)?;
+    let (body, exit) = loop_body_and_exit(fn_ir, lp.header, &lp.body).ok_or(Skip::LoopShape)?;
     if body != lp.latch || lp.exits[0] != exit {
-        return None;
+        return Err(Skip::LoopShape);
     }
-    if !matches!(fn_ir.blocks.get(body)?.term, Terminator::Goto(target) if target == lp.header) {
-        return None;
+    if !matches!(fn_ir.blocks.get(body).map(|block| &block.term), Some(Terminator::Goto(target)) if *target == lp.header)
+    {
+        return Err(Skip::LoopShape);
     }
     if fn_ir.blocks[body]
         .instrs
         .iter()
         .any(|instr| matches!(instr, Instr::UnsafeRBlock { .. }))
     {
-        return None;
+        return Err(Skip::UnsafeRBlock);
     }
     if !body_values_are_unrollable(fn_ir, body) {
-        return None;
+        return Err(Skip::LoopCarriedValue);
     }
-    let mode = choose_unroll_mode(fn_ir, body, trip_count, policy)?;
+    let mode = choose_unroll_mode(fn_ir, body, trip_count, policy).ok_or(
+        if trip_count <= policy.max_partial_trip && policy.partial_enabled {
+            Skip::GrowthLimit
+        } else {
+            Skip::TripCountOutOfRange
+        },
+    )?;
     let outside_preds = outside_predecessors(fn_ir, lp.header, &lp.body);
     if outside_preds.is_empty() {
-        return None;
+        return Err(Skip::NoPreheader);
     }
-    Some(UnrollCandidate {
+    Ok(UnrollCandidate {
         header: lp.header,
         body,
         exit,
@@ -119,10 +119,12 @@ fn clone_instr(fn_ir: &mut FnIR, instr: &Instr, remap: &mut FxHashMap<ValueId, V
         Instr::UnsafeRBlock {
             code,
             read_only,
+            access,
             span,
         } => Instr::UnsafeRBlock {
             code: code.clone(),
             read_only: *read_only,
+            access: access.clone(),
             span: *span,
         },
     }
@@ -1,4 +1,5 @@
 use super::*;
+use crate::mir::analyze::ownership::{self, OwnershipFacts};
 pub(crate) fn is_loop_compatible_base(lp: &LoopInfo, fn_ir: &FnIR, base: ValueId) -> bool {
     if loop_matches_vec(lp, fn_ir, base) {
         return true;
@@ -18,7 +19,47 @@ pub(crate) fn loop_covers_whole_destination(
     base: ValueId,
     start: ValueId,
 ) -> bool {
-    is_const_one(fn_ir, start) && loop_matches_full_base(lp, fn_ir, base)
+    is_const_one(fn_ir, start)
+        && (loop_matches_full_base(lp, fn_ir, base) || loop_fills_owned_allocation(lp, fn_ir, base))
+}
+
+/// Flow-sensitive fallback for whole-destination writes: the destination
+/// uniquely owns one allocation throughout the loop and that allocation's
+/// pinned length is the loop trip count, so the vector result can replace it
+/// directly instead of going through a defensive slice assignment.
+pub(crate) fn loop_fills_owned_allocation(lp: &LoopInfo, fn_ir: &FnIR, base: ValueId) -> bool {
+    resolve_base_var(fn_ir, base).is_some_and(|var| lp.owned_full_dests.contains(&var))
+}
+
+/// Fills [`LoopInfo::owned_full_dests`] for every loop from a single
+/// ownership analysis of `fn_ir`.
+pub(crate) fn annotate_owned_full_dests(fn_ir: &FnIR, loops: &mut [LoopInfo]) {
+    let ownership = OwnershipFacts::compute(fn_ir);
+    for lp in loops {
+        lp.owned_full_dests.clear();
+        if lp.limit_adjust != 0 {
+            continue;
+        }
+        let Some(limit) = lp.is_seq_len.or(lp.limit) else {
+            continue;
+        };
+        let Some(candidates) = ownership.owned_at_entry(lp.header) else {
+            continue;
+        };
+        let owned = candidates
+            .keys()
+            .filter(|var| {
+                ownership
+                    .owned_throughout(fn_ir, lp.body.iter().copied(), var)
+                    .and_then(|alloc| ownership::allocation_length(fn_ir, alloc))
+                    .is_some_and(|alloc_len| {
+                        ownership::same_length_operand(fn_ir, limit, alloc_len)
+                    })
+            })
+            .cloned()
+            .collect();
+        lp.owned_full_dests = owned;
+    }
 }
 
 pub(crate) fn loop_length_key(lp: &LoopInfo, fn_ir: &FnIR) -> Option<ValueId> {
@@ -1,6 +1,6 @@
 use super::analysis::{
-    choose_call_map_lowering, estimate_loop_trip_count_hint, estimate_vector_plan_helper_cost,
-    induction_origin_var, loop_vectorize_skip_reason,
+    annotate_owned_full_dests, choose_call_map_lowering, estimate_loop_trip_count_hint,
+    estimate_vector_plan_helper_cost, induction_origin_var, loop_vectorize_skip_reason,
 };
 use super::debug::{
     VectorizeSkipReason, proof_engine_enabled, trace_no_iv_context, vectorize_trace_enabled,
@@ -12,6 +12,7 @@ use super::types::MemoryStrideClass;
 use super::types::{CallMapLoweringMode, ProofFallbackReason, ProofOutcome};
 use crate::mir::opt::loop_analysis::{LoopAnalyzer, LoopInfo};
 use crate::mir::opt::poly::is_generated_poly_loop_var_name;
+use crate::mir::opt::remarks;
 use crate::mir::{BlockId, FnIR};
 use rustc_hash::FxHashSet;
 
@@ -68,16 +69,19 @@ pub fn optimize_with_stats_with_whitelist_and_loops(
     let trace_enabled = vectorize_trace_enabled();
     let proof_enabled = proof_engine_enabled();
     let reachable = reachable_blocks(fn_ir);
-    let loops = loops
+    let mut loops = loops
         .iter()
         .filter(|lp| reachable.contains(&lp.header))
         .cloned()
         .collect::<Vec<_>>();
+    annotate_owned_full_dests(fn_ir, &mut loops);
 
-    for (loop_idx, lp) in loops.iter().enumerate() {
+    for loop_idx in 0..loops.len() {
+        let lp = &loops[loop_idx];
         stats.loops_seen += 1;
         stats.record_trip_tier(trip_count_tier(fn_ir, lp));
         let before = stats;
+        let span = remarks::loop_span(fn_ir, lp);
         if trace_enabled {
             let mut body_ids: Vec<BlockId> = lp.body.iter().copied().collect();
             body_ids.sort_unstable();
@@ -134,9 +138,23 @@ pub fn optimize_with_stats_with_whitelist_and_loops(
                 }
             }
         }
-        if try_apply_proof_plan(fn_ir, lp, &proof_outcome, &mut stats) {
+        if try_apply_proof_plan(fn_ir, lp, span, &proof_outcome, &mut stats) {
             continue;
         }
+        if let ProofOutcome::NotApplicable { reason } | ProofOutcome::FallbackToPattern { reason } =
+            &proof_outcome
+            && *reason != ProofFallbackReason::Disabled
+        {
+            remarks::analysis(
+                "vectorize",
+                fn_ir,
+                span,
+                format!(
+                    "proof engine fell back to pattern matching: {}",
+                    reason.label()
+                ),
+            );
+        }
         let mut reduction_candidates = collect_reduction_candidates(fn_ir, lp, user_call_whitelist);
         for plan in &reduction_candidates {
             record_plan_counts(&mut stats, fn_ir, plan, false);
@@ -172,6 +190,15 @@ pub fn optimize_with_stats_with_whitelist_and_loops(
             .find(|plan| apply_vectorization(fn_ir, lp, plan.clone()))
         {
             record_plan_counts(&mut stats, fn_ir, &plan, true);
+            remarks::passed(
+                "vectorize",
+                fn_ir,
+                span,
+                format!(
+                    "reduction loop rewritten as {}",
+                    vector_plan_trace_label_with_lowering(fn_ir, &plan)
+                ),
+            );
             if trace_enabled {
                 eprintln!(
                     "   [vec-choose] {} reduction={}",
@@ -185,6 +212,15 @@ pub fn optimize_with_stats_with_whitelist_and_loops(
             .find(|plan| apply_vectorization(fn_ir, lp, plan.clone()))
         {
             record_plan_counts(&mut stats, fn_ir, &plan, true);
+            remarks::passed(
+                "vectorize",
+                fn_ir,
+                span,
+                format!(
+                    "loop vectorized as {}",
+                    vector_plan_trace_label_with_lowering(fn_ir, &plan)
+                ),
+            );
             if trace_enabled {
                 eprintln!(
                     "   [vec-choose] {} vector={}",
@@ -204,7 +240,22 @@ pub fn optimize_with_stats_with_whitelist_and_loops(
             }
         }
         if !applied {
-            stats.record_skip(loop_vectorize_skip_reason(fn_ir, lp));
+            let reason = loop_vectorize_skip_reason(fn_ir, lp);
+            stats.record_skip(reason);
+            let proof_note = match &proof_outcome {
+                ProofOutcome::Certified(_) => " (certified plan failed to apply)",
+                _ => "",
+            };
+            remarks::missed(
+                "vectorize",
+                fn_ir,
+                span,
+                format!("loop not vectorized: {}{}", reason.label(), proof_note),
+                reason.code(),
+            );
+        } else {
+            // The rewrite rebinds variables, so later loops need fresh facts.
+            annotate_owned_full_dests(fn_ir, &mut loops[loop_idx + 1..]);
         }
     }
 
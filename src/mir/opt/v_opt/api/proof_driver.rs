@@ -6,6 +6,8 @@ use super::{
 };
 use crate::mir::FnIR;
 use crate::mir::opt::loop_analysis::LoopInfo;
+use crate::mir::opt::remarks;
+use crate::utils::Span;
 
 pub(super) fn trace_proof_outcome(fn_ir: &FnIR, lp: &LoopInfo, outcome: &ProofOutcome) {
     match outcome {
@@ -29,6 +31,7 @@ pub(super) fn trace_proof_outcome(fn_ir: &FnIR, lp: &LoopInfo, outcome: &ProofOu
 pub(super) fn try_apply_proof_plan(
     fn_ir: &mut FnIR,
     lp: &LoopInfo,
+    span: Span,
     outcome: &ProofOutcome,
     stats: &mut VOptStats,
 ) -> bool {
@@ -51,10 +54,22 @@ pub(super) fn try_apply_proof_plan(
 
     stats.proof_applied += 1;
     record_plan_counts(stats, fn_ir, &plan, true);
-    if matches!(vector_plan_family(&plan), VectorPlanFamily::Reduction) {
+    let shape = if matches!(vector_plan_family(&plan), VectorPlanFamily::Reduction) {
         stats.reduced += 1;
+        "reduction loop rewritten"
     } else {
         stats.vectorized += 1;
-    }
+        "loop vectorized"
+    };
+    remarks::passed(
+        "vectorize",
+        fn_ir,
+        span,
+        format!(
+            "{} as {} (proof-certified)",
+            shape,
+            vector_plan_label(&plan)
+        ),
+    );
     true
 }
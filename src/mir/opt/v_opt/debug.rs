@@ -25,6 +25,18 @@ impl VectorizeSkipReason {
             Self::NoSupportedPattern => "no supported map/reduction/call-map pattern matched",
         }
     }
+
+    /// Stable code for optimization remarks.
+    pub(super) const fn code(self) -> &'static str {
+        match self {
+            Self::NoIv => "no-induction-var",
+            Self::NonCanonicalBound => "non-canonical-bound",
+            Self::UnsupportedCfgShape => "unsupported-cfg-shape",
+            Self::IndirectIndexAccess => "indirect-index-access",
+            Self::StoreEffects => "store-effects",
+            Self::NoSupportedPattern => "no-supported-pattern",
+        }
+    }
 }
 
 pub(super) fn vectorize_trace_enabled() -> bool {
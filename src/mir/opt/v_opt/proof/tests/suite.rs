@@ -14,6 +14,7 @@ pub(crate) fn dummy_loop() -> LoopInfo {
         iv: None,
         limit: None,
         limit_adjust: 0,
+        owned_full_dests: FxHashSet::default(),
     }
 }
 
@@ -28,6 +28,7 @@ fn loop_info_without_apply_site() -> LoopInfo {
         iv: None,
         limit: None,
         limit_adjust: 0,
+        owned_full_dests: FxHashSet::default(),
     }
 }
 
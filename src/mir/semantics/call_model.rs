@@ -17,8 +17,9 @@ pub(crate) use self::call_model_surfaces::{
     builtin_arity, is_runtime_helper, is_runtime_reserved_symbol,
 };
 pub(crate) use self::call_model_surfaces::{
-    is_dynamic_fallback_builtin, is_namespaced_r_call, is_supported_package_call,
-    is_supported_tidy_helper_call, is_tidy_data_mask_call, is_tidy_helper_call,
+    is_dynamic_fallback_builtin, is_modeled_package_export, is_namespaced_r_call, is_rng_call,
+    is_supported_package_call, is_supported_tidy_helper_call, is_tidy_data_mask_call,
+    is_tidy_helper_call,
 };
 
 #[derive(Debug, Clone)]
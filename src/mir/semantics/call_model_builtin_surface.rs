@@ -97,3 +97,44 @@ pub(crate) fn is_tidy_data_mask_call(name: &str) -> bool {
             | "tidyr::unite"
     )
 }
+
+/// Calls that draw from, or reseed, R's session random number generator.
+/// Their results depend on the order they run in, so kernels that contain
+/// them must not be split across workers.
+pub(crate) fn is_rng_call(name: &str) -> bool {
+    let sym = match name.split_once("::") {
+        Some(("base" | "stats", sym)) => sym,
+        Some(_) => return false,
+        None => name,
+    };
+    matches!(
+        sym,
+        "sample"
+            | "sample.int"
+            | "set.seed"
+            | "RNGkind"
+            | "RNGversion"
+            | "rnorm"
+            | "runif"
+            | "rbinom"
+            | "rpois"
+            | "rexp"
+            | "rgamma"
+            | "rbeta"
+            | "rchisq"
+            | "rt"
+            | "rf"
+            | "rcauchy"
+            | "rlogis"
+            | "rlnorm"
+            | "rweibull"
+            | "rgeom"
+            | "rhyper"
+            | "rnbinom"
+            | "rmultinom"
+            | "rsignrank"
+            | "rwilcox"
+            | "r2dtable"
+            | "simulate"
+    )
+}
@@ -56,6 +56,32 @@ pub(crate) fn is_supported_package_call(name: &str) -> bool {
         || name.starts_with("base::")
 }
 
+/// Whether `pkg` exports `name` on the modeled surface. Unlike
+/// [`is_supported_package_call`], every `base::` name is not assumed to exist.
+pub(crate) fn is_modeled_package_export(pkg: &str, name: &str) -> bool {
+    let qualified = format!("{}::{}", pkg, name);
+    match pkg {
+        "base" => base::contains(&qualified),
+        "compiler" => compiler::contains(&qualified),
+        "dplyr" => dplyr::contains(&qualified),
+        "ggplot2" => ggplot2::contains(&qualified),
+        "graphics" => graphics::contains(&qualified),
+        "grDevices" => grdevices::contains(&qualified),
+        "grid" => grid::contains(&qualified),
+        "methods" => methods::contains(&qualified),
+        "parallel" => parallel::contains(&qualified),
+        "readr" => readr::contains(&qualified),
+        "splines" => splines::contains(&qualified),
+        "stats" => stats::contains(&qualified),
+        "stats4" => stats4::contains(&qualified),
+        "tcltk" => tcltk::contains(&qualified),
+        "tidyr" => tidyr::contains(&qualified),
+        "tools" => tools::contains(&qualified),
+        "utils" => utils::contains(&qualified),
+        _ => false,
+    }
+}
+
 pub(crate) fn is_supported_tidy_helper_call(name: &str) -> bool {
     super::call_model_builtin_surface::is_tidy_helper_call(name)
 }
@@ -235,3 +235,7 @@ impl<'a> Structurizer<'a> {
         best
     }
 }
+
+#[cfg(test)]
+#[path = "structurizer/tests.rs"]
+mod tests;
@@ -46,6 +46,15 @@ pub(crate) fn compute_loop_headers(
         if !reachable.contains(&lp.header) {
             continue;
         }
+        // A back edge from dead code (e.g. the fallthrough after a returning
+        // `then` arm) does not make the header a loop.
+        let has_live_latch = lp
+            .body
+            .iter()
+            .any(|b| reachable.contains(b) && successors(fn_ir, *b).contains(&lp.header));
+        if !has_live_latch {
+            continue;
+        }
         let entry = grouped.entry(lp.header).or_default();
         for b in lp.body {
             entry.insert(b);
@@ -0,0 +1,47 @@
+use super::*;
+use crate::mir::text::parse_fn;
+
+fn contains_loop(block: &StructuredBlock) -> bool {
+    match block {
+        StructuredBlock::Loop { .. } => true,
+        StructuredBlock::Sequence(items) => items.iter().any(contains_loop),
+        StructuredBlock::If {
+            then_body,
+            else_body,
+            ..
+        } => contains_loop(then_body) || else_body.as_deref().is_some_and(contains_loop),
+        _ => false,
+    }
+}
+
+#[test]
+fn back_edge_from_dead_block_is_not_a_loop() {
+    // bb2 is the fallthrough after a returning `then` arm: nothing reaches
+    // it, so its edge back to bb1 must not turn bb1 into a loop header.
+    let fn_ir = parse_fn(
+        r#"
+fn pick(x) {
+  entry bb0
+  body bb0
+  %0 = param 0 : int scalar origin=x
+  %1 = const 0L : int scalar !na
+  %2 = const 1L : int scalar !na
+  %3 = gt %0, %1
+bb0:
+  goto bb1
+bb1:
+  if %3 then bb3 else bb4
+bb2:
+  goto bb1
+bb3:
+  return %2
+bb4:
+  return %1
+}
+"#,
+    )
+    .expect("MIR should parse");
+
+    let structured = Structurizer::new(&fn_ir).build();
+    assert!(!contains_loop(&structured), "{structured:?}");
+}
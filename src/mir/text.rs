@@ -0,0 +1,63 @@
+//! Textual MIR.
+//!
+//! A line-oriented, human-writable form of `FnIR` that round-trips through
+//! [`print_fn`] and [`parse_fns`]. It exists so optimizer passes can be tested
+//! from hand-written MIR without going through the front end:
+//!
+//! ```text
+//! fn sum(x) {
+//!   entry bb0
+//!   body bb0
+//!   %0 = param 0 : dbl vector origin=x
+//!   %1 = const 1L : int scalar !na
+//!   %2 = phi in bb1 [bb0: %1, bb2: %5] origin=i
+//!   %3 = len %0
+//!   %4 = le %2, %3
+//!   %5 = add %2, %1
+//! bb0:
+//!   goto bb1
+//! bb1:
+//!   if %4 then bb2 else bb3
+//! bb2:
+//!   goto bb1
+//! bb3:
+//!   return %0
+//! }
+//! ```
+//!
+//! Values are listed before blocks because MIR values are not owned by a
+//! block (phis name theirs with `in bbN`). After the kind, a value may carry
+//! `: <prim> <shape> [!na] [len(N)]` type facts followed by `key=value`
+//! attributes: `origin`, `term`, `facts`, `range` and `escape`. Text after
+//! `//` is a comment, so FileCheck-style directives can live in the input.
+//! Spans, call semantics and parameter hints are not represented.
+
+use std::fmt;
+
+#[path = "text/parse.rs"]
+mod parse;
+#[path = "text/print.rs"]
+mod print;
+
+use self::parse::is_bare_name;
+pub use self::parse::{parse_fn, parse_fns};
+pub use self::print::{print_fn, print_fns};
+pub(crate) use self::print::{term_text, type_text};
+
+#[cfg(test)]
+#[path = "text/tests.rs"]
+mod tests;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MirParseError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for MirParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for MirParseError {}
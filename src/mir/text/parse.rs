@@ -0,0 +1,1083 @@
+use super::MirParseError;
+use super::print::{BINOPS, FACT_FLAGS, INTRINSICS, UNOPS};
+use crate::mir::analyze::unsafe_r::analyze_unsafe_r;
+use crate::mir::flow::Interval;
+use crate::mir::*;
+use crate::typeck::{LenSym, NaTy, PrimTy, ShapeTy, TypeState, TypeTerm};
+use crate::utils::Span;
+use rustc_hash::FxHashMap;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Tok {
+    Ident(String),
+    Str(String),
+    Num(String),
+    Val(ValueId),
+    Punct(char),
+}
+
+impl Tok {
+    fn describe(&self) -> String {
+        match self {
+            Self::Ident(text) | Self::Num(text) => format!("'{text}'"),
+            Self::Str(text) => format!("string {text:?}"),
+            Self::Val(id) => format!("'%{id}'"),
+            Self::Punct(ch) => format!("'{ch}'"),
+        }
+    }
+}
+
+fn is_name_char(ch: char) -> bool {
+    ch.is_ascii_alphanumeric() || ch == '_' || ch == '.'
+}
+
+pub(super) fn is_bare_name(text: &str) -> bool {
+    let mut chars = text.chars();
+    matches!(chars.next(), Some(ch) if ch.is_ascii_alphabetic() || ch == '_' || ch == '.')
+        && text.chars().all(is_name_char)
+}
+
+fn lex(line: &str, line_no: usize) -> Result<Vec<Tok>, MirParseError> {
+    let err = |message: String| MirParseError {
+        line: line_no,
+        message,
+    };
+    let chars: Vec<char> = line.chars().collect();
+    let mut toks = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let ch = chars[i];
+        let next = chars.get(i + 1).copied();
+        if ch.is_whitespace() {
+            i += 1;
+        } else if ch == '/' && next == Some('/') {
+            break;
+        } else if ch == '"' {
+            let mut text = String::new();
+            i += 1;
+            loop {
+                match chars.get(i) {
+                    None => return Err(err("unterminated string".to_string())),
+                    Some('"') => break,
+                    Some('\\') => {
+                        text.push(match chars.get(i + 1) {
+                            Some('n') => '\n',
+                            Some('t') => '\t',
+                            Some('r') => '\r',
+                            Some('"') => '"',
+                            Some('\\') => '\\',
+                            other => {
+                                return Err(err(format!(
+                                    "invalid escape '\\{}'",
+                                    other.map_or(' ', |c| *c)
+                                )));
+                            }
+                        });
+                        i += 2;
+                    }
+                    Some(c) => {
+                        text.push(*c);
+                        i += 1;
+                    }
+                }
+            }
+            i += 1;
+            toks.push(Tok::Str(text));
+        } else if ch == '%' && next.is_some_and(|c| c.is_ascii_digit()) {
+            let start = i + 1;
+            i += 1;
+            while chars.get(i).is_some_and(|c| c.is_ascii_digit()) {
+                i += 1;
+            }
+            let text: String = chars[start..i].iter().collect();
+            let id = text
+                .parse()
+                .map_err(|_| err(format!("value id '%{text}' is out of range")))?;
+            toks.push(Tok::Val(id));
+        } else if ch.is_ascii_digit()
+            || (ch == '-' && next.is_some_and(|c| c.is_ascii_digit() || c == 'i' || c == 'N'))
+        {
+            let start = i;
+            i += 1;
+            while let Some(c) = chars.get(i).copied() {
+                let exponent_sign = (c == '+' || c == '-') && matches!(chars[i - 1], 'e' | 'E');
+                if c.is_ascii_alphanumeric() || c == '.' || exponent_sign {
+                    i += 1;
+                } else {
+                    break;
+                }
+            }
+            toks.push(Tok::Num(chars[start..i].iter().collect()));
+        } else if is_name_char(ch) {
+            let start = i;
+            while chars.get(i).is_some_and(|c| is_name_char(*c)) {
+                i += 1;
+            }
+            toks.push(Tok::Ident(chars[start..i].iter().collect()));
+        } else if "()[]{}<>,:=|!?".contains(ch) {
+            toks.push(Tok::Punct(ch));
+            i += 1;
+        } else {
+            return Err(err(format!("unexpected character '{ch}'")));
+        }
+    }
+    Ok(toks)
+}
+
+/// Token cursor over one source line.
+struct Line {
+    toks: Vec<Tok>,
+    pos: usize,
+    line: usize,
+}
+
+impl Line {
+    fn err(&self, message: impl Into<String>) -> MirParseError {
+        MirParseError {
+            line: self.line,
+            message: message.into(),
+        }
+    }
+
+    fn peek(&self) -> Option<&Tok> {
+        self.toks.get(self.pos)
+    }
+
+    fn next(&mut self, what: &str) -> Result<Tok, MirParseError> {
+        let tok = self
+            .toks
+            .get(self.pos)
+            .cloned()
+            .ok_or_else(|| self.err(format!("expected {what}, found end of line")))?;
+        self.pos += 1;
+        Ok(tok)
+    }
+
+    fn at_end(&self) -> bool {
+        self.pos >= self.toks.len()
+    }
+
+    fn finish(&self) -> Result<(), MirParseError> {
+        match self.peek() {
+            None => Ok(()),
+            Some(tok) => Err(self.err(format!("unexpected {} at end of line", tok.describe()))),
+        }
+    }
+
+    fn eat_punct(&mut self, ch: char) -> bool {
+        if self.peek() == Some(&Tok::Punct(ch)) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn eat_ident(&mut self, word: &str) -> bool {
+        if matches!(self.peek(), Some(Tok::Ident(text)) if text == word) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn punct(&mut self, ch: char) -> Result<(), MirParseError> {
+        match self.next(&format!("'{ch}'"))? {
+            Tok::Punct(found) if found == ch => Ok(()),
+            tok => Err(self.err(format!("expected '{ch}', found {}", tok.describe()))),
+        }
+    }
+
+    fn keyword(&mut self, word: &str) -> Result<(), MirParseError> {
+        if self.eat_ident(word) {
+            Ok(())
+        } else {
+            let found = self.peek().map_or("end of line".to_string(), Tok::describe);
+            Err(self.err(format!("expected '{word}', found {found}")))
+        }
+    }
+
+    fn ident(&mut self, what: &str) -> Result<String, MirParseError> {
+        match self.next(what)? {
+            Tok::Ident(text) => Ok(text),
+            tok => Err(self.err(format!("expected {what}, found {}", tok.describe()))),
+        }
+    }
+
+    /// A variable, field or function name: bare identifier or quoted string.
+    fn name(&mut self, what: &str) -> Result<String, MirParseError> {
+        match self.next(what)? {
+            Tok::Ident(text) | Tok::Str(text) => Ok(text),
+            tok => Err(self.err(format!("expected {what}, found {}", tok.describe()))),
+        }
+    }
+
+    fn string(&mut self, what: &str) -> Result<String, MirParseError> {
+        match self.next(what)? {
+            Tok::Str(text) => Ok(text),
+            tok => Err(self.err(format!("expected {what}, found {}", tok.describe()))),
+        }
+    }
+
+    fn value(&mut self) -> Result<ValueId, MirParseError> {
+        match self.next("value reference")? {
+            Tok::Val(id) => Ok(id),
+            tok => Err(self.err(format!(
+                "expected value reference, found {}",
+                tok.describe()
+            ))),
+        }
+    }
+
+    fn block(&mut self) -> Result<BlockId, MirParseError> {
+        let text = self.ident("block label")?;
+        text.strip_prefix("bb")
+            .and_then(|n| n.parse().ok())
+            .ok_or_else(|| self.err(format!("expected block label 'bbN', found '{text}'")))
+    }
+
+    fn number<T: std::str::FromStr>(&mut self, what: &str) -> Result<T, MirParseError> {
+        match self.next(what)? {
+            Tok::Num(text) => text
+                .parse()
+                .map_err(|_| self.err(format!("invalid {what} '{text}'"))),
+            tok => Err(self.err(format!("expected {what}, found {}", tok.describe()))),
+        }
+    }
+
+    fn values_until(&mut self, close: char) -> Result<Vec<ValueId>, MirParseError> {
+        let mut out = Vec::new();
+        while !self.eat_punct(close) {
+            if !out.is_empty() {
+                self.punct(',')?;
+            }
+            out.push(self.value()?);
+        }
+        Ok(out)
+    }
+}
+
+#[derive(Default)]
+struct FnBuilder {
+    name: String,
+    params: Vec<VarId>,
+    entry: Option<BlockId>,
+    body_head: Option<BlockId>,
+    unsupported_dynamic: bool,
+    opaque_interop: bool,
+    values: FxHashMap<ValueId, (Value, usize)>,
+    blocks: FxHashMap<BlockId, (Block, Vec<usize>)>,
+    open_block: Option<OpenBlock>,
+    header_line: usize,
+}
+
+/// A block whose terminator has not been seen yet, with the source line of
+/// the label and of every instruction.
+struct OpenBlock {
+    id: BlockId,
+    instrs: Vec<Instr>,
+    lines: Vec<usize>,
+    label_line: usize,
+}
+
+/// Parse every function in `src`.
+pub fn parse_fns(src: &str) -> Result<Vec<FnIR>, MirParseError> {
+    let mut fns = Vec::new();
+    let mut current: Option<FnBuilder> = None;
+    for (idx, raw) in src.lines().enumerate() {
+        let line_no = idx + 1;
+        let toks = lex(raw, line_no)?;
+        if toks.is_empty() {
+            continue;
+        }
+        let mut line = Line {
+            toks,
+            pos: 0,
+            line: line_no,
+        };
+        match current.take() {
+            None => {
+                line.keyword("fn")?;
+                current = Some(parse_header(&mut line)?);
+            }
+            Some(builder) if line.toks == [Tok::Punct('}')] => {
+                fns.push(builder.finish(line_no)?);
+            }
+            Some(mut builder) => {
+                builder.parse_line(&mut line)?;
+                current = Some(builder);
+            }
+        }
+    }
+    if let Some(builder) = current {
+        return Err(MirParseError {
+            line: builder.header_line,
+            message: format!("function '{}' is missing its closing '}}'", builder.name),
+        });
+    }
+    Ok(fns)
+}
+
+/// Parse a source that must contain exactly one function.
+pub fn parse_fn(src: &str) -> Result<FnIR, MirParseError> {
+    let mut fns = parse_fns(src)?;
+    match (fns.pop(), fns.len()) {
+        (Some(fn_ir), 0) => Ok(fn_ir),
+        (popped, rest) => Err(MirParseError {
+            line: 1,
+            message: format!(
+                "expected exactly one function, found {}",
+                rest + usize::from(popped.is_some())
+            ),
+        }),
+    }
+}
+
+fn parse_header(line: &mut Line) -> Result<FnBuilder, MirParseError> {
+    let name = line.name("function name")?;
+    line.punct('(')?;
+    let mut params = Vec::new();
+    while !line.eat_punct(')') {
+        if !params.is_empty() {
+            line.punct(',')?;
+        }
+        params.push(line.name("parameter name")?);
+    }
+    line.punct('{')?;
+    line.finish()?;
+    Ok(FnBuilder {
+        name,
+        params,
+        header_line: line.line,
+        ..FnBuilder::default()
+    })
+}
+
+impl FnBuilder {
+    fn parse_line(&mut self, line: &mut Line) -> Result<(), MirParseError> {
+        match line.peek().cloned() {
+            Some(Tok::Val(id)) => {
+                line.pos += 1;
+                line.punct('=')?;
+                let value = parse_value(line, id)?;
+                line.finish()?;
+                if self.values.insert(id, (value, line.line)).is_some() {
+                    return Err(line.err(format!("value %{id} is defined twice")));
+                }
+                return Ok(());
+            }
+            Some(Tok::Ident(word))
+                if word.starts_with("bb") && line.toks.get(1) == Some(&Tok::Punct(':')) =>
+            {
+                let bid = line.block()?;
+                line.punct(':')?;
+                line.finish()?;
+                if let Some(open) = &self.open_block {
+                    return Err(MirParseError {
+                        line: open.label_line,
+                        message: format!("block bb{} has no terminator", open.id),
+                    });
+                }
+                if self.blocks.contains_key(&bid) {
+                    return Err(line.err(format!("block bb{bid} is defined twice")));
+                }
+                self.open_block = Some(OpenBlock {
+                    id: bid,
+                    instrs: Vec::new(),
+                    lines: Vec::new(),
+                    label_line: line.line,
+                });
+                return Ok(());
+            }
+            _ => {}
+        }
+
+        let Some(open) = self.open_block.as_mut() else {
+            let word = line.ident("function attribute")?;
+            match word.as_str() {
+                "entry" => self.entry = Some(line.block()?),
+                "body" => self.body_head = Some(line.block()?),
+                "dynamic" => self.unsupported_dynamic = true,
+                "opaque" => self.opaque_interop = true,
+                _ => return Err(line.err(format!("unknown function attribute '{word}'"))),
+            }
+            return line.finish();
+        };
+
+        if let Some(term) = parse_terminator(line)? {
+            line.finish()?;
+            open.lines.push(line.line);
+            let id = open.id;
+            let instrs = std::mem::take(&mut open.instrs);
+            let lines = std::mem::take(&mut open.lines);
+            self.open_block = None;
+            self.blocks.insert(id, (Block { id, instrs, term }, lines));
+            return Ok(());
+        }
+        let instr = parse_instr(line)?;
+        line.finish()?;
+        open.instrs.push(instr);
+        open.lines.push(line.line);
+        Ok(())
+    }
+
+    fn finish(mut self, close_line: usize) -> Result<FnIR, MirParseError> {
+        if let Some(open) = self.open_block.take() {
+            return Err(MirParseError {
+                line: open.label_line,
+                message: format!("block bb{} has no terminator", open.id),
+            });
+        }
+        let err = |line: usize, message: String| MirParseError { line, message };
+        let mut fn_ir = FnIR::new(self.name, self.params);
+        fn_ir.unsupported_dynamic = self.unsupported_dynamic;
+        fn_ir.opaque_interop = self.opaque_interop;
+
+        let block_count = self.blocks.len();
+        let mut block_lines = Vec::with_capacity(block_count);
+        for bid in 0..block_count {
+            let (block, lines) = self.blocks.remove(&bid).ok_or_else(|| {
+                err(
+                    close_line,
+                    format!(
+                        "blocks must be numbered bb0..bb{}; bb{bid} is missing",
+                        block_count - 1
+                    ),
+                )
+            })?;
+            fn_ir.blocks.push(block);
+            block_lines.push(lines);
+        }
+        if let Some(extra) = self.blocks.keys().min() {
+            return Err(err(
+                close_line,
+                format!("block bb{extra} is out of sequence"),
+            ));
+        }
+        let value_count = self.values.len();
+        let mut lines = Vec::with_capacity(value_count);
+        for vid in 0..value_count {
+            let (value, line) = self.values.remove(&vid).ok_or_else(|| {
+                err(
+                    close_line,
+                    format!(
+                        "values must be numbered %0..%{}; %{vid} is missing",
+                        value_count - 1
+                    ),
+                )
+            })?;
+            fn_ir.values.push(value);
+            lines.push(line);
+        }
+        if let Some(extra) = self.values.keys().min() {
+            return Err(err(
+                close_line,
+                format!("value %{extra} is out of sequence"),
+            ));
+        }
+        fn_ir.entry = self.entry.unwrap_or(0);
+        fn_ir.body_head = self.body_head.unwrap_or(fn_ir.entry);
+
+        // Dangling references are reported here rather than left for the
+        // verifier, so a typo in a test points at the offending line.
+        let check_block = |bid: BlockId, line: usize| {
+            if bid < block_count {
+                Ok(())
+            } else {
+                Err(err(line, format!("reference to undefined block bb{bid}")))
+            }
+        };
+        let check_value = |vid: ValueId, line: usize| {
+            if vid < value_count {
+                Ok(())
+            } else {
+                Err(err(line, format!("reference to undefined value %{vid}")))
+            }
+        };
+        check_block(fn_ir.entry, close_line)?;
+        check_block(fn_ir.body_head, close_line)?;
+        for (value, line) in fn_ir.values.iter().zip(lines) {
+            for used in value_operands(&value.kind) {
+                check_value(used, line)?;
+            }
+            if let ValueKind::Phi { args } = &value.kind {
+                for (_, from) in args {
+                    check_block(*from, line)?;
+                }
+            }
+            if let Some(bid) = value.phi_block {
+                check_block(bid, line)?;
+            }
+            if let ValueKind::Param { index } = value.kind
+                && index >= fn_ir.params.len()
+            {
+                return Err(err(
+                    line,
+                    format!("parameter index {index} is out of range"),
+                ));
+            }
+        }
+        for (block, lines) in fn_ir.blocks.iter().zip(&block_lines) {
+            for (instr, line) in block.instrs.iter().zip(lines) {
+                for used in instr_operands(instr) {
+                    check_value(used, *line)?;
+                }
+            }
+            let term_line = lines.last().copied().unwrap_or(close_line);
+            match &block.term {
+                Terminator::Goto(target) => check_block(*target, term_line)?,
+                Terminator::If {
+                    cond,
+                    then_bb,
+                    else_bb,
+                } => {
+                    check_value(*cond, term_line)?;
+                    check_block(*then_bb, term_line)?;
+                    check_block(*else_bb, term_line)?;
+                }
+                Terminator::Return(Some(val)) => check_value(*val, term_line)?,
+                Terminator::Return(None) | Terminator::Unreachable => {}
+            }
+        }
+        Ok(fn_ir)
+    }
+}
+
+fn value_operands(kind: &ValueKind) -> Vec<ValueId> {
+    match kind {
+        ValueKind::Const(_)
+        | ValueKind::Param { .. }
+        | ValueKind::Load { .. }
+        | ValueKind::RSymbol { .. } => Vec::new(),
+        ValueKind::Phi { args } => args.iter().map(|(val, _)| *val).collect(),
+        ValueKind::Len { base } | ValueKind::Indices { base } => vec![*base],
+        ValueKind::Range { start, end } => vec![*start, *end],
+        ValueKind::Binary { lhs, rhs, .. } => vec![*lhs, *rhs],
+        ValueKind::Unary { rhs, .. } => vec![*rhs],
+        ValueKind::Call { args, .. } | ValueKind::Intrinsic { args, .. } => args.clone(),
+        ValueKind::RecordLit { fields } => fields.iter().map(|(_, val)| *val).collect(),
+        ValueKind::FieldGet { base, .. } => vec![*base],
+        ValueKind::FieldSet { base, value, .. } => vec![*base, *value],
+        ValueKind::Index1D { base, idx, .. } => vec![*base, *idx],
+        ValueKind::Index2D { base, r, c } => vec![*base, *r, *c],
+        ValueKind::Index3D { base, i, j, k } => vec![*base, *i, *j, *k],
+    }
+}
+
+fn instr_operands(instr: &Instr) -> Vec<ValueId> {
+    match instr {
+        Instr::Assign { src, .. } => vec![*src],
+        Instr::Eval { val, .. } => vec![*val],
+        Instr::StoreIndex1D { base, idx, val, .. } => vec![*base, *idx, *val],
+        Instr::StoreIndex2D {
+            base, r, c, val, ..
+        } => vec![*base, *r, *c, *val],
+        Instr::StoreIndex3D {
+            base, i, j, k, val, ..
+        } => vec![*base, *i, *j, *k, *val],
+        Instr::UnsafeRBlock { .. } => Vec::new(),
+    }
+}
+
+fn parse_value(line: &mut Line, id: ValueId) -> Result<Value, MirParseError> {
+    let op = line.ident("value kind")?;
+    let mut phi_block = None;
+    let kind = match op.as_str() {
+        "const" => ValueKind::Const(parse_lit(line)?),
+        "phi" => {
+            if line.eat_ident("in") {
+                phi_block = Some(line.block()?);
+            }
+            line.punct('[')?;
+            let mut args = Vec::new();
+            while !line.eat_punct(']') {
+                if !args.is_empty() {
+                    line.punct(',')?;
+                }
+                let from = line.block()?;
+                line.punct(':')?;
+                args.push((line.value()?, from));
+            }
+            ValueKind::Phi { args }
+        }
+        "param" => ValueKind::Param {
+            index: line.number("parameter index")?,
+        },
+        "len" => ValueKind::Len {
+            base: line.value()?,
+        },
+        "indices" => ValueKind::Indices {
+            base: line.value()?,
+        },
+        "range" => {
+            let start = line.value()?;
+            line.punct(',')?;
+            ValueKind::Range {
+                start,
+                end: line.value()?,
+            }
+        }
+        "call" => {
+            let callee = line.string("callee")?;
+            line.punct('(')?;
+            let mut args = Vec::new();
+            let mut names = Vec::new();
+            while !line.eat_punct(')') {
+                if !args.is_empty() {
+                    line.punct(',')?;
+                }
+                if matches!(line.peek(), Some(Tok::Val(_))) {
+                    names.push(None);
+                } else {
+                    names.push(Some(line.name("argument label")?));
+                    line.punct(':')?;
+                }
+                args.push(line.value()?);
+            }
+            ValueKind::Call {
+                callee,
+                args,
+                names,
+            }
+        }
+        "record" => {
+            line.punct('{')?;
+            let mut fields = Vec::new();
+            while !line.eat_punct('}') {
+                if !fields.is_empty() {
+                    line.punct(',')?;
+                }
+                let field = line.name("field name")?;
+                line.punct(':')?;
+                fields.push((field, line.value()?));
+            }
+            ValueKind::RecordLit { fields }
+        }
+        "field_get" => {
+            let base = line.value()?;
+            line.punct(',')?;
+            ValueKind::FieldGet {
+                base,
+                field: line.string("field name")?,
+            }
+        }
+        "field_set" => {
+            let base = line.value()?;
+            line.punct(',')?;
+            let field = line.string("field name")?;
+            line.punct(',')?;
+            ValueKind::FieldSet {
+                base,
+                field,
+                value: line.value()?,
+            }
+        }
+        "intrinsic" => {
+            let name = line.ident("intrinsic name")?;
+            let op = INTRINSICS
+                .iter()
+                .find(|(_, n)| *n == name)
+                .map(|(op, _)| *op)
+                .ok_or_else(|| line.err(format!("unknown intrinsic '{name}'")))?;
+            line.punct('(')?;
+            ValueKind::Intrinsic {
+                op,
+                args: line.values_until(')')?,
+            }
+        }
+        "index1d" => {
+            let base = line.value()?;
+            line.punct('[')?;
+            let idx = line.value()?;
+            line.punct(']')?;
+            ValueKind::Index1D {
+                base,
+                idx,
+                is_safe: line.eat_ident("safe"),
+                is_na_safe: line.eat_ident("na_safe"),
+            }
+        }
+        "index2d" => {
+            let base = line.value()?;
+            line.punct('[')?;
+            let subs = line.values_until(']')?;
+            let [r, c] = subs[..] else {
+                return Err(line.err("index2d takes two subscripts"));
+            };
+            ValueKind::Index2D { base, r, c }
+        }
+        "index3d" => {
+            let base = line.value()?;
+            line.punct('[')?;
+            let subs = line.values_until(']')?;
+            let [i, j, k] = subs[..] else {
+                return Err(line.err("index3d takes three subscripts"));
+            };
+            ValueKind::Index3D { base, i, j, k }
+        }
+        "load" => ValueKind::Load {
+            var: line.name("variable name")?,
+        },
+        "rsymbol" => ValueKind::RSymbol {
+            name: line.string("symbol name")?,
+        },
+        other => {
+            if let Some((op, _)) = BINOPS.iter().find(|(_, n)| *n == other) {
+                let lhs = line.value()?;
+                line.punct(',')?;
+                ValueKind::Binary {
+                    op: *op,
+                    lhs,
+                    rhs: line.value()?,
+                }
+            } else if let Some((op, _)) = UNOPS.iter().find(|(_, n)| *n == other) {
+                ValueKind::Unary {
+                    op: *op,
+                    rhs: line.value()?,
+                }
+            } else {
+                return Err(line.err(format!("unknown value kind '{other}'")));
+            }
+        }
+    };
+
+    let mut value = Value {
+        id,
+        kind,
+        span: Span::default(),
+        facts: Facts::empty(),
+        value_ty: TypeState::unknown(),
+        value_term: TypeTerm::Any,
+        origin_var: None,
+        phi_block,
+        escape: EscapeStatus::Unknown,
+    };
+    if line.eat_punct(':') {
+        value.value_ty = parse_type(line)?;
+    }
+    while !line.at_end() {
+        let key = line.ident("attribute")?;
+        line.punct('=')?;
+        match key.as_str() {
+            "origin" => value.origin_var = Some(line.name("origin variable")?),
+            "term" => value.value_term = parse_term(line)?,
+            "facts" => loop {
+                let flag = match line.next("fact flag")? {
+                    Tok::Ident(flag) => FACT_FLAGS
+                        .iter()
+                        .find(|(_, n)| *n == flag)
+                        .map(|(bit, _)| *bit)
+                        .ok_or_else(|| line.err(format!("unknown fact flag '{flag}'")))?,
+                    Tok::Num(hex) if hex.starts_with("0x") => u32::from_str_radix(&hex[2..], 16)
+                        .map_err(|_| line.err(format!("invalid fact bits '{hex}'")))?,
+                    tok => {
+                        return Err(
+                            line.err(format!("expected fact flag, found {}", tok.describe()))
+                        );
+                    }
+                };
+                value.facts.flags |= flag;
+                if !line.eat_punct('|') {
+                    break;
+                }
+            },
+            "range" => {
+                line.punct('[')?;
+                let min = parse_bound(line)?;
+                line.punct(',')?;
+                let max = parse_bound(line)?;
+                line.punct(']')?;
+                value.facts.interval = Interval::new(min, max);
+            }
+            "escape" => {
+                value.escape = match line.ident("escape status")?.as_str() {
+                    "local" => EscapeStatus::Local,
+                    "escaped" => EscapeStatus::Escaped,
+                    "unknown" => EscapeStatus::Unknown,
+                    other => return Err(line.err(format!("unknown escape status '{other}'"))),
+                }
+            }
+            _ => return Err(line.err(format!("unknown value attribute '{key}'"))),
+        }
+    }
+    Ok(value)
+}
+
+fn parse_bound(line: &mut Line) -> Result<i64, MirParseError> {
+    match line.next("interval bound")? {
+        Tok::Ident(text) if text == "inf" => Ok(i64::MAX),
+        Tok::Num(text) if text == "-inf" => Ok(i64::MIN),
+        Tok::Num(text) => text
+            .parse()
+            .map_err(|_| line.err(format!("invalid interval bound '{text}'"))),
+        tok => Err(line.err(format!("expected interval bound, found {}", tok.describe()))),
+    }
+}
+
+fn parse_lit(line: &mut Line) -> Result<Lit, MirParseError> {
+    match line.next("literal")? {
+        Tok::Str(text) => Ok(Lit::Str(text)),
+        Tok::Ident(word) => match word.as_str() {
+            "TRUE" => Ok(Lit::Bool(true)),
+            "FALSE" => Ok(Lit::Bool(false)),
+            "NULL" => Ok(Lit::Null),
+            "NA" => Ok(Lit::Na),
+            "inf" => Ok(Lit::Float(f64::INFINITY)),
+            "NaN" => Ok(Lit::Float(f64::NAN)),
+            _ => Err(line.err(format!("invalid literal '{word}'"))),
+        },
+        Tok::Num(text) => {
+            if let Some(int) = text.strip_suffix('L') {
+                int.parse()
+                    .map(Lit::Int)
+                    .map_err(|_| line.err(format!("invalid integer literal '{text}'")))
+            } else {
+                text.parse()
+                    .map(Lit::Float)
+                    .map_err(|_| line.err(format!("invalid numeric literal '{text}'")))
+            }
+        }
+        tok => Err(line.err(format!("expected literal, found {}", tok.describe()))),
+    }
+}
+
+fn parse_type(line: &mut Line) -> Result<TypeState, MirParseError> {
+    let prim = match line.ident("primitive type")?.as_str() {
+        "any" => PrimTy::Any,
+        "null" => PrimTy::Null,
+        "lgl" => PrimTy::Logical,
+        "int" => PrimTy::Int,
+        "dbl" => PrimTy::Double,
+        "chr" => PrimTy::Char,
+        other => return Err(line.err(format!("unknown primitive type '{other}'"))),
+    };
+    let shape = if line.eat_punct('?') {
+        ShapeTy::Unknown
+    } else {
+        match line.ident("shape")?.as_str() {
+            "scalar" => ShapeTy::Scalar,
+            "vector" => ShapeTy::Vector,
+            "matrix" => ShapeTy::Matrix,
+            other => return Err(line.err(format!("unknown shape '{other}'"))),
+        }
+    };
+    let mut ty = TypeState {
+        prim,
+        shape,
+        na: NaTy::Maybe,
+        len_sym: None,
+    };
+    if line.eat_punct('!') {
+        line.keyword("na")?;
+        ty.na = NaTy::Never;
+    }
+    if line.eat_ident("len") {
+        line.punct('(')?;
+        ty.len_sym = Some(LenSym(line.number("length symbol")?));
+        line.punct(')')?;
+    }
+    Ok(ty)
+}
+
+fn parse_term(line: &mut Line) -> Result<TypeTerm, MirParseError> {
+    let head = line.ident("type term")?;
+    let simple = match head.as_str() {
+        "any" => Some(TypeTerm::Any),
+        "never" => Some(TypeTerm::Never),
+        "null" => Some(TypeTerm::Null),
+        "lgl" => Some(TypeTerm::Logical),
+        "int" => Some(TypeTerm::Int),
+        "dbl" => Some(TypeTerm::Double),
+        "chr" => Some(TypeTerm::Char),
+        _ => None,
+    };
+    if let Some(term) = simple {
+        return Ok(term);
+    }
+    line.punct('<')?;
+    let term = match head.as_str() {
+        "vec" => {
+            let elem = Box::new(parse_term(line)?);
+            if line.eat_punct(',') {
+                TypeTerm::VectorLen(elem, parse_dim(line)?)
+            } else {
+                TypeTerm::Vector(elem)
+            }
+        }
+        "mat" => {
+            let elem = Box::new(parse_term(line)?);
+            if line.eat_punct(',') {
+                let r = parse_dim(line)?;
+                line.punct(',')?;
+                TypeTerm::MatrixDim(elem, r, parse_dim(line)?)
+            } else {
+                TypeTerm::Matrix(elem)
+            }
+        }
+        "array" => {
+            let elem = Box::new(parse_term(line)?);
+            line.punct(',')?;
+            line.punct('[')?;
+            let mut dims = Vec::new();
+            while !line.eat_punct(']') {
+                if !dims.is_empty() {
+                    line.punct(',')?;
+                }
+                dims.push(parse_dim(line)?);
+            }
+            TypeTerm::ArrayDim(elem, dims)
+        }
+        "list" => TypeTerm::List(Box::new(parse_term(line)?)),
+        "box" => TypeTerm::Boxed(Box::new(parse_term(line)?)),
+        "option" => TypeTerm::Option(Box::new(parse_term(line)?)),
+        "df" | "union" => {
+            let mut terms = vec![parse_term(line)?];
+            while line.eat_punct(',') {
+                terms.push(parse_term(line)?);
+            }
+            if head == "df" {
+                TypeTerm::DataFrame(terms)
+            } else {
+                TypeTerm::Union(terms)
+            }
+        }
+        "dfn" | "nlist" => {
+            let mut fields = Vec::new();
+            loop {
+                let field = line.name("field name")?;
+                line.punct(':')?;
+                fields.push((field, parse_term(line)?));
+                if !line.eat_punct(',') {
+                    break;
+                }
+            }
+            if head == "dfn" {
+                TypeTerm::DataFrameNamed(fields)
+            } else {
+                TypeTerm::NamedList(fields)
+            }
+        }
+        other => return Err(line.err(format!("unknown type term '{other}'"))),
+    };
+    line.punct('>')?;
+    Ok(term)
+}
+
+fn parse_dim(line: &mut Line) -> Result<Option<i64>, MirParseError> {
+    if line.eat_punct('?') {
+        Ok(None)
+    } else {
+        line.number("dimension").map(Some)
+    }
+}
+
+fn parse_instr(line: &mut Line) -> Result<Instr, MirParseError> {
+    let span = Span::default();
+    let word = line.ident("instruction")?;
+    let instr = match word.as_str() {
+        "assign" => {
+            let dst = line.name("variable name")?;
+            line.punct('=')?;
+            Instr::Assign {
+                dst,
+                src: line.value()?,
+                span,
+            }
+        }
+        "eval" => Instr::Eval {
+            val: line.value()?,
+            span,
+        },
+        "store1d" | "store2d" | "store3d" => {
+            let base = line.value()?;
+            line.punct('[')?;
+            let subs = line.values_until(']')?;
+            line.punct('=')?;
+            let val = line.value()?;
+            match (word.as_str(), &subs[..]) {
+                ("store1d", [idx]) => Instr::StoreIndex1D {
+                    base,
+                    idx: *idx,
+                    val,
+                    is_safe: line.eat_ident("safe"),
+                    is_na_safe: line.eat_ident("na_safe"),
+                    is_vector: line.eat_ident("vector"),
+                    span,
+                },
+                ("store2d", [r, c]) => Instr::StoreIndex2D {
+                    base,
+                    r: *r,
+                    c: *c,
+                    val,
+                    span,
+                },
+                ("store3d", [i, j, k]) => Instr::StoreIndex3D {
+                    base,
+                    i: *i,
+                    j: *j,
+                    k: *k,
+                    val,
+                    span,
+                },
+                _ => {
+                    return Err(line.err(format!(
+                        "{word} takes {} subscript(s), found {}",
+                        &word[5..6],
+                        subs.len()
+                    )));
+                }
+            }
+        }
+        "unsafe_r" => {
+            let read_only = line.eat_ident("read_only");
+            let code = line.string("R code")?;
+            Instr::UnsafeRBlock {
+                access: analyze_unsafe_r(&code),
+                code,
+                read_only,
+                span,
+            }
+        }
+        _ => return Err(line.err(format!("unknown instruction '{word}'"))),
+    };
+    Ok(instr)
+}
+
+fn parse_terminator(line: &mut Line) -> Result<Option<Terminator>, MirParseError> {
+    let Some(Tok::Ident(word)) = line.peek() else {
+        return Ok(None);
+    };
+    let term = match word.as_str() {
+        "goto" => {
+            line.pos += 1;
+            Terminator::Goto(line.block()?)
+        }
+        "if" => {
+            line.pos += 1;
+            let cond = line.value()?;
+            line.keyword("then")?;
+            let then_bb = line.block()?;
+            line.keyword("else")?;
+            Terminator::If {
+                cond,
+                then_bb,
+                else_bb: line.block()?,
+            }
+        }
+        "return" => {
+            line.pos += 1;
+            if line.at_end() {
+                Terminator::Return(None)
+            } else {
+                Terminator::Return(Some(line.value()?))
+            }
+        }
+        "unreachable" => {
+            line.pos += 1;
+            Terminator::Unreachable
+        }
+        _ => return Ok(None),
+    };
+    Ok(Some(term))
+}
@@ -0,0 +1,384 @@
+use crate::mir::*;
+use crate::typeck::{NaTy, PrimTy, ShapeTy, TypeState, TypeTerm};
+use std::fmt::Write;
+
+pub fn print_fn(fn_ir: &FnIR) -> String {
+    let mut out = String::new();
+    let params: Vec<String> = fn_ir.params.iter().map(|p| name(p)).collect();
+    let _ = writeln!(out, "fn {}({}) {{", name(&fn_ir.name), params.join(", "));
+    let _ = writeln!(out, "  entry bb{}", fn_ir.entry);
+    let _ = writeln!(out, "  body bb{}", fn_ir.body_head);
+    if fn_ir.unsupported_dynamic {
+        let _ = writeln!(out, "  dynamic");
+    }
+    if fn_ir.opaque_interop {
+        let _ = writeln!(out, "  opaque");
+    }
+    for value in &fn_ir.values {
+        let _ = writeln!(out, "  {}", value_line(value));
+    }
+    for block in &fn_ir.blocks {
+        let _ = writeln!(out, "bb{}:", block.id);
+        for instr in &block.instrs {
+            let _ = writeln!(out, "  {}", instr_line(instr));
+        }
+        let _ = writeln!(out, "  {}", term_line(&block.term));
+    }
+    out.push_str("}\n");
+    out
+}
+
+pub fn print_fns<'a>(fns: impl IntoIterator<Item = &'a FnIR>) -> String {
+    fns.into_iter().map(print_fn).collect::<Vec<_>>().join("\n")
+}
+
+fn value_line(value: &Value) -> String {
+    let mut line = format!("%{} = {}", value.id, kind_text(value));
+    if value.value_ty != TypeState::unknown() {
+        let _ = write!(line, " : {}", type_text(value.value_ty));
+    }
+    if let Some(var) = &value.origin_var {
+        let _ = write!(line, " origin={}", name(var));
+    }
+    if value.value_term != TypeTerm::Any {
+        let _ = write!(line, " term={}", term_text(&value.value_term));
+    }
+    let flags = fact_flags(value.facts.flags);
+    if !flags.is_empty() {
+        let _ = write!(line, " facts={}", flags.join("|"));
+    }
+    if value.facts.interval != flow::Interval::BOTTOM {
+        let bound = |v: i64| match v {
+            i64::MIN => "-inf".to_string(),
+            i64::MAX => "inf".to_string(),
+            v => v.to_string(),
+        };
+        let _ = write!(
+            line,
+            " range=[{},{}]",
+            bound(value.facts.interval.min),
+            bound(value.facts.interval.max)
+        );
+    }
+    match value.escape {
+        EscapeStatus::Unknown => {}
+        EscapeStatus::Local => line.push_str(" escape=local"),
+        EscapeStatus::Escaped => line.push_str(" escape=escaped"),
+    }
+    line
+}
+
+fn kind_text(value: &Value) -> String {
+    let v = |id: &ValueId| format!("%{id}");
+    let list = |ids: &[ValueId]| ids.iter().map(v).collect::<Vec<_>>().join(", ");
+    match &value.kind {
+        ValueKind::Const(lit) => format!("const {}", lit_text(lit)),
+        ValueKind::Phi { args } => {
+            let args: Vec<String> = args
+                .iter()
+                .map(|(val, from)| format!("bb{from}: %{val}"))
+                .collect();
+            match value.phi_block {
+                Some(bid) => format!("phi in bb{bid} [{}]", args.join(", ")),
+                None => format!("phi [{}]", args.join(", ")),
+            }
+        }
+        ValueKind::Param { index } => format!("param {index}"),
+        ValueKind::Len { base } => format!("len {}", v(base)),
+        ValueKind::Indices { base } => format!("indices {}", v(base)),
+        ValueKind::Range { start, end } => format!("range {}, {}", v(start), v(end)),
+        ValueKind::Binary { op, lhs, rhs } => {
+            format!("{} {}, {}", binop_name(*op), v(lhs), v(rhs))
+        }
+        ValueKind::Unary { op, rhs } => format!("{} {}", unop_name(*op), v(rhs)),
+        ValueKind::Call {
+            callee,
+            args,
+            names,
+        } => {
+            let args: Vec<String> = args
+                .iter()
+                .enumerate()
+                .map(|(i, arg)| match names.get(i).and_then(Option::as_ref) {
+                    Some(label) => format!("{}: %{arg}", name(label)),
+                    None => format!("%{arg}"),
+                })
+                .collect();
+            format!("call {}({})", quote(callee), args.join(", "))
+        }
+        ValueKind::RecordLit { fields } => {
+            let fields: Vec<String> = fields
+                .iter()
+                .map(|(field, val)| format!("{}: %{val}", name(field)))
+                .collect();
+            format!("record {{{}}}", fields.join(", "))
+        }
+        ValueKind::FieldGet { base, field } => format!("field_get {}, {}", v(base), quote(field)),
+        ValueKind::FieldSet { base, field, value } => {
+            format!("field_set {}, {}, {}", v(base), quote(field), v(value))
+        }
+        ValueKind::Intrinsic { op, args } => {
+            format!("intrinsic {}({})", intrinsic_name(*op), list(args))
+        }
+        ValueKind::Index1D {
+            base,
+            idx,
+            is_safe,
+            is_na_safe,
+        } => {
+            let mut text = format!("index1d {}[{}]", v(base), v(idx));
+            if *is_safe {
+                text.push_str(" safe");
+            }
+            if *is_na_safe {
+                text.push_str(" na_safe");
+            }
+            text
+        }
+        ValueKind::Index2D { base, r, c } => format!("index2d {}[{}, {}]", v(base), v(r), v(c)),
+        ValueKind::Index3D { base, i, j, k } => {
+            format!("index3d {}[{}, {}, {}]", v(base), v(i), v(j), v(k))
+        }
+        ValueKind::Load { var } => format!("load {}", name(var)),
+        ValueKind::RSymbol { name: sym } => format!("rsymbol {}", quote(sym)),
+    }
+}
+
+fn instr_line(instr: &Instr) -> String {
+    match instr {
+        Instr::Assign { dst, src, .. } => format!("assign {} = %{src}", name(dst)),
+        Instr::Eval { val, .. } => format!("eval %{val}"),
+        Instr::StoreIndex1D {
+            base,
+            idx,
+            val,
+            is_safe,
+            is_na_safe,
+            is_vector,
+            ..
+        } => {
+            let mut text = format!("store1d %{base}[%{idx}] = %{val}");
+            for (flag, label) in [
+                (*is_safe, " safe"),
+                (*is_na_safe, " na_safe"),
+                (*is_vector, " vector"),
+            ] {
+                if flag {
+                    text.push_str(label);
+                }
+            }
+            text
+        }
+        Instr::StoreIndex2D {
+            base, r, c, val, ..
+        } => {
+            format!("store2d %{base}[%{r}, %{c}] = %{val}")
+        }
+        Instr::StoreIndex3D {
+            base, i, j, k, val, ..
+        } => format!("store3d %{base}[%{i}, %{j}, %{k}] = %{val}"),
+        Instr::UnsafeRBlock {
+            code, read_only, ..
+        } => {
+            if *read_only {
+                format!("unsafe_r read_only {}", quote(code))
+            } else {
+                format!("unsafe_r {}", quote(code))
+            }
+        }
+    }
+}
+
+fn term_line(term: &Terminator) -> String {
+    match term {
+        Terminator::Goto(target) => format!("goto bb{target}"),
+        Terminator::If {
+            cond,
+            then_bb,
+            else_bb,
+        } => format!("if %{cond} then bb{then_bb} else bb{else_bb}"),
+        Terminator::Return(Some(val)) => format!("return %{val}"),
+        Terminator::Return(None) => "return".to_string(),
+        Terminator::Unreachable => "unreachable".to_string(),
+    }
+}
+
+fn lit_text(lit: &Lit) -> String {
+    match lit {
+        Lit::Int(i) => format!("{i}L"),
+        Lit::Float(f) => format!("{f:?}"),
+        Lit::Str(s) => quote(s),
+        Lit::Bool(true) => "TRUE".to_string(),
+        Lit::Bool(false) => "FALSE".to_string(),
+        Lit::Null => "NULL".to_string(),
+        Lit::Na => "NA".to_string(),
+    }
+}
+
+pub(crate) fn type_text(ty: TypeState) -> String {
+    let prim = match ty.prim {
+        PrimTy::Any => "any",
+        PrimTy::Null => "null",
+        PrimTy::Logical => "lgl",
+        PrimTy::Int => "int",
+        PrimTy::Double => "dbl",
+        PrimTy::Char => "chr",
+    };
+    let shape = match ty.shape {
+        ShapeTy::Unknown => "?",
+        ShapeTy::Scalar => "scalar",
+        ShapeTy::Vector => "vector",
+        ShapeTy::Matrix => "matrix",
+    };
+    let mut text = format!("{prim} {shape}");
+    if ty.na == NaTy::Never {
+        text.push_str(" !na");
+    }
+    if let Some(sym) = ty.len_sym {
+        let _ = write!(text, " len({})", sym.0);
+    }
+    text
+}
+
+pub(crate) fn term_text(term: &TypeTerm) -> String {
+    let dim = |d: &Option<i64>| d.map_or_else(|| "?".to_string(), |d| d.to_string());
+    let many = |terms: &[TypeTerm]| terms.iter().map(term_text).collect::<Vec<_>>().join(",");
+    let named = |fields: &[(String, TypeTerm)]| {
+        fields
+            .iter()
+            .map(|(field, term)| format!("{}:{}", name(field), term_text(term)))
+            .collect::<Vec<_>>()
+            .join(",")
+    };
+    match term {
+        TypeTerm::Any => "any".to_string(),
+        TypeTerm::Never => "never".to_string(),
+        TypeTerm::Null => "null".to_string(),
+        TypeTerm::Logical => "lgl".to_string(),
+        TypeTerm::Int => "int".to_string(),
+        TypeTerm::Double => "dbl".to_string(),
+        TypeTerm::Char => "chr".to_string(),
+        TypeTerm::Vector(elem) => format!("vec<{}>", term_text(elem)),
+        TypeTerm::VectorLen(elem, len) => format!("vec<{},{}>", term_text(elem), dim(len)),
+        TypeTerm::Matrix(elem) => format!("mat<{}>", term_text(elem)),
+        TypeTerm::MatrixDim(elem, r, c) => {
+            format!("mat<{},{},{}>", term_text(elem), dim(r), dim(c))
+        }
+        TypeTerm::ArrayDim(elem, dims) => {
+            let dims: Vec<String> = dims.iter().map(dim).collect();
+            format!("array<{},[{}]>", term_text(elem), dims.join(","))
+        }
+        TypeTerm::DataFrame(cols) => format!("df<{}>", many(cols)),
+        TypeTerm::DataFrameNamed(cols) => format!("dfn<{}>", named(cols)),
+        TypeTerm::NamedList(fields) => format!("nlist<{}>", named(fields)),
+        TypeTerm::List(elem) => format!("list<{}>", term_text(elem)),
+        TypeTerm::Boxed(inner) => format!("box<{}>", term_text(inner)),
+        TypeTerm::Option(inner) => format!("option<{}>", term_text(inner)),
+        TypeTerm::Union(arms) => format!("union<{}>", many(arms)),
+    }
+}
+
+pub(super) const FACT_FLAGS: &[(u32, &str)] = &[
+    (Facts::INT_SCALAR, "int_scalar"),
+    (Facts::BOOL_SCALAR, "bool_scalar"),
+    (Facts::NON_NA, "non_na"),
+    (Facts::NON_NEG, "non_neg"),
+    (Facts::IN_BOUNDS, "in_bounds"),
+    (Facts::ONE_BASED, "one_based"),
+    (Facts::IS_VECTOR, "is_vector"),
+];
+
+fn fact_flags(flags: u32) -> Vec<String> {
+    let mut out: Vec<String> = FACT_FLAGS
+        .iter()
+        .filter(|(bit, _)| flags & bit != 0)
+        .map(|(_, label)| (*label).to_string())
+        .collect();
+    let known = FACT_FLAGS.iter().fold(0, |acc, (bit, _)| acc | bit);
+    if flags & !known != 0 {
+        out.push(format!("0x{:x}", flags & !known));
+    }
+    out
+}
+
+pub(super) const BINOPS: &[(BinOp, &str)] = &[
+    (BinOp::Add, "add"),
+    (BinOp::Sub, "sub"),
+    (BinOp::Mul, "mul"),
+    (BinOp::Div, "div"),
+    (BinOp::Mod, "mod"),
+    (BinOp::MatMul, "matmul"),
+    (BinOp::Eq, "eq"),
+    (BinOp::Ne, "ne"),
+    (BinOp::Lt, "lt"),
+    (BinOp::Le, "le"),
+    (BinOp::Gt, "gt"),
+    (BinOp::Ge, "ge"),
+    (BinOp::And, "and"),
+    (BinOp::Or, "or"),
+];
+
+pub(super) const UNOPS: &[(UnaryOp, &str)] = &[
+    (UnaryOp::Neg, "neg"),
+    (UnaryOp::Not, "not"),
+    (UnaryOp::Formula, "formula"),
+];
+
+pub(super) const INTRINSICS: &[(IntrinsicOp, &str)] = &[
+    (IntrinsicOp::VecAddF64, "vec_add_f64"),
+    (IntrinsicOp::VecSubF64, "vec_sub_f64"),
+    (IntrinsicOp::VecMulF64, "vec_mul_f64"),
+    (IntrinsicOp::VecDivF64, "vec_div_f64"),
+    (IntrinsicOp::VecAbsF64, "vec_abs_f64"),
+    (IntrinsicOp::VecLogF64, "vec_log_f64"),
+    (IntrinsicOp::VecSqrtF64, "vec_sqrt_f64"),
+    (IntrinsicOp::VecPmaxF64, "vec_pmax_f64"),
+    (IntrinsicOp::VecPminF64, "vec_pmin_f64"),
+    (IntrinsicOp::VecSumF64, "vec_sum_f64"),
+    (IntrinsicOp::VecMeanF64, "vec_mean_f64"),
+];
+
+fn binop_name(op: BinOp) -> &'static str {
+    BINOPS
+        .iter()
+        .find(|(o, _)| *o == op)
+        .map_or("?", |(_, n)| n)
+}
+
+fn unop_name(op: UnaryOp) -> &'static str {
+    UNOPS.iter().find(|(o, _)| *o == op).map_or("?", |(_, n)| n)
+}
+
+fn intrinsic_name(op: IntrinsicOp) -> &'static str {
+    INTRINSICS
+        .iter()
+        .find(|(o, _)| *o == op)
+        .map_or("?", |(_, n)| n)
+}
+
+/// Names that lex as a single identifier are printed bare, anything else
+/// (R operators, backticked names, empty strings) is quoted.
+fn name(text: &str) -> String {
+    if super::is_bare_name(text) {
+        text.to_string()
+    } else {
+        quote(text)
+    }
+}
+
+fn quote(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() + 2);
+    out.push('"');
+    for ch in text.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            '\r' => out.push_str("\\r"),
+            ch => out.push(ch),
+        }
+    }
+    out.push('"');
+    out
+}
@@ -0,0 +1,144 @@
+use super::*;
+use crate::mir::*;
+use crate::typeck::{LenSym, NaTy, PrimTy, ShapeTy, TypeState, TypeTerm};
+use crate::utils::Span;
+
+const LOOP: &str = r#"
+// A counted loop with a phi-carried index.
+fn count(x) {
+  entry bb0
+  body bb0
+  %0 = param 0 : dbl vector origin=x
+  %1 = const 1L : int scalar !na facts=int_scalar|non_na range=[1,1]
+  %2 = phi in bb1 [bb0: %1, bb2: %5] origin=i
+  %3 = len %0 : int scalar !na
+  %4 = le %2, %3
+  %5 = add %2, %1
+  %6 = index1d %0[%2] safe
+bb0:
+  goto bb1
+bb1:
+  if %4 then bb2 else bb3
+bb2:
+  store1d %0[%2] = %6 safe na_safe
+  goto bb1
+bb3:
+  return %0
+}
+"#;
+
+#[test]
+fn parses_values_blocks_and_phis() {
+    let fn_ir = parse_fn(LOOP).expect("loop should parse");
+    assert_eq!(fn_ir.name, "count");
+    assert_eq!(fn_ir.params, vec!["x".to_string()]);
+    assert_eq!(fn_ir.values.len(), 7);
+    assert_eq!(fn_ir.blocks.len(), 4);
+
+    let phi = &fn_ir.values[2];
+    assert_eq!(phi.phi_block, Some(1));
+    assert_eq!(phi.origin_var.as_deref(), Some("i"));
+    assert!(matches!(&phi.kind, ValueKind::Phi { args } if args == &[(1, 0), (5, 2)]));
+
+    let one = &fn_ir.values[1];
+    assert_eq!(one.kind, ValueKind::Const(Lit::Int(1)));
+    assert_eq!(one.value_ty.prim, PrimTy::Int);
+    assert_eq!(one.value_ty.na, NaTy::Never);
+    assert!(one.facts.has(Facts::INT_SCALAR | Facts::NON_NA));
+    assert_eq!(one.facts.interval, flow::Interval::point(1));
+
+    assert!(matches!(
+        fn_ir.blocks[1].term,
+        Terminator::If {
+            cond: 4,
+            then_bb: 2,
+            else_bb: 3
+        }
+    ));
+    assert!(matches!(
+        fn_ir.blocks[2].instrs[..],
+        [Instr::StoreIndex1D {
+            is_safe: true,
+            is_na_safe: true,
+            is_vector: false,
+            ..
+        }]
+    ));
+}
+
+#[test]
+fn printing_round_trips() {
+    let mut fn_ir = parse_fn(LOOP).unwrap();
+    let call = fn_ir.add_value(
+        ValueKind::Call {
+            callee: "rep.int".to_string(),
+            args: vec![1, 3],
+            names: vec![None, Some("times".to_string())],
+        },
+        Span::default(),
+        Facts::empty(),
+        Some("out tmp".to_string()),
+    );
+    fn_ir.values[call].value_ty = TypeState {
+        prim: PrimTy::Double,
+        shape: ShapeTy::Vector,
+        na: NaTy::Maybe,
+        len_sym: Some(LenSym(3)),
+    };
+    fn_ir.values[call].value_term = TypeTerm::NamedList(vec![
+        (
+            "a".to_string(),
+            TypeTerm::VectorLen(Box::new(TypeTerm::Double), None),
+        ),
+        (
+            "b c".to_string(),
+            TypeTerm::MatrixDim(Box::new(TypeTerm::Int), Some(2), None),
+        ),
+    ]);
+    fn_ir.values[call].escape = EscapeStatus::Local;
+    fn_ir.add_value(
+        ValueKind::Const(Lit::Str("say \"hi\"\n".to_string())),
+        Span::default(),
+        Facts::empty(),
+        None,
+    );
+    fn_ir.add_value(
+        ValueKind::Const(Lit::Float(-2.5e-7)),
+        Span::default(),
+        Facts::new(Facts::NON_NEG, flow::Interval::TOP),
+        None,
+    );
+
+    let text = print_fn(&fn_ir);
+    let reparsed = parse_fn(&text).unwrap_or_else(|e| panic!("{e}\n{text}"));
+    assert_eq!(print_fn(&reparsed), text);
+    assert_eq!(
+        reparsed.values[call].value_term,
+        fn_ir.values[call].value_term
+    );
+    assert_eq!(reparsed.values[call].origin_var.as_deref(), Some("out tmp"));
+}
+
+#[test]
+fn errors_point_at_the_offending_line() {
+    let cases = [
+        ("fn f() {\nbb0:\n  return %3\n}\n", 3, "undefined value %3"),
+        (
+            "fn f() {\n  %0 = frobnicate %0\nbb0:\n  return\n}\n",
+            2,
+            "unknown value kind 'frobnicate'",
+        ),
+        ("fn f() {\nbb0:\n  eval %0\n}\n", 2, "has no terminator"),
+        ("fn f() {\nbb1:\n  return\n}\n", 4, "bb0 is missing"),
+        (
+            "fn f() {\n  %0 = param 0\nbb0:\n  return %0\n}\n",
+            2,
+            "parameter index 0 is out of range",
+        ),
+    ];
+    for (src, line, needle) in cases {
+        let err = parse_fns(src).unwrap_err();
+        assert_eq!(err.line, line, "{err}");
+        assert!(err.message.contains(needle), "{err}");
+    }
+}
@@ -0,0 +1,394 @@
+//! Exported API of a package release.
+//!
+//! `RR registry diff` reports how the surface changed between two releases
+//! and `RR publish` refuses a minor or patch release that breaks it. The
+//! surface is read from parsed sources: exported and re-exported functions,
+//! exported traits with their method signatures, and exported impls.
+
+use super::*;
+use crate::syntax::ast::{FnParam, StmtKind, TraitMethodSig, TypeExpr};
+use crate::syntax::parse::Parser;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct ApiParam {
+    name: String,
+    ty: Option<String>,
+    has_default: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct ApiFn {
+    params: Vec<ApiParam>,
+    ret: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct ApiMethod {
+    sig: ApiFn,
+    provided: bool,
+}
+
+/// A function callers reach by its exported name. `sig` is `None` for a
+/// re-export of a function defined outside the release.
+#[derive(Clone, Debug)]
+struct ApiFnEntry {
+    file: String,
+    sig: Option<ApiFn>,
+}
+
+/// Functions are keyed by name because all module functions share one R
+/// namespace, so moving one to another file does not change the surface.
+/// Traits and impls are keyed by `(file, name)`; impls are named
+/// `Trait for Type`.
+#[derive(Clone, Debug, Default)]
+pub(super) struct ApiSurface {
+    fns: BTreeMap<String, ApiFnEntry>,
+    traits: BTreeMap<(String, String), BTreeMap<String, ApiMethod>>,
+    impls: BTreeMap<(String, String), bool>,
+}
+
+impl ApiSurface {
+    /// Read the surface of every `.rr` file in a release's file map.
+    pub(super) fn from_files(files: &BTreeMap<String, Vec<u8>>) -> Result<Self, String> {
+        let mut surface = Self::default();
+        for (rel, bytes) in files {
+            if !rel.ends_with(".rr") {
+                continue;
+            }
+            let source = String::from_utf8_lossy(bytes);
+            surface.add_file(rel, &source)?;
+        }
+        Ok(surface)
+    }
+
+    fn add_file(&mut self, rel: &str, source: &str) -> Result<(), String> {
+        let program = Parser::new(source)
+            .parse_program()
+            .map_err(|e| format!("failed to read the API of '{}': {}", rel, e.message))?;
+        for stmt in &program.stmts {
+            match &stmt.kind {
+                StmtKind::Export(decl) => {
+                    self.fns.insert(
+                        decl.name.clone(),
+                        ApiFnEntry {
+                            file: rel.to_string(),
+                            sig: Some(api_fn(&decl.params, decl.ret_ty_hint.as_ref())),
+                        },
+                    );
+                }
+                StmtKind::ReExport { names, .. } => {
+                    // The defining `export fn` supplies the signature when it
+                    // is part of the release, whichever file is read first.
+                    for name in names {
+                        self.fns.entry(name.clone()).or_insert_with(|| ApiFnEntry {
+                            file: rel.to_string(),
+                            sig: None,
+                        });
+                    }
+                }
+                StmtKind::TraitDecl(decl) if decl.public => {
+                    let methods = decl
+                        .methods
+                        .iter()
+                        .map(|method| (method.name.clone(), api_method(method)))
+                        .collect();
+                    self.traits
+                        .insert((rel.to_string(), decl.name.clone()), methods);
+                }
+                StmtKind::ImplDecl(decl) if decl.public && !decl.negative => {
+                    let name = format!("{} for {}", decl.trait_name, type_text(&decl.for_ty));
+                    self.impls.insert((rel.to_string(), name), decl.s3);
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ApiChangeKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Added => "added",
+            Self::Removed => "removed",
+            Self::Narrowed => "narrowed",
+            Self::Widened => "widened",
+        }
+    }
+
+    /// Whether callers or implementors written against the old release can
+    /// stop compiling.
+    pub fn is_breaking(self) -> bool {
+        matches!(self, Self::Removed | Self::Narrowed)
+    }
+}
+
+/// Every change from `old` to `new`, ordered by file and item.
+pub(super) fn diff_api(old: &ApiSurface, new: &ApiSurface) -> Vec<ApiChange> {
+    let mut changes = Vec::new();
+    for (name, old_fn) in &old.fns {
+        match new.fns.get(name) {
+            Some(new_fn) => {
+                if let (Some(old_sig), Some(new_sig)) = (&old_fn.sig, &new_fn.sig) {
+                    let key = (new_fn.file.clone(), name.clone());
+                    diff_fn(&mut changes, &key, "fn", old_sig, new_sig);
+                }
+            }
+            None => {
+                let key = (old_fn.file.clone(), name.clone());
+                push(&mut changes, &key, "fn", ApiChangeKind::Removed, "removed");
+            }
+        }
+    }
+    for (name, new_fn) in &new.fns {
+        if !old.fns.contains_key(name) {
+            let key = (new_fn.file.clone(), name.clone());
+            push(&mut changes, &key, "fn", ApiChangeKind::Added, "added");
+        }
+    }
+
+    for (key, old_methods) in &old.traits {
+        let Some(new_methods) = new.traits.get(key) else {
+            push(
+                &mut changes,
+                key,
+                "trait",
+                ApiChangeKind::Removed,
+                "removed",
+            );
+            continue;
+        };
+        for (name, old_method) in old_methods {
+            let method_key = (key.0.clone(), format!("{}.{}", key.1, name));
+            match new_methods.get(name) {
+                Some(new_method) => {
+                    diff_fn(
+                        &mut changes,
+                        &method_key,
+                        "trait method",
+                        &old_method.sig,
+                        &new_method.sig,
+                    );
+                    if old_method.provided && !new_method.provided {
+                        push(
+                            &mut changes,
+                            &method_key,
+                            "trait method",
+                            ApiChangeKind::Narrowed,
+                            "default body removed; implementors must now provide it",
+                        );
+                    }
+                }
+                None => push(
+                    &mut changes,
+                    &method_key,
+                    "trait method",
+                    ApiChangeKind::Removed,
+                    "removed",
+                ),
+            }
+        }
+        for (name, new_method) in new_methods {
+            if old_methods.contains_key(name) {
+                continue;
+            }
+            let method_key = (key.0.clone(), format!("{}.{}", key.1, name));
+            if new_method.provided {
+                push(
+                    &mut changes,
+                    &method_key,
+                    "trait method",
+                    ApiChangeKind::Added,
+                    "added with a default body",
+                );
+            } else {
+                push(
+                    &mut changes,
+                    &method_key,
+                    "trait method",
+                    ApiChangeKind::Narrowed,
+                    "added without a default body; existing impls must provide it",
+                );
+            }
+        }
+    }
+    for key in new
+        .traits
+        .keys()
+        .filter(|key| !old.traits.contains_key(*key))
+    {
+        push(&mut changes, key, "trait", ApiChangeKind::Added, "added");
+    }
+
+    for (key, old_s3) in &old.impls {
+        match new.impls.get(key) {
+            None => push(&mut changes, key, "impl", ApiChangeKind::Removed, "removed"),
+            Some(false) if *old_s3 => push(
+                &mut changes,
+                key,
+                "impl",
+                ApiChangeKind::Removed,
+                "no longer exported as S3 methods",
+            ),
+            Some(true) if !old_s3 => push(
+                &mut changes,
+                key,
+                "impl",
+                ApiChangeKind::Added,
+                "now exported as S3 methods",
+            ),
+            Some(_) => {}
+        }
+    }
+    for key in new.impls.keys().filter(|key| !old.impls.contains_key(*key)) {
+        push(&mut changes, key, "impl", ApiChangeKind::Added, "added");
+    }
+
+    changes.sort_by(|a, b| (&a.file, &a.item).cmp(&(&b.file, &b.item)));
+    changes
+}
+
+fn diff_fn(
+    changes: &mut Vec<ApiChange>,
+    key: &(String, String),
+    kind: &str,
+    old: &ApiFn,
+    new: &ApiFn,
+) {
+    for (idx, old_param) in old.params.iter().enumerate() {
+        let Some(new_idx) = new.params.iter().position(|p| p.name == old_param.name) else {
+            let detail = format!("parameter `{}` removed", old_param.name);
+            push(changes, key, kind, ApiChangeKind::Narrowed, &detail);
+            continue;
+        };
+        let new_param = &new.params[new_idx];
+        // R passes arguments by position as often as by name.
+        if new_idx != idx {
+            let detail = format!(
+                "parameter `{}` moved from position {} to {}",
+                old_param.name,
+                idx + 1,
+                new_idx + 1
+            );
+            push(changes, key, kind, ApiChangeKind::Narrowed, &detail);
+        }
+        match (old_param.has_default, new_param.has_default) {
+            (true, false) => {
+                let detail = format!("parameter `{}` lost its default", old_param.name);
+                push(changes, key, kind, ApiChangeKind::Narrowed, &detail);
+            }
+            (false, true) => {
+                let detail = format!("parameter `{}` gained a default", old_param.name);
+                push(changes, key, kind, ApiChangeKind::Widened, &detail);
+            }
+            _ => {}
+        }
+        if let Some((change, detail)) = diff_param_type(old_param, new_param) {
+            push(changes, key, kind, change, &detail);
+        }
+    }
+    for new_param in &new.params {
+        if old.params.iter().any(|p| p.name == new_param.name) {
+            continue;
+        }
+        if new_param.has_default {
+            let detail = format!("optional parameter `{}` added", new_param.name);
+            push(changes, key, kind, ApiChangeKind::Widened, &detail);
+        } else {
+            let detail = format!("required parameter `{}` added", new_param.name);
+            push(changes, key, kind, ApiChangeKind::Narrowed, &detail);
+        }
+    }
+    // Callers may rely on a declared return type, so dropping or changing
+    // it breaks them; declaring one where there was none does not.
+    match (&old.ret, &new.ret) {
+        (Some(old_ret), Some(new_ret)) if old_ret != new_ret => {
+            let detail = format!("return type changed from {} to {}", old_ret, new_ret);
+            push(changes, key, kind, ApiChangeKind::Narrowed, &detail);
+        }
+        (Some(old_ret), None) => {
+            let detail = format!("return type {} no longer declared", old_ret);
+            push(changes, key, kind, ApiChangeKind::Narrowed, &detail);
+        }
+        (None, Some(new_ret)) => {
+            let detail = format!("return type {} declared", new_ret);
+            push(changes, key, kind, ApiChangeKind::Widened, &detail);
+        }
+        _ => {}
+    }
+}
+
+/// Parameter hints accept more when they go away and less when they
+/// appear or change. `any` is treated as no hint.
+fn diff_param_type(old: &ApiParam, new: &ApiParam) -> Option<(ApiChangeKind, String)> {
+    match (&old.ty, &new.ty) {
+        (Some(old_ty), Some(new_ty)) if old_ty != new_ty => Some((
+            ApiChangeKind::Narrowed,
+            format!(
+                "parameter `{}` type changed from {} to {}",
+                old.name, old_ty, new_ty
+            ),
+        )),
+        (None, Some(new_ty)) => Some((
+            ApiChangeKind::Narrowed,
+            format!("parameter `{}` now requires {}", old.name, new_ty),
+        )),
+        (Some(old_ty), None) => Some((
+            ApiChangeKind::Widened,
+            format!("parameter `{}` no longer requires {}", old.name, old_ty),
+        )),
+        _ => None,
+    }
+}
+
+fn push(
+    changes: &mut Vec<ApiChange>,
+    key: &(String, String),
+    kind: &str,
+    change: ApiChangeKind,
+    detail: &str,
+) {
+    changes.push(ApiChange {
+        kind: change,
+        file: key.0.clone(),
+        item: format!("{} {}", kind, key.1),
+        detail: detail.to_string(),
+    });
+}
+
+fn api_fn(params: &[FnParam], ret: Option<&TypeExpr>) -> ApiFn {
+    ApiFn {
+        params: params
+            .iter()
+            .map(|param| ApiParam {
+                name: param.name.clone(),
+                ty: param.ty_hint.as_ref().and_then(hint_text),
+                has_default: param.default.is_some(),
+            })
+            .collect(),
+        ret: ret.and_then(hint_text),
+    }
+}
+
+fn api_method(method: &TraitMethodSig) -> ApiMethod {
+    ApiMethod {
+        sig: api_fn(&method.params, method.ret_ty_hint.as_ref()),
+        provided: method.default_body.is_some(),
+    }
+}
+
+fn hint_text(ty: &TypeExpr) -> Option<String> {
+    let text = type_text(ty);
+    (text != "any").then_some(text)
+}
+
+fn type_text(ty: &TypeExpr) -> String {
+    match ty {
+        TypeExpr::Named(name) => name.clone(),
+        TypeExpr::Generic { base, args } => format!(
+            "{}<{}>",
+            base,
+            args.iter().map(type_text).collect::<Vec<_>>().join(", ")
+        ),
+    }
+}
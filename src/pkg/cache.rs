@@ -0,0 +1,526 @@
+use super::project::{load_lockfile, lock_map};
+use super::util::module_path_to_rel_path;
+use super::*;
+use std::time::Duration;
+
+/// Marker touched inside a registry checkout's `.git/` each time it is used,
+/// so `RR mod cache clean --older-than` can tell stale clones from live ones.
+const REGISTRY_LAST_USED_MARKER: &str = "rr-last-used";
+
+pub fn list_package_cache() -> Result<Vec<CacheEntry>, String> {
+    list_cache_entries(&package_home())
+}
+
+pub fn verify_package_cache() -> Result<CacheVerifyReport, String> {
+    verify_cache_entries(&package_home())
+}
+
+pub fn clean_package_cache(older_than: Option<Duration>) -> Result<CacheCleanReport, String> {
+    clean_cache_entries(&package_home(), older_than, SystemTime::now())
+}
+
+/// Parse an age such as `30d`, `12h`, `90m`, `45s` or `2w`.
+pub fn parse_cache_age(raw: &str) -> Result<Duration, String> {
+    let raw = raw.trim();
+    let split = raw
+        .find(|ch: char| !ch.is_ascii_digit())
+        .unwrap_or(raw.len());
+    let (digits, unit) = raw.split_at(split);
+    let Ok(count) = digits.parse::<u64>() else {
+        return Err(format!(
+            "invalid age '{raw}'; expected a number with a unit such as 30d, 12h or 90m"
+        ));
+    };
+    let unit_secs = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" | "" => 24 * 60 * 60,
+        "w" => 7 * 24 * 60 * 60,
+        _ => {
+            return Err(format!(
+                "invalid age unit '{unit}' in '{raw}'; use s, m, h, d or w"
+            ));
+        }
+    };
+    Ok(Duration::from_secs(count.saturating_mul(unit_secs)))
+}
+
+pub(super) fn list_cache_entries(home: &Path) -> Result<Vec<CacheEntry>, String> {
+    let mut entries = Vec::new();
+    let module_root = home.join("pkg").join("mod");
+    if module_root.is_dir() {
+        collect_module_cache_entries(&module_root, &module_root, &mut entries)?;
+    }
+    let registry_root = home.join("registry");
+    if registry_root.is_dir() {
+        for entry in fs::read_dir(&registry_root)
+            .map_err(|e| format!("failed to read '{}': {}", registry_root.display(), e))?
+        {
+            let entry = entry.map_err(|e| format!("failed to read directory entry: {}", e))?;
+            let root = entry.path();
+            if !root.is_dir() {
+                continue;
+            }
+            let name = registry_remote_url(&root).unwrap_or_else(|| {
+                root.file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_default()
+            });
+            let marker = root.join(".git").join(REGISTRY_LAST_USED_MARKER);
+            let last_used = if marker.is_file() {
+                marker
+            } else {
+                root.join(".git")
+            };
+            entries.push(CacheEntry {
+                kind: "registry".to_string(),
+                name,
+                version: None,
+                size_bytes: directory_size(&root),
+                last_used_secs: modified_secs(&last_used).or_else(|| modified_secs(&root)),
+                root,
+            });
+        }
+    }
+    entries.sort_by(|a, b| {
+        (a.kind.as_str(), a.name.as_str(), a.version.as_deref()).cmp(&(
+            b.kind.as_str(),
+            b.name.as_str(),
+            b.version.as_deref(),
+        ))
+    });
+    Ok(entries)
+}
+
+fn collect_module_cache_entries(
+    module_root: &Path,
+    dir: &Path,
+    out: &mut Vec<CacheEntry>,
+) -> Result<(), String> {
+    let mut children = Vec::new();
+    for entry in
+        fs::read_dir(dir).map_err(|e| format!("failed to read '{}': {}", dir.display(), e))?
+    {
+        let entry = entry.map_err(|e| format!("failed to read directory entry: {}", e))?;
+        if entry.path().is_dir() {
+            children.push(entry.path());
+        }
+    }
+    children.sort();
+    for child in children {
+        let name = child
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let Some((leaf, version)) = name.split_once('@') else {
+            collect_module_cache_entries(module_root, &child, out)?;
+            continue;
+        };
+        let parent = child
+            .parent()
+            .and_then(|parent| parent.strip_prefix(module_root).ok())
+            .map(|rel| rel.to_string_lossy().replace('\\', "/"))
+            .unwrap_or_default();
+        let path = if parent.is_empty() {
+            leaf.to_string()
+        } else {
+            format!("{parent}/{leaf}")
+        };
+        let meta = child.join(".rr-meta");
+        out.push(CacheEntry {
+            kind: "module".to_string(),
+            name: path,
+            version: Some(version.to_string()),
+            size_bytes: directory_size(&child),
+            last_used_secs: modified_secs(&meta).or_else(|| modified_secs(&child)),
+            root: child,
+        });
+    }
+    Ok(())
+}
+
+pub(super) fn verify_cache_entries(home: &Path) -> Result<CacheVerifyReport, String> {
+    let entries = list_cache_entries(home)?;
+    let mut issues = Vec::new();
+    for entry in &entries {
+        let problem = if entry.kind == "registry" {
+            if !entry.root.join(".git").is_dir() {
+                Some("registry cache is not a git checkout".to_string())
+            } else if !entry.root.join("index").is_dir() {
+                Some("registry cache has no index/ directory".to_string())
+            } else {
+                None
+            }
+        } else if !entry.root.join("rr.mod").is_file() {
+            Some("cached module has no rr.mod".to_string())
+        } else if !entry.root.join(".rr-meta").is_file() {
+            Some("cached module has no .rr-meta checksum record".to_string())
+        } else {
+            let (_, expected) = read_meta_file(&entry.root)?;
+            let actual = directory_checksum(&entry.root)?;
+            (actual != expected)
+                .then(|| format!("checksum mismatch: expected {expected}, got {actual}"))
+        };
+        if let Some(message) = problem {
+            issues.push(CacheVerifyIssue {
+                entry: entry.clone(),
+                message,
+            });
+        }
+    }
+    Ok(CacheVerifyReport {
+        checked: entries.len(),
+        issues,
+    })
+}
+
+pub(super) fn clean_cache_entries(
+    home: &Path,
+    older_than: Option<Duration>,
+    now: SystemTime,
+) -> Result<CacheCleanReport, String> {
+    let now_secs = now
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default();
+    let mut removed = Vec::new();
+    let mut kept = 0usize;
+    for entry in list_cache_entries(home)? {
+        let stale = match (older_than, entry.last_used_secs) {
+            (None, _) => true,
+            (Some(age), Some(last_used)) => now_secs.saturating_sub(last_used) >= age.as_secs(),
+            (Some(_), None) => true,
+        };
+        if !stale {
+            kept += 1;
+            continue;
+        }
+        fs::remove_dir_all(&entry.root)
+            .map_err(|e| format!("failed to remove '{}': {}", entry.root.display(), e))?;
+        prune_empty_parents(&entry.root, home);
+        removed.push(entry);
+    }
+    let freed_bytes = removed.iter().map(|entry| entry.size_bytes).sum();
+    Ok(CacheCleanReport {
+        removed,
+        kept,
+        freed_bytes,
+    })
+}
+
+fn prune_empty_parents(removed: &Path, home: &Path) {
+    let stop = [home.join("pkg").join("mod"), home.join("registry")];
+    let mut cursor = removed.parent();
+    while let Some(dir) = cursor {
+        if !dir.starts_with(home) || stop.iter().any(|stop| stop == dir) {
+            break;
+        }
+        if fs::remove_dir(dir).is_err() {
+            break;
+        }
+        cursor = dir.parent();
+    }
+}
+
+fn directory_size(root: &Path) -> u64 {
+    let Ok(entries) = fs::read_dir(root) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .map(|entry| match entry.metadata() {
+            Ok(meta) if meta.is_dir() => directory_size(&entry.path()),
+            Ok(meta) => meta.len(),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
+fn modified_secs(path: &Path) -> Option<u64> {
+    fs::metadata(path)
+        .and_then(|meta| meta.modified())
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|elapsed| elapsed.as_secs())
+}
+
+fn registry_remote_url(root: &Path) -> Option<String> {
+    let config = fs::read_to_string(root.join(".git").join("config")).ok()?;
+    config.lines().find_map(|line| {
+        line.trim()
+            .strip_prefix("url")
+            .and_then(|rest| rest.trim_start().strip_prefix('='))
+            .map(|url| url.trim().to_string())
+    })
+}
+
+/// Record that a cached module or registry checkout was just used.
+pub(super) fn touch_cache_last_used(root: &Path) {
+    let git_dir = root.join(".git");
+    if git_dir.is_dir() {
+        let _ = fs::write(git_dir.join(REGISTRY_LAST_USED_MARKER), "");
+        return;
+    }
+    if let Ok(file) = fs::File::options().append(true).open(root.join(".rr-meta")) {
+        let _ = file.set_modified(SystemTime::now());
+    }
+}
+
+/// The newest version of `module_path` already unpacked in the module cache.
+pub(super) fn highest_cached_version(
+    package_home: &Path,
+    module_path: &str,
+) -> Result<Option<String>, String> {
+    let probe = module_cache_dir(package_home, module_path, "probe");
+    let Some(parent) = probe.parent() else {
+        return Ok(None);
+    };
+    if !parent.is_dir() {
+        return Ok(None);
+    }
+    let leaf = module_path.rsplit('/').next().unwrap_or(module_path);
+    let mut best: Option<String> = None;
+    for entry in
+        fs::read_dir(parent).map_err(|e| format!("failed to read '{}': {}", parent.display(), e))?
+    {
+        let entry = entry.map_err(|e| format!("failed to read directory entry: {}", e))?;
+        let name = entry.file_name().to_string_lossy().to_string();
+        let Some(version) = name
+            .strip_prefix(leaf)
+            .and_then(|rest| rest.strip_prefix('@'))
+        else {
+            continue;
+        };
+        if !entry.path().join("rr.mod").is_file()
+            || !version_matches_module_path(version, module_path)
+        {
+            continue;
+        }
+        match &best {
+            Some(current) if compare_versions(version, current) != Ordering::Greater => {}
+            _ => best = Some(version.to_string()),
+        }
+    }
+    Ok(best)
+}
+
+/// Offline counterpart of `resolve_manifest_dependencies`: the same minimal
+/// version selection, but every module must come from a `replace` target, the
+/// module cache or `vendor/`, and versions never drop below `rr.lock`. All
+/// unavailable modules are reported together.
+pub(super) fn resolve_offline_dependencies(
+    project_root: &Path,
+    manifest: &Manifest,
+) -> Result<Vec<InstalledModule>, String> {
+    let locked = if project_root.join("rr.lock").is_file() {
+        lock_map(&load_lockfile(project_root)?)
+    } else {
+        BTreeMap::new()
+    };
+    let at_least_locked = |path: &str, version: &str| -> String {
+        match locked.get(path) {
+            Some(entry) if compare_versions(&entry.version, version) == Ordering::Greater => {
+                entry.version.clone()
+            }
+            _ => version.to_string(),
+        }
+    };
+    let vendored = vendored_module_versions(project_root);
+    let mut state = InstallState {
+        package_home: package_home(),
+        installed: BTreeMap::new(),
+    };
+    let mut selected = BTreeMap::<String, SelectedModule>::new();
+    let mut queue = Vec::<String>::new();
+    let mut processed = BTreeMap::<String, String>::new();
+    let mut loaded = BTreeMap::<String, InstalledModule>::new();
+    let mut missing = BTreeMap::<String, String>::new();
+
+    for (path, version) in &manifest.requires {
+        selected.insert(
+            path.clone(),
+            SelectedModule {
+                version: at_least_locked(path, version),
+                direct: true,
+            },
+        );
+        queue.push(path.clone());
+    }
+
+    while let Some(module_path) = queue.pop() {
+        let Some(selection) = selected.get(&module_path).cloned() else {
+            continue;
+        };
+        if processed.get(&module_path) == Some(&selection.version) {
+            continue;
+        }
+        processed.insert(module_path.clone(), selection.version.clone());
+
+        let found = if manifest.replaces.contains_key(&module_path) {
+            Some(load_selected_module(
+                project_root,
+                manifest,
+                &module_path,
+                &selection.version,
+                selection.direct,
+                &mut state,
+            )?)
+        } else {
+            load_offline_module(
+                project_root,
+                &module_path,
+                &selection.version,
+                &locked,
+                &vendored,
+            )?
+        };
+        let Some(found) = found else {
+            missing.insert(module_path.clone(), selection.version.clone());
+            continue;
+        };
+        missing.remove(&module_path);
+        loaded.insert(module_path.clone(), found.installed);
+
+        for (dep_path, dep_version) in found.manifest.requires {
+            let dep_version = at_least_locked(&dep_path, &dep_version);
+            let should_enqueue = match selected.get_mut(&dep_path) {
+                Some(existing) => {
+                    if compare_versions(&existing.version, &dep_version) == Ordering::Less {
+                        existing.version = dep_version;
+                        true
+                    } else {
+                        false
+                    }
+                }
+                None => {
+                    selected.insert(
+                        dep_path.clone(),
+                        SelectedModule {
+                            version: dep_version,
+                            direct: false,
+                        },
+                    );
+                    true
+                }
+            };
+            if should_enqueue {
+                queue.push(dep_path);
+            }
+        }
+    }
+
+    if !missing.is_empty() {
+        // Modules behind a missing one were never reached; fall back to the
+        // lock so the report names everything the machine still needs.
+        for entry in locked.values() {
+            if loaded.contains_key(&entry.path)
+                || missing.contains_key(&entry.path)
+                || manifest.replaces.contains_key(&entry.path)
+            {
+                continue;
+            }
+            if load_offline_module(
+                project_root,
+                &entry.path,
+                &entry.version,
+                &locked,
+                &vendored,
+            )?
+            .is_none()
+            {
+                missing.insert(entry.path.clone(), entry.version.clone());
+            }
+        }
+        let listed: Vec<String> = missing
+            .iter()
+            .map(|(path, version)| format!("  {path}@{version}"))
+            .collect();
+        return Err(format!(
+            "offline mode: {} module(s) are not available from rr.lock, vendor/ or the module cache:\n{}\nrun once without --offline, or copy a vendor/ tree made by RR mod vendor",
+            listed.len(),
+            listed.join("\n")
+        ));
+    }
+
+    let mut final_modules = Vec::new();
+    for (path, selection) in selected {
+        if let Some(mut installed) = loaded.remove(&path) {
+            installed.direct = selection.direct;
+            final_modules.push(installed);
+        }
+    }
+    Ok(final_modules)
+}
+
+fn load_offline_module(
+    project_root: &Path,
+    module_path: &str,
+    version: &str,
+    locked: &BTreeMap<String, InstalledModule>,
+    vendored: &BTreeMap<String, String>,
+) -> Result<Option<LoadedModule>, String> {
+    let cache_dir = module_cache_dir(&package_home(), module_path, version);
+    if cache_dir.join("rr.mod").is_file() {
+        let installed = load_cached_module(&cache_dir, module_path, version, false)?;
+        let manifest = Manifest::load_from_dir(&cache_dir)?;
+        return Ok(Some(LoadedModule {
+            installed,
+            manifest,
+            root: cache_dir,
+        }));
+    }
+
+    let vendor_dir = project_root
+        .join("vendor")
+        .join(module_path_to_rel_path(module_path));
+    if vendored.get(module_path).map(String::as_str) != Some(version)
+        || !vendor_dir.join("rr.mod").is_file()
+    {
+        return Ok(None);
+    }
+    let locked_entry = locked
+        .get(module_path)
+        .filter(|entry| entry.version == version);
+    let sum = directory_checksum(&vendor_dir)?;
+    if let Some(entry) = locked_entry
+        && entry.sum != sum
+    {
+        return Err(format!(
+            "checksum mismatch for module '{}'\n  source root: {}\n  expected sum: {}\n  actual sum: {}\nrerun RR mod vendor to refresh the locked module contents",
+            module_path,
+            vendor_dir.display(),
+            entry.sum,
+            sum
+        ));
+    }
+    let installed = InstalledModule {
+        path: module_path.to_string(),
+        version: version.to_string(),
+        commit: locked_entry
+            .map(|entry| entry.commit.clone())
+            .unwrap_or_else(|| "vendor".to_string()),
+        sum,
+        direct: false,
+    };
+    let manifest = Manifest::load_from_dir(&vendor_dir)?;
+    Ok(Some(LoadedModule {
+        installed,
+        manifest,
+        root: vendor_dir,
+    }))
+}
+
+/// Module versions recorded in `vendor/modules.txt` by `RR mod vendor`.
+fn vendored_module_versions(project_root: &Path) -> BTreeMap<String, String> {
+    let Ok(content) = fs::read_to_string(project_root.join("vendor").join("modules.txt")) else {
+        return BTreeMap::new();
+    };
+    content
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            Some((parts.next()?.to_string(), parts.next()?.to_string()))
+        })
+        .collect()
+}
@@ -3,6 +3,9 @@ use std::cell::RefCell;
 use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static OFFLINE_OVERRIDE: AtomicBool = AtomicBool::new(false);
 
 thread_local! {
     static PROJECT_ROOT_HINT: RefCell<Option<PathBuf>> = const { RefCell::new(None) };
@@ -20,6 +23,23 @@ pub fn package_home() -> PathBuf {
         .join(".rr")
 }
 
+/// Force offline mode for the rest of the process, as `--offline` does.
+pub fn set_offline_mode(enabled: bool) {
+    OFFLINE_OVERRIDE.store(enabled, Ordering::Relaxed);
+}
+
+/// True when dependency resolution must not touch the network, either because
+/// `--offline` was passed or `RR_OFFLINE` is set to a truthy value.
+pub fn offline_mode() -> bool {
+    OFFLINE_OVERRIDE.load(Ordering::Relaxed)
+        || env::var("RR_OFFLINE").is_ok_and(|value| {
+            matches!(
+                value.trim().to_ascii_lowercase().as_str(),
+                "1" | "true" | "yes" | "on"
+            )
+        })
+}
+
 pub(super) fn registry_root() -> Result<Option<PathBuf>, String> {
     let Some(raw) = env::var_os("RR_REGISTRY_DIR").filter(|value| !value.is_empty()) else {
         return Ok(None);
@@ -1,7 +1,11 @@
+use super::env::offline_mode;
 use std::env;
 use std::path::Path;
 use std::process::Command;
 
+/// Git subcommands that talk to a remote and are refused in offline mode.
+const NETWORK_GIT_COMMANDS: &[&str] = &["clone", "fetch", "ls-remote", "pull", "push"];
+
 pub(super) fn run_git(cwd: Option<&Path>, args: &[&str]) -> Result<String, String> {
     run_git_with_env(cwd, &[], args)
 }
@@ -80,6 +84,14 @@ pub(super) fn run_git_with_env(
     envs: &[(&str, &str)],
     args: &[&str],
 ) -> Result<String, String> {
+    if offline_mode()
+        && let Some(subcommand) = args.first()
+        && NETWORK_GIT_COMMANDS.contains(subcommand)
+    {
+        return Err(format!(
+            "git {subcommand} needs network access, which is disabled in offline mode (--offline / RR_OFFLINE)"
+        ));
+    }
     let mut command = Command::new("git");
     command.args(args);
     if let Some(cwd) = cwd {
@@ -160,6 +172,11 @@ pub(super) fn create_git_tag(project_root: &Path, tag: &str) -> Result<(), Strin
 }
 
 pub(super) fn push_git_tag(project_root: &Path, remote: &str, tag: &str) -> Result<(), String> {
+    if offline_mode() {
+        return Err(format!(
+            "cannot push tag '{tag}' to '{remote}' in offline mode (--offline / RR_OFFLINE)"
+        ));
+    }
     let output = Command::new("git")
         .current_dir(project_root)
         .args(["push", remote, tag])
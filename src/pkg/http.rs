@@ -0,0 +1,277 @@
+//! Minimal HTTP/1.1 over `std::net` for HTTP registries. Only plain
+//! `http://` is supported; put a TLS-terminating proxy in front of
+//! `RR registry serve` for anything beyond a trusted network.
+
+use super::env::offline_mode;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::time::{Duration, Instant};
+
+/// Largest request or response body either side accepts.
+pub(super) const MAX_BODY_BYTES: usize = 256 * 1024 * 1024;
+const IO_TIMEOUT: Duration = Duration::from_secs(60);
+/// Time the server allows a client to send one whole request.
+const REQUEST_DEADLINE: Duration = Duration::from_secs(300);
+/// Longest request or header line the server reads.
+const MAX_LINE_BYTES: usize = 8 * 1024;
+const MAX_HEADERS: usize = 64;
+
+pub(super) struct HttpResponse {
+    pub status: u16,
+    pub body: Vec<u8>,
+}
+
+pub(super) struct HttpRequest {
+    pub method: String,
+    pub path: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl HttpRequest {
+    /// Header value by case-insensitive name.
+    pub(super) fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Send one request and read the whole response.
+pub(super) fn http_request(
+    method: &str,
+    url: &str,
+    headers: &[(&str, &str)],
+    body: &[u8],
+) -> Result<HttpResponse, String> {
+    if offline_mode() {
+        return Err(format!(
+            "{method} {url} needs network access, which is disabled in offline mode (--offline / RR_OFFLINE)"
+        ));
+    }
+    let Some(rest) = url.strip_prefix("http://") else {
+        return Err(format!("unsupported registry URL '{}'", url));
+    };
+    let (authority, path) = match rest.find('/') {
+        Some(idx) => (&rest[..idx], &rest[idx..]),
+        None => (rest, "/"),
+    };
+    let address = if authority.contains(':') {
+        authority.to_string()
+    } else {
+        format!("{authority}:80")
+    };
+    let mut stream = TcpStream::connect(&address)
+        .map_err(|e| format!("failed to connect to '{}': {}", url, e))?;
+    let _ = stream.set_read_timeout(Some(IO_TIMEOUT));
+    let _ = stream.set_write_timeout(Some(IO_TIMEOUT));
+
+    let mut head = format!(
+        "{method} {path} HTTP/1.1\r\nHost: {authority}\r\nUser-Agent: RR\r\nConnection: close\r\nContent-Length: {}\r\n",
+        body.len()
+    );
+    for (name, value) in headers {
+        head.push_str(&format!("{name}: {value}\r\n"));
+    }
+    head.push_str("\r\n");
+    stream
+        .write_all(head.as_bytes())
+        .and_then(|()| stream.write_all(body))
+        .map_err(|e| format!("failed to send request to '{}': {}", url, e))?;
+
+    let mut reader = BufReader::new(stream);
+    let status_line = read_line(&mut reader).map_err(|e| format!("{}: {}", url, e))?;
+    let status = status_line
+        .split_whitespace()
+        .nth(1)
+        .and_then(|code| code.parse::<u16>().ok())
+        .ok_or_else(|| format!("invalid HTTP response from '{}': {}", url, status_line))?;
+    let headers = read_headers(&mut reader).map_err(|e| format!("{}: {}", url, e))?;
+    let body = match content_length(&headers, MAX_BODY_BYTES)
+        .map_err(|(_, e)| format!("{}: {}", url, e))?
+    {
+        Some(len) => read_body(&mut reader, len),
+        None => {
+            let mut body = Vec::new();
+            reader
+                .by_ref()
+                .take(MAX_BODY_BYTES as u64)
+                .read_to_end(&mut body)
+                .map(|_| body)
+                .map_err(|e| e.to_string())
+        }
+    }
+    .map_err(|e| format!("failed to read response from '{}': {}", url, e))?;
+    Ok(HttpResponse { status, body })
+}
+
+/// GET `url`; `None` when the server answers 404.
+pub(super) fn http_get(url: &str) -> Result<Option<Vec<u8>>, String> {
+    let response = http_request("GET", url, &[], &[])?;
+    match response.status {
+        200 => Ok(Some(response.body)),
+        404 => Ok(None),
+        status => Err(format!(
+            "GET {} failed with HTTP {}: {}",
+            url,
+            status,
+            String::from_utf8_lossy(&response.body).trim()
+        )),
+    }
+}
+
+/// Read one request, which must arrive within [`REQUEST_DEADLINE`].
+/// `body_limit` gives the largest body accepted for a method and path.
+/// Errors carry the status to answer with.
+pub(super) fn read_http_request(
+    stream: &TcpStream,
+    body_limit: impl FnOnce(&str, &str) -> usize,
+) -> Result<HttpRequest, (u16, String)> {
+    let bad_request = |message: String| (400, message);
+    let mut reader = BufReader::new(DeadlineReader {
+        stream,
+        deadline: Instant::now() + REQUEST_DEADLINE,
+    });
+    let request_line = read_line(&mut reader).map_err(bad_request)?;
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(path)) = (parts.next(), parts.next()) else {
+        return Err(bad_request(format!(
+            "invalid request line '{}'",
+            request_line
+        )));
+    };
+    let (method, path) = (method.to_string(), path.to_string());
+    let headers = read_headers(&mut reader).map_err(bad_request)?;
+    let limit = body_limit(&method, &path);
+    let len = content_length(&headers, limit)?.unwrap_or(0);
+    let body = read_body(&mut reader, len).map_err(bad_request)?;
+    Ok(HttpRequest {
+        method,
+        path,
+        headers,
+        body,
+    })
+}
+
+pub(super) fn write_http_response(
+    stream: &mut TcpStream,
+    status: u16,
+    content_type: &str,
+    body: &[u8],
+) -> Result<(), String> {
+    let _ = stream.set_write_timeout(Some(IO_TIMEOUT));
+    let reason = match status {
+        200 => "OK",
+        400 => "Bad Request",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        409 => "Conflict",
+        413 => "Payload Too Large",
+        _ => "Internal Server Error",
+    };
+    let head = format!(
+        "HTTP/1.1 {status} {reason}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        body.len()
+    );
+    stream
+        .write_all(head.as_bytes())
+        .and_then(|()| stream.write_all(body))
+        .and_then(|()| stream.flush())
+        .map_err(|e| format!("failed to write response: {}", e))
+}
+
+/// Reads a connection for at most a fixed total time, however slowly the
+/// peer sends.
+struct DeadlineReader<'a> {
+    stream: &'a TcpStream,
+    deadline: Instant,
+}
+
+impl Read for DeadlineReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let remaining = self.deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::TimedOut,
+                "request not received in time",
+            ));
+        }
+        self.stream.set_read_timeout(Some(remaining))?;
+        self.stream.read(buf)
+    }
+}
+
+fn read_line(reader: &mut impl BufRead) -> Result<String, String> {
+    let mut line = String::new();
+    reader
+        .by_ref()
+        .take(MAX_LINE_BYTES as u64)
+        .read_line(&mut line)
+        .map_err(|e| format!("failed to read HTTP line: {}", e))?;
+    if !line.ends_with('\n') && line.len() >= MAX_LINE_BYTES {
+        return Err(format!(
+            "HTTP line exceeds the {} byte limit",
+            MAX_LINE_BYTES
+        ));
+    }
+    Ok(line.trim_end_matches(['\r', '\n']).to_string())
+}
+
+fn read_headers(reader: &mut impl BufRead) -> Result<Vec<(String, String)>, String> {
+    let mut headers = Vec::new();
+    loop {
+        let line = read_line(reader)?;
+        if line.is_empty() {
+            return Ok(headers);
+        }
+        if headers.len() == MAX_HEADERS {
+            return Err(format!("more than {} HTTP headers", MAX_HEADERS));
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            headers.push((name.trim().to_string(), value.trim().to_string()));
+        }
+    }
+}
+
+fn content_length(
+    headers: &[(String, String)],
+    limit: usize,
+) -> Result<Option<usize>, (u16, String)> {
+    let Some((_, value)) = headers
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+    else {
+        return Ok(None);
+    };
+    let len = value
+        .parse::<usize>()
+        .map_err(|_| (400, format!("invalid Content-Length '{}'", value)))?;
+    if len > limit {
+        return Err((
+            413,
+            format!("body of {} bytes exceeds the {} byte limit", len, limit),
+        ));
+    }
+    Ok(Some(len))
+}
+
+/// Read a `len`-byte body. The buffer grows with the bytes that arrive
+/// rather than trusting `Content-Length` up front.
+fn read_body(reader: &mut impl Read, len: usize) -> Result<Vec<u8>, String> {
+    let mut body = Vec::new();
+    reader
+        .by_ref()
+        .take(len as u64)
+        .read_to_end(&mut body)
+        .map_err(|e| format!("failed to read HTTP body: {}", e))?;
+    if body.len() < len {
+        return Err(format!(
+            "HTTP body ended after {} of {} bytes",
+            body.len(),
+            len
+        ));
+    }
+    Ok(body)
+}
@@ -6,6 +6,7 @@ impl Manifest {
     pub fn parse(content: &str) -> Result<Self, String> {
         let mut manifest = Manifest::default();
         let mut in_require_block = false;
+        let mut in_lint_block = false;
 
         for line in content.lines() {
             let trimmed = line.trim();
@@ -23,6 +24,16 @@ impl Manifest {
                 continue;
             }
 
+            if in_lint_block {
+                if trimmed == ")" {
+                    in_lint_block = false;
+                    continue;
+                }
+                let (id, level) = parse_lint_line(trimmed)?;
+                manifest.lints.insert(id, level);
+                continue;
+            }
+
             if let Some(rest) = trimmed.strip_prefix("module ") {
                 manifest.module_path = rest.trim().to_string();
             } else if let Some(rest) = trimmed.strip_prefix("rr ") {
@@ -39,6 +50,11 @@ impl Manifest {
                 manifest.homepage = Some(parse_manifest_string(
                     rest.trim().trim_start_matches('=').trim(),
                 )?);
+            } else if trimmed == "lint (" {
+                in_lint_block = true;
+            } else if let Some(rest) = trimmed.strip_prefix("lint ") {
+                let (id, level) = parse_lint_line(rest)?;
+                manifest.lints.insert(id, level);
             } else if trimmed == "require (" {
                 in_require_block = true;
             } else if let Some(rest) = trimmed.strip_prefix("replace ") {
@@ -99,6 +115,20 @@ impl Manifest {
                 out.push_str(&format!("replace {} => {}\n", path, target));
             }
         }
+        if !self.lints.is_empty() {
+            out.push('\n');
+            if self.lints.len() == 1 {
+                if let Some((id, level)) = self.lints.iter().next() {
+                    out.push_str(&format!("lint {} {}\n", id, level));
+                }
+            } else {
+                out.push_str("lint (\n");
+                for (id, level) in &self.lints {
+                    out.push_str(&format!("    {} {}\n", id, level));
+                }
+                out.push_str(")\n");
+            }
+        }
         out
     }
 
@@ -123,6 +153,23 @@ fn parse_require_line(raw: &str) -> Result<(String, String), String> {
     Ok((path.to_string(), version.to_string()))
 }
 
+fn parse_lint_line(raw: &str) -> Result<(String, String), String> {
+    let mut parts = raw.split_whitespace();
+    let (Some(id), Some(level), None) = (parts.next(), parts.next(), parts.next()) else {
+        return Err(format!(
+            "invalid lint line '{}': expected `lint <id> <allow|warn|deny>`",
+            raw
+        ));
+    };
+    if !matches!(level, "allow" | "warn" | "deny") {
+        return Err(format!(
+            "invalid lint level '{}' for '{}': expected allow, warn or deny",
+            level, id
+        ));
+    }
+    Ok((id.to_string(), level.to_string()))
+}
+
 fn parse_manifest_string(raw: &str) -> Result<String, String> {
     if raw.starts_with('"') {
         parse_toml_string(raw)
@@ -1,8 +1,12 @@
+mod api;
+mod cache;
 mod env;
 mod git;
+mod http;
 mod manifest;
 mod project;
 mod registry;
+mod sbom;
 #[cfg(test)]
 mod tests;
 mod types;
@@ -16,7 +20,10 @@ use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::time::{SystemTime, UNIX_EPOCH};
 
-pub use env::{find_manifest_root, package_home, with_project_root_hint};
+pub use cache::{clean_package_cache, list_package_cache, parse_cache_age, verify_package_cache};
+pub use env::{
+    find_manifest_root, offline_mode, package_home, set_offline_mode, with_project_root_hint,
+};
 pub use project::{
     graph_project_dependencies, install_dependency_in_project, is_package_import,
     outdated_direct_dependencies, remove_dependency_from_project, resolve_import_path,
@@ -24,41 +31,49 @@ pub use project::{
     verify_project_dependencies, why_project_dependency,
 };
 pub use registry::{
-    RegistryOnboardOptions, RegistryPolicyBootstrapOptions, apply_registry_policy,
-    approve_registry_release, bootstrap_registry_policy, clear_registry_channel,
-    deprecate_registry_module, export_registry_audit_log, generate_registry_keypair,
-    lint_registry_policy, list_registry_modules, list_registry_queue, onboard_registry,
+    RegistryOnboardOptions, RegistryPolicyBootstrapOptions, add_registry_advisory,
+    apply_registry_policy, approve_registry_release, audit_project_dependencies,
+    bootstrap_registry_policy, clear_registry_channel, deprecate_registry_module,
+    export_registry_audit_log, generate_registry_keypair, lint_registry_policy,
+    list_registry_modules, list_registry_queue, onboard_registry, project_advisory_warnings,
     promote_registry_release, publish_project, read_registry_audit_log,
     read_registry_audit_log_filtered, registry_diff, registry_module_info, registry_report,
-    registry_risk, rotate_registry_policy_key, search_registry_modules, set_registry_channel,
-    show_registry_policy, unapprove_registry_release, undeprecate_registry_module,
-    unyank_registry_release, verify_registry, yank_registry_release,
+    registry_risk, reseal_registry_audit_log, rotate_registry_policy_key, search_registry_modules,
+    serve_registry, set_registry_channel, show_registry_policy, unapprove_registry_release,
+    undeprecate_registry_module, unyank_registry_release, verify_registry,
+    verify_registry_audit_log, yank_registry_release,
 };
+pub use sbom::project_sbom;
 pub use types::{
-    InstallReport, InstalledModule, Manifest, OutdatedDependency, PublishOptions, PublishReport,
-    RegistryAuditEntry, RegistryDiffReport, RegistryInfo, RegistryKeygenReport,
-    RegistryOnboardReport, RegistryPolicyLintReport, RegistryPolicyShowReport, RegistryQueueItem,
-    RegistryReleaseInfo, RegistryReport, RegistryReportModule, RegistryRiskFactor,
-    RegistryRiskReport, RegistrySearchResult, RegistryVerifyIssue, RegistryVerifyReport,
-    VerifyMismatch, VerifyReport,
+    AdvisoryFinding, ApiChange, ApiChangeKind, AuditReport, CacheCleanReport, CacheEntry,
+    CacheVerifyIssue, CacheVerifyReport, InstallReport, InstalledModule, Manifest,
+    OutdatedDependency, PublishOptions, PublishReport, RegistryAdvisory, RegistryApproval,
+    RegistryAuditBreak, RegistryAuditEntry, RegistryAuditVerifyReport, RegistryDiffReport,
+    RegistryInfo, RegistryKeygenReport, RegistryOnboardReport, RegistryPolicyLintReport,
+    RegistryPolicyShowReport, RegistryQueueItem, RegistryReleaseInfo, RegistryReport,
+    RegistryReportModule, RegistryRiskFactor, RegistryRiskReport, RegistrySearchResult,
+    RegistryVerifyIssue, RegistryVerifyReport, VerifyMismatch, VerifyReport,
 };
 
+use self::cache::{highest_cached_version, resolve_offline_dependencies, touch_cache_last_used};
 use self::env::registry_root;
 use self::git::{run_git, run_git_with_env};
 use self::manifest::parse_toml_string;
 use self::project::normalize_replace_target;
 use self::registry::{
     latest_registry_version, load_registry_index, load_registry_trust_policy,
-    materialize_registry_read_root, registry_channel_version, verify_registry_release_trust,
+    materialize_registry_read_root, registry_channel_version, sync_registry_archive,
+    verify_registry_license_policy, verify_registry_owner_approvals, verify_registry_release_trust,
 };
 use self::types::{
     InstallState, LoadedModule, ModuleRequest, ModuleSource, RegistryAuditFilter, RegistryEntry,
-    RegistryIndex, RegistryTrustPolicy, RequestedVersion, ResolvedVersion, SelectedModule,
-    Workspace,
+    RegistryIndex, RegistryOwnerRule, RegistryTrustPolicy, RequestedVersion, ResolvedVersion,
+    SelectedModule, Workspace,
 };
 use self::util::{
     archive_checksum, compare_versions, copy_dir_recursive, directory_checksum, github_repo_root,
-    normalize_path, read_meta_file, unique_temp_dir, version_matches_module_path, write_meta_file,
+    normalize_path, read_manifest_from_archive, read_meta_file, registry_rel_join, unique_temp_dir,
+    version_matches_module_path, write_meta_file,
 };
 
 fn parse_module_request(spec: &str) -> Result<ModuleRequest, String> {
@@ -207,6 +222,13 @@ fn install_single_remote_module(
     let cache_dir = module_cache_dir(&state.package_home, &request.module_path, &resolved.version);
     let installed = if cache_dir.join("rr.mod").is_file() {
         load_cached_module(&cache_dir, &request.module_path, &resolved.version, direct)?
+    } else if offline_mode() && matches!(request.source, ModuleSource::Git { .. }) {
+        return Err(format!(
+            "offline mode: module '{}@{}' is not in the module cache at '{}'",
+            request.module_path,
+            resolved.version,
+            cache_dir.display()
+        ));
     } else {
         match &request.source {
             ModuleSource::Git { .. } => {
@@ -255,7 +277,8 @@ fn install_registry_module(
             request.module_path, resolved.version
         ));
     }
-    let archive_path = registry_root.join(&entry.archive_rel);
+    let archive_path = registry_rel_join(registry_root, "archive", &entry.archive_rel)?;
+    sync_registry_archive(registry_root, &archive_path, &entry.archive_sum)?;
     if !archive_path.is_file() {
         return Err(format!(
             "registry archive '{}' is missing",
@@ -279,6 +302,14 @@ fn install_registry_module(
         entry.signer.as_deref(),
         &policy,
     )?;
+    verify_registry_owner_approvals(&request.module_path, &entry, &policy)?;
+    let archive_manifest = read_manifest_from_archive(&archive_path)?;
+    verify_registry_license_policy(
+        &request.module_path,
+        &resolved.version,
+        archive_manifest.license.as_deref(),
+        &policy,
+    )?;
 
     let tmp_root = unique_temp_dir("rr-registry");
     fs::create_dir_all(&tmp_root)
@@ -340,6 +371,9 @@ fn resolve_manifest_dependencies(
     project_root: &Path,
     manifest: &Manifest,
 ) -> Result<Vec<InstalledModule>, String> {
+    if offline_mode() {
+        return resolve_offline_dependencies(project_root, manifest);
+    }
     let mut selected = BTreeMap::<String, SelectedModule>::new();
     let mut queue = Vec::<String>::new();
     let mut state = InstallState {
@@ -527,6 +561,9 @@ fn resolve_version(request: &ModuleRequest) -> Result<ResolvedVersion, String> {
 
 fn latest_available_version(request: &ModuleRequest) -> Result<Option<String>, String> {
     match &request.source {
+        ModuleSource::Git { .. } if offline_mode() => {
+            highest_cached_version(&package_home(), &request.module_path)
+        }
         ModuleSource::Git { repo_url } => highest_remote_tag(repo_url, &request.module_path),
         ModuleSource::Registry { registry_root } => {
             latest_registry_version(registry_root, &request.module_path)
@@ -647,6 +684,7 @@ fn load_cached_module(
     direct: bool,
 ) -> Result<InstalledModule, String> {
     let (commit, sum) = read_meta_file(cache_dir)?;
+    touch_cache_last_used(cache_dir);
     Ok(InstalledModule {
         path: module_path.to_string(),
         version: version.to_string(),
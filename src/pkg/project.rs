@@ -213,50 +213,72 @@ pub fn graph_project_dependencies(project_root: &Path) -> Result<Vec<(String, St
 }
 
 pub fn why_project_dependency(project_root: &Path, target: &str) -> Result<Vec<String>, String> {
-    let manifest = Manifest::load_from_dir(project_root)?;
-    let (manifest, locked, graph) = load_project_graph(project_root, &manifest)?;
-    let locked_map = lock_map(&locked);
-    let target_module = infer_module_path_for_import(target);
-    let root = manifest.module_path.clone();
-    let mut preds = BTreeMap::<String, Option<String>>::new();
-    let mut queue = std::collections::VecDeque::new();
-
-    preds.insert(root.clone(), None);
-    queue.push_back(root.clone());
-
-    while let Some(node) = queue.pop_front() {
-        let mut deps = Vec::new();
-        if node == root {
-            deps.extend(manifest.requires.keys().cloned());
-        } else if let Some(children) = graph.get(&node) {
-            deps.extend(children.clone());
-        }
-        deps.sort();
-        for dep in deps {
-            if preds.contains_key(&dep) {
-                continue;
+    let chains = DependencyChains::load(project_root)?;
+    Ok(chains.chain(&infer_module_path_for_import(target)))
+}
+
+/// Shortest requirement chains from the project to every locked module,
+/// shared by `RR mod why` and `RR audit`.
+pub(super) struct DependencyChains {
+    root: String,
+    preds: BTreeMap<String, Option<String>>,
+    locked_map: BTreeMap<String, InstalledModule>,
+}
+
+impl DependencyChains {
+    pub(super) fn load(project_root: &Path) -> Result<Self, String> {
+        let manifest = Manifest::load_from_dir(project_root)?;
+        let (manifest, locked, graph) = load_project_graph(project_root, &manifest)?;
+        let root = manifest.module_path.clone();
+        let mut preds = BTreeMap::<String, Option<String>>::new();
+        let mut queue = std::collections::VecDeque::new();
+
+        preds.insert(root.clone(), None);
+        queue.push_back(root.clone());
+
+        while let Some(node) = queue.pop_front() {
+            let mut deps = Vec::new();
+            if node == root {
+                deps.extend(manifest.requires.keys().cloned());
+            } else if let Some(children) = graph.get(&node) {
+                deps.extend(children.clone());
+            }
+            deps.sort();
+            for dep in deps {
+                if preds.contains_key(&dep) {
+                    continue;
+                }
+                preds.insert(dep.clone(), Some(node.clone()));
+                queue.push_back(dep);
             }
-            preds.insert(dep.clone(), Some(node.clone()));
-            queue.push_back(dep);
         }
-    }
 
-    if !preds.contains_key(&target_module) {
-        return Ok(Vec::new());
+        Ok(Self {
+            root,
+            preds,
+            locked_map: lock_map(&locked),
+        })
     }
 
-    let mut chain = Vec::new();
-    let mut cursor = Some(target_module.clone());
-    while let Some(node) = cursor {
-        chain.push(if node == root {
-            node.clone()
-        } else {
-            display_locked_node(&node, &locked_map)
-        });
-        cursor = preds.get(&node).cloned().flatten();
+    /// `root -> dep@version -> ... -> module@version`; empty when the
+    /// project does not reach `module_path`.
+    pub(super) fn chain(&self, module_path: &str) -> Vec<String> {
+        if !self.preds.contains_key(module_path) {
+            return Vec::new();
+        }
+        let mut chain = Vec::new();
+        let mut cursor = Some(module_path.to_string());
+        while let Some(node) = cursor {
+            chain.push(if node == self.root {
+                node.clone()
+            } else {
+                display_locked_node(&node, &self.locked_map)
+            });
+            cursor = self.preds.get(&node).cloned().flatten();
+        }
+        chain.reverse();
+        chain
     }
-    chain.reverse();
-    Ok(chain)
 }
 
 pub fn verify_project_dependencies(project_root: &Path) -> Result<VerifyReport, String> {
@@ -655,7 +677,7 @@ fn collect_package_rr_files(package_dir: &Path) -> Result<Vec<PathBuf>, String>
     Ok(files)
 }
 
-fn load_lockfile(project_root: &Path) -> Result<Vec<InstalledModule>, String> {
+pub(super) fn load_lockfile(project_root: &Path) -> Result<Vec<InstalledModule>, String> {
     let lock_path = project_root.join("rr.lock");
     let content = fs::read_to_string(&lock_path)
         .map_err(|e| format!("failed to read '{}': {}", lock_path.display(), e))?;
@@ -714,7 +736,7 @@ type ProjectGraphLoad = (
     BTreeMap<String, Vec<String>>,
 );
 
-fn load_project_graph(
+pub(super) fn load_project_graph(
     project_root: &Path,
     manifest: &Manifest,
 ) -> Result<ProjectGraphLoad, String> {
@@ -732,7 +754,7 @@ fn load_project_graph(
     Ok((manifest.clone(), locked, graph))
 }
 
-fn lock_map(entries: &[InstalledModule]) -> BTreeMap<String, InstalledModule> {
+pub(super) fn lock_map(entries: &[InstalledModule]) -> BTreeMap<String, InstalledModule> {
     let mut out = BTreeMap::new();
     for entry in entries {
         out.insert(entry.path.clone(), entry.clone());
@@ -740,7 +762,7 @@ fn lock_map(entries: &[InstalledModule]) -> BTreeMap<String, InstalledModule> {
     out
 }
 
-fn display_locked_node(
+pub(super) fn display_locked_node(
     module_path: &str,
     locked_map: &BTreeMap<String, InstalledModule>,
 ) -> String {
@@ -751,7 +773,7 @@ fn display_locked_node(
     }
 }
 
-fn source_root_for_locked_module(
+pub(super) fn source_root_for_locked_module(
     project_root: &Path,
     manifest: &Manifest,
     entry: &InstalledModule,
@@ -1,12 +1,21 @@
 use super::*;
-use super::{env, git, manifest, util};
+use super::{api, env, git, http, manifest, project, util};
 
+mod advisories;
+mod audit_chain;
+mod http_registry;
 mod primitives;
 mod publishing;
 mod query_reports;
 mod signing;
 mod trust_policy;
 
+pub use advisories::{
+    add_registry_advisory, audit_project_dependencies, project_advisory_warnings,
+};
+pub use audit_chain::{reseal_registry_audit_log, verify_registry_audit_log};
+pub use http_registry::serve_registry;
+pub(super) use http_registry::sync_registry_archive;
 pub use publishing::{
     RegistryOnboardOptions, RegistryPolicyBootstrapOptions, apply_registry_policy,
     approve_registry_release, bootstrap_registry_policy, onboard_registry,
@@ -24,3 +33,4 @@ pub use query_reports::{
     set_registry_channel, show_registry_policy, undeprecate_registry_module,
     unyank_registry_release, verify_registry, yank_registry_release,
 };
+pub(super) use trust_policy::{verify_registry_license_policy, verify_registry_owner_approvals};
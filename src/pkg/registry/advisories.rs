@@ -0,0 +1,261 @@
+//! Security advisories stored in the registry under
+//! `advisories/<module-path>.toml`, signed like releases. `RR audit` checks a
+//! project's `rr.lock` against them, and install and update warn about
+//! affected versions.
+
+use super::env::{registry_root, registry_spec_from_override};
+use super::http_registry::sync_registry_file;
+use super::manifest::{escape_toml, parse_toml_string};
+use super::project::{DependencyChains, load_lockfile};
+use super::publishing::{
+    append_registry_audit_entry, materialize_registry_write_root, verify_registry_signature,
+};
+use super::signing::sign_registry_payload;
+use super::util::{module_path_to_rel_path, version_in_range};
+use super::*;
+
+/// Record an advisory for `module_path` versions in `range`, signed with the
+/// configured registry signing key when there is one.
+pub fn add_registry_advisory(
+    module_path: &str,
+    range: &str,
+    id: &str,
+    summary: &str,
+    registry_spec: &Path,
+) -> Result<RegistryAdvisory, String> {
+    let id = id.trim();
+    let summary = summary.trim();
+    if id.is_empty() || id.contains(char::is_whitespace) {
+        return Err(format!(
+            "advisory id '{}' must be non-empty without spaces",
+            id
+        ));
+    }
+    if summary.is_empty() {
+        return Err("advisory summary must be non-empty".to_string());
+    }
+    version_in_range("v0.0.0", range)?;
+
+    let (write_root, is_remote) = materialize_registry_write_root(registry_spec, false)?;
+    let policy = load_registry_trust_policy(&write_root)?;
+    let mut advisories = load_registry_advisories(&write_root, module_path)?;
+    if advisories.iter().any(|advisory| advisory.id == id) {
+        return Err(format!(
+            "registry module '{}' already has advisory '{}'",
+            module_path, id
+        ));
+    }
+    let range = range.trim().to_string();
+    let payload = registry_advisory_payload(module_path, &range, id, summary);
+    let (sig, signer) = sign_registry_payload(&payload)?;
+    if policy.require_signed && sig.is_none() {
+        return Err("registry policy requires signed advisories".to_string());
+    }
+    let advisory = RegistryAdvisory {
+        id: id.to_string(),
+        module_path: module_path.to_string(),
+        range,
+        summary: summary.to_string(),
+        sig,
+        signer,
+    };
+    advisories.push(advisory.clone());
+    write_registry_advisories(&write_root, module_path, &advisories)?;
+    append_registry_audit_entry(
+        &write_root,
+        "advisory-add",
+        &format!(
+            "module={} id={} range={}",
+            module_path, advisory.id, advisory.range
+        ),
+    )?;
+    if is_remote {
+        let message = format!("add advisory {} for {}", advisory.id, module_path);
+        run_git(Some(&write_root), &["add", "."])?;
+        let _ = run_git(Some(&write_root), &["commit", "-m", &message]);
+        run_git(Some(&write_root), &["push", "origin", "HEAD"])?;
+    }
+    Ok(advisory)
+}
+
+/// Check the project's locked modules against the registry's advisories.
+pub fn audit_project_dependencies(
+    project_root: &Path,
+    registry_spec: Option<&Path>,
+) -> Result<AuditReport, String> {
+    let spec = registry_spec_from_override(registry_spec)?;
+    let registry_root = materialize_registry_read_root(&spec)?;
+    audit_locked_modules(project_root, &registry_root)
+}
+
+/// Advisories affecting the project's locked modules, for install and
+/// update to warn about. Empty when no registry is configured.
+pub fn project_advisory_warnings(project_root: &Path) -> Result<Vec<AdvisoryFinding>, String> {
+    let Some(registry_root) = registry_root()? else {
+        return Ok(Vec::new());
+    };
+    Ok(audit_locked_modules(project_root, &registry_root)?.findings)
+}
+
+fn audit_locked_modules(project_root: &Path, registry_root: &Path) -> Result<AuditReport, String> {
+    if !project_root.join("rr.lock").is_file() {
+        return Ok(AuditReport {
+            checked: 0,
+            findings: Vec::new(),
+        });
+    }
+    let locked = load_lockfile(project_root)?;
+    let policy = load_registry_trust_policy(registry_root)?;
+    let mut affected = Vec::new();
+    for entry in &locked {
+        for advisory in load_registry_advisories(registry_root, &entry.path)? {
+            verify_registry_advisory(&advisory, &policy)?;
+            if version_in_range(&entry.version, &advisory.range)? {
+                affected.push((advisory, entry.version.clone()));
+            }
+        }
+    }
+
+    let mut findings = Vec::new();
+    if !affected.is_empty() {
+        let chains = DependencyChains::load(project_root)?;
+        for (advisory, version) in affected {
+            let chain = chains.chain(&advisory.module_path);
+            findings.push(AdvisoryFinding {
+                advisory,
+                version,
+                chain,
+            });
+        }
+    }
+    findings.sort_by(|a, b| {
+        (&a.advisory.module_path, &a.advisory.id).cmp(&(&b.advisory.module_path, &b.advisory.id))
+    });
+    Ok(AuditReport {
+        checked: locked.len(),
+        findings,
+    })
+}
+
+fn verify_registry_advisory(
+    advisory: &RegistryAdvisory,
+    policy: &RegistryTrustPolicy,
+) -> Result<(), String> {
+    let Some(signature) = advisory.sig.as_deref() else {
+        if policy.require_signed || !policy.allowed_signers.is_empty() {
+            return Err(format!(
+                "registry policy requires signed advisories for '{} {}'",
+                advisory.module_path, advisory.id
+            ));
+        }
+        return Ok(());
+    };
+    let payload = registry_advisory_payload(
+        &advisory.module_path,
+        &advisory.range,
+        &advisory.id,
+        &advisory.summary,
+    );
+    verify_registry_signature(
+        &advisory.module_path,
+        &advisory.id,
+        &payload,
+        signature,
+        advisory.signer.as_deref(),
+        policy,
+    )
+}
+
+fn registry_advisory_payload(module_path: &str, range: &str, id: &str, summary: &str) -> String {
+    format!("advisory={id}\nmodule={module_path}\nrange={range}\nsummary={summary}\n")
+}
+
+fn registry_advisories_path(registry_root: &Path, module_path: &str) -> PathBuf {
+    let mut path = registry_root
+        .join("advisories")
+        .join(module_path_to_rel_path(module_path));
+    path.set_extension("toml");
+    path
+}
+
+fn load_registry_advisories(
+    registry_root: &Path,
+    module_path: &str,
+) -> Result<Vec<RegistryAdvisory>, String> {
+    let path = registry_advisories_path(registry_root, module_path);
+    sync_registry_file(registry_root, &path)?;
+    if !path.is_file() {
+        return Ok(Vec::new());
+    }
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("failed to read '{}': {}", path.display(), e))?;
+    let mut advisories = Vec::new();
+    let mut current: Option<RegistryAdvisory> = None;
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed == "version = 1" {
+            continue;
+        }
+        if trimmed == "[[advisory]]" {
+            if let Some(advisory) = current.take() {
+                advisories.push(advisory);
+            }
+            current = Some(RegistryAdvisory {
+                id: String::new(),
+                module_path: module_path.to_string(),
+                range: String::new(),
+                summary: String::new(),
+                sig: None,
+                signer: None,
+            });
+            continue;
+        }
+        let (Some(advisory), Some((key, value))) = (current.as_mut(), trimmed.split_once('='))
+        else {
+            continue;
+        };
+        let value = value.trim();
+        match key.trim() {
+            "id" => advisory.id = parse_toml_string(value)?,
+            "range" => advisory.range = parse_toml_string(value)?,
+            "summary" => advisory.summary = parse_toml_string(value)?,
+            "sig" => advisory.sig = Some(parse_toml_string(value)?),
+            "signer" => advisory.signer = Some(parse_toml_string(value)?),
+            _ => {}
+        }
+    }
+    if let Some(advisory) = current.take() {
+        advisories.push(advisory);
+    }
+    advisories.retain(|advisory| !advisory.id.is_empty());
+    Ok(advisories)
+}
+
+fn write_registry_advisories(
+    registry_root: &Path,
+    module_path: &str,
+    advisories: &[RegistryAdvisory],
+) -> Result<(), String> {
+    let path = registry_advisories_path(registry_root, module_path);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("failed to create '{}': {}", parent.display(), e))?;
+    }
+    let mut out = String::from("version = 1\n");
+    for advisory in advisories {
+        out.push_str("\n[[advisory]]\n");
+        out.push_str(&format!("id = \"{}\"\n", escape_toml(&advisory.id)));
+        out.push_str(&format!("range = \"{}\"\n", escape_toml(&advisory.range)));
+        out.push_str(&format!(
+            "summary = \"{}\"\n",
+            escape_toml(&advisory.summary)
+        ));
+        if let Some(sig) = &advisory.sig {
+            out.push_str(&format!("sig = \"{}\"\n", escape_toml(sig)));
+        }
+        if let Some(signer) = &advisory.signer {
+            out.push_str(&format!("signer = \"{}\"\n", escape_toml(signer)));
+        }
+    }
+    fs::write(&path, out).map_err(|e| format!("failed to write '{}': {}", path.display(), e))
+}
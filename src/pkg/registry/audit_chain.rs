@@ -0,0 +1,326 @@
+//! Hash chain over the registry's `audit.log`.
+//!
+//! Each line ends in `\thash=<sha256>`, the digest of the previous entry's
+//! hash and this entry's text, so editing, removing or reordering an entry
+//! breaks every hash after it. Lines written before chaining seed the chain.
+//! `audit.checkpoint` records the entry count and head hash, signed with the
+//! registry ed25519 key when one is configured, so truncating the tail is
+//! caught as well. `RR registry audit verify` walks the chain, and
+//! `RR registry audit reseal` moves a signed checkpoint onto an intact chain
+//! after the old checkpoint was lost or the tail was deliberately cut.
+
+use super::env::{registry_signing_identity, registry_spec_from_override};
+use super::manifest::{escape_toml, parse_toml_string};
+use super::primitives::{sha256_hex, signature_scheme_name};
+use super::publishing::{append_registry_audit_entry, verify_registry_signature};
+use super::signing::sign_registry_payload_ed25519;
+use super::*;
+
+const HASH_FIELD: &str = "\thash=";
+
+struct AuditCheckpoint {
+    entries: usize,
+    head: String,
+    sig: Option<String>,
+    signer: Option<String>,
+}
+
+/// Split a line into its entry text and chain hash; legacy lines have none.
+pub(super) fn split_audit_hash(line: &str) -> (&str, Option<&str>) {
+    match line.rsplit_once(HASH_FIELD) {
+        Some((body, hash))
+            if hash.len() == 64 && hash.bytes().all(|byte| byte.is_ascii_hexdigit()) =>
+        {
+            (body, Some(hash))
+        }
+        _ => (line, None),
+    }
+}
+
+/// Append `body` to `audit.log` chained to the current head, and move the
+/// checkpoint to it. Refuses to extend a log that no longer matches its
+/// checkpoint, so a truncated tail cannot be papered over.
+pub(super) fn append_chained_audit_line(registry_root: &Path, body: &str) -> Result<(), String> {
+    let audit_path = registry_root.join("audit.log");
+    let mut existing = if audit_path.is_file() {
+        fs::read_to_string(&audit_path)
+            .map_err(|e| format!("failed to read '{}': {}", audit_path.display(), e))?
+    } else {
+        String::new()
+    };
+    let mut entries = 0usize;
+    let mut head = None::<String>;
+    let mut legacy = String::new();
+    for line in existing.lines() {
+        match split_audit_hash(line) {
+            (_, Some(hash)) => {
+                entries += 1;
+                head = Some(hash.to_string());
+            }
+            (_, None) if head.is_none() => {
+                legacy.push_str(line);
+                legacy.push('\n');
+            }
+            (_, None) => {}
+        }
+    }
+    if let Some(checkpoint) = load_audit_checkpoint(registry_root)?
+        && (checkpoint.entries != entries || head.as_deref() != Some(checkpoint.head.as_str()))
+    {
+        return Err(format!(
+            "registry audit log in '{}' does not match its checkpoint; run `RR registry audit verify` to find the break, then restore audit.log from a backup or, if the change was intended, run `RR registry audit reseal` with RR_REGISTRY_SIGNING_ED25519_SECRET set",
+            registry_root.display()
+        ));
+    }
+
+    let prev = head.unwrap_or_else(|| sha256_hex(legacy.as_bytes()));
+    let hash = audit_entry_hash(&prev, body);
+    if !existing.is_empty() && !existing.ends_with('\n') {
+        existing.push('\n');
+    }
+    existing.push_str(&format!("{}{}{}\n", body, HASH_FIELD, hash));
+    fs::write(&audit_path, existing)
+        .map_err(|e| format!("failed to write '{}': {}", audit_path.display(), e))?;
+    write_audit_checkpoint(registry_root, entries + 1, &hash)
+}
+
+/// Walk the audit chain and checkpoint, stopping at the first broken link.
+pub fn verify_registry_audit_log(
+    registry_spec: Option<&Path>,
+) -> Result<RegistryAuditVerifyReport, String> {
+    let registry_root = registry_spec_from_override(registry_spec)?;
+    let audit_path = registry_root.join("audit.log");
+    let content = if audit_path.is_file() {
+        fs::read_to_string(&audit_path)
+            .map_err(|e| format!("failed to read '{}': {}", audit_path.display(), e))?
+    } else {
+        String::new()
+    };
+    let (mut report, line_count) = walk_audit_chain(&content);
+    if report.broken.is_some() {
+        return Ok(report);
+    }
+
+    let Some(checkpoint) = load_audit_checkpoint(&registry_root)? else {
+        if report.entries > 0 {
+            report.broken = Some(RegistryAuditBreak {
+                line: line_count,
+                reason: "audit.checkpoint is missing".to_string(),
+            });
+        }
+        return Ok(report);
+    };
+    if checkpoint.entries > report.entries {
+        report.broken = Some(RegistryAuditBreak {
+            line: line_count + 1,
+            reason: format!(
+                "log truncated: checkpoint records {} chained entries but the log has {}",
+                checkpoint.entries, report.entries
+            ),
+        });
+        return Ok(report);
+    }
+    if checkpoint.entries < report.entries || report.head.as_deref() != Some(&checkpoint.head) {
+        report.broken = Some(RegistryAuditBreak {
+            line: line_count,
+            reason: format!(
+                "checkpoint head {} (entry {}) does not match the log",
+                checkpoint.head, checkpoint.entries
+            ),
+        });
+        return Ok(report);
+    }
+    let policy = load_registry_trust_policy(&registry_root)?;
+    let Some(sig) = checkpoint.sig.as_deref() else {
+        if policy.require_signed || !policy.trusted_ed25519_keys.is_empty() {
+            return Err(format!(
+                "audit.checkpoint in '{}' is not signed, but the registry policy requires signed checkpoints; run `RR registry audit reseal` with RR_REGISTRY_SIGNING_ED25519_SECRET set",
+                registry_root.display()
+            ));
+        }
+        return Ok(report);
+    };
+    let payload = audit_checkpoint_payload(checkpoint.entries, &checkpoint.head);
+    verify_registry_signature(
+        "audit.log",
+        "checkpoint",
+        &payload,
+        sig,
+        checkpoint.signer.as_deref(),
+        &policy,
+    )?;
+    report.checkpoint_scheme = signature_scheme_name(sig);
+    report.checkpoint_signer = checkpoint.signer;
+    Ok(report)
+}
+
+/// Point a freshly signed checkpoint at the current head of an intact chain
+/// and record the reseal in the log. Only the registry ed25519 key can do
+/// this, and a chain with an edited entry is refused: resealing accepts a
+/// lost checkpoint or a deliberately cut tail, never a rewritten entry.
+pub fn reseal_registry_audit_log(
+    registry_spec: Option<&Path>,
+) -> Result<RegistryAuditVerifyReport, String> {
+    let registry_root = registry_spec_from_override(registry_spec)?;
+    let audit_path = registry_root.join("audit.log");
+    let content = if audit_path.is_file() {
+        fs::read_to_string(&audit_path)
+            .map_err(|e| format!("failed to read '{}': {}", audit_path.display(), e))?
+    } else {
+        String::new()
+    };
+    let (report, _) = walk_audit_chain(&content);
+    if let Some(broken) = &report.broken {
+        return Err(format!(
+            "registry audit log is broken at line {}: {}; restore audit.log from a backup, a reseal only accepts an intact chain",
+            broken.line, broken.reason
+        ));
+    }
+    let head = report
+        .head
+        .clone()
+        .unwrap_or_else(|| sha256_hex(content.as_bytes()));
+    let payload = audit_checkpoint_payload(report.entries, &head);
+    let Some(sig) = sign_registry_payload_ed25519(&payload)? else {
+        return Err(
+            "resealing the audit checkpoint needs the registry signing key; set RR_REGISTRY_SIGNING_ED25519_SECRET"
+                .to_string(),
+        );
+    };
+    let policy = load_registry_trust_policy(&registry_root)?;
+    if !policy.trusted_ed25519_keys.is_empty() {
+        let public_key = sig
+            .strip_prefix("ed25519:")
+            .and_then(|rest| rest.split_once(':'))
+            .map(|(key, _)| key.to_ascii_lowercase())
+            .unwrap_or_default();
+        if !policy.trusted_ed25519_keys.contains(&public_key)
+            || policy.revoked_ed25519_keys.contains(&public_key)
+        {
+            return Err(format!(
+                "resealing the audit checkpoint needs a key the registry policy trusts; {} is not one",
+                public_key
+            ));
+        }
+    }
+
+    if report.entries > 0 {
+        write_audit_checkpoint(&registry_root, report.entries, &head)?;
+    } else {
+        // Nothing is chained yet; the reseal entry below starts the chain.
+        let path = registry_root.join("audit.checkpoint");
+        if path.is_file() {
+            fs::remove_file(&path)
+                .map_err(|e| format!("failed to remove '{}': {}", path.display(), e))?;
+        }
+    }
+    let by = registry_signing_identity()
+        .map(|identity| format!(" by={}", identity))
+        .unwrap_or_default();
+    append_registry_audit_entry(
+        &registry_root,
+        "audit-reseal",
+        &format!("entries={} head={}{}", report.entries, head, by),
+    )?;
+    verify_registry_audit_log(Some(&registry_root))
+}
+
+/// Walk the hash chain of `content`, stopping at the first broken link.
+/// Returns the report and the number of lines read.
+fn walk_audit_chain(content: &str) -> (RegistryAuditVerifyReport, usize) {
+    let mut report = RegistryAuditVerifyReport {
+        entries: 0,
+        legacy_entries: 0,
+        head: None,
+        checkpoint_scheme: None,
+        checkpoint_signer: None,
+        broken: None,
+    };
+    let mut legacy = String::new();
+    let mut line_count = 0usize;
+    for (idx, line) in content.lines().enumerate() {
+        line_count = idx + 1;
+        let (body, hash) = split_audit_hash(line);
+        let Some(hash) = hash else {
+            if report.head.is_some() {
+                report.broken = Some(RegistryAuditBreak {
+                    line: idx + 1,
+                    reason: "entry has no chain hash but follows chained entries".to_string(),
+                });
+                return (report, line_count);
+            }
+            report.legacy_entries += 1;
+            legacy.push_str(line);
+            legacy.push('\n');
+            continue;
+        };
+        let prev = report
+            .head
+            .clone()
+            .unwrap_or_else(|| sha256_hex(legacy.as_bytes()));
+        if audit_entry_hash(&prev, body) != hash {
+            report.broken = Some(RegistryAuditBreak {
+                line: idx + 1,
+                reason: "hash does not match the chain; this entry or an earlier one was modified, removed or reordered".to_string(),
+            });
+            return (report, line_count);
+        }
+        report.entries += 1;
+        report.head = Some(hash.to_string());
+    }
+
+    (report, line_count)
+}
+
+fn audit_entry_hash(prev: &str, body: &str) -> String {
+    sha256_hex(format!("{prev}\n{body}").as_bytes())
+}
+
+fn audit_checkpoint_payload(entries: usize, head: &str) -> String {
+    format!("audit-checkpoint\nentries={entries}\nhead={head}\n")
+}
+
+fn write_audit_checkpoint(registry_root: &Path, entries: usize, head: &str) -> Result<(), String> {
+    let path = registry_root.join("audit.checkpoint");
+    let mut out = format!("version = 1\nentries = {}\nhead = \"{}\"\n", entries, head);
+    if let Some(sig) = sign_registry_payload_ed25519(&audit_checkpoint_payload(entries, head))? {
+        out.push_str(&format!("sig = \"{}\"\n", escape_toml(&sig)));
+        if let Some(signer) = registry_signing_identity() {
+            out.push_str(&format!("signer = \"{}\"\n", escape_toml(&signer)));
+        }
+    }
+    fs::write(&path, out).map_err(|e| format!("failed to write '{}': {}", path.display(), e))
+}
+
+fn load_audit_checkpoint(registry_root: &Path) -> Result<Option<AuditCheckpoint>, String> {
+    let path = registry_root.join("audit.checkpoint");
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("failed to read '{}': {}", path.display(), e))?;
+    let mut checkpoint = AuditCheckpoint {
+        entries: 0,
+        head: String::new(),
+        sig: None,
+        signer: None,
+    };
+    for line in content.lines() {
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let value = value.trim();
+        match key.trim() {
+            "entries" => {
+                checkpoint.entries = value
+                    .parse()
+                    .map_err(|_| format!("invalid entries count in '{}'", path.display()))?;
+            }
+            "head" => checkpoint.head = parse_toml_string(value)?,
+            "sig" => checkpoint.sig = Some(parse_toml_string(value)?),
+            "signer" => checkpoint.signer = Some(parse_toml_string(value)?),
+            _ => {}
+        }
+    }
+    Ok(Some(checkpoint))
+}
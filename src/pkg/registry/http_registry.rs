@@ -0,0 +1,425 @@
+//! HTTP registry protocol over the on-disk registry layout.
+//!
+//! `RR registry serve` answers `GET /index` with the module paths it holds,
+//! serves `policy.toml`, `index/`, `advisories/` and `pkg/` files at their
+//! registry-relative paths, and accepts signed release uploads at
+//! `POST /publish`. Clients keep a sparse mirror under the package home and
+//! fetch each file when a command reads it, so an install only downloads
+//! the indexes and archives it needs.
+
+use super::env::offline_mode;
+use super::http::{
+    HttpRequest, MAX_BODY_BYTES, http_get, http_request, read_http_request, write_http_response,
+};
+use super::publishing::{
+    RegistryUpload, load_all_registry_indices, record_registry_release, verify_registry_signature,
+};
+use super::signing::{registry_signature_payload, sign_registry_approval, sign_registry_release};
+use super::util::{
+    archive_checksum, check_registry_rel_path, collect_file_map, extract_registry_archive_to_temp,
+    module_path_to_rel_path, normalize_path, read_manifest_from_archive, unique_temp_dir,
+};
+use super::*;
+use std::net::{Shutdown, TcpListener, TcpStream};
+use std::sync::{Arc, RwLock};
+use std::thread;
+
+/// Holds the base URL in a mirror directory.
+const MIRROR_MARKER: &str = ".rr-http-registry";
+
+/// Top-level registry entries the server exposes for reading.
+const SERVED_ROOTS: &[&str] = &["index", "advisories", "pkg", "policy.toml"];
+
+/// Largest body accepted on routes other than `POST /publish`.
+const MAX_QUERY_BODY_BYTES: usize = 64 * 1024;
+
+/// `http://` specs speak this protocol; URLs ending in `.git` stay git
+/// remotes.
+pub(super) fn is_http_registry_spec(spec: &Path) -> bool {
+    let text = spec.to_string_lossy();
+    text.starts_with("http://") && !text.ends_with(".git")
+}
+
+/// Point the mirror at `base_url` and refresh its policy.
+pub(super) fn materialize_http_registry(base_url: &str, local_root: &Path) -> Result<(), String> {
+    let marker = local_root.join(MIRROR_MARKER);
+    if offline_mode() {
+        if marker.is_file() {
+            return Ok(());
+        }
+        return Err(format!(
+            "registry '{}' has no local cache at '{}' and offline mode is enabled; run once without --offline to fetch it",
+            base_url,
+            local_root.display()
+        ));
+    }
+    fs::create_dir_all(local_root)
+        .map_err(|e| format!("failed to create '{}': {}", local_root.display(), e))?;
+    fs::write(&marker, base_url.trim_end_matches('/'))
+        .map_err(|e| format!("failed to write '{}': {}", marker.display(), e))?;
+    sync_registry_file(local_root, &local_root.join("policy.toml"))
+}
+
+/// Refresh `path` inside a mirror from the server, removing it when the
+/// server no longer has it. A no-op for plain registry directories and in
+/// offline mode. Paths that would leave the mirror are refused, since they
+/// come from indexes and listings the server controls.
+pub(super) fn sync_registry_file(registry_root: &Path, path: &Path) -> Result<(), String> {
+    let Some(base_url) = mirror_base_url(registry_root) else {
+        return Ok(());
+    };
+    if offline_mode() {
+        return Ok(());
+    }
+    let Ok(rel) = path.strip_prefix(registry_root) else {
+        return Err(format!(
+            "refusing to mirror '{}' outside registry '{}'",
+            path.display(),
+            registry_root.display()
+        ));
+    };
+    let rel = rel.to_string_lossy().replace('\\', "/");
+    check_registry_rel_path("path", &rel)?;
+    match http_get(&format!("{}/{}", base_url, rel))? {
+        Some(bytes) => {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)
+                    .map_err(|e| format!("failed to create '{}': {}", parent.display(), e))?;
+                // A symlink inside the mirror could still point elsewhere.
+                let inside = match (fs::canonicalize(parent), fs::canonicalize(registry_root)) {
+                    (Ok(parent), Ok(root)) => parent.starts_with(root),
+                    _ => false,
+                };
+                if !inside {
+                    return Err(format!(
+                        "refusing to mirror '{}' outside registry '{}'",
+                        path.display(),
+                        registry_root.display()
+                    ));
+                }
+            }
+            fs::write(path, bytes)
+                .map_err(|e| format!("failed to write '{}': {}", path.display(), e))
+        }
+        None => {
+            if path.is_file() {
+                fs::remove_file(path)
+                    .map_err(|e| format!("failed to remove '{}': {}", path.display(), e))?;
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Download a release archive into a mirror unless the cached copy already
+/// has `expected_sum`.
+pub(in crate::pkg) fn sync_registry_archive(
+    registry_root: &Path,
+    archive_path: &Path,
+    expected_sum: &str,
+) -> Result<(), String> {
+    if mirror_base_url(registry_root).is_none()
+        || (archive_path.is_file() && archive_checksum(archive_path)? == expected_sum)
+    {
+        return Ok(());
+    }
+    sync_registry_file(registry_root, archive_path)
+}
+
+/// Fetch the server's module list and refresh every index it names, so
+/// listing and search see modules the mirror has not read yet.
+pub(super) fn sync_registry_module_list(registry_root: &Path) -> Result<(), String> {
+    let Some(base_url) = mirror_base_url(registry_root) else {
+        return Ok(());
+    };
+    if offline_mode() {
+        return Ok(());
+    }
+    let listing = http_get(&format!("{}/index", base_url))?.unwrap_or_default();
+    for module_path in String::from_utf8_lossy(&listing).lines() {
+        let module_path = module_path.trim();
+        if module_path.is_empty() {
+            continue;
+        }
+        check_registry_rel_path("module path", module_path)?;
+        let mut index_path = registry_root
+            .join("index")
+            .join(module_path_to_rel_path(module_path));
+        index_path.set_extension("toml");
+        sync_registry_file(registry_root, &index_path)?;
+    }
+    Ok(())
+}
+
+fn mirror_base_url(registry_root: &Path) -> Option<String> {
+    fs::read_to_string(registry_root.join(MIRROR_MARKER))
+        .ok()
+        .map(|url| url.trim().to_string())
+        .filter(|url| !url.is_empty())
+}
+
+/// Sign a packed release and upload it to an HTTP registry.
+pub(super) fn upload_registry_release(
+    registry_spec: &Path,
+    module_path: &str,
+    version: &str,
+    archive_path: &Path,
+    options: &PublishOptions,
+) -> Result<(), String> {
+    let base_url = registry_spec
+        .to_string_lossy()
+        .trim_end_matches('/')
+        .to_string();
+    let archive_sum = archive_checksum(archive_path)?;
+    let (archive_sig, signer) = sign_registry_release(module_path, version, &archive_sum)?;
+    let Some(archive_sig) = archive_sig else {
+        return Err(format!(
+            "publishing to HTTP registry '{}' requires a signing key; set RR_REGISTRY_SIGNING_ED25519_SECRET or RR_REGISTRY_SIGNING_KEY",
+            base_url
+        ));
+    };
+    let approval_sig = match signer.as_deref() {
+        Some(signer) => sign_registry_approval(module_path, version, signer)?,
+        None => None,
+    };
+    if options.dry_run {
+        return Ok(());
+    }
+    let body = fs::read(archive_path)
+        .map_err(|e| format!("failed to read '{}': {}", archive_path.display(), e))?;
+    let mut headers = vec![
+        ("Content-Type", "application/gzip"),
+        ("X-RR-Version", version),
+        ("X-RR-Signature", archive_sig.as_str()),
+    ];
+    if let Some(signer) = signer.as_deref() {
+        headers.push(("X-RR-Signer", signer));
+    }
+    if let Some(approval_sig) = approval_sig.as_deref() {
+        headers.push(("X-RR-Approval-Signature", approval_sig));
+    }
+    if options.allow_breaking {
+        headers.push(("X-RR-Allow-Breaking", "true"));
+    }
+    let response = http_request("POST", &format!("{}/publish", base_url), &headers, &body)?;
+    if response.status == 200 {
+        return Ok(());
+    }
+    Err(format!(
+        "registry '{}' rejected {} {} (HTTP {}): {}",
+        base_url,
+        module_path,
+        version,
+        response.status,
+        String::from_utf8_lossy(&response.body).trim()
+    ))
+}
+
+/// Serve `registry_dir` over HTTP until the process is stopped. `on_ready`
+/// receives the bound address, which matters when `addr` asks for port 0.
+pub fn serve_registry(
+    registry_dir: &Path,
+    addr: &str,
+    on_ready: impl FnOnce(&str),
+) -> Result<(), String> {
+    let registry_root = normalize_path(registry_dir);
+    if !registry_root.is_dir() {
+        return Err(format!(
+            "registry directory '{}' does not exist",
+            registry_root.display()
+        ));
+    }
+    let listener =
+        TcpListener::bind(addr).map_err(|e| format!("failed to listen on '{}': {}", addr, e))?;
+    let bound = listener
+        .local_addr()
+        .map(|local| local.to_string())
+        .unwrap_or_else(|_| addr.to_string());
+    on_ready(&bound);
+    // Each connection gets its own thread so a slow client cannot stall the
+    // others. Uploads take the lock exclusively, which serializes them and
+    // keeps readers from seeing a half-recorded release.
+    let registry_root = Arc::new(registry_root);
+    let registry_lock = Arc::new(RwLock::new(()));
+    for stream in listener.incoming() {
+        let Ok(stream) = stream else {
+            continue;
+        };
+        let registry_root = Arc::clone(&registry_root);
+        let registry_lock = Arc::clone(&registry_lock);
+        let _ = thread::Builder::new()
+            .name("rr-registry-conn".to_string())
+            .spawn(move || serve_connection(&registry_root, &registry_lock, stream));
+    }
+    Ok(())
+}
+
+fn serve_connection(registry_root: &Path, registry_lock: &RwLock<()>, mut stream: TcpStream) {
+    let (status, content_type, body) = match read_http_request(&stream, request_body_limit) {
+        Ok(request) if is_publish(&request.method, &request.path) => {
+            let _upload = registry_lock
+                .write()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            handle_registry_request(registry_root, &request)
+        }
+        Ok(request) => {
+            let _read = registry_lock
+                .read()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            handle_registry_request(registry_root, &request)
+        }
+        Err((status, message)) => (status, "text/plain", message.into_bytes()),
+    };
+    let _ = write_http_response(&mut stream, status, content_type, &body);
+    let _ = stream.shutdown(Shutdown::Both);
+}
+
+fn is_publish(method: &str, path: &str) -> bool {
+    method == "POST" && path.split('?').next() == Some("/publish")
+}
+
+/// Only uploads may send a large body.
+fn request_body_limit(method: &str, path: &str) -> usize {
+    if is_publish(method, path) {
+        MAX_BODY_BYTES
+    } else {
+        MAX_QUERY_BODY_BYTES
+    }
+}
+
+fn handle_registry_request(
+    registry_root: &Path,
+    request: &HttpRequest,
+) -> (u16, &'static str, Vec<u8>) {
+    let path = request.path.split('?').next().unwrap_or_default();
+    match (request.method.as_str(), path) {
+        ("GET", "/index") => match load_all_registry_indices(registry_root) {
+            Ok(indices) => {
+                let listing = indices
+                    .into_iter()
+                    .map(|(module_path, _)| format!("{module_path}\n"))
+                    .collect::<String>();
+                (200, "text/plain", listing.into_bytes())
+            }
+            Err(message) => (500, "text/plain", message.into_bytes()),
+        },
+        ("POST", "/publish") => match accept_registry_upload(registry_root, request) {
+            Ok(message) => (200, "text/plain", message.into_bytes()),
+            Err((status, message)) => (status, "text/plain", message.into_bytes()),
+        },
+        ("GET", _) => {
+            let Some(file) = served_registry_file(registry_root, path) else {
+                return (404, "text/plain", b"not found".to_vec());
+            };
+            match fs::read(&file) {
+                Ok(bytes) => (200, "application/octet-stream", bytes),
+                Err(_) => (404, "text/plain", b"not found".to_vec()),
+            }
+        }
+        _ => (405, "text/plain", b"method not allowed".to_vec()),
+    }
+}
+
+/// Map a request path to a file under the registry, refusing anything
+/// outside the served entries.
+fn served_registry_file(registry_root: &Path, path: &str) -> Option<PathBuf> {
+    let rel = path.strip_prefix('/')?;
+    let mut parts = rel.split('/');
+    if !SERVED_ROOTS.contains(&parts.next()?) {
+        return None;
+    }
+    let safe = rel
+        .split('/')
+        .all(|part| !part.is_empty() && !part.starts_with('.') && !part.contains('\\'));
+    let file = registry_root.join(rel);
+    (safe && file.is_file()).then_some(file)
+}
+
+fn accept_registry_upload(
+    registry_root: &Path,
+    request: &HttpRequest,
+) -> Result<String, (u16, String)> {
+    let bad_request = |message: String| (400, message);
+    let version = request
+        .header("X-RR-Version")
+        .filter(|version| version.starts_with('v'))
+        .ok_or_else(|| bad_request("X-RR-Version must name a version like v1.0.0".to_string()))?;
+    let Some(signature) = request.header("X-RR-Signature") else {
+        return Err((
+            403,
+            "the registry server only accepts signed uploads".to_string(),
+        ));
+    };
+    let signer = request.header("X-RR-Signer");
+
+    let upload_dir = unique_temp_dir("rr-registry-upload");
+    fs::create_dir_all(&upload_dir).map_err(|e| (500, e.to_string()))?;
+    let archive_path = upload_dir.join("release.tar.gz");
+    let result = fs::write(&archive_path, &request.body)
+        .map_err(|e| (500, e.to_string()))
+        .and_then(|()| {
+            record_uploaded_release(
+                registry_root,
+                &archive_path,
+                version,
+                signature,
+                signer,
+                request.header("X-RR-Approval-Signature"),
+                request.header("X-RR-Allow-Breaking") == Some("true"),
+            )
+        });
+    let _ = fs::remove_dir_all(&upload_dir);
+    result
+}
+
+fn record_uploaded_release(
+    registry_root: &Path,
+    archive_path: &Path,
+    version: &str,
+    signature: &str,
+    signer: Option<&str>,
+    approval_sig: Option<&str>,
+    allow_breaking: bool,
+) -> Result<String, (u16, String)> {
+    let manifest = read_manifest_from_archive(archive_path).map_err(|e| (400, e))?;
+    let archive_sum = archive_checksum(archive_path).map_err(|e| (500, e))?;
+    let policy = load_registry_trust_policy(registry_root).map_err(|e| (500, e))?;
+    let payload = registry_signature_payload(&manifest.module_path, version, &archive_sum);
+    verify_registry_signature(
+        &manifest.module_path,
+        version,
+        &payload,
+        signature,
+        signer,
+        &policy,
+    )
+    .map_err(|e| (403, e))?;
+
+    let source_root = extract_registry_archive_to_temp(archive_path, "rr-registry-upload")
+        .map_err(|e| (400, e))?;
+    let included_files = collect_file_map(&source_root)
+        .map(|files| files.into_keys().collect::<Vec<_>>())
+        .map_err(|e| (500, e));
+    let recorded = included_files.and_then(|included_files| {
+        record_registry_release(
+            registry_root,
+            RegistryUpload {
+                manifest: &manifest,
+                version,
+                archive_path,
+                archive_sum,
+                source_root: &source_root,
+                included_files: &included_files,
+                archive_sig: Some(signature.to_string()),
+                signer: signer.map(str::to_string),
+                approval_sig: approval_sig.map(str::to_string),
+                allow_breaking,
+                dry_run: false,
+            },
+        )
+        .map_err(|e| (409, e))
+    });
+    let _ = fs::remove_dir_all(&source_root);
+    recorded?;
+    Ok(format!("published {} {}", manifest.module_path, version))
+}
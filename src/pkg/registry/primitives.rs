@@ -1,5 +1,5 @@
 use hmac::{Hmac, Mac};
-use sha2::Sha256;
+use sha2::{Digest, Sha256};
 use std::collections::BTreeSet;
 
 pub(super) fn validate_unique_hex_keys(
@@ -69,6 +69,10 @@ pub(super) fn hmac_sha256_hex(key: &str, payload: &[u8]) -> Result<String, Strin
     Ok(hex_encode(bytes.as_slice()))
 }
 
+pub(super) fn sha256_hex(payload: &[u8]) -> String {
+    hex_encode(Sha256::digest(payload).as_slice())
+}
+
 pub(super) fn hex_encode(bytes: &[u8]) -> String {
     let mut out = String::with_capacity(bytes.len() * 2);
     for byte in bytes {
@@ -1,18 +1,28 @@
-use super::env::{registry_policy_override_path, registry_trust_key};
+use super::api::{ApiSurface, diff_api};
+use super::audit_chain::append_chained_audit_line;
+use super::env::{registry_policy_override_path, registry_signing_identity, registry_trust_key};
 use super::git::{
     create_git_tag, ensure_git_identity, git_repo_is_dirty, git_tag_exists, push_git_tag,
 };
+use super::http_registry::{
+    is_http_registry_spec, materialize_http_registry, sync_registry_file,
+    sync_registry_module_list, upload_registry_release,
+};
 use super::manifest::{escape_toml, parse_toml_string};
 use super::primitives::*;
 use super::signing::{
-    registry_signature_payload, resolved_registry_trust_ed25519_keys, sign_registry_release,
-    verify_registry_signer_policy,
+    registry_signature_payload, resolved_registry_trust_ed25519_keys, sign_registry_approval,
+    sign_registry_release, verify_owner_approval, verify_registry_signer_policy,
+};
+use super::trust_policy::{
+    mutate_registry_policy, owner_approval_count, parse_registry_trust_policy, registry_owner_rule,
+    verify_registry_license_policy,
 };
-use super::trust_policy::{mutate_registry_policy, parse_registry_trust_policy};
 use super::util::{
-    archive_checksum, collect_publishable_files, compare_versions, module_path_to_rel_path,
-    normalize_path, project_dir_name_from_module_path, stable_hash_update, unique_temp_dir,
-    version_matches_module_path,
+    archive_checksum, collect_file_map, collect_publishable_files, compare_versions,
+    extract_registry_archive_to_temp, is_major_bump, module_path_to_rel_path, normalize_path,
+    project_dir_name_from_module_path, read_manifest_from_archive, registry_rel_join,
+    stable_hash_update, unique_temp_dir, version_matches_module_path,
 };
 use super::*;
 use ed25519_dalek::{Signature, Verifier, VerifyingKey};
@@ -91,30 +101,103 @@ pub fn onboard_registry(
     })
 }
 
+/// Record the current signing identity's approval of a release, signed with
+/// the configured key. When an `[[owners]]` rule covers the module, the
+/// approver must be an owner whose signature verifies, and the release is only
+/// approved once enough distinct owners have signed off.
 pub fn approve_registry_release(
     module_path: &str,
     version: &str,
     registry_spec: &Path,
-) -> Result<(), String> {
-    mutate_registry_index(
-        registry_spec,
-        module_path,
-        &format!("approve {} {}", module_path, version),
-        |index| {
-            let Some(entry) = index
-                .releases
-                .iter_mut()
-                .find(|entry| entry.version == version)
-            else {
-                return Err(format!(
-                    "registry module '{}' does not contain version '{}'",
-                    module_path, version
-                ));
-            };
-            entry.approved = true;
-            Ok(())
-        },
-    )
+) -> Result<RegistryApproval, String> {
+    let approver = registry_signing_identity();
+    let message = match &approver {
+        Some(approver) => format!("approve {} {} by {}", module_path, version, approver),
+        None => format!("approve {} {}", module_path, version),
+    };
+    let mut outcome = None;
+    mutate_registry_index_at(registry_spec, module_path, &message, |root, index| {
+        let index_license = index.license.clone();
+        let Some(entry) = index
+            .releases
+            .iter_mut()
+            .find(|entry| entry.version == version)
+        else {
+            return Err(format!(
+                "registry module '{}' does not contain version '{}'",
+                module_path, version
+            ));
+        };
+        let archive_path = registry_rel_join(root, "archive", &entry.archive_rel)?;
+        let license = if archive_path.is_file() {
+            read_manifest_from_archive(&archive_path)?.license
+        } else {
+            index_license
+        };
+        let policy = load_registry_trust_policy(root)?;
+        verify_registry_license_policy(module_path, version, license.as_deref(), &policy)?;
+        let (owner_approvals, required_approvals) = match registry_owner_rule(&policy, module_path)
+        {
+            Some(rule) => {
+                let Some(approver) = approver.as_deref() else {
+                    return Err(format!(
+                        "registry policy requires owner approvals for '{}'; set RR_REGISTRY_SIGNING_IDENTITY to an owner",
+                        module_path
+                    ));
+                };
+                if !rule.owners.iter().any(|owner| owner == approver) {
+                    return Err(format!(
+                        "'{}' is not an owner of '{}'",
+                        approver, module_path
+                    ));
+                }
+                if entry.approvals.iter().any(|id| id == approver) {
+                    return Err(format!(
+                        "'{}' has already approved '{} {}'",
+                        approver, module_path, version
+                    ));
+                }
+                let Some(sig) = sign_registry_approval(module_path, version, approver)? else {
+                    return Err(format!(
+                        "owner approvals of '{}' must be signed; set RR_REGISTRY_SIGNING_ED25519_SECRET or RR_REGISTRY_SIGNING_KEY",
+                        module_path
+                    ));
+                };
+                verify_owner_approval(rule, module_path, version, approver, Some(&sig))?;
+                entry.approvals.push(approver.to_string());
+                entry.approval_sigs.insert(approver.to_string(), sig);
+                let count = owner_approval_count(
+                    rule,
+                    module_path,
+                    version,
+                    &entry.approvals,
+                    &entry.approval_sigs,
+                );
+                entry.approved = count >= rule.required_approvals;
+                (count, Some(rule.required_approvals))
+            }
+            None => {
+                if let Some(approver) = approver.as_deref()
+                    && !entry.approvals.iter().any(|id| id == approver)
+                {
+                    entry.approvals.push(approver.to_string());
+                    if let Some(sig) = sign_registry_approval(module_path, version, approver)? {
+                        entry.approval_sigs.insert(approver.to_string(), sig);
+                    }
+                }
+                entry.approved = true;
+                (0, None)
+            }
+        };
+        outcome = Some(RegistryApproval {
+            approved: entry.approved,
+            approvals: entry.approvals.clone(),
+            owner_approvals,
+            required_approvals,
+        });
+        Ok(())
+    })?;
+    outcome.ok_or_else(|| format!("failed to approve '{} {}'", module_path, version))
 }
 
 pub fn unapprove_registry_release(
@@ -138,6 +221,8 @@ pub fn unapprove_registry_release(
                 ));
             };
             entry.approved = false;
+            entry.approvals.clear();
+            entry.approval_sigs.clear();
             index.channels.retain(|_, assigned| assigned != version);
             Ok(())
         },
@@ -149,11 +234,13 @@ pub fn promote_registry_release(
     version: &str,
     registry_spec: &Path,
 ) -> Result<(), String> {
-    mutate_registry_index(
+    mutate_registry_index_at(
         registry_spec,
         module_path,
         &format!("promote registry release {} {}", module_path, version),
-        |index| {
+        |root, index| {
+            let policy = load_registry_trust_policy(root)?;
+            let rule = registry_owner_rule(&policy, module_path);
             let mut found = false;
             for entry in &mut index.releases {
                 if entry.version == version {
@@ -163,6 +250,21 @@ pub fn promote_registry_release(
                             module_path, version
                         ));
                     }
+                    if let Some(rule) = rule {
+                        let count = owner_approval_count(
+                            rule,
+                            module_path,
+                            version,
+                            &entry.approvals,
+                            &entry.approval_sigs,
+                        );
+                        if count < rule.required_approvals {
+                            return Err(format!(
+                                "cannot promote '{} {}' with {}/{} owner approvals",
+                                module_path, version, count, rule.required_approvals
+                            ));
+                        }
+                    }
                     entry.approved = true;
                     found = true;
                 } else {
@@ -276,22 +378,136 @@ fn publish_to_registry(
     options: &PublishOptions,
     registry_spec: &Path,
 ) -> Result<(), String> {
+    if is_http_registry_spec(registry_spec) {
+        return upload_registry_release(
+            registry_spec,
+            &manifest.module_path,
+            version,
+            archive_path,
+            options,
+        );
+    }
     let (write_root, is_remote) = materialize_registry_write_root(registry_spec, options.dry_run)?;
     let archive_sum = archive_checksum(archive_path)?;
-    let registry_archive = registry_archive_path(&write_root, &manifest.module_path, version);
-
-    let policy = load_registry_trust_policy(&write_root)?;
     let (archive_sig, signer) =
         sign_registry_release(&manifest.module_path, version, &archive_sum)?;
+    let approval_sig = match signer.as_deref() {
+        Some(signer) => sign_registry_approval(&manifest.module_path, version, signer)?,
+        None => None,
+    };
+    record_registry_release(
+        &write_root,
+        RegistryUpload {
+            manifest,
+            version,
+            archive_path,
+            archive_sum,
+            source_root: project_root,
+            included_files,
+            archive_sig,
+            signer,
+            approval_sig,
+            allow_breaking: options.allow_breaking,
+            dry_run: options.dry_run,
+        },
+    )?;
+
+    if is_remote && !options.dry_run {
+        run_git(Some(&write_root), &["add", "."])?;
+        let _ = run_git(
+            Some(&write_root),
+            &[
+                "commit",
+                "-m",
+                &format!("publish {} {}", manifest.module_path, version),
+            ],
+        );
+        run_git(Some(&write_root), &["push", "origin", "HEAD"])?;
+    }
+
+    Ok(())
+}
+
+/// A packed release on its way into a registry directory.
+pub(super) struct RegistryUpload<'a> {
+    pub manifest: &'a Manifest,
+    pub version: &'a str,
+    pub archive_path: &'a Path,
+    pub archive_sum: String,
+    /// Directory holding the release's files, read by the API check.
+    pub source_root: &'a Path,
+    pub included_files: &'a [String],
+    pub archive_sig: Option<String>,
+    pub signer: Option<String>,
+    /// The signer's signature on its own approval, for auto-approving signers.
+    pub approval_sig: Option<String>,
+    pub allow_breaking: bool,
+    pub dry_run: bool,
+}
+
+/// Apply the registry policy to a release, then store its archive and index
+/// entry under `write_root`. Shared by local publishing and HTTP uploads.
+pub(super) fn record_registry_release(
+    write_root: &Path,
+    upload: RegistryUpload<'_>,
+) -> Result<(), String> {
+    let RegistryUpload {
+        manifest,
+        version,
+        archive_path,
+        archive_sum,
+        source_root,
+        included_files,
+        archive_sig,
+        signer,
+        approval_sig,
+        allow_breaking,
+        dry_run,
+    } = upload;
+    let registry_archive = registry_archive_path(write_root, &manifest.module_path, version);
+    let policy = load_registry_trust_policy(write_root)?;
     if policy.require_signed && archive_sig.is_none() {
         return Err("registry policy requires signed releases".to_string());
     }
     if !policy.allowed_signers.is_empty() {
         verify_registry_signer_policy(&manifest.module_path, version, signer.as_deref(), &policy)?;
     }
-    let approved = should_auto_approve_release(&policy, signer.as_deref());
+    let mut approvals = Vec::new();
+    let mut approval_sigs = BTreeMap::new();
+    let approved = match registry_owner_rule(&policy, &manifest.module_path) {
+        Some(rule) => {
+            // An auto-approving signer counts as one owner approval when it
+            // signed that approval too.
+            if let Some(signer) = signer.as_deref()
+                && policy.auto_approve_signers.iter().any(|id| id == signer)
+                && rule.owners.iter().any(|owner| owner == signer)
+                && let Some(sig) = approval_sig
+            {
+                verify_owner_approval(rule, &manifest.module_path, version, signer, Some(&sig))?;
+                approvals.push(signer.to_string());
+                approval_sigs.insert(signer.to_string(), sig);
+            }
+            owner_approval_count(
+                rule,
+                &manifest.module_path,
+                version,
+                &approvals,
+                &approval_sigs,
+            ) >= rule.required_approvals
+        }
+        None => should_auto_approve_release(&policy, signer.as_deref()),
+    };
+    if !allow_breaking {
+        check_release_api_compat(
+            write_root,
+            source_root,
+            &manifest.module_path,
+            version,
+            included_files,
+        )?;
+    }
 
-    if !options.dry_run {
+    if !dry_run {
         if let Some(parent) = registry_archive.parent() {
             fs::create_dir_all(parent)
                 .map_err(|e| format!("failed to create '{}': {}", parent.display(), e))?;
@@ -307,13 +523,13 @@ fn publish_to_registry(
     }
 
     let archive_rel = registry_archive
-        .strip_prefix(&write_root)
+        .strip_prefix(write_root)
         .ok()
         .map(|path| path.to_string_lossy().to_string())
         .unwrap_or_else(|| registry_archive.to_string_lossy().to_string())
         .replace('\\', "/");
 
-    let mut index = load_registry_index(&write_root, &manifest.module_path)?;
+    let mut index = load_registry_index(write_root, &manifest.module_path)?;
     index.module_path = manifest.module_path.clone();
     index.description = manifest.description.clone();
     index.license = manifest.license.clone();
@@ -328,14 +544,16 @@ fn publish_to_registry(
         approved,
         archive_sig,
         signer,
+        approvals,
+        approval_sigs,
     });
     index
         .releases
         .sort_by(|a, b| compare_versions(&a.version, &b.version));
-    if !options.dry_run {
-        write_registry_index(&write_root, &manifest.module_path, &index)?;
+    if !dry_run {
+        write_registry_index(write_root, &manifest.module_path, &index)?;
         append_registry_audit_entry(
-            &write_root,
+            write_root,
             "publish",
             &format!(
                 "module={} version={} approved={} archive={}",
@@ -346,28 +564,81 @@ fn publish_to_registry(
             ),
         )?;
     }
+    Ok(())
+}
 
-    if is_remote && !options.dry_run {
-        run_git(Some(&write_root), &["add", "."])?;
-        let _ = run_git(
-            Some(&write_root),
-            &[
-                "commit",
-                "-m",
-                &format!("publish {} {}", manifest.module_path, version),
-            ],
-        );
-        run_git(Some(&write_root), &["push", "origin", "HEAD"])?;
+/// Refuse a release that breaks the exported API of the latest earlier
+/// release unless it bumps the major version.
+fn check_release_api_compat(
+    registry_root: &Path,
+    project_root: &Path,
+    module_path: &str,
+    version: &str,
+    included_files: &[String],
+) -> Result<(), String> {
+    let index = load_registry_index(registry_root, module_path)?;
+    let Some(baseline) = index
+        .releases
+        .iter()
+        .filter(|entry| {
+            !entry.yanked && compare_versions(&entry.version, version) == Ordering::Less
+        })
+        .max_by(|a, b| compare_versions(&a.version, &b.version))
+    else {
+        return Ok(());
+    };
+    if is_major_bump(&baseline.version, version) {
+        return Ok(());
     }
+    let baseline_archive = registry_rel_join(registry_root, "archive", &baseline.archive_rel)?;
+    if !baseline_archive.is_file() {
+        return Err(format!(
+            "registry archive '{}' is missing",
+            baseline_archive.display()
+        ));
+    }
+    let baseline_root = extract_registry_archive_to_temp(&baseline_archive, "rr-publish-api")?;
+    let baseline_files = collect_file_map(&baseline_root);
+    let _ = fs::remove_dir_all(&baseline_root);
+    let old = ApiSurface::from_files(&baseline_files?)?;
 
-    let _ = project_root;
-    Ok(())
+    let mut current_files = BTreeMap::new();
+    for rel in included_files.iter().filter(|rel| rel.ends_with(".rr")) {
+        let path = project_root.join(rel);
+        let bytes =
+            fs::read(&path).map_err(|e| format!("failed to read '{}': {}", path.display(), e))?;
+        current_files.insert(rel.clone(), bytes);
+    }
+    let new = ApiSurface::from_files(&current_files)?;
+
+    let breaking = diff_api(&old, &new)
+        .into_iter()
+        .filter(|change| change.kind.is_breaking())
+        .map(|change| format!("  {} {}: {}", change.file, change.item, change.detail))
+        .collect::<Vec<_>>();
+    if breaking.is_empty() {
+        return Ok(());
+    }
+    Err(format!(
+        "{} breaks the exported API of {} without a major version bump:\n{}\nbump the major version or pass --allow-breaking",
+        version,
+        baseline.version,
+        breaking.join("\n")
+    ))
 }
 
 pub(in crate::pkg) fn load_registry_index(
     registry_root: &Path,
     module_path: &str,
 ) -> Result<RegistryIndex, String> {
+    sync_registry_file(
+        registry_root,
+        &registry_index_path(registry_root, module_path),
+    )?;
+    read_registry_index(registry_root, module_path)
+}
+
+fn read_registry_index(registry_root: &Path, module_path: &str) -> Result<RegistryIndex, String> {
     let index_path = registry_index_path(registry_root, module_path);
     if !index_path.is_file() {
         return Ok(RegistryIndex {
@@ -400,6 +671,8 @@ pub(in crate::pkg) fn load_registry_index(
                 approved: true,
                 archive_sig: None,
                 signer: None,
+                approvals: Vec::new(),
+                approval_sigs: BTreeMap::new(),
             });
             continue;
         }
@@ -416,6 +689,15 @@ pub(in crate::pkg) fn load_registry_index(
                     "approved" => entry.approved = value == "true",
                     "sig" => entry.archive_sig = Some(parse_toml_string(value)?),
                     "signer" => entry.signer = Some(parse_toml_string(value)?),
+                    "approved_by" => entry.approvals.push(parse_toml_string(value)?),
+                    // Signs the `approved_by` line just above it.
+                    "approval_sig" => {
+                        if let Some(approver) = entry.approvals.last() {
+                            entry
+                                .approval_sigs
+                                .insert(approver.clone(), parse_toml_string(value)?);
+                        }
+                    }
                     _ => {}
                 }
             } else {
@@ -511,12 +793,25 @@ pub(in crate::pkg) fn verify_registry_release_trust(
     };
 
     let payload = registry_signature_payload(module_path, version, archive_sum);
+    verify_registry_signature(module_path, version, &payload, signature, signer, policy)
+}
+
+/// Check `signature` over `payload` against the trust policy. `label` names
+/// the signed item (a release version or advisory id) in errors.
+pub(super) fn verify_registry_signature(
+    module_path: &str,
+    label: &str,
+    payload: &str,
+    signature: &str,
+    signer: Option<&str>,
+    policy: &RegistryTrustPolicy,
+) -> Result<(), String> {
     if let Some(raw_digest) = signature.strip_prefix("hmac-sha256:") {
-        verify_registry_signer_policy(module_path, version, signer, policy)?;
+        verify_registry_signer_policy(module_path, label, signer, policy)?;
         let Some(key) = registry_trust_key() else {
             return Err(format!(
-                "registry release '{} {}' uses HMAC signing but RR_REGISTRY_TRUST_KEY is not configured",
-                module_path, version
+                "registry item '{} {}' uses HMAC signing but RR_REGISTRY_TRUST_KEY is not configured",
+                module_path, label
             ));
         };
         let expected = hmac_sha256_hex(&key, payload.as_bytes())?;
@@ -525,27 +820,27 @@ pub(in crate::pkg) fn verify_registry_release_trust(
         }
         return Err(format!(
             "registry signature mismatch for '{} {}': expected hmac-sha256:{}, got {}",
-            module_path, version, expected, signature
+            module_path, label, expected, signature
         ));
     }
 
     let Some(rest) = signature.strip_prefix("ed25519:") else {
         return Err(format!(
             "unsupported registry signature format for '{} {}': {}",
-            module_path, version, signature
+            module_path, label, signature
         ));
     };
     let Some((public_key_hex, signature_hex)) = rest.split_once(':') else {
         return Err(format!(
             "invalid ed25519 registry signature format for '{} {}': {}",
-            module_path, version, signature
+            module_path, label, signature
         ));
     };
     let trusted_keys = resolved_registry_trust_ed25519_keys(policy)?;
     if trusted_keys.is_empty() {
         return Err(format!(
-            "registry release '{} {}' uses ed25519 signing but no trusted ed25519 keys are configured",
-            module_path, version
+            "registry item '{} {}' uses ed25519 signing but no trusted ed25519 keys are configured",
+            module_path, label
         ));
     }
     if policy
@@ -555,28 +850,28 @@ pub(in crate::pkg) fn verify_registry_release_trust(
     {
         return Err(format!(
             "registry signer key is revoked for '{} {}': {}",
-            module_path, version, public_key_hex
+            module_path, label, public_key_hex
         ));
     }
     if !trusted_keys.iter().any(|key| key == public_key_hex) {
         return Err(format!(
             "registry signer key is not trusted for '{} {}': {}",
-            module_path, version, public_key_hex
+            module_path, label, public_key_hex
         ));
     }
-    verify_registry_signer_policy(module_path, version, signer, policy)?;
+    verify_registry_signer_policy(module_path, label, signer, policy)?;
 
     let public_key_bytes = hex_decode(public_key_hex)?;
     let public_key_arr: [u8; 32] = public_key_bytes.try_into().map_err(|_| {
         format!(
             "invalid ed25519 public key length for '{} {}'",
-            module_path, version
+            module_path, label
         )
     })?;
     let verifying_key = VerifyingKey::from_bytes(&public_key_arr).map_err(|e| {
         format!(
             "invalid ed25519 public key for '{} {}': {}",
-            module_path, version, e
+            module_path, label, e
         )
     })?;
 
@@ -584,7 +879,7 @@ pub(in crate::pkg) fn verify_registry_release_trust(
     let signature_arr: [u8; 64] = signature_bytes.try_into().map_err(|_| {
         format!(
             "invalid ed25519 signature length for '{} {}'",
-            module_path, version
+            module_path, label
         )
     })?;
     let signature = Signature::from_bytes(&signature_arr);
@@ -593,7 +888,7 @@ pub(in crate::pkg) fn verify_registry_release_trust(
         .map_err(|e| {
             format!(
                 "registry signature mismatch for '{} {}': {}",
-                module_path, version, e
+                module_path, label, e
             )
         })
 }
@@ -634,9 +929,23 @@ pub(in crate::pkg) fn materialize_registry_read_root(spec: &Path) -> Result<Path
     }
     let remote = registry_spec_text(spec);
     let local_root = registry_cache_root_for_spec(spec);
+    if is_http_registry_spec(spec) {
+        materialize_http_registry(&remote, &local_root)?;
+        touch_cache_last_used(&local_root);
+        return Ok(local_root);
+    }
     if local_root.join(".git").exists() {
-        let _ = run_git(Some(&local_root), &["pull", "--ff-only"])?;
+        if !offline_mode() {
+            let _ = run_git(Some(&local_root), &["pull", "--ff-only"])?;
+        }
     } else {
+        if offline_mode() {
+            return Err(format!(
+                "registry '{}' has no local cache at '{}' and offline mode is enabled; run once without --offline to fetch it",
+                remote,
+                local_root.display()
+            ));
+        }
         if let Some(parent) = local_root.parent() {
             fs::create_dir_all(parent)
                 .map_err(|e| format!("failed to create '{}': {}", parent.display(), e))?;
@@ -651,6 +960,7 @@ pub(in crate::pkg) fn materialize_registry_read_root(spec: &Path) -> Result<Path
             ],
         )?;
     }
+    touch_cache_last_used(&local_root);
     Ok(local_root)
 }
 
@@ -662,6 +972,12 @@ pub(super) fn materialize_registry_write_root(
         return Ok((normalize_path(spec), false));
     }
     let remote = registry_spec_text(spec);
+    if is_http_registry_spec(spec) {
+        return Err(format!(
+            "HTTP registry '{}' only accepts releases from RR publish; run this command against the server's registry directory",
+            remote
+        ));
+    }
     let worktree = unique_temp_dir("rr-registry-publish");
     if !dry_run {
         run_git(
@@ -747,6 +1063,12 @@ fn write_registry_index(
         if let Some(signer) = &entry.signer {
             out.push_str(&format!("signer = \"{}\"\n", escape_toml(signer)));
         }
+        for approver in &entry.approvals {
+            out.push_str(&format!("approved_by = \"{}\"\n", escape_toml(approver)));
+            if let Some(sig) = entry.approval_sigs.get(approver) {
+                out.push_str(&format!("approval_sig = \"{}\"\n", escape_toml(sig)));
+            }
+        }
     }
     fs::write(&index_path, out)
         .map_err(|e| format!("failed to write '{}': {}", index_path.display(), e))
@@ -755,6 +1077,7 @@ fn write_registry_index(
 pub(super) fn load_all_registry_indices(
     registry_root: &Path,
 ) -> Result<Vec<(String, RegistryIndex)>, String> {
+    sync_registry_module_list(registry_root)?;
     let index_root = registry_root.join("index");
     if !index_root.is_dir() {
         return Ok(Vec::new());
@@ -766,7 +1089,7 @@ pub(super) fn load_all_registry_indices(
     let mut out = Vec::new();
     for path in files {
         let module_path = registry_module_path_from_index_path(&index_root, &path)?;
-        let mut index = load_registry_index(registry_root, &module_path)?;
+        let mut index = read_registry_index(registry_root, &module_path)?;
         if index.module_path.is_empty() {
             index.module_path = module_path.clone();
         }
@@ -868,25 +1191,19 @@ pub(super) fn append_registry_audit_entry(
     action: &str,
     detail: &str,
 ) -> Result<(), String> {
-    let audit_path = registry_root.join("audit.log");
     let timestamp_secs = SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .map(|duration| duration.as_secs())
         .unwrap_or(0);
-    let mut existing = if audit_path.is_file() {
-        fs::read_to_string(&audit_path)
-            .map_err(|e| format!("failed to read '{}': {}", audit_path.display(), e))?
-    } else {
-        String::new()
-    };
-    existing.push_str(&format!(
-        "{}\t{}\t{}\n",
-        timestamp_secs,
-        action,
-        detail.replace('\n', " ")
-    ));
-    fs::write(&audit_path, existing)
-        .map_err(|e| format!("failed to write '{}': {}", audit_path.display(), e))
+    append_chained_audit_line(
+        registry_root,
+        &format!(
+            "{}\t{}\t{}",
+            timestamp_secs,
+            action,
+            detail.replace('\n', " ")
+        ),
+    )
 }
 
 pub(super) fn audit_entry_matches(
@@ -917,13 +1234,26 @@ pub(super) fn mutate_registry_index(
     module_path: &str,
     commit_message: &str,
     update: impl FnOnce(&mut RegistryIndex) -> Result<(), String>,
+) -> Result<(), String> {
+    mutate_registry_index_at(registry_spec, module_path, commit_message, |_, index| {
+        update(index)
+    })
+}
+
+/// Like `mutate_registry_index`, but also hands the update the materialized
+/// registry root so it can inspect archives and the policy file.
+fn mutate_registry_index_at(
+    registry_spec: &Path,
+    module_path: &str,
+    commit_message: &str,
+    update: impl FnOnce(&Path, &mut RegistryIndex) -> Result<(), String>,
 ) -> Result<(), String> {
     let (write_root, is_remote) = materialize_registry_write_root(registry_spec, false)?;
     let mut index = load_registry_index(&write_root, module_path)?;
     if index.module_path.is_empty() {
         index.module_path = module_path.to_string();
     }
-    update(&mut index)?;
+    update(&write_root, &mut index)?;
     write_registry_index(&write_root, module_path, &index)?;
     append_registry_audit_entry(
         &write_root,
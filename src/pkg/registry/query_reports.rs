@@ -1,12 +1,17 @@
+use super::api::{ApiSurface, diff_api};
+use super::audit_chain::split_audit_hash;
 use super::env::{registry_policy_override_path, registry_spec_from_override};
 use super::primitives::*;
 use super::publishing::{
     audit_entry_matches, default_registry_risk_baseline, latest_non_yanked_registry_release,
     load_all_registry_indices, mutate_registry_index, registry_query_matches,
 };
-use super::trust_policy::{mutate_registry_policy, parse_registry_trust_policy};
+use super::trust_policy::{
+    mutate_registry_policy, owner_approval_count, parse_registry_trust_policy, registry_owner_rule,
+};
 use super::util::{
-    collect_file_map, escape_json, extract_registry_archive_to_temp, read_manifest_from_archive,
+    collect_file_map, escape_json, extract_registry_archive_to_temp, is_major_bump,
+    read_manifest_from_archive, registry_rel_join,
 };
 use super::*;
 use ed25519_dalek::SigningKey;
@@ -16,7 +21,8 @@ pub fn search_registry_modules(
     query: &str,
     registry_spec: Option<&Path>,
 ) -> Result<Vec<RegistrySearchResult>, String> {
-    let registry_root = registry_spec_from_override(registry_spec)?;
+    let registry_root =
+        materialize_registry_read_root(&registry_spec_from_override(registry_spec)?)?;
     let indices = load_all_registry_indices(&registry_root)?;
     let mut matches = Vec::new();
     for (module_path, index) in indices {
@@ -49,7 +55,8 @@ pub fn search_registry_modules(
 pub fn list_registry_modules(
     registry_spec: Option<&Path>,
 ) -> Result<Vec<RegistrySearchResult>, String> {
-    let registry_root = registry_spec_from_override(registry_spec)?;
+    let registry_root =
+        materialize_registry_read_root(&registry_spec_from_override(registry_spec)?)?;
     let indices = load_all_registry_indices(&registry_root)?;
     let mut modules = Vec::new();
     for (module_path, index) in indices {
@@ -135,20 +142,35 @@ pub fn clear_registry_channel(
 }
 
 pub fn list_registry_queue(registry_spec: Option<&Path>) -> Result<Vec<RegistryQueueItem>, String> {
-    let registry_root = registry_spec_from_override(registry_spec)?;
+    let registry_root =
+        materialize_registry_read_root(&registry_spec_from_override(registry_spec)?)?;
     let indices = load_all_registry_indices(&registry_root)?;
+    let policy = load_registry_trust_policy(&registry_root)?;
     let mut queue = Vec::new();
     for (module_path, index) in indices {
+        let rule = registry_owner_rule(&policy, &module_path);
         for entry in index.releases {
             if entry.approved {
                 continue;
             }
+            let owner_approvals = rule.map_or(0, |rule| {
+                owner_approval_count(
+                    rule,
+                    &module_path,
+                    &entry.version,
+                    &entry.approvals,
+                    &entry.approval_sigs,
+                )
+            });
             queue.push(RegistryQueueItem {
                 path: module_path.clone(),
                 version: entry.version,
                 yanked: entry.yanked,
                 signed: entry.archive_sig.is_some(),
                 signer: entry.signer,
+                approvals: entry.approvals,
+                owner_approvals,
+                required_approvals: rule.map(|rule| rule.required_approvals),
             });
         }
     }
@@ -188,6 +210,7 @@ pub fn read_registry_audit_log_filtered(
     };
     let mut entries = Vec::new();
     for line in content.lines() {
+        let (line, _) = split_audit_hash(line);
         let mut parts = line.splitn(3, '\t');
         let timestamp_secs = parts
             .next()
@@ -266,7 +289,8 @@ pub fn registry_report(
     registry_spec: Option<&Path>,
     module_path: Option<&str>,
 ) -> Result<RegistryReport, String> {
-    let registry_root = registry_spec_from_override(registry_spec)?;
+    let registry_root =
+        materialize_registry_read_root(&registry_spec_from_override(registry_spec)?)?;
     let modules: Vec<(String, RegistryIndex)> = if let Some(module_path) = module_path {
         vec![(
             module_path.to_string(),
@@ -429,6 +453,21 @@ pub fn registry_risk(
                 detail: format!("{} files differ from {}", changed_total, baseline_version),
             });
         }
+        let breaking = diff
+            .api_changes
+            .iter()
+            .filter(|change| change.kind.is_breaking())
+            .count();
+        if breaking > 0 && !is_major_bump(baseline_version, version) {
+            factors.push(RegistryRiskFactor {
+                key: "breaking-api".to_string(),
+                points: 4,
+                detail: format!(
+                    "{} breaking API change(s) from {} without a major version bump",
+                    breaking, baseline_version
+                ),
+            });
+        }
         if diff.from_signer != diff.to_signer {
             factors.push(RegistryRiskFactor {
                 key: "signer-change".to_string(),
@@ -504,8 +543,8 @@ fn registry_diff_from_index(
             )
         })?;
 
-    let from_archive = registry_root.join(&from_entry.archive_rel);
-    let to_archive = registry_root.join(&to_entry.archive_rel);
+    let from_archive = registry_rel_join(registry_root, "archive", &from_entry.archive_rel)?;
+    let to_archive = registry_rel_join(registry_root, "archive", &to_entry.archive_rel)?;
     if !from_archive.is_file() {
         return Err(format!(
             "registry archive '{}' is missing",
@@ -525,6 +564,10 @@ fn registry_diff_from_index(
     let to_files = collect_file_map(&to_root)?;
     let _ = fs::remove_dir_all(&from_root);
     let _ = fs::remove_dir_all(&to_root);
+    let api_changes = diff_api(
+        &ApiSurface::from_files(&from_files)?,
+        &ApiSurface::from_files(&to_files)?,
+    );
 
     let mut added_files = Vec::new();
     let mut removed_files = Vec::new();
@@ -562,6 +605,7 @@ fn registry_diff_from_index(
         added_files,
         removed_files,
         changed_files,
+        api_changes,
     })
 }
 
@@ -588,7 +632,8 @@ pub fn registry_module_info(
     module_path: &str,
     registry_spec: Option<&Path>,
 ) -> Result<RegistryInfo, String> {
-    let registry_root = registry_spec_from_override(registry_spec)?;
+    let registry_root =
+        materialize_registry_read_root(&registry_spec_from_override(registry_spec)?)?;
     let index = load_registry_index(&registry_root, module_path)?;
     let mut releases = index
         .releases
@@ -603,6 +648,7 @@ pub fn registry_module_info(
             signed: entry.archive_sig.is_some(),
             signer: entry.signer,
             signature_scheme: entry.archive_sig.as_deref().and_then(signature_scheme_name),
+            approvals: entry.approvals,
         })
         .collect::<Vec<_>>();
     releases.sort_by(|a, b| compare_versions(&a.version, &b.version));
@@ -717,7 +763,19 @@ pub fn verify_registry(
     for (module_path, index) in &modules {
         for entry in &index.releases {
             checked_releases += 1;
-            let archive_path = registry_root.join(&entry.archive_rel);
+            let archive_path =
+                match registry_rel_join(&registry_root, "archive", &entry.archive_rel) {
+                    Ok(path) => path,
+                    Err(message) => {
+                        issues.push(RegistryVerifyIssue {
+                            path: module_path.clone(),
+                            version: entry.version.clone(),
+                            archive_path: PathBuf::from(&entry.archive_rel),
+                            message,
+                        });
+                        continue;
+                    }
+                };
             if !archive_path.is_file() {
                 issues.push(RegistryVerifyIssue {
                     path: module_path.clone(),
@@ -895,6 +953,51 @@ pub fn lint_registry_policy(
         &mut warnings,
     );
 
+    validate_unique_strings(&policy.allowed_licenses, "license allow", &mut warnings);
+    validate_unique_strings(&policy.denied_licenses, "license deny", &mut warnings);
+    for license in &policy.denied_licenses {
+        if policy
+            .allowed_licenses
+            .iter()
+            .any(|allowed| allowed.eq_ignore_ascii_case(license))
+        {
+            errors.push(format!(
+                "license {} appears in both license allow and deny",
+                license
+            ));
+        }
+    }
+
+    let mut owner_modules = Vec::new();
+    for rule in &policy.owner_rules {
+        if owner_modules.contains(&rule.module) {
+            warnings.push(format!(
+                "owners rule for {} appears more than once",
+                rule.module
+            ));
+        }
+        owner_modules.push(rule.module.clone());
+        validate_unique_strings(
+            &rule.owners,
+            &format!("owners for {}", rule.module),
+            &mut warnings,
+        );
+        for (owner, key) in &rule.owner_keys {
+            if !rule.owners.contains(owner) {
+                warnings.push(format!(
+                    "owners rule for {} lists a key for '{}', who is not an owner",
+                    rule.module, owner
+                ));
+            }
+            if normalize_ed25519_public_key(key).is_err() {
+                errors.push(format!(
+                    "owners rule for {} has an invalid ed25519 key for '{}'",
+                    rule.module, owner
+                ));
+            }
+        }
+    }
+
     for revoked in &policy.revoked_ed25519_keys {
         if let Ok(normalized) = normalize_ed25519_public_key(revoked)
             && policy
@@ -918,6 +1021,9 @@ pub fn lint_registry_policy(
         revoked_count: policy.revoked_ed25519_keys.len(),
         allowed_signer_count: policy.allowed_signers.len(),
         auto_approve_signer_count: policy.auto_approve_signers.len(),
+        allowed_license_count: policy.allowed_licenses.len(),
+        denied_license_count: policy.denied_licenses.len(),
+        owner_rule_count: policy.owner_rules.len(),
         warnings,
         errors,
     })
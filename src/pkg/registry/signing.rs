@@ -1,22 +1,29 @@
 use super::env::{
     registry_signing_ed25519_secret, registry_signing_identity, registry_signing_key,
-    registry_trust_ed25519_keys,
+    registry_trust_ed25519_keys, registry_trust_key,
 };
 use super::primitives::{hex_decode, hex_encode, hmac_sha256_hex};
 use super::*;
-use ed25519_dalek::{Signer, SigningKey};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
 
 pub(super) fn sign_registry_release(
     module_path: &str,
     version: &str,
     archive_sum: &str,
 ) -> Result<(Option<String>, Option<String>), String> {
-    let payload = registry_signature_payload(module_path, version, archive_sum);
-    if let Some(secret) = registry_signing_ed25519_secret() {
-        let signing_key = load_ed25519_signing_key(&secret)?;
-        let public_key = hex_encode(signing_key.verifying_key().as_bytes());
-        let signature = signing_key.sign(payload.as_bytes());
-        let sig = format!("ed25519:{public_key}:{}", hex_encode(&signature.to_bytes()));
+    sign_registry_payload(&registry_signature_payload(
+        module_path,
+        version,
+        archive_sum,
+    ))
+}
+
+/// Sign `payload` with the configured ed25519 secret or HMAC key, returning
+/// the signature and signer identity, or `(None, None)` without a key.
+pub(super) fn sign_registry_payload(
+    payload: &str,
+) -> Result<(Option<String>, Option<String>), String> {
+    if let Some(sig) = sign_registry_payload_ed25519(payload)? {
         return Ok((Some(sig), registry_signing_identity()));
     }
     if let Some(key) = registry_signing_key() {
@@ -29,6 +36,21 @@ pub(super) fn sign_registry_release(
     Ok((None, None))
 }
 
+/// Sign `payload` with the configured ed25519 secret only, ignoring any HMAC
+/// key.
+pub(super) fn sign_registry_payload_ed25519(payload: &str) -> Result<Option<String>, String> {
+    let Some(secret) = registry_signing_ed25519_secret() else {
+        return Ok(None);
+    };
+    let signing_key = load_ed25519_signing_key(&secret)?;
+    let public_key = hex_encode(signing_key.verifying_key().as_bytes());
+    let signature = signing_key.sign(payload.as_bytes());
+    Ok(Some(format!(
+        "ed25519:{public_key}:{}",
+        hex_encode(&signature.to_bytes())
+    )))
+}
+
 pub(super) fn verify_registry_signer_policy(
     module_path: &str,
     version: &str,
@@ -92,3 +114,98 @@ pub(super) fn registry_signature_payload(
 ) -> String {
     format!("module={module_path}\nversion={version}\nsum={archive_sum}\n")
 }
+
+/// Sign `approver`'s approval of a release with the configured key.
+pub(super) fn sign_registry_approval(
+    module_path: &str,
+    version: &str,
+    approver: &str,
+) -> Result<Option<String>, String> {
+    let payload = registry_approval_payload(module_path, version, approver);
+    Ok(sign_registry_payload(&payload)?.0)
+}
+
+pub(super) fn registry_approval_payload(
+    module_path: &str,
+    version: &str,
+    approver: &str,
+) -> String {
+    format!("module={module_path}\nversion={version}\napprover={approver}\n")
+}
+
+/// Check `approver`'s signature on a release approval. Owners with a
+/// `key.<owner>` entry must sign with that ed25519 key. The others sign with
+/// the registry HMAC key, which is shared and so only accepted when a single
+/// approval meets the rule.
+pub(super) fn verify_owner_approval(
+    rule: &RegistryOwnerRule,
+    module_path: &str,
+    version: &str,
+    approver: &str,
+    signature: Option<&str>,
+) -> Result<(), String> {
+    let Some(signature) = signature else {
+        return Err(format!(
+            "approval of '{} {}' by '{}' is not signed",
+            module_path, version, approver
+        ));
+    };
+    let payload = registry_approval_payload(module_path, version, approver);
+    let mismatch = || {
+        format!(
+            "approval signature of '{}' for '{} {}' does not verify",
+            approver, module_path, version
+        )
+    };
+    if let Some(owner_key) = rule.owner_keys.get(approver) {
+        let Some((public_key_hex, signature_hex)) = signature
+            .strip_prefix("ed25519:")
+            .and_then(|rest| rest.split_once(':'))
+        else {
+            return Err(format!(
+                "approval of '{} {}' by '{}' must be signed with the owner's ed25519 key",
+                module_path, version, approver
+            ));
+        };
+        if !public_key_hex.eq_ignore_ascii_case(owner_key) {
+            return Err(format!(
+                "approval of '{} {}' by '{}' is signed with key {}, not the owner's key {}",
+                module_path, version, approver, public_key_hex, owner_key
+            ));
+        }
+        let public_key: [u8; 32] = hex_decode(owner_key)?
+            .try_into()
+            .map_err(|_| format!("invalid ed25519 key for owner '{}'", approver))?;
+        let verifying_key = VerifyingKey::from_bytes(&public_key)
+            .map_err(|e| format!("invalid ed25519 key for owner '{}': {}", approver, e))?;
+        let signature: [u8; 64] = hex_decode(signature_hex)?
+            .try_into()
+            .map_err(|_| mismatch())?;
+        return verifying_key
+            .verify(payload.as_bytes(), &Signature::from_bytes(&signature))
+            .map_err(|_| mismatch());
+    }
+    if rule.required_approvals > 1 {
+        return Err(format!(
+            "approval of '{} {}' by '{}' cannot count toward {} owner approvals without the owner's own key; add key.{} to the owners rule",
+            module_path, version, approver, rule.required_approvals, approver
+        ));
+    }
+    let Some(digest) = signature.strip_prefix("hmac-sha256:") else {
+        return Err(format!(
+            "approval of '{} {}' by '{}' must be signed with the registry HMAC key; the owners rule lists no ed25519 key for them",
+            module_path, version, approver
+        ));
+    };
+    let Some(key) = registry_trust_key() else {
+        return Err(format!(
+            "approval of '{} {}' by '{}' uses HMAC signing but RR_REGISTRY_TRUST_KEY is not configured",
+            module_path, version, approver
+        ));
+    };
+    if hmac_sha256_hex(&key, payload.as_bytes())? == digest {
+        Ok(())
+    } else {
+        Err(mismatch())
+    }
+}
@@ -1,20 +1,76 @@
 use super::git::run_git;
 use super::manifest::{escape_toml, parse_toml_string};
 use super::publishing::{append_registry_audit_entry, materialize_registry_write_root};
+use super::signing::verify_owner_approval;
 use super::*;
 
 pub(super) fn parse_registry_trust_policy(content: &str) -> Result<RegistryTrustPolicy, String> {
     let mut policy = RegistryTrustPolicy::default();
+    let mut section = String::new();
     for line in content.lines() {
         let trimmed = line.trim();
         if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with("//") {
             continue;
         }
+        if trimmed == "[[owners]]" {
+            policy.owner_rules.push(RegistryOwnerRule {
+                module: String::new(),
+                owners: Vec::new(),
+                required_approvals: 1,
+                owner_keys: BTreeMap::new(),
+            });
+            section = "owners".to_string();
+            continue;
+        }
+        if let Some(name) = trimmed
+            .strip_prefix('[')
+            .and_then(|rest| rest.strip_suffix(']'))
+        {
+            section = name.trim().to_string();
+            continue;
+        }
         let Some((key, value)) = trimmed.split_once('=') else {
             continue;
         };
         let key = key.trim();
         let value = value.trim();
+        if section == "owners" {
+            let Some(rule) = policy.owner_rules.last_mut() else {
+                continue;
+            };
+            match key {
+                "module" => rule.module = parse_toml_string(value)?,
+                "owners" => rule.owners.extend(parse_policy_string_list(value)?),
+                "required_approvals" => {
+                    rule.required_approvals = value.parse::<usize>().map_err(|_| {
+                        format!(
+                            "required_approvals must be a positive integer, got {}",
+                            value
+                        )
+                    })?;
+                }
+                _ if key.starts_with("key.") => {
+                    rule.owner_keys.insert(
+                        key["key.".len()..].to_string(),
+                        parse_toml_string(value)?.to_ascii_lowercase(),
+                    );
+                }
+                _ => {}
+            }
+            continue;
+        }
+        if section == "license" {
+            match key {
+                "allow" => policy
+                    .allowed_licenses
+                    .extend(parse_policy_string_list(value)?),
+                "deny" => policy
+                    .denied_licenses
+                    .extend(parse_policy_string_list(value)?),
+                _ => {}
+            }
+            continue;
+        }
         match key {
             "version" => {}
             "require_signed" => policy.require_signed = value == "true",
@@ -30,9 +86,106 @@ pub(super) fn parse_registry_trust_policy(content: &str) -> Result<RegistryTrust
             _ => {}
         }
     }
+    policy.owner_rules.retain(|rule| !rule.module.is_empty());
+    for rule in &policy.owner_rules {
+        validate_owner_rule(rule)?;
+    }
     Ok(policy)
 }
 
+/// A threshold of zero would approve every release, and one above the owner
+/// count could never be met.
+fn validate_owner_rule(rule: &RegistryOwnerRule) -> Result<(), String> {
+    if rule.required_approvals == 0 {
+        return Err(format!(
+            "owners rule for {} requires 0 approvals",
+            rule.module
+        ));
+    }
+    let mut owners = rule.owners.iter().collect::<Vec<_>>();
+    owners.sort();
+    owners.dedup();
+    if rule.required_approvals > owners.len() {
+        return Err(format!(
+            "owners rule for {} requires {} approvals but lists {} owner(s)",
+            rule.module,
+            rule.required_approvals,
+            owners.len()
+        ));
+    }
+    Ok(())
+}
+
+/// The `[[owners]]` rule covering `module_path`: an exact module match, else
+/// the `*` prefix rule with the longest prefix.
+pub(super) fn registry_owner_rule<'a>(
+    policy: &'a RegistryTrustPolicy,
+    module_path: &str,
+) -> Option<&'a RegistryOwnerRule> {
+    if let Some(rule) = policy
+        .owner_rules
+        .iter()
+        .find(|rule| rule.module == module_path)
+    {
+        return Some(rule);
+    }
+    policy
+        .owner_rules
+        .iter()
+        .filter_map(|rule| {
+            let prefix = rule.module.strip_suffix('*')?;
+            module_path
+                .starts_with(prefix)
+                .then_some((prefix.len(), rule))
+        })
+        .max_by_key(|(len, _)| *len)
+        .map(|(_, rule)| rule)
+}
+
+/// Distinct owners among a release's recorded approvals whose approval
+/// signature verifies.
+pub(super) fn owner_approval_count(
+    rule: &RegistryOwnerRule,
+    module_path: &str,
+    version: &str,
+    approvals: &[String],
+    approval_sigs: &BTreeMap<String, String>,
+) -> usize {
+    let mut counted = approvals
+        .iter()
+        .filter(|approver| rule.owners.contains(approver))
+        .filter(|approver| {
+            verify_owner_approval(
+                rule,
+                module_path,
+                version,
+                approver,
+                approval_sigs.get(approver.as_str()).map(String::as_str),
+            )
+            .is_ok()
+        })
+        .collect::<Vec<_>>();
+    counted.sort();
+    counted.dedup();
+    counted.len()
+}
+
+/// Accepts either one quoted string or a single-line array of them.
+fn parse_policy_string_list(value: &str) -> Result<Vec<String>, String> {
+    let Some(items) = value
+        .strip_prefix('[')
+        .and_then(|rest| rest.strip_suffix(']'))
+    else {
+        return Ok(vec![parse_toml_string(value)?]);
+    };
+    items
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(parse_toml_string)
+        .collect()
+}
+
 fn render_registry_trust_policy(policy: &RegistryTrustPolicy) -> String {
     let mut out = String::new();
     out.push_str("version = 1\n");
@@ -84,9 +237,252 @@ fn render_registry_trust_policy(policy: &RegistryTrustPolicy) -> String {
         ));
     }
 
+    for rule in &policy.owner_rules {
+        out.push_str("\n[[owners]]\n");
+        out.push_str(&format!("module = \"{}\"\n", escape_toml(&rule.module)));
+        let mut owners = rule.owners.clone();
+        owners.sort();
+        owners.dedup();
+        let owners = owners
+            .iter()
+            .map(|owner| format!("\"{}\"", escape_toml(owner)))
+            .collect::<Vec<_>>();
+        out.push_str(&format!("owners = [{}]\n", owners.join(", ")));
+        out.push_str(&format!(
+            "required_approvals = {}\n",
+            rule.required_approvals
+        ));
+        for (owner, key) in &rule.owner_keys {
+            out.push_str(&format!("key.{} = \"{}\"\n", owner, escape_toml(key)));
+        }
+    }
+
+    let mut allowed = policy.allowed_licenses.clone();
+    allowed.sort();
+    allowed.dedup();
+    let mut denied = policy.denied_licenses.clone();
+    denied.sort();
+    denied.dedup();
+    if !allowed.is_empty() || !denied.is_empty() {
+        out.push_str("\n[license]\n");
+        for license in allowed {
+            out.push_str(&format!("allow = \"{}\"\n", escape_toml(&license)));
+        }
+        for license in denied {
+            out.push_str(&format!("deny = \"{}\"\n", escape_toml(&license)));
+        }
+    }
+
     out
 }
 
+/// Recount a release's owner approvals from their signatures before it is
+/// installed, so an `approved = true` or `approved_by` line edited into the
+/// index does not stand in for an owner's signed approval.
+pub(in crate::pkg) fn verify_registry_owner_approvals(
+    module_path: &str,
+    entry: &RegistryEntry,
+    policy: &RegistryTrustPolicy,
+) -> Result<(), String> {
+    let Some(rule) = registry_owner_rule(policy, module_path) else {
+        return Ok(());
+    };
+    let count = owner_approval_count(
+        rule,
+        module_path,
+        &entry.version,
+        &entry.approvals,
+        &entry.approval_sigs,
+    );
+    if count >= rule.required_approvals {
+        return Ok(());
+    }
+    Err(format!(
+        "registry release '{} {}' has {}/{} verified owner approvals",
+        module_path, entry.version, count, rule.required_approvals
+    ))
+}
+
+/// Checks a release's declared SPDX license expression against the `[license]`
+/// section. Any denied identifier rejects the release; with an allow list, the
+/// expression must be satisfiable from allowed identifiers alone (`OR` needs
+/// one side, `AND` both). An expression that does not parse is rejected.
+pub(in crate::pkg) fn verify_registry_license_policy(
+    module_path: &str,
+    version: &str,
+    license: Option<&str>,
+    policy: &RegistryTrustPolicy,
+) -> Result<(), String> {
+    if policy.allowed_licenses.is_empty() && policy.denied_licenses.is_empty() {
+        return Ok(());
+    }
+    let Some(license) = license.map(str::trim).filter(|value| !value.is_empty()) else {
+        if policy.allowed_licenses.is_empty() {
+            return Ok(());
+        }
+        return Err(format!(
+            "registry policy requires an allowed license for '{} {}', but none is declared",
+            module_path, version
+        ));
+    };
+    let listed =
+        |list: &[String], id: &str| list.iter().any(|entry| entry.eq_ignore_ascii_case(id));
+
+    let expression = parse_license_expression(license)
+        .map_err(|e| format!("{} for '{} {}'", e, module_path, version))?;
+    if expression.any_id(&|id| listed(&policy.denied_licenses, id)) {
+        return Err(format!(
+            "license '{}' of '{} {}' is denied by registry policy",
+            license, module_path, version
+        ));
+    }
+    if !policy.allowed_licenses.is_empty()
+        && !expression.satisfied_by(&|id| listed(&policy.allowed_licenses, id))
+    {
+        return Err(format!(
+            "license '{}' of '{} {}' is not allowed by registry policy",
+            license, module_path, version
+        ));
+    }
+    Ok(())
+}
+
+/// Deepest parenthesis nesting accepted in a license expression.
+const MAX_LICENSE_NESTING: usize = 32;
+
+/// A parsed SPDX license expression. `X WITH exception` is kept as `Id(X)`.
+enum LicenseExpression {
+    Id(String),
+    And(Vec<LicenseExpression>),
+    Or(Vec<LicenseExpression>),
+}
+
+impl LicenseExpression {
+    fn any_id(&self, pred: &dyn Fn(&str) -> bool) -> bool {
+        match self {
+            Self::Id(id) => pred(id),
+            Self::And(terms) | Self::Or(terms) => terms.iter().any(|term| term.any_id(pred)),
+        }
+    }
+
+    /// Whether the licensee can pick alternatives so that every license id
+    /// they end up bound by satisfies `allowed`.
+    fn satisfied_by(&self, allowed: &dyn Fn(&str) -> bool) -> bool {
+        match self {
+            Self::Id(id) => allowed(id),
+            Self::And(terms) => terms.iter().all(|term| term.satisfied_by(allowed)),
+            Self::Or(terms) => terms.iter().any(|term| term.satisfied_by(allowed)),
+        }
+    }
+}
+
+/// Parses an SPDX expression. `AND` binds tighter than `OR` and parentheses
+/// group, so `MIT AND (Apache-2.0 OR GPL-3.0)` needs `MIT` and one of the
+/// other two.
+fn parse_license_expression(expression: &str) -> Result<LicenseExpression, String> {
+    let spaced = expression.replace('(', " ( ").replace(')', " ) ");
+    let mut parser = LicenseParser {
+        tokens: spaced.split_whitespace().collect(),
+        pos: 0,
+        depth: 0,
+        expression,
+    };
+    let parsed = parser.or()?;
+    if let Some(token) = parser.tokens.get(parser.pos) {
+        return Err(parser.error(&format!("unexpected '{}'", token)));
+    }
+    Ok(parsed)
+}
+
+struct LicenseParser<'a> {
+    tokens: Vec<&'a str>,
+    pos: usize,
+    depth: usize,
+    expression: &'a str,
+}
+
+impl LicenseParser<'_> {
+    fn error(&self, message: &str) -> String {
+        format!(
+            "invalid license expression '{}': {}",
+            self.expression, message
+        )
+    }
+
+    fn eat_operator(&mut self, op: &str) -> bool {
+        let matched = self
+            .tokens
+            .get(self.pos)
+            .is_some_and(|token| is_license_operator(token, op));
+        if matched {
+            self.pos += 1;
+        }
+        matched
+    }
+
+    fn or(&mut self) -> Result<LicenseExpression, String> {
+        let mut terms = vec![self.and()?];
+        while self.eat_operator("OR") {
+            terms.push(self.and()?);
+        }
+        Ok(if terms.len() == 1 {
+            terms.remove(0)
+        } else {
+            LicenseExpression::Or(terms)
+        })
+    }
+
+    fn and(&mut self) -> Result<LicenseExpression, String> {
+        let mut terms = vec![self.term()?];
+        while self.eat_operator("AND") {
+            terms.push(self.term()?);
+        }
+        Ok(if terms.len() == 1 {
+            terms.remove(0)
+        } else {
+            LicenseExpression::And(terms)
+        })
+    }
+
+    fn term(&mut self) -> Result<LicenseExpression, String> {
+        let Some(&token) = self.tokens.get(self.pos) else {
+            return Err(self.error("expected a license id"));
+        };
+        self.pos += 1;
+        if token == "(" {
+            if self.depth == MAX_LICENSE_NESTING {
+                return Err(self.error("parentheses nest too deeply"));
+            }
+            self.depth += 1;
+            let inner = self.or()?;
+            self.depth -= 1;
+            if self.tokens.get(self.pos) != Some(&")") {
+                return Err(self.error("missing ')'"));
+            }
+            self.pos += 1;
+            return Ok(inner);
+        }
+        if token == ")"
+            || ["AND", "OR", "WITH"]
+                .iter()
+                .any(|op| is_license_operator(token, op))
+        {
+            return Err(self.error(&format!("expected a license id, found '{}'", token)));
+        }
+        if self.eat_operator("WITH") {
+            match self.tokens.get(self.pos) {
+                Some(&exception) if !["(", ")"].contains(&exception) => self.pos += 1,
+                _ => return Err(self.error("expected an exception after WITH")),
+            }
+        }
+        Ok(LicenseExpression::Id(token.to_string()))
+    }
+}
+
+fn is_license_operator(token: &str, op: &str) -> bool {
+    token == op || token == op.to_ascii_lowercase()
+}
+
 pub(super) fn mutate_registry_policy(
     registry_spec: &Path,
     commit_message: &str,
@@ -104,6 +500,9 @@ pub(super) fn mutate_registry_policy(
     };
 
     update(&mut policy)?;
+    for rule in &policy.owner_rules {
+        validate_owner_rule(rule)?;
+    }
     fs::write(&policy_path, render_registry_trust_policy(&policy))
         .map_err(|e| format!("failed to write '{}': {}", policy_path.display(), e))?;
     append_registry_audit_entry(
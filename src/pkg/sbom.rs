@@ -0,0 +1,322 @@
+use super::project::{
+    display_locked_node, load_project_graph, lock_map, source_root_for_locked_module,
+};
+use super::util::{github_repo_root, stable_hash_update};
+use super::*;
+use serde_json::{Value, json};
+
+struct SbomComponent {
+    module: InstalledModule,
+    license: Option<String>,
+    homepage: Option<String>,
+    signer: Option<String>,
+    signature_scheme: Option<String>,
+    depends_on: Vec<String>,
+}
+
+/// Renders the `rr.lock` graph of a project as a CycloneDX 1.5 or SPDX 2.3
+/// JSON document.
+pub fn project_sbom(project_root: &Path, format: &str) -> Result<String, String> {
+    if !matches!(format, "cyclonedx" | "spdx") {
+        return Err(format!(
+            "unsupported SBOM format '{}'; expected 'cyclonedx' or 'spdx'",
+            format
+        ));
+    }
+    let manifest = Manifest::load_from_dir(project_root)?;
+    let (manifest, locked, graph) = load_project_graph(project_root, &manifest)?;
+    let locked_map = lock_map(&locked);
+    let registry = if locked.iter().any(|entry| entry.commit == "registry") {
+        registry_root()?
+    } else {
+        None
+    };
+
+    let mut components = Vec::new();
+    for entry in locked {
+        let source_root = source_root_for_locked_module(project_root, &manifest, &entry);
+        let dep_manifest = Manifest::load_from_dir(&source_root)?;
+        let release = match &registry {
+            Some(root) if entry.commit == "registry" => load_registry_index(root, &entry.path)?
+                .releases
+                .into_iter()
+                .find(|release| release.version == entry.version),
+            _ => None,
+        };
+        let depends_on = graph
+            .get(&entry.path)
+            .into_iter()
+            .flatten()
+            .map(|dep| display_locked_node(dep, &locked_map))
+            .collect();
+        components.push(SbomComponent {
+            module: entry,
+            license: dep_manifest.license,
+            homepage: dep_manifest.homepage,
+            signer: release.as_ref().and_then(|release| release.signer.clone()),
+            signature_scheme: release
+                .and_then(|release| release.archive_sig)
+                .and_then(|sig| sig.split(':').next().map(ToOwned::to_owned)),
+            depends_on,
+        });
+    }
+    components.sort_by(|a, b| a.module.path.cmp(&b.module.path));
+    let root_deps: Vec<String> = manifest
+        .requires
+        .keys()
+        .map(|dep| display_locked_node(dep, &locked_map))
+        .collect();
+
+    let created = sbom_timestamp()?;
+    let document = if format == "cyclonedx" {
+        cyclonedx_document(&manifest, &root_deps, &components, &created)
+    } else {
+        spdx_document(&manifest, &root_deps, &components, &created)
+    };
+    serde_json::to_string_pretty(&document)
+        .map(|rendered| rendered + "\n")
+        .map_err(|e| format!("failed to render SBOM: {}", e))
+}
+
+fn cyclonedx_document(
+    manifest: &Manifest,
+    root_deps: &[String],
+    components: &[SbomComponent],
+    created: &str,
+) -> Value {
+    let mut root = json!({
+        "type": "application",
+        "bom-ref": manifest.module_path,
+        "name": manifest.module_path,
+    });
+    if let Some(license) = &manifest.license {
+        root["licenses"] = cyclonedx_licenses(license);
+    }
+
+    let mut dependencies = vec![json!({ "ref": manifest.module_path, "dependsOn": root_deps })];
+    let mut rendered = Vec::new();
+    for component in components {
+        let module = &component.module;
+        let bom_ref = format!("{}@{}", module.path, module.version);
+        let mut properties = vec![
+            json!({ "name": "rr:sum", "value": module.sum }),
+            json!({ "name": "rr:commit", "value": module.commit }),
+            json!({ "name": "rr:direct", "value": module.direct.to_string() }),
+        ];
+        if let Some(signer) = &component.signer {
+            properties.push(json!({ "name": "rr:signer", "value": signer }));
+        }
+        if let Some(scheme) = &component.signature_scheme {
+            properties.push(json!({ "name": "rr:signature", "value": scheme }));
+        }
+        let mut external = Vec::new();
+        if let Some(homepage) = &component.homepage {
+            external.push(json!({ "type": "website", "url": homepage }));
+        }
+        if let Some(vcs) = vcs_url(module) {
+            external.push(json!({ "type": "vcs", "url": vcs }));
+        }
+
+        let mut entry = json!({
+            "type": "library",
+            "bom-ref": bom_ref,
+            "name": module.path,
+            "version": module.version,
+            "properties": properties,
+        });
+        if let Some(license) = &component.license {
+            entry["licenses"] = cyclonedx_licenses(license);
+        }
+        if !external.is_empty() {
+            entry["externalReferences"] = Value::Array(external);
+        }
+        rendered.push(entry);
+        dependencies.push(json!({ "ref": bom_ref, "dependsOn": component.depends_on }));
+    }
+
+    json!({
+        "bomFormat": "CycloneDX",
+        "specVersion": "1.5",
+        "version": 1,
+        "metadata": {
+            "timestamp": created,
+            "tools": {
+                "components": [{
+                    "type": "application",
+                    "name": "RR",
+                    "version": env!("CARGO_PKG_VERSION"),
+                }],
+            },
+            "component": root,
+        },
+        "components": rendered,
+        "dependencies": dependencies,
+    })
+}
+
+fn cyclonedx_licenses(license: &str) -> Value {
+    let compound = license
+        .split_whitespace()
+        .any(|token| matches!(token, "AND" | "OR" | "WITH"));
+    if compound {
+        json!([{ "expression": license }])
+    } else {
+        json!([{ "license": { "id": license } }])
+    }
+}
+
+fn spdx_document(
+    manifest: &Manifest,
+    root_deps: &[String],
+    components: &[SbomComponent],
+    created: &str,
+) -> Value {
+    let root_id = spdx_id(&manifest.module_path);
+    let mut root = json!({
+        "SPDXID": root_id,
+        "name": manifest.module_path,
+        "downloadLocation": "NOASSERTION",
+        "filesAnalyzed": false,
+        "licenseConcluded": "NOASSERTION",
+        "licenseDeclared": manifest.license.as_deref().unwrap_or("NOASSERTION"),
+        "copyrightText": "NOASSERTION",
+    });
+    if let Some(homepage) = &manifest.homepage {
+        root["homepage"] = json!(homepage);
+    }
+
+    let mut packages = vec![root];
+    let mut relationships = vec![json!({
+        "spdxElementId": "SPDXRef-DOCUMENT",
+        "relationshipType": "DESCRIBES",
+        "relatedSpdxElement": root_id,
+    })];
+    for dep in root_deps {
+        relationships.push(json!({
+            "spdxElementId": root_id,
+            "relationshipType": "DEPENDS_ON",
+            "relatedSpdxElement": spdx_id(dep),
+        }));
+    }
+
+    let mut namespace_hash =
+        stable_hash_update(0xcbf29ce484222325_u64, manifest.module_path.as_bytes());
+    for component in components {
+        let module = &component.module;
+        let id = spdx_id(&format!("{}@{}", module.path, module.version));
+        namespace_hash = stable_hash_update(namespace_hash, module.sum.as_bytes());
+
+        let mut comment = format!("rr.lock sum {}", module.sum);
+        if let Some(signer) = &component.signer {
+            comment.push_str(&format!("; registry signer {}", signer));
+        }
+        if let Some(scheme) = &component.signature_scheme {
+            comment.push_str(&format!("; signature {}", scheme));
+        }
+        let mut package = json!({
+            "SPDXID": id,
+            "name": module.path,
+            "versionInfo": module.version,
+            "downloadLocation": vcs_url(module)
+                .map(|url| format!("git+{}@{}", url, module.commit))
+                .unwrap_or_else(|| "NOASSERTION".to_string()),
+            "filesAnalyzed": false,
+            "licenseConcluded": "NOASSERTION",
+            "licenseDeclared": component.license.as_deref().unwrap_or("NOASSERTION"),
+            "copyrightText": "NOASSERTION",
+            "sourceInfo": format!("commit {}", module.commit),
+            "comment": comment,
+        });
+        if let Some(homepage) = &component.homepage {
+            package["homepage"] = json!(homepage);
+        }
+        packages.push(package);
+        for dep in &component.depends_on {
+            relationships.push(json!({
+                "spdxElementId": id,
+                "relationshipType": "DEPENDS_ON",
+                "relatedSpdxElement": spdx_id(dep),
+            }));
+        }
+    }
+
+    json!({
+        "spdxVersion": "SPDX-2.3",
+        "dataLicense": "CC0-1.0",
+        "SPDXID": "SPDXRef-DOCUMENT",
+        "name": manifest.module_path,
+        "documentNamespace": format!(
+            "https://spdx.org/spdxdocs/{}-{:016x}",
+            spdx_id(&manifest.module_path).trim_start_matches("SPDXRef-"),
+            namespace_hash
+        ),
+        "creationInfo": {
+            "created": created,
+            "creators": [format!("Tool: RR-{}", env!("CARGO_PKG_VERSION"))],
+        },
+        "packages": packages,
+        "relationships": relationships,
+    })
+}
+
+/// SPDX identifiers only allow letters, digits, `.` and `-`.
+fn spdx_id(node: &str) -> String {
+    let sanitized: String = node
+        .chars()
+        .map(|ch| {
+            if ch.is_ascii_alphanumeric() || ch == '.' || ch == '-' {
+                ch
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    format!("SPDXRef-{}", sanitized)
+}
+
+fn vcs_url(module: &InstalledModule) -> Option<String> {
+    (module.commit != "registry" && module.path.starts_with("github.com/"))
+        .then(|| format!("https://github.com/{}", github_repo_root(&module.path)))
+}
+
+/// Document creation time; `SOURCE_DATE_EPOCH` pins it so two runs over the
+/// same lockfile produce byte-identical documents.
+fn sbom_timestamp() -> Result<String, String> {
+    let secs = match std::env::var("SOURCE_DATE_EPOCH") {
+        Ok(raw) => raw.trim().parse::<u64>().map_err(|_| {
+            format!(
+                "SOURCE_DATE_EPOCH must be a non-negative integer number of seconds, got '{}'",
+                raw
+            )
+        })?,
+        Err(_) => SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0),
+    };
+    Ok(utc_timestamp(secs))
+}
+
+fn utc_timestamp(secs: u64) -> String {
+    let days = (secs / 86_400) as i64;
+    let rem = secs % 86_400;
+    // Civil-from-days over the proleptic Gregorian calendar.
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        rem / 3_600,
+        rem % 3_600 / 60,
+        rem % 60
+    )
+}
@@ -25,6 +25,33 @@ require (
     );
 }
 
+#[test]
+fn parses_and_renders_manifest_lint_levels() {
+    let manifest = Manifest::parse(
+        r#"
+module github.com/acme/app
+
+lint unused_import allow
+lint (
+    na_comparison deny
+    shadowed_binding warn
+)
+"#,
+    )
+    .expect("manifest should parse");
+    assert_eq!(manifest.lints.len(), 3);
+    assert_eq!(
+        manifest.lints.get("na_comparison"),
+        Some(&"deny".to_string())
+    );
+    let reparsed = Manifest::parse(&manifest.render()).expect("rendered manifest should parse");
+    assert_eq!(reparsed.lints, manifest.lints);
+
+    let err = Manifest::parse("module github.com/acme/app\nlint unused_import loud\n")
+        .expect_err("bad level should be rejected");
+    assert!(err.contains("expected allow, warn or deny"), "{err}");
+}
+
 #[test]
 fn compares_semver_tags_in_numeric_order() {
     assert_eq!(compare_versions("v1.10.0", "v1.2.0"), Ordering::Greater);
@@ -105,3 +132,318 @@ fn plus_two(x) {
         "compiled artifact should include imported subpackage logic"
     );
 }
+
+#[test]
+fn parses_cache_ages_with_units() {
+    assert_eq!(
+        parse_cache_age("30d").unwrap(),
+        std::time::Duration::from_secs(30 * 86_400)
+    );
+    assert_eq!(
+        parse_cache_age("12h").unwrap(),
+        std::time::Duration::from_secs(12 * 3_600)
+    );
+    assert_eq!(
+        parse_cache_age("2w").unwrap(),
+        std::time::Duration::from_secs(14 * 86_400)
+    );
+    assert!(parse_cache_age("soon").is_err());
+    assert!(parse_cache_age("3y").is_err());
+}
+
+#[test]
+fn cache_clean_respects_last_use_age() {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let home = root
+        .join("target")
+        .join("tests")
+        .join("pkg_cache")
+        .join(format!("home_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&home);
+    for (path, version) in [
+        ("github.com/acme/math", "v1.0.0"),
+        ("github.com/acme/math", "v1.1.0"),
+        ("rr.local/plot", "v0.2.0"),
+    ] {
+        let dir = module_cache_dir(&home, path, version);
+        fs::create_dir_all(&dir).expect("failed to create cache dir");
+        fs::write(dir.join("rr.mod"), format!("module {path}\n")).expect("failed to write rr.mod");
+        let sum = directory_checksum(&dir).expect("checksum");
+        write_meta_file(&dir, "abc123", &sum).expect("failed to write meta");
+    }
+
+    let entries = cache::list_cache_entries(&home).expect("list cache");
+    let labels: Vec<String> = entries
+        .iter()
+        .map(|entry| format!("{}@{}", entry.name, entry.version.as_deref().unwrap_or("")))
+        .collect();
+    assert_eq!(
+        labels,
+        vec![
+            "github.com/acme/math@v1.0.0",
+            "github.com/acme/math@v1.1.0",
+            "rr.local/plot@v0.2.0",
+        ]
+    );
+    assert_eq!(
+        cache::highest_cached_version(&home, "github.com/acme/math").unwrap(),
+        Some("v1.1.0".to_string())
+    );
+
+    let plot = module_cache_dir(&home, "rr.local/plot", "v0.2.0");
+    fs::write(plot.join("extra.rr"), "fn x() {}\n").expect("failed to tamper");
+    let report = cache::verify_cache_entries(&home).expect("verify cache");
+    assert_eq!(report.checked, 3);
+    assert_eq!(report.issues.len(), 1);
+    assert!(report.issues[0].message.contains("checksum mismatch"));
+
+    let day = std::time::Duration::from_secs(86_400);
+    let now = SystemTime::now();
+    let fresh = cache::clean_cache_entries(&home, Some(day), now).expect("clean fresh");
+    assert!(fresh.removed.is_empty());
+    assert_eq!(fresh.kept, 3);
+
+    let later = now + day * 2;
+    let stale = cache::clean_cache_entries(&home, Some(day), later).expect("clean stale");
+    assert_eq!(stale.removed.len(), 3);
+    assert!(stale.freed_bytes > 0);
+    assert!(!home.join("pkg").join("mod").join("rr.local").exists());
+    let _ = fs::remove_dir_all(&home);
+}
+
+#[test]
+fn license_policy_checks_spdx_alternatives() {
+    let policy = RegistryTrustPolicy {
+        allowed_licenses: vec!["MIT".to_string(), "Apache-2.0".to_string()],
+        denied_licenses: vec!["GPL-3.0-only".to_string()],
+        ..RegistryTrustPolicy::default()
+    };
+    let check = |license: Option<&str>| {
+        verify_registry_license_policy("rr.local/lib", "v1.0.0", license, &policy)
+    };
+    assert!(check(Some("MIT")).is_ok());
+    assert!(check(Some("mit")).is_ok());
+    assert!(check(Some("(MIT OR BSD-3-Clause)")).is_ok());
+    assert!(check(Some("MIT AND Apache-2.0")).is_ok());
+    assert!(check(Some("Apache-2.0 WITH LLVM-exception")).is_ok());
+    assert!(
+        check(Some("MIT AND BSD-3-Clause"))
+            .unwrap_err()
+            .contains("is not allowed")
+    );
+    assert!(
+        check(Some("MIT OR GPL-3.0-only"))
+            .unwrap_err()
+            .contains("is denied")
+    );
+    assert!(check(None).unwrap_err().contains("none is declared"));
+
+    // AND binds tighter than OR, and parentheses group.
+    let gpl = RegistryTrustPolicy {
+        allowed_licenses: vec!["GPL-3.0".to_string()],
+        ..RegistryTrustPolicy::default()
+    };
+    let check_gpl = |license: &str| {
+        verify_registry_license_policy("rr.local/lib", "v1.0.0", Some(license), &gpl)
+    };
+    assert!(
+        check_gpl("MIT AND (Apache-2.0 OR GPL-3.0)")
+            .unwrap_err()
+            .contains("is not allowed")
+    );
+    assert!(check_gpl("MIT AND Apache-2.0 OR GPL-3.0").is_ok());
+    assert!(check_gpl("(MIT OR (BSD-2-Clause AND GPL-3.0)) AND GPL-3.0").is_err());
+    assert!(check_gpl("((GPL-3.0 WITH Classpath-exception-2.0))").is_ok());
+    assert!(
+        check(Some(
+            "(MIT OR (Apache-2.0 AND BSD-3-Clause)) AND Apache-2.0"
+        ))
+        .is_ok()
+    );
+    assert!(check(Some("MIT AND (Apache-2.0 OR BSD-3-Clause)")).is_ok());
+    assert!(
+        check(Some(
+            "(MIT AND BSD-3-Clause) OR (Apache-2.0 AND BSD-3-Clause)"
+        ))
+        .unwrap_err()
+        .contains("is not allowed")
+    );
+    // Many OR clauses joined by AND are evaluated, not expanded into every
+    // combination of alternatives.
+    let wide = vec!["(GPL-3.0 OR MIT)"; 40].join(" AND ");
+    assert!(check_gpl(&wide).is_ok());
+    assert!(
+        check_gpl(&format!("{wide} AND Apache-2.0"))
+            .unwrap_err()
+            .contains("is not allowed")
+    );
+    let deep = format!("{}MIT{}", "(".repeat(200), ")".repeat(200));
+    assert!(check(Some(&deep)).unwrap_err().contains("nest too deeply"));
+
+    for malformed in [
+        "(MIT OR Apache-2.0",
+        "MIT OR",
+        "MIT Apache-2.0",
+        "MIT WITH",
+        "()",
+    ] {
+        assert!(
+            check(Some(malformed))
+                .unwrap_err()
+                .contains("invalid license expression"),
+            "{malformed}"
+        );
+    }
+
+    let deny_only = RegistryTrustPolicy {
+        denied_licenses: vec!["AGPL-3.0-only".to_string()],
+        ..RegistryTrustPolicy::default()
+    };
+    assert!(verify_registry_license_policy("m", "v1", None, &deny_only).is_ok());
+    assert!(verify_registry_license_policy("m", "v1", Some("BSD-2-Clause"), &deny_only).is_ok());
+}
+
+#[test]
+fn api_diff_classifies_signature_changes() {
+    let surface = |source: &str| {
+        let mut files = BTreeMap::new();
+        files.insert("src/lib.rr".to_string(), source.as_bytes().to_vec());
+        api::ApiSurface::from_files(&files).expect("API should be readable")
+    };
+    let old = surface(
+        r#"
+export fn fit(x: float, w = 1.0) -> float {
+  return x * w
+}
+
+export trait Model {
+  fn predict(self: Self, x: float) -> float
+}
+
+export impl Model for Linear as s3 {
+  fn predict(self: Linear, x: float) -> float {
+    return x
+  }
+}
+
+fn hidden(x) {
+  return x
+}
+"#,
+    );
+    let new = surface(
+        r#"
+export fn fit(w = 1.0, x, tol = 0.1) -> float {
+  return x * w
+}
+
+export trait Model {
+  fn predict(self: Self, x: float) -> float
+  fn score(self: Self) -> float
+}
+
+export impl Model for Linear {
+  fn predict(self: Linear, x: float) -> float {
+    return x
+  }
+  fn score(self: Linear) -> float {
+    return 0.0
+  }
+}
+"#,
+    );
+    let changes = api::diff_api(&old, &new)
+        .into_iter()
+        .map(|change| {
+            format!(
+                "{} {}: {}",
+                change.kind.as_str(),
+                change.item,
+                change.detail
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        changes,
+        vec![
+            "narrowed fn fit: parameter `x` moved from position 1 to 2",
+            "widened fn fit: parameter `x` no longer requires float",
+            "narrowed fn fit: parameter `w` moved from position 2 to 1",
+            "widened fn fit: optional parameter `tol` added",
+            "removed impl Model for Linear: no longer exported as S3 methods",
+            "narrowed trait method Model.score: added without a default body; existing impls must provide it",
+        ]
+    );
+    assert!(util::is_major_bump("v0.1.0", "v0.2.0"));
+    assert!(util::is_major_bump("v1.4.2", "v2.0.0"));
+    assert!(!util::is_major_bump("v1.0.0", "v1.1.0"));
+}
+
+#[test]
+fn api_diff_keys_functions_by_exported_name() {
+    let surface = |sources: &[(&str, &str)]| {
+        let files = sources
+            .iter()
+            .map(|(rel, source)| (rel.to_string(), source.as_bytes().to_vec()))
+            .collect::<BTreeMap<_, _>>();
+        api::ApiSurface::from_files(&files).expect("API should be readable")
+    };
+    let old = surface(&[(
+        "src/lib.rr",
+        r#"
+export { solve, Body } from "./inner.rr"
+
+export fn fit(x: float) -> float {
+  return x
+}
+"#,
+    )]);
+    let new = surface(&[
+        (
+            "src/lib.rr",
+            r#"
+export { solve } from "./inner.rr"
+export { fit } from "./fit.rr"
+"#,
+        ),
+        (
+            "src/fit.rr",
+            r#"
+export fn fit(x: float) -> float {
+  return x
+}
+"#,
+        ),
+    ]);
+    let changes = api::diff_api(&old, &new)
+        .into_iter()
+        .map(|change| {
+            format!(
+                "{} {} in {}",
+                change.kind.as_str(),
+                change.item,
+                change.file
+            )
+        })
+        .collect::<Vec<_>>();
+    // Moving `fit` behind a re-export keeps its name; dropping the `Body`
+    // re-export removes a name callers could import.
+    assert_eq!(changes, vec!["removed fn Body in src/lib.rr"]);
+}
+
+#[test]
+fn advisory_ranges_match_semver_constraints() {
+    assert_eq!(
+        util::version_in_range("v1.1.5", ">=v1.0.0, <v1.2.0"),
+        Ok(true)
+    );
+    assert_eq!(
+        util::version_in_range("v1.2.0", ">=v1.0.0, <v1.2.0"),
+        Ok(false)
+    );
+    assert_eq!(util::version_in_range("v1.10.0", ">v1.9.0"), Ok(true));
+    assert_eq!(util::version_in_range("v2.0.0", "v2.0.0"), Ok(true));
+    assert_eq!(util::version_in_range("v0.1.0", "*"), Ok(true));
+    let err = util::version_in_range("v1.0.0", "<1.2").expect_err("bad bound");
+    assert!(err.contains("'1.2' is not a version"), "{err}");
+}
@@ -10,6 +10,7 @@ pub struct Manifest {
     pub homepage: Option<String>,
     pub requires: BTreeMap<String, String>,
     pub replaces: BTreeMap<String, String>,
+    pub lints: BTreeMap<String, String>,
 }
 
 #[derive(Clone, Debug)]
@@ -42,6 +43,35 @@ pub struct VerifyReport {
     pub mismatches: Vec<VerifyMismatch>,
 }
 
+#[derive(Clone, Debug)]
+pub struct CacheEntry {
+    pub kind: String,
+    pub name: String,
+    pub version: Option<String>,
+    pub root: PathBuf,
+    pub size_bytes: u64,
+    pub last_used_secs: Option<u64>,
+}
+
+#[derive(Clone, Debug)]
+pub struct CacheVerifyIssue {
+    pub entry: CacheEntry,
+    pub message: String,
+}
+
+#[derive(Clone, Debug)]
+pub struct CacheVerifyReport {
+    pub checked: usize,
+    pub issues: Vec<CacheVerifyIssue>,
+}
+
+#[derive(Clone, Debug)]
+pub struct CacheCleanReport {
+    pub removed: Vec<CacheEntry>,
+    pub kept: usize,
+    pub freed_bytes: u64,
+}
+
 #[derive(Clone, Debug)]
 pub struct OutdatedDependency {
     pub path: String,
@@ -66,6 +96,8 @@ pub struct PublishOptions {
     pub push_tag: bool,
     pub remote: Option<String>,
     pub registry: Option<PathBuf>,
+    /// Publish a minor or patch release even if it breaks the exported API.
+    pub allow_breaking: bool,
 }
 
 #[derive(Clone, Debug)]
@@ -102,6 +134,7 @@ pub struct RegistryReleaseInfo {
     pub signed: bool,
     pub signer: Option<String>,
     pub signature_scheme: Option<String>,
+    pub approvals: Vec<String>,
 }
 
 #[derive(Clone, Debug)]
@@ -137,6 +170,9 @@ pub struct RegistryPolicyLintReport {
     pub revoked_count: usize,
     pub allowed_signer_count: usize,
     pub auto_approve_signer_count: usize,
+    pub allowed_license_count: usize,
+    pub denied_license_count: usize,
+    pub owner_rule_count: usize,
     pub warnings: Vec<String>,
     pub errors: Vec<String>,
 }
@@ -148,6 +184,48 @@ pub struct RegistryQueueItem {
     pub yanked: bool,
     pub signed: bool,
     pub signer: Option<String>,
+    pub approvals: Vec<String>,
+    /// Distinct owners whose approval signature verifies; only meaningful
+    /// with `required_approvals`.
+    pub owner_approvals: usize,
+    /// Owner approvals needed when an `[[owners]]` rule covers the module.
+    pub required_approvals: Option<usize>,
+}
+
+#[derive(Clone, Debug)]
+pub struct RegistryApproval {
+    pub approved: bool,
+    pub approvals: Vec<String>,
+    pub owner_approvals: usize,
+    pub required_approvals: Option<usize>,
+}
+
+/// A known-bad version range of one module, stored under the registry's
+/// `advisories/` directory.
+#[derive(Clone, Debug)]
+pub struct RegistryAdvisory {
+    pub id: String,
+    pub module_path: String,
+    /// `*` or constraints such as `>=v1.0.0, <v1.4.2`.
+    pub range: String,
+    pub summary: String,
+    pub sig: Option<String>,
+    pub signer: Option<String>,
+}
+
+/// A locked module version covered by an advisory.
+#[derive(Clone, Debug)]
+pub struct AdvisoryFinding {
+    pub advisory: RegistryAdvisory,
+    pub version: String,
+    /// Requirement chain from the project, as printed by `RR mod why`.
+    pub chain: Vec<String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct AuditReport {
+    pub checked: usize,
+    pub findings: Vec<AdvisoryFinding>,
 }
 
 #[derive(Clone, Debug)]
@@ -157,6 +235,26 @@ pub struct RegistryAuditEntry {
     pub detail: String,
 }
 
+#[derive(Clone, Debug)]
+pub struct RegistryAuditVerifyReport {
+    /// Hash-chained entries.
+    pub entries: usize,
+    /// Entries written before the log was chained; they seed the chain.
+    pub legacy_entries: usize,
+    pub head: Option<String>,
+    /// Signature scheme of the checkpoint, when it is signed.
+    pub checkpoint_scheme: Option<String>,
+    pub checkpoint_signer: Option<String>,
+    pub broken: Option<RegistryAuditBreak>,
+}
+
+/// First line of `audit.log` that no longer matches the chain.
+#[derive(Clone, Debug)]
+pub struct RegistryAuditBreak {
+    pub line: usize,
+    pub reason: String,
+}
+
 #[derive(Clone, Debug)]
 pub struct RegistryReportModule {
     pub path: String,
@@ -199,6 +297,27 @@ pub struct RegistryDiffReport {
     pub added_files: Vec<String>,
     pub removed_files: Vec<String>,
     pub changed_files: Vec<String>,
+    pub api_changes: Vec<ApiChange>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ApiChangeKind {
+    Added,
+    Removed,
+    /// The item still exists but accepts less or promises less.
+    Narrowed,
+    /// The item still exists and accepts at least what it did.
+    Widened,
+}
+
+/// One change to an exported item between two releases.
+#[derive(Clone, Debug)]
+pub struct ApiChange {
+    pub kind: ApiChangeKind,
+    pub file: String,
+    /// `fn name`, `trait Name`, `trait method Name.method` or `impl Trait for Type`.
+    pub item: String,
+    pub detail: String,
 }
 
 #[derive(Clone, Debug)]
@@ -298,6 +417,10 @@ pub(crate) struct RegistryEntry {
     pub approved: bool,
     pub archive_sig: Option<String>,
     pub signer: Option<String>,
+    /// Identities that approved this release, in approval order.
+    pub approvals: Vec<String>,
+    /// Each approver's signature over module, version and approver.
+    pub approval_sigs: BTreeMap<String, String>,
 }
 
 #[derive(Clone, Debug, Default)]
@@ -319,4 +442,20 @@ pub(crate) struct RegistryTrustPolicy {
     pub revoked_ed25519_keys: Vec<String>,
     pub allowed_signers: Vec<String>,
     pub auto_approve_signers: Vec<String>,
+    pub allowed_licenses: Vec<String>,
+    pub denied_licenses: Vec<String>,
+    pub owner_rules: Vec<RegistryOwnerRule>,
+}
+
+/// `[[owners]]` entry: releases of matching modules need approvals from
+/// `required_approvals` distinct owners.
+#[derive(Clone, Debug)]
+pub(crate) struct RegistryOwnerRule {
+    /// A module path, or a prefix ending in `*`.
+    pub module: String,
+    pub owners: Vec<String>,
+    pub required_approvals: usize,
+    /// ed25519 public key per owner, as `key.<owner>`. Owners without one
+    /// sign approvals with the registry HMAC key instead.
+    pub owner_keys: BTreeMap<String, String>,
 }
@@ -326,6 +326,50 @@ fn parse_version(raw: &str) -> Option<(u64, u64, u64, String)> {
     Some((major, minor, patch, tail))
 }
 
+/// Whether `to` may break the API of `from` under semver: a higher major,
+/// or a higher minor while the major is still 0. Unparseable versions are
+/// never a major bump.
+pub(super) fn is_major_bump(from: &str, to: &str) -> bool {
+    match (parse_version(from), parse_version(to)) {
+        (Some((0, from_minor, _, _)), Some((0, to_minor, _, _))) => to_minor > from_minor,
+        (Some((from_major, _, _, _)), Some((to_major, _, _, _))) => to_major > from_major,
+        _ => false,
+    }
+}
+
+/// Whether `version` falls in an advisory range: `*`, or comma-separated
+/// constraints such as `>=v1.0.0, <v1.4.2`. A bare version or `=v1.2.0`
+/// matches exactly.
+pub(super) fn version_in_range(version: &str, range: &str) -> Result<bool, String> {
+    let range = range.trim();
+    if range == "*" {
+        return Ok(true);
+    }
+    let mut matched = true;
+    for constraint in range.split(',') {
+        let constraint = constraint.trim();
+        let (op, bound) = [">=", "<=", ">", "<", "="]
+            .into_iter()
+            .find_map(|op| constraint.strip_prefix(op).map(|rest| (op, rest.trim())))
+            .unwrap_or(("=", constraint));
+        if parse_version(bound).is_none() {
+            return Err(format!(
+                "invalid version range '{}': '{}' is not a version like v1.2.3",
+                range, bound
+            ));
+        }
+        let ord = compare_versions(version, bound);
+        matched &= match op {
+            ">=" => ord != Ordering::Less,
+            "<=" => ord != Ordering::Greater,
+            ">" => ord == Ordering::Greater,
+            "<" => ord == Ordering::Less,
+            _ => ord == Ordering::Equal,
+        };
+    }
+    Ok(matched)
+}
+
 fn version_major(raw: &str) -> Option<u64> {
     parse_version(raw).map(|(major, _, _, _)| major)
 }
@@ -353,6 +397,32 @@ pub(super) fn is_major_version_segment(segment: &str) -> bool {
         .is_some_and(|major| major >= 2)
 }
 
+/// Reject a registry-relative path that could leave the registry root: an
+/// absolute path, a drive prefix, or an empty, `.` or `..` component.
+/// `what` names the path in the error.
+pub(super) fn check_registry_rel_path(what: &str, rel: &str) -> Result<(), String> {
+    let bad_component = rel
+        .split(['/', '\\'])
+        .any(|part| part.is_empty() || part == "." || part == ".." || part.contains(':'));
+    if bad_component || Path::new(rel).is_absolute() {
+        return Err(format!(
+            "invalid registry {} '{}': it must stay inside the registry",
+            what, rel
+        ));
+    }
+    Ok(())
+}
+
+/// `registry_root.join(rel)` for a path read from a registry index.
+pub(super) fn registry_rel_join(
+    registry_root: &Path,
+    what: &str,
+    rel: &str,
+) -> Result<PathBuf, String> {
+    check_registry_rel_path(what, rel)?;
+    Ok(registry_root.join(rel))
+}
+
 pub(super) fn module_path_to_rel_path(module_path: &str) -> PathBuf {
     let mut out = PathBuf::new();
     for segment in module_path.split('/') {
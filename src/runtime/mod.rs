@@ -1,7 +1,8 @@
 pub mod runner;
+pub mod session;
 
 mod source;
 mod subset;
 
-pub use source::R_RUNTIME;
+pub use source::{R_COVERAGE_HOOK, R_DEBUG_HOOK, R_RUNTIME};
 pub use subset::{referenced_runtime_symbols, render_runtime_subset};
@@ -4,9 +4,11 @@ use std::fs;
 use std::io::ErrorKind;
 use std::io::IsTerminal;
 use std::io::Write;
+use std::io::{BufRead, BufReader};
 use std::path::PathBuf;
-use std::process::Command;
+use std::process::{ChildStdout, Command, Stdio};
 use std::sync::OnceLock;
+use std::thread;
 
 use regex::Regex;
 
@@ -19,6 +21,7 @@ impl Runner {
         r_code: &str,
         source_map: &[MapEntry],
         rscript_path: Option<&str>,
+        program_args: &[String],
         keep_r: bool,
     ) -> i32 {
         let color = color_enabled_stderr();
@@ -56,13 +59,18 @@ impl Runner {
             .or_else(|| env::var("RRSCRIPT").ok().filter(|v| !v.trim().is_empty()))
             .unwrap_or_else(|| "Rscript".to_string());
         // The runner intentionally executes the CLI/env-selected Rscript path,
-        // passes only the generated file, and reports OS failures as diagnostics.
-        let output = match Command::new(&rscript /* audit: allow */)
+        // passes the generated file and the program arguments, and reports OS
+        // failures as diagnostics. stdin is inherited so programs can read it.
+        let mut child = match Command::new(&rscript /* audit: allow */)
             .arg("--vanilla")
             .arg(&gen_path)
-            .output()
+            .args(program_args)
+            .stdin(Stdio::inherit())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
         {
-            Ok(out) => out,
+            Ok(child) => child,
             Err(e) => {
                 eprintln!(
                     "{}",
@@ -111,8 +119,38 @@ impl Runner {
             }
         };
 
-        // Stdout
-        if let Err(e) = std::io::stdout().write_all(&output.stdout) {
+        // Stdout is forwarded from its own thread so that neither pipe can
+        // fill up and stall R while the other one is being read. The thread
+        // is joined before the exit status is read.
+        let stdout_pump = child.stdout.take().map(|stdout| {
+            thread::spawn(move || forward_stdout(stdout)) /* audit: allow */
+        });
+
+        // Stderr Mapping
+        if let Some(stderr) = child.stderr.take() {
+            let mut mapper = StderrMapper::new(source_path, source_map, color);
+            let mut reader = BufReader::new(stderr);
+            let mut line = Vec::new();
+            loop {
+                line.clear();
+                match reader.read_until(b'\n', &mut line) {
+                    Ok(0) | Err(_) => break,
+                    Ok(_) => {
+                        let text = String::from_utf8_lossy(&line);
+                        mapper.emit_line(text.trim_end_matches(['\n', '\r']));
+                    }
+                }
+            }
+        }
+
+        let stdout_result = match stdout_pump.map(|pump| pump.join()) {
+            Some(Ok(result)) => result,
+            Some(Err(_)) => Err(std::io::Error::other("stdout forwarding thread panicked")),
+            None => Ok(()),
+        };
+        let status = child.wait();
+
+        if let Err(e) = stdout_result {
             if e.kind() == ErrorKind::BrokenPipe {
                 if !keep_r {
                     fs::remove_file(&gen_path).ok();
@@ -144,12 +182,6 @@ impl Runner {
             return 1;
         }
 
-        // Stderr Mapping
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        if !stderr.is_empty() {
-            Self::emit_mapped_stderr(source_path, &stderr, source_map, color);
-        }
-
         if keep_r {
             eprintln!(
                 "{}",
@@ -163,82 +195,122 @@ impl Runner {
             fs::remove_file(&gen_path).ok();
         }
 
-        output.status.code().unwrap_or(1)
+        status.ok().and_then(|status| status.code()).unwrap_or(1)
+    }
+}
+
+/// Copy the program's stdout line by line, flushing each line so long runs
+/// show progress as it happens. After a write error the rest is drained so R
+/// does not block on a full pipe.
+fn forward_stdout(stdout: ChildStdout) -> std::io::Result<()> {
+    let mut reader = BufReader::new(stdout);
+    let mut out = std::io::stdout().lock();
+    let mut line = Vec::new();
+    loop {
+        line.clear();
+        if reader.read_until(b'\n', &mut line)? == 0 {
+            return Ok(());
+        }
+        if let Err(e) = out.write_all(&line).and_then(|()| out.flush()) {
+            std::io::copy(&mut reader, &mut std::io::sink()).ok();
+            return Err(e);
+        }
+    }
+}
+
+/// Rewrites R stderr lines to RR source locations as they arrive. The active
+/// colors carry over from one line to the next, since a diagnostic header
+/// sets the color of the lines that follow it.
+struct StderrMapper<'a> {
+    source_path: &'a str,
+    source_map: &'a [MapEntry],
+    color: bool,
+    active_module_color: &'static str,
+    active_code_color: &'static str,
+}
+
+impl<'a> StderrMapper<'a> {
+    fn new(source_path: &'a str, source_map: &'a [MapEntry], color: bool) -> Self {
+        let active_module_color = palette_for_module("RR.RuntimeError");
+        Self {
+            source_path,
+            source_map,
+            color,
+            active_module_color,
+            active_code_color: active_module_color,
+        }
     }
 
-    fn find_mapping(r_line: u32, map: &[MapEntry]) -> Option<&MapEntry> {
-        map.iter()
+    fn find_mapping(&self, r_line: u32) -> Option<&MapEntry> {
+        self.source_map
+            .iter()
             .filter(|e| e.r_line <= r_line)
             .max_by_key(|e| e.r_line)
     }
 
-    fn emit_mapped_stderr(source_path: &str, stderr: &str, source_map: &[MapEntry], color: bool) {
-        let mut active_module_color = palette_for_module("RR.RuntimeError");
-        let mut active_code_color = active_module_color;
+    fn emit_line(&mut self, line: &str) {
+        let (source_path, color) = (self.source_path, self.color);
+        if let Some(module) = extract_rr_module(line) {
+            self.active_module_color = palette_for_module(module);
+            self.active_code_color = self.active_module_color;
+        }
+        if let Some(regex) = rr_code_regex()
+            && let Some(cap) = regex.captures(line)
+        {
+            self.active_code_color = palette_for_rr_code(&cap[1], self.active_module_color);
+        }
+        if let Some(regex) = rrdiag_regex()
+            && regex.captures(line).is_some()
+        {
+            // Runtime already emits formatted multi-line user diagnostics.
+            return;
+        }
 
-        for line in stderr.lines() {
-            if let Some(module) = extract_rr_module(line) {
-                active_module_color = palette_for_module(module);
-                active_code_color = active_module_color;
-            }
-            if let Some(regex) = rr_code_regex()
-                && let Some(cap) = regex.captures(line)
-            {
-                active_code_color = palette_for_rr_code(&cap[1], active_module_color);
-            }
-            if let Some(regex) = rrdiag_regex()
-                && regex.captures(line).is_some()
-            {
-                // Runtime already emits formatted multi-line user diagnostics.
-                continue;
-            }
+        if let Some(regex) = rr_runtime_loc_regex()
+            && let Some(cap) = regex.captures(line)
+        {
+            let rr_line: u32 = cap[1].parse().unwrap_or(0);
+            let rr_col: u32 = cap[2].parse().unwrap_or(0);
+            eprintln!(
+                "{}",
+                style(
+                    color,
+                    self.active_module_color,
+                    &format!("{}:{}:{}: {}", source_path, rr_line, rr_col, line),
+                )
+            );
+            return;
+        }
 
-            if let Some(regex) = rr_runtime_loc_regex()
-                && let Some(cap) = regex.captures(line)
+        if let Some(regex) = r_loc_regex()
+            && let Some(cap) = regex.captures(line)
+        {
+            let file = &cap[1];
+            let r_line: u32 = cap[2].parse().unwrap_or(0);
+            if (file.ends_with(".gen.R") || file.ends_with(".R"))
+                && let Some(entry) = self.find_mapping(r_line)
             {
-                let rr_line: u32 = cap[1].parse().unwrap_or(0);
-                let rr_col: u32 = cap[2].parse().unwrap_or(0);
                 eprintln!(
                     "{}",
                     style(
                         color,
-                        active_module_color,
-                        &format!("{}:{}:{}: {}", source_path, rr_line, rr_col, line),
+                        self.active_module_color,
+                        &format!(
+                            "{}:{}:{}: (R {}): {}",
+                            source_path,
+                            entry.rr_span.start_line,
+                            entry.rr_span.start_col,
+                            r_line,
+                            line
+                        ),
                     )
                 );
-                continue;
+                return;
             }
-
-            if let Some(regex) = r_loc_regex()
-                && let Some(cap) = regex.captures(line)
-            {
-                let file = &cap[1];
-                let r_line: u32 = cap[2].parse().unwrap_or(0);
-                if (file.ends_with(".gen.R") || file.ends_with(".R"))
-                    && let Some(entry) = Self::find_mapping(r_line, source_map)
-                {
-                    eprintln!(
-                        "{}",
-                        style(
-                            color,
-                            active_module_color,
-                            &format!(
-                                "{}:{}:{}: (R {}): {}",
-                                source_path,
-                                entry.rr_span.start_line,
-                                entry.rr_span.start_col,
-                                r_line,
-                                line
-                            ),
-                        )
-                    );
-                    continue;
-                }
-            }
-
-            let code = fallback_stderr_style(line, active_module_color, active_code_color);
-            eprintln!("{}", style(color, code, line));
         }
+
+        let code = fallback_stderr_style(line, self.active_module_color, self.active_code_color);
+        eprintln!("{}", style(color, code, line));
     }
 }
 
@@ -0,0 +1,129 @@
+use std::env;
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+
+const END_MARKER: &str = "#<<RR-END>>";
+const DONE_MARKER: &str = "#<<RR-DONE>>";
+const ERROR_MARKER: &str = "#<<RR-ERROR>>";
+
+/// Read-eval loop run by the child. Each request is a batch of R lines ended
+/// by `END_MARKER`; it is evaluated in the global environment so definitions
+/// persist, and the reply ends with `DONE_MARKER`. Errors are reported on
+/// stdout behind `ERROR_MARKER` so the caller can tell a failed cell apart.
+/// Warnings go to stderr as they happen, since the loop never returns to the
+/// top level where R would flush deferred ones.
+const DRIVER: &str = r##"local({
+  con <- file("stdin")
+  open(con)
+  repeat {
+    lines <- character()
+    repeat {
+      line <- readLines(con, n = 1L)
+      if (length(line) == 0L) quit(save = "no")
+      if (identical(line, "#<<RR-END>>")) break
+      lines <- c(lines, line)
+    }
+    tryCatch(
+      withCallingHandlers(
+        eval(parse(text = lines), envir = globalenv()),
+        warning = function(w) {
+          message("Warning: ", conditionMessage(w))
+          invokeRestart("muffleWarning")
+        }
+      ),
+      error = function(e) cat("\n#<<RR-ERROR>>", gsub("\n", " ", conditionMessage(e)), "\n", sep = "")
+    )
+    cat("\n#<<RR-DONE>>\n")
+    flush(stdout())
+  }
+})"##;
+
+/// A long-lived Rscript process that evaluates batches of generated R in one
+/// global environment. Used by `RR repl`.
+pub struct RSession {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+}
+
+impl RSession {
+    /// Start Rscript from `rscript_path`, `RRSCRIPT` or `PATH`, in that order.
+    pub fn start(rscript_path: Option<&str>) -> Result<Self, String> {
+        let rscript = rscript_path
+            .map(|p| p.to_string())
+            .or_else(|| env::var("RRSCRIPT").ok().filter(|v| !v.trim().is_empty()))
+            .unwrap_or_else(|| "Rscript".to_string());
+        // Like the runner, this executes the CLI/env-selected Rscript path and
+        // only ever passes it the fixed driver loop above.
+        let mut child = Command::new(&rscript /* audit: allow */)
+            .arg("--vanilla")
+            .arg("-e")
+            .arg(DRIVER)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .map_err(|e| format!("failed to execute '{}': {}", rscript, e))?;
+        let (Some(stdin), Some(stdout)) = (child.stdin.take(), child.stdout.take()) else {
+            let _ = child.kill();
+            return Err(format!("failed to open pipes to '{}'", rscript));
+        };
+        Ok(Self {
+            child,
+            stdin,
+            stdout: BufReader::new(stdout),
+        })
+    }
+
+    /// Evaluate `r_code`, copying the R output to `out` as it arrives.
+    /// Returns `Ok(Some(message))` when R raised an error and `Err` when the
+    /// session itself is gone.
+    pub fn eval(&mut self, r_code: &str, out: &mut dyn Write) -> Result<Option<String>, String> {
+        let request = format!("{}\n{}\n", r_code.trim_end(), END_MARKER);
+        self.stdin
+            .write_all(request.as_bytes())
+            .and_then(|_| self.stdin.flush())
+            .map_err(|e| format!("R session is not accepting input: {}", e))?;
+
+        let mut error = None;
+        // The driver starts its markers on a fresh line, so a single blank
+        // line before a marker is ours rather than R output.
+        let mut pending_blank = false;
+        let mut line = String::new();
+        loop {
+            line.clear();
+            let read = self
+                .stdout
+                .read_line(&mut line)
+                .map_err(|e| format!("failed to read R output: {}", e))?;
+            if read == 0 {
+                return Err("R session exited".to_string());
+            }
+            let text = line.trim_end_matches(['\n', '\r']);
+            if text == DONE_MARKER {
+                break;
+            }
+            if let Some(message) = text.strip_prefix(ERROR_MARKER) {
+                error = Some(message.trim().to_string());
+                pending_blank = false;
+                continue;
+            }
+            if pending_blank {
+                let _ = writeln!(out);
+            }
+            pending_blank = text.is_empty();
+            if !pending_blank {
+                let _ = writeln!(out, "{}", text);
+            }
+        }
+        let _ = out.flush();
+        Ok(error)
+    }
+}
+
+impl Drop for RSession {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
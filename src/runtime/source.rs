@@ -11,3 +11,9 @@ pub const R_RUNTIME: &str = concat!(
     "\n",
     include_str!("runtime_prelude/reductions.R"),
 );
+
+/// Hook prepended to debug builds run under `RR dap`.
+pub const R_DEBUG_HOOK: &str = include_str!("debug_hook.R");
+
+/// Counters prepended to coverage builds run by `RR run --coverage`.
+pub const R_COVERAGE_HOOK: &str = include_str!("coverage_hook.R");
@@ -10,6 +10,9 @@ pub enum TypeExpr {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Program {
     pub stmts: Vec<Stmt>,
+    /// `//!` module documentation.
+    #[serde(default)]
+    pub doc: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -36,6 +39,7 @@ pub enum StmtKind {
         ret_ty_hint: Option<TypeExpr>,
         where_bounds: Vec<TraitBound>,
         body: Block,
+        doc: Option<String>,
     }, // Global fn
     TraitDecl(TraitDecl),
     ImplDecl(ImplDecl),
@@ -52,6 +56,9 @@ pub enum StmtKind {
         var: String,
         iter: Expr,
         body: Block,
+        /// `parallel for ... reduce(...)`; `None` for an ordinary loop.
+        #[serde(default)]
+        parallel: Option<ParallelClause>,
     },
     Return {
         value: Option<Expr>,
@@ -70,8 +77,53 @@ pub enum StmtKind {
         source: ImportSource,
         path: String,
         spec: ImportSpec,
-    }, // import "path" | import r "pkg" | import r default from "pkg" | import r { foo as bar } from "pkg" | import r * as ns from "pkg"
+    }, // import "path" | import { a, b as c } from "path" | import * as ns from "path" | import r "pkg" | import r default from "pkg" | import r { foo as bar } from "pkg" | import r * as ns from "pkg"
     Export(FnDecl), // export fn
+    ReExport {
+        path: String,
+        names: Vec<String>,
+    }, // export { a, b } from "path"
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParallelClause {
+    pub reductions: Vec<ParallelReduction>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParallelReduction {
+    pub op: ReductionOp,
+    pub var: String,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReductionOp {
+    Sum,
+    Prod,
+    Min,
+    Max,
+}
+
+impl ReductionOp {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "sum" => Some(Self::Sum),
+            "prod" => Some(Self::Prod),
+            "min" => Some(Self::Min),
+            "max" => Some(Self::Max),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Sum => "sum",
+            Self::Prod => "prod",
+            Self::Min => "min",
+            Self::Max => "max",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -110,6 +162,8 @@ pub struct FnDecl {
     pub where_bounds: Vec<TraitBound>,
     pub body: Block,
     pub public: bool, // export
+    #[serde(default)]
+    pub doc: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -125,6 +179,8 @@ pub struct TraitDecl {
     pub methods: Vec<TraitMethodSig>,
     #[serde(default)]
     pub public: bool,
+    #[serde(default)]
+    pub doc: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -142,6 +198,11 @@ pub struct ImplDecl {
     pub methods: Vec<FnDecl>,
     #[serde(default)]
     pub public: bool,
+    /// `impl Trait for Type as s3`: also expose the methods to R's S3 dispatch.
+    #[serde(default)]
+    pub s3: bool,
+    #[serde(default)]
+    pub doc: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -189,6 +250,8 @@ pub struct TraitMethodSig {
     pub where_bounds: Vec<TraitBound>,
     pub default_body: Option<Block>,
     pub span: Span,
+    #[serde(default)]
+    pub doc: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
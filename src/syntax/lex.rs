@@ -1,5 +1,6 @@
 use crate::syntax::token::{Token, TokenKind};
 use crate::utils::Span;
+use rustc_hash::FxHashMap;
 use std::iter::Peekable;
 use std::str::Chars;
 
@@ -9,6 +10,9 @@ pub struct Lexer<'a> {
     byte_pos: usize,
     line: u32,
     col: u32,
+    pending_doc: Vec<String>,
+    docs: FxHashMap<usize, String>,
+    module_doc: Vec<String>,
 }
 
 impl<'a> Lexer<'a> {
@@ -19,9 +23,37 @@ impl<'a> Lexer<'a> {
             byte_pos: 0,
             line: 1,
             col: 1,
+            pending_doc: Vec::new(),
+            docs: FxHashMap::default(),
+            module_doc: Vec::new(),
         }
     }
 
+    /// `///` doc comment written right before the token starting at `byte`.
+    pub fn take_doc(&mut self, byte: usize) -> Option<String> {
+        self.docs.remove(&byte)
+    }
+
+    /// `//!` doc comments of the file, joined in order.
+    pub fn module_doc(&self) -> Option<String> {
+        (!self.module_doc.is_empty()).then(|| self.module_doc.join("\n"))
+    }
+
+    fn record_doc_line(&mut self, comment: &str) {
+        let (target, text) = if let Some(text) = comment.strip_prefix("///") {
+            if text.starts_with('/') {
+                return;
+            }
+            (&mut self.pending_doc, text)
+        } else if let Some(text) = comment.strip_prefix("//!") {
+            (&mut self.module_doc, text)
+        } else {
+            return;
+        };
+        let text = text.strip_prefix(' ').unwrap_or(text);
+        target.push(text.trim_end().to_string());
+    }
+
     fn peek(&mut self) -> Option<char> {
         self.chars.peek().copied()
     }
@@ -53,13 +85,16 @@ impl<'a> Lexer<'a> {
                 clone.next(); // skip /
                 match clone.next() {
                     Some('/') => {
-                        // Line comment
+                        // Line comment; `///` and `//!` are kept as docs.
+                        let start = self.byte_pos;
                         while let Some(nc) = self.peek() {
                             if nc == '\n' {
                                 break;
                             }
                             self.advance();
                         }
+                        let input = self.input;
+                        self.record_doc_line(&input[start..self.byte_pos]);
                     }
                     Some('*') => {
                         // Block comment
@@ -491,6 +526,11 @@ impl<'a> Lexer<'a> {
         self.skip_whitespace();
 
         let start_byte = self.byte_pos;
+        if !self.pending_doc.is_empty() {
+            let doc = self.pending_doc.join("\n");
+            self.pending_doc.clear();
+            self.docs.insert(start_byte, doc);
+        }
         let start_line = self.line;
         let start_col = self.col;
 
@@ -1,4 +1,5 @@
 pub mod ast;
 pub mod lex;
 pub mod parse;
+pub(crate) mod r_subset;
 pub mod token;
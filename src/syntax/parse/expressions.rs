@@ -128,6 +128,18 @@ impl<'a> Parser<'a> {
 
     pub(crate) fn parse_for_stmt(&mut self) -> RR<Stmt> {
         let start = self.current.span;
+        self.parse_for_stmt_from(start, false)
+    }
+
+    /// `parallel for i in a..b reduce(sum: acc, max: best) { ... }`.
+    /// `parallel` is contextual, so it stays usable as an ordinary name.
+    pub(crate) fn parse_parallel_for_stmt(&mut self) -> RR<Stmt> {
+        let start = self.current.span;
+        self.advance(); // parallel
+        self.parse_for_stmt_from(start, true)
+    }
+
+    fn parse_for_stmt_from(&mut self, start: Span, parallel: bool) -> RR<Stmt> {
         self.advance(); // for
         let has_paren = self.current.kind == TokenKind::LParen;
         if has_paren {
@@ -148,17 +160,80 @@ impl<'a> Parser<'a> {
         if has_paren {
             self.expect(TokenKind::RParen)?;
         }
+        let parallel = if parallel {
+            Some(self.parse_parallel_clause()?)
+        } else {
+            None
+        };
         let body = self.parse_stmt_or_block()?;
         Ok(Stmt {
             kind: StmtKind::For {
                 var,
                 iter,
                 body: body.clone(),
+                parallel,
             },
             span: start.merge(body.span),
         })
     }
 
+    fn parse_parallel_clause(&mut self) -> RR<ParallelClause> {
+        let mut reductions = Vec::new();
+        let is_reduce = matches!(&self.current.kind, TokenKind::Ident(name) if name == "reduce");
+        if !is_reduce || self.peek.kind != TokenKind::LParen {
+            return Ok(ParallelClause { reductions });
+        }
+        self.advance(); // reduce
+        self.advance(); // (
+        loop {
+            let span = self.current.span;
+            let TokenKind::Ident(op_name) = self.current.kind.clone() else {
+                bail_at!(
+                    span,
+                    "RR.ParseError",
+                    RRCode::E0001,
+                    Stage::Parse,
+                    "Expected reduction operator in reduce(...)"
+                );
+            };
+            let Some(op) = ReductionOp::parse(&op_name) else {
+                bail_at!(
+                    span,
+                    "RR.ParseError",
+                    RRCode::E0001,
+                    Stage::Parse,
+                    "Unknown reduction operator '{}' (expected sum, prod, min or max)",
+                    op_name
+                );
+            };
+            self.advance();
+            self.expect(TokenKind::Colon)?;
+            let TokenKind::Ident(var) = self.current.kind.clone() else {
+                bail_at!(
+                    self.current.span,
+                    "RR.ParseError",
+                    RRCode::E0001,
+                    Stage::Parse,
+                    "Expected accumulator name after '{}:' in reduce(...)",
+                    op_name
+                );
+            };
+            reductions.push(ParallelReduction {
+                op,
+                var,
+                span: span.merge(self.current.span),
+            });
+            self.advance();
+            if self.current.kind == TokenKind::Comma {
+                self.advance();
+                continue;
+            }
+            break;
+        }
+        self.expect(TokenKind::RParen)?;
+        Ok(ParallelClause { reductions })
+    }
+
     pub(crate) fn parse_return_stmt(&mut self) -> RR<Stmt> {
         let start = self.current.span;
         self.advance(); // return
@@ -230,7 +305,11 @@ impl<'a> Parser<'a> {
 
     pub(crate) fn parse_import_payload(&mut self, source: ImportSource) -> RR<ParsedImport> {
         if source != ImportSource::RPackage {
-            return self.parse_glob_import("Expected string after import");
+            return match &self.current.kind {
+                TokenKind::LBrace => self.parse_module_named_import(),
+                TokenKind::Star => self.parse_module_namespace_import(),
+                _ => self.parse_glob_import("Expected string after import"),
+            };
         }
 
         match &self.current.kind {
@@ -306,6 +385,105 @@ impl<'a> Parser<'a> {
         })
     }
 
+    pub(crate) fn parse_module_named_import(&mut self) -> RR<ParsedImport> {
+        let bindings = self.parse_module_import_bindings("in module import list")?;
+        self.expect_ident_keyword("from", "Expected 'from' after module import list")?;
+        let (path, end_fallback) =
+            self.parse_import_string("Expected module path string after 'from'")?;
+
+        Ok(ParsedImport {
+            path,
+            spec: ImportSpec::Named(bindings),
+            end_fallback,
+        })
+    }
+
+    pub(crate) fn parse_module_namespace_import(&mut self) -> RR<ParsedImport> {
+        self.advance(); // *
+        self.expect_ident_keyword("as", "Expected 'as' after '*' in module import")?;
+        let alias = self.expect_ident_name("after 'as' in module import")?;
+        self.expect_ident_keyword("from", "Expected 'from' after module namespace alias")?;
+        let (path, end_fallback) =
+            self.parse_import_string("Expected module path string after 'from'")?;
+
+        Ok(ParsedImport {
+            path,
+            spec: ImportSpec::Namespace(alias),
+            end_fallback,
+        })
+    }
+
+    pub(crate) fn parse_module_import_bindings(&mut self, context: &str) -> RR<Vec<ImportBinding>> {
+        let mut bindings = Vec::new();
+        self.advance(); // {
+
+        while self.current.kind != TokenKind::RBrace {
+            let imported = self.expect_ident_name(context)?;
+            let local = if self.current_is_ident_keyword("as") {
+                self.advance();
+                Some(self.expect_ident_name("after 'as' in module import list")?)
+            } else {
+                None
+            };
+            bindings.push(ImportBinding { imported, local });
+
+            if self.current.kind != TokenKind::Comma {
+                break;
+            }
+            self.advance();
+        }
+
+        self.expect(TokenKind::RBrace)?;
+        Ok(bindings)
+    }
+
+    pub(crate) fn expect_ident_name(&mut self, context: &str) -> RR<String> {
+        match &self.current.kind {
+            TokenKind::Ident(name) => {
+                let name = name.clone();
+                self.advance();
+                Ok(name)
+            }
+            _ => bail_at!(
+                self.current.span,
+                "RR.ParseError",
+                RRCode::E0001,
+                Stage::Parse,
+                "Expected identifier {}",
+                context
+            ),
+        }
+    }
+
+    pub(crate) fn parse_re_export(&mut self, start: Span) -> RR<Stmt> {
+        let bindings = self.parse_module_import_bindings("in export list")?;
+        if let Some(binding) = bindings.iter().find(|binding| binding.local.is_some()) {
+            bail_at!(
+                self.previous_span,
+                "RR.ParseError",
+                RRCode::E0001,
+                Stage::Parse,
+                "re-export of '{}' cannot be renamed; export it under its own name",
+                binding.imported
+            );
+        }
+        self.expect_ident_keyword("from", "Expected 'from' after export list")?;
+        let (path, end_fallback) =
+            self.parse_import_string("Expected module path string after 'from'")?;
+        let end = self.consume_stmt_end(end_fallback)?;
+
+        Ok(Stmt {
+            kind: StmtKind::ReExport {
+                path,
+                names: bindings
+                    .into_iter()
+                    .map(|binding| binding.imported)
+                    .collect(),
+            },
+            span: start.merge(end),
+        })
+    }
+
     pub(crate) fn parse_glob_import(&mut self, error_message: &str) -> RR<ParsedImport> {
         let (path, end_fallback) = self.parse_import_string(error_message)?;
         Ok(ParsedImport {
@@ -355,17 +533,28 @@ impl<'a> Parser<'a> {
 
     pub(crate) fn parse_export_modifier(&mut self) -> RR<Stmt> {
         let start = self.current.span;
+        let export_doc = self.lexer.take_doc(start.start_byte);
         self.advance(); // export
 
         if self.current.kind == TokenKind::Trait {
             let mut stmt = self.parse_trait_decl_with_visibility(true)?;
             stmt.span = start.merge(stmt.span);
+            if let StmtKind::TraitDecl(decl) = &mut stmt.kind {
+                decl.doc = export_doc.or(decl.doc.take());
+            }
             return Ok(stmt);
         }
 
+        if self.current.kind == TokenKind::LBrace {
+            return self.parse_re_export(start);
+        }
+
         if self.current.kind == TokenKind::Impl {
             let mut stmt = self.parse_impl_decl_with_visibility(true)?;
             stmt.span = start.merge(stmt.span);
+            if let StmtKind::ImplDecl(decl) = &mut stmt.kind {
+                decl.doc = export_doc.or(decl.doc.take());
+            }
             return Ok(stmt);
         }
 
@@ -379,6 +568,7 @@ impl<'a> Parser<'a> {
             ret_ty_hint,
             where_bounds,
             body,
+            doc,
         } = stmt.kind
         {
             Ok(Stmt {
@@ -390,6 +580,7 @@ impl<'a> Parser<'a> {
                     where_bounds,
                     body,
                     public: true,
+                    doc: export_doc.or(doc),
                 }),
                 span: start.merge(stmt.span),
             })
@@ -399,7 +590,7 @@ impl<'a> Parser<'a> {
                 "RR.ParseError",
                 RRCode::E0001,
                 Stage::Parse,
-                "Expected function, trait, or impl after export"
+                "Expected function, trait, impl, or export list after export"
             );
         }
     }
@@ -502,7 +502,10 @@ impl<'a> Parser<'a> {
             }
         }
         if errors.is_empty() {
-            Ok(Program { stmts })
+            Ok(Program {
+                stmts,
+                doc: self.lexer.module_doc(),
+            })
         } else if errors.len() == 1 {
             Err(errors.remove(0))
         } else {
@@ -530,6 +533,11 @@ impl<'a> Parser<'a> {
             TokenKind::If => self.parse_if_stmt(),
             TokenKind::While => self.parse_while_stmt(),
             TokenKind::For => self.parse_for_stmt(),
+            TokenKind::Ident(ref name)
+                if name == "parallel" && self.peek.kind == TokenKind::For =>
+            {
+                self.parse_parallel_for_stmt()
+            }
             TokenKind::Return => self.parse_return_stmt(),
             TokenKind::Break => self.parse_break_stmt(),
             TokenKind::Next => self.parse_next_stmt(),
@@ -589,6 +597,7 @@ impl<'a> Parser<'a> {
 
     pub(crate) fn parse_fn_decl(&mut self) -> RR<Stmt> {
         let start = self.current.span;
+        let doc = self.lexer.take_doc(start.start_byte);
         self.advance(); // fn
         let name = self.parse_dotted_ident("for fn")?;
         let type_params = self.parse_optional_type_params()?;
@@ -638,6 +647,7 @@ impl<'a> Parser<'a> {
                 ret_ty_hint,
                 where_bounds,
                 body: body.clone(),
+                doc,
             },
             span: start.merge(body.span),
         })
@@ -645,6 +655,7 @@ impl<'a> Parser<'a> {
 
     pub(crate) fn parse_trait_method_sig(&mut self) -> RR<TraitMethodSig> {
         let start = self.current.span;
+        let doc = self.lexer.take_doc(start.start_byte);
         self.advance(); // fn
         let name = self.parse_dotted_ident("for trait method")?;
         if self.current.kind == TokenKind::Lt {
@@ -699,6 +710,7 @@ impl<'a> Parser<'a> {
             where_bounds,
             default_body,
             span: start.merge(end),
+            doc,
         })
     }
 
@@ -816,6 +828,7 @@ impl<'a> Parser<'a> {
 
     pub(crate) fn parse_trait_decl_with_visibility(&mut self, public: bool) -> RR<Stmt> {
         let start = self.current.span;
+        let doc = self.lexer.take_doc(start.start_byte);
         self.advance(); // trait
         let name = self.parse_dotted_ident("after trait")?;
         let type_params = self.parse_optional_type_params()?;
@@ -857,6 +870,7 @@ impl<'a> Parser<'a> {
                 assoc_consts,
                 methods,
                 public,
+                doc,
             }),
             span: start.merge(end),
         })
@@ -868,6 +882,7 @@ impl<'a> Parser<'a> {
 
     pub(crate) fn parse_impl_decl_with_visibility(&mut self, public: bool) -> RR<Stmt> {
         let start = self.current.span;
+        let doc = self.lexer.take_doc(start.start_byte);
         self.advance(); // impl
         let type_params = self.parse_optional_type_params()?;
         let negative = if self.current.kind == TokenKind::Bang {
@@ -879,6 +894,23 @@ impl<'a> Parser<'a> {
         let trait_name = self.parse_dotted_ident("after impl")?;
         self.expect(TokenKind::For)?;
         let for_ty = self.parse_type_expr("after impl Trait for")?;
+        let s3 = if self.current_is_ident_keyword("as") {
+            self.advance();
+            let backend = self.expect_ident_name("after 'as' in impl header")?;
+            if backend != "s3" {
+                bail_at!(
+                    self.previous_span,
+                    "RR.ParseError",
+                    RRCode::E0001,
+                    Stage::Parse,
+                    "Unknown impl export '{}'; only 'as s3' is supported",
+                    backend
+                );
+            }
+            true
+        } else {
+            false
+        };
         let where_bounds = self.parse_optional_where_bounds()?;
         self.expect(TokenKind::LBrace)?;
         let mut assoc_types = Vec::new();
@@ -911,6 +943,7 @@ impl<'a> Parser<'a> {
                 ret_ty_hint,
                 where_bounds,
                 body,
+                doc: method_doc,
             } = stmt.kind
             else {
                 return Err(InternalCompilerError::new(
@@ -928,6 +961,7 @@ impl<'a> Parser<'a> {
                 where_bounds,
                 body,
                 public: false,
+                doc: method_doc,
             });
         }
         let end = self.current.span;
@@ -943,6 +977,8 @@ impl<'a> Parser<'a> {
                 assoc_consts,
                 methods,
                 public,
+                s3,
+                doc,
             }),
             span: start.merge(end),
         })
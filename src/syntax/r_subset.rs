@@ -0,0 +1,722 @@
+//! Lexer and parser for the subset of R that RR reads: `RR import-r` input
+//! and the contents of `unsafe r { }` blocks.
+//!
+//! The tree keeps every construct a consumer needs to reason about, including
+//! ones RR cannot spell (`<<-`, formulas, slot access, `:::`). A statement that
+//! does not parse is kept as a `Raw` statement with the reason.
+
+// ----- Lexer -----
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Tok {
+    Ident(String),
+    Num(String),
+    /// String literal, already spelled as an RR double-quoted literal.
+    Str(String),
+    Op(String),
+    LParen,
+    RParen,
+    LBrace,
+    RBrace,
+    LBracket,
+    RBracket,
+    Comma,
+    Semi,
+    Newline,
+    /// A lexeme RR has no spelling for; the reason is reported.
+    Unsupported(String),
+    Eof,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct Token {
+    pub(crate) tok: Tok,
+    pub(crate) start: usize,
+    pub(crate) end: usize,
+    pub(crate) line: u32,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct Comment {
+    pub(crate) line: u32,
+    pub(crate) text: String,
+    /// Whether code precedes the comment on its line.
+    pub(crate) trailing: bool,
+}
+
+const OPERATORS: &[&str] = &[
+    "<<-", "->>", ":::", "<-", "<=", ">=", "==", "!=", "->", "&&", "||", "|>", "::", "**", "+",
+    "-", "*", "/", "^", "<", ">", "!", "&", "|", "~", "?", ":", "=", "$", "@", "\\",
+];
+
+pub(crate) fn lex(src: &str) -> (Vec<Token>, Vec<Comment>) {
+    let bytes = src.as_bytes();
+    let mut toks = Vec::new();
+    let mut comments = Vec::new();
+    let mut i = 0;
+    let mut line = 1u32;
+    let mut line_has_code = false;
+    while i < bytes.len() {
+        let c = bytes[i];
+        let start = i;
+        let tok = match c {
+            b'\n' => {
+                i += 1;
+                toks.push(Token {
+                    tok: Tok::Newline,
+                    start,
+                    end: i,
+                    line,
+                });
+                line += 1;
+                line_has_code = false;
+                continue;
+            }
+            b' ' | b'\t' | b'\r' => {
+                i += 1;
+                continue;
+            }
+            b'#' => {
+                let end = src[i..].find('\n').map_or(src.len(), |n| i + n);
+                comments.push(Comment {
+                    line,
+                    text: src[i..end].trim_start_matches('#').trim_end().to_string(),
+                    trailing: line_has_code,
+                });
+                i = end;
+                continue;
+            }
+            b'"' | b'\'' => {
+                let (tok, end, newlines) = lex_string(src, i);
+                i = end;
+                let token = Token {
+                    tok,
+                    start,
+                    end,
+                    line,
+                };
+                line += newlines;
+                toks.push(token);
+                line_has_code = true;
+                continue;
+            }
+            b'`' => {
+                let end = src[i + 1..].find('`').map_or(src.len(), |n| i + n + 2);
+                i = end;
+                Tok::Unsupported(format!("backtick-quoted name {}", &src[start..end]))
+            }
+            b'(' => {
+                i += 1;
+                Tok::LParen
+            }
+            b')' => {
+                i += 1;
+                Tok::RParen
+            }
+            b'{' => {
+                i += 1;
+                Tok::LBrace
+            }
+            b'}' => {
+                i += 1;
+                Tok::RBrace
+            }
+            b'[' => {
+                i += 1;
+                Tok::LBracket
+            }
+            b']' => {
+                i += 1;
+                Tok::RBracket
+            }
+            b',' => {
+                i += 1;
+                Tok::Comma
+            }
+            b';' => {
+                i += 1;
+                Tok::Semi
+            }
+            b'%' => {
+                let end = src[i + 1..]
+                    .find(['%', '\n'])
+                    .map_or(src.len(), |n| i + n + 2);
+                i = end;
+                Tok::Op(src[start..end].to_string())
+            }
+            _ if c.is_ascii_digit()
+                || (c == b'.' && bytes.get(i + 1).is_some_and(u8::is_ascii_digit)) =>
+            {
+                i += 1;
+                while i < bytes.len() {
+                    let d = bytes[i];
+                    let exponent_sign =
+                        matches!(d, b'+' | b'-') && matches!(bytes[i - 1], b'e' | b'E');
+                    if d.is_ascii_alphanumeric() || d == b'.' || exponent_sign {
+                        i += 1;
+                    } else {
+                        break;
+                    }
+                }
+                Tok::Num(src[start..i].to_string())
+            }
+            _ if c == b'.' || c == b'_' || c.is_ascii_alphabetic() || c >= 0x80 => {
+                let end = src[i..]
+                    .find(|ch: char| !(ch.is_alphanumeric() || ch == '.' || ch == '_'))
+                    .map_or(src.len(), |n| i + n);
+                i = end.max(i + src[i..].chars().next().map_or(1, char::len_utf8));
+                Tok::Ident(src[start..i].to_string())
+            }
+            _ => match OPERATORS.iter().find(|op| src[i..].starts_with(**op)) {
+                Some(op) => {
+                    i += op.len();
+                    Tok::Op(op.to_string())
+                }
+                None => {
+                    let ch = src[i..].chars().next().unwrap_or('?');
+                    i += ch.len_utf8();
+                    Tok::Unsupported(format!("unexpected character '{}'", ch))
+                }
+            },
+        };
+        line_has_code = true;
+        toks.push(Token {
+            tok,
+            start,
+            end: i,
+            line,
+        });
+    }
+    toks.push(Token {
+        tok: Tok::Eof,
+        start: src.len(),
+        end: src.len(),
+        line,
+    });
+    (toks, comments)
+}
+
+/// Lex an R string starting at `start`. Returns the token, the end offset and
+/// the number of newlines inside the literal.
+fn lex_string(src: &str, start: usize) -> (Tok, usize, u32) {
+    let quote = src.as_bytes()[start] as char;
+    let mut out = String::from("\"");
+    let mut chars = src[start + 1..].char_indices();
+    let mut newlines = 0;
+    while let Some((offset, c)) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some((_, '\'')) => out.push('\''),
+                Some((_, '\n')) => {
+                    newlines += 1;
+                    out.push_str("\\n");
+                }
+                Some((_, e)) => {
+                    out.push('\\');
+                    out.push(e);
+                }
+                None => break,
+            },
+            _ if c == quote => {
+                out.push('"');
+                return (Tok::Str(out), start + 1 + offset + 1, newlines);
+            }
+            '"' => out.push_str("\\\""),
+            '\n' => {
+                newlines += 1;
+                out.push_str("\\n");
+            }
+            _ => out.push(c),
+        }
+    }
+    (
+        Tok::Unsupported("unterminated string".to_string()),
+        src.len(),
+        newlines,
+    )
+}
+
+// ----- Parser -----
+
+#[derive(Clone, Debug)]
+pub(crate) struct Arg {
+    pub(crate) name: Option<String>,
+    pub(crate) value: Option<Expr>,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) enum Expr {
+    Num(String),
+    Str(String),
+    Ident(String),
+    /// `pkg::name`
+    Ns(String, String),
+    /// `pkg:::name`
+    NsInternal(String, String),
+    Call(Box<Expr>, Vec<Arg>),
+    Index {
+        base: Box<Expr>,
+        args: Vec<Arg>,
+        double: bool,
+    },
+    Dollar(Box<Expr>, String),
+    /// `obj@slot`
+    Slot(Box<Expr>, String),
+    Unary(String, Box<Expr>),
+    Binary(String, Box<Expr>, Box<Expr>),
+    Paren(Box<Expr>),
+    Block(Vec<Stmt>, u32),
+    If(Box<Expr>, Box<Expr>, Option<Box<Expr>>),
+    For(String, Box<Expr>, Box<Expr>),
+    While(Box<Expr>, Box<Expr>),
+    Repeat(Box<Expr>),
+    Break,
+    Next,
+    Function(Vec<(String, Option<Expr>)>, Box<Expr>),
+    /// `target <- value`, `target = value` or `value -> target`.
+    Assign(Box<Expr>, Box<Expr>),
+    /// `target <<- value` or `value ->> target`.
+    SuperAssign(Box<Expr>, Box<Expr>),
+}
+
+#[derive(Clone, Debug)]
+pub(crate) enum StmtKind {
+    Expr(Expr),
+    /// A statement that did not parse; kept as source text.
+    Raw(String),
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct Stmt {
+    pub(crate) kind: StmtKind,
+    /// Byte range of the statement in the source.
+    pub(crate) start: usize,
+    pub(crate) end: usize,
+    pub(crate) line: u32,
+    pub(crate) end_line: u32,
+}
+
+type PResult<T> = Result<T, String>;
+
+/// Parse R `source` into statements, returning the comments alongside.
+pub(crate) fn parse_program(source: &str) -> (Vec<Stmt>, Vec<Comment>) {
+    let (toks, comments) = lex(source);
+    let mut parser = Parser {
+        toks,
+        pos: 0,
+        skip_newlines: vec![false],
+    };
+    (parser.parse_stmts(false), comments)
+}
+
+struct Parser {
+    toks: Vec<Token>,
+    pos: usize,
+    /// Whether newlines are insignificant, per bracket nesting level.
+    skip_newlines: Vec<bool>,
+}
+
+impl Parser {
+    fn peek(&mut self) -> &Tok {
+        if self.skip_newlines.last() == Some(&true) {
+            while self.toks[self.pos].tok == Tok::Newline {
+                self.pos += 1;
+            }
+        }
+        &self.toks[self.pos].tok
+    }
+
+    fn bump(&mut self) -> Token {
+        self.peek();
+        let tok = self.toks[self.pos].clone();
+        if tok.tok != Tok::Eof {
+            self.pos += 1;
+        }
+        tok
+    }
+
+    fn skip_blank(&mut self) {
+        while matches!(self.toks[self.pos].tok, Tok::Newline | Tok::Semi) {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, tok: Tok, what: &str) -> PResult<()> {
+        if *self.peek() == tok {
+            self.bump();
+            Ok(())
+        } else {
+            Err(format!("expected {}", what))
+        }
+    }
+
+    fn is_op(&mut self, op: &str) -> bool {
+        matches!(self.peek(), Tok::Op(o) if o == op)
+    }
+
+    fn is_ident(&mut self, word: &str) -> bool {
+        matches!(self.peek(), Tok::Ident(w) if w == word)
+    }
+
+    fn parse_stmts(&mut self, in_braces: bool) -> Vec<Stmt> {
+        let mut stmts = Vec::new();
+        loop {
+            self.skip_blank();
+            match self.toks[self.pos].tok {
+                Tok::Eof => break,
+                Tok::RBrace if in_braces => break,
+                _ => {}
+            }
+            let first = self.pos;
+            let start = self.toks[first].start;
+            let line = self.toks[first].line;
+            let parsed = self
+                .parse_expr(0)
+                .and_then(|expr| match self.toks[self.pos].tok {
+                    Tok::Newline | Tok::Semi | Tok::Eof => Ok(expr),
+                    Tok::RBrace if in_braces => Ok(expr),
+                    _ => Err("unexpected token after statement".to_string()),
+                });
+            let kind = match parsed {
+                Ok(expr) => StmtKind::Expr(expr),
+                Err(reason) => {
+                    self.pos = first;
+                    self.skip_statement(in_braces);
+                    StmtKind::Raw(reason)
+                }
+            };
+            let last = &self.toks[self.pos.saturating_sub(1).max(first)];
+            stmts.push(Stmt {
+                kind,
+                start,
+                end: last.end,
+                line,
+                end_line: last.line,
+            });
+            if self.pos == first {
+                // Never loop on a token no rule consumes.
+                self.pos += 1;
+            }
+        }
+        stmts
+    }
+
+    /// Advance past one statement without parsing it: to the first newline or
+    /// `;` outside brackets that neither follows an operator nor precedes
+    /// `else`.
+    fn skip_statement(&mut self, in_braces: bool) {
+        let mut depth = 0usize;
+        loop {
+            match &self.toks[self.pos].tok {
+                Tok::Eof => return,
+                Tok::LParen | Tok::LBrace | Tok::LBracket => depth += 1,
+                Tok::RParen | Tok::RBracket => depth = depth.saturating_sub(1),
+                Tok::RBrace => {
+                    if depth == 0 && in_braces {
+                        return;
+                    }
+                    depth = depth.saturating_sub(1);
+                }
+                Tok::Semi if depth == 0 => return,
+                Tok::Newline if depth == 0 => {
+                    let continues = self.pos > 0
+                        && matches!(self.toks[self.pos - 1].tok, Tok::Op(_) | Tok::Comma);
+                    let mut next = self.pos;
+                    while self.toks[next].tok == Tok::Newline {
+                        next += 1;
+                    }
+                    let else_follows = matches!(&self.toks[next].tok, Tok::Ident(w) if w == "else");
+                    if !continues && !else_follows {
+                        return;
+                    }
+                }
+                _ => {}
+            }
+            self.pos += 1;
+        }
+    }
+
+    /// Binding powers `(left, right)` of R's binary operators.
+    fn binary_power(op: &str) -> Option<(u8, u8)> {
+        Some(match op {
+            "=" => (2, 2),
+            "<-" | "<<-" => (3, 3),
+            "->" | "->>" => (4, 5),
+            "~" => (5, 6),
+            "||" | "|" => (6, 7),
+            "&&" | "&" => (8, 9),
+            "==" | "!=" | "<" | ">" | "<=" | ">=" => (12, 13),
+            "+" | "-" => (14, 15),
+            "*" | "/" => (16, 17),
+            "|>" => (18, 19),
+            _ if op.starts_with('%') => (18, 19),
+            ":" => (20, 21),
+            "^" | "**" => (25, 24),
+            _ => return None,
+        })
+    }
+
+    fn parse_expr(&mut self, min: u8) -> PResult<Expr> {
+        let mut lhs = self.parse_prefix()?;
+        loop {
+            lhs = match self.peek().clone() {
+                Tok::LParen => {
+                    self.bump();
+                    let args = self.parse_args(Tok::RParen)?;
+                    Expr::Call(Box::new(lhs), args)
+                }
+                Tok::LBracket => {
+                    let open = self.bump();
+                    let double = self.toks[self.pos].tok == Tok::LBracket
+                        && self.toks[self.pos].start == open.end;
+                    if double {
+                        self.pos += 1;
+                    }
+                    let args = self.parse_args(Tok::RBracket)?;
+                    if double {
+                        self.expect(Tok::RBracket, "']]'")?;
+                    }
+                    Expr::Index {
+                        base: Box::new(lhs),
+                        args,
+                        double,
+                    }
+                }
+                Tok::Op(op) if op == "$" => {
+                    self.bump();
+                    match self.bump().tok {
+                        Tok::Ident(field) => Expr::Dollar(Box::new(lhs), field),
+                        _ => return Err("`$` with a computed or quoted field".to_string()),
+                    }
+                }
+                Tok::Op(op) if op == "@" => {
+                    self.bump();
+                    match self.bump().tok {
+                        Tok::Ident(slot) => Expr::Slot(Box::new(lhs), slot),
+                        _ => return Err("`@` with a computed or quoted slot".to_string()),
+                    }
+                }
+                Tok::Op(op) => {
+                    let Some((left, right)) = Self::binary_power(&op) else {
+                        return Err(unsupported_operator(&op));
+                    };
+                    if left < min {
+                        break;
+                    }
+                    self.bump();
+                    self.skip_operand_newlines();
+                    let rhs = self.parse_expr(right)?;
+                    match op.as_str() {
+                        "<-" | "=" => Expr::Assign(Box::new(lhs), Box::new(rhs)),
+                        "->" => Expr::Assign(Box::new(rhs), Box::new(lhs)),
+                        "<<-" => Expr::SuperAssign(Box::new(lhs), Box::new(rhs)),
+                        "->>" => Expr::SuperAssign(Box::new(rhs), Box::new(lhs)),
+                        _ => Expr::Binary(op, Box::new(lhs), Box::new(rhs)),
+                    }
+                }
+                _ => break,
+            };
+        }
+        Ok(lhs)
+    }
+
+    fn skip_operand_newlines(&mut self) {
+        while self.toks[self.pos].tok == Tok::Newline {
+            self.pos += 1;
+        }
+    }
+
+    fn parse_prefix(&mut self) -> PResult<Expr> {
+        let tok = self.bump();
+        match tok.tok {
+            Tok::Num(text) => Ok(Expr::Num(text)),
+            Tok::Str(text) => Ok(Expr::Str(text)),
+            Tok::Unsupported(reason) => Err(reason),
+            Tok::LParen => {
+                self.skip_newlines.push(true);
+                let inner = self.parse_expr(0);
+                let close = self.expect(Tok::RParen, "')'");
+                self.skip_newlines.pop();
+                close?;
+                Ok(Expr::Paren(Box::new(inner?)))
+            }
+            Tok::LBrace => {
+                self.skip_newlines.push(false);
+                let stmts = self.parse_stmts(true);
+                self.skip_newlines.pop();
+                let close = self.bump();
+                if close.tok != Tok::RBrace {
+                    return Err("expected '}'".to_string());
+                }
+                Ok(Expr::Block(stmts, close.line))
+            }
+            Tok::Op(op) => match op.as_str() {
+                "-" | "+" => Ok(Expr::Unary(op, Box::new(self.parse_expr(22)?))),
+                "!" => Ok(Expr::Unary(op, Box::new(self.parse_expr(10)?))),
+                "~" => Ok(Expr::Unary(op, Box::new(self.parse_expr(6)?))),
+                "\\" => self.parse_function(),
+                _ => Err(unsupported_operator(&op)),
+            },
+            Tok::Ident(word) => self.parse_word(word),
+            _ => Err("unexpected token".to_string()),
+        }
+    }
+
+    fn parse_word(&mut self, word: String) -> PResult<Expr> {
+        match word.as_str() {
+            "function" => self.parse_function(),
+            "if" => {
+                let cond = self.parse_condition()?;
+                let then = self.parse_body()?;
+                let save = self.pos;
+                self.skip_operand_newlines();
+                let els = if self.is_ident("else") {
+                    self.bump();
+                    self.skip_operand_newlines();
+                    Some(Box::new(self.parse_expr(1)?))
+                } else {
+                    self.pos = save;
+                    None
+                };
+                Ok(Expr::If(Box::new(cond), Box::new(then), els))
+            }
+            "for" => {
+                self.expect(Tok::LParen, "'(' after for")?;
+                self.skip_newlines.push(true);
+                let header = (|| {
+                    let Tok::Ident(var) = self.bump().tok else {
+                        return Err("expected loop variable".to_string());
+                    };
+                    if !self.is_ident("in") {
+                        return Err("expected 'in'".to_string());
+                    }
+                    self.bump();
+                    let seq = self.parse_expr(0)?;
+                    self.expect(Tok::RParen, "')'")?;
+                    Ok((var, seq))
+                })();
+                self.skip_newlines.pop();
+                let (var, seq) = header?;
+                let body = self.parse_body()?;
+                Ok(Expr::For(var, Box::new(seq), Box::new(body)))
+            }
+            "while" => {
+                let cond = self.parse_condition()?;
+                let body = self.parse_body()?;
+                Ok(Expr::While(Box::new(cond), Box::new(body)))
+            }
+            "repeat" => Ok(Expr::Repeat(Box::new(self.parse_body()?))),
+            "break" => Ok(Expr::Break),
+            "next" => Ok(Expr::Next),
+            "else" | "in" => Err(format!("unexpected '{}'", word)),
+            _ if self.is_op("::") => {
+                self.bump();
+                match self.bump().tok {
+                    Tok::Ident(name) => Ok(Expr::Ns(word, name)),
+                    _ => Err("expected a name after '::'".to_string()),
+                }
+            }
+            _ if self.is_op(":::") => {
+                self.bump();
+                match self.bump().tok {
+                    Tok::Ident(name) => Ok(Expr::NsInternal(word, name)),
+                    _ => Err("expected a name after ':::'".to_string()),
+                }
+            }
+            _ => Ok(Expr::Ident(word)),
+        }
+    }
+
+    fn parse_condition(&mut self) -> PResult<Expr> {
+        self.expect(Tok::LParen, "'('")?;
+        self.skip_newlines.push(true);
+        let cond = self.parse_expr(0);
+        let close = self.expect(Tok::RParen, "')'");
+        self.skip_newlines.pop();
+        close?;
+        cond
+    }
+
+    fn parse_body(&mut self) -> PResult<Expr> {
+        self.skip_operand_newlines();
+        self.parse_expr(1)
+    }
+
+    fn parse_function(&mut self) -> PResult<Expr> {
+        self.expect(Tok::LParen, "'(' after function")?;
+        self.skip_newlines.push(true);
+        let params = (|| {
+            let mut params = Vec::new();
+            while *self.peek() != Tok::RParen {
+                let Tok::Ident(name) = self.bump().tok else {
+                    return Err("expected parameter name".to_string());
+                };
+                let default = if self.is_op("=") {
+                    self.bump();
+                    Some(self.parse_expr(3)?)
+                } else {
+                    None
+                };
+                params.push((name, default));
+                if *self.peek() == Tok::Comma {
+                    self.bump();
+                } else if *self.peek() != Tok::RParen {
+                    return Err("expected ',' or ')' in parameters".to_string());
+                }
+            }
+            self.bump();
+            Ok(params)
+        })();
+        self.skip_newlines.pop();
+        let params = params?;
+        let body = self.parse_body()?;
+        Ok(Expr::Function(params, Box::new(body)))
+    }
+
+    fn parse_args(&mut self, close: Tok) -> PResult<Vec<Arg>> {
+        self.skip_newlines.push(true);
+        let args = (|| {
+            let mut args = Vec::new();
+            if *self.peek() == close {
+                self.bump();
+                return Ok(args);
+            }
+            loop {
+                let named = match (&self.toks[self.pos].tok, &self.toks[self.pos + 1].tok) {
+                    (Tok::Ident(name), Tok::Op(op)) if op == "=" => Some(name.clone()),
+                    (Tok::Str(_), Tok::Op(op)) if op == "=" => {
+                        return Err("quoted argument name".to_string());
+                    }
+                    _ => None,
+                };
+                if named.is_some() {
+                    self.pos += 2;
+                }
+                let value = if matches!(self.peek(), Tok::Comma) || *self.peek() == close {
+                    None
+                } else {
+                    Some(self.parse_expr(3)?)
+                };
+                args.push(Arg { name: named, value });
+                let next = self.bump().tok;
+                if next == close {
+                    return Ok(args);
+                }
+                if next != Tok::Comma {
+                    return Err("expected ',' between arguments".to_string());
+                }
+            }
+        })();
+        self.skip_newlines.pop();
+        args
+    }
+}
+
+pub(crate) fn unsupported_operator(op: &str) -> String {
+    match op {
+        "<<-" | "->>" => format!("superassignment `{}`", op),
+        "~" => "formula `~`".to_string(),
+        "@" => "slot access `@`".to_string(),
+        _ => format!("operator `{}`", op),
+    }
+}
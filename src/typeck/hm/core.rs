@@ -510,7 +510,7 @@ impl InferEnv {
                     HirUnOp::Neg => numeric_unary_result(&self.subst.apply(&ty)),
                 }
             }
-            HirExpr::Binary { op, lhs, rhs } => {
+            HirExpr::Binary { op, lhs, rhs, .. } => {
                 let lhs = self.infer_expr(lhs, symbols);
                 let rhs = self.infer_expr(rhs, symbols);
                 self.infer_binary(op, lhs, rhs)
@@ -131,6 +131,7 @@ pub(crate) mod tests {
             modules: vec![HirModule {
                 id: ModuleId(0),
                 path: Vec::new(),
+                unqualified_refs: Vec::new(),
                 items: vec![HirItem::Fn(HirFn {
                     id: FnId(1),
                     name: SymbolId(1),
@@ -154,6 +155,7 @@ pub(crate) mod tests {
                                     op: HirBinOp::Add,
                                     lhs: Box::new(HirExpr::Local(local)),
                                     rhs: Box::new(HirExpr::Lit(HirLit::Double(2.0))),
+                                    span: Span::default(),
                                 },
                                 span: Span::default(),
                             },
@@ -195,6 +197,7 @@ pub(crate) mod tests {
             modules: vec![HirModule {
                 id: ModuleId(0),
                 path: Vec::new(),
+                unqualified_refs: Vec::new(),
                 items: vec![HirItem::Fn(HirFn {
                     id: FnId(1),
                     name: SymbolId(1),
@@ -301,6 +304,7 @@ pub(crate) mod tests {
             modules: vec![HirModule {
                 id: ModuleId(0),
                 path: Vec::new(),
+                unqualified_refs: Vec::new(),
                 items: vec![HirItem::Fn(HirFn {
                     id: FnId(1),
                     name: SymbolId(1),
@@ -322,6 +326,7 @@ pub(crate) mod tests {
                                 op: HirBinOp::Add,
                                 lhs: Box::new(HirExpr::Local(local)),
                                 rhs: Box::new(HirExpr::Lit(HirLit::Int(1))),
+                                span: Span::default(),
                             },
                             span: Span::default(),
                         }],
@@ -360,6 +365,7 @@ pub(crate) mod tests {
             modules: vec![HirModule {
                 id: ModuleId(0),
                 path: Vec::new(),
+                unqualified_refs: Vec::new(),
                 items: vec![HirItem::Fn(HirFn {
                     id: FnId(1),
                     name: SymbolId(1),
@@ -381,6 +387,7 @@ pub(crate) mod tests {
                                 op: HirBinOp::Mod,
                                 lhs: Box::new(HirExpr::Local(local)),
                                 rhs: Box::new(HirExpr::Lit(HirLit::Int(2))),
+                                span: Span::default(),
                             },
                             span: Span::default(),
                         }],
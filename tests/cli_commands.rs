@@ -2,10 +2,13 @@ mod common;
 
 use common::{rscript_available, rscript_path, unique_dir};
 use std::fs;
+use std::io::{BufRead, BufReader, Write};
 #[cfg(unix)]
 use std::os::unix::fs::PermissionsExt;
 use std::path::PathBuf;
-use std::process::Command;
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::time::Duration;
 
 #[test]
 fn build_command_on_project_dir_builds_entry_only() {
@@ -829,6 +832,242 @@ main()
     );
 }
 
+#[cfg(unix)]
+fn write_fake_rscript(path: &std::path::Path, body: &str) {
+    fs::write(path, format!("#!/bin/sh\n{}", body)).expect("failed to write fake Rscript");
+    let mut perms = fs::metadata(path)
+        .expect("failed to stat fake Rscript")
+        .permissions();
+    perms.set_mode(0o755);
+    fs::set_permissions(path, perms).expect("failed to chmod fake Rscript");
+}
+
+#[cfg(unix)]
+#[test]
+fn run_forwards_program_args_stdin_and_exit_status() {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let sandbox_root = root.join("target").join("tests").join("cli_run");
+    fs::create_dir_all(&sandbox_root).expect("failed to create sandbox root");
+    let proj_dir = unique_dir(&sandbox_root, "program_args");
+    fs::create_dir_all(proj_dir.join("src")).expect("failed to create project dirs");
+
+    let main_src = r#"
+fn main(args: vector<str>) -> int {
+  print(args)
+  return length(args)
+}
+"#;
+    fs::write(proj_dir.join("src").join("main.rr"), main_src).expect("failed to write src/main.rr");
+
+    // The fake runner echoes what it receives after `--vanilla <gen.R>`.
+    let fake_rscript = proj_dir.join("fake_rscript.sh");
+    write_fake_rscript(
+        &fake_rscript,
+        "shift 2\nprintf 'args:%s|' \"$@\"\nprintf '\\n'\nread line\nprintf 'stdin:%s\\n' \"$line\"\nexit 3\n",
+    );
+
+    let rr_bin = PathBuf::from(env!("CARGO_BIN_EXE_RR"));
+    let mut child = Command::new(&rr_bin)
+        .current_dir(&proj_dir)
+        .args(["run", ".", "-O0", "--keep-r", "--", "first", "two words"])
+        .env("PATH", "")
+        .env("RRSCRIPT", &fake_rscript)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("failed to spawn rr run");
+    child
+        .stdin
+        .take()
+        .expect("stdin is piped")
+        .write_all(b"hello\n")
+        .expect("failed to write stdin");
+    let output = child.wait_with_output().expect("failed to wait for rr run");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(
+        output.status.code(),
+        Some(3),
+        "expected the runner exit status, stderr:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(stdout.contains("args:first|args:two words|"), "{}", stdout);
+    assert!(stdout.contains("stdin:hello"), "{}", stdout);
+
+    let generated =
+        fs::read_to_string(proj_dir.join("src").join("main.gen.R")).expect("kept artifact");
+    assert!(
+        generated.contains(
+            "base::quit(save = \"no\", status = main(base::commandArgs(trailingOnly = TRUE)))"
+        ),
+        "expected main(args) -> int to be called with the arguments and its result used as status:\n{}",
+        generated
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn run_streams_stdout_while_program_is_running() {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let sandbox_root = root.join("target").join("tests").join("cli_run");
+    fs::create_dir_all(&sandbox_root).expect("failed to create sandbox root");
+    let proj_dir = unique_dir(&sandbox_root, "streaming");
+    fs::create_dir_all(proj_dir.join("src")).expect("failed to create project dirs");
+    fs::write(
+        proj_dir.join("src").join("main.rr"),
+        "fn main() {\n  print(1)\n}\n",
+    )
+    .expect("failed to write src/main.rr");
+
+    // The second line is only printed once the test has seen the first one
+    // and created the `go` file.
+    let fake_rscript = proj_dir.join("fake_rscript.sh");
+    write_fake_rscript(
+        &fake_rscript,
+        "echo progress 1\nwhile [ ! -f go ]; do sleep 0.05; done\necho done\nexit 0\n",
+    );
+
+    let rr_bin = PathBuf::from(env!("CARGO_BIN_EXE_RR"));
+    let mut child = Command::new(&rr_bin)
+        .current_dir(&proj_dir)
+        .args(["run", ".", "-O0"])
+        .env("RRSCRIPT", &fake_rscript)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .expect("failed to spawn rr run");
+    let stdout = child.stdout.take().expect("stdout is piped");
+    let (tx, rx) = mpsc::channel();
+    let reader = std::thread::spawn(move || {
+        for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+            if tx.send(line).is_err() {
+                break;
+            }
+        }
+    });
+
+    // Compiler progress comes first on stdout; wait for the program's line.
+    let mut saw_progress = false;
+    while let Ok(line) = rx.recv_timeout(Duration::from_secs(60)) {
+        if line == "progress 1" {
+            saw_progress = true;
+            break;
+        }
+    }
+    fs::write(proj_dir.join("go"), "").expect("failed to write go marker");
+    let status = child.wait().expect("failed to wait for rr run");
+    reader.join().ok();
+    let rest = rx.iter().collect::<Vec<_>>();
+
+    assert!(
+        saw_progress,
+        "first line should arrive before the program exits"
+    );
+    assert!(status.success());
+    assert!(rest.iter().any(|line| line == "done"), "{:?}", rest);
+}
+
+#[test]
+fn build_rejects_main_with_more_than_one_parameter() {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let sandbox_root = root.join("target").join("tests").join("cli_build");
+    fs::create_dir_all(&sandbox_root).expect("failed to create sandbox root");
+    let proj_dir = unique_dir(&sandbox_root, "main_two_params");
+    fs::create_dir_all(proj_dir.join("src")).expect("failed to create project dirs");
+    fs::write(
+        proj_dir.join("src").join("main.rr"),
+        "fn main(a, b) {\n  print(a)\n}\n",
+    )
+    .expect("failed to write src/main.rr");
+
+    let rr_bin = PathBuf::from(env!("CARGO_BIN_EXE_RR"));
+    let output = Command::new(&rr_bin)
+        .args(["build"])
+        .arg(&proj_dir)
+        .arg("--out-dir")
+        .arg(proj_dir.join("build"))
+        .output()
+        .expect("failed to run rr build");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!output.status.success());
+    assert!(
+        stdout.contains("fn main() takes at most one parameter"),
+        "{}",
+        stdout
+    );
+}
+
+#[test]
+fn build_accepts_multi_parameter_main_the_file_calls_itself() {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let sandbox_root = root.join("target").join("tests").join("cli_build");
+    fs::create_dir_all(&sandbox_root).expect("failed to create sandbox root");
+    let proj_dir = unique_dir(&sandbox_root, "main_called_two_params");
+    fs::create_dir_all(proj_dir.join("src")).expect("failed to create project dirs");
+    fs::write(
+        proj_dir.join("src").join("main.rr"),
+        "fn main(a: int, b: int) {\n  print(a + b)\n}\n\nmain(1L, 2L)\n",
+    )
+    .expect("failed to write src/main.rr");
+
+    let rr_bin = PathBuf::from(env!("CARGO_BIN_EXE_RR"));
+    let output = Command::new(&rr_bin)
+        .args(["build"])
+        .arg(&proj_dir)
+        .arg("--out-dir")
+        .arg(proj_dir.join("build"))
+        .output()
+        .expect("failed to run rr build");
+    assert!(
+        output.status.success(),
+        "stdout:\n{}\nstderr:\n{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn run_main_args_and_status_with_rscript() {
+    let Some(rscript) = rscript_path() else {
+        return;
+    };
+    if !rscript_available(&rscript) {
+        return;
+    }
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let sandbox_root = root.join("target").join("tests").join("cli_run");
+    fs::create_dir_all(&sandbox_root).expect("failed to create sandbox root");
+    let proj_dir = unique_dir(&sandbox_root, "main_status_r");
+    fs::create_dir_all(proj_dir.join("src")).expect("failed to create project dirs");
+    fs::write(
+        proj_dir.join("src").join("main.rr"),
+        r#"
+fn main(args: vector<str>) -> int {
+  print(args)
+  return length(args) + 1L
+}
+"#,
+    )
+    .expect("failed to write src/main.rr");
+
+    let rr_bin = PathBuf::from(env!("CARGO_BIN_EXE_RR"));
+    let output = Command::new(&rr_bin)
+        .current_dir(&proj_dir)
+        .args(["run", ".", "-O1", "--", "x", "y"])
+        .env("RRSCRIPT", &rscript)
+        .output()
+        .expect("failed to run rr run");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(
+        output.status.code(),
+        Some(3),
+        "stderr:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(stdout.contains("\"x\" \"y\""), "{}", stdout);
+}
+
 #[test]
 fn version_flag_prints_crate_version() {
     let rr_bin = PathBuf::from(env!("CARGO_BIN_EXE_RR"));
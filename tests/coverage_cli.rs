@@ -0,0 +1,193 @@
+mod common;
+
+use common::{rscript_available, rscript_path, unique_dir};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+const PROGRAM: &str = "\
+fn classify(x: int) -> int {
+  if (x > 0L) {
+    return 1L
+  } else if (x < 0L) {
+    return -1L
+  }
+  return 0L
+}
+
+fn main() {
+  let total = 0L
+  for (i in 1L..3L) {
+    total = total + classify(i - 2L)
+  }
+  print(total)
+}
+";
+
+fn sandbox(name: &str) -> PathBuf {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("target")
+        .join("tests")
+        .join("coverage_cli");
+    fs::create_dir_all(&root).expect("failed to create sandbox root");
+    let dir = unique_dir(&root, name);
+    fs::create_dir_all(&dir).expect("failed to create sandbox");
+    fs::write(dir.join("prog.rr"), PROGRAM).expect("failed to write prog.rr");
+    dir
+}
+
+fn run_with_coverage(dir: &Path, rscript: &str, report: &str, extra: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_RR"))
+        .current_dir(dir)
+        .args(["run", "prog.rr", "--coverage", report])
+        .args(extra)
+        .env("RRSCRIPT", rscript)
+        .output()
+        .expect("failed to run RR run --coverage")
+}
+
+fn records(report: &str, prefix: &str) -> Vec<String> {
+    report
+        .lines()
+        .filter_map(|line| line.strip_prefix(prefix))
+        .map(str::to_string)
+        .collect()
+}
+
+#[test]
+fn coverage_report_is_written_without_hits_when_r_fails() {
+    let dir = sandbox("no_r");
+    let output = run_with_coverage(&dir, "/nonexistent/Rscript", "cov.info", &["--keep-r"]);
+    assert!(
+        !output.status.success(),
+        "missing Rscript must fail the run"
+    );
+
+    let lcov = fs::read_to_string(dir.join("cov.info")).expect("lcov report");
+    let source = fs::canonicalize(dir.join("prog.rr")).expect("canonical source");
+    assert!(
+        lcov.starts_with(&format!("TN:\nSF:{}\n", source.display())),
+        "{lcov}"
+    );
+    assert_eq!(
+        records(&lcov, "DA:"),
+        vec!["2,0", "3,0", "5,0", "7,0", "11,0", "12,0", "13,0", "15,0"]
+    );
+    assert_eq!(
+        records(&lcov, "BRDA:"),
+        vec!["2,0,0,-", "2,0,1,-", "4,1,0,-", "4,1,1,-"]
+    );
+    assert!(lcov.contains("LF:8\nLH:0\n"), "{lcov}");
+    assert!(lcov.ends_with("end_of_record\n"), "{lcov}");
+    assert!(!dir.join("prog.rrcov").exists());
+
+    // Kept R points `rr_mark` at the original lines, not the probed copy.
+    let generated = fs::read_to_string(dir.join("prog.gen.R")).expect("kept R");
+    assert!(generated.contains("rr_cov_hit(\"L7\")"), "{generated}");
+    assert!(generated.contains("rr_mark(7L, "), "{generated}");
+    assert!(generated.contains("return(0L)"), "{generated}");
+
+    let output = run_with_coverage(&dir, "/nonexistent/Rscript", "cov.xml", &[]);
+    assert!(!output.status.success());
+    let xml = fs::read_to_string(dir.join("cov.xml")).expect("cobertura report");
+    assert!(xml.contains("filename=\"prog.rr\""), "{xml}");
+    assert!(
+        xml.contains(
+            "<line number=\"2\" hits=\"0\" branch=\"true\" condition-coverage=\"0% (0/2)\"/>"
+        ),
+        "{xml}"
+    );
+
+    let output = Command::new(env!("CARGO_BIN_EXE_RR"))
+        .current_dir(&dir)
+        .args(["build", ".", "--coverage", "cov.info"])
+        .output()
+        .expect("failed to run RR build --coverage");
+    assert!(!output.status.success(), "build must reject --coverage");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Unknown option: --coverage"), "{stderr}");
+}
+
+#[test]
+fn coverage_counts_lines_and_branches() {
+    let rscript = match rscript_path() {
+        Some(p) if rscript_available(&p) => p,
+        _ => {
+            eprintln!("Skipping RR run --coverage: Rscript unavailable.");
+            return;
+        }
+    };
+    let dir = sandbox("hits");
+    let output = run_with_coverage(&dir, &rscript, "cov.info", &[]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        output.status.success(),
+        "stdout:\n{stdout}\nstderr:\n{stderr}"
+    );
+    assert!(stdout.contains("[1] 0"), "{stdout}");
+
+    let lcov = fs::read_to_string(dir.join("cov.info")).expect("lcov report");
+    assert_eq!(
+        records(&lcov, "DA:"),
+        vec!["2,3", "3,1", "5,1", "7,1", "11,1", "12,1", "13,3", "15,1"]
+    );
+    assert_eq!(
+        records(&lcov, "BRDA:"),
+        vec!["2,0,0,1", "2,0,1,2", "4,1,0,1", "4,1,1,1"]
+    );
+    assert!(lcov.contains("BRF:4\nBRH:4\n"), "{lcov}");
+    assert!(lcov.contains("LF:8\nLH:8\n"), "{lcov}");
+}
+
+#[test]
+fn coverage_reports_imported_project_modules() {
+    let dir = sandbox("modules");
+    fs::create_dir_all(dir.join("lib")).expect("failed to create lib dir");
+    fs::write(
+        dir.join("lib").join("clamp.rr"),
+        "\
+fn clamp(x: int) -> int {
+  if (x > 9L) {
+    return 9L
+  }
+  return x
+}
+",
+    )
+    .expect("failed to write clamp.rr");
+    fs::write(
+        dir.join("prog.rr"),
+        "\
+import \"./lib/clamp.rr\"
+
+fn main() {
+  print(clamp(12L))
+}
+",
+    )
+    .expect("failed to write prog.rr");
+
+    let output = run_with_coverage(&dir, "/nonexistent/Rscript", "cov.info", &["--keep-r"]);
+    assert!(!output.status.success());
+    let lcov = fs::read_to_string(dir.join("cov.info")).expect("lcov report");
+    let entry = fs::canonicalize(dir.join("prog.rr")).expect("canonical entry");
+    let module = fs::canonicalize(dir.join("lib").join("clamp.rr")).expect("canonical module");
+    assert_eq!(
+        records(&lcov, "SF:"),
+        vec![entry.display().to_string(), module.display().to_string()]
+    );
+    assert_eq!(records(&lcov, "DA:"), vec!["4,0", "2,0", "3,0", "5,0"]);
+    assert_eq!(records(&lcov, "BRDA:"), vec!["2,0,0,-", "2,0,1,-"]);
+    let generated = fs::read_to_string(dir.join("prog.gen.R")).expect("kept R");
+    assert!(generated.contains("rr_cov_hit(\"M1:L3\")"), "{generated}");
+
+    let output = run_with_coverage(&dir, "/nonexistent/Rscript", "cov.xml", &[]);
+    assert!(!output.status.success());
+    let xml = fs::read_to_string(dir.join("cov.xml")).expect("cobertura report");
+    assert!(xml.contains("filename=\"prog.rr\""), "{xml}");
+    assert!(
+        xml.contains("<class name=\"lib.clamp\" filename=\"lib/clamp.rr\""),
+        "{xml}"
+    );
+}
@@ -0,0 +1,290 @@
+mod common;
+
+use common::{rscript_available, rscript_path, unique_dir};
+use serde_json::{Value, json};
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use std::time::Duration;
+
+const PROGRAM: &str = "\
+fn addup(xs: vector<float>, k: int) -> float {
+  let total = 0.0
+  for (i in 1L..length(xs)) {
+    let v = xs[i] * k
+    total = total + v
+  }
+  return total
+}
+
+print(addup(c(1.0, 2.0, 3.0), 2L))
+";
+
+struct Client {
+    child: Child,
+    stdin: ChildStdin,
+    messages: Receiver<Value>,
+    seq: i64,
+    seen: Vec<Value>,
+}
+
+impl Client {
+    fn start(dir: &Path, rscript: &str) -> Self {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_RR"))
+            .current_dir(dir)
+            .arg("dap")
+            .env("RRSCRIPT", rscript)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .expect("failed to start RR dap");
+        let stdin = child.stdin.take().expect("dap stdin");
+        let stdout = child.stdout.take().expect("dap stdout");
+        let (tx, messages) = mpsc::channel();
+        thread::spawn(move || {
+            let mut reader = BufReader::new(stdout);
+            loop {
+                let mut length = 0;
+                loop {
+                    let mut header = String::new();
+                    if reader.read_line(&mut header).unwrap_or(0) == 0 {
+                        return;
+                    }
+                    let header = header.trim();
+                    if header.is_empty() {
+                        break;
+                    }
+                    if let Some(value) = header.strip_prefix("Content-Length:") {
+                        length = value.trim().parse().expect("content length");
+                    }
+                }
+                let mut body = vec![0; length];
+                reader.read_exact(&mut body).expect("message body");
+                let message: Value = serde_json::from_slice(&body).expect("json message");
+                if tx.send(message).is_err() {
+                    return;
+                }
+            }
+        });
+        Self {
+            child,
+            stdin,
+            messages,
+            seq: 0,
+            seen: Vec::new(),
+        }
+    }
+
+    fn next_message(&mut self) -> Value {
+        let message = self
+            .messages
+            .recv_timeout(Duration::from_secs(60))
+            .unwrap_or_else(|_| panic!("no message from RR dap; seen {:?}", self.seen));
+        self.seen.push(message.clone());
+        message
+    }
+
+    fn request(&mut self, command: &str, arguments: Value) -> Value {
+        self.seq += 1;
+        let body = json!({
+            "seq": self.seq,
+            "type": "request",
+            "command": command,
+            "arguments": arguments,
+        })
+        .to_string();
+        write!(self.stdin, "Content-Length: {}\r\n\r\n{}", body.len(), body)
+            .expect("write request");
+        self.stdin.flush().expect("flush request");
+        loop {
+            let message = self.next_message();
+            if message["type"] == "response" && message["request_seq"] == self.seq {
+                assert_eq!(message["command"], command);
+                return message;
+            }
+        }
+    }
+
+    fn wait_event(&mut self, event: &str) -> Value {
+        if let Some(found) = self
+            .seen
+            .iter()
+            .position(|m| m["type"] == "event" && m["event"] == event)
+        {
+            return self.seen.remove(found);
+        }
+        loop {
+            let message = self.next_message();
+            if message["type"] == "event" && message["event"] == event {
+                self.seen.pop();
+                return message;
+            }
+        }
+    }
+
+    fn finish(mut self) {
+        let response = self.request("disconnect", json!({}));
+        assert_eq!(response["success"], true);
+        let status = self.child.wait().expect("wait for RR dap");
+        assert!(status.success());
+    }
+}
+
+fn sandbox(name: &str) -> PathBuf {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("target")
+        .join("tests")
+        .join("dap_cli");
+    fs::create_dir_all(&root).expect("failed to create sandbox root");
+    let dir = unique_dir(&root, name);
+    fs::create_dir_all(&dir).expect("failed to create sandbox");
+    fs::write(dir.join("main.rr"), PROGRAM).expect("failed to write main.rr");
+    dir
+}
+
+fn breakpoint_lines(response: &Value) -> Vec<(bool, u64)> {
+    response["body"]["breakpoints"]
+        .as_array()
+        .expect("breakpoints array")
+        .iter()
+        .map(|bp| {
+            (
+                bp["verified"].as_bool().unwrap_or(false),
+                bp["line"].as_u64().unwrap_or(0),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn launch_verifies_breakpoints_and_reports_compile_errors() {
+    let dir = sandbox("launch");
+    fs::write(dir.join("bad.rr"), "let x = 1L\nprint(y)\n").expect("failed to write bad.rr");
+    let mut client = Client::start(&dir, "/nonexistent/Rscript");
+
+    let response = client.request("initialize", json!({ "adapterID": "rr" }));
+    assert_eq!(response["success"], true);
+    assert_eq!(response["body"]["supportsConfigurationDoneRequest"], true);
+
+    let response = client.request("launch", json!({ "program": "bad.rr" }));
+    assert_eq!(response["success"], false);
+    let message = response["message"].as_str().unwrap_or_default();
+    assert!(message.starts_with("bad.rr:2:"), "{message}");
+
+    let response = client.request("launch", json!({ "program": "main.rr" }));
+    assert_eq!(response["success"], true, "{response}");
+    client.wait_event("initialized");
+
+    let response = client.request(
+        "setBreakpoints",
+        json!({
+            "source": { "path": "main.rr" },
+            "breakpoints": [{ "line": 1 }, { "line": 5 }, { "line": 9 }, { "line": 20 }],
+        }),
+    );
+    assert_eq!(
+        breakpoint_lines(&response),
+        vec![(true, 2), (true, 5), (true, 10), (false, 20)]
+    );
+    let response = client.request(
+        "setBreakpoints",
+        json!({ "source": { "path": "other.rr" }, "breakpoints": [{ "line": 1 }] }),
+    );
+    assert_eq!(breakpoint_lines(&response), vec![(false, 1)]);
+
+    let response = client.request("configurationDone", json!({}));
+    assert_eq!(response["success"], false);
+    assert!(
+        response["message"]
+            .as_str()
+            .unwrap_or_default()
+            .contains("RRSCRIPT"),
+        "{response}"
+    );
+    client.wait_event("terminated");
+    assert_eq!(
+        client.request("evaluate", json!({ "expression": "1" }))["success"],
+        false
+    );
+    client.finish();
+}
+
+#[test]
+fn breakpoints_stop_with_rr_frames_and_locals() {
+    let rscript = match rscript_path() {
+        Some(p) if rscript_available(&p) => p,
+        _ => {
+            eprintln!("Skipping RR dap session: Rscript unavailable.");
+            return;
+        }
+    };
+    let dir = sandbox("session");
+    let mut client = Client::start(&dir, &rscript);
+    client.request("initialize", json!({ "adapterID": "rr" }));
+    client.request("launch", json!({ "program": "main.rr" }));
+    client.wait_event("initialized");
+    client.request(
+        "setBreakpoints",
+        json!({ "source": { "path": "main.rr" }, "breakpoints": [{ "line": 5 }] }),
+    );
+    assert_eq!(
+        client.request("configurationDone", json!({}))["success"],
+        true
+    );
+
+    let stopped = client.wait_event("stopped");
+    assert_eq!(stopped["body"]["reason"], "breakpoint");
+    let trace = client.request("stackTrace", json!({ "threadId": 1 }));
+    let frames = trace["body"]["stackFrames"]
+        .as_array()
+        .expect("frames")
+        .clone();
+    let names: Vec<&str> = frames.iter().filter_map(|f| f["name"].as_str()).collect();
+    assert_eq!(names, vec!["addup", "<top-level>"], "{trace}");
+    assert_eq!(frames[0]["line"], 5);
+    assert_eq!(frames[1]["line"], 10);
+
+    let frame = frames[0]["id"].clone();
+    let scopes = client.request("scopes", json!({ "frameId": frame }));
+    let reference = scopes["body"]["scopes"][0]["variablesReference"].clone();
+    let vars = client.request("variables", json!({ "variablesReference": reference }));
+    let vars = vars["body"]["variables"]
+        .as_array()
+        .expect("variables")
+        .clone();
+    let value_of = |name: &str| {
+        vars.iter()
+            .find(|v| v["name"] == name)
+            .and_then(|v| v["value"].as_str())
+            .map(str::to_string)
+    };
+    assert_eq!(value_of("total").as_deref(), Some("0"), "{vars:?}");
+    assert_eq!(value_of("v").as_deref(), Some("2"), "{vars:?}");
+    assert_eq!(value_of("k").as_deref(), Some("2"), "{vars:?}");
+    assert!(
+        vars.iter()
+            .all(|v| !v["name"].as_str().unwrap_or(".").starts_with('.'))
+    );
+
+    client.request("next", json!({ "threadId": 1 }));
+    assert_eq!(client.wait_event("stopped")["body"]["reason"], "step");
+    client.request(
+        "setBreakpoints",
+        json!({ "source": { "path": "main.rr" }, "breakpoints": [] }),
+    );
+    client.request("continue", json!({ "threadId": 1 }));
+    let exited = client.wait_event("exited");
+    assert_eq!(exited["body"]["exitCode"], 0);
+    let output: String = client
+        .seen
+        .iter()
+        .filter(|m| m["event"] == "output")
+        .filter_map(|m| m["body"]["output"].as_str())
+        .collect();
+    assert!(output.contains("[1] 12"), "{output}");
+    client.finish();
+}
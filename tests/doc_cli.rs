@@ -0,0 +1,169 @@
+mod common;
+
+use common::{rscript_available, rscript_path, unique_dir};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+const LIB: &str = "\
+//! Plane shapes.
+
+/// Something with an area.
+export trait Shape {
+  /// Area of the shape.
+  fn area(self) -> float
+}
+
+/// Area of a square.
+///
+/// ```rr
+/// print(square_area(3.0))
+/// ```
+export fn square_area(side: float = 1.0) -> float {
+  return side * side
+}
+
+fn hidden() -> int {
+  return 1L
+}
+";
+
+const CIRCLES: &str = "\
+import \"../lib.rr\"
+
+/// Circles are floats holding their radius.
+export impl Shape for float {
+  fn area(self) -> float {
+    return 3.0 * self * self
+  }
+}
+
+/// Compiles but is not run.
+///
+/// ```rr,no_run
+/// print(square_area(2.0))
+/// ```
+///
+/// ```rr,no_run
+/// print(missing_fn(2.0))
+/// ```
+export fn circle_area(r: float) -> float {
+  return r.area()
+}
+";
+
+fn project(name: &str) -> PathBuf {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("target")
+        .join("tests")
+        .join("doc_cli");
+    fs::create_dir_all(&root).expect("failed to create sandbox root");
+    let dir = unique_dir(&root, name);
+    fs::create_dir_all(dir.join("src").join("geo")).expect("failed to create project");
+    fs::write(dir.join("src").join("lib.rr"), LIB).expect("failed to write lib.rr");
+    fs::write(dir.join("src").join("geo").join("circles.rr"), CIRCLES)
+        .expect("failed to write circles.rr");
+    dir
+}
+
+fn rr_doc(dir: &Path, rscript: &str, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_RR"))
+        .current_dir(dir)
+        .arg("doc")
+        .args(args)
+        .env("RRSCRIPT", rscript)
+        .output()
+        .expect("failed to run RR doc")
+}
+
+#[test]
+fn doc_writes_module_pages_and_checks_examples() {
+    let dir = project("pages");
+    let output = rr_doc(&dir, "/nonexistent/Rscript", &[]);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let out = dir.join("Build").join("doc");
+    let index = fs::read_to_string(out.join("index.md")).expect("index.md");
+    assert!(
+        index.contains("- [`geo/circles`](geo.circles.md)"),
+        "{index}"
+    );
+    assert!(
+        index.contains("- [`lib`](lib.md) — Plane shapes."),
+        "{index}"
+    );
+    let lib = fs::read_to_string(out.join("lib.md")).expect("lib.md");
+    assert!(
+        lib.contains("```rr\nfn square_area(side: float = 1.0) -> float\n```\n\nArea of a square."),
+        "{lib}"
+    );
+    assert!(
+        lib.contains("- [`impl Shape for float`](geo.circles.md)"),
+        "{lib}"
+    );
+    assert!(!lib.contains("hidden"), "{lib}");
+    let html = fs::read_to_string(out.join("geo.circles.html")).expect("html page");
+    assert!(
+        html.contains("<h3><code>impl Shape for float</code></h3>"),
+        "{html}"
+    );
+
+    let custom = dir.join("api");
+    let output = rr_doc(
+        &dir,
+        "/nonexistent/Rscript",
+        &["src", "--out-dir", "api", "--test"],
+    );
+    assert!(
+        !output.status.success(),
+        "failing examples must fail the run"
+    );
+    assert!(custom.join("lib.html").is_file());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout
+        .lines()
+        .filter(|line| line.starts_with("doctest"))
+        .collect();
+    assert_eq!(
+        lines,
+        vec![
+            "doctest geo/circles::circle_area (line 19) ... ok",
+            "doctest geo/circles::circle_area (line 19) ... FAILED",
+            "doctest lib::square_area (line 14) ... FAILED",
+            "doctest result: 1 passed; 2 failed",
+        ]
+    );
+}
+
+#[test]
+fn doc_test_runs_examples_with_rscript() {
+    let rscript = match rscript_path() {
+        Some(p) if rscript_available(&p) => p,
+        _ => {
+            eprintln!("Skipping RR doc --test run: Rscript unavailable.");
+            return;
+        }
+    };
+    let dir = project("run");
+    fs::write(
+        dir.join("src").join("geo").join("circles.rr"),
+        CIRCLES.replace("print(missing_fn(2.0))", "print(circle_area(2.0))"),
+    )
+    .expect("failed to rewrite circles.rr");
+    let output = rr_doc(&dir, &rscript, &["--test"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "stdout:\n{stdout}\nstderr:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(stdout.contains("[1] 9"), "{stdout}");
+    assert!(
+        stdout.contains("doctest result: 3 passed; 0 failed"),
+        "{stdout}"
+    );
+}
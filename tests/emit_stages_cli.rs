@@ -0,0 +1,234 @@
+mod common;
+
+use common::unique_dir;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+const EMIT_SRC: &str = r#"fn scale(x) {
+  let y = x * 2.0
+  return y
+}
+
+fn shift(x) {
+  return x + 1.0
+}
+
+print(scale(c(1.0, 2.0)))
+print(shift(c(1.0, 2.0)))
+"#;
+
+const ALL_SUFFIXES: [&str; 8] = [
+    "tokens.txt",
+    "ast.txt",
+    "hir.txt",
+    "typed.txt",
+    "mir.txt",
+    "opt.mir.txt",
+    "raw.R",
+    "final.R",
+];
+
+fn sandbox(name: &str) -> PathBuf {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let sandbox_root = root.join("target").join("tests").join("emit_stages_cli");
+    fs::create_dir_all(&sandbox_root).expect("failed to create sandbox root");
+    let proj_dir = unique_dir(&sandbox_root, name);
+    fs::create_dir_all(&proj_dir).expect("failed to create project dir");
+    proj_dir
+}
+
+fn run_rr(args: &[&std::ffi::OsStr]) -> Output {
+    Command::new(PathBuf::from(env!("CARGO_BIN_EXE_RR")))
+        .args(args)
+        .output()
+        .expect("failed to run RR CLI")
+}
+
+fn assert_success(output: &Output) {
+    assert!(
+        output.status.success(),
+        "RR CLI failed:\nstdout:\n{}\nstderr:\n{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+fn read(path: &Path) -> String {
+    fs::read_to_string(path).unwrap_or_else(|e| panic!("failed to read {}: {e}", path.display()))
+}
+
+fn assert_scale_only_dumps(emit_dir: &Path, stem: &str, module: &str) {
+    for suffix in ALL_SUFFIXES {
+        let path = emit_dir.join(format!("{stem}.{suffix}"));
+        assert!(path.is_file(), "missing dump {}", path.display());
+    }
+    let tokens = read(&emit_dir.join(format!("{stem}.tokens.txt")));
+    assert!(tokens.contains("1:1-1:3 Fn"), "tokens:\n{tokens}");
+    assert!(tokens.contains("Ident(\"scale\")"), "tokens:\n{tokens}");
+    assert!(tokens.contains("Ident(\"shift\")"), "tokens are whole-file");
+
+    let header = format!("// module: {module}\n");
+    assert!(tokens.starts_with(&header), "tokens:\n{tokens}");
+
+    let ast = read(&emit_dir.join(format!("{stem}.ast.txt")));
+    assert!(ast.starts_with(&header), "ast:\n{ast}");
+    assert!(ast.contains("\"scale\""), "ast:\n{ast}");
+    assert!(
+        ast.contains("FnDecl(\"scale\")(\"x\")\n  Let(\"y\")\n"),
+        "ast:\n{ast}"
+    );
+    assert!(!ast.contains("\"shift\""), "ast should be filtered:\n{ast}");
+
+    let hir = read(&emit_dir.join(format!("{stem}.hir.txt")));
+    assert!(hir.starts_with(&header), "hir:\n{hir}");
+    assert!(hir.contains("Fn("), "hir:\n{hir}");
+    assert!(hir.contains("Fn(scale)(x)\n"), "hir:\n{hir}");
+    assert!(hir.contains("      Local(x)\n"), "hir:\n{hir}");
+    assert!(hir.contains("    Local(y)\n"), "hir:\n{hir}");
+    for id in ["SymbolId", "LocalId", "FnId"] {
+        assert!(!ast.contains(id), "ast should not show {id}:\n{ast}");
+        assert!(!hir.contains(id), "hir should not show {id}:\n{hir}");
+    }
+
+    let typed = read(&emit_dir.join(format!("{stem}.typed.txt")));
+    assert!(typed.contains("fn scale(x"), "typed:\n{typed}");
+    assert!(typed.contains("  2:11 y: dbl"), "typed:\n{typed}");
+    assert!(
+        !typed.contains("fn shift("),
+        "typed should be filtered:\n{typed}"
+    );
+
+    for suffix in ["mir.txt", "opt.mir.txt"] {
+        let mir = read(&emit_dir.join(format!("{stem}.{suffix}")));
+        assert!(mir.contains("// scale\nfn "), "{suffix}:\n{mir}");
+        assert!(
+            !mir.contains("// shift"),
+            "{suffix} should be filtered:\n{mir}"
+        );
+    }
+
+    let raw = read(&emit_dir.join(format!("{stem}.raw.R")));
+    assert!(raw.contains("function("), "raw R:\n{raw}");
+}
+
+#[test]
+fn emit_all_writes_filtered_stage_dumps_for_single_file_compile() {
+    let proj_dir = sandbox("single");
+    let main_path = proj_dir.join("main.rr");
+    fs::write(&main_path, EMIT_SRC).expect("failed to write main.rr");
+    let out_file = proj_dir.join("out.R");
+    let emit_dir = proj_dir.join("dumps");
+
+    let output = run_rr(&[
+        main_path.as_os_str(),
+        "-o".as_ref(),
+        out_file.as_os_str(),
+        "--no-runtime".as_ref(),
+        "--emit".as_ref(),
+        "all".as_ref(),
+        "--emit-dir".as_ref(),
+        emit_dir.as_os_str(),
+        "--emit-fn".as_ref(),
+        "scale".as_ref(),
+    ]);
+    assert_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Emitted mir-opt -> "), "stdout:\n{stdout}");
+
+    assert_scale_only_dumps(&emit_dir, "main", "main.rr");
+    assert_eq!(
+        read(&emit_dir.join("main.final.R")),
+        read(&out_file),
+        "final.R should match the compiled output"
+    );
+}
+
+#[test]
+fn emit_in_build_mirrors_output_layout() {
+    let proj_dir = sandbox("build");
+    let src_dir = proj_dir.join("src");
+    fs::create_dir_all(src_dir.join("nested")).expect("failed to create src dir");
+    fs::write(src_dir.join("nested").join("calc.rr"), EMIT_SRC).expect("failed to write calc.rr");
+    let out_dir = proj_dir.join("out");
+    let emit_dir = proj_dir.join("dumps");
+
+    let output = run_rr(&[
+        "build".as_ref(),
+        src_dir.as_os_str(),
+        "--out-dir".as_ref(),
+        out_dir.as_os_str(),
+        "--emit".as_ref(),
+        "all".as_ref(),
+        "--emit-dir".as_ref(),
+        emit_dir.as_os_str(),
+        "--emit-fn".as_ref(),
+        "scale".as_ref(),
+    ]);
+    assert_success(&output);
+
+    let dump_dir = emit_dir.join("nested");
+    assert_scale_only_dumps(&dump_dir, "calc", "calc.rr");
+    assert_eq!(
+        read(&dump_dir.join("calc.final.R")),
+        read(&out_dir.join("nested").join("calc.R")),
+        "final.R should match the built output"
+    );
+}
+
+#[test]
+fn emit_typed_lists_only_user_functions() {
+    let proj_dir = sandbox("typed");
+    let main_path = proj_dir.join("main.rr");
+    fs::write(&main_path, EMIT_SRC).expect("failed to write main.rr");
+    let emit_dir = proj_dir.join("dumps");
+
+    let output = run_rr(&[
+        main_path.as_os_str(),
+        "-o".as_ref(),
+        proj_dir.join("out.R").as_os_str(),
+        "--no-runtime".as_ref(),
+        "--emit".as_ref(),
+        "typed".as_ref(),
+        "--emit-dir".as_ref(),
+        emit_dir.as_os_str(),
+    ]);
+    assert_success(&output);
+
+    let typed = read(&emit_dir.join("main.typed.txt"));
+    assert!(typed.contains("fn scale(x"), "typed:\n{typed}");
+    assert!(typed.contains("  2:11 y: dbl"), "typed:\n{typed}");
+    assert!(typed.contains("fn shift(x"), "typed:\n{typed}");
+    assert!(!typed.contains("Sym_"), "typed:\n{typed}");
+}
+
+#[test]
+fn emit_rejects_unknown_stage_and_orphan_options() {
+    let output = run_rr(&[
+        "main.rr".as_ref(),
+        "--emit".as_ref(),
+        "mir,bytecode".as_ref(),
+    ]);
+    assert!(!output.status.success());
+    let combined = format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(
+        combined.contains("unknown --emit stage 'bytecode'"),
+        "unexpected output:\n{combined}"
+    );
+
+    let output = run_rr(&["main.rr".as_ref(), "--emit-fn".as_ref(), "scale".as_ref()]);
+    assert!(!output.status.success());
+    let combined = format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(
+        combined.contains("--emit-dir and --emit-fn need --emit <stages>"),
+        "unexpected output:\n{combined}"
+    );
+}
@@ -0,0 +1,129 @@
+mod common;
+
+use common::{normalize, rscript_available, rscript_path, run_rscript, unique_dir};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+const SCRIPT: &str = r#"# Rolling summary of a numeric vector.
+library(stats)
+
+window_mean <- function(v, k = 2) {
+  out <- numeric(length(v))
+  for (i in 1:length(v)) {
+    lo <- max(1, i - k + 1)
+    out[i] <- mean(v[lo:i])
+  }
+  out
+}
+
+values <- c(4, 8, 15, 16, 23, 42)
+smooth <- window_mean(values)
+names(smooth) <- NULL
+tag <- if (median(values) > 10) "wide" else "narrow"
+print(smooth)
+print(tag)
+"#;
+
+fn sandbox(name: &str) -> PathBuf {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("target")
+        .join("tests")
+        .join("import_r_cli");
+    fs::create_dir_all(&root).expect("failed to create sandbox root");
+    let dir = unique_dir(&root, name);
+    fs::create_dir_all(&dir).expect("failed to create sandbox");
+    dir
+}
+
+fn rr(dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_RR"))
+        .current_dir(dir)
+        .args(args)
+        .env("NO_COLOR", "1")
+        .output()
+        .expect("failed to run RR")
+}
+
+#[test]
+fn import_r_writes_rr_source_and_reports_wrapped_statements() {
+    let dir = sandbox("translate");
+    fs::write(dir.join("rolling.R"), SCRIPT).expect("failed to write rolling.R");
+
+    let output = rr(&dir, &["import-r", "rolling.R"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        output.status.success(),
+        "stdout:\n{stdout}\nstderr:\n{stderr}"
+    );
+    assert!(
+        stdout.contains("rolling.R:15: wrapped in unsafe r: replacement call `names(...) <-`"),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("translated source compiles with the strict pipeline"),
+        "{stdout}"
+    );
+
+    let rr_source = fs::read_to_string(dir.join("rolling.rr")).expect("rolling.rr");
+    for expected in [
+        "// Rolling summary of a numeric vector.",
+        "import r { median } from \"stats\"",
+        "fn window_mean(v, k = 2.0) {",
+        "  let out <- numeric(length(v))",
+        "  for (i in 1L..length(v)) {",
+        "  let lo = NULL",
+        "let tag = NULL",
+        "unsafe r {\n  names(smooth) <- NULL\n}",
+    ] {
+        assert!(
+            rr_source.contains(expected),
+            "missing {expected:?}:\n{rr_source}"
+        );
+    }
+
+    let output = rr(&dir, &["import-r", "rolling.R", "-o", "custom.rr"]);
+    assert!(output.status.success());
+    assert_eq!(
+        fs::read_to_string(dir.join("custom.rr")).expect("custom.rr"),
+        rr_source
+    );
+}
+
+#[test]
+fn import_r_fails_when_the_translation_does_not_compile() {
+    let dir = sandbox("broken");
+    fs::write(dir.join("broken.R"), "f <- function(a) a\nprint(f(1, 2))\n").expect("write");
+    let output = rr(&dir, &["import-r", "broken.R"]);
+    assert!(!output.status.success());
+    assert!(dir.join("broken.rr").is_file());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("translated source does not compile"),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn import_r_output_matches_r_when_rscript_is_available() {
+    let rscript = match rscript_path() {
+        Some(p) if rscript_available(&p) => p,
+        _ => {
+            eprintln!("Skipping RR import-r run: Rscript unavailable.");
+            return;
+        }
+    };
+    let dir = sandbox("run");
+    fs::write(dir.join("rolling.R"), SCRIPT).expect("failed to write rolling.R");
+    let expected = run_rscript(&rscript, &dir.join("rolling.R"));
+    assert_eq!(expected.status, 0, "{}", expected.stderr);
+
+    let output = rr(&dir, &["import-r", "rolling.R"]);
+    assert!(output.status.success());
+    let output = rr(&dir, &["rolling.rr", "-o", "rolling.out.R"]);
+    assert!(output.status.success());
+    let actual = run_rscript(&rscript, &dir.join("rolling.out.R"));
+    assert_eq!(actual.status, 0, "{}", actual.stderr);
+    assert_eq!(normalize(&actual.stdout), normalize(&expected.stdout));
+}
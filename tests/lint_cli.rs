@@ -0,0 +1,167 @@
+mod common;
+
+use common::unique_dir;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+const LINT_FIXTURE: &str = r#"
+import r { median } from "stats"
+
+fn addv(x: vector<float>, y: vector<float>) -> vector<float> {
+  let unused = 3L
+  return x + y
+  print(x)
+}
+
+fn check(v) {
+  let n = 1L
+  for (i in 1L..3L) {
+    let n = i
+    print(n)
+  }
+  let missing = v == NA
+  print(missing)
+  eval(parse(text = "1"))
+  return n
+}
+
+print(check(1L))
+print(addv(c(1.0, 2.0), c(3.0, 4.0)))
+print(c(1.0, 2.0) + c(1.0, 2.0, 3.0))
+"#;
+
+fn run_lint(dir: &Path, target: &str) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_RR"))
+        .current_dir(dir)
+        .args(["lint", target])
+        .env("NO_COLOR", "1")
+        .output()
+        .expect("failed to run RR lint")
+}
+
+fn sandbox(name: &str) -> PathBuf {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("target")
+        .join("tests")
+        .join("lint_cli");
+    fs::create_dir_all(&root).expect("failed to create sandbox root");
+    let dir = unique_dir(&root, name);
+    fs::create_dir_all(dir.join("src")).expect("failed to create project dir");
+    dir
+}
+
+#[test]
+fn lint_reports_every_initial_lint_as_warning() {
+    let dir = sandbox("defaults");
+    fs::write(dir.join("src").join("main.rr"), LINT_FIXTURE).expect("failed to write fixture");
+
+    let output = run_lint(&dir, ".");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "warnings alone should not fail RR lint:\n{stdout}\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    for code in [
+        "W1001", "W1002", "W1003", "W1004", "W1005", "W1006", "W1007",
+    ] {
+        assert!(
+            stdout.contains(&format!("warning[{code}]")),
+            "missing {code}:\n{stdout}"
+        );
+    }
+    assert!(stdout.contains("=> `is.na(v)`"), "{stdout}");
+    assert!(
+        stdout.contains("call to `eval` forces `check` onto the hybrid fallback path"),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("Linted 1 file(s): 8 warning(s), 0 error(s)"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn rr_mod_lint_levels_allow_and_deny() {
+    let dir = sandbox("levels");
+    fs::write(dir.join("src").join("main.rr"), LINT_FIXTURE).expect("failed to write fixture");
+    fs::write(
+        dir.join("rr.mod"),
+        "module github.com/example/linted\n\nlint (\n    hybrid_fallback allow\n    na_comparison deny\n    unused_import allow\n)\n",
+    )
+    .expect("failed to write rr.mod");
+
+    let output = run_lint(&dir, "src/main.rr");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!output.status.success(), "deny lint should fail:\n{stdout}");
+    assert!(stdout.contains("error[W1004]"), "{stdout}");
+    assert!(!stdout.contains("W1002"), "{stdout}");
+    assert!(!stdout.contains("W1007"), "{stdout}");
+    assert!(stdout.contains("warning[W1001]"), "{stdout}");
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("Lint failed for 1 file(s)"),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    fs::write(
+        dir.join("rr.mod"),
+        "module github.com/example/linted\n\nlint no_such_lint deny\n",
+    )
+    .expect("failed to write rr.mod");
+    let output = run_lint(&dir, ".");
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("unknown lint 'no_such_lint'"),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn length_recycling_only_warns_on_known_mismatched_lengths() {
+    let dir = sandbox("recycling");
+    fs::write(
+        dir.join("src").join("main.rr"),
+        r#"
+fn addv(x: vector<float>, y: vector<float>) -> vector<float> {
+  return x + y
+}
+
+fn main() {
+  let a = c(1.0, 2.0)
+  let b = c(1.0, 2.0, 3.0)
+  print(a + b)
+  print(numeric(5L) * numeric(3L))
+  print(addv(a, a))
+  print(a * c(3.0, 4.0))
+}
+
+main()
+"#,
+    )
+    .expect("failed to write fixture");
+
+    let output = run_lint(&dir, ".");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{stdout}");
+    assert!(
+        stdout.contains("vector operands have lengths 2 and 3")
+            && stdout.contains("src/main.rr:9:9 (MIR)"),
+        "`a + b` should warn at the binary expression:\n{stdout}"
+    );
+    assert!(
+        stdout.contains("vector operands have lengths 5 and 3")
+            && stdout.contains("src/main.rr:10:9 (MIR)"),
+        "`numeric(5L) * numeric(3L)` should warn:\n{stdout}"
+    );
+    assert!(
+        !stdout.contains("src/main.rr:3:"),
+        "`x + y` on two vector parameters must stay silent:\n{stdout}"
+    );
+    assert!(
+        stdout.contains("Linted 1 file(s): 2 warning(s), 0 error(s)"),
+        "{stdout}"
+    );
+}
@@ -0,0 +1,138 @@
+mod common;
+
+use common::unique_dir;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+fn run_mir_opt(input: &Path, passes: Option<&str>) -> Output {
+    let mut cmd = Command::new(env!("CARGO_BIN_EXE_RR"));
+    cmd.arg("mir-opt").arg(input);
+    if let Some(passes) = passes {
+        cmd.arg("--passes").arg(passes);
+    }
+    cmd.output().expect("failed to run RR mir-opt")
+}
+
+fn directive<'a>(line: &'a str, name: &str) -> Option<&'a str> {
+    line.trim()
+        .strip_prefix("//")
+        .map(str::trim_start)
+        .and_then(|rest| rest.strip_prefix(name))
+        .and_then(|rest| rest.strip_prefix(':'))
+        .map(str::trim)
+}
+
+/// A small FileCheck: `CHECK` matches in order, `CHECK-NEXT` must match the
+/// line right after the previous match, and `CHECK-NOT` must not appear
+/// between the surrounding matches.
+fn file_check(case: &str, checks: &str, output: &str) {
+    let lines: Vec<&str> = output.lines().collect();
+    let mut pos = 0usize;
+    let mut pending_not: Vec<&str> = Vec::new();
+    let assert_absent = |pending: &mut Vec<&str>, range: &[&str]| {
+        for pattern in pending.drain(..) {
+            if let Some(hit) = range.iter().find(|line| line.contains(pattern)) {
+                panic!("{case}: CHECK-NOT '{pattern}' matched '{hit}'\n{output}");
+            }
+        }
+    };
+    for check in checks.lines() {
+        if let Some(pattern) = directive(check, "CHECK-NOT") {
+            pending_not.push(pattern);
+        } else if let Some(pattern) = directive(check, "CHECK-NEXT") {
+            let line = lines.get(pos).copied().unwrap_or_default();
+            assert!(
+                line.contains(pattern),
+                "{case}: CHECK-NEXT '{pattern}' did not match '{line}'\n{output}"
+            );
+            pos += 1;
+        } else if let Some(pattern) = directive(check, "CHECK") {
+            let Some(offset) = lines[pos..].iter().position(|line| line.contains(pattern)) else {
+                panic!("{case}: CHECK '{pattern}' not found after line {pos}\n{output}");
+            };
+            assert_absent(&mut pending_not, &lines[pos..pos + offset]);
+            pos += offset + 1;
+        }
+    }
+    assert_absent(&mut pending_not, &lines[pos..]);
+}
+
+#[test]
+fn mir_opt_file_check_cases() {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("mir_opt");
+    let mut cases: Vec<PathBuf> = fs::read_dir(&dir)
+        .expect("failed to read tests/mir_opt")
+        .map(|entry| entry.expect("failed to read case entry").path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "mir"))
+        .collect();
+    cases.sort();
+    assert!(!cases.is_empty(), "no .mir cases under {}", dir.display());
+
+    for case in cases {
+        let name = case.display().to_string();
+        let src = fs::read_to_string(&case).expect("failed to read case");
+        let passes = src.lines().find_map(|line| directive(line, "PASSES"));
+        let output = run_mir_opt(&case, passes);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(
+            output.status.success(),
+            "{name}: RR mir-opt failed\nstderr:\n{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        file_check(&name, &src, &stdout);
+    }
+}
+
+#[test]
+fn mir_opt_output_reparses_to_itself() {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let sandbox = unique_dir(&root.join("target").join("tests"), "mir_opt_roundtrip");
+    fs::create_dir_all(&sandbox).expect("failed to create sandbox");
+    let case = root
+        .join("tests")
+        .join("mir_opt")
+        .join("sccp_fold_branch.mir");
+    let first = run_mir_opt(&case, Some("sccp,simplify_cfg"));
+    assert!(first.status.success());
+    let printed = sandbox.join("printed.mir");
+    fs::write(&printed, &first.stdout).expect("failed to write printed MIR");
+    let second = run_mir_opt(&printed, None);
+    assert!(second.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&first.stdout),
+        String::from_utf8_lossy(&second.stdout)
+    );
+}
+
+#[test]
+fn mir_opt_rejects_bad_input_and_passes() {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let sandbox = unique_dir(&root.join("target").join("tests"), "mir_opt_errors");
+    fs::create_dir_all(&sandbox).expect("failed to create sandbox");
+    let input = sandbox.join("bad.mir");
+    fs::write(&input, "fn f() {\nbb0:\n  return %3\n}\n").expect("failed to write input");
+    let output = run_mir_opt(&input, None);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success());
+    assert!(
+        stderr.contains("line 3: reference to undefined value %3"),
+        "stderr:\n{stderr}"
+    );
+
+    let case = root
+        .join("tests")
+        .join("mir_opt")
+        .join("gvn_pure_binop.mir");
+    for (passes, needle) in [
+        ("inline", "whole program"),
+        ("frobnicate", "unknown pass 'frobnicate'"),
+    ] {
+        let output = run_mir_opt(&case, Some(passes));
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(!output.status.success(), "{passes} should be rejected");
+        assert!(stderr.contains(needle), "stderr:\n{stderr}");
+    }
+}
@@ -109,3 +109,115 @@ fn leaf_value() {
     assert!(invalidated_profile.contains("\"parsed_modules\": 2"));
     assert!(invalidated_profile.contains("\"cached_modules\": 1"));
 }
+
+#[test]
+fn cached_module_selection_is_rechecked_against_changed_exports() {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let sandbox_root = root
+        .join("target")
+        .join("tests")
+        .join("module_export_cache_invalidation");
+    fs::create_dir_all(&sandbox_root).expect("failed to create sandbox root");
+    let proj_dir = unique_dir(&sandbox_root, "selected");
+    fs::create_dir_all(&proj_dir).expect("failed to create project dir");
+    let cache_dir = proj_dir.join(".rr-cache");
+
+    let main_path = proj_dir.join("main.rr");
+    let mid_path = proj_dir.join("mid.rr");
+    let leaf_path = proj_dir.join("leaf.rr");
+    fs::write(
+        &main_path,
+        r#"
+import { mid_value } from "./mid.rr"
+
+fn main() {
+  print(mid_value())
+}
+main()
+"#,
+    )
+    .expect("failed to write main.rr");
+    fs::write(
+        &mid_path,
+        r#"
+import { leaf_value as leaf } from "./leaf.rr"
+
+export fn mid_value() {
+  return leaf()
+}
+"#,
+    )
+    .expect("failed to write mid.rr");
+    fs::write(
+        &leaf_path,
+        r#"
+export fn leaf_value() {
+  return 10L
+}
+"#,
+    )
+    .expect("failed to write leaf.rr");
+
+    let out_file = proj_dir.join("out.R");
+    let profile_cached = proj_dir.join("cached-profile.json");
+    let rr_bin = PathBuf::from(env!("CARGO_BIN_EXE_RR"));
+    let compile = |profile: &PathBuf| {
+        Command::new(&rr_bin)
+            .arg(&main_path)
+            .arg("-o")
+            .arg(&out_file)
+            .arg("-O1")
+            .arg("--no-runtime")
+            .arg("--no-incremental")
+            .arg("--profile-compile-out")
+            .arg(profile)
+            .env("RR_INCREMENTAL_CACHE_DIR", &cache_dir)
+            .output()
+            .expect("failed to run module export cache compile")
+    };
+
+    for _ in 0..2 {
+        let output = compile(&profile_cached);
+        assert!(
+            output.status.success(),
+            "module export cache compile failed"
+        );
+    }
+    let cached_profile =
+        fs::read_to_string(&profile_cached).expect("failed to read cached profile");
+    assert!(cached_profile.contains("\"cached_modules\": 2"));
+
+    let artifacts = fs::read_dir(cache_dir.join("modules"))
+        .expect("module cache dir should exist")
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .map(|path| fs::read_to_string(path).expect("failed to read module artifact"))
+        .collect::<Vec<_>>();
+    assert!(
+        artifacts
+            .iter()
+            .any(|text| text.contains("\"selected_imports\"") && text.contains("\"./leaf.rr\"")),
+        "mid.rr artifact should record the names it selects from leaf.rr"
+    );
+
+    fs::write(
+        &leaf_path,
+        r#"
+export fn leaf_total() {
+  return 10L
+}
+"#,
+    )
+    .expect("failed to update leaf.rr");
+
+    let output = compile(&proj_dir.join("changed-profile.json"));
+    assert!(
+        !output.status.success(),
+        "cached mid.rr must not keep importing a name leaf.rr stopped exporting"
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("module './leaf.rr' does not export 'leaf_value'"),
+        "{}",
+        stdout
+    );
+}
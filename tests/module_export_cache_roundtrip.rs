@@ -73,7 +73,7 @@ fn square(x) {
     let artifact_text =
         fs::read_to_string(&artifacts[0]).expect("failed to read module artifact json");
     assert!(artifact_text.contains("\"schema\": \"rr-module-artifact\""));
-    assert!(artifact_text.contains("\"schema_version\": 2"));
+    assert!(artifact_text.contains("\"schema_version\": 3"));
     assert!(artifact_text.contains("\"public_symbols\""));
     assert!(artifact_text.contains("\"public_function_arities\""));
     assert!(artifact_text.contains("\"emit_roots\""));
@@ -181,7 +181,7 @@ main()
     );
     let artifact_text = fs::read_to_string(&artifacts[0]).expect("read module artifact json");
     assert!(artifact_text.contains("\"source_metadata\""));
-    assert!(artifact_text.contains("\"schema_version\": 2"));
+    assert!(artifact_text.contains("\"schema_version\": 3"));
     assert!(artifact_text.contains("Physical"));
     assert!(artifact_text.contains("energy_of"));
 
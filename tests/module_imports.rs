@@ -0,0 +1,445 @@
+mod common;
+
+use common::unique_dir;
+use rr::compiler::{OptLevel, compile};
+use rr::error::{RRCode, RRException};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const INNER: &str = r#"
+export fn solve(x: float) -> float {
+  return x * 2.0
+}
+
+fn hidden(x: float) -> float {
+  return x
+}
+"#;
+
+const PHYSICS: &str = r#"
+export { solve } from "./inner.rr"
+
+export fn Body(m: float) -> float {
+  return m + 1.0
+}
+
+export fn other(x: float) -> float {
+  return x - 1.0
+}
+"#;
+
+fn project(name: &str, files: &[(&str, &str)]) -> PathBuf {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("target")
+        .join("tests")
+        .join("module_imports");
+    fs::create_dir_all(&root).expect("failed to create sandbox root");
+    let proj_dir = unique_dir(&root, name);
+    fs::create_dir_all(&proj_dir).expect("failed to create project dir");
+    for (file, src) in files {
+        fs::write(proj_dir.join(file), src).expect("failed to write module");
+    }
+    proj_dir
+}
+
+fn compile_main(proj_dir: &Path, main_src: &str) -> Result<String, RRException> {
+    let main_path = proj_dir.join("main.rr");
+    fs::write(&main_path, main_src).expect("failed to write main.rr");
+    compile(&main_path.to_string_lossy(), main_src, OptLevel::O0).map(|(code, _map)| code)
+}
+
+fn messages(err: &RRException) -> Vec<String> {
+    std::iter::once(err)
+        .chain(err.related.iter())
+        .map(|e| e.message.to_string())
+        .collect()
+}
+
+#[test]
+fn named_namespace_and_re_exported_imports_resolve_to_module_functions() {
+    let proj_dir = project("resolve", &[("inner.rr", INNER), ("physics.rr", PHYSICS)]);
+    let code = compile_main(
+        &proj_dir,
+        r#"
+import { solve, Body as PhysBody } from "./physics.rr"
+import * as phys from "./physics.rr"
+
+fn main() {
+  print(solve(3.0))
+  print(PhysBody(1.0))
+  print(phys.solve(5.0))
+  print(phys.Body(2.0))
+}
+main()
+"#,
+    )
+    .expect("selective imports should compile");
+
+    // Both functions are small enough to inline, so each call shows up as the
+    // body of the function it resolved to.
+    for call in ["3.0 * 2.0", "1.0 + 1.0", "5.0 * 2.0", "2.0 + 1.0"] {
+        assert!(code.contains(call), "missing `{}`:\n{}", call, code);
+    }
+    assert!(
+        !code.contains("PhysBody") && !code.contains("phys."),
+        "import aliases must not reach the emitted R:\n{}",
+        code
+    );
+}
+
+#[test]
+fn selecting_a_name_the_module_does_not_export_is_an_error() {
+    let proj_dir = project(
+        "not_exported",
+        &[("inner.rr", INNER), ("physics.rr", PHYSICS)],
+    );
+    let err = compile_main(
+        &proj_dir,
+        r#"
+import { hidden } from "./inner.rr"
+import * as phys from "./physics.rr"
+
+print(hidden(1.0))
+print(phys.missing(1.0))
+"#,
+    )
+    .expect_err("unexported names should be rejected");
+
+    assert!(matches!(err.code, RRCode::E1002));
+    let messages = messages(&err);
+    assert!(
+        messages
+            .iter()
+            .any(|m| m.contains("module './inner.rr' does not export 'hidden'")),
+        "{:?}",
+        messages
+    );
+    assert!(
+        messages
+            .iter()
+            .any(|m| m.contains("module './physics.rr' does not export 'missing'")),
+        "{:?}",
+        messages
+    );
+}
+
+#[test]
+fn namespace_members_are_not_visible_unqualified() {
+    let proj_dir = project("ns_unqualified", &[("inner.rr", INNER)]);
+    let err = compile_main(
+        &proj_dir,
+        r#"
+import * as inn from "./inner.rr"
+
+print(inn.solve(1.0))
+print(hidden(1.0))
+"#,
+    )
+    .expect_err("a private function behind a namespace import should not resolve");
+
+    assert!(matches!(err.code, RRCode::E1002));
+    assert!(
+        err.message.contains("function 'hidden' from '")
+            && err.message.contains("is not imported here"),
+        "{}",
+        err.message
+    );
+    assert!(
+        err.notes
+            .iter()
+            .any(|n| n.contains("'hidden' is not exported by")),
+        "{:?}",
+        err.notes
+    );
+
+    let err = compile_main(
+        &proj_dir,
+        r#"
+import * as inn from "./inner.rr"
+
+print(solve(1.0))
+"#,
+    )
+    .expect_err("a namespace member should only resolve through its alias");
+    assert!(err.message.contains("function 'solve'"), "{}", err.message);
+    assert!(
+        err.helps.iter().any(|h| h.contains("`inn.solve`")),
+        "{:?}",
+        err.helps
+    );
+}
+
+#[test]
+fn unselected_names_are_not_visible_unqualified() {
+    let proj_dir = project(
+        "unselected",
+        &[("inner.rr", INNER), ("physics.rr", PHYSICS)],
+    );
+    let err = compile_main(
+        &proj_dir,
+        r#"
+import { solve } from "./physics.rr"
+
+print(solve(1.0))
+print(other(1.0))
+"#,
+    )
+    .expect_err("names left out of `import { ... }` should not resolve");
+
+    assert!(matches!(err.code, RRCode::E1002));
+    assert!(
+        err.message.contains("function 'other' from '")
+            && err.message.contains("is not imported here"),
+        "{}",
+        err.message
+    );
+
+    compile_main(
+        &proj_dir,
+        r#"
+import "./physics.rr"
+
+print(other(1.0))
+"#,
+    )
+    .expect("a whole-module import still exposes every function");
+}
+
+#[test]
+fn function_defined_by_two_modules_is_an_error() {
+    let proj_dir = project(
+        "duplicate",
+        &[
+            ("inner.rr", INNER),
+            (
+                "other.rr",
+                r#"
+export fn solve(x: float) -> float {
+  return x + 1.0
+}
+"#,
+            ),
+        ],
+    );
+    let err = compile_main(
+        &proj_dir,
+        r#"
+import { solve } from "./inner.rr"
+import * as other from "./other.rr"
+
+print(solve(1.0))
+"#,
+    )
+    .expect_err("two modules defining the same function should be rejected");
+
+    assert!(matches!(err.code, RRCode::E1002));
+    assert!(
+        err.message.contains("function 'solve' is defined in both"),
+        "{}",
+        err.message
+    );
+
+    // Aliases only rename at the call site; both modules still define the
+    // R function `solve`.
+    let err = compile_main(
+        &proj_dir,
+        r#"
+import { solve as solve_a } from "./inner.rr"
+import * as other from "./other.rr"
+
+print(solve_a(1.0))
+print(other.solve(1.0))
+"#,
+    )
+    .expect_err("import aliases do not keep same-named module functions apart");
+    assert!(
+        err.message.contains("function 'solve' is defined in both"),
+        "{}",
+        err.message
+    );
+    assert!(
+        err.notes
+            .iter()
+            .any(|n| n.contains("cannot tell them apart")),
+        "{:?}",
+        err.notes
+    );
+    assert!(
+        err.helps
+            .iter()
+            .any(|h| h.contains("rename one of the functions")),
+        "{:?}",
+        err.helps
+    );
+}
+
+const SHAPES: &str = r#"
+export trait Area {
+  fn area(self: Self) -> float
+}
+
+export impl Area for Square {
+  fn area(self: Square) -> float {
+    self.side * self.side
+  }
+}
+
+trait Hidden {
+  fn hidden(self: Self) -> float
+}
+"#;
+
+#[test]
+fn exported_traits_can_be_selected_and_renamed() {
+    let proj_dir = project("traits", &[("shapes.rr", SHAPES)]);
+    let code = compile_main(
+        &proj_dir,
+        r#"
+import { Area as PhysArea } from "./shapes.rr"
+
+impl PhysArea for Circle {
+  fn area(self: Circle) -> float {
+    self.r * self.r * 3.0
+  }
+}
+
+fn total<T>(x: T) -> float where T: PhysArea {
+  x.area()
+}
+
+fn main() {
+  let s: Square = {side: 2.0}
+  let c: Circle = {r: 1.0}
+  let d: dyn PhysArea = c
+  print(total(s))
+  print(PhysArea::area(c))
+  print(d.area())
+}
+main()
+"#,
+    )
+    .expect("a renamed trait import should compile");
+    assert!(
+        !code.contains("PhysArea"),
+        "trait import aliases must not reach the emitted R:\n{}",
+        code
+    );
+
+    compile_main(
+        &proj_dir,
+        r#"
+import { Area } from "./shapes.rr"
+
+fn total<T>(x: T) -> float where T: Area {
+  x.area()
+}
+
+fn main() {
+  let s: Square = {side: 2.0}
+  print(total(s))
+}
+main()
+"#,
+    )
+    .expect("an exported trait can be selected under its own name");
+
+    let err = compile_main(
+        &proj_dir,
+        r#"
+import { Hidden } from "./shapes.rr"
+"#,
+    )
+    .expect_err("a private trait is not exported");
+    assert!(
+        err.message
+            .contains("module './shapes.rr' does not export 'Hidden'"),
+        "{}",
+        err.message
+    );
+    assert!(
+        err.helps
+            .iter()
+            .any(|h| h.contains("`export trait Hidden`")),
+        "{:?}",
+        err.helps
+    );
+
+    let err = compile_main(
+        &proj_dir,
+        r#"
+import { Area as PhysArea } from "./shapes.rr"
+
+trait PhysArea {
+  fn area(self: Self) -> float
+}
+"#,
+    )
+    .expect_err("a trait alias may not shadow a local trait");
+    assert!(
+        err.message
+            .contains("trait 'PhysArea' conflicts with the import of 'Area' as 'PhysArea'"),
+        "{}",
+        err.message
+    );
+}
+
+#[test]
+fn import_alias_conflicts_are_reported() {
+    let proj_dir = project(
+        "alias_conflict",
+        &[("inner.rr", INNER), ("physics.rr", PHYSICS)],
+    );
+
+    let err = compile_main(
+        &proj_dir,
+        r#"
+import { Body as solve } from "./physics.rr"
+
+fn solve() {
+  return 1.0
+}
+"#,
+    )
+    .expect_err("an alias may not shadow a local function");
+    assert!(
+        err.message
+            .contains("function 'solve' conflicts with the import of 'Body' as 'solve'"),
+        "{}",
+        err.message
+    );
+
+    let err = compile_main(
+        &proj_dir,
+        r#"
+import r * as phys from "stats"
+import * as phys from "./physics.rr"
+"#,
+    )
+    .expect_err("a module namespace may not reuse an R namespace alias");
+    assert!(
+        err.message
+            .contains("module namespace alias 'phys' is already bound to R namespace 'stats'"),
+        "{}",
+        err.message
+    );
+}
+
+#[test]
+fn renamed_re_export_is_a_parse_error() {
+    let proj_dir = project("rename_re_export", &[("inner.rr", INNER)]);
+    let err = compile_main(
+        &proj_dir,
+        r#"
+export { solve as s } from "./inner.rr"
+"#,
+    )
+    .expect_err("re-exports keep their names");
+    assert!(matches!(err.code, RRCode::E0001));
+    assert!(
+        messages(&err)
+            .iter()
+            .any(|m| m.contains("re-export of 'solve' cannot be renamed")),
+        "{:?}",
+        err
+    );
+}
@@ -0,0 +1,238 @@
+mod common;
+
+use common::unique_dir;
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const REMARKS_SRC: &str = r#"fn scale(x) {
+  let y = seq_along(x)
+  for (i in 1..length(x)) {
+    y[i] = x[i] * 2.0
+  }
+  return y
+}
+
+fn walk(x) {
+  let y = seq_along(x)
+  let j = 1
+  while (j <= length(x)) {
+    y[j] = print(x[j])
+    j = j + 2
+  }
+  return y
+}
+
+print(scale(c(1.0, 2.0, 3.0)))
+print(walk(c(1.0, 2.0, 3.0)))
+"#;
+
+fn compile_with_remarks(main_path: &Path, out_file: &Path, remarks_file: &Path) {
+    compile_with_remarks_and_args(main_path, out_file, remarks_file, &[]);
+}
+
+fn compile_with_remarks_and_args(
+    main_path: &Path,
+    out_file: &Path,
+    remarks_file: &Path,
+    extra_args: &[&str],
+) {
+    let rr_bin = PathBuf::from(env!("CARGO_BIN_EXE_RR"));
+    let output = Command::new(&rr_bin)
+        .arg(main_path)
+        .arg("-o")
+        .arg(out_file)
+        .arg("-O2")
+        .arg("--no-runtime")
+        .arg("--remarks")
+        .arg(remarks_file)
+        .args(extra_args)
+        .output()
+        .expect("failed to run RR CLI");
+    assert!(
+        output.status.success(),
+        "RR CLI failed:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("Optimization remarks ("),
+        "missing remarks summary:\n{stdout}"
+    );
+}
+
+#[test]
+fn remarks_report_records_passed_and_missed_loop_decisions() {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let sandbox_root = root.join("target").join("tests").join("opt_remarks_cli");
+    fs::create_dir_all(&sandbox_root).expect("failed to create sandbox root");
+    let proj_dir = unique_dir(&sandbox_root, "remarks");
+    fs::create_dir_all(&proj_dir).expect("failed to create project dir");
+    let main_path = proj_dir.join("main.rr");
+    fs::write(&main_path, REMARKS_SRC).expect("failed to write main.rr");
+
+    let json_path = proj_dir.join("remarks.json");
+    compile_with_remarks(&main_path, &proj_dir.join("out.R"), &json_path);
+    let report: Value =
+        serde_json::from_str(&fs::read_to_string(&json_path).expect("failed to read remarks json"))
+            .expect("remarks report should be valid JSON");
+    assert_eq!(report["schema"], "rr-opt-remarks");
+    assert_eq!(report["version"], 1);
+    let remarks = report["remarks"]
+        .as_array()
+        .expect("remarks should be an array");
+    let find = |function: &str, pass: &str, kind: &str| {
+        remarks.iter().find(|remark| {
+            remark["function"] == function && remark["pass"] == pass && remark["kind"] == kind
+        })
+    };
+
+    let vectorized = find("scale", "vectorize", "passed").expect("scale loop should vectorize");
+    assert_eq!(vectorized["line"], 3);
+    assert!(
+        vectorized["file"]
+            .as_str()
+            .is_some_and(|file| file.ends_with("main.rr")),
+        "remark should carry the source file: {vectorized}"
+    );
+    let bce = find("scale", "bce", "passed").expect("scale store should lose its bounds check");
+    assert_eq!(bce["line"], 4);
+
+    let missed = find("walk", "vectorize", "missed").expect("walk loop should not vectorize");
+    assert_eq!(missed["line"], 12);
+    assert_eq!(missed["reason"], "non-canonical-bound");
+    assert!(
+        missed["message"]
+            .as_str()
+            .is_some_and(|message| message.starts_with("loop not vectorized: ")),
+        "unexpected message: {missed}"
+    );
+
+    let text_path = proj_dir.join("remarks.txt");
+    compile_with_remarks(&main_path, &proj_dir.join("out2.R"), &text_path);
+    let text = fs::read_to_string(&text_path).expect("failed to read remarks text");
+    assert!(
+        text.lines().any(|line| line
+            .ends_with("main.rr:3:3: passed [vectorize] scale: loop vectorized as expr_map")),
+        "missing human-readable vectorize remark:\n{text}"
+    );
+    assert!(
+        text.lines()
+            .any(|line| line.contains(":12:10: missed [vectorize] walk: ")
+                && line.ends_with("(non-canonical-bound)")),
+        "missing human-readable missed remark:\n{text}"
+    );
+}
+
+#[test]
+fn parallel_compile_reports_one_verdict_per_loop_like_serial() {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let sandbox_root = root.join("target").join("tests").join("opt_remarks_cli");
+    fs::create_dir_all(&sandbox_root).expect("failed to create sandbox root");
+    let proj_dir = unique_dir(&sandbox_root, "parallel");
+    fs::create_dir_all(&proj_dir).expect("failed to create project dir");
+    let main_path = proj_dir.join("main.rr");
+    fs::write(&main_path, REMARKS_SRC).expect("failed to write main.rr");
+
+    let serial_path = proj_dir.join("serial.txt");
+    compile_with_remarks(&main_path, &proj_dir.join("serial.R"), &serial_path);
+    let parallel_path = proj_dir.join("parallel.txt");
+    compile_with_remarks_and_args(
+        &main_path,
+        &proj_dir.join("parallel.R"),
+        &parallel_path,
+        &[
+            "--compiler-parallel-mode",
+            "on",
+            "--compiler-parallel-threads",
+            "4",
+            "--compiler-parallel-min-functions",
+            "1",
+            "--compiler-parallel-min-fn-ir",
+            "1",
+        ],
+    );
+    let serial = fs::read_to_string(&serial_path).expect("failed to read serial remarks");
+    let parallel = fs::read_to_string(&parallel_path).expect("failed to read parallel remarks");
+    assert!(
+        serial.contains("passed [vectorize] scale:"),
+        "serial remarks:\n{serial}"
+    );
+    assert_eq!(
+        serial, parallel,
+        "parallel workers should report every remark"
+    );
+
+    // Fixed-point iterations revisit each loop; only one verdict per pass survives.
+    for (function, pass) in [
+        ("scale", "unroll"),
+        ("walk", "unroll"),
+        ("walk", "vectorize"),
+    ] {
+        let verdicts = parallel
+            .lines()
+            .filter(|line| {
+                (line.contains(" missed [") || line.contains(" passed ["))
+                    && line.contains(&format!("[{pass}] {function}:"))
+            })
+            .count();
+        assert!(
+            verdicts <= 1,
+            "{function} got {verdicts} {pass} verdicts:\n{parallel}"
+        );
+    }
+}
+
+#[test]
+fn bce_load_remark_points_at_the_statement_that_reads() {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let sandbox_root = root.join("target").join("tests").join("opt_remarks_cli");
+    fs::create_dir_all(&sandbox_root).expect("failed to create sandbox root");
+    let proj_dir = unique_dir(&sandbox_root, "bce_load");
+    fs::create_dir_all(&proj_dir).expect("failed to create project dir");
+    let main_path = proj_dir.join("main.rr");
+    fs::write(
+        &main_path,
+        r#"fn shift(x) {
+  let y = seq_along(x)
+  for (i in 1..(length(x) - 1)) {
+    y[i] = print(x[i + 1])
+  }
+  return y
+}
+
+print(shift(c(1.0, 2.0, 3.0)))
+"#,
+    )
+    .expect("failed to write main.rr");
+
+    let text_path = proj_dir.join("remarks.txt");
+    compile_with_remarks(&main_path, &proj_dir.join("out.R"), &text_path);
+    let text = fs::read_to_string(&text_path).expect("failed to read remarks text");
+    assert!(
+        text.lines().any(|line| line
+            .ends_with("main.rr:4:5: passed [bce] shift: bounds check removed from load")),
+        "load remark should carry the reading statement's position:\n{text}"
+    );
+}
+
+#[test]
+fn remarks_flag_requires_a_report_path() {
+    let rr_bin = PathBuf::from(env!("CARGO_BIN_EXE_RR"));
+    let output = Command::new(&rr_bin)
+        .arg("main.rr")
+        .arg("--remarks")
+        .output()
+        .expect("failed to run RR CLI");
+    assert!(!output.status.success());
+    let combined = format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(
+        combined.contains("Missing value after --remarks"),
+        "unexpected output:\n{combined}"
+    );
+}
@@ -0,0 +1,91 @@
+mod common;
+
+use common::unique_dir;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+const ALIAS_SRC: &str = r#"fn shared(n) {
+  let a = rep.int(0.0, n)
+  let b = a
+  return sum(a) + sum(b)
+}
+
+fn written(n) {
+  let a = rep.int(0.0, n)
+  let b = a
+  b[1] = 1.0
+  return sum(a) + sum(b)
+}
+"#;
+
+fn fn_section<'a>(dump: &'a str, name: &str) -> &'a str {
+    let header = format!("// {name}\n");
+    let start = dump
+        .find(&header)
+        .unwrap_or_else(|| panic!("missing {name} in dump:\n{dump}"));
+    let rest = &dump[start + header.len()..];
+    let end = rest.find("\n// ").unwrap_or(rest.len());
+    &rest[..end]
+}
+
+#[test]
+fn owned_allocation_alias_is_not_defensively_reallocated() {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let sandbox_root = root
+        .join("target")
+        .join("tests")
+        .join("ownership_fresh_alias");
+    fs::create_dir_all(&sandbox_root).expect("failed to create sandbox root");
+    let proj_dir = unique_dir(&sandbox_root, "alias");
+    fs::create_dir_all(&proj_dir).expect("failed to create project dir");
+    let src_path = proj_dir.join("main.rr");
+    fs::write(&src_path, ALIAS_SRC).expect("failed to write main.rr");
+    let out_path = proj_dir.join("out.R");
+    let emit_dir = proj_dir.join("dumps");
+
+    let output = Command::new(PathBuf::from(env!("CARGO_BIN_EXE_RR")))
+        .arg(&src_path)
+        .arg("-o")
+        .arg(&out_path)
+        .arg("-O2")
+        .arg("--no-runtime")
+        .arg("--preserve-all-defs")
+        .arg("--emit")
+        .arg("mir-opt")
+        .arg("--emit-dir")
+        .arg(&emit_dir)
+        .output()
+        .expect("failed to run RR CLI");
+    assert!(
+        output.status.success(),
+        "RR CLI failed:\nstdout:\n{}\nstderr:\n{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let mir = fs::read_to_string(emit_dir.join("main.opt.mir.txt")).expect("missing mir-opt dump");
+    // `a` owns its allocation and neither name is written in place, so `b`
+    // shares it instead of re-running `rep.int`.
+    let shared = fn_section(&mir, "shared");
+    assert_eq!(
+        shared.matches("call \"rep.int\"").count(),
+        1,
+        "shared should keep a single allocation:\n{shared}"
+    );
+    // Writing `b[1]` in place still needs its own allocation.
+    let written = fn_section(&mir, "written");
+    assert_eq!(
+        written.matches("call \"rep.int\"").count(),
+        2,
+        "written should keep the defensive copy:\n{written}"
+    );
+
+    let code = fs::read_to_string(&out_path).expect("failed to read emitted R");
+    assert_eq!(
+        code.matches("rep.int(0.0, n)").count(),
+        2,
+        "emitted R should allocate once per function:\n{code}"
+    );
+    assert!(code.contains("b[1L] <- 1.0"), "emitted R:\n{code}");
+}
@@ -12,5 +12,9 @@ mod chunk_4;
 mod chunk_5;
 #[path = "package_manager_cli/chunk_6.rs"]
 mod chunk_6;
+#[path = "package_manager_cli/chunk_7.rs"]
+mod chunk_7;
+#[path = "package_manager_cli/chunk_8.rs"]
+mod chunk_8;
 #[path = "package_manager_cli/common.rs"]
 mod package_manager_cli_common;
@@ -83,6 +83,117 @@ fn add_one(x) {
     );
 }
 
+#[test]
+pub(crate) fn publish_refuses_breaking_api_without_major_bump() {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let sandbox_root = root
+        .join("target")
+        .join("tests")
+        .join("package_manager_cli");
+    fs::create_dir_all(&sandbox_root).expect("failed to create sandbox root");
+
+    let rr_bin = rr_bin();
+    let pkg_dir = unique_dir(&sandbox_root, "registry_api_pkg");
+    let status = Command::new(&rr_bin)
+        .arg("new")
+        .arg("--lib")
+        .arg("rr.local/apidiff")
+        .arg(&pkg_dir)
+        .status()
+        .expect("failed to run rr new --lib");
+    assert!(status.success(), "rr new --lib failed");
+
+    let registry_dir = unique_dir(&sandbox_root, "registry_api_root");
+    fs::create_dir_all(&registry_dir).expect("failed to create registry dir");
+    let publish = |version: &str, extra: &[&str]| {
+        Command::new(&rr_bin)
+            .current_dir(&pkg_dir)
+            .arg("publish")
+            .arg(version)
+            .args(extra)
+            .arg("--registry")
+            .arg(&registry_dir)
+            .output()
+            .expect("failed to run rr publish")
+    };
+
+    fs::write(
+        pkg_dir.join("src").join("lib.rr"),
+        r#"
+export fn solve(x: float) -> float {
+  return x * 2.0
+}
+
+export fn scale(x: float, k: float = 2.0) -> float {
+  return x * k
+}
+"#,
+    )
+    .expect("failed to write first library source");
+    let publish_v1 = publish("v1.0.0", &[]);
+    assert!(publish_v1.status.success(), "publish v1.0.0 failed");
+
+    fs::write(
+        pkg_dir.join("src").join("lib.rr"),
+        r#"
+export fn scale(x: float, k: float) -> float {
+  return x * k
+}
+
+export fn norm(x: float) -> float {
+  return abs(x)
+}
+"#,
+    )
+    .expect("failed to write second library source");
+    let refused = publish("v1.1.0", &[]);
+    assert!(
+        !refused.status.success(),
+        "breaking v1.1.0 should be refused"
+    );
+    let output = format!(
+        "{}{}",
+        String::from_utf8_lossy(&refused.stdout),
+        String::from_utf8_lossy(&refused.stderr)
+    );
+    assert!(
+        output.contains("v1.1.0 breaks the exported API of v1.0.0 without a major version bump")
+            && output.contains("src/lib.rr fn solve: removed")
+            && output.contains("src/lib.rr fn scale: parameter `k` lost its default")
+            && output.contains("--allow-breaking"),
+        "expected breaking API report, got:\n{}",
+        output
+    );
+
+    let allowed = publish("v1.1.0", &["--allow-breaking"]);
+    assert!(
+        allowed.status.success(),
+        "publish --allow-breaking failed: {}",
+        String::from_utf8_lossy(&allowed.stderr)
+    );
+
+    let diff = Command::new(&rr_bin)
+        .arg("registry")
+        .arg("diff")
+        .arg("rr.local/apidiff")
+        .arg("v1.0.0")
+        .arg("v1.1.0")
+        .arg("--registry")
+        .arg(&registry_dir)
+        .output()
+        .expect("failed to run rr registry diff");
+    assert!(diff.status.success(), "rr registry diff failed");
+    let stdout = String::from_utf8_lossy(&diff.stdout);
+    assert!(
+        stdout.contains("api breaking=2 compatible=1")
+            && stdout.contains(". added src/lib.rr fn norm: added")
+            && stdout.contains("! narrowed src/lib.rr fn scale: parameter `k` lost its default")
+            && stdout.contains("! removed src/lib.rr fn solve: removed"),
+        "expected API diff output, got:\n{}",
+        stdout
+    );
+}
+
 #[test]
 pub(crate) fn registry_channels_can_be_assigned_and_used_for_install() {
     let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
@@ -0,0 +1,260 @@
+use super::package_manager_cli_common::*;
+
+fn offline_fixture(sandbox_root: &Path) -> (PathBuf, PathBuf, PathBuf) {
+    let github_root = unique_dir(sandbox_root, "offline_github");
+    let acme_root = github_root.join("acme");
+    fs::create_dir_all(&acme_root).expect("failed to create fake github org root");
+    init_git_repo(
+        &acme_root.join("baseutil"),
+        &[
+            ("rr.mod", "module github.com/acme/baseutil\n\nrr 8.0\n"),
+            ("src/lib.rr", "fn plus_one(x) {\n  return x + 1L\n}\n"),
+        ],
+        "v0.1.0",
+    );
+    init_git_repo(
+        &acme_root.join("mathlib"),
+        &[
+            (
+                "rr.mod",
+                "module github.com/acme/mathlib\n\nrr 8.0\n\nrequire github.com/acme/baseutil v0.1.0\n",
+            ),
+            (
+                "src/lib.rr",
+                "import \"github.com/acme/baseutil\"\n\nfn add_one(x) {\n  return plus_one(x)\n}\n",
+            ),
+        ],
+        "v1.2.3",
+    );
+
+    let proj_dir = unique_dir(sandbox_root, "offline_app");
+    let status = Command::new(rr_bin())
+        .arg("new")
+        .arg("github.com/example/app")
+        .arg(&proj_dir)
+        .status()
+        .expect("failed to run rr new");
+    assert!(status.success(), "rr new failed");
+    fs::write(
+        proj_dir.join("src").join("main.rr"),
+        "import \"github.com/acme/mathlib\"\n\nfn main() {\n  print(add_one(41L))\n}\n\nmain()\n",
+    )
+    .expect("failed to write app src/main.rr");
+
+    let pkg_home = unique_dir(sandbox_root, "offline_pkg_home");
+    fs::create_dir_all(&pkg_home).expect("failed to create pkg home");
+    let mut install = Command::new(rr_bin());
+    install
+        .current_dir(&proj_dir)
+        .arg("install")
+        .arg("https://github.com/acme/mathlib@latest");
+    configure_github_mapping(&mut install, &github_root, &pkg_home);
+    let output = install.output().expect("failed to run rr install");
+    assert!(
+        output.status.success(),
+        "rr install failed:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    (github_root, proj_dir, pkg_home)
+}
+
+fn combined_output(output: &std::process::Output) -> String {
+    format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    )
+}
+
+#[test]
+pub(crate) fn offline_mode_resolves_from_cache_and_vendor_and_lists_missing_modules() {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let sandbox_root = root
+        .join("target")
+        .join("tests")
+        .join("package_manager_cli");
+    fs::create_dir_all(&sandbox_root).expect("failed to create sandbox root");
+    let (_github_root, proj_dir, pkg_home) = offline_fixture(&sandbox_root);
+
+    // Warm cache: no git remapping, so any network access would fail.
+    let tidy = Command::new(rr_bin())
+        .current_dir(&proj_dir)
+        .env("RRPKGHOME", &pkg_home)
+        .args(["mod", "tidy", "--offline"])
+        .output()
+        .expect("failed to run rr mod tidy --offline");
+    assert!(
+        tidy.status.success(),
+        "offline tidy should resolve from the cache:\n{}",
+        combined_output(&tidy)
+    );
+
+    let install = Command::new(rr_bin())
+        .current_dir(&proj_dir)
+        .env("RRPKGHOME", &pkg_home)
+        .args([
+            "--offline",
+            "install",
+            "https://github.com/acme/mathlib@latest",
+        ])
+        .output()
+        .expect("failed to run rr install --offline");
+    assert!(
+        install.status.success(),
+        "offline install of a cached module should succeed:\n{}",
+        combined_output(&install)
+    );
+    assert!(combined_output(&install).contains("Installed github.com/acme/mathlib v1.2.3"));
+
+    // Cold cache: every missing module is reported at once.
+    let empty_home = unique_dir(&sandbox_root, "offline_empty_home");
+    fs::create_dir_all(&empty_home).expect("failed to create empty pkg home");
+    let cold = Command::new(rr_bin())
+        .current_dir(&proj_dir)
+        .env("RRPKGHOME", &empty_home)
+        .env("RR_OFFLINE", "1")
+        .args(["mod", "tidy"])
+        .output()
+        .expect("failed to run offline rr mod tidy");
+    let cold_out = combined_output(&cold);
+    assert!(!cold.status.success(), "cold offline tidy should fail");
+    assert!(
+        cold_out.contains("2 module(s) are not available")
+            && cold_out.contains("github.com/acme/baseutil@v0.1.0")
+            && cold_out.contains("github.com/acme/mathlib@v1.2.3"),
+        "expected both missing modules to be listed, got:\n{cold_out}"
+    );
+
+    let cold_latest = Command::new(rr_bin())
+        .current_dir(&proj_dir)
+        .env("RRPKGHOME", &empty_home)
+        .args([
+            "install",
+            "https://github.com/acme/baseutil@v0.1.0",
+            "--offline",
+        ])
+        .output()
+        .expect("failed to run rr install --offline");
+    assert!(!cold_latest.status.success());
+    assert!(
+        combined_output(&cold_latest).contains("not in the module cache"),
+        "got:\n{}",
+        combined_output(&cold_latest)
+    );
+
+    // A vendored tree is enough on its own.
+    let vendor = Command::new(rr_bin())
+        .current_dir(&proj_dir)
+        .env("RRPKGHOME", &pkg_home)
+        .args(["mod", "vendor"])
+        .output()
+        .expect("failed to run rr mod vendor");
+    assert!(vendor.status.success(), "{}", combined_output(&vendor));
+    let vendored = Command::new(rr_bin())
+        .current_dir(&proj_dir)
+        .env("RRPKGHOME", &empty_home)
+        .args(["mod", "tidy", "--offline"])
+        .output()
+        .expect("failed to run vendored offline tidy");
+    assert!(
+        vendored.status.success(),
+        "offline tidy should resolve from vendor/:\n{}",
+        combined_output(&vendored)
+    );
+
+    // An edited vendor/ tree must not be re-locked as trusted.
+    let lock_before = fs::read_to_string(proj_dir.join("rr.lock")).expect("failed to read rr.lock");
+    fs::write(
+        proj_dir
+            .join("vendor")
+            .join("github.com")
+            .join("acme")
+            .join("baseutil")
+            .join("src")
+            .join("lib.rr"),
+        "fn plus_one(x) {\n  return x + 2L\n}\n",
+    )
+    .expect("failed to edit vendored module");
+    let tampered = Command::new(rr_bin())
+        .current_dir(&proj_dir)
+        .env("RRPKGHOME", &empty_home)
+        .args(["mod", "tidy", "--offline"])
+        .output()
+        .expect("failed to run offline tidy with edited vendor/");
+    let tampered_out = combined_output(&tampered);
+    assert!(!tampered.status.success(), "{tampered_out}");
+    assert!(
+        tampered_out.contains("checksum mismatch for module 'github.com/acme/baseutil'"),
+        "got:\n{tampered_out}"
+    );
+    assert_eq!(
+        fs::read_to_string(proj_dir.join("rr.lock")).expect("failed to read rr.lock"),
+        lock_before
+    );
+}
+
+#[test]
+pub(crate) fn mod_cache_list_verify_and_clean_manage_package_home() {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let sandbox_root = root
+        .join("target")
+        .join("tests")
+        .join("package_manager_cli");
+    fs::create_dir_all(&sandbox_root).expect("failed to create sandbox root");
+    let (_github_root, _proj_dir, pkg_home) = offline_fixture(&sandbox_root);
+    let cache_cmd = |args: &[&str]| {
+        Command::new(rr_bin())
+            .env("RRPKGHOME", &pkg_home)
+            .args(["mod", "cache"])
+            .args(args)
+            .output()
+            .expect("failed to run rr mod cache")
+    };
+
+    let list = cache_cmd(&["list"]);
+    let list_out = String::from_utf8_lossy(&list.stdout).to_string();
+    assert!(list.status.success(), "{}", combined_output(&list));
+    assert!(
+        list_out.contains("module github.com/acme/baseutil@v0.1.0 size=")
+            && list_out.contains("module github.com/acme/mathlib@v1.2.3 size="),
+        "got:\n{list_out}"
+    );
+
+    let verify = cache_cmd(&["verify"]);
+    assert!(verify.status.success(), "{}", combined_output(&verify));
+
+    let cached_lib = pkg_home
+        .join("pkg")
+        .join("mod")
+        .join("github.com")
+        .join("acme")
+        .join("baseutil@v0.1.0")
+        .join("src")
+        .join("lib.rr");
+    fs::write(&cached_lib, "fn plus_one(x) {\n  return x + 2L\n}\n")
+        .expect("failed to tamper with cached module");
+    let tampered = cache_cmd(&["verify"]);
+    assert!(!tampered.status.success());
+    assert!(
+        combined_output(&tampered).contains("baseutil@v0.1.0: checksum mismatch"),
+        "got:\n{}",
+        combined_output(&tampered)
+    );
+
+    let keep = cache_cmd(&["clean", "--older-than", "30d"]);
+    assert!(keep.status.success(), "{}", combined_output(&keep));
+    assert!(combined_output(&keep).contains("Removed 0 cache entries"));
+
+    let bad_age = cache_cmd(&["clean", "--older-than", "soon"]);
+    assert!(!bad_age.status.success());
+
+    let clean = cache_cmd(&["clean"]);
+    assert!(clean.status.success(), "{}", combined_output(&clean));
+    assert!(combined_output(&clean).contains("Removed 2 cache entries"));
+    let after = cache_cmd(&["list"]);
+    let after_out = combined_output(&after);
+    assert!(
+        after_out.contains("0 cache entries") && !after_out.contains("module github.com"),
+        "got:\n{after_out}"
+    );
+}
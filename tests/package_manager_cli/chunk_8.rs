@@ -0,0 +1,1153 @@
+use super::package_manager_cli_common::*;
+use std::process::Output;
+
+fn publish_licensed_lib(
+    sandbox_root: &Path,
+    name: &str,
+    license: &str,
+    registry_dir: &Path,
+) -> PathBuf {
+    let pkg_dir = unique_dir(sandbox_root, &format!("license_{name}_pkg"));
+    let status = Command::new(rr_bin())
+        .arg("new")
+        .arg("--lib")
+        .arg(format!("rr.local/{name}"))
+        .arg(&pkg_dir)
+        .status()
+        .expect("failed to run rr new --lib");
+    assert!(status.success(), "rr new --lib failed");
+    let manifest_path = pkg_dir.join("rr.mod");
+    let manifest = fs::read_to_string(&manifest_path).expect("failed to read rr.mod");
+    fs::write(
+        &manifest_path,
+        format!("{manifest}license = \"{license}\"\nhomepage = \"https://example.com/{name}\"\n"),
+    )
+    .expect("failed to write rr.mod");
+    fs::write(
+        pkg_dir.join("src").join("lib.rr"),
+        format!("fn {name}_one(x) {{\n  return x + 1L\n}}\n"),
+    )
+    .expect("failed to write library source");
+
+    let publish = Command::new(rr_bin())
+        .current_dir(&pkg_dir)
+        .args(["publish", "v1.0.0", "--registry"])
+        .arg(registry_dir)
+        .env("RR_REGISTRY_SIGNING_KEY", "license-secret")
+        .env("RR_REGISTRY_SIGNING_IDENTITY", "release-bot")
+        .output()
+        .expect("failed to run rr publish --registry");
+    assert!(
+        publish.status.success(),
+        "rr publish failed:\n{}",
+        String::from_utf8_lossy(&publish.stderr)
+    );
+    pkg_dir
+}
+
+fn new_app(sandbox_root: &Path, label: &str) -> PathBuf {
+    let app_dir = unique_dir(sandbox_root, label);
+    let status = Command::new(rr_bin())
+        .arg("new")
+        .arg("github.com/example/sbom-app")
+        .arg(&app_dir)
+        .status()
+        .expect("failed to run rr new");
+    assert!(status.success(), "rr new failed");
+    app_dir
+}
+
+fn registry_install(app_dir: &Path, pkg_home: &Path, registry_dir: &Path, spec: &str) -> Output {
+    Command::new(rr_bin())
+        .current_dir(app_dir)
+        .args(["install", spec])
+        .env("RRPKGHOME", pkg_home)
+        .env("RR_REGISTRY_DIR", registry_dir)
+        .env("RR_REGISTRY_TRUST_KEY", "license-secret")
+        .output()
+        .expect("failed to run rr install")
+}
+
+fn stderr_of(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).to_string()
+}
+
+#[test]
+pub(crate) fn mod_sbom_emits_cyclonedx_and_spdx_for_locked_graph() {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let sandbox_root = root
+        .join("target")
+        .join("tests")
+        .join("package_manager_cli");
+    fs::create_dir_all(&sandbox_root).expect("failed to create sandbox root");
+    let registry_dir = unique_dir(&sandbox_root, "sbom_registry");
+    fs::create_dir_all(&registry_dir).expect("failed to create registry dir");
+    publish_licensed_lib(&sandbox_root, "sbomlib", "MIT OR Apache-2.0", &registry_dir);
+
+    let app_dir = new_app(&sandbox_root, "sbom_app");
+    let pkg_home = unique_dir(&sandbox_root, "sbom_pkg_home");
+    fs::create_dir_all(&pkg_home).expect("failed to create pkg home");
+    let install = registry_install(
+        &app_dir,
+        &pkg_home,
+        &registry_dir,
+        "rr.local/sbomlib@latest",
+    );
+    assert!(install.status.success(), "{}", stderr_of(&install));
+
+    let sbom = |format: &str| {
+        Command::new(rr_bin())
+            .current_dir(&app_dir)
+            .args(["mod", "sbom", "--format", format])
+            .env("RRPKGHOME", &pkg_home)
+            .env("RR_REGISTRY_DIR", &registry_dir)
+            .env("SOURCE_DATE_EPOCH", "1700000000")
+            .output()
+            .expect("failed to run rr mod sbom")
+    };
+
+    let cyclonedx = sbom("cyclonedx");
+    assert!(cyclonedx.status.success(), "{}", stderr_of(&cyclonedx));
+    let doc: serde_json::Value =
+        serde_json::from_slice(&cyclonedx.stdout).expect("CycloneDX output should be JSON");
+    assert_eq!(doc["bomFormat"], "CycloneDX");
+    assert_eq!(doc["metadata"]["timestamp"], "2023-11-14T22:13:20Z");
+    assert_eq!(
+        sbom("cyclonedx").stdout,
+        cyclonedx.stdout,
+        "SOURCE_DATE_EPOCH should make the document reproducible"
+    );
+    let component = &doc["components"][0];
+    assert_eq!(component["bom-ref"], "rr.local/sbomlib@v1.0.0");
+    assert_eq!(component["licenses"][0]["expression"], "MIT OR Apache-2.0");
+    let property = |name: &str| {
+        component["properties"]
+            .as_array()
+            .and_then(|props| props.iter().find(|prop| prop["name"] == name))
+            .map(|prop| prop["value"].as_str().unwrap_or_default().to_string())
+    };
+    assert!(property("rr:sum").is_some_and(|sum| sum.starts_with("fnv64:")));
+    assert_eq!(property("rr:commit").as_deref(), Some("registry"));
+    assert_eq!(property("rr:signer").as_deref(), Some("release-bot"));
+    assert_eq!(property("rr:signature").as_deref(), Some("hmac-sha256"));
+    assert_eq!(doc["dependencies"][0]["ref"], "github.com/example/sbom-app");
+    assert_eq!(
+        doc["dependencies"][0]["dependsOn"][0],
+        "rr.local/sbomlib@v1.0.0"
+    );
+
+    let spdx = sbom("spdx");
+    assert!(spdx.status.success(), "{}", stderr_of(&spdx));
+    let doc: serde_json::Value =
+        serde_json::from_slice(&spdx.stdout).expect("SPDX output should be JSON");
+    assert_eq!(doc["spdxVersion"], "SPDX-2.3");
+    assert_eq!(doc["creationInfo"]["created"], "2023-11-14T22:13:20Z");
+    let package = &doc["packages"][1];
+    assert_eq!(package["SPDXID"], "SPDXRef-rr.local-sbomlib-v1.0.0");
+    assert_eq!(package["licenseDeclared"], "MIT OR Apache-2.0");
+    assert_eq!(package["homepage"], "https://example.com/sbomlib");
+    assert!(
+        package["comment"]
+            .as_str()
+            .is_some_and(|comment| comment.contains("registry signer release-bot")),
+        "got: {package}"
+    );
+    assert!(
+        doc["relationships"]
+            .as_array()
+            .is_some_and(|rels| rels.iter().any(|rel| {
+                rel["relationshipType"] == "DEPENDS_ON"
+                    && rel["relatedSpdxElement"] == "SPDXRef-rr.local-sbomlib-v1.0.0"
+            })),
+        "got: {}",
+        doc["relationships"]
+    );
+
+    let bad_epoch = Command::new(rr_bin())
+        .current_dir(&app_dir)
+        .args(["mod", "sbom"])
+        .env("RRPKGHOME", &pkg_home)
+        .env("RR_REGISTRY_DIR", &registry_dir)
+        .env("SOURCE_DATE_EPOCH", "yesterday")
+        .output()
+        .expect("failed to run rr mod sbom");
+    assert!(!bad_epoch.status.success());
+    assert!(stderr_of(&bad_epoch).contains("SOURCE_DATE_EPOCH must be"));
+
+    let bad = sbom("swid");
+    assert!(!bad.status.success());
+    assert!(stderr_of(&bad).contains("unsupported SBOM format 'swid'"));
+}
+
+#[test]
+pub(crate) fn registry_license_policy_gates_install_and_approval() {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let sandbox_root = root
+        .join("target")
+        .join("tests")
+        .join("package_manager_cli");
+    fs::create_dir_all(&sandbox_root).expect("failed to create sandbox root");
+    let registry_dir = unique_dir(&sandbox_root, "license_registry");
+    fs::create_dir_all(&registry_dir).expect("failed to create registry dir");
+    publish_licensed_lib(&sandbox_root, "mitlib", "MIT", &registry_dir);
+    publish_licensed_lib(&sandbox_root, "gpllib", "GPL-3.0-only", &registry_dir);
+
+    let policy_src = unique_dir(&sandbox_root, "license_policy_src");
+    fs::create_dir_all(&policy_src).expect("failed to create policy dir");
+    let policy_file = policy_src.join("policy.toml");
+    fs::write(
+        &policy_file,
+        "version = 1\n\n[license]\nallow = [\"MIT\", \"Apache-2.0\"]\ndeny = \"GPL-3.0-only\"\n",
+    )
+    .expect("failed to write policy file");
+    let apply = Command::new(rr_bin())
+        .args(["registry", "policy", "apply"])
+        .arg(&policy_file)
+        .arg("--registry")
+        .arg(&registry_dir)
+        .output()
+        .expect("failed to run rr registry policy apply");
+    assert!(apply.status.success(), "{}", stderr_of(&apply));
+    let rendered =
+        fs::read_to_string(registry_dir.join("policy.toml")).expect("failed to read policy");
+    assert!(
+        rendered.contains(
+            "[license]\nallow = \"Apache-2.0\"\nallow = \"MIT\"\ndeny = \"GPL-3.0-only\"\n"
+        ),
+        "got:\n{rendered}"
+    );
+
+    let lint = Command::new(rr_bin())
+        .args(["registry", "policy", "lint", "--registry"])
+        .arg(&registry_dir)
+        .output()
+        .expect("failed to run rr registry policy lint");
+    assert!(lint.status.success(), "{}", stderr_of(&lint));
+    assert!(String::from_utf8_lossy(&lint.stdout).contains("allowed_licenses=2 denied_licenses=1"));
+
+    let app_dir = new_app(&sandbox_root, "license_app");
+    let pkg_home = unique_dir(&sandbox_root, "license_pkg_home");
+    fs::create_dir_all(&pkg_home).expect("failed to create pkg home");
+    let allowed = registry_install(&app_dir, &pkg_home, &registry_dir, "rr.local/mitlib@latest");
+    assert!(allowed.status.success(), "{}", stderr_of(&allowed));
+    let denied = registry_install(&app_dir, &pkg_home, &registry_dir, "rr.local/gpllib@v1.0.0");
+    assert!(!denied.status.success());
+    assert!(
+        stderr_of(&denied).contains(
+            "license 'GPL-3.0-only' of 'rr.local/gpllib v1.0.0' is denied by registry policy"
+        ),
+        "got:\n{}",
+        stderr_of(&denied)
+    );
+
+    let registry_cmd = |args: &[&str]| {
+        Command::new(rr_bin())
+            .arg("registry")
+            .args(args)
+            .arg("--registry")
+            .arg(&registry_dir)
+            .output()
+            .expect("failed to run rr registry")
+    };
+    let unapprove = registry_cmd(&["unapprove", "rr.local/gpllib", "v1.0.0"]);
+    assert!(unapprove.status.success(), "{}", stderr_of(&unapprove));
+    let approve = registry_cmd(&["approve", "rr.local/gpllib", "v1.0.0"]);
+    assert!(!approve.status.success());
+    assert!(
+        stderr_of(&approve).contains("is denied by registry policy"),
+        "got:\n{}",
+        stderr_of(&approve)
+    );
+    let approve_mit = registry_cmd(&["approve", "rr.local/mitlib", "v1.0.0"]);
+    assert!(approve_mit.status.success(), "{}", stderr_of(&approve_mit));
+}
+
+#[test]
+pub(crate) fn audit_reports_locked_versions_covered_by_signed_advisories() {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let sandbox_root = root
+        .join("target")
+        .join("tests")
+        .join("package_manager_cli");
+    fs::create_dir_all(&sandbox_root).expect("failed to create sandbox root");
+    let registry_dir = unique_dir(&sandbox_root, "advisory_registry");
+    fs::create_dir_all(&registry_dir).expect("failed to create registry dir");
+    publish_licensed_lib(&sandbox_root, "vulnlib", "MIT", &registry_dir);
+
+    let app_dir = new_app(&sandbox_root, "advisory_app");
+    let pkg_home = unique_dir(&sandbox_root, "advisory_pkg_home");
+    fs::create_dir_all(&pkg_home).expect("failed to create pkg home");
+    let install = registry_install(
+        &app_dir,
+        &pkg_home,
+        &registry_dir,
+        "rr.local/vulnlib@v1.0.0",
+    );
+    assert!(install.status.success(), "{}", stderr_of(&install));
+
+    let audit = || {
+        Command::new(rr_bin())
+            .current_dir(&app_dir)
+            .arg("audit")
+            .env("RRPKGHOME", &pkg_home)
+            .env("RR_REGISTRY_DIR", &registry_dir)
+            .env("RR_REGISTRY_TRUST_KEY", "license-secret")
+            .output()
+            .expect("failed to run rr audit")
+    };
+    let clean = audit();
+    assert!(clean.status.success(), "{}", stderr_of(&clean));
+
+    let add = Command::new(rr_bin())
+        .args([
+            "registry",
+            "advisory",
+            "add",
+            "rr.local/vulnlib",
+            ">=v1.0.0, <v1.2.0",
+            "RRSEC-2026-0001",
+            "unchecked",
+            "index",
+            "--registry",
+        ])
+        .arg(&registry_dir)
+        .env("RR_REGISTRY_SIGNING_KEY", "license-secret")
+        .env("RR_REGISTRY_SIGNING_IDENTITY", "release-bot")
+        .output()
+        .expect("failed to run rr registry advisory add");
+    assert!(add.status.success(), "{}", stderr_of(&add));
+
+    let affected = audit();
+    assert!(
+        !affected.status.success(),
+        "audit should fail on advisories"
+    );
+    let stdout = String::from_utf8_lossy(&affected.stdout);
+    assert!(
+        stdout.contains(
+            "RRSEC-2026-0001 rr.local/vulnlib@v1.0.0 range=>=v1.0.0, <v1.2.0: unchecked index"
+        ) && stdout.contains("github.com/example/sbom-app -> rr.local/vulnlib@v1.0.0"),
+        "got:\n{}",
+        stdout
+    );
+
+    let reinstall = registry_install(
+        &app_dir,
+        &pkg_home,
+        &registry_dir,
+        "rr.local/vulnlib@v1.0.0",
+    );
+    assert!(reinstall.status.success(), "{}", stderr_of(&reinstall));
+    let reinstall_output = format!(
+        "{}{}",
+        String::from_utf8_lossy(&reinstall.stdout),
+        stderr_of(&reinstall)
+    );
+    assert!(
+        reinstall_output
+            .contains("rr.local/vulnlib@v1.0.0 is affected by advisory RRSEC-2026-0001"),
+        "got:\n{}",
+        reinstall_output
+    );
+
+    let advisory_path = registry_dir
+        .join("advisories")
+        .join("rr.local")
+        .join("vulnlib.toml");
+    let stored = fs::read_to_string(&advisory_path).expect("failed to read advisory file");
+    fs::write(
+        &advisory_path,
+        stored.replace("unchecked index", "harmless"),
+    )
+    .expect("failed to tamper advisory file");
+    let tampered = audit();
+    assert!(!tampered.status.success());
+    assert!(
+        stderr_of(&tampered)
+            .contains("registry signature mismatch for 'rr.local/vulnlib RRSEC-2026-0001'"),
+        "got:\n{}",
+        stderr_of(&tampered)
+    );
+}
+
+#[test]
+pub(crate) fn registry_audit_verify_detects_tampered_and_truncated_logs() {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let sandbox_root = root
+        .join("target")
+        .join("tests")
+        .join("package_manager_cli");
+    fs::create_dir_all(&sandbox_root).expect("failed to create sandbox root");
+    let registry_dir = unique_dir(&sandbox_root, "audit_chain_registry");
+    fs::create_dir_all(&registry_dir).expect("failed to create registry dir");
+    let audit_path = registry_dir.join("audit.log");
+    fs::write(&audit_path, "100\tlegacy\tbefore chaining\n").expect("failed to seed audit log");
+    publish_licensed_lib(&sandbox_root, "chainlib", "MIT", &registry_dir);
+
+    let secret_hex = "4f".repeat(32);
+    let public_hex = ed25519_public_hex(&secret_hex);
+    let registry_cmd = |args: &[&str]| {
+        Command::new(rr_bin())
+            .arg("registry")
+            .args(args)
+            .arg("--registry")
+            .arg(&registry_dir)
+            .env("RR_REGISTRY_SIGNING_ED25519_SECRET", &secret_hex)
+            .env("RR_REGISTRY_SIGNING_IDENTITY", "release-bot")
+            .env("RR_REGISTRY_TRUST_ED25519_KEYS", &public_hex)
+            .output()
+            .expect("failed to run rr registry")
+    };
+    let deprecate = registry_cmd(&["deprecate", "rr.local/chainlib", "use newlib"]);
+    assert!(deprecate.status.success(), "{}", stderr_of(&deprecate));
+
+    let verify = registry_cmd(&["audit", "verify"]);
+    assert!(verify.status.success(), "{}", stderr_of(&verify));
+    let stdout = String::from_utf8_lossy(&verify.stdout);
+    assert!(
+        stdout.contains("1 legacy entrie(s) predate the hash chain")
+            && stdout.contains("checkpoint signed with ed25519 by release-bot"),
+        "got:\n{}",
+        stdout
+    );
+    let listing = registry_cmd(&["audit"]);
+    assert!(listing.status.success(), "{}", stderr_of(&listing));
+    let listing = String::from_utf8_lossy(&listing.stdout);
+    assert!(
+        listing.contains("legacy\tbefore chaining") && !listing.contains("hash="),
+        "got:\n{}",
+        listing
+    );
+
+    let original = fs::read_to_string(&audit_path).expect("failed to read audit log");
+    let lines = original.lines().collect::<Vec<_>>();
+    assert!(lines.len() >= 3, "got:\n{}", original);
+    let edited = original.replacen("module=rr.local/chainlib", "module=rr.local/otherlib", 1);
+    assert_ne!(edited, original);
+    fs::write(&audit_path, &edited).expect("failed to edit audit log");
+    let broken = registry_cmd(&["audit", "verify"]);
+    assert!(!broken.status.success());
+    assert!(
+        stderr_of(&broken).contains("Registry audit log is broken at line 2"),
+        "got:\n{}",
+        stderr_of(&broken)
+    );
+
+    let mut swapped = lines.clone();
+    swapped.swap(1, 2);
+    fs::write(&audit_path, format!("{}\n", swapped.join("\n"))).expect("failed to reorder");
+    let reordered = registry_cmd(&["audit", "verify"]);
+    assert!(!reordered.status.success());
+    assert!(
+        stderr_of(&reordered).contains("broken at line 2"),
+        "got:\n{}",
+        stderr_of(&reordered)
+    );
+
+    let truncated = format!("{}\n", lines[..lines.len() - 1].join("\n"));
+    fs::write(&audit_path, truncated).expect("failed to truncate audit log");
+    let short = registry_cmd(&["audit", "verify"]);
+    assert!(!short.status.success());
+    assert!(
+        stderr_of(&short).contains(&format!(
+            "broken at line {}: log truncated: checkpoint records {} chained entries but the log has {}",
+            lines.len(),
+            lines.len() - 1,
+            lines.len() - 2
+        )),
+        "got:\n{}",
+        stderr_of(&short)
+    );
+    let append = registry_cmd(&["undeprecate", "rr.local/chainlib"]);
+    assert!(!append.status.success());
+    assert!(
+        stderr_of(&append).contains("does not match its checkpoint"),
+        "got:\n{}",
+        stderr_of(&append)
+    );
+
+    assert!(
+        stderr_of(&append).contains("RR registry audit reseal"),
+        "got:\n{}",
+        stderr_of(&append)
+    );
+
+    fs::write(&audit_path, &original).expect("failed to restore audit log");
+    let restored = registry_cmd(&["audit", "verify"]);
+    assert!(restored.status.success(), "{}", stderr_of(&restored));
+
+    // A reseal needs the signing key and an intact chain.
+    let truncated = format!("{}\n", lines[..lines.len() - 1].join("\n"));
+    fs::write(&audit_path, &truncated).expect("failed to truncate audit log");
+    let keyless = Command::new(rr_bin())
+        .args(["registry", "audit", "reseal", "--registry"])
+        .arg(&registry_dir)
+        .env_remove("RR_REGISTRY_SIGNING_ED25519_SECRET")
+        .output()
+        .expect("failed to run rr registry audit reseal");
+    assert!(!keyless.status.success());
+    assert!(
+        stderr_of(&keyless).contains("needs the registry signing key"),
+        "got:\n{}",
+        stderr_of(&keyless)
+    );
+    fs::write(&audit_path, &edited).expect("failed to edit audit log");
+    let rewritten = registry_cmd(&["audit", "reseal"]);
+    assert!(!rewritten.status.success());
+    assert!(
+        stderr_of(&rewritten).contains("a reseal only accepts an intact chain"),
+        "got:\n{}",
+        stderr_of(&rewritten)
+    );
+    fs::write(&audit_path, &truncated).expect("failed to truncate audit log");
+    let reseal = registry_cmd(&["audit", "reseal"]);
+    assert!(reseal.status.success(), "{}", stderr_of(&reseal));
+    let resealed = registry_cmd(&["audit", "verify"]);
+    assert!(resealed.status.success(), "{}", stderr_of(&resealed));
+    let append = registry_cmd(&["undeprecate", "rr.local/chainlib"]);
+    assert!(append.status.success(), "{}", stderr_of(&append));
+    let listing = registry_cmd(&["audit", "--action", "audit-reseal"]);
+    assert!(
+        String::from_utf8_lossy(&listing.stdout).contains("by=release-bot"),
+        "got:\n{}",
+        String::from_utf8_lossy(&listing.stdout)
+    );
+
+    // Once the policy asks for signatures, an unsigned checkpoint fails.
+    let policy_file = unique_dir(&sandbox_root, "audit_chain_policy").with_extension("toml");
+    fs::write(
+        &policy_file,
+        format!("version = 1\nrequire_signed = true\ntrusted_ed25519 = \"{public_hex}\"\n"),
+    )
+    .expect("failed to write policy file");
+    let apply = registry_cmd(&["policy", "apply", policy_file.to_str().expect("utf-8 path")]);
+    assert!(apply.status.success(), "{}", stderr_of(&apply));
+    let checkpoint_path = registry_dir.join("audit.checkpoint");
+    let checkpoint = fs::read_to_string(&checkpoint_path).expect("failed to read checkpoint");
+    let unsigned = checkpoint
+        .lines()
+        .filter(|line| !line.starts_with("sig") && !line.starts_with("signer"))
+        .map(|line| format!("{line}\n"))
+        .collect::<String>();
+    fs::write(&checkpoint_path, unsigned).expect("failed to strip checkpoint signature");
+    let unsigned = registry_cmd(&["audit", "verify"]);
+    assert!(!unsigned.status.success());
+    assert!(
+        stderr_of(&unsigned).contains("audit.checkpoint")
+            && stderr_of(&unsigned).contains("is not signed"),
+        "got:\n{}",
+        stderr_of(&unsigned)
+    );
+    let reseal = registry_cmd(&["audit", "reseal"]);
+    assert!(reseal.status.success(), "{}", stderr_of(&reseal));
+    let resealed = registry_cmd(&["audit", "verify"]);
+    assert!(resealed.status.success(), "{}", stderr_of(&resealed));
+}
+
+#[test]
+pub(crate) fn owner_rules_gate_approval_on_distinct_owner_threshold() {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let sandbox_root = root
+        .join("target")
+        .join("tests")
+        .join("package_manager_cli");
+    fs::create_dir_all(&sandbox_root).expect("failed to create sandbox root");
+    let registry_dir = unique_dir(&sandbox_root, "owners_registry");
+    fs::create_dir_all(&registry_dir).expect("failed to create registry dir");
+    let policy_file = unique_dir(&sandbox_root, "owners_policy").with_extension("toml");
+    let alice_secret = "a1".repeat(32);
+    let bob_secret = "b0".repeat(32);
+    fs::write(
+        &policy_file,
+        format!(
+            "version = 1\nrequire_approval = true\n\n[[owners]]\nmodule = \"rr.local/*\"\nowners = [\"alice\", \"bob\", \"carol\"]\nrequired_approvals = 2\nkey.alice = \"{}\"\nkey.bob = \"{}\"\n\n[[owners]]\nmodule = \"rr.local/solo\"\nowners = \"alice\"\nkey.alice = \"{}\"\n",
+            ed25519_public_hex(&alice_secret),
+            ed25519_public_hex(&bob_secret),
+            ed25519_public_hex(&alice_secret),
+        ),
+    )
+    .expect("failed to write policy file");
+
+    let registry_signed = |identity: &str, secret: &str, args: &[&str]| {
+        Command::new(rr_bin())
+            .arg("registry")
+            .args(args)
+            .arg("--registry")
+            .arg(&registry_dir)
+            .env("RR_REGISTRY_SIGNING_IDENTITY", identity)
+            .env("RR_REGISTRY_SIGNING_ED25519_SECRET", secret)
+            .output()
+            .expect("failed to run rr registry")
+    };
+    let registry_as = |identity: &str, args: &[&str]| {
+        let secret = match identity {
+            "alice" => alice_secret.as_str(),
+            "bob" => bob_secret.as_str(),
+            _ => "",
+        };
+        registry_signed(identity, secret, args)
+    };
+    let apply = Command::new(rr_bin())
+        .args(["registry", "policy", "apply"])
+        .arg(&policy_file)
+        .arg("--registry")
+        .arg(&registry_dir)
+        .output()
+        .expect("failed to run rr registry policy apply");
+    assert!(apply.status.success(), "{}", stderr_of(&apply));
+    let lint = registry_as("alice", &["policy", "lint"]);
+    assert!(lint.status.success(), "{}", stderr_of(&lint));
+    assert!(String::from_utf8_lossy(&lint.stdout).contains("owner_rules=2"));
+
+    publish_licensed_lib(&sandbox_root, "teamlib", "MIT", &registry_dir);
+    let queue = registry_as("alice", &["queue"]);
+    assert!(
+        String::from_utf8_lossy(&queue.stdout).contains(
+            "rr.local/teamlib v1.0.0 yanked=false signed=true signer=release-bot approvals=0/2"
+        ),
+        "got:\n{}",
+        String::from_utf8_lossy(&queue.stdout)
+    );
+
+    let outsider = registry_as("dave", &["approve", "rr.local/teamlib", "v1.0.0"]);
+    assert!(!outsider.status.success());
+    assert!(
+        stderr_of(&outsider).contains("'dave' is not an owner of 'rr.local/teamlib'"),
+        "got:\n{}",
+        stderr_of(&outsider)
+    );
+    let first = registry_as("alice", &["approve", "rr.local/teamlib", "v1.0.0"]);
+    assert!(first.status.success(), "{}", stderr_of(&first));
+    assert!(
+        String::from_utf8_lossy(&first.stdout).contains("(1/2 owner approvals; still pending)"),
+        "got:\n{}",
+        String::from_utf8_lossy(&first.stdout)
+    );
+    let repeat = registry_as("alice", &["approve", "rr.local/teamlib", "v1.0.0"]);
+    assert!(!repeat.status.success());
+    assert!(stderr_of(&repeat).contains("'alice' has already approved"));
+    let promote = registry_as("alice", &["promote", "rr.local/teamlib", "v1.0.0"]);
+    assert!(!promote.status.success());
+    assert!(stderr_of(&promote).contains("with 1/2 owner approvals"));
+
+    let queue = registry_as("alice", &["queue"]);
+    assert!(
+        String::from_utf8_lossy(&queue.stdout).contains("approvals=1/2 approved_by=alice"),
+        "got:\n{}",
+        String::from_utf8_lossy(&queue.stdout)
+    );
+    let app_dir = new_app(&sandbox_root, "owners_app");
+    let pkg_home = unique_dir(&sandbox_root, "owners_pkg_home");
+    fs::create_dir_all(&pkg_home).expect("failed to create pkg home");
+    let pending = registry_install(
+        &app_dir,
+        &pkg_home,
+        &registry_dir,
+        "rr.local/teamlib@v1.0.0",
+    );
+    assert!(!pending.status.success());
+    assert!(stderr_of(&pending).contains("is pending approval"));
+
+    // Bob's identity with Alice's key is not Bob's approval.
+    let impostor = registry_signed(
+        "bob",
+        &alice_secret,
+        &["approve", "rr.local/teamlib", "v1.0.0"],
+    );
+    assert!(!impostor.status.success());
+    assert!(
+        stderr_of(&impostor).contains("not the owner's key"),
+        "got:\n{}",
+        stderr_of(&impostor)
+    );
+    // Carol has no ed25519 key, so her approval needs the registry HMAC key.
+    let unsigned = registry_as("carol", &["approve", "rr.local/teamlib", "v1.0.0"]);
+    assert!(!unsigned.status.success());
+    assert!(
+        stderr_of(&unsigned).contains("must be signed"),
+        "got:\n{}",
+        stderr_of(&unsigned)
+    );
+
+    // Approvals edited into the index without signatures do not count.
+    let index_path = registry_dir
+        .join("index")
+        .join("rr.local")
+        .join("teamlib.toml");
+    let index = fs::read_to_string(&index_path).expect("failed to read index");
+    assert!(index.contains("approval_sig = \"ed25519:"), "{index}");
+    let unverified = format!(
+        "{}approved_by = \"carol\"\napproval_sig = \"hmac-sha256:00\"\n",
+        index
+    );
+    fs::write(&index_path, unverified).expect("failed to forge index");
+    let queue = registry_as("alice", &["queue"]);
+    assert!(
+        String::from_utf8_lossy(&queue.stdout).contains("approvals=1/2 approved_by=alice,carol"),
+        "got:\n{}",
+        String::from_utf8_lossy(&queue.stdout)
+    );
+    let forged = format!(
+        "{}approved_by = \"carol\"\napproval_sig = \"hmac-sha256:00\"\n",
+        index.replace("approved = false", "approved = true")
+    );
+    fs::write(&index_path, forged).expect("failed to forge index");
+    let forged_install = registry_install(
+        &app_dir,
+        &pkg_home,
+        &registry_dir,
+        "rr.local/teamlib@v1.0.0",
+    );
+    assert!(!forged_install.status.success());
+    assert!(
+        stderr_of(&forged_install).contains("has 1/2 verified owner approvals"),
+        "got:\n{}",
+        stderr_of(&forged_install)
+    );
+    fs::write(&index_path, index).expect("failed to restore index");
+
+    let second = registry_as("bob", &["approve", "rr.local/teamlib", "v1.0.0"]);
+    assert!(second.status.success(), "{}", stderr_of(&second));
+    assert!(
+        String::from_utf8_lossy(&second.stdout)
+            .contains("Approved rr.local/teamlib v1.0.0 (2/2 owner approvals)"),
+        "got:\n{}",
+        String::from_utf8_lossy(&second.stdout)
+    );
+    let info = registry_as("bob", &["info", "rr.local/teamlib"]);
+    assert!(String::from_utf8_lossy(&info.stdout).contains("approved_by=alice,bob"));
+    let audit = registry_as("bob", &["audit", "--action", "registry-index"]);
+    let audit = String::from_utf8_lossy(&audit.stdout);
+    assert!(
+        audit.contains("approve rr.local/teamlib v1.0.0 by alice")
+            && audit.contains("approve rr.local/teamlib v1.0.0 by bob"),
+        "got:\n{}",
+        audit
+    );
+    let installed = registry_install(
+        &app_dir,
+        &pkg_home,
+        &registry_dir,
+        "rr.local/teamlib@v1.0.0",
+    );
+    assert!(installed.status.success(), "{}", stderr_of(&installed));
+
+    publish_licensed_lib(&sandbox_root, "solo", "MIT", &registry_dir);
+    let solo = registry_as("bob", &["approve", "rr.local/solo", "v1.0.0"]);
+    assert!(stderr_of(&solo).contains("'bob' is not an owner of 'rr.local/solo'"));
+    let solo = registry_as("alice", &["approve", "rr.local/solo", "v1.0.0"]);
+    assert!(
+        String::from_utf8_lossy(&solo.stdout).contains("(1/1 owner approvals)"),
+        "got:\n{}",
+        String::from_utf8_lossy(&solo.stdout)
+    );
+}
+
+#[test]
+pub(crate) fn shared_hmac_key_cannot_approve_as_several_owners() {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let sandbox_root = root
+        .join("target")
+        .join("tests")
+        .join("package_manager_cli");
+    fs::create_dir_all(&sandbox_root).expect("failed to create sandbox root");
+    let registry_dir = unique_dir(&sandbox_root, "hmac_owners_registry");
+    fs::create_dir_all(&registry_dir).expect("failed to create registry dir");
+    let policy_file = unique_dir(&sandbox_root, "hmac_owners_policy").with_extension("toml");
+    let apply_policy = |required: usize| {
+        fs::write(
+            &policy_file,
+            format!(
+                "version = 1\nrequire_approval = true\n\n[[owners]]\nmodule = \"rr.local/*\"\nowners = [\"carol\", \"dave\"]\nrequired_approvals = {required}\n"
+            ),
+        )
+        .expect("failed to write policy file");
+        Command::new(rr_bin())
+            .args(["registry", "policy", "apply"])
+            .arg(&policy_file)
+            .arg("--registry")
+            .arg(&registry_dir)
+            .output()
+            .expect("failed to run rr registry policy apply")
+    };
+
+    let none = apply_policy(0);
+    assert!(!none.status.success());
+    assert!(
+        stderr_of(&none).contains("requires 0 approvals"),
+        "got:\n{}",
+        stderr_of(&none)
+    );
+    let too_many = apply_policy(3);
+    assert!(!too_many.status.success());
+    assert!(
+        stderr_of(&too_many).contains("requires 3 approvals but lists 2 owner(s)"),
+        "got:\n{}",
+        stderr_of(&too_many)
+    );
+    let apply = apply_policy(2);
+    assert!(apply.status.success(), "{}", stderr_of(&apply));
+
+    publish_licensed_lib(&sandbox_root, "hmacteam", "MIT", &registry_dir);
+    // One HMAC secret signing under two identities is one person, not two
+    // owners.
+    for identity in ["carol", "dave"] {
+        let approve = Command::new(rr_bin())
+            .args(["registry", "approve", "rr.local/hmacteam", "v1.0.0"])
+            .arg("--registry")
+            .arg(&registry_dir)
+            .env("RR_REGISTRY_SIGNING_IDENTITY", identity)
+            .env("RR_REGISTRY_SIGNING_KEY", "license-secret")
+            .env("RR_REGISTRY_TRUST_KEY", "license-secret")
+            .output()
+            .expect("failed to run rr registry approve");
+        assert!(!approve.status.success());
+        assert!(
+            stderr_of(&approve).contains(&format!(
+                "by '{identity}' cannot count toward 2 owner approvals without the owner's own key"
+            )),
+            "got:\n{}",
+            stderr_of(&approve)
+        );
+    }
+    let info = Command::new(rr_bin())
+        .args(["registry", "info", "rr.local/hmacteam"])
+        .arg("--registry")
+        .arg(&registry_dir)
+        .output()
+        .expect("failed to run rr registry info");
+    assert!(
+        String::from_utf8_lossy(&info.stdout).contains("approved=false")
+            && String::from_utf8_lossy(&info.stdout).contains("approved_by=-"),
+        "got:\n{}",
+        String::from_utf8_lossy(&info.stdout)
+    );
+}
+
+/// Start `RR registry serve` on a free port and return it with its URL.
+fn spawn_registry_server(registry_dir: &Path) -> (std::process::Child, String) {
+    use std::io::{BufRead, BufReader};
+    use std::process::Stdio;
+
+    let mut server = Command::new(rr_bin())
+        .args(["registry", "serve"])
+        .arg(registry_dir)
+        .args(["--addr", "127.0.0.1:0"])
+        .env("RR_REGISTRY_TRUST_KEY", "license-secret")
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("failed to run rr registry serve");
+    let mut banner = String::new();
+    BufReader::new(server.stdout.take().expect("server stdout"))
+        .read_line(&mut banner)
+        .expect("failed to read server banner");
+    let url = banner
+        .split_whitespace()
+        .find(|word| word.starts_with("http://"))
+        .unwrap_or_else(|| panic!("no URL in banner: {banner}"))
+        .to_string();
+    (server, url)
+}
+
+#[test]
+pub(crate) fn http_registry_serves_search_install_and_signed_publish() {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let sandbox_root = root
+        .join("target")
+        .join("tests")
+        .join("package_manager_cli");
+    fs::create_dir_all(&sandbox_root).expect("failed to create sandbox root");
+    let registry_dir = unique_dir(&sandbox_root, "http_registry");
+    fs::create_dir_all(&registry_dir).expect("failed to create registry dir");
+
+    let (mut server, url) = spawn_registry_server(&registry_dir);
+    let registry_url = PathBuf::from(&url);
+
+    publish_licensed_lib(&sandbox_root, "httplib", "MIT", &registry_url);
+    assert!(
+        registry_dir
+            .join("index")
+            .join("rr.local")
+            .join("httplib.toml")
+            .is_file(),
+        "upload did not reach the served registry"
+    );
+
+    let pkg_home = unique_dir(&sandbox_root, "http_pkg_home");
+    fs::create_dir_all(&pkg_home).expect("failed to create pkg home");
+    let search = Command::new(rr_bin())
+        .args(["search", "httplib", "--registry", &url])
+        .env("RRPKGHOME", &pkg_home)
+        .env("RR_REGISTRY_TRUST_KEY", "license-secret")
+        .output()
+        .expect("failed to run rr search");
+    assert!(search.status.success(), "{}", stderr_of(&search));
+    assert!(
+        String::from_utf8_lossy(&search.stdout).contains("rr.local/httplib"),
+        "got:\n{}",
+        String::from_utf8_lossy(&search.stdout)
+    );
+
+    let app_dir = new_app(&sandbox_root, "http_app");
+    let install = registry_install(
+        &app_dir,
+        &pkg_home,
+        &registry_url,
+        "rr.local/httplib@latest",
+    );
+    assert!(install.status.success(), "{}", stderr_of(&install));
+    assert!(
+        fs::read_to_string(app_dir.join("rr.lock"))
+            .expect("failed to read rr.lock")
+            .contains("rr.local/httplib")
+    );
+
+    let unsigned_dir = unique_dir(&sandbox_root, "http_unsigned_pkg");
+    let status = Command::new(rr_bin())
+        .args(["new", "--lib", "rr.local/unsigned"])
+        .arg(&unsigned_dir)
+        .status()
+        .expect("failed to run rr new --lib");
+    assert!(status.success(), "rr new --lib failed");
+    let unsigned = Command::new(rr_bin())
+        .current_dir(&unsigned_dir)
+        .args(["publish", "v1.0.0", "--registry", &url])
+        .output()
+        .expect("failed to run rr publish");
+    assert!(!unsigned.status.success());
+    assert!(
+        stderr_of(&unsigned).contains("requires a signing key"),
+        "got:\n{}",
+        stderr_of(&unsigned)
+    );
+
+    let forged = Command::new(rr_bin())
+        .current_dir(&unsigned_dir)
+        .args(["publish", "v1.0.0", "--registry", &url])
+        .env("RR_REGISTRY_SIGNING_KEY", "wrong-secret")
+        .output()
+        .expect("failed to run rr publish");
+    let _ = server.kill();
+    let _ = server.wait();
+    assert!(!forged.status.success());
+    assert!(
+        stderr_of(&forged).contains("rejected rr.local/unsigned v1.0.0 (HTTP 403)"),
+        "got:\n{}",
+        stderr_of(&forged)
+    );
+}
+
+#[test]
+pub(crate) fn http_registry_answers_while_a_client_stalls_and_caps_query_bodies() {
+    use std::io::{Read, Write};
+    use std::net::TcpStream;
+    use std::time::Duration;
+
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let sandbox_root = root
+        .join("target")
+        .join("tests")
+        .join("package_manager_cli");
+    fs::create_dir_all(&sandbox_root).expect("failed to create sandbox root");
+    let registry_dir = unique_dir(&sandbox_root, "http_registry_stall");
+    fs::create_dir_all(&registry_dir).expect("failed to create registry dir");
+    let (mut server, url) = spawn_registry_server(&registry_dir);
+    let addr = url.trim_start_matches("http://").trim_end_matches('/');
+
+    let exchange = |request: &str| {
+        let mut stream = TcpStream::connect(addr).expect("failed to connect");
+        stream
+            .set_read_timeout(Some(Duration::from_secs(10)))
+            .expect("failed to set timeout");
+        stream
+            .write_all(request.as_bytes())
+            .expect("failed to send request");
+        let mut response = String::new();
+        let _ = stream.read_to_string(&mut response);
+        response
+    };
+
+    // Half a request line, never finished.
+    let mut stalled = TcpStream::connect(addr).expect("failed to connect");
+    stalled.write_all(b"GET /ind").expect("failed to send");
+
+    let index = exchange("GET /index HTTP/1.1\r\nHost: x\r\n\r\n");
+    let oversized = exchange("GET /index HTTP/1.1\r\nHost: x\r\nContent-Length: 1048576\r\n\r\n");
+    drop(stalled);
+    let _ = server.kill();
+    let _ = server.wait();
+    assert!(index.starts_with("HTTP/1.1 200 OK"), "got:\n{index}");
+    assert!(
+        oversized.starts_with("HTTP/1.1 413 Payload Too Large")
+            && oversized.contains("exceeds the 65536 byte limit"),
+        "got:\n{oversized}"
+    );
+}
+
+#[test]
+pub(crate) fn audit_over_http_registry_reads_served_advisories() {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let sandbox_root = root
+        .join("target")
+        .join("tests")
+        .join("package_manager_cli");
+    fs::create_dir_all(&sandbox_root).expect("failed to create sandbox root");
+    let registry_dir = unique_dir(&sandbox_root, "http_advisory_registry");
+    fs::create_dir_all(&registry_dir).expect("failed to create registry dir");
+    publish_licensed_lib(&sandbox_root, "httpvuln", "MIT", &registry_dir);
+    let (mut server, url) = spawn_registry_server(&registry_dir);
+    let registry_url = PathBuf::from(&url);
+
+    let app_dir = new_app(&sandbox_root, "http_advisory_app");
+    let pkg_home = unique_dir(&sandbox_root, "http_advisory_pkg_home");
+    fs::create_dir_all(&pkg_home).expect("failed to create pkg home");
+    let install = registry_install(
+        &app_dir,
+        &pkg_home,
+        &registry_url,
+        "rr.local/httpvuln@v1.0.0",
+    );
+    assert!(install.status.success(), "{}", stderr_of(&install));
+
+    let add = Command::new(rr_bin())
+        .args([
+            "registry",
+            "advisory",
+            "add",
+            "rr.local/httpvuln",
+            ">=v1.0.0",
+            "RRSEC-2026-0042",
+            "served",
+            "advisory",
+            "--registry",
+        ])
+        .arg(&registry_dir)
+        .env("RR_REGISTRY_SIGNING_KEY", "license-secret")
+        .env("RR_REGISTRY_SIGNING_IDENTITY", "release-bot")
+        .output()
+        .expect("failed to run rr registry advisory add");
+    assert!(add.status.success(), "{}", stderr_of(&add));
+
+    let audit = Command::new(rr_bin())
+        .current_dir(&app_dir)
+        .args(["audit", "--registry", &url])
+        .env("RRPKGHOME", &pkg_home)
+        .env("RR_REGISTRY_TRUST_KEY", "license-secret")
+        .output()
+        .expect("failed to run rr audit");
+    let _ = server.kill();
+    let _ = server.wait();
+    assert!(
+        !audit.status.success(),
+        "audit should fail on the served advisory"
+    );
+    let stdout = String::from_utf8_lossy(&audit.stdout);
+    assert!(
+        stdout.contains("RRSEC-2026-0042 rr.local/httpvuln@v1.0.0 range=>=v1.0.0: served advisory"),
+        "got:\n{}\n{}",
+        stdout,
+        stderr_of(&audit)
+    );
+}
+
+/// Serve fixed responses over HTTP until the test process exits, so a test
+/// can play a registry that lies about its paths.
+fn serve_fixed_registry(files: Vec<(&'static str, String)>) -> String {
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+
+    let listener = TcpListener::bind("127.0.0.1:0").expect("failed to bind fake registry");
+    let url = format!("http://{}", listener.local_addr().expect("local addr"));
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let Ok(mut stream) = stream else {
+                continue;
+            };
+            let mut request = Vec::new();
+            let mut buf = [0u8; 1024];
+            while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+                match stream.read(&mut buf) {
+                    Ok(0) | Err(_) => break,
+                    Ok(n) => request.extend_from_slice(&buf[..n]),
+                }
+            }
+            let request = String::from_utf8_lossy(&request);
+            let path = request.split_whitespace().nth(1).unwrap_or("/");
+            let (status, body) = match files.iter().find(|(served, _)| *served == path) {
+                Some((_, body)) => ("200 OK", body.as_str()),
+                None => ("404 Not Found", ""),
+            };
+            let _ = write!(
+                stream,
+                "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+        }
+    });
+    url
+}
+
+#[test]
+pub(crate) fn http_registry_mirror_rejects_paths_outside_the_mirror() {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let sandbox_root = root
+        .join("target")
+        .join("tests")
+        .join("package_manager_cli");
+    fs::create_dir_all(&sandbox_root).expect("failed to create sandbox root");
+    let url = serve_fixed_registry(vec![
+        ("/index", "rr.local/evil\n../../escaped\n".to_string()),
+        (
+            "/index/rr.local/evil.toml",
+            "version = 1\nmodule = \"rr.local/evil\"\n\n[[release]]\nversion = \"v1.0.0\"\narchive = \"../../../escaped.tar.gz\"\nsum = \"0\"\nfiles = 1\nyanked = false\napproved = true\n".to_string(),
+        ),
+        ("/../../escaped.toml", "version = 1\n".to_string()),
+        ("/../../../escaped.tar.gz", "not an archive".to_string()),
+    ]);
+
+    let pkg_home = unique_dir(&sandbox_root, "hostile_pkg_home");
+    fs::create_dir_all(&pkg_home).expect("failed to create pkg home");
+    let search = Command::new(rr_bin())
+        .args(["search", "evil", "--registry", &url])
+        .env("RRPKGHOME", &pkg_home)
+        .output()
+        .expect("failed to run rr search");
+    assert!(!search.status.success());
+    assert!(
+        stderr_of(&search).contains("invalid registry module path '../../escaped'"),
+        "got:\n{}",
+        stderr_of(&search)
+    );
+
+    let app_dir = new_app(&sandbox_root, "hostile_app");
+    let install = registry_install(
+        &app_dir,
+        &pkg_home,
+        &PathBuf::from(&url),
+        "rr.local/evil@v1.0.0",
+    );
+    assert!(!install.status.success());
+    assert!(
+        stderr_of(&install).contains("invalid registry archive '../../../escaped.tar.gz'"),
+        "got:\n{}",
+        stderr_of(&install)
+    );
+
+    let mut escaped = Vec::new();
+    let mut pending = vec![pkg_home.clone()];
+    while let Some(dir) = pending.pop() {
+        for entry in fs::read_dir(&dir).expect("failed to read dir").flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                pending.push(path);
+            } else if path.to_string_lossy().contains("escaped") {
+                escaped.push(path);
+            }
+        }
+    }
+    assert!(escaped.is_empty(), "wrote {:?}", escaped);
+    for parent in pkg_home.ancestors().take(4) {
+        assert!(!parent.join("escaped.toml").exists());
+        assert!(!parent.join("escaped.tar.gz").exists());
+    }
+}
@@ -0,0 +1,240 @@
+mod common;
+
+use common::{normalize, rscript_available, rscript_path, run_rscript, unique_dir};
+use rr::compiler::internal::typeck::{NativeBackend, TypeConfig, TypeMode};
+use rr::compiler::{OptLevel, ParallelBackend, ParallelConfig, ParallelMode, compile_with_configs};
+use rr::error::{RRCode, RRException};
+use std::fs;
+use std::path::PathBuf;
+
+fn compile_parallel_for(name: &str, src: &str, mode: ParallelMode) -> Result<String, RRException> {
+    compile_with_configs(
+        name,
+        src,
+        OptLevel::O2,
+        TypeConfig {
+            mode: TypeMode::Strict,
+            native_backend: NativeBackend::Off,
+        },
+        ParallelConfig {
+            mode,
+            backend: ParallelBackend::R,
+            threads: 2,
+            min_trip: 1,
+        },
+    )
+    .map(|(code, _map)| code)
+}
+
+const PROVEN_LOOP: &str = r#"
+fn scale(xs: vector<float>, k: float) -> vector<float> {
+  let out = xs
+  let acc = 0.0
+  parallel for i in 1..length(xs) reduce(sum: acc) {
+    acc <- acc + xs[i] * k
+    out[i] <- xs[i] * 2.0
+  }
+  print(acc)
+  return out
+}
+print(scale(c(1.0, 2.0, 3.0, 4.0), 0.5))
+"#;
+
+#[test]
+fn proven_parallel_for_keeps_parallel_helper() {
+    let code = compile_parallel_for(
+        "parallel_for_proven.rr",
+        PROVEN_LOOP,
+        ParallelMode::Required,
+    )
+    .expect("independent loop should compile under required mode");
+    assert!(
+        code.contains("rr_parallel_for(\"5:3\""),
+        "proven loop should call the parallel helper:\n{}",
+        code
+    );
+    assert!(!code.contains("rr_parallel_for_serial(\""));
+    assert!(
+        code.contains("c(\"sum\", \"slice\")"),
+        "slot kinds should list the reduction and the slice:\n{}",
+        code
+    );
+}
+
+#[test]
+fn loop_carried_dependence_falls_back_to_serial() {
+    let src = r#"
+fn smooth(xs: vector<float>) -> vector<float> {
+  let out = xs
+  parallel for i in 2..length(xs) {
+    out[i] <- out[i - 1L] + xs[i]
+  }
+  return out
+}
+print(smooth(c(1.0, 2.0, 3.0)))
+"#;
+    let code = compile_parallel_for("parallel_for_dependence.rr", src, ParallelMode::Optional)
+        .expect("optional mode should fall back instead of failing");
+    assert!(code.contains("rr_parallel_for_serial(\"4:3\""), "{}", code);
+
+    let err = compile_parallel_for("parallel_for_dependence.rr", src, ParallelMode::Required)
+        .expect_err("required mode should reject the dependent loop");
+    assert!(matches!(err.code, RRCode::E1030));
+    assert!(
+        err.message.contains("cannot run in parallel"),
+        "unexpected message: {}",
+        err.message
+    );
+}
+
+#[test]
+fn carried_scalar_without_reduce_falls_back_to_serial() {
+    let src = r#"
+fn total(xs: vector<float>) -> float {
+  let s = 0.0
+  parallel for i in 1..length(xs) {
+    s <- s + xs[i]
+  }
+  return s
+}
+print(total(c(1.0, 2.0)))
+"#;
+    let code = compile_parallel_for("parallel_for_carried.rr", src, ParallelMode::Optional)
+        .expect("optional mode should fall back instead of failing");
+    assert!(code.contains("rr_parallel_for_serial(\""), "{}", code);
+
+    let err = compile_parallel_for("parallel_for_carried.rr", src, ParallelMode::Required)
+        .expect_err("required mode should reject the carried scalar");
+    assert!(matches!(err.code, RRCode::E1030));
+    assert!(
+        err.message.contains("`s` carries a value"),
+        "{}",
+        err.message
+    );
+}
+
+#[test]
+fn non_matching_reduction_update_is_rejected() {
+    let src = r#"
+fn decay(xs: vector<float>) -> float {
+  let acc = 0.0
+  parallel for i in 1..length(xs) reduce(sum: acc) {
+    acc <- acc * 0.5 + xs[i]
+  }
+  return acc
+}
+print(decay(c(1.0, 2.0)))
+"#;
+    let code = compile_parallel_for("parallel_for_bad_sum.rr", src, ParallelMode::Optional)
+        .expect("optional mode should fall back instead of failing");
+    assert!(code.contains("rr_parallel_for_serial(\""), "{}", code);
+
+    let err = compile_parallel_for("parallel_for_bad_sum.rr", src, ParallelMode::Required)
+        .expect_err("required mode should reject the update");
+    assert!(matches!(err.code, RRCode::E1032));
+    assert!(err.message.contains("`sum`"), "{}", err.message);
+}
+
+#[test]
+fn return_inside_parallel_for_is_an_error() {
+    let src = r#"
+fn first_big(xs: vector<float>) -> float {
+  parallel for i in 1..length(xs) {
+    if (xs[i] > 1.0) {
+      return xs[i]
+    }
+  }
+  return 0.0
+}
+"#;
+    let err = compile_parallel_for("parallel_for_return.rr", src, ParallelMode::Optional)
+        .expect_err("return cannot leave a parallel loop");
+    assert!(matches!(err.code, RRCode::E1030));
+}
+
+#[test]
+fn unknown_reduction_operator_is_a_parse_error() {
+    let src = r#"
+let acc = 0.0
+parallel for i in 1..3 reduce(mean: acc) {
+  acc <- acc + 1.0
+}
+"#;
+    let err = compile_parallel_for("parallel_for_bad_op.rr", src, ParallelMode::Optional)
+        .expect_err("unknown operator should not parse");
+    assert!(matches!(err.code, RRCode::E0001));
+    assert!(
+        std::iter::once(&err)
+            .chain(err.related.iter())
+            .any(|e| e.message.contains("Unknown reduction operator 'mean'")),
+        "{:?}",
+        err
+    );
+}
+
+#[test]
+fn aliased_record_fields_keep_their_labels() {
+    let src = r#"
+fn g(r) {
+  return r
+}
+fn f(xs: vector<float>) {
+  let out = xs
+  let r = g({out: out, a: 1.0})
+  return r
+}
+fn h() {
+  let best = -1.0
+  return g({best: best, a: 1.0})
+}
+print(f(c(1.0, 2.0)))
+print(h())
+"#;
+    let code = compile_parallel_for("parallel_for_labels.rr", src, ParallelMode::Off)
+        .expect("compile should succeed");
+    assert!(
+        !code.contains(") = "),
+        "argument labels must not be replaced by values:\n{}",
+        code
+    );
+}
+
+#[test]
+fn parallel_for_matches_serial_output() {
+    let Some(rscript) = rscript_path() else {
+        return;
+    };
+    if !rscript_available(&rscript) {
+        return;
+    }
+
+    let code = compile_parallel_for(
+        "parallel_for_runtime.rr",
+        PROVEN_LOOP,
+        ParallelMode::Optional,
+    )
+    .expect("compile should succeed");
+    let ref_src = r#"
+xs <- c(1.0, 2.0, 3.0, 4.0)
+print(sum(xs * 0.5))
+print(xs * 2.0)
+"#;
+
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("target")
+        .join("tests")
+        .join("parallel_for");
+    fs::create_dir_all(&root).expect("create test root");
+    let dir = unique_dir(&root, "runtime");
+    fs::create_dir_all(&dir).expect("create test dir");
+    let compiled_path = dir.join("compiled.R");
+    let ref_path = dir.join("ref.R");
+    fs::write(&compiled_path, code).expect("write compiled");
+    fs::write(&ref_path, ref_src).expect("write reference");
+
+    let expected = run_rscript(&rscript, &ref_path);
+    let actual = run_rscript(&rscript, &compiled_path);
+    assert_eq!(expected.status, 0, "reference failed:\n{}", expected.stderr);
+    assert_eq!(actual.status, 0, "compiled failed:\n{}", actual.stderr);
+    assert_eq!(normalize(&expected.stdout), normalize(&actual.stdout));
+}
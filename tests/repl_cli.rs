@@ -0,0 +1,103 @@
+mod common;
+
+use common::{rscript_available, rscript_path, unique_dir};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+
+fn run_repl(dir: &Path, input: &str, rscript: Option<&str>) -> Output {
+    let mut cmd = Command::new(env!("CARGO_BIN_EXE_RR"));
+    cmd.current_dir(dir)
+        .arg("repl")
+        .env("NO_COLOR", "1")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    if let Some(rscript) = rscript {
+        cmd.env("RRSCRIPT", rscript);
+    }
+    let mut child = cmd.spawn().expect("failed to start RR repl");
+    child
+        .stdin
+        .take()
+        .expect("repl stdin")
+        .write_all(input.as_bytes())
+        .expect("failed to write repl input");
+    child
+        .wait_with_output()
+        .expect("failed to wait for RR repl")
+}
+
+fn sandbox(name: &str) -> PathBuf {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("target")
+        .join("tests")
+        .join("repl_cli");
+    fs::create_dir_all(&root).expect("failed to create sandbox root");
+    let dir = unique_dir(&root, name);
+    fs::create_dir_all(&dir).expect("failed to create sandbox");
+    dir
+}
+
+#[test]
+fn meta_commands_work_on_definitions_without_r() {
+    let dir = sandbox("meta");
+    fs::write(
+        dir.join("lib.rr"),
+        "fn twice(v: vector<float>) -> vector<float> {\n  return v * 2.0\n}\n",
+    )
+    .expect("failed to write lib.rr");
+    let output = run_repl(
+        &dir,
+        "fn sq(x: int) -> int {\n  return x * x\n}\n:load lib.rr\n:type sq(3L) + 1L\n:type twice(c(1.0, 2.0))\n:mir sq\n:emit twice\n:type missing\n:bogus\n",
+        Some("/nonexistent/Rscript"),
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.first(), Some(&"int"), "{stdout}\n{stderr}");
+    assert_eq!(lines.get(1), Some(&"vector<float>"), "{stdout}\n{stderr}");
+    assert!(stdout.contains("fn "), "{stdout}");
+    assert!(stdout.contains("twice <- function(v)"), "{stdout}");
+    assert!(!stdout.contains("Tachyon Pulse"), "{stdout}");
+    assert!(
+        stdout.contains("undefined variable 'missing'"),
+        "strict let should hold in :type\n{stdout}"
+    );
+    assert!(stderr.contains("unknown REPL command ':bogus'"), "{stderr}");
+    assert!(!output.status.success());
+}
+
+#[test]
+fn repl_keeps_bindings_in_a_persistent_r_session() {
+    let rscript = match rscript_path() {
+        Some(p) if rscript_available(&p) => p,
+        _ => {
+            eprintln!("Skipping RR repl evaluation: Rscript unavailable.");
+            return;
+        }
+    };
+    let dir = sandbox("eval");
+    let output = run_repl(
+        &dir,
+        "let xs = c(1.0, 2.0)\nxs * 2.0\nxs = xs + 1.0\nsum(xs)\nfn inc(n: int) -> int {\n  return n + 1L\n}\ninc(41L)\nprint(\"done\")\n",
+        Some(&rscript),
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "{stdout}\n{stderr}");
+    assert!(stdout.contains("[1] 2 4"), "{stdout}");
+    assert!(stdout.contains("it: vector<float>"), "{stdout}");
+    assert!(stdout.contains("[1] 5"), "{stdout}");
+    assert!(stdout.contains("[1] 42"), "{stdout}");
+    assert!(stdout.contains("it: int"), "{stdout}");
+    assert!(stdout.contains("[1] \"done\""), "{stdout}");
+
+    let output = run_repl(&dir, "let x = 1L\nstop(\"bad\")\nx\n", Some(&rscript));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("R error: bad"), "{stderr}");
+    assert!(stdout.contains("[1] 1"), "{stdout}\n{stderr}");
+    assert!(!output.status.success());
+}
@@ -59,3 +59,24 @@ fn call_map_profit_runtime_guards_exist() {
         "vector fallback trip threshold should be configurable in the runtime"
     );
 }
+
+#[test]
+fn compiled_kernels_split_into_one_part_per_core() {
+    // Only `rng = TRUE` callers need the core-independent 64-way split;
+    // kernels that never draw keep one part per worker.
+    for helper in [
+        "rr_parallel_binop_r <- function(",
+        "rr_parallel_unary_r <- function(",
+        "rr_parallel_typed_vec_r <- function(",
+    ] {
+        let start = R_RUNTIME
+            .find(helper)
+            .unwrap_or_else(|| panic!("missing {helper}"));
+        let body = &R_RUNTIME[start..];
+        let body = &body[..body[1..].find("\nrr_").map_or(body.len(), |end| end + 1)];
+        assert!(
+            body.contains("min(cores, ") && !body.contains("rr_parallel_split("),
+            "{helper} should split by core count:\n{body}"
+        );
+    }
+}
@@ -0,0 +1,252 @@
+mod common;
+
+use common::unique_dir;
+use rr::compiler::{OptLevel, compile};
+use rr::error::RRException;
+use std::fs;
+use std::path::PathBuf;
+
+const DISPLAY: &str = r#"
+import r * as base from "base"
+
+export trait Display {
+  fn print(self: Self)
+  fn format(self: Self, digits: int) -> str
+  fn as_data_frame(self: Self) -> any
+}
+"#;
+
+fn compile_src(name: &str, src: &str, opt: OptLevel) -> Result<String, RRException> {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("target")
+        .join("tests")
+        .join("s3_exports");
+    fs::create_dir_all(&root).expect("failed to create sandbox root");
+    let proj_dir = unique_dir(&root, name);
+    fs::create_dir_all(&proj_dir).expect("failed to create project dir");
+    let main_path = proj_dir.join("main.rr");
+    fs::write(&main_path, src).expect("failed to write main.rr");
+    compile(&main_path.to_string_lossy(), src, opt).map(|(code, _map)| code)
+}
+
+fn messages(err: &RRException) -> Vec<String> {
+    std::iter::once(err)
+        .chain(err.related.iter())
+        .map(|e| e.message.to_string())
+        .collect()
+}
+
+fn with_impl(header: &str) -> String {
+    format!(
+        r#"{DISPLAY}
+{header} {{
+  fn print(self: Body) {{
+    print(base.paste("<Body mass", self.mass, ">"))
+  }}
+  fn format(self: Body, digits: int) -> str {{
+    return base.paste("Body", base.round(self.mass, digits))
+  }}
+  fn as_data_frame(self: Body) -> any {{
+    return base.data.frame(mass = self.mass)
+  }}
+}}
+
+export fn make(m: float) -> Body {{
+  return {{mass: m}}
+}}
+
+fn main() {{
+  let b: Body = {{mass: 3.25}}
+  print(b.format(1))
+  print(make(4.0).mass)
+}}
+main()
+"#
+    )
+}
+
+#[test]
+fn s3_impl_emits_registered_generic_class_wrappers() {
+    for opt in [OptLevel::O0, OptLevel::O2] {
+        let code = compile_src(
+            "wrappers",
+            &with_impl("export impl Display for Body as s3"),
+            opt,
+        )
+        .expect("S3 impl should compile");
+        for header in [
+            "print.Body <- function(self, ...)",
+            "format.Body <- function(self, digits, ...)",
+            "as.data.frame.Body <- function(self, ...)",
+        ] {
+            assert!(code.contains(header), "missing `{header}` in:\n{code}");
+        }
+        for generic in ["print", "format", "as.data.frame"] {
+            let registration =
+                format!("base::registerS3method(\"{generic}\", \"Body\", {generic}.Body)");
+            assert!(
+                code.contains(&registration),
+                "missing registration in:\n{code}"
+            );
+        }
+        assert!(
+            code.contains("return(base::invisible(self))"),
+            "print method should return its receiver invisibly:\n{code}"
+        );
+        assert!(
+            code.contains("base::paste(\"<Body mass\""),
+            "print wrapper should keep the impl body:\n{code}"
+        );
+    }
+}
+
+#[test]
+fn records_of_s3_types_carry_their_class() {
+    let code = compile_src(
+        "class_tag",
+        &with_impl("export impl Display for Body as s3"),
+        OptLevel::O0,
+    )
+    .expect("S3 impl should compile");
+    assert!(
+        code.contains("base::structure(list(mass = 3.25), class = \"Body\")"),
+        "annotated let should tag the record:\n{code}"
+    );
+    assert!(
+        code.contains("base::structure(list(mass = 4.0), class = \"Body\")"),
+        "declared return type should tag the record:\n{code}"
+    );
+}
+
+#[test]
+fn records_passed_to_s3_typed_parameters_carry_their_class() {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("target")
+        .join("tests")
+        .join("s3_exports");
+    fs::create_dir_all(&root).expect("failed to create sandbox root");
+    let proj_dir = unique_dir(&root, "arg_tag");
+    fs::create_dir_all(&proj_dir).expect("failed to create project dir");
+    fs::write(
+        proj_dir.join("shapes.rr"),
+        r#"
+export fn weigh(b: Body) -> float {
+  return b.mass
+}
+"#,
+    )
+    .expect("failed to write shapes.rr");
+    let src = with_impl("export impl Display for Body as s3").replace(
+        "fn main() {\n",
+        r#"import "./shapes.rr"
+
+fn describe(b: Body, label: str) {
+  print(label)
+  print(b)
+}
+
+fn main() {
+  describe({mass: 5.5}, "pos")
+  describe(label = "named", b = {mass: 6.5})
+  {mass: 7.5} |> describe("piped")
+  print(weigh({mass: 8.5}))
+  describe(label = "mixed", {mass: 9.5})
+"#,
+    );
+    let main_path = proj_dir.join("main.rr");
+    fs::write(&main_path, &src).expect("failed to write main.rr");
+    let (code, _map) =
+        compile(&main_path.to_string_lossy(), &src, OptLevel::O0).expect("S3 impl should compile");
+    for mass in ["5.5", "6.5", "7.5", "8.5", "9.5"] {
+        assert!(
+            code.contains(&format!(
+                "base::structure(list(mass = {mass}), class = \"Body\")"
+            )),
+            "record argument {mass} should be tagged:\n{code}"
+        );
+    }
+
+    // The second compile loads shapes.rr from its cached artifact.
+    let (cached, _map) =
+        compile(&main_path.to_string_lossy(), &src, OptLevel::O0).expect("S3 impl should compile");
+    assert!(
+        cached.contains("base::structure(list(mass = 8.5), class = \"Body\")"),
+        "cached module signature should still tag the record:\n{cached}"
+    );
+}
+
+#[test]
+fn plain_impls_do_not_create_s3_methods() {
+    let code = compile_src(
+        "plain",
+        &with_impl("export impl Display for Body"),
+        OptLevel::O0,
+    )
+    .expect("plain impl should compile");
+    assert!(!code.contains("print.Body"), "{code}");
+    assert!(!code.contains("registerS3method"), "{code}");
+    assert!(!code.contains("class = \"Body\""), "{code}");
+}
+
+#[test]
+fn s3_impls_must_be_exported() {
+    let err = compile_src(
+        "private",
+        &with_impl("impl Display for Body as s3"),
+        OptLevel::O0,
+    )
+    .expect_err("private S3 impl should be rejected");
+    assert!(
+        err.message
+            .contains("write `export impl Display for Body as s3`"),
+        "{}",
+        err.message
+    );
+}
+
+#[test]
+fn s3_impls_need_a_user_record_type() {
+    let err = compile_src(
+        "builtin",
+        &format!(
+            r#"{DISPLAY}
+export impl Display for float as s3 {{
+  fn print(self: float) {{
+    print(self)
+  }}
+  fn format(self: float, digits: int) -> str {{
+    return base.paste(self)
+  }}
+  fn as_data_frame(self: float) -> any {{
+    return base.data.frame(x = self)
+  }}
+}}
+"#
+        ),
+        OptLevel::O0,
+    )
+    .expect_err("S3 impl for a builtin type should be rejected");
+    assert!(
+        err.message
+            .contains("S3 methods need a user-defined record type"),
+        "{}",
+        err.message
+    );
+}
+
+#[test]
+fn unknown_impl_export_target_is_a_parse_error() {
+    let err = compile_src(
+        "unknown",
+        &with_impl("export impl Display for Body as s4"),
+        OptLevel::O0,
+    )
+    .expect_err("`as s4` should be rejected");
+    let messages = messages(&err);
+    assert!(
+        messages
+            .iter()
+            .any(|m| m.contains("Unknown impl export 's4'; only 'as s3' is supported")),
+        "{messages:?}"
+    );
+}
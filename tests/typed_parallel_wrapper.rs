@@ -456,3 +456,326 @@ fn pick(flag: bool, a: vector<float>, b: vector<float>) -> vector<float> {
         "branchy typed vector functions should stay on the sequential path"
     );
 }
+
+const RNG_KERNEL_SRC: &str = r#"
+import r default from "stats"
+import r { set.seed } from "base"
+
+fn noisy(a: vector<float>) -> vector<float> {
+  return a * 0.5 + stats.runif(8L)
+}
+
+set.seed(20260419L)
+print(noisy(c(1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0)))
+"#;
+
+fn compile_rng_kernel(mode: ParallelMode, threads: usize) -> String {
+    let (code, _map) = compile_with_configs(
+        "typed_parallel_wrapper_rng.rr",
+        RNG_KERNEL_SRC,
+        OptLevel::O2,
+        strict_type_cfg(),
+        ParallelConfig {
+            mode,
+            backend: ParallelBackend::R,
+            threads,
+            min_trip: 1,
+        },
+    )
+    .expect("compile should succeed");
+    code
+}
+
+#[test]
+fn rng_kernel_does_not_emit_parallel_wrapper() {
+    let code = compile_rng_kernel(ParallelMode::Optional, 4);
+    assert!(
+        !code.contains("# rr-typed-parallel-wrapper"),
+        "kernels that draw random numbers should stay serial:\n{code}"
+    );
+}
+
+/// Draws through the runtime's parallel map directly with `rng = TRUE`, over
+/// a split that only depends on the length, so the part-to-worker assignment
+/// is the only thing that changes between settings.
+fn rng_streams_src(backend: &str, cores: usize) -> String {
+    format!(
+        r#"
+fn main() {{
+  unsafe r {{
+    set.seed(20260419L)
+    parts <- rr_parallel_split(8L)
+    draws <- rr_parallel_lapply("{backend}", parts, function(ix) stats::runif(2L * length(ix)), {cores}L, rng = TRUE)
+    print(unlist(draws))
+    print(length(unique(unlist(draws))) == 16L)
+    print(stats::runif(1L))
+  }}
+  return 0L
+}}
+
+main()
+"#
+    )
+}
+
+#[test]
+fn runtime_parallel_parts_run_on_lecuyer_streams() {
+    let (code, _map) = compile_with_configs(
+        "typed_parallel_wrapper_rng_streams.rr",
+        &rng_streams_src("r", 2),
+        OptLevel::O2,
+        strict_type_cfg(),
+        ParallelConfig::default(),
+    )
+    .expect("compile should succeed");
+    for needle in [
+        "rr_parallel_split <- function(",
+        "rr_parallel_rng_streams <- function(",
+        "rr_parallel_seeded <- function(",
+        "RNGkind(\"L'Ecuyer-CMRG\")",
+        "parallel::nextRNGStream(",
+    ] {
+        assert!(code.contains(needle), "missing {needle}:\n{code}");
+    }
+}
+
+#[test]
+fn runtime_parallel_part_draws_do_not_depend_on_workers() {
+    let Some(rscript) = rscript_path() else {
+        return;
+    };
+    if !rscript_available(&rscript) {
+        return;
+    }
+
+    let tmp = unique_tmp_dir("rng_streams");
+    let mut outputs = Vec::new();
+    let mut settings = vec![("r", 1), ("psock", 2)];
+    if !cfg!(windows) {
+        settings.extend([("r", 2), ("r", 4)]);
+    }
+    for (backend, cores) in settings {
+        let (code, _map) = compile_with_configs(
+            "typed_parallel_wrapper_rng_streams.rr",
+            &rng_streams_src(backend, cores),
+            OptLevel::O2,
+            strict_type_cfg(),
+            ParallelConfig::default(),
+        )
+        .expect("compile should succeed");
+        let path = tmp.join(format!("rng_streams_{backend}_{cores}.R"));
+        fs::write(&path, code).expect("write compiled");
+        let out = Command::new(&rscript)
+            .arg("--vanilla")
+            .arg(&path)
+            .output()
+            .expect("run compiled");
+        assert!(
+            out.status.success(),
+            "compiled failed:\n{}",
+            String::from_utf8_lossy(&out.stderr)
+        );
+        outputs.push(normalize(&String::from_utf8_lossy(&out.stdout)));
+    }
+    assert!(
+        outputs[0].contains("[1] TRUE"),
+        "every part should draw from its own stream:\n{}",
+        outputs[0]
+    );
+    for out in &outputs[1..] {
+        assert_eq!(out, &outputs[0]);
+    }
+}
+
+#[test]
+fn rng_kernel_output_matches_across_parallel_settings() {
+    let Some(rscript) = rscript_path() else {
+        return;
+    };
+    if !rscript_available(&rscript) {
+        return;
+    }
+
+    let tmp = unique_tmp_dir("rng");
+    let mut outputs = Vec::new();
+    for (mode, threads) in [
+        (ParallelMode::Off, 0),
+        (ParallelMode::Optional, 1),
+        (ParallelMode::Optional, 2),
+        (ParallelMode::Optional, 4),
+    ] {
+        let path = tmp.join(format!("rng_{mode:?}_{threads}.R"));
+        fs::write(&path, compile_rng_kernel(mode, threads)).expect("write compiled");
+        let out = Command::new(&rscript)
+            .arg("--vanilla")
+            .arg(&path)
+            .output()
+            .expect("run compiled");
+        assert!(
+            out.status.success(),
+            "compiled failed:\n{}",
+            String::from_utf8_lossy(&out.stderr)
+        );
+        outputs.push(normalize(&String::from_utf8_lossy(&out.stdout)));
+    }
+    assert!(!outputs[0].is_empty());
+    for out in &outputs[1..] {
+        assert_eq!(out, &outputs[0]);
+    }
+}
+
+const PLAIN_KERNEL_DRAWS_SRC: &str = r#"
+import r default from "stats"
+import r { set.seed } from "base"
+
+fn fused(a: vector<float>, b: vector<float>) -> vector<float> {
+  return (a + b) * 0.5
+}
+
+set.seed(20260419L)
+print(stats.runif(2L))
+print(fused(c(1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0), c(8.0, 7.0, 6.0, 5.0, 4.0, 3.0, 2.0, 1.0)))
+print(stats.runif(3L))
+"#;
+
+fn compile_plain_kernel_draws(mode: ParallelMode, threads: usize) -> String {
+    let (code, _map) = compile_with_configs(
+        "typed_parallel_wrapper_plain_draws.rr",
+        PLAIN_KERNEL_DRAWS_SRC,
+        OptLevel::O2,
+        strict_type_cfg(),
+        ParallelConfig {
+            mode,
+            backend: ParallelBackend::R,
+            threads,
+            min_trip: 1,
+        },
+    )
+    .expect("compile should succeed");
+    code
+}
+
+#[test]
+fn plain_kernel_takes_the_parallel_path_without_rng_streams() {
+    let code = compile_plain_kernel_draws(ParallelMode::Optional, 4);
+    assert!(
+        code.contains("# rr-typed-parallel-wrapper"),
+        "kernel without RNG calls should get the parallel wrapper:\n{code}"
+    );
+    assert!(
+        !code.contains("rng = TRUE"),
+        "compiled kernels never ask the runtime for RNG streams:\n{code}"
+    );
+}
+
+#[test]
+fn plain_kernel_leaves_later_draws_alone_for_every_thread_count() {
+    let Some(rscript) = rscript_path() else {
+        return;
+    };
+    if !rscript_available(&rscript) {
+        return;
+    }
+
+    let tmp = unique_tmp_dir("plain_draws");
+    let mut settings = vec![(ParallelMode::Off, 0), (ParallelMode::Optional, 1)];
+    if !cfg!(windows) {
+        settings.extend([(ParallelMode::Optional, 2), (ParallelMode::Optional, 4)]);
+    }
+    let mut outputs = Vec::new();
+    for (mode, threads) in settings {
+        let path = tmp.join(format!("plain_draws_{mode:?}_{threads}.R"));
+        fs::write(&path, compile_plain_kernel_draws(mode, threads)).expect("write compiled");
+        let out = Command::new(&rscript)
+            .arg("--vanilla")
+            .arg(&path)
+            .output()
+            .expect("run compiled");
+        assert!(
+            out.status.success(),
+            "compiled failed:\n{}",
+            String::from_utf8_lossy(&out.stderr)
+        );
+        outputs.push(normalize(&String::from_utf8_lossy(&out.stdout)));
+    }
+    assert!(!outputs[0].is_empty());
+    for out in &outputs[1..] {
+        assert_eq!(out, &outputs[0]);
+    }
+}
+
+const PSOCK_KERNEL_SRC: &str = r#"
+fn shift(v: vector<float>) -> vector<float> {
+  return v + 0.25
+}
+
+fn fused(a: vector<float>, b: vector<float>) -> vector<float> {
+  return shift((a + b) * 0.5)
+}
+
+print(fused(c(1.0, 2.0, 3.0, 4.0, 5.0, 6.0), c(6.0, 5.0, 4.0, 3.0, 2.0, 1.0)))
+"#;
+
+fn compile_psock_kernel(mode: ParallelMode, backend: ParallelBackend) -> String {
+    let (code, _map) = compile_with_configs(
+        "typed_parallel_wrapper_psock.rr",
+        PSOCK_KERNEL_SRC,
+        OptLevel::O2,
+        strict_type_cfg(),
+        ParallelConfig {
+            mode,
+            backend,
+            threads: 2,
+            min_trip: 1,
+        },
+    )
+    .expect("compile should succeed");
+    code
+}
+
+#[test]
+fn psock_backend_embeds_socket_cluster_runtime() {
+    let code = compile_psock_kernel(ParallelMode::Required, ParallelBackend::Psock);
+    assert!(code.contains(".rr_env$parallel_backend <- \"psock\";"));
+    assert!(code.contains("# rr-typed-parallel-wrapper"), "{code}");
+    for helper in [
+        "rr_parallel_psock_cluster <- function(",
+        "rr_parallel_psock_export <- function(",
+        "rr_parallel_psock_stop <- function(",
+    ] {
+        assert!(code.contains(helper), "missing {helper}:\n{code}");
+    }
+}
+
+#[test]
+fn psock_backend_output_matches_serial_in_required_mode() {
+    let Some(rscript) = rscript_path() else {
+        return;
+    };
+    if !rscript_available(&rscript) {
+        return;
+    }
+
+    let tmp = unique_tmp_dir("psock");
+    let mut outputs = Vec::new();
+    for (name, mode, backend) in [
+        ("serial", ParallelMode::Off, ParallelBackend::Auto),
+        ("psock", ParallelMode::Required, ParallelBackend::Psock),
+    ] {
+        let path = tmp.join(format!("{name}.R"));
+        fs::write(&path, compile_psock_kernel(mode, backend)).expect("write compiled");
+        let out = Command::new(&rscript)
+            .arg("--vanilla")
+            .arg(&path)
+            .output()
+            .expect("run compiled");
+        assert!(
+            out.status.success(),
+            "{name} failed:\n{}",
+            String::from_utf8_lossy(&out.stderr)
+        );
+        outputs.push(normalize(&String::from_utf8_lossy(&out.stdout)));
+    }
+    assert!(!outputs[0].is_empty());
+    assert_eq!(outputs[1], outputs[0]);
+}
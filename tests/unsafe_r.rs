@@ -1,11 +1,11 @@
 mod common;
 
 use common::{normalize, rscript_available, rscript_path, run_rscript, unique_dir};
-use rr::compiler::{OptLevel, compile};
+use rr::compiler::{OptLevel, compile, compile_for_debug, instrument_for_coverage};
 use std::fs;
 
 #[test]
-fn unsafe_r_block_is_emitted_verbatim_and_marks_function_opaque() {
+fn unsafe_r_block_is_emitted_verbatim() {
     let src = r#"
 fn main() {
   let x = 2L
@@ -23,7 +23,6 @@ main()
 "#;
 
     let (code, _map) = compile("unsafe_r_verbatim.rr", src, OptLevel::O2).expect("compile");
-    assert!(code.contains("# rr-opaque-interop:"), "{code}");
     assert!(code.contains("y <- x + 40L"), "{code}");
     assert!(
         code.contains(r#"f <- function(v) { paste0("{", v, "}") }"#),
@@ -37,6 +36,144 @@ main()
     assert!(code.contains("print(f(y))"), "{code}");
 }
 
+#[test]
+fn unsafe_r_block_marks_function_opaque_only_when_it_may_rebind_rr_locals() {
+    let opaque = |body: &str| {
+        let src = format!(
+            "fn main(n: int) {{\n  let x = n + 1L\n  unsafe r {{\n    {body}\n  }}\n  return x\n}}\n\nprint(main(2L))\n"
+        );
+        let (code, _map) = compile("unsafe_r_opaque.rr", &src, OptLevel::O2).expect("compile");
+        code.contains("# rr-opaque-interop: unsafe R block")
+    };
+
+    assert!(!opaque("msg <- sprintf('x = %d', x); print(msg)"));
+    assert!(!opaque("print(x)"));
+    assert!(opaque("x <- x * 2L"));
+    assert!(opaque("names(x) <- 'a'"));
+    assert!(opaque("n <- 0L"));
+    assert!(opaque("counter <<- x"));
+    assert!(opaque("assign('x', 0L)"));
+    assert!(opaque("eval(parse(text = 'x <- 0L'))"));
+}
+
+#[test]
+fn analyzed_unsafe_r_block_in_loop_keeps_invariant_hoisting() {
+    let src = r#"
+fn main(n: int) {
+  let scale = n * 2L
+  let total = 0L
+  for (i in 1L..n) {
+    unsafe r {
+      msg <- sprintf("step %d", i)
+      print(msg)
+    }
+    total <- total + (scale * scale) + i
+  }
+  return total
+}
+
+print(main(3L))
+"#;
+
+    let (code, _map) = compile("unsafe_r_licm.rr", src, OptLevel::O2).expect("compile");
+    assert!(
+        !code.contains("# rr-opaque-interop: unsafe R block"),
+        "{code}"
+    );
+    let loop_body = &code[code.find("repeat {").expect("loop")..];
+    let update = loop_body
+        .lines()
+        .find(|line| line.trim_start().starts_with("total <- "))
+        .expect("total update");
+    assert!(update.contains("licm_"), "{code}");
+}
+
+#[test]
+fn unanalyzable_unsafe_r_block_in_loop_blocks_invariant_hoisting() {
+    for body in [
+        r#""assign"("scale", 0L)"#,
+        r#"load("state.RData")"#,
+        "rlang::env_bind(rlang::current_env(), scale = 0L)",
+        "refresh_scale()",
+    ] {
+        let src = format!(
+            "fn main(n: int) {{\n  let scale = n * 2L\n  let total = 0L\n  for (i in 1L..n) {{\n    unsafe r {{ {body} }}\n    total <- total + (scale * scale) + i\n  }}\n  return total\n}}\n\nprint(main(3L))\n"
+        );
+        let (code, _map) = compile("unsafe_r_no_licm.rr", &src, OptLevel::O2).expect("compile");
+        assert!(
+            code.contains("# rr-opaque-interop: unsafe R block"),
+            "{body}\n{code}"
+        );
+        assert!(!code.contains("licm_"), "{body}\n{code}");
+    }
+}
+
+#[test]
+fn instrumentation_probes_keep_their_function_opaque() {
+    let src = r#"
+fn main(n: int) {
+  let scale = n * 2L
+  let total = 0L
+  for (i in 1L..n) {
+    total <- total + (scale * scale) + i
+  }
+  return total
+}
+
+print(main(3L))
+"#;
+
+    // Coverage probes must count every statement where it was written, so
+    // nothing may be hoisted past them even when the build is optimized.
+    let coverage = instrument_for_coverage("probe_licm.rr", src).expect("instrument");
+    let (code, _map) = compile("probe_licm.rr", &coverage.source, OptLevel::O2).expect("compile");
+    assert!(
+        code.contains("# rr-opaque-interop: unsafe R block"),
+        "{code}"
+    );
+    assert!(!code.contains("licm_"), "{code}");
+    let loop_body = &code[code.find("repeat {").expect("loop")..];
+    assert!(
+        loop_body
+            .lines()
+            .any(|line| line.trim_start().starts_with("total <- ")),
+        "{code}"
+    );
+
+    // The debug hook reads locals from its caller's frame by their RR names.
+    let dir = unique_dir(&std::env::temp_dir(), "rr_unsafe_r_probe");
+    fs::create_dir_all(&dir).expect("failed to create temp dir");
+    let entry = dir.join("probe_licm.rr");
+    fs::write(&entry, src).expect("failed to write source");
+    let debug = compile_for_debug(&entry.to_string_lossy(), src).expect("debug compile");
+    assert!(
+        debug.r_code.contains("# rr-opaque-interop: unsafe R block"),
+        "{}",
+        debug.r_code
+    );
+    for local in ["scale <- ", "total <- "] {
+        assert!(debug.r_code.contains(local), "{}", debug.r_code);
+    }
+}
+
+#[test]
+fn top_level_unsafe_r_writes_are_read_back_by_later_statements() {
+    let src = r#"
+let x = NULL
+unsafe r {
+  x <- c(1L, 2L)
+}
+print(x)
+"#;
+
+    for level in [OptLevel::O0, OptLevel::O1, OptLevel::O2] {
+        let (code, _map) = compile("unsafe_r_top_level.rr", src, level).expect("compile");
+        assert!(code.contains("x <- NULL"), "{code}");
+        assert!(code.contains("print(x)"), "{code}");
+        assert!(!code.contains("print(NULL)"), "{code}");
+    }
+}
+
 #[test]
 fn unsafe_r_read_block_is_emitted_without_opaque_interop() {
     let src = r#"
@@ -110,6 +247,32 @@ main()
     );
 }
 
+#[test]
+fn unsafe_r_after_returning_branch_keeps_trailing_statements() {
+    let src = r#"
+fn pick(x: int) -> int {
+  if (x > 0L) {
+    return 1L
+  }
+  unsafe r { cat("neg\n") }
+  print("after")
+  return 0L
+}
+
+fn main() {
+  print(pick(-1L))
+}
+
+main()
+"#;
+
+    for level in [OptLevel::O0, OptLevel::O2] {
+        let (code, _map) = compile("unsafe_r_after_return.rr", src, level).expect("compile");
+        assert!(code.contains("print(\"after\")"), "{code}");
+        assert!(code.contains("return(0L)"), "{code}");
+    }
+}
+
 #[test]
 fn unsafe_r_is_statement_only_not_expression() {
     let src = r#"